@@ -46,9 +46,19 @@ CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 #if defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__)
 #import <Metal/Metal.h>
 #endif
-#if defined(LIBRA_RUNTIME_VULKAN)
+#if defined(LIBRA_RUNTIME_VULKAN) || defined(LIBRA_RUNTIME_WGPU_VULKAN)
 #include <vulkan/vulkan.h>
 #endif
+#if defined(LIBRA_RUNTIME_WGPU)
+/// A handle to a wgpu device, created with `libra_wgpu_device_create_vk`.
+typedef const struct _wgpu_device *libra_wgpu_device_t;
+/// A handle to a wgpu queue, created with `libra_wgpu_device_create_vk`.
+typedef const struct _wgpu_queue *libra_wgpu_queue_t;
+/// A handle to a wgpu texture, created with `libra_wgpu_texture_create_vk`.
+typedef const struct _wgpu_texture *libra_wgpu_texture_t;
+/// A handle to a wgpu command encoder, created with `libra_wgpu_command_encoder_create`.
+typedef struct _wgpu_command_encoder *libra_wgpu_command_encoder_t;
+#endif
 
 
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
@@ -88,7 +98,7 @@ enum LIBRA_ERRNO
   LIBRA_ERRNO_INVALID_PARAMETER = 1,
   /// Error code for an invalid (non-UTF8) string.
   LIBRA_ERRNO_INVALID_STRING = 2,
-  /// Error code for a preset parser error.
+  /// Error code for a preset parser or preset pack error.
   LIBRA_ERRNO_PRESET_ERROR = 3,
   /// Error code for a preprocessor error.
   LIBRA_ERRNO_PREPROCESS_ERROR = 4,
@@ -98,11 +108,115 @@ enum LIBRA_ERRNO
   LIBRA_ERRNO_REFLECT_ERROR = 6,
   /// Error code for a runtime error.
   LIBRA_ERRNO_RUNTIME_ERROR = 7,
+  /// Error code for a cancelled operation.
+  LIBRA_ERRNO_CANCELLED = 8,
 };
 #ifndef __cplusplus
 typedef int32_t LIBRA_ERRNO;
 #endif // __cplusplus
 
+/// Sub-codes that refine the error code of an error.
+enum LIBRA_ERROR_SUBCODE
+#ifdef __cplusplus
+  : int32_t
+#endif // __cplusplus
+ {
+  /// No further information is available.
+  LIBRA_ERROR_SUBCODE_NONE = 0,
+  /// A file could not be found.
+  LIBRA_ERROR_SUBCODE_FILE_NOT_FOUND = 1,
+  /// A file could not be read.
+  LIBRA_ERROR_SUBCODE_IO_ERROR = 2,
+  /// A file was not valid UTF-8 or Latin-1 text.
+  LIBRA_ERROR_SUBCODE_INVALID_ENCODING = 3,
+  /// The shader preset could not be parsed.
+  LIBRA_ERROR_SUBCODE_PRESET_SYNTAX_ERROR = 4,
+  /// Shader preset references were nested too deeply.
+  LIBRA_ERROR_SUBCODE_PRESET_REFERENCE_DEPTH = 5,
+  /// The shader source was missing its `#version` header.
+  LIBRA_ERROR_SUBCODE_MISSING_VERSION_HEADER = 6,
+  /// A `#pragma` or `#include` directive in the shader source was invalid.
+  LIBRA_ERROR_SUBCODE_INVALID_DIRECTIVE = 7,
+  /// The image format requested by the shader is unknown or unsupported.
+  LIBRA_ERROR_SUBCODE_UNSUPPORTED_FORMAT = 8,
+  /// The shader source failed to compile.
+  LIBRA_ERROR_SUBCODE_SHADER_COMPILE_ERROR = 9,
+  /// The shader could not be transpiled to the target shader language.
+  LIBRA_ERROR_SUBCODE_SHADER_TRANSPILE_ERROR = 10,
+  /// The bindings or uniforms declared by the shader were invalid.
+  LIBRA_ERROR_SUBCODE_SHADER_SEMANTIC_ERROR = 11,
+  /// The uniform layouts of the vertex and fragment shader did not match.
+  LIBRA_ERROR_SUBCODE_SHADER_LAYOUT_MISMATCH = 12,
+  /// A pass tried to read the output of a pass that is not rendered before it.
+  LIBRA_ERROR_SUBCODE_NON_CAUSAL_FILTER_CHAIN = 13,
+  /// A lookup texture could not be loaded.
+  LIBRA_ERROR_SUBCODE_TEXTURE_LOAD_ERROR = 14,
+  /// The graphics device was lost or removed.
+  LIBRA_ERROR_SUBCODE_DEVICE_LOST = 15,
+};
+#ifndef __cplusplus
+typedef int32_t LIBRA_ERROR_SUBCODE;
+#endif // __cplusplus
+
+/// An enum representing the encoding of the built-in HDR output pass for use in filter chain options.
+enum LIBRA_HDR_OUTPUT
+#ifdef __cplusplus
+  : uint32_t
+#endif // __cplusplus
+ {
+  /// Do not append the HDR output pass.
+  LIBRA_HDR_OUTPUT_NONE = 0,
+  /// Linear scRGB with Rec. 709 primaries, where 1.0 corresponds to 80 nits.
+  /// The filter chain should be drawn to an `R16G16B16A16_SFLOAT` output.
+  LIBRA_HDR_OUTPUT_SCRGB,
+  /// Rec. 2020 primaries encoded with the SMPTE ST 2084 (PQ) transfer function.
+  /// The filter chain should be drawn to an `A2B10G10R10_UNORM_PACK32` output.
+  LIBRA_HDR_OUTPUT_HDR10,
+};
+#ifndef __cplusplus
+typedef uint32_t LIBRA_HDR_OUTPUT;
+#endif // __cplusplus
+
+/// A stage of filter chain creation reported to a `libra_load_progress_callback_t`.
+enum LIBRA_LOAD_STAGE
+#ifdef __cplusplus
+  : int32_t
+#endif // __cplusplus
+ {
+  /// The source of a pass was loaded and preprocessed.
+  LIBRA_LOAD_STAGE_PREPROCESS = 0,
+  /// A pass was compiled and reflected.
+  LIBRA_LOAD_STAGE_COMPILE = 1,
+};
+#ifndef __cplusplus
+typedef int32_t LIBRA_LOAD_STAGE;
+#endif // __cplusplus
+
+/// The verbosity of a log message.
+enum LIBRA_LOG_LEVEL
+#ifdef __cplusplus
+  : int32_t
+#endif // __cplusplus
+ {
+  /// No messages are logged.
+  LIBRA_LOG_LEVEL_OFF = 0,
+  /// Errors that librashader could not recover from.
+  LIBRA_LOG_LEVEL_ERROR = 1,
+  /// Unexpected conditions that librashader recovered from, such as shader compiler
+  /// warnings or an unavailable cache.
+  LIBRA_LOG_LEVEL_WARN = 2,
+  /// General information.
+  LIBRA_LOG_LEVEL_INFO = 3,
+  /// Diagnostic information, such as cache hits and misses, compile timings, and pipeline
+  /// recompilations.
+  LIBRA_LOG_LEVEL_DEBUG = 4,
+  /// Very verbose diagnostic information, such as the files that are read.
+  LIBRA_LOG_LEVEL_TRACE = 5,
+};
+#ifndef __cplusplus
+typedef int32_t LIBRA_LOG_LEVEL;
+#endif // __cplusplus
+
 /// An enum representing orientation for use in preset contexts.
 enum LIBRA_PRESET_CTX_ORIENTATION
 #ifdef __cplusplus
@@ -143,6 +257,27 @@ enum LIBRA_PRESET_CTX_RUNTIME
 typedef uint32_t LIBRA_PRESET_CTX_RUNTIME;
 #endif // __cplusplus
 
+/// An enum representing the shader languages a shader can be transpiled to.
+enum LIBRA_SHADER_TARGET
+#ifdef __cplusplus
+  : uint32_t
+#endif // __cplusplus
+ {
+  /// GLSL, for OpenGL.
+  LIBRA_SHADER_TARGET_GLSL = 0,
+  /// HLSL, for Direct3D.
+  LIBRA_SHADER_TARGET_HLSL,
+  /// Metal Shading Language.
+  LIBRA_SHADER_TARGET_MSL,
+  /// WGSL, for WebGPU.
+  LIBRA_SHADER_TARGET_WGSL,
+  /// SPIR-V, for Vulkan.
+  LIBRA_SHADER_TARGET_SPIRV,
+};
+#ifndef __cplusplus
+typedef uint32_t LIBRA_SHADER_TARGET;
+#endif // __cplusplus
+
 /// Opaque struct for a Direct3D 11 filter chain.
 typedef struct _filter_chain_d3d11 _filter_chain_d3d11;
 
@@ -155,30 +290,91 @@ typedef struct _filter_chain_d3d9 _filter_chain_d3d9;
 /// Opaque struct for an OpenGL filter chain.
 typedef struct _filter_chain_gl _filter_chain_gl;
 
+/// Opaque struct for a filter chain that is being created asynchronously.
+typedef struct _filter_chain_job _filter_chain_job;
+
 /// Opaque struct for a Metal filter chain.
 typedef struct _filter_chain_mtl _filter_chain_mtl;
 
 /// Opaque struct for a Vulkan filter chain.
 typedef struct _filter_chain_vk _filter_chain_vk;
 
+/// Opaque struct for a wgpu filter chain.
+typedef struct _filter_chain_wgpu _filter_chain_wgpu;
+
 /// The error type for librashader C API.
 typedef struct _libra_error _libra_error;
 
 /// Opaque struct for a shader preset.
 typedef struct _shader_preset _shader_preset;
 
+/// Opaque struct for a shader preset pack.
+typedef struct _shader_preset_pack _shader_preset_pack;
+
+/// Opaque struct for a preprocessed shader source.
+typedef struct _shader_source _shader_source;
+
 /// Opaque struct for a preset context.
 typedef struct _preset_ctx _preset_ctx;
 
 /// A handle to a librashader error object.
 typedef struct _libra_error *libra_error_t;
 
+/// A handle to a filter chain that is being created asynchronously.
+typedef struct _filter_chain_job *libra_filter_chain_job_t;
+
 /// A handle to a shader preset object.
 typedef struct _shader_preset *libra_shader_preset_t;
 
+/// A handle to a shader preset pack object.
+typedef struct _shader_preset_pack *libra_preset_pack_t;
+
+/// A handle to a preprocessed shader source object.
+typedef struct _shader_source *libra_shader_source_t;
+
 /// A handle to a preset wildcard context object.
 typedef struct _preset_ctx *libra_preset_ctx_t;
 
+/// Detailed information about an error.
+///
+/// Fields that are not available for an error are set to `-1`, null, or `0`.
+typedef struct libra_error_details_t {
+  /// The error code, the same as returned by `libra_error_errno`.
+  LIBRA_ERRNO code;
+  /// The sub-code of the error.
+  LIBRA_ERROR_SUBCODE subcode;
+  /// The index of the shader pass the error occurred in, or `-1` if not known.
+  int32_t pass_index;
+  /// The path of the file the error occurred in, or null if not known.
+  char *path;
+  /// The line the error occurred on, starting at 1, or `0` if not known.
+  uint32_t line;
+  /// The column the error occurred on, starting at 1, or `0` if not known.
+  uint32_t column;
+} libra_error_details_t;
+
+/// A function that receives log messages from librashader.
+///
+/// `target` is the name of the module that emitted the message, such as `librashader_cache::cache`,
+/// and `message` is the formatted message without a trailing newline. Both strings are only valid
+/// for the duration of the call. `userdata` is the pointer that was passed to `libra_set_log_callback`.
+typedef void (*libra_log_callback_t)(LIBRA_LOG_LEVEL level,
+                                     const char *target,
+                                     const char *message,
+                                     void *userdata);
+
+/// A function that receives the progress of an asynchronous filter chain creation.
+///
+/// `completed` is the number of passes that have completed `stage` so far, and `pass_count` is
+/// the number of passes in the shader preset. Passes complete each stage in parallel, so
+/// `completed` counts up from 1 to `pass_count` rather than naming a pass. The callback is called from the worker threads of librashader,
+/// possibly concurrently, and must return quickly. `userdata` is the pointer that was passed
+/// to the `*_filter_chain_create_async` function.
+typedef void (*libra_load_progress_callback_t)(LIBRA_LOAD_STAGE stage,
+                                               uint32_t completed,
+                                               uint32_t pass_count,
+                                               void *userdata);
+
 /// A preset parameter.
 typedef struct libra_preset_param_t {
   /// The name of the parameter
@@ -205,6 +401,69 @@ typedef struct libra_preset_param_list_t {
   uint64_t length;
 } libra_preset_param_list_t;
 
+/// A parameter of a filter chain.
+typedef struct libra_filter_chain_param_t {
+  /// The name of the parameter
+  const char *name;
+  /// The description of the parameter.
+  const char *description;
+  /// The current value of the parameter.
+  float value;
+  /// The value the parameter had when the filter chain was loaded, which is the value set
+  /// by the shader preset if it overrides the parameter, and the initial value otherwise.
+  float default_value;
+  /// The initial value of the parameter declared in the shader source.
+  float initial;
+  /// The minimum value that the parameter can be set to.
+  float minimum;
+  /// The maximum value that the parameter can be set to.
+  float maximum;
+  /// The step by which this parameter can be incremented or decremented.
+  float step;
+} libra_filter_chain_param_t;
+
+/// A list of filter chain parameters.
+typedef struct libra_filter_chain_param_list_t {
+  /// A pointer to the parameter
+  const struct libra_filter_chain_param_t *parameters;
+  /// The number of parameters in the list. This field
+  /// is readonly, and changing it will lead to undefined
+  /// behaviour on free.
+  uint64_t length;
+} libra_filter_chain_param_list_t;
+
+/// A buffer holding a serialized shader preset pack.
+typedef struct libra_preset_pack_buffer_t {
+  /// A pointer to the bytes of the serialized pack.
+  const uint8_t *data;
+  /// The number of bytes in the buffer. This field
+  /// is readonly, and changing it will lead to undefined
+  /// behaviour on free.
+  uint64_t length;
+} libra_preset_pack_buffer_t;
+
+/// A buffer of shader output.
+///
+/// The buffer is always followed by a NUL terminator that is not included in `length`,
+/// so text output can be read as a C string. SPIR-V output is the raw module in native
+/// byte order.
+typedef struct libra_shader_blob_t {
+  /// A pointer to the bytes of the buffer.
+  const uint8_t *data;
+  /// The number of bytes in the buffer. This field
+  /// is readonly, and changing it will lead to undefined
+  /// behaviour on free.
+  uint64_t length;
+} libra_shader_blob_t;
+
+/// The vertex and fragment output of a shader.
+typedef struct libra_shader_output_t {
+  /// The vertex shader.
+  struct libra_shader_blob_t vertex;
+  /// The fragment shader.
+  struct libra_shader_blob_t fragment;
+} libra_shader_output_t;
+
 #if defined(LIBRA_RUNTIME_OPENGL)
 /// A GL function loader that librashader needs to be initialized with.
 typedef const void *(*libra_gl_loader_t)(const char*);
@@ -213,6 +472,30 @@ typedef const void *(*libra_gl_loader_t)(const char*);
 /// API version type alias.
 typedef size_t LIBRASHADER_API_VERSION;
 
+/// Options for shader transpilation.
+///
+/// A zero-initialized `transpile_opt_t` uses the default for every option.
+typedef struct transpile_opt_t {
+  /// The librashader API version.
+  LIBRASHADER_API_VERSION version;
+  /// The GLSL version to output, such as `330` or `460`. `0` uses the default of `330`.
+  ///
+  /// Only desktop GLSL versions are supported.
+  uint16_t glsl_version;
+  /// The HLSL shader model to output, multiplied by 10, such as `50` for Shader Model 5.0.
+  /// `0` uses the default of `50`.
+  uint16_t hlsl_shader_model;
+  /// The MSL version to output, encoded as `major * 10000 + minor * 100 + patch`,
+  /// such as `20100` for MSL 2.1. `0` uses the default of `10200`.
+  uint32_t msl_version;
+  /// Whether or not to keep the push constant block in WGSL instead of lowering it to a UBO.
+  /// Default is false.
+  bool wgsl_keep_push_constant_block;
+  /// The bind group to place samplers in for WGSL. `0` uses the default of `1`, since
+  /// textures are bound in group 0.
+  uint32_t wgsl_sampler_bind_group;
+} transpile_opt_t;
+
 #if defined(LIBRA_RUNTIME_OPENGL)
 /// Options for filter chain creation.
 typedef struct filter_chain_gl_opt_t {
@@ -229,6 +512,9 @@ typedef struct filter_chain_gl_opt_t {
   /// Disable the shader object cache. Shaders will be
   /// recompiled rather than loaded from the cache.
   bool disable_cache;
+  /// The built-in HDR output pass to append to the shader preset, if any. The filter chain
+  /// should then be drawn to an output with the format of the encoding.
+  LIBRA_HDR_OUTPUT hdr_output;
 } filter_chain_gl_opt_t;
 #endif
 
@@ -281,6 +567,44 @@ typedef struct frame_gl_opt_t {
   uint32_t total_subframes;
   /// The current sub frame. Default is 1.
   uint32_t current_subframe;
+  /// The aspect ratio of the original content.
+  /// If zero, the aspect ratio of the input image is used. Default is 0.
+  float aspect_ratio;
+  /// The refresh rate of the original content, in frames per second. Default is 60.
+  float original_fps;
+  /// The time elapsed since the previous frame, in microseconds. Default is 0.
+  uint32_t frametime_delta;
+  /// The rate at which frames are currently being produced, in frames per second.
+  /// Default is 60.
+  float core_fps;
+  /// Whether or not the output is HDR. Default is false.
+  bool enable_hdr;
+  /// The brightness of SDR paper white in nits. Default is 200.
+  float paper_white_nits;
+  /// The peak brightness of the display in nits. Default is 1000.
+  float max_nits;
+  /// Whether or not to expand the Rec. 709 gamut when converting to HDR. Default is false.
+  bool expand_gamut;
+  /// Whether or not to inverse tonemap SDR content when converting to HDR. Default is false.
+  bool inverse_tonemap;
+  /// The index of the first pass to render. Default is 0.
+  ///
+  /// Passes before the first pass are skipped, and the first pass reads the original
+  /// image as its `Source`. Framebuffers are still sized as if every pass was rendered.
+  uint32_t first_pass;
+  /// The number of passes to render, starting from the first pass.
+  /// If zero, all enabled passes after the first pass are rendered. Default is 0.
+  ///
+  /// The last pass rendered is drawn to the output viewport.
+  uint32_t pass_count;
+  /// The position of the comparison split line, as a fraction of the viewport width.
+  /// Default is 0.
+  ///
+  /// If greater than zero, the original image is drawn to the left of the split line,
+  /// and the output of the last pass rendered to the right of it.
+  float compare_split;
+  /// The width of the comparison split line, in pixels. Default is 1.
+  float compare_line_width;
 } frame_gl_opt_t;
 #endif
 
@@ -320,6 +644,9 @@ typedef struct filter_chain_vk_opt_t {
   /// Disable the shader object cache. Shaders will be
   /// recompiled rather than loaded from the cache.
   bool disable_cache;
+  /// The built-in HDR output pass to append to the shader preset, if any. The filter chain
+  /// should then be drawn to an output with the format of the encoding.
+  LIBRA_HDR_OUTPUT hdr_output;
 } filter_chain_vk_opt_t;
 #endif
 
@@ -358,6 +685,44 @@ typedef struct frame_vk_opt_t {
   uint32_t total_subframes;
   /// The current sub frame. Default is 1.
   uint32_t current_subframe;
+  /// The aspect ratio of the original content.
+  /// If zero, the aspect ratio of the input image is used. Default is 0.
+  float aspect_ratio;
+  /// The refresh rate of the original content, in frames per second. Default is 60.
+  float original_fps;
+  /// The time elapsed since the previous frame, in microseconds. Default is 0.
+  uint32_t frametime_delta;
+  /// The rate at which frames are currently being produced, in frames per second.
+  /// Default is 60.
+  float core_fps;
+  /// Whether or not the output is HDR. Default is false.
+  bool enable_hdr;
+  /// The brightness of SDR paper white in nits. Default is 200.
+  float paper_white_nits;
+  /// The peak brightness of the display in nits. Default is 1000.
+  float max_nits;
+  /// Whether or not to expand the Rec. 709 gamut when converting to HDR. Default is false.
+  bool expand_gamut;
+  /// Whether or not to inverse tonemap SDR content when converting to HDR. Default is false.
+  bool inverse_tonemap;
+  /// The index of the first pass to render. Default is 0.
+  ///
+  /// Passes before the first pass are skipped, and the first pass reads the original
+  /// image as its `Source`. Framebuffers are still sized as if every pass was rendered.
+  uint32_t first_pass;
+  /// The number of passes to render, starting from the first pass.
+  /// If zero, all enabled passes after the first pass are rendered. Default is 0.
+  ///
+  /// The last pass rendered is drawn to the output viewport.
+  uint32_t pass_count;
+  /// The position of the comparison split line, as a fraction of the viewport width.
+  /// Default is 0.
+  ///
+  /// If greater than zero, the original image is drawn to the left of the split line,
+  /// and the output of the last pass rendered to the right of it.
+  float compare_split;
+  /// The width of the comparison split line, in pixels. Default is 1.
+  float compare_line_width;
 } frame_vk_opt_t;
 #endif
 
@@ -372,6 +737,9 @@ typedef struct filter_chain_d3d11_opt_t {
   /// Disable the shader object cache. Shaders will be
   /// recompiled rather than loaded from the cache.
   bool disable_cache;
+  /// The built-in HDR output pass to append to the shader preset, if any. The filter chain
+  /// should then be drawn to an output with the format of the encoding.
+  LIBRA_HDR_OUTPUT hdr_output;
 } filter_chain_d3d11_opt_t;
 #endif
 
@@ -396,6 +764,44 @@ typedef struct frame_d3d11_opt_t {
   uint32_t total_subframes;
   /// The current sub frame. Default is 1.
   uint32_t current_subframe;
+  /// The aspect ratio of the original content.
+  /// If zero, the aspect ratio of the input image is used. Default is 0.
+  float aspect_ratio;
+  /// The refresh rate of the original content, in frames per second. Default is 60.
+  float original_fps;
+  /// The time elapsed since the previous frame, in microseconds. Default is 0.
+  uint32_t frametime_delta;
+  /// The rate at which frames are currently being produced, in frames per second.
+  /// Default is 60.
+  float core_fps;
+  /// Whether or not the output is HDR. Default is false.
+  bool enable_hdr;
+  /// The brightness of SDR paper white in nits. Default is 200.
+  float paper_white_nits;
+  /// The peak brightness of the display in nits. Default is 1000.
+  float max_nits;
+  /// Whether or not to expand the Rec. 709 gamut when converting to HDR. Default is false.
+  bool expand_gamut;
+  /// Whether or not to inverse tonemap SDR content when converting to HDR. Default is false.
+  bool inverse_tonemap;
+  /// The index of the first pass to render. Default is 0.
+  ///
+  /// Passes before the first pass are skipped, and the first pass reads the original
+  /// image as its `Source`. Framebuffers are still sized as if every pass was rendered.
+  uint32_t first_pass;
+  /// The number of passes to render, starting from the first pass.
+  /// If zero, all enabled passes after the first pass are rendered. Default is 0.
+  ///
+  /// The last pass rendered is drawn to the output viewport.
+  uint32_t pass_count;
+  /// The position of the comparison split line, as a fraction of the viewport width.
+  /// Default is 0.
+  ///
+  /// If greater than zero, the original image is drawn to the left of the split line,
+  /// and the output of the last pass rendered to the right of it.
+  float compare_split;
+  /// The width of the comparison split line, in pixels. Default is 1.
+  float compare_line_width;
 } frame_d3d11_opt_t;
 #endif
 
@@ -410,6 +816,9 @@ typedef struct filter_chain_d3d9_opt_t {
   /// Disable the shader object cache. Shaders will be
   /// recompiled rather than loaded from the cache.
   bool disable_cache;
+  /// The built-in HDR output pass to append to the shader preset, if any. The filter chain
+  /// should then be drawn to an output with the format of the encoding.
+  LIBRA_HDR_OUTPUT hdr_output;
 } filter_chain_d3d9_opt_t;
 #endif
 
@@ -434,6 +843,44 @@ typedef struct frame_d3d9_opt_t {
   uint32_t total_subframes;
   /// The current sub frame. Default is 1.
   uint32_t current_subframe;
+  /// The aspect ratio of the original content.
+  /// If zero, the aspect ratio of the input image is used. Default is 0.
+  float aspect_ratio;
+  /// The refresh rate of the original content, in frames per second. Default is 60.
+  float original_fps;
+  /// The time elapsed since the previous frame, in microseconds. Default is 0.
+  uint32_t frametime_delta;
+  /// The rate at which frames are currently being produced, in frames per second.
+  /// Default is 60.
+  float core_fps;
+  /// Whether or not the output is HDR. Default is false.
+  bool enable_hdr;
+  /// The brightness of SDR paper white in nits. Default is 200.
+  float paper_white_nits;
+  /// The peak brightness of the display in nits. Default is 1000.
+  float max_nits;
+  /// Whether or not to expand the Rec. 709 gamut when converting to HDR. Default is false.
+  bool expand_gamut;
+  /// Whether or not to inverse tonemap SDR content when converting to HDR. Default is false.
+  bool inverse_tonemap;
+  /// The index of the first pass to render. Default is 0.
+  ///
+  /// Passes before the first pass are skipped, and the first pass reads the original
+  /// image as its `Source`. Framebuffers are still sized as if every pass was rendered.
+  uint32_t first_pass;
+  /// The number of passes to render, starting from the first pass.
+  /// If zero, all enabled passes after the first pass are rendered. Default is 0.
+  ///
+  /// The last pass rendered is drawn to the output viewport.
+  uint32_t pass_count;
+  /// The position of the comparison split line, as a fraction of the viewport width.
+  /// Default is 0.
+  ///
+  /// If greater than zero, the original image is drawn to the left of the split line,
+  /// and the output of the last pass rendered to the right of it.
+  float compare_split;
+  /// The width of the comparison split line, in pixels. Default is 1.
+  float compare_line_width;
 } frame_d3d9_opt_t;
 #endif
 
@@ -451,6 +898,9 @@ typedef struct filter_chain_d3d12_opt_t {
   /// Disable the shader object cache. Shaders will be
   /// recompiled rather than loaded from the cache.
   bool disable_cache;
+  /// The built-in HDR output pass to append to the shader preset, if any. The filter chain
+  /// should then be drawn to an output with the format of the encoding.
+  LIBRA_HDR_OUTPUT hdr_output;
 } filter_chain_d3d12_opt_t;
 #endif
 
@@ -524,6 +974,44 @@ typedef struct frame_d3d12_opt_t {
   uint32_t total_subframes;
   /// The current sub frame. Default is 1.
   uint32_t current_subframe;
+  /// The aspect ratio of the original content.
+  /// If zero, the aspect ratio of the input image is used. Default is 0.
+  float aspect_ratio;
+  /// The refresh rate of the original content, in frames per second. Default is 60.
+  float original_fps;
+  /// The time elapsed since the previous frame, in microseconds. Default is 0.
+  uint32_t frametime_delta;
+  /// The rate at which frames are currently being produced, in frames per second.
+  /// Default is 60.
+  float core_fps;
+  /// Whether or not the output is HDR. Default is false.
+  bool enable_hdr;
+  /// The brightness of SDR paper white in nits. Default is 200.
+  float paper_white_nits;
+  /// The peak brightness of the display in nits. Default is 1000.
+  float max_nits;
+  /// Whether or not to expand the Rec. 709 gamut when converting to HDR. Default is false.
+  bool expand_gamut;
+  /// Whether or not to inverse tonemap SDR content when converting to HDR. Default is false.
+  bool inverse_tonemap;
+  /// The index of the first pass to render. Default is 0.
+  ///
+  /// Passes before the first pass are skipped, and the first pass reads the original
+  /// image as its `Source`. Framebuffers are still sized as if every pass was rendered.
+  uint32_t first_pass;
+  /// The number of passes to render, starting from the first pass.
+  /// If zero, all enabled passes after the first pass are rendered. Default is 0.
+  ///
+  /// The last pass rendered is drawn to the output viewport.
+  uint32_t pass_count;
+  /// The position of the comparison split line, as a fraction of the viewport width.
+  /// Default is 0.
+  ///
+  /// If greater than zero, the original image is drawn to the left of the split line,
+  /// and the output of the last pass rendered to the right of it.
+  float compare_split;
+  /// The width of the comparison split line, in pixels. Default is 1.
+  float compare_line_width;
 } frame_d3d12_opt_t;
 #endif
 
@@ -534,6 +1022,9 @@ typedef struct filter_chain_mtl_opt_t {
   LIBRASHADER_API_VERSION version;
   /// Whether or not to explicitly disable mipmap generation regardless of shader preset settings.
   bool force_no_mipmaps;
+  /// The built-in HDR output pass to append to the shader preset, if any. The filter chain
+  /// should then be drawn to an output with the format of the encoding.
+  LIBRA_HDR_OUTPUT hdr_output;
 } filter_chain_mtl_opt_t;
 #endif
 
@@ -558,9 +1049,170 @@ typedef struct frame_mtl_opt_t {
   uint32_t total_subframes;
   /// The current sub frame. Default is 1.
   uint32_t current_subframe;
+  /// The aspect ratio of the original content.
+  /// If zero, the aspect ratio of the input image is used. Default is 0.
+  float aspect_ratio;
+  /// The refresh rate of the original content, in frames per second. Default is 60.
+  float original_fps;
+  /// The time elapsed since the previous frame, in microseconds. Default is 0.
+  uint32_t frametime_delta;
+  /// The rate at which frames are currently being produced, in frames per second.
+  /// Default is 60.
+  float core_fps;
+  /// Whether or not the output is HDR. Default is false.
+  bool enable_hdr;
+  /// The brightness of SDR paper white in nits. Default is 200.
+  float paper_white_nits;
+  /// The peak brightness of the display in nits. Default is 1000.
+  float max_nits;
+  /// Whether or not to expand the Rec. 709 gamut when converting to HDR. Default is false.
+  bool expand_gamut;
+  /// Whether or not to inverse tonemap SDR content when converting to HDR. Default is false.
+  bool inverse_tonemap;
+  /// The index of the first pass to render. Default is 0.
+  ///
+  /// Passes before the first pass are skipped, and the first pass reads the original
+  /// image as its `Source`. Framebuffers are still sized as if every pass was rendered.
+  uint32_t first_pass;
+  /// The number of passes to render, starting from the first pass.
+  /// If zero, all enabled passes after the first pass are rendered. Default is 0.
+  ///
+  /// The last pass rendered is drawn to the output viewport.
+  uint32_t pass_count;
+  /// The position of the comparison split line, as a fraction of the viewport width.
+  /// Default is 0.
+  ///
+  /// If greater than zero, the original image is drawn to the left of the split line,
+  /// and the output of the last pass rendered to the right of it.
+  float compare_split;
+  /// The width of the comparison split line, in pixels. Default is 1.
+  float compare_line_width;
 } frame_mtl_opt_t;
 #endif
 
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Options for filter chain creation.
+typedef struct filter_chain_wgpu_opt_t {
+  /// The librashader API version.
+  LIBRASHADER_API_VERSION version;
+  /// Whether or not to explicitly disable mipmap generation regardless of shader preset settings.
+  bool force_no_mipmaps;
+  /// Enable the shader object cache. Shaders will be loaded from the cache
+  /// if this is enabled.
+  bool enable_cache;
+  /// The built-in HDR output pass to append to the shader preset, if any. The filter chain
+  /// should then be drawn to an output with the format of the encoding.
+  LIBRA_HDR_OUTPUT hdr_output;
+} filter_chain_wgpu_opt_t;
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// A handle to a wgpu filter chain.
+typedef struct _filter_chain_wgpu *libra_wgpu_filter_chain_t;
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Options for each wgpu shader frame.
+typedef struct frame_wgpu_opt_t {
+  /// The librashader API version.
+  LIBRASHADER_API_VERSION version;
+  /// Whether or not to clear the history buffers.
+  bool clear_history;
+  /// The direction of rendering.
+  /// -1 indicates that the frames are played in reverse order.
+  int32_t frame_direction;
+  /// The rotation of the output. 0 = 0deg, 1 = 90deg, 2 = 180deg, 3 = 270deg.
+  uint32_t rotation;
+  /// The total number of subframes ran. Default is 1.
+  uint32_t total_subframes;
+  /// The current sub frame. Default is 1.
+  uint32_t current_subframe;
+  /// The aspect ratio of the original content.
+  /// If zero, the aspect ratio of the input image is used. Default is 0.
+  float aspect_ratio;
+  /// The refresh rate of the original content, in frames per second. Default is 60.
+  float original_fps;
+  /// The time elapsed since the previous frame, in microseconds. Default is 0.
+  uint32_t frametime_delta;
+  /// The rate at which frames are currently being produced, in frames per second.
+  /// Default is 60.
+  float core_fps;
+  /// Whether or not the output is HDR. Default is false.
+  bool enable_hdr;
+  /// The brightness of SDR paper white in nits. Default is 200.
+  float paper_white_nits;
+  /// The peak brightness of the display in nits. Default is 1000.
+  float max_nits;
+  /// Whether or not to expand the Rec. 709 gamut when converting to HDR. Default is false.
+  bool expand_gamut;
+  /// Whether or not to inverse tonemap SDR content when converting to HDR. Default is false.
+  bool inverse_tonemap;
+  /// The index of the first pass to render. Default is 0.
+  ///
+  /// Passes before the first pass are skipped, and the first pass reads the original
+  /// image as its `Source`. Framebuffers are still sized as if every pass was rendered.
+  uint32_t first_pass;
+  /// The number of passes to render, starting from the first pass.
+  /// If zero, all enabled passes after the first pass are rendered. Default is 0.
+  ///
+  /// The last pass rendered is drawn to the output viewport.
+  uint32_t pass_count;
+  /// The position of the comparison split line, as a fraction of the viewport width.
+  /// Default is 0.
+  ///
+  /// If greater than zero, the original image is drawn to the left of the split line,
+  /// and the output of the last pass rendered to the right of it.
+  float compare_split;
+  /// The width of the comparison split line, in pixels. Default is 1.
+  float compare_line_width;
+} frame_wgpu_opt_t;
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU_VULKAN)
+/// Handles required to create a wgpu device over a Vulkan device.
+typedef struct libra_wgpu_vk_device_t {
+  /// The entry loader for the Vulkan library.
+  PFN_vkGetInstanceProcAddr entry;
+  /// A raw `VkInstance` handle for the Vulkan instance that will perform rendering.
+  VkInstance instance;
+  /// The Vulkan API version the instance was created with.
+  uint32_t instance_api_version;
+  /// The names of the extensions enabled for the instance.
+  const char *const *instance_extensions;
+  /// The number of names in `instance_extensions`.
+  size_t instance_extension_count;
+  /// A raw `VkPhysicalDevice` handle for the physical device that will perform rendering.
+  VkPhysicalDevice physical_device;
+  /// A raw `VkDevice` handle created with `libra_wgpu_vk_create_device`
+  /// for the device attached to the instance that will perform rendering.
+  VkDevice device;
+  /// The names of the extensions to enable for the device, in addition to the extensions that wgpu requires.
+  const char *const *device_extensions;
+  /// The number of names in `device_extensions`.
+  size_t device_extension_count;
+  /// The queue family of the queue to submit commands to. This must be a graphics queue family.
+  uint32_t queue_family_index;
+  /// The index of the queue to submit commands to within its queue family.
+  uint32_t queue_index;
+} libra_wgpu_vk_device_t;
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU_VULKAN)
+/// A Vulkan image to create a wgpu texture over.
+typedef struct libra_wgpu_vk_image_t {
+  /// A raw `VkImage` handle.
+  VkImage handle;
+  /// The `VkFormat` of the `VkImage`.
+  VkFormat format;
+  /// The width of the `VkImage`.
+  uint32_t width;
+  /// The height of the `VkImage`.
+  uint32_t height;
+  /// The `VkImageUsageFlags` the `VkImage` was created with.
+  VkImageUsageFlags usage;
+} libra_wgpu_vk_image_t;
+#endif
+
 /// ABI version type alias.
 typedef size_t LIBRASHADER_ABI_VERSION;
 
@@ -609,6 +1261,70 @@ typedef libra_error_t (*PFN_libra_preset_create_with_context)(const char *filena
                                                               libra_preset_ctx_t *context,
                                                               libra_shader_preset_t *out);
 
+/// Function pointer definition for
+///libra_preset_pack_create_from_preset
+typedef libra_error_t (*PFN_libra_preset_pack_create_from_preset)(libra_shader_preset_t *preset,
+                                                                  libra_preset_pack_t *out);
+
+/// Function pointer definition for
+///libra_preset_pack_load
+typedef libra_error_t (*PFN_libra_preset_pack_load)(const uint8_t *buffer,
+                                                    size_t length,
+                                                    libra_preset_pack_t *out);
+
+/// Function pointer definition for
+///libra_preset_pack_write
+typedef libra_error_t (*PFN_libra_preset_pack_write)(const libra_preset_pack_t *pack,
+                                                     struct libra_preset_pack_buffer_t *out);
+
+/// Function pointer definition for
+///libra_preset_pack_free_buffer
+typedef libra_error_t (*PFN_libra_preset_pack_free_buffer)(struct libra_preset_pack_buffer_t buffer);
+
+/// Function pointer definition for
+///libra_preset_pack_free
+typedef libra_error_t (*PFN_libra_preset_pack_free)(libra_preset_pack_t *pack);
+
+/// Function pointer definition for
+///libra_shader_source_create
+typedef libra_error_t (*PFN_libra_shader_source_create)(const char *filename,
+                                                        libra_shader_source_t *out);
+
+/// Function pointer definition for
+///libra_shader_source_get_text
+typedef libra_error_t (*PFN_libra_shader_source_get_text)(const libra_shader_source_t *source,
+                                                          struct libra_shader_output_t *out);
+
+/// Function pointer definition for
+///libra_shader_source_get_params
+typedef libra_error_t (*PFN_libra_shader_source_get_params)(const libra_shader_source_t *source,
+                                                            struct libra_preset_param_list_t *out);
+
+/// Function pointer definition for
+///libra_shader_source_transpile
+typedef libra_error_t (*PFN_libra_shader_source_transpile)(const libra_shader_source_t *source,
+                                                           LIBRA_SHADER_TARGET target,
+                                                           const struct transpile_opt_t *options,
+                                                           struct libra_shader_output_t *out);
+
+/// Function pointer definition for
+///libra_preset_reflect_pass
+typedef libra_error_t (*PFN_libra_preset_reflect_pass)(const libra_shader_preset_t *preset,
+                                                       uint32_t index,
+                                                       struct libra_shader_blob_t *out);
+
+/// Function pointer definition for
+///libra_shader_blob_free
+typedef libra_error_t (*PFN_libra_shader_blob_free)(struct libra_shader_blob_t blob);
+
+/// Function pointer definition for
+///libra_shader_output_free
+typedef libra_error_t (*PFN_libra_shader_output_free)(struct libra_shader_output_t output);
+
+/// Function pointer definition for
+///libra_shader_source_free
+typedef libra_error_t (*PFN_libra_shader_source_free)(libra_shader_source_t *source);
+
 /// Function pointer definition for
 ///libra_preset_ctx_create
 typedef libra_error_t (*PFN_libra_preset_ctx_create)(libra_preset_ctx_t *out);
@@ -683,6 +1399,36 @@ typedef int32_t (*PFN_libra_error_write)(libra_error_t error, char **out);
 /// Function pointer definition for libra_error_free_string
 typedef int32_t (*PFN_libra_error_free_string)(char **out);
 
+/// Function pointer definition for libra_error_get_details
+typedef int32_t (*PFN_libra_error_get_details)(libra_error_t error,
+                                               struct libra_error_details_t *out);
+
+/// Function pointer definition for libra_error_free_details
+typedef int32_t (*PFN_libra_error_free_details)(struct libra_error_details_t *details);
+
+/// Function pointer definition for
+///libra_set_log_callback
+typedef libra_error_t (*PFN_libra_set_log_callback)(LIBRA_LOG_LEVEL level,
+                                                    libra_log_callback_t callback,
+                                                    void *userdata);
+
+/// Function pointer definition for
+///libra_filter_chain_free_params
+typedef libra_error_t (*PFN_libra_filter_chain_free_params)(struct libra_filter_chain_param_list_t list);
+
+/// Function pointer definition for
+///libra_filter_chain_job_cancel
+typedef libra_error_t (*PFN_libra_filter_chain_job_cancel)(const libra_filter_chain_job_t *job);
+
+/// Function pointer definition for
+///libra_filter_chain_job_is_finished
+typedef libra_error_t (*PFN_libra_filter_chain_job_is_finished)(const libra_filter_chain_job_t *job,
+                                                                bool *out);
+
+/// Function pointer definition for
+///libra_filter_chain_job_free
+typedef libra_error_t (*PFN_libra_filter_chain_job_free)(libra_filter_chain_job_t *job);
+
 #if defined(LIBRA_RUNTIME_OPENGL)
 /// Function pointer definition for
 ///libra_gl_filter_chain_create
@@ -692,6 +1438,15 @@ typedef libra_error_t (*PFN_libra_gl_filter_chain_create)(libra_shader_preset_t
                                                           libra_gl_filter_chain_t *out);
 #endif
 
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Function pointer definition for
+///libra_gl_filter_chain_create_from_pack
+typedef libra_error_t (*PFN_libra_gl_filter_chain_create_from_pack)(libra_preset_pack_t *pack,
+                                                                    libra_gl_loader_t loader,
+                                                                    const struct filter_chain_gl_opt_t *options,
+                                                                    libra_gl_filter_chain_t *out);
+#endif
+
 #if defined(LIBRA_RUNTIME_OPENGL)
 /// Function pointer definition for
 ///libra_gl_filter_chain_frame
@@ -720,6 +1475,19 @@ typedef libra_error_t (*PFN_libra_gl_filter_chain_get_param)(const libra_gl_filt
                                                              float *out);
 #endif
 
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Function pointer definition for
+///libra_gl_filter_chain_get_params
+typedef libra_error_t (*PFN_libra_gl_filter_chain_get_params)(const libra_gl_filter_chain_t *chain,
+                                                              struct libra_filter_chain_param_list_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Function pointer definition for
+///libra_gl_filter_chain_reset_params
+typedef libra_error_t (*PFN_libra_gl_filter_chain_reset_params)(libra_gl_filter_chain_t *chain);
+#endif
+
 #if defined(LIBRA_RUNTIME_OPENGL)
 /// Function pointer definition for
 ///libra_gl_filter_chain_set_active_pass_count
@@ -759,6 +1527,44 @@ typedef libra_error_t (*PFN_libra_vk_filter_chain_create_deferred)(libra_shader_
                                                                    libra_vk_filter_chain_t *out);
 #endif
 
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Function pointer definition for
+///libra_vk_filter_chain_create_async
+typedef libra_error_t (*PFN_libra_vk_filter_chain_create_async)(libra_shader_preset_t *preset,
+                                                                struct libra_device_vk_t vulkan,
+                                                                VkCommandBuffer command_buffer,
+                                                                const struct filter_chain_vk_opt_t *options,
+                                                                libra_load_progress_callback_t progress,
+                                                                void *userdata,
+                                                                libra_filter_chain_job_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Function pointer definition for
+///libra_vk_filter_chain_job_finish
+typedef libra_error_t (*PFN_libra_vk_filter_chain_job_finish)(libra_filter_chain_job_t *job,
+                                                              libra_vk_filter_chain_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Function pointer definition for
+///libra_vk_filter_chain_create_from_pack
+typedef libra_error_t (*PFN_libra_vk_filter_chain_create_from_pack)(libra_preset_pack_t *pack,
+                                                                    struct libra_device_vk_t vulkan,
+                                                                    const struct filter_chain_vk_opt_t *options,
+                                                                    libra_vk_filter_chain_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Function pointer definition for
+///libra_vk_filter_chain_create_from_pack_deferred
+typedef libra_error_t (*PFN_libra_vk_filter_chain_create_from_pack_deferred)(libra_preset_pack_t *pack,
+                                                                             struct libra_device_vk_t vulkan,
+                                                                             VkCommandBuffer command_buffer,
+                                                                             const struct filter_chain_vk_opt_t *options,
+                                                                             libra_vk_filter_chain_t *out);
+#endif
+
 #if defined(LIBRA_RUNTIME_VULKAN)
 /// Function pointer definition for
 ///libra_vk_filter_chain_frame
@@ -788,6 +1594,19 @@ typedef libra_error_t (*PFN_libra_vk_filter_chain_get_param)(const libra_vk_filt
                                                              float *out);
 #endif
 
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Function pointer definition for
+///libra_vk_filter_chain_get_params
+typedef libra_error_t (*PFN_libra_vk_filter_chain_get_params)(const libra_vk_filter_chain_t *chain,
+                                                              struct libra_filter_chain_param_list_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Function pointer definition for
+///libra_vk_filter_chain_reset_params
+typedef libra_error_t (*PFN_libra_vk_filter_chain_reset_params)(libra_vk_filter_chain_t *chain);
+#endif
+
 #if defined(LIBRA_RUNTIME_VULKAN)
 /// Function pointer definition for
 ///libra_vk_filter_chain_set_active_pass_count
@@ -827,6 +1646,44 @@ typedef libra_error_t (*PFN_libra_d3d11_filter_chain_create_deferred)(libra_shad
                                                                       libra_d3d11_filter_chain_t *out);
 #endif
 
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Function pointer definition for
+///libra_d3d11_filter_chain_create_async
+typedef libra_error_t (*PFN_libra_d3d11_filter_chain_create_async)(libra_shader_preset_t *preset,
+                                                                   ID3D11Device * device,
+                                                                   ID3D11DeviceContext * device_context,
+                                                                   const struct filter_chain_d3d11_opt_t *options,
+                                                                   libra_load_progress_callback_t progress,
+                                                                   void *userdata,
+                                                                   libra_filter_chain_job_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Function pointer definition for
+///libra_d3d11_filter_chain_job_finish
+typedef libra_error_t (*PFN_libra_d3d11_filter_chain_job_finish)(libra_filter_chain_job_t *job,
+                                                                 libra_d3d11_filter_chain_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Function pointer definition for
+///libra_d3d11_filter_chain_create_from_pack
+typedef libra_error_t (*PFN_libra_d3d11_filter_chain_create_from_pack)(libra_preset_pack_t *pack,
+                                                                       ID3D11Device * device,
+                                                                       const struct filter_chain_d3d11_opt_t *options,
+                                                                       libra_d3d11_filter_chain_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Function pointer definition for
+///libra_d3d11_filter_chain_create_from_pack_deferred
+typedef libra_error_t (*PFN_libra_d3d11_filter_chain_create_from_pack_deferred)(libra_preset_pack_t *pack,
+                                                                                ID3D11Device * device,
+                                                                                ID3D11DeviceContext * device_context,
+                                                                                const struct filter_chain_d3d11_opt_t *options,
+                                                                                libra_d3d11_filter_chain_t *out);
+#endif
+
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
 /// Function pointer definition for
 ///libra_d3d11_filter_chain_frame
@@ -856,6 +1713,19 @@ typedef libra_error_t (*PFN_libra_d3d11_filter_chain_get_param)(const libra_d3d1
                                                                 float *out);
 #endif
 
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Function pointer definition for
+///libra_d3d11_filter_chain_get_params
+typedef libra_error_t (*PFN_libra_d3d11_filter_chain_get_params)(const libra_d3d11_filter_chain_t *chain,
+                                                                 struct libra_filter_chain_param_list_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Function pointer definition for
+///libra_d3d11_filter_chain_reset_params
+typedef libra_error_t (*PFN_libra_d3d11_filter_chain_reset_params)(libra_d3d11_filter_chain_t *chain);
+#endif
+
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
 /// Function pointer definition for
 ///libra_d3d11_filter_chain_set_active_pass_count
@@ -885,6 +1755,15 @@ typedef libra_error_t (*PFN_libra_d3d9_filter_chain_create)(libra_shader_preset_
                                                             libra_d3d9_filter_chain_t *out);
 #endif
 
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// Function pointer definition for
+///libra_d3d9_filter_chain_create_from_pack
+typedef libra_error_t (*PFN_libra_d3d9_filter_chain_create_from_pack)(libra_preset_pack_t *pack,
+                                                                      IDirect3DDevice9 * device,
+                                                                      const struct filter_chain_d3d9_opt_t *options,
+                                                                      libra_d3d9_filter_chain_t *out);
+#endif
+
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
 /// Function pointer definition for
 ///libra_d3d9_filter_chain_frame
@@ -913,6 +1792,19 @@ typedef libra_error_t (*PFN_libra_d3d9_filter_chain_get_param)(const libra_d3d9_
                                                                float *out);
 #endif
 
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// Function pointer definition for
+///libra_d3d9_filter_chain_get_params
+typedef libra_error_t (*PFN_libra_d3d9_filter_chain_get_params)(const libra_d3d9_filter_chain_t *chain,
+                                                                struct libra_filter_chain_param_list_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// Function pointer definition for
+///libra_d3d9_filter_chain_reset_params
+typedef libra_error_t (*PFN_libra_d3d9_filter_chain_reset_params)(libra_d3d9_filter_chain_t *chain);
+#endif
+
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
 /// Function pointer definition for
 ///libra_d3d9_filter_chain_set_active_pass_count
@@ -952,6 +1844,44 @@ typedef libra_error_t (*PFN_libra_d3d12_filter_chain_create_deferred)(libra_shad
                                                                       libra_d3d12_filter_chain_t *out);
 #endif
 
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Function pointer definition for
+///libra_d3d12_filter_chain_create_async
+typedef libra_error_t (*PFN_libra_d3d12_filter_chain_create_async)(libra_shader_preset_t *preset,
+                                                                   ID3D12Device * device,
+                                                                   ID3D12GraphicsCommandList * command_list,
+                                                                   const struct filter_chain_d3d12_opt_t *options,
+                                                                   libra_load_progress_callback_t progress,
+                                                                   void *userdata,
+                                                                   libra_filter_chain_job_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Function pointer definition for
+///libra_d3d12_filter_chain_job_finish
+typedef libra_error_t (*PFN_libra_d3d12_filter_chain_job_finish)(libra_filter_chain_job_t *job,
+                                                                 libra_d3d12_filter_chain_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Function pointer definition for
+///libra_d3d12_filter_chain_create_from_pack
+typedef libra_error_t (*PFN_libra_d3d12_filter_chain_create_from_pack)(libra_preset_pack_t *pack,
+                                                                       ID3D12Device * device,
+                                                                       const struct filter_chain_d3d12_opt_t *options,
+                                                                       libra_d3d12_filter_chain_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Function pointer definition for
+///libra_d3d12_filter_chain_create_from_pack_deferred
+typedef libra_error_t (*PFN_libra_d3d12_filter_chain_create_from_pack_deferred)(libra_preset_pack_t *pack,
+                                                                                ID3D12Device * device,
+                                                                                ID3D12GraphicsCommandList * command_list,
+                                                                                const struct filter_chain_d3d12_opt_t *options,
+                                                                                libra_d3d12_filter_chain_t *out);
+#endif
+
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
 /// Function pointer definition for
 ///libra_d3d12_filter_chain_frame
@@ -983,8 +1913,21 @@ typedef libra_error_t (*PFN_libra_d3d12_filter_chain_get_param)(const libra_d3d1
 
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
 /// Function pointer definition for
-///libra_d3d12_filter_chain_set_active_pass_count
-typedef libra_error_t (*PFN_libra_d3d12_filter_chain_set_active_pass_count)(libra_d3d12_filter_chain_t *chain,
+///libra_d3d12_filter_chain_get_params
+typedef libra_error_t (*PFN_libra_d3d12_filter_chain_get_params)(const libra_d3d12_filter_chain_t *chain,
+                                                                 struct libra_filter_chain_param_list_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Function pointer definition for
+///libra_d3d12_filter_chain_reset_params
+typedef libra_error_t (*PFN_libra_d3d12_filter_chain_reset_params)(libra_d3d12_filter_chain_t *chain);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Function pointer definition for
+///libra_d3d12_filter_chain_set_active_pass_count
+typedef libra_error_t (*PFN_libra_d3d12_filter_chain_set_active_pass_count)(libra_d3d12_filter_chain_t *chain,
                                                                             uint32_t value);
 #endif
 
@@ -1020,6 +1963,25 @@ typedef libra_error_t (*PFN_libra_mtl_filter_chain_create_deferred)(libra_shader
                                                                     libra_mtl_filter_chain_t *out);
 #endif
 
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Function pointer definition for
+///libra_mtl_filter_chain_create_from_pack
+typedef libra_error_t (*PFN_libra_mtl_filter_chain_create_from_pack)(libra_preset_pack_t *pack,
+                                                                     id<MTLCommandQueue> queue,
+                                                                     const struct filter_chain_mtl_opt_t *options,
+                                                                     libra_mtl_filter_chain_t *out);
+#endif
+
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Function pointer definition for
+///libra_mtl_filter_chain_create_from_pack_deferred
+typedef libra_error_t (*PFN_libra_mtl_filter_chain_create_from_pack_deferred)(libra_preset_pack_t *pack,
+                                                                              id<MTLCommandQueue> queue,
+                                                                              id<MTLCommandBuffer> command_buffer,
+                                                                              const struct filter_chain_mtl_opt_t *options,
+                                                                              libra_mtl_filter_chain_t *out);
+#endif
+
 #if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
 /// Function pointer definition for
 ///libra_mtl_filter_chain_frame
@@ -1049,6 +2011,19 @@ typedef libra_error_t (*PFN_libra_mtl_filter_chain_get_param)(const libra_mtl_fi
                                                               float *out);
 #endif
 
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Function pointer definition for
+///libra_mtl_filter_chain_get_params
+typedef libra_error_t (*PFN_libra_mtl_filter_chain_get_params)(const libra_mtl_filter_chain_t *chain,
+                                                               struct libra_filter_chain_param_list_t *out);
+#endif
+
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Function pointer definition for
+///libra_mtl_filter_chain_reset_params
+typedef libra_error_t (*PFN_libra_mtl_filter_chain_reset_params)(libra_mtl_filter_chain_t *chain);
+#endif
+
 #if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
 /// Function pointer definition for
 ///libra_mtl_filter_chain_set_active_pass_count
@@ -1069,6 +2044,191 @@ typedef libra_error_t (*PFN_libra_mtl_filter_chain_get_active_pass_count)(const
 typedef libra_error_t (*PFN_libra_mtl_filter_chain_free)(libra_mtl_filter_chain_t *chain);
 #endif
 
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Function pointer definition for
+///libra_wgpu_filter_chain_create
+typedef libra_error_t (*PFN_libra_wgpu_filter_chain_create)(libra_shader_preset_t *preset,
+                                                            libra_wgpu_device_t device,
+                                                            libra_wgpu_queue_t queue,
+                                                            const struct filter_chain_wgpu_opt_t *options,
+                                                            libra_wgpu_filter_chain_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Function pointer definition for
+///libra_wgpu_filter_chain_create_deferred
+typedef libra_error_t (*PFN_libra_wgpu_filter_chain_create_deferred)(libra_shader_preset_t *preset,
+                                                                     libra_wgpu_device_t device,
+                                                                     libra_wgpu_queue_t queue,
+                                                                     libra_wgpu_command_encoder_t encoder,
+                                                                     const struct filter_chain_wgpu_opt_t *options,
+                                                                     libra_wgpu_filter_chain_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Function pointer definition for
+///libra_wgpu_filter_chain_create_async
+typedef libra_error_t (*PFN_libra_wgpu_filter_chain_create_async)(libra_shader_preset_t *preset,
+                                                                  libra_wgpu_device_t device,
+                                                                  libra_wgpu_queue_t queue,
+                                                                  libra_wgpu_command_encoder_t encoder,
+                                                                  const struct filter_chain_wgpu_opt_t *options,
+                                                                  libra_load_progress_callback_t progress,
+                                                                  void *userdata,
+                                                                  libra_filter_chain_job_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Function pointer definition for
+///libra_wgpu_filter_chain_job_finish
+typedef libra_error_t (*PFN_libra_wgpu_filter_chain_job_finish)(libra_filter_chain_job_t *job,
+                                                                libra_wgpu_filter_chain_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Function pointer definition for
+///libra_wgpu_filter_chain_create_from_pack
+typedef libra_error_t (*PFN_libra_wgpu_filter_chain_create_from_pack)(libra_preset_pack_t *pack,
+                                                                      libra_wgpu_device_t device,
+                                                                      libra_wgpu_queue_t queue,
+                                                                      const struct filter_chain_wgpu_opt_t *options,
+                                                                      libra_wgpu_filter_chain_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Function pointer definition for
+///libra_wgpu_filter_chain_create_from_pack_deferred
+typedef libra_error_t (*PFN_libra_wgpu_filter_chain_create_from_pack_deferred)(libra_preset_pack_t *pack,
+                                                                               libra_wgpu_device_t device,
+                                                                               libra_wgpu_queue_t queue,
+                                                                               libra_wgpu_command_encoder_t encoder,
+                                                                               const struct filter_chain_wgpu_opt_t *options,
+                                                                               libra_wgpu_filter_chain_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Function pointer definition for
+///libra_wgpu_filter_chain_frame
+typedef libra_error_t (*PFN_libra_wgpu_filter_chain_frame)(libra_wgpu_filter_chain_t *chain,
+                                                           libra_wgpu_command_encoder_t encoder,
+                                                           size_t frame_count,
+                                                           libra_wgpu_texture_t image,
+                                                           libra_wgpu_texture_t output,
+                                                           const struct libra_viewport_t *viewport,
+                                                           const float *mvp,
+                                                           const struct frame_wgpu_opt_t *opt);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Function pointer definition for
+///libra_wgpu_filter_chain_set_param
+typedef libra_error_t (*PFN_libra_wgpu_filter_chain_set_param)(libra_wgpu_filter_chain_t *chain,
+                                                               const char *param_name,
+                                                               float value);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Function pointer definition for
+///libra_wgpu_filter_chain_get_param
+typedef libra_error_t (*PFN_libra_wgpu_filter_chain_get_param)(const libra_wgpu_filter_chain_t *chain,
+                                                               const char *param_name,
+                                                               float *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Function pointer definition for
+///libra_wgpu_filter_chain_get_params
+typedef libra_error_t (*PFN_libra_wgpu_filter_chain_get_params)(const libra_wgpu_filter_chain_t *chain,
+                                                                struct libra_filter_chain_param_list_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Function pointer definition for
+///libra_wgpu_filter_chain_reset_params
+typedef libra_error_t (*PFN_libra_wgpu_filter_chain_reset_params)(libra_wgpu_filter_chain_t *chain);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Function pointer definition for
+///libra_wgpu_filter_chain_set_active_pass_count
+typedef libra_error_t (*PFN_libra_wgpu_filter_chain_set_active_pass_count)(libra_wgpu_filter_chain_t *chain,
+                                                                           uint32_t value);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Function pointer definition for
+///libra_wgpu_filter_chain_get_active_pass_count
+typedef libra_error_t (*PFN_libra_wgpu_filter_chain_get_active_pass_count)(const libra_wgpu_filter_chain_t *chain,
+                                                                           uint32_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Function pointer definition for
+///libra_wgpu_filter_chain_free
+typedef libra_error_t (*PFN_libra_wgpu_filter_chain_free)(libra_wgpu_filter_chain_t *chain);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Function pointer definition for
+///libra_wgpu_command_encoder_create
+typedef libra_error_t (*PFN_libra_wgpu_command_encoder_create)(libra_wgpu_device_t device,
+                                                               libra_wgpu_command_encoder_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Function pointer definition for
+///libra_wgpu_queue_submit
+typedef libra_error_t (*PFN_libra_wgpu_queue_submit)(libra_wgpu_queue_t queue,
+                                                     libra_wgpu_command_encoder_t *encoder);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Function pointer definition for
+///libra_wgpu_command_encoder_free
+typedef libra_error_t (*PFN_libra_wgpu_command_encoder_free)(libra_wgpu_command_encoder_t *encoder);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Function pointer definition for
+///libra_wgpu_device_free
+typedef libra_error_t (*PFN_libra_wgpu_device_free)(libra_wgpu_device_t *device);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Function pointer definition for
+///libra_wgpu_queue_free
+typedef libra_error_t (*PFN_libra_wgpu_queue_free)(libra_wgpu_queue_t *queue);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Function pointer definition for
+///libra_wgpu_texture_free
+typedef libra_error_t (*PFN_libra_wgpu_texture_free)(libra_wgpu_texture_t *texture);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU_VULKAN)
+/// Function pointer definition for
+///libra_wgpu_vk_create_device
+typedef libra_error_t (*PFN_libra_wgpu_vk_create_device)(struct libra_wgpu_vk_device_t vulkan,
+                                                         VkDevice *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU_VULKAN)
+/// Function pointer definition for
+///libra_wgpu_device_create_vk
+typedef libra_error_t (*PFN_libra_wgpu_device_create_vk)(struct libra_wgpu_vk_device_t vulkan,
+                                                         libra_wgpu_device_t *out_device,
+                                                         libra_wgpu_queue_t *out_queue);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU_VULKAN)
+/// Function pointer definition for
+///libra_wgpu_texture_create_vk
+typedef libra_error_t (*PFN_libra_wgpu_texture_create_vk)(libra_wgpu_device_t device,
+                                                          struct libra_wgpu_vk_image_t image,
+                                                          libra_wgpu_texture_t *out);
+#endif
+
 /// The current version of the librashader API.
 /// Pass this into `version` for config structs.
 ///
@@ -1082,7 +2242,19 @@ typedef libra_error_t (*PFN_libra_mtl_filter_chain_free)(libra_mtl_filter_chain_
 ///     - Added rotation, total_subframes, current_subframes to frame options
 ///     - Added preset context API
 ///     - Added Metal runtime API
-#define LIBRASHADER_CURRENT_VERSION 1
+/// - API version 2: 0.6.0
+///     - Added aspect_ratio, original_fps, frametime_delta, core_fps to frame options
+///     - Added enable_hdr, paper_white_nits, max_nits, expand_gamut, inverse_tonemap to frame options
+///     - Added first_pass, pass_count to frame options
+///     - Added preset pack API, and `*_filter_chain_create_from_pack` for each runtime
+///     - Added shader source API for preprocessing, transpilation, and preset reflection
+///     - Added `libra_error_get_details` and `libra_error_free_details`
+///     - Added `libra_set_log_callback`
+///     - Added `*_filter_chain_get_params`, `*_filter_chain_reset_params`, and `libra_filter_chain_free_params`
+///     - Added `*_filter_chain_create_async`, `*_filter_chain_job_finish`, and the `libra_filter_chain_job_*` functions
+///     - Added compare_split, compare_line_width to frame options
+///     - Added wgpu runtime API
+#define LIBRASHADER_CURRENT_VERSION 2
 
 /// The current version of the librashader ABI.
 /// Used by the loader to check ABI compatibility.
@@ -1147,6 +2319,109 @@ int32_t libra_error_write(libra_error_t error,
 ///     Undefined Behaviour.
 int32_t libra_error_free_string(char **out);
 
+/// Writes detailed information about the error into `out`.
+///
+/// Details are taken from the preset parser, preprocessor, shader compiler, and
+/// reflection errors that caused this error, where they record it. In particular,
+/// the pass index is only known for errors found when validating the filter chain,
+/// and the line is only known for preset syntax errors, invalid directives, and
+/// errors reported by the shader compiler.
+///
+/// If `error` is null, this function does nothing and returns 1. Otherwise, this function returns 0.
+/// ## Safety
+///   - `error` must be a valid and initialized instance of `libra_error_t`.
+///   - `out` must be a non-null pointer. The resulting struct must be freed with `libra_error_free_details`.
+int32_t libra_error_get_details(libra_error_t error, struct libra_error_details_t *out);
+
+/// Frees the path of error details previously written by `libra_error_get_details`.
+///
+/// After freeing, the path will be set to null.
+/// If `details` is null, this function does nothing and returns 1. Otherwise, this function returns 0.
+/// ## Safety
+///   - `details` must be null or point to details previously written by `libra_error_get_details`.
+int32_t libra_error_free_details(struct libra_error_details_t *details);
+
+/// Set the function that receives log messages from librashader.
+///
+/// Messages more verbose than `level` are discarded before they are formatted. Passing a null
+/// `callback` or `LIBRA_LOG_LEVEL_OFF` disables logging. Subsequent calls replace the previous callback
+/// and level.
+///
+/// The callback may be called from any thread that calls into librashader, including threads
+/// internal to librashader, and may be called concurrently. The callback may call back into
+/// librashader. A message that was being logged on another thread while the callback was
+/// replaced may still be delivered to the previous callback.
+///
+/// ## Safety
+/// - `callback` must be null, or a valid function pointer that is safe to call from any thread
+///   until it is replaced by a subsequent call to `libra_set_log_callback`, and every call
+///   into librashader on other threads that started before then has returned.
+/// - `userdata` is passed to `callback` as is, and must be safe to use from any thread.
+/// ## Returns
+/// - If librashader was linked into a Rust program that already set up its own logger,
+///   this function returns `LIBRA_ERR_UNKNOWN_ERROR`.
+libra_error_t libra_set_log_callback(LIBRA_LOG_LEVEL level,
+                                     libra_log_callback_t callback,
+                                     void *userdata);
+
+/// Load the passes and lookup textures of a shader preset into a shader preset pack.
+///
+/// The shader preset is immediately invalidated and must be recreated after
+/// the pack is created.
+///
+/// ## Safety
+///  - `preset` must be either null, or valid and aligned.
+///  - `out` must be either null, or an aligned pointer to an uninitialized or invalid `libra_preset_pack_t`.
+/// ## Returns
+///  - If any parameters are null, `out` is unchanged, and this function returns `LIBRA_ERR_INVALID_PARAMETER`.
+libra_error_t libra_preset_pack_create_from_preset(libra_shader_preset_t *preset,
+                                                   libra_preset_pack_t *out);
+
+/// Load a shader preset pack from a buffer in memory.
+///
+/// The buffer must contain a pack serialized as MessagePack, such as one written by
+/// `libra_preset_pack_write`. The buffer is not retained after this function returns.
+///
+/// ## Safety
+///  - `buffer` must be either null, or a valid, aligned pointer to at least `length` bytes.
+///  - `out` must be either null, or an aligned pointer to an uninitialized or invalid `libra_preset_pack_t`.
+/// ## Returns
+///  - If any parameters are null, `out` is unchanged, and this function returns `LIBRA_ERR_INVALID_PARAMETER`.
+libra_error_t libra_preset_pack_load(const uint8_t *buffer, size_t length, libra_preset_pack_t *out);
+
+/// Serialize the shader preset pack into a buffer as MessagePack.
+///
+/// The pack is not invalidated, and can still be used to create a filter chain.
+///
+/// ## Safety
+/// - `pack` must be null or a valid and aligned pointer to a `libra_preset_pack_t`.
+/// - `out` must be an aligned pointer to a `libra_preset_pack_buffer_t`.
+/// - The output struct should be treated as immutable. Mutating any struct fields
+///   in the returned struct may at best cause memory leaks, and at worse
+///   cause undefined behaviour when later freed.
+/// - The output buffer must be freed with `libra_preset_pack_free_buffer`.
+libra_error_t libra_preset_pack_write(const libra_preset_pack_t *pack,
+                                      struct libra_preset_pack_buffer_t *out);
+
+/// Free a buffer previously written by `libra_preset_pack_write`.
+///
+/// Like `libra_preset_free_runtime_params`, this function takes the struct directly.
+///
+/// ## Safety
+/// - `buffer` must have been returned by `libra_preset_pack_write`, with its fields unchanged.
+/// - Accessing the data of the buffer after it has been freed is a use-after-free and is
+///   immediate undefined behaviour.
+libra_error_t libra_preset_pack_free_buffer(struct libra_preset_pack_buffer_t buffer);
+
+/// Free the shader preset pack.
+///
+/// If `pack` is null, this function does nothing. The resulting value in `pack` then becomes
+/// null.
+///
+/// ## Safety
+/// - `pack` must be a valid and aligned pointer to a `libra_preset_pack_t`.
+libra_error_t libra_preset_pack_free(libra_preset_pack_t *pack);
+
 /// Load a preset.
 ///
 /// ## Safety
@@ -1240,6 +2515,161 @@ libra_error_t libra_preset_get_runtime_params(const libra_shader_preset_t *prese
 ///   in undefined behaviour.
 libra_error_t libra_preset_free_runtime_params(struct libra_preset_param_list_t preset);
 
+/// Load and preprocess a shader source file.
+///
+/// `#include` directives are resolved relative to the location of the shader.
+///
+/// ## Safety
+///  - `filename` must be either null or a valid, aligned pointer to a string path to the shader.
+///  - `out` must be either null, or an aligned pointer to an uninitialized or invalid `libra_shader_source_t`.
+/// ## Returns
+///  - If any parameters are null, `out` is unchanged, and this function returns `LIBRA_ERR_INVALID_PARAMETER`.
+libra_error_t libra_shader_source_create(const char *filename, libra_shader_source_t *out);
+
+/// Get the preprocessed GLSL text of the vertex and fragment stages of the shader.
+///
+/// ## Safety
+/// - `source` must be null or a valid and aligned pointer to a `libra_shader_source_t`.
+/// - `out` must be an aligned pointer to a `libra_shader_output_t`.
+/// - The output struct should be treated as immutable, and must be freed with
+///   `libra_shader_output_free`.
+libra_error_t libra_shader_source_get_text(const libra_shader_source_t *source,
+                                           struct libra_shader_output_t *out);
+
+/// Get the parameters declared in the shader, in the order they are declared.
+///
+/// ## Safety
+/// - `source` must be null or a valid and aligned pointer to a `libra_shader_source_t`.
+/// - `out` must be an aligned pointer to a `libra_preset_param_list_t`.
+/// - The output struct should be treated as immutable, and must be freed with
+///   `libra_preset_free_runtime_params`.
+libra_error_t libra_shader_source_get_params(const libra_shader_source_t *source,
+                                             struct libra_preset_param_list_t *out);
+
+/// Transpile the shader to the given target.
+///
+/// Text targets are returned as NUL-terminated strings. SPIR-V is returned as the raw module.
+///
+/// ## Safety
+/// - `source` must be null or a valid and aligned pointer to a `libra_shader_source_t`.
+/// - `options` must be either null, or valid and aligned.
+/// - `out` must be an aligned pointer to a `libra_shader_output_t`.
+/// - The output struct should be treated as immutable, and must be freed with
+///   `libra_shader_output_free`.
+/// ## Returns
+///  - If a version in `options` is not supported by the target, `out` is unchanged,
+///    and this function returns `LIBRA_ERR_INVALID_PARAMETER`.
+libra_error_t libra_shader_source_transpile(const libra_shader_source_t *source,
+                                            LIBRA_SHADER_TARGET target,
+                                            const struct transpile_opt_t *options,
+                                            struct libra_shader_output_t *out);
+
+/// Reflect a pass of the shader preset.
+///
+/// The reflection is written as NUL-terminated JSON, following the versioned pass reflection
+/// schema in `librashader-reflect/schema/pass-reflection.schema.json`. It describes the
+/// layout of the UBO and push constant block, the uniforms bound to each semantic and
+/// parameter, and the binding of each texture.
+///
+/// ## Safety
+/// - `preset` must be null or a valid and aligned pointer to a `libra_shader_preset_t`.
+/// - `out` must be an aligned pointer to a `libra_shader_blob_t`.
+/// - The output struct should be treated as immutable, and must be freed with
+///   `libra_shader_blob_free`.
+/// ## Returns
+///  - If `index` is not a pass of the preset, `out` is unchanged, and this function
+///    returns `LIBRA_ERR_INVALID_PARAMETER`.
+libra_error_t libra_preset_reflect_pass(const libra_shader_preset_t *preset,
+                                        uint32_t index,
+                                        struct libra_shader_blob_t *out);
+
+/// Free a buffer written by `libra_preset_reflect_pass`.
+///
+/// Like `libra_preset_free_runtime_params`, this function takes the struct directly.
+///
+/// ## Safety
+/// - `blob` must have been returned by `libra_preset_reflect_pass`, with its fields unchanged.
+/// - Accessing the data of the buffer after it has been freed is a use-after-free and is
+///   immediate undefined behaviour.
+libra_error_t libra_shader_blob_free(struct libra_shader_blob_t blob);
+
+/// Free the output of `libra_shader_source_get_text` or `libra_shader_source_transpile`.
+///
+/// Like `libra_preset_free_runtime_params`, this function takes the struct directly.
+///
+/// ## Safety
+/// - `output` must have been returned by `libra_shader_source_get_text` or
+///   `libra_shader_source_transpile`, with its fields unchanged.
+/// - Accessing the data of the output after it has been freed is a use-after-free and is
+///   immediate undefined behaviour.
+libra_error_t libra_shader_output_free(struct libra_shader_output_t output);
+
+/// Free the shader source.
+///
+/// If `source` is null, this function does nothing. The resulting value in `source` then becomes
+/// null.
+///
+/// ## Safety
+/// - `source` must be a valid and aligned pointer to a `libra_shader_source_t`.
+libra_error_t libra_shader_source_free(libra_shader_source_t *source);
+
+/// Free the parameters of a filter chain.
+///
+/// Like `libra_preset_free_runtime_params`, `libra_filter_chain_free_params`
+/// takes the struct directly. The caller must take care to maintain the lifetime
+/// of any pointers contained within the input `libra_filter_chain_param_list_t`.
+///
+/// ## Safety
+/// - Any pointers rooted at `parameters` becomes invalid after this function returns,
+///   including any strings accessible via the input `libra_filter_chain_param_list_t`.
+///   The caller must ensure that there are no live pointers, aliased or unaliased,
+///   to data accessible via the input `libra_filter_chain_param_list_t`.
+///
+/// - Accessing any data pointed to via the input `libra_filter_chain_param_list_t` after it
+///   has been freed is a use-after-free and is immediate undefined behaviour.
+///
+/// - If any struct fields of the input `libra_filter_chain_param_list_t` was modified from
+///   their values given after `*_filter_chain_get_params`, this may result
+///   in undefined behaviour.
+libra_error_t libra_filter_chain_free_params(struct libra_filter_chain_param_list_t list);
+
+/// Request that the creation of a filter chain be cancelled.
+///
+/// The job stops after the pass it is currently preprocessing or compiling, after which
+/// `*_filter_chain_job_finish` returns an error with the code `LIBRA_ERRNO_CANCELLED`.
+/// If the filter chain was already created, the job finishes as usual.
+///
+/// This function may be called from any thread, but must not be called concurrently with
+/// a function that frees the job.
+///
+/// ## Safety
+/// - `job` must be either null or a valid and aligned pointer to an initialized `libra_filter_chain_job_t`.
+libra_error_t libra_filter_chain_job_cancel(const libra_filter_chain_job_t *job);
+
+/// Get whether the creation of a filter chain has finished, either successfully or
+/// with an error.
+///
+/// If this returns true, `*_filter_chain_job_finish` will return without blocking.
+/// This function may be called from any thread, but must not be called concurrently with
+/// a function that frees the job.
+///
+/// ## Safety
+/// - `job` must be either null or a valid and aligned pointer to an initialized `libra_filter_chain_job_t`.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_filter_chain_job_is_finished(const libra_filter_chain_job_t *job, bool *out);
+
+/// Cancel and free a filter chain creation job.
+///
+/// This blocks until the worker threads stop after the pass they are currently preprocessing
+/// or compiling, then frees any filter chain that was created by the job. Commands that were
+/// recorded to the command buffer, command list, or deferred context of the job must be
+/// discarded.
+///
+/// The resulting value in `job` then becomes null.
+/// ## Safety
+/// - `job` must be either null or a valid and aligned pointer to an initialized `libra_filter_chain_job_t`.
+libra_error_t libra_filter_chain_job_free(libra_filter_chain_job_t *job);
+
 #if defined(LIBRA_RUNTIME_OPENGL)
 /// Create the filter chain given the shader preset.
 ///
@@ -1256,6 +2686,22 @@ libra_error_t libra_gl_filter_chain_create(libra_shader_preset_t *preset,
                                            libra_gl_filter_chain_t *out);
 #endif
 
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Create the filter chain given the shader preset pack.
+///
+/// The shader preset pack is immediately invalidated and must be recreated after
+/// the filter chain is created.
+///
+/// ## Safety:
+/// - `pack` must be either null, or valid and aligned.
+/// - `options` must be either null, or valid and aligned.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_gl_filter_chain_create_from_pack(libra_preset_pack_t *pack,
+                                                     libra_gl_loader_t loader,
+                                                     const struct filter_chain_gl_opt_t *options,
+                                                     libra_gl_filter_chain_t *out);
+#endif
+
 #if defined(LIBRA_RUNTIME_OPENGL)
 /// Draw a frame with the given parameters for the given filter chain.
 ///
@@ -1320,6 +2766,29 @@ libra_error_t libra_gl_filter_chain_get_param(const libra_gl_filter_chain_t *cha
                                               float *out);
 #endif
 
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Gets the metadata and current value of every parameter of the filter chain.
+///
+/// Parameters are listed in the order they are declared by the passes of the filter chain.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_gl_filter_chain_t`.
+/// - `out` must be an aligned pointer to a `libra_filter_chain_param_list_t`.
+/// - The output struct should be treated as immutable. Mutating any struct fields
+///   in the returned struct may at best cause memory leaks, and at worse
+///   cause undefined behaviour when later freed.
+/// - The output struct must be freed with `libra_filter_chain_free_params`.
+libra_error_t libra_gl_filter_chain_get_params(const libra_gl_filter_chain_t *chain,
+                                               struct libra_filter_chain_param_list_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Resets every parameter of the filter chain to the value it had when the filter chain was loaded.
+///
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_gl_filter_chain_t`.
+libra_error_t libra_gl_filter_chain_reset_params(libra_gl_filter_chain_t *chain);
+#endif
+
 #if defined(LIBRA_RUNTIME_OPENGL)
 /// Sets the number of active passes for this chain.
 ///
@@ -1392,29 +2861,122 @@ libra_error_t libra_vk_filter_chain_create_deferred(libra_shader_preset_t *prese
 #endif
 
 #if defined(LIBRA_RUNTIME_VULKAN)
-/// Records rendering commands for a frame with the given parameters for the given filter chain
-/// to the input command buffer.
+/// Create the filter chain given the shader preset on the librashader worker threads,
+/// deferring GPU-side initialization to the caller as with `libra_vk_filter_chain_create_deferred`.
 ///
-/// A pipeline barrier **will not** be created for the final pass. The output image must be
-/// in `VK_COLOR_ATTACHMENT_OPTIMAL`, and will remain so after all shader passes. The caller must transition
-/// the output image to the final layout.
+/// The passes of the shader preset are preprocessed and compiled without blocking the calling thread.
+/// `progress` is called with `userdata` as each pass is preprocessed and compiled, and may be null.
+/// The filter chain is retrieved from the resulting job with `libra_vk_filter_chain_job_finish`, or
+/// the job can be cancelled with `libra_filter_chain_job_cancel` and freed with `libra_filter_chain_job_free`.
 ///
-/// ## Parameters
+/// The shader preset is immediately invalidated and must be recreated after
+/// the job is created.
 ///
-/// - `chain` is a handle to the filter chain.
-/// - `command_buffer` is a `VkCommandBuffer` handle to record draw commands to.
-///    The provided command buffer must be ready for recording and contain no prior commands
-/// - `frame_count` is the number of frames passed to the shader
-/// - `image` is a `libra_image_vk_t`, containing a `VkImage` handle, it's format and size information,
-///    to an image that will serve as the source image for the frame. The input image must be in
-///    the `VK_SHADER_READ_ONLY_OPTIMAL` layout.
-/// - `out` is a `libra_image_vk_t`, containing a `VkImage` handle, it's format and size information,
-///    for the render target of the frame. The output image must be in `VK_COLOR_ATTACHMENT_OPTIMAL` layout.
-///    The output image will remain in `VK_COLOR_ATTACHMENT_OPTIMAL` after all shader passes.
+/// ## Safety:
+/// - The handles provided in `vulkan` must be valid for the command buffers that
+///   `libra_vk_filter_chain_frame` will write to.
+/// - `preset` must be either null, or valid and aligned.
+/// - `options` must be either null, or valid and aligned.
+/// - `progress` and `userdata` must be safe to use from any thread until the job is finished or freed.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
 ///
-/// - `viewport` is a pointer to a `libra_viewport_t` that specifies the area onto which scissor and viewport
-///    will be applied to the render target. It may be null, in which case a default viewport spanning the
-///    entire render target will be used.
+/// The provided command buffer must be ready for recording and contain no prior commands.
+/// The command buffer, and the command pool it was allocated from, must not be used until the
+/// job is finished or freed. After `libra_vk_filter_chain_job_finish` returns the filter chain,
+/// the caller is responsible for ending the command buffer and immediately submitting it to a
+/// graphics queue. The command buffer must be completely executed before calling `libra_vk_filter_chain_frame`.
+libra_error_t libra_vk_filter_chain_create_async(libra_shader_preset_t *preset,
+                                                 struct libra_device_vk_t vulkan,
+                                                 VkCommandBuffer command_buffer,
+                                                 const struct filter_chain_vk_opt_t *options,
+                                                 libra_load_progress_callback_t progress,
+                                                 void *userdata,
+                                                 libra_filter_chain_job_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Wait for the creation of a filter chain to finish, and retrieve the filter chain.
+///
+/// This blocks until the job created by `libra_vk_filter_chain_create_async` has finished.
+/// Once the job has finished, the resulting value in `job` becomes null, even if the filter
+/// chain could not be created. If the job was cancelled, the returned error has the code
+/// `LIBRA_ERRNO_CANCELLED`.
+///
+/// ## Safety
+/// - `job` must be either null or a valid and aligned pointer to a `libra_filter_chain_job_t`
+///   created by `libra_vk_filter_chain_create_async`.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_vk_filter_chain_job_finish(libra_filter_chain_job_t *job,
+                                               libra_vk_filter_chain_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Create the filter chain given the shader preset pack.
+///
+/// The shader preset pack is immediately invalidated and must be recreated after
+/// the filter chain is created.
+///
+/// ## Safety:
+/// - The handles provided in `vulkan` must be valid for the command buffers that
+///   `libra_vk_filter_chain_frame` will write to.
+///    created with the `VK_KHR_dynamic_rendering` extension.
+/// - `pack` must be either null, or valid and aligned.
+/// - `options` must be either null, or valid and aligned.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_vk_filter_chain_create_from_pack(libra_preset_pack_t *pack,
+                                                     struct libra_device_vk_t vulkan,
+                                                     const struct filter_chain_vk_opt_t *options,
+                                                     libra_vk_filter_chain_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Create the filter chain given the shader preset pack deferring and GPU-side initialization
+/// to the caller. This function therefore requires no external synchronization of the device queue.
+///
+/// The shader preset pack is immediately invalidated and must be recreated after
+/// the filter chain is created.
+///
+/// ## Safety:
+/// - The handles provided in `vulkan` must be valid for the command buffers that
+///   `libra_vk_filter_chain_frame` will write to.
+/// - `pack` must be either null, or valid and aligned.
+/// - `options` must be either null, or valid and aligned.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+///
+/// The provided command buffer must be ready for recording and contain no prior commands.
+/// The caller is responsible for ending the command buffer and immediately submitting it to a
+/// graphics queue. The command buffer must be completely executed before calling `libra_vk_filter_chain_frame`.
+libra_error_t libra_vk_filter_chain_create_from_pack_deferred(libra_preset_pack_t *pack,
+                                                              struct libra_device_vk_t vulkan,
+                                                              VkCommandBuffer command_buffer,
+                                                              const struct filter_chain_vk_opt_t *options,
+                                                              libra_vk_filter_chain_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Records rendering commands for a frame with the given parameters for the given filter chain
+/// to the input command buffer.
+///
+/// A pipeline barrier **will not** be created for the final pass. The output image must be
+/// in `VK_COLOR_ATTACHMENT_OPTIMAL`, and will remain so after all shader passes. The caller must transition
+/// the output image to the final layout.
+///
+/// ## Parameters
+///
+/// - `chain` is a handle to the filter chain.
+/// - `command_buffer` is a `VkCommandBuffer` handle to record draw commands to.
+///    The provided command buffer must be ready for recording and contain no prior commands
+/// - `frame_count` is the number of frames passed to the shader
+/// - `image` is a `libra_image_vk_t`, containing a `VkImage` handle, it's format and size information,
+///    to an image that will serve as the source image for the frame. The input image must be in
+///    the `VK_SHADER_READ_ONLY_OPTIMAL` layout.
+/// - `out` is a `libra_image_vk_t`, containing a `VkImage` handle, it's format and size information,
+///    for the render target of the frame. The output image must be in `VK_COLOR_ATTACHMENT_OPTIMAL` layout.
+///    The output image will remain in `VK_COLOR_ATTACHMENT_OPTIMAL` after all shader passes.
+///
+/// - `viewport` is a pointer to a `libra_viewport_t` that specifies the area onto which scissor and viewport
+///    will be applied to the render target. It may be null, in which case a default viewport spanning the
+///    entire render target will be used.
 /// - `mvp` is a pointer to an array of 16 `float` values to specify the model view projection matrix to
 ///    be passed to the shader.
 /// - `options` is a pointer to options for the frame. Valid options are dependent on the `LIBRASHADER_API_VERSION`
@@ -1465,6 +3027,29 @@ libra_error_t libra_vk_filter_chain_get_param(const libra_vk_filter_chain_t *cha
                                               float *out);
 #endif
 
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Gets the metadata and current value of every parameter of the filter chain.
+///
+/// Parameters are listed in the order they are declared by the passes of the filter chain.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_vk_filter_chain_t`.
+/// - `out` must be an aligned pointer to a `libra_filter_chain_param_list_t`.
+/// - The output struct should be treated as immutable. Mutating any struct fields
+///   in the returned struct may at best cause memory leaks, and at worse
+///   cause undefined behaviour when later freed.
+/// - The output struct must be freed with `libra_filter_chain_free_params`.
+libra_error_t libra_vk_filter_chain_get_params(const libra_vk_filter_chain_t *chain,
+                                               struct libra_filter_chain_param_list_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Resets every parameter of the filter chain to the value it had when the filter chain was loaded.
+///
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_vk_filter_chain_t`.
+libra_error_t libra_vk_filter_chain_reset_params(libra_vk_filter_chain_t *chain);
+#endif
+
 #if defined(LIBRA_RUNTIME_VULKAN)
 /// Sets the number of active passes for this chain.
 ///
@@ -1541,6 +3126,104 @@ libra_error_t libra_d3d11_filter_chain_create_deferred(libra_shader_preset_t *pr
                                                        libra_d3d11_filter_chain_t *out);
 #endif
 
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Create the filter chain given the shader preset on the librashader worker threads,
+/// deferring GPU-side initialization to the caller as with `libra_d3d11_filter_chain_create_deferred`.
+///
+/// The passes of the shader preset are preprocessed and compiled without blocking the calling thread.
+/// `progress` is called with `userdata` as each pass is preprocessed and compiled, and may be null.
+/// The filter chain is retrieved from the resulting job with `libra_d3d11_filter_chain_job_finish`, or
+/// the job can be cancelled with `libra_filter_chain_job_cancel` and freed with `libra_filter_chain_job_free`.
+///
+/// The shader preset is immediately invalidated and must be recreated after
+/// the job is created.
+///
+/// ## Safety:
+/// - `preset` must be either null, or valid and aligned.
+/// - `options` must be either null, or valid and aligned.
+/// - `device` must not be null, and must not have been created with `D3D11_CREATE_DEVICE_SINGLETHREADED`.
+/// - `device_context` must not be null, and must be a deferred context.
+/// - `progress` and `userdata` must be safe to use from any thread until the job is finished or freed.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+///
+/// The deferred context must be ready for command recording, have no prior commands recorded, and
+/// must not be used until the job is finished or freed. After `libra_d3d11_filter_chain_job_finish`
+/// returns the filter chain, the caller must immediately call [`FinishCommandList`](https://learn.microsoft.com/en-us/windows/win32/api/d3d11/nf-d3d11-id3d11devicecontext-finishcommandlist)
+/// and execute the command list on the immediate context, **before drawing frames**.
+libra_error_t libra_d3d11_filter_chain_create_async(libra_shader_preset_t *preset,
+                                                    ID3D11Device * device,
+                                                    ID3D11DeviceContext * device_context,
+                                                    const struct filter_chain_d3d11_opt_t *options,
+                                                    libra_load_progress_callback_t progress,
+                                                    void *userdata,
+                                                    libra_filter_chain_job_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Wait for the creation of a filter chain to finish, and retrieve the filter chain.
+///
+/// This blocks until the job created by `libra_d3d11_filter_chain_create_async` has finished.
+/// Once the job has finished, the resulting value in `job` becomes null, even if the filter
+/// chain could not be created. If the job was cancelled, the returned error has the code
+/// `LIBRA_ERRNO_CANCELLED`.
+///
+/// ## Safety
+/// - `job` must be either null or a valid and aligned pointer to a `libra_filter_chain_job_t`
+///   created by `libra_d3d11_filter_chain_create_async`.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_d3d11_filter_chain_job_finish(libra_filter_chain_job_t *job,
+                                                  libra_d3d11_filter_chain_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Create the filter chain given the shader preset pack.
+///
+/// The shader preset pack is immediately invalidated and must be recreated after
+/// the filter chain is created.
+///
+/// ## Safety:
+/// - `pack` must be either null, or valid and aligned.
+/// - `options` must be either null, or valid and aligned.
+/// - `device` must not be null.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_d3d11_filter_chain_create_from_pack(libra_preset_pack_t *pack,
+                                                        ID3D11Device * device,
+                                                        const struct filter_chain_d3d11_opt_t *options,
+                                                        libra_d3d11_filter_chain_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Create the filter chain given the shader preset pack, deferring and GPU-side initialization
+/// to the caller. This function is therefore requires no external synchronization of the
+/// immediate context, as long as the immediate context is not used as the input context,
+/// nor of the device, as long as the device is not single-threaded only.
+///
+/// The shader preset pack is immediately invalidated and must be recreated after
+/// the filter chain is created.
+///
+/// ## Safety:
+/// - `pack` must be either null, or valid and aligned.
+/// - `options` must be either null, or valid and aligned.
+/// - `device` must not be null.
+/// - `device_context` not be null.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+///
+/// The provided context must either be immediate, or immediately submitted after this function
+/// returns, **before drawing frames**, or lookup textures will fail to load and the filter chain
+/// will be in an invalid state.
+///
+/// If the context is deferred, it must be ready for command recording, and have no prior commands
+/// recorded. No commands shall be recorded after, the caller must immediately call [`FinishCommandList`](https://learn.microsoft.com/en-us/windows/win32/api/d3d11/nf-d3d11-id3d11devicecontext-finishcommandlist)
+/// and execute the command list on the immediate context after this function returns.
+///
+/// If the context is immediate, then access to the immediate context requires external synchronization.
+libra_error_t libra_d3d11_filter_chain_create_from_pack_deferred(libra_preset_pack_t *pack,
+                                                                 ID3D11Device * device,
+                                                                 ID3D11DeviceContext * device_context,
+                                                                 const struct filter_chain_d3d11_opt_t *options,
+                                                                 libra_d3d11_filter_chain_t *out);
+#endif
+
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
 /// Draw a frame with the given parameters for the given filter chain.
 ///
@@ -1613,6 +3296,29 @@ libra_error_t libra_d3d11_filter_chain_get_param(const libra_d3d11_filter_chain_
                                                  float *out);
 #endif
 
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Gets the metadata and current value of every parameter of the filter chain.
+///
+/// Parameters are listed in the order they are declared by the passes of the filter chain.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d11_filter_chain_t`.
+/// - `out` must be an aligned pointer to a `libra_filter_chain_param_list_t`.
+/// - The output struct should be treated as immutable. Mutating any struct fields
+///   in the returned struct may at best cause memory leaks, and at worse
+///   cause undefined behaviour when later freed.
+/// - The output struct must be freed with `libra_filter_chain_free_params`.
+libra_error_t libra_d3d11_filter_chain_get_params(const libra_d3d11_filter_chain_t *chain,
+                                                  struct libra_filter_chain_param_list_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Resets every parameter of the filter chain to the value it had when the filter chain was loaded.
+///
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d11_filter_chain_t`.
+libra_error_t libra_d3d11_filter_chain_reset_params(libra_d3d11_filter_chain_t *chain);
+#endif
+
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
 /// Sets the number of active passes for this chain.
 ///
@@ -1657,6 +3363,23 @@ libra_error_t libra_d3d9_filter_chain_create(libra_shader_preset_t *preset,
                                              libra_d3d9_filter_chain_t *out);
 #endif
 
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// Create the filter chain given the shader preset pack.
+///
+/// The shader preset pack is immediately invalidated and must be recreated after
+/// the filter chain is created.
+///
+/// ## Safety:
+/// - `pack` must be either null, or valid and aligned.
+/// - `options` must be either null, or valid and aligned.
+/// - `device` must not be null.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_d3d9_filter_chain_create_from_pack(libra_preset_pack_t *pack,
+                                                       IDirect3DDevice9 * device,
+                                                       const struct filter_chain_d3d9_opt_t *options,
+                                                       libra_d3d9_filter_chain_t *out);
+#endif
+
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
 /// Draw a frame with the given parameters for the given filter chain.
 ///
@@ -1719,6 +3442,29 @@ libra_error_t libra_d3d9_filter_chain_get_param(const libra_d3d9_filter_chain_t
                                                 float *out);
 #endif
 
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// Gets the metadata and current value of every parameter of the filter chain.
+///
+/// Parameters are listed in the order they are declared by the passes of the filter chain.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d9_filter_chain_t`.
+/// - `out` must be an aligned pointer to a `libra_filter_chain_param_list_t`.
+/// - The output struct should be treated as immutable. Mutating any struct fields
+///   in the returned struct may at best cause memory leaks, and at worse
+///   cause undefined behaviour when later freed.
+/// - The output struct must be freed with `libra_filter_chain_free_params`.
+libra_error_t libra_d3d9_filter_chain_get_params(const libra_d3d9_filter_chain_t *chain,
+                                                 struct libra_filter_chain_param_list_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// Resets every parameter of the filter chain to the value it had when the filter chain was loaded.
+///
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d9_filter_chain_t`.
+libra_error_t libra_d3d9_filter_chain_reset_params(libra_d3d9_filter_chain_t *chain);
+#endif
+
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
 /// Sets the number of active passes for this chain.
 ///
@@ -1787,6 +3533,97 @@ libra_error_t libra_d3d12_filter_chain_create_deferred(libra_shader_preset_t *pr
                                                        libra_d3d12_filter_chain_t *out);
 #endif
 
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Create the filter chain given the shader preset on the librashader worker threads,
+/// deferring GPU-side initialization to the caller as with `libra_d3d12_filter_chain_create_deferred`.
+///
+/// The passes of the shader preset are preprocessed and compiled without blocking the calling thread.
+/// `progress` is called with `userdata` as each pass is preprocessed and compiled, and may be null.
+/// The filter chain is retrieved from the resulting job with `libra_d3d12_filter_chain_job_finish`, or
+/// the job can be cancelled with `libra_filter_chain_job_cancel` and freed with `libra_filter_chain_job_free`.
+///
+/// The shader preset is immediately invalidated and must be recreated after
+/// the job is created.
+///
+/// ## Safety:
+/// - `preset` must be either null, or valid and aligned.
+/// - `options` must be either null, or valid and aligned.
+/// - `device` must not be null.
+/// - `command_list` must not be null.
+/// - `progress` and `userdata` must be safe to use from any thread until the job is finished or freed.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+///
+/// The provided command list must be ready for recording and contain no prior commands.
+/// The command list, and the command allocator it records to, must not be used until the
+/// job is finished or freed. After `libra_d3d12_filter_chain_job_finish` returns the filter chain,
+/// the caller is responsible for ending the command list and immediately submitting it to a
+/// graphics queue. The command list must be completely executed before calling `libra_d3d12_filter_chain_frame`.
+libra_error_t libra_d3d12_filter_chain_create_async(libra_shader_preset_t *preset,
+                                                    ID3D12Device * device,
+                                                    ID3D12GraphicsCommandList * command_list,
+                                                    const struct filter_chain_d3d12_opt_t *options,
+                                                    libra_load_progress_callback_t progress,
+                                                    void *userdata,
+                                                    libra_filter_chain_job_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Wait for the creation of a filter chain to finish, and retrieve the filter chain.
+///
+/// This blocks until the job created by `libra_d3d12_filter_chain_create_async` has finished.
+/// Once the job has finished, the resulting value in `job` becomes null, even if the filter
+/// chain could not be created. If the job was cancelled, the returned error has the code
+/// `LIBRA_ERRNO_CANCELLED`.
+///
+/// ## Safety
+/// - `job` must be either null or a valid and aligned pointer to a `libra_filter_chain_job_t`
+///   created by `libra_d3d12_filter_chain_create_async`.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_d3d12_filter_chain_job_finish(libra_filter_chain_job_t *job,
+                                                  libra_d3d12_filter_chain_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Create the filter chain given the shader preset pack.
+///
+/// The shader preset pack is immediately invalidated and must be recreated after
+/// the filter chain is created.
+///
+/// ## Safety:
+/// - `pack` must be either null, or valid and aligned.
+/// - `options` must be either null, or valid and aligned.
+/// - `device` must not be null.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_d3d12_filter_chain_create_from_pack(libra_preset_pack_t *pack,
+                                                        ID3D12Device * device,
+                                                        const struct filter_chain_d3d12_opt_t *options,
+                                                        libra_d3d12_filter_chain_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Create the filter chain given the shader preset pack deferring and GPU-side initialization
+/// to the caller. This function therefore requires no external synchronization of the device queue.
+///
+/// The shader preset pack is immediately invalidated and must be recreated after
+/// the filter chain is created.
+///
+/// ## Safety:
+/// - `pack` must be either null, or valid and aligned.
+/// - `options` must be either null, or valid and aligned.
+/// - `device` must not be null.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+/// - `cmd` must not be null.
+///
+/// The provided command list must be ready for recording and contain no prior commands.
+/// The caller is responsible for ending the command list and immediately submitting it to a
+/// graphics queue. The command list must be completely executed before calling `libra_d3d12_filter_chain_frame`.
+libra_error_t libra_d3d12_filter_chain_create_from_pack_deferred(libra_preset_pack_t *pack,
+                                                                 ID3D12Device * device,
+                                                                 ID3D12GraphicsCommandList * command_list,
+                                                                 const struct filter_chain_d3d12_opt_t *options,
+                                                                 libra_d3d12_filter_chain_t *out);
+#endif
+
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
 /// Records rendering commands for a frame with the given parameters for the given filter chain
 /// to the input command list.
@@ -1876,6 +3713,29 @@ libra_error_t libra_d3d12_filter_chain_get_param(const libra_d3d12_filter_chain_
                                                  float *out);
 #endif
 
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Gets the metadata and current value of every parameter of the filter chain.
+///
+/// Parameters are listed in the order they are declared by the passes of the filter chain.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d12_filter_chain_t`.
+/// - `out` must be an aligned pointer to a `libra_filter_chain_param_list_t`.
+/// - The output struct should be treated as immutable. Mutating any struct fields
+///   in the returned struct may at best cause memory leaks, and at worse
+///   cause undefined behaviour when later freed.
+/// - The output struct must be freed with `libra_filter_chain_free_params`.
+libra_error_t libra_d3d12_filter_chain_get_params(const libra_d3d12_filter_chain_t *chain,
+                                                  struct libra_filter_chain_param_list_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Resets every parameter of the filter chain to the value it had when the filter chain was loaded.
+///
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d12_filter_chain_t`.
+libra_error_t libra_d3d12_filter_chain_reset_params(libra_d3d12_filter_chain_t *chain);
+#endif
+
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
 /// Sets the number of active passes for this chain.
 ///
@@ -1948,6 +3808,51 @@ libra_error_t libra_mtl_filter_chain_create_deferred(libra_shader_preset_t *pres
                                                      libra_mtl_filter_chain_t *out);
 #endif
 
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Create the filter chain given the shader preset pack.
+///
+/// The shader preset pack is immediately invalidated and must be recreated after
+/// the filter chain is created.
+///
+/// ## Safety:
+/// - `queue` must be valid for the command buffers
+///     that `libra_mtl_filter_chain_frame` will write to.
+/// - `queue` must be a reference to a `id<MTLCommandQueue>`.
+/// - `pack` must be either null, or valid and aligned.
+/// - `options` must be either null, or valid and aligned.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_mtl_filter_chain_create_from_pack(libra_preset_pack_t *pack,
+                                                      id<MTLCommandQueue> queue,
+                                                      const struct filter_chain_mtl_opt_t *options,
+                                                      libra_mtl_filter_chain_t *out);
+#endif
+
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Create the filter chain given the shader preset pack deferring and GPU-side initialization
+/// to the caller. This function therefore requires no external synchronization of the device queue.
+///
+/// The shader preset pack is immediately invalidated and must be recreated after
+/// the filter chain is created.
+///
+/// ## Safety:
+/// - `queue` must be valid for the command buffers
+///     that `libra_mtl_filter_chain_frame` will write to.
+/// - `queue` must be a reference to a `id<MTLCommandQueue>`.
+/// - `command_buffer` must be a valid reference to a `MTLCommandBuffer` that is not already encoding.
+/// - `pack` must be either null, or valid and aligned.
+/// - `options` must be either null, or valid and aligned.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+///
+/// The provided command buffer must be ready for recording and contain no prior commands.
+/// The caller is responsible for ending the command buffer and immediately submitting it to a
+/// graphics queue. The command buffer must be completely executed before calling `libra_mtl_filter_chain_frame`.
+libra_error_t libra_mtl_filter_chain_create_from_pack_deferred(libra_preset_pack_t *pack,
+                                                               id<MTLCommandQueue> queue,
+                                                               id<MTLCommandBuffer> command_buffer,
+                                                               const struct filter_chain_mtl_opt_t *options,
+                                                               libra_mtl_filter_chain_t *out);
+#endif
+
 #if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
 /// Records rendering commands for a frame with the given parameters for the given filter chain
 /// to the input command buffer.
@@ -2012,6 +3917,29 @@ libra_error_t libra_mtl_filter_chain_get_param(const libra_mtl_filter_chain_t *c
                                                float *out);
 #endif
 
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Gets the metadata and current value of every parameter of the filter chain.
+///
+/// Parameters are listed in the order they are declared by the passes of the filter chain.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_mtl_filter_chain_t`.
+/// - `out` must be an aligned pointer to a `libra_filter_chain_param_list_t`.
+/// - The output struct should be treated as immutable. Mutating any struct fields
+///   in the returned struct may at best cause memory leaks, and at worse
+///   cause undefined behaviour when later freed.
+/// - The output struct must be freed with `libra_filter_chain_free_params`.
+libra_error_t libra_mtl_filter_chain_get_params(const libra_mtl_filter_chain_t *chain,
+                                                struct libra_filter_chain_param_list_t *out);
+#endif
+
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Resets every parameter of the filter chain to the value it had when the filter chain was loaded.
+///
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_mtl_filter_chain_t`.
+libra_error_t libra_mtl_filter_chain_reset_params(libra_mtl_filter_chain_t *chain);
+#endif
+
 #if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
 /// Sets the number of active passes for this chain.
 ///
@@ -2039,6 +3967,403 @@ libra_error_t libra_mtl_filter_chain_get_active_pass_count(const libra_mtl_filte
 libra_error_t libra_mtl_filter_chain_free(libra_mtl_filter_chain_t *chain);
 #endif
 
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Create the filter chain given the shader preset.
+///
+/// The shader preset is immediately invalidated and must be recreated after
+/// the filter chain is created.
+///
+/// ## Safety:
+/// - `device` and `queue` must be valid device and queue handles. The filter chain takes its
+///   own reference to both, so the caller keeps ownership of theirs.
+/// - `queue` must be a queue of `device`.
+/// - `preset` must be either null, or valid and aligned.
+/// - `options` must be either null, or valid and aligned.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_wgpu_filter_chain_create(libra_shader_preset_t *preset,
+                                             libra_wgpu_device_t device,
+                                             libra_wgpu_queue_t queue,
+                                             const struct filter_chain_wgpu_opt_t *options,
+                                             libra_wgpu_filter_chain_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Create the filter chain given the shader preset deferring and GPU-side initialization
+/// to the caller. This function therefore requires no external synchronization of the device queue.
+///
+/// The shader preset is immediately invalidated and must be recreated after
+/// the filter chain is created.
+///
+/// ## Safety:
+/// - `device` and `queue` must be valid device and queue handles. The filter chain takes its
+///   own reference to both, so the caller keeps ownership of theirs.
+/// - `queue` must be a queue of `device`.
+/// - `encoder` must be a valid command encoder handle of `device`.
+/// - `preset` must be either null, or valid and aligned.
+/// - `options` must be either null, or valid and aligned.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+///
+/// The provided command encoder must be ready for recording and contain no prior commands.
+/// The caller is responsible for immediately submitting the command encoder to `queue` with
+/// `libra_wgpu_queue_submit`. The command buffer must be completely executed before calling
+/// `libra_wgpu_filter_chain_frame`.
+libra_error_t libra_wgpu_filter_chain_create_deferred(libra_shader_preset_t *preset,
+                                                      libra_wgpu_device_t device,
+                                                      libra_wgpu_queue_t queue,
+                                                      libra_wgpu_command_encoder_t encoder,
+                                                      const struct filter_chain_wgpu_opt_t *options,
+                                                      libra_wgpu_filter_chain_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Create the filter chain given the shader preset on the librashader worker threads,
+/// deferring GPU-side initialization to the caller as with `libra_wgpu_filter_chain_create_deferred`.
+///
+/// The passes of the shader preset are preprocessed and compiled without blocking the calling thread.
+/// `progress` is called with `userdata` as each pass is preprocessed and compiled, and may be null.
+/// The filter chain is retrieved from the resulting job with `libra_wgpu_filter_chain_job_finish`, or
+/// the job can be cancelled with `libra_filter_chain_job_cancel` and freed with `libra_filter_chain_job_free`.
+///
+/// The shader preset is immediately invalidated and must be recreated after
+/// the job is created.
+///
+/// ## Safety:
+/// - `device` and `queue` must be valid device and queue handles. The filter chain takes its
+///   own reference to both, so the caller keeps ownership of theirs.
+/// - `queue` must be a queue of `device`.
+/// - `encoder` must be a valid command encoder handle of `device`.
+/// - `preset` must be either null, or valid and aligned.
+/// - `options` must be either null, or valid and aligned.
+/// - `progress` and `userdata` must be safe to use from any thread until the job is finished or freed.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+///
+/// The provided command encoder must be ready for recording and contain no prior commands.
+/// The command encoder must not be used or freed until the job is finished or freed. After
+/// `libra_wgpu_filter_chain_job_finish` returns the filter chain, the caller is responsible for
+/// immediately submitting the command encoder to `queue` with `libra_wgpu_queue_submit`. The
+/// command buffer must be completely executed before calling `libra_wgpu_filter_chain_frame`.
+libra_error_t libra_wgpu_filter_chain_create_async(libra_shader_preset_t *preset,
+                                                   libra_wgpu_device_t device,
+                                                   libra_wgpu_queue_t queue,
+                                                   libra_wgpu_command_encoder_t encoder,
+                                                   const struct filter_chain_wgpu_opt_t *options,
+                                                   libra_load_progress_callback_t progress,
+                                                   void *userdata,
+                                                   libra_filter_chain_job_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Wait for the creation of a filter chain to finish, and retrieve the filter chain.
+///
+/// This blocks until the job created by `libra_wgpu_filter_chain_create_async` has finished.
+/// Once the job has finished, the resulting value in `job` becomes null, even if the filter
+/// chain could not be created. If the job was cancelled, the returned error has the code
+/// `LIBRA_ERRNO_CANCELLED`.
+///
+/// ## Safety
+/// - `job` must be either null or a valid and aligned pointer to a `libra_filter_chain_job_t`
+///   created by `libra_wgpu_filter_chain_create_async`.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_wgpu_filter_chain_job_finish(libra_filter_chain_job_t *job,
+                                                 libra_wgpu_filter_chain_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Create the filter chain given the shader preset pack.
+///
+/// The shader preset pack is immediately invalidated and must be recreated after
+/// the filter chain is created.
+///
+/// ## Safety:
+/// - `device` and `queue` must be valid device and queue handles. The filter chain takes its
+///   own reference to both, so the caller keeps ownership of theirs.
+/// - `queue` must be a queue of `device`.
+/// - `pack` must be either null, or valid and aligned.
+/// - `options` must be either null, or valid and aligned.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_wgpu_filter_chain_create_from_pack(libra_preset_pack_t *pack,
+                                                       libra_wgpu_device_t device,
+                                                       libra_wgpu_queue_t queue,
+                                                       const struct filter_chain_wgpu_opt_t *options,
+                                                       libra_wgpu_filter_chain_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Create the filter chain given the shader preset pack deferring and GPU-side initialization
+/// to the caller. This function therefore requires no external synchronization of the device queue.
+///
+/// The shader preset pack is immediately invalidated and must be recreated after
+/// the filter chain is created.
+///
+/// ## Safety:
+/// - `device` and `queue` must be valid device and queue handles. The filter chain takes its
+///   own reference to both, so the caller keeps ownership of theirs.
+/// - `queue` must be a queue of `device`.
+/// - `encoder` must be a valid command encoder handle of `device`.
+/// - `pack` must be either null, or valid and aligned.
+/// - `options` must be either null, or valid and aligned.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+///
+/// The provided command encoder must be ready for recording and contain no prior commands.
+/// The caller is responsible for immediately submitting the command encoder to `queue` with
+/// `libra_wgpu_queue_submit`. The command buffer must be completely executed before calling
+/// `libra_wgpu_filter_chain_frame`.
+libra_error_t libra_wgpu_filter_chain_create_from_pack_deferred(libra_preset_pack_t *pack,
+                                                                libra_wgpu_device_t device,
+                                                                libra_wgpu_queue_t queue,
+                                                                libra_wgpu_command_encoder_t encoder,
+                                                                const struct filter_chain_wgpu_opt_t *options,
+                                                                libra_wgpu_filter_chain_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Records rendering commands for a frame with the given parameters for the given filter chain
+/// to the input command encoder.
+/// ## Parameters
+///
+/// - `chain` is a handle to the filter chain.
+/// - `encoder` is a command encoder handle to record draw commands to.
+/// - `frame_count` is the number of frames passed to the shader
+/// - `image` is a texture handle that will serve as the source image for the frame. It must have
+///    been created with the `TEXTURE_BINDING` usage.
+/// - `output` is a texture handle that is the render target of the frame. It must have
+///    been created with the `RENDER_ATTACHMENT` usage.
+///
+/// - `viewport` is a pointer to a `libra_viewport_t` that specifies the area onto which scissor and viewport
+///    will be applied to the render target. It may be null, in which case a default viewport spanning the
+///    entire render target will be used.
+/// - `mvp` is a pointer to an array of 16 `float` values to specify the model view projection matrix to
+///    be passed to the shader.
+/// - `options` is a pointer to options for the frame. Valid options are dependent on the `LIBRASHADER_API_VERSION`
+///    passed in. It may be null, in which case default options for the filter chain are used.
+///
+/// ## Safety
+/// - `encoder` must be a valid command encoder handle of the device the filter chain was created with.
+/// - `image` must be a valid texture handle. The filter chain may keep its own reference to the
+///    texture until the next frame.
+/// - `output` must be a valid texture handle.
+/// - `chain` may be null, invalid, but not uninitialized. If `chain` is null or invalid, this
+///    function will return an error.
+/// - `mvp` may be null, or if it is not null, must be an aligned pointer to 16 consecutive `float`
+///    values for the model view projection matrix.
+/// - `opt` may be null, or if it is not null, must be an aligned pointer to a valid `frame_wgpu_opt_t`
+///    struct.
+/// - You must ensure that only one thread has access to `chain` before you call this function. Only one
+///   thread at a time may call this function.
+libra_error_t libra_wgpu_filter_chain_frame(libra_wgpu_filter_chain_t *chain,
+                                            libra_wgpu_command_encoder_t encoder,
+                                            size_t frame_count,
+                                            libra_wgpu_texture_t image,
+                                            libra_wgpu_texture_t output,
+                                            const struct libra_viewport_t *viewport,
+                                            const float *mvp,
+                                            const struct frame_wgpu_opt_t *opt);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Sets a parameter for the filter chain.
+///
+/// If the parameter does not exist, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_wgpu_filter_chain_t`.
+/// - `param_name` must be either null or a null terminated string.
+libra_error_t libra_wgpu_filter_chain_set_param(libra_wgpu_filter_chain_t *chain,
+                                                const char *param_name,
+                                                float value);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Gets a parameter for the filter chain.
+///
+/// If the parameter does not exist, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_wgpu_filter_chain_t`.
+/// - `param_name` must be either null or a null terminated string.
+libra_error_t libra_wgpu_filter_chain_get_param(const libra_wgpu_filter_chain_t *chain,
+                                                const char *param_name,
+                                                float *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Gets the metadata and current value of every parameter of the filter chain.
+///
+/// Parameters are listed in the order they are declared by the passes of the filter chain.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_wgpu_filter_chain_t`.
+/// - `out` must be an aligned pointer to a `libra_filter_chain_param_list_t`.
+/// - The output struct should be treated as immutable. Mutating any struct fields
+///   in the returned struct may at best cause memory leaks, and at worse
+///   cause undefined behaviour when later freed.
+/// - The output struct must be freed with `libra_filter_chain_free_params`.
+libra_error_t libra_wgpu_filter_chain_get_params(const libra_wgpu_filter_chain_t *chain,
+                                                 struct libra_filter_chain_param_list_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Resets every parameter of the filter chain to the value it had when the filter chain was loaded.
+///
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_wgpu_filter_chain_t`.
+libra_error_t libra_wgpu_filter_chain_reset_params(libra_wgpu_filter_chain_t *chain);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Sets the number of active passes for this chain.
+///
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_wgpu_filter_chain_t`.
+libra_error_t libra_wgpu_filter_chain_set_active_pass_count(libra_wgpu_filter_chain_t *chain,
+                                                            uint32_t value);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Gets the number of active passes for this chain.
+///
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_wgpu_filter_chain_t`.
+libra_error_t libra_wgpu_filter_chain_get_active_pass_count(const libra_wgpu_filter_chain_t *chain,
+                                                            uint32_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Free a wgpu filter chain.
+///
+/// The resulting value in `chain` then becomes null.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_wgpu_filter_chain_t`.
+libra_error_t libra_wgpu_filter_chain_free(libra_wgpu_filter_chain_t *chain);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Create a command encoder to record filter chain commands to.
+///
+/// ## Safety
+/// - `device` must be a valid device handle.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_wgpu_command_encoder_create(libra_wgpu_device_t device,
+                                                libra_wgpu_command_encoder_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Finish recording the command encoder and submit its commands to the queue.
+///
+/// The command encoder is consumed, and the resulting value in `encoder` then becomes null.
+///
+/// ## Safety
+/// - `queue` must be a valid queue handle of the device the command encoder was created with.
+/// - `encoder` must be either null or a valid and aligned pointer to a command encoder handle.
+libra_error_t libra_wgpu_queue_submit(libra_wgpu_queue_t queue,
+                                      libra_wgpu_command_encoder_t *encoder);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Free a command encoder without submitting its commands.
+///
+/// The resulting value in `encoder` then becomes null.
+/// ## Safety
+/// - `encoder` must be either null or a valid and aligned pointer to a command encoder handle.
+libra_error_t libra_wgpu_command_encoder_free(libra_wgpu_command_encoder_t *encoder);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Free a device handle.
+///
+/// Filter chains created with the device keep their own reference to it, so the device
+/// handle may be freed before them. The resulting value in `device` then becomes null.
+/// ## Safety
+/// - `device` must be either null or a valid and aligned pointer to a device handle.
+libra_error_t libra_wgpu_device_free(libra_wgpu_device_t *device);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Free a queue handle.
+///
+/// Filter chains created with the queue keep their own reference to it, so the queue
+/// handle may be freed before them. The resulting value in `queue` then becomes null.
+/// ## Safety
+/// - `queue` must be either null or a valid and aligned pointer to a queue handle.
+libra_error_t libra_wgpu_queue_free(libra_wgpu_queue_t *queue);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+/// Free a texture handle.
+///
+/// A filter chain may keep its own reference to its last input texture until the next frame.
+/// The image the texture was created over must outlive every reference to the texture.
+/// The resulting value in `texture` then becomes null.
+/// ## Safety
+/// - `texture` must be either null or a valid and aligned pointer to a texture handle.
+libra_error_t libra_wgpu_texture_free(libra_wgpu_texture_t *texture);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU_VULKAN)
+/// Create a Vulkan device that a wgpu device can be created over with `libra_wgpu_device_create_vk`.
+///
+/// The device is created from `vulkan.physical_device` with the extensions and features that wgpu
+/// requires, the extensions in `vulkan.device_extensions`, and `vulkan.queue_index + 1` queues of
+/// `vulkan.queue_family_index`. `vulkan.device` is ignored.
+///
+/// The caller owns the device, and must destroy it with `vkDestroyDevice` after every wgpu object
+/// created over it is freed.
+///
+/// ## Safety
+/// - `vulkan.entry` must be the `vkGetInstanceProcAddr` of the Vulkan library the instance was created with.
+/// - `vulkan.instance` must have been created with `vulkan.instance_api_version` and the extensions
+///   in `vulkan.instance_extensions`.
+/// - `vulkan.instance_extensions` and `vulkan.device_extensions` must be null if their count is zero,
+///   or point to that many null-terminated strings.
+/// - `vulkan.queue_family_index` must be a graphics queue family of the physical device.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_wgpu_vk_create_device(struct libra_wgpu_vk_device_t vulkan,
+                                          VkDevice *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU_VULKAN)
+/// Create a wgpu device and queue over a Vulkan device.
+///
+/// Commands submitted to the queue are submitted to the Vulkan queue at `queue_index` of `queue_family_index`.
+/// The Vulkan instance and device are not destroyed when the wgpu device is freed, and must outlive
+/// the wgpu device, its queue, and every object created with them, including filter chains.
+///
+/// The device and queue are freed with `libra_wgpu_device_free` and `libra_wgpu_queue_free`.
+///
+/// ## Safety
+/// - `vulkan.entry` must be the `vkGetInstanceProcAddr` of the Vulkan library the instance was created with.
+/// - `vulkan.instance` must have been created with `vulkan.instance_api_version` and the extensions
+///   in `vulkan.instance_extensions`.
+/// - `vulkan.device` must have been created by `libra_wgpu_vk_create_device` with the same instance,
+///   physical device, device extensions, and queue as `vulkan`.
+/// - `vulkan.instance_extensions` and `vulkan.device_extensions` must be null if their count is zero,
+///   or point to that many null-terminated strings.
+/// - Access to the Vulkan queue must be externally synchronized with the queue of the wgpu device.
+/// - `out_device` and `out_queue` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_wgpu_device_create_vk(struct libra_wgpu_vk_device_t vulkan,
+                                          libra_wgpu_device_t *out_device,
+                                          libra_wgpu_queue_t *out_queue);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU_VULKAN)
+/// Create a wgpu texture over a Vulkan image.
+///
+/// The image is not destroyed when the texture is freed, and must outlive the texture. A filter
+/// chain may keep its own reference to its input texture until the next frame.
+///
+/// wgpu does not know the layout of the image, and transitions it from `VK_IMAGE_LAYOUT_UNDEFINED`
+/// the first time the texture is used, which may discard the contents of the image. After commands
+/// using the texture are submitted, the image is left in the layout of its last use by wgpu.
+///
+/// The texture is freed with `libra_wgpu_texture_free`.
+///
+/// ## Safety
+/// - `device` must be a device handle created with `libra_wgpu_device_create_vk`.
+/// - `image.handle` must be a `VkImage` of the Vulkan device, created with a single mip level and
+///   array layer, and with `image.format`, `image.width`, `image.height`, and `image.usage`.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_wgpu_texture_create_vk(libra_wgpu_device_t device,
+                                           struct libra_wgpu_vk_image_t image,
+                                           libra_wgpu_texture_t *out);
+#endif
+
 /// Get the ABI version of the loaded instance.
 LIBRASHADER_ABI_VERSION libra_instance_abi_version(void);
 
@@ -41,6 +41,8 @@ CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 // #if (defined(__APPLE__) && defined(__OBJC__))
 // #define LIBRA_RUNTIME_METAL
 // #endif
+// #define LIBRA_RUNTIME_WGPU
+// #define LIBRA_RUNTIME_WGPU_VULKAN
 
 #if defined(_WIN32)
 #include <windows.h>
@@ -97,6 +99,42 @@ int32_t __librashader__noop_error_write(libra_error_t error, char **out) {
 
 int32_t __librashader__noop_error_free_string(char **out) { return 1; }
 
+int32_t __librashader__noop_error_get_details(
+    libra_error_t error, struct libra_error_details_t *out) {
+    return 1;
+}
+
+int32_t __librashader__noop_error_free_details(
+    struct libra_error_details_t *details) {
+    return 1;
+}
+
+libra_error_t __librashader__noop_set_log_callback(
+    LIBRA_LOG_LEVEL level, libra_log_callback_t callback, void *userdata) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_filter_chain_free_params(
+    struct libra_filter_chain_param_list_t list) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_filter_chain_job_cancel(
+    const libra_filter_chain_job_t *job) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_filter_chain_job_is_finished(
+    const libra_filter_chain_job_t *job, bool *out) {
+    *out = false;
+    return NULL;
+}
+
+libra_error_t __librashader__noop_filter_chain_job_free(
+    libra_filter_chain_job_t *job) {
+    return NULL;
+}
+
 libra_error_t __librashader__noop_preset_ctx_create(libra_preset_ctx_t *out) {
     *out = NULL;
     return NULL;
@@ -194,6 +232,77 @@ libra_error_t __librashader__noop_preset_free_runtime_params(
     struct libra_preset_param_list_t out) {
     return NULL;
 }
+
+libra_error_t __librashader__noop_preset_pack_create_from_preset(
+    libra_shader_preset_t *preset, libra_preset_pack_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
+libra_error_t __librashader__noop_preset_pack_load(const uint8_t *buffer,
+                                                   size_t length,
+                                                   libra_preset_pack_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
+libra_error_t __librashader__noop_preset_pack_write(
+    const libra_preset_pack_t *pack, struct libra_preset_pack_buffer_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_preset_pack_free_buffer(
+    struct libra_preset_pack_buffer_t buffer) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_preset_pack_free(libra_preset_pack_t *pack) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_shader_source_create(
+    const char *filename, libra_shader_source_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
+libra_error_t __librashader__noop_shader_source_get_text(
+    const libra_shader_source_t *source, struct libra_shader_output_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_shader_source_get_params(
+    const libra_shader_source_t *source,
+    struct libra_preset_param_list_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_shader_source_transpile(
+    const libra_shader_source_t *source, LIBRA_SHADER_TARGET target,
+    const struct transpile_opt_t *options, struct libra_shader_output_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_shader_source_free(
+    libra_shader_source_t *source) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_shader_output_free(
+    struct libra_shader_output_t output) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_preset_reflect_pass(
+    const libra_shader_preset_t *preset, uint32_t index,
+    struct libra_shader_blob_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_shader_blob_free(
+    struct libra_shader_blob_t blob) {
+    return NULL;
+}
 #if defined(LIBRA_RUNTIME_OPENGL)
 libra_error_t __librashader__noop_gl_filter_chain_create(
     libra_shader_preset_t *preset, libra_gl_loader_t loader,
@@ -203,6 +312,14 @@ libra_error_t __librashader__noop_gl_filter_chain_create(
     return NULL;
 }
 
+libra_error_t __librashader__noop_gl_filter_chain_create_from_pack(
+    libra_preset_pack_t *pack, libra_gl_loader_t loader,
+    const struct filter_chain_gl_opt_t *options,
+    libra_gl_filter_chain_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
 libra_error_t __librashader__noop_gl_filter_chain_frame(
     libra_gl_filter_chain_t *chain, size_t frame_count,
     struct libra_image_gl_t image, struct libra_image_gl_t out,
@@ -226,6 +343,17 @@ libra_error_t __librashader__noop_gl_filter_chain_get_param(
     return NULL;
 }
 
+libra_error_t __librashader__noop_gl_filter_chain_get_params(
+    const libra_gl_filter_chain_t *chain,
+    struct libra_filter_chain_param_list_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_gl_filter_chain_reset_params(
+    libra_gl_filter_chain_t *chain) {
+    return NULL;
+}
+
 libra_error_t __librashader__noop_gl_filter_chain_set_active_pass_count(
     libra_gl_filter_chain_t *chain, uint32_t value) {
     return NULL;
@@ -253,6 +381,36 @@ libra_error_t __librashader__noop_vk_filter_chain_create_deferred(
     return NULL;
 }
 
+libra_error_t __librashader__noop_vk_filter_chain_create_async(
+    libra_shader_preset_t *preset, struct libra_device_vk_t vulkan,
+    VkCommandBuffer command_buffer, const struct filter_chain_vk_opt_t *options,
+    libra_load_progress_callback_t progress, void *userdata,
+    libra_filter_chain_job_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
+libra_error_t __librashader__noop_vk_filter_chain_job_finish(
+    libra_filter_chain_job_t *job, libra_vk_filter_chain_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
+libra_error_t __librashader__noop_vk_filter_chain_create_from_pack(
+    libra_preset_pack_t *pack, struct libra_device_vk_t vulkan,
+    const struct filter_chain_vk_opt_t *options, libra_vk_filter_chain_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
+libra_error_t __librashader__noop_vk_filter_chain_create_from_pack_deferred(
+    libra_preset_pack_t *pack, struct libra_device_vk_t vulkan,
+    VkCommandBuffer command_buffer, const struct filter_chain_vk_opt_t *options,
+    libra_vk_filter_chain_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
 libra_error_t __librashader__noop_vk_filter_chain_frame(
     libra_vk_filter_chain_t *chain, VkCommandBuffer command_buffer,
     size_t frame_count, struct libra_image_vk_t image, struct libra_image_vk_t out,
@@ -276,6 +434,17 @@ libra_error_t __librashader__noop_vk_filter_chain_get_param(
     return NULL;
 }
 
+libra_error_t __librashader__noop_vk_filter_chain_get_params(
+    const libra_vk_filter_chain_t *chain,
+    struct libra_filter_chain_param_list_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_vk_filter_chain_reset_params(
+    libra_vk_filter_chain_t *chain) {
+    return NULL;
+}
+
 libra_error_t __librashader__noop_vk_filter_chain_set_active_pass_count(
     libra_vk_filter_chain_t *chain, uint32_t value) {
     return NULL;
@@ -305,6 +474,39 @@ libra_error_t __librashader__noop_d3d11_filter_chain_create_deferred(
     return NULL;
 }
 
+libra_error_t __librashader__noop_d3d11_filter_chain_create_async(
+    libra_shader_preset_t *preset, ID3D11Device *device,
+    ID3D11DeviceContext *device_context,
+    const struct filter_chain_d3d11_opt_t *options,
+    libra_load_progress_callback_t progress, void *userdata,
+    libra_filter_chain_job_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d11_filter_chain_job_finish(
+    libra_filter_chain_job_t *job, libra_d3d11_filter_chain_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d11_filter_chain_create_from_pack(
+    libra_preset_pack_t *pack, ID3D11Device *device,
+    const struct filter_chain_d3d11_opt_t *options,
+    libra_d3d11_filter_chain_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d11_filter_chain_create_from_pack_deferred(
+    libra_preset_pack_t *pack, ID3D11Device *device,
+    ID3D11DeviceContext *device_context,
+    const struct filter_chain_d3d11_opt_t *options,
+    libra_d3d11_filter_chain_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
 libra_error_t __librashader__noop_d3d11_filter_chain_frame(
     libra_d3d11_filter_chain_t *chain, ID3D11DeviceContext *device_context,
     size_t frame_count, ID3D11ShaderResourceView *image, ID3D11RenderTargetView *out,
@@ -328,6 +530,17 @@ libra_error_t __librashader__noop_d3d11_filter_chain_get_param(
     return NULL;
 }
 
+libra_error_t __librashader__noop_d3d11_filter_chain_get_params(
+    const libra_d3d11_filter_chain_t *chain,
+    struct libra_filter_chain_param_list_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d11_filter_chain_reset_params(
+    libra_d3d11_filter_chain_t *chain) {
+    return NULL;
+}
+
 libra_error_t __librashader__noop_d3d11_filter_chain_set_active_pass_count(
     libra_d3d11_filter_chain_t *chain, uint32_t value) {
     return NULL;
@@ -357,6 +570,39 @@ libra_error_t __librashader__noop_d3d12_filter_chain_create_deferred(
     return NULL;
 }
 
+libra_error_t __librashader__noop_d3d12_filter_chain_create_async(
+    libra_shader_preset_t *preset, ID3D12Device *device,
+    ID3D12GraphicsCommandList *command_list,
+    const struct filter_chain_d3d12_opt_t *options,
+    libra_load_progress_callback_t progress, void *userdata,
+    libra_filter_chain_job_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d12_filter_chain_job_finish(
+    libra_filter_chain_job_t *job, libra_d3d12_filter_chain_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d12_filter_chain_create_from_pack(
+    libra_preset_pack_t *pack, ID3D12Device *device,
+    const struct filter_chain_d3d12_opt_t *options,
+    libra_d3d12_filter_chain_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d12_filter_chain_create_from_pack_deferred(
+    libra_preset_pack_t *pack, ID3D12Device *device,
+    ID3D12GraphicsCommandList *command_list,
+    const struct filter_chain_d3d12_opt_t *options,
+    libra_d3d12_filter_chain_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
 libra_error_t __librashader__noop_d3d12_filter_chain_frame(
     libra_d3d12_filter_chain_t *chain, ID3D12GraphicsCommandList *command_list,
     size_t frame_count, struct libra_image_d3d12_t image, struct libra_image_d3d12_t out,
@@ -380,6 +626,17 @@ libra_error_t __librashader__noop_d3d12_filter_chain_get_param(
     return NULL;
 }
 
+libra_error_t __librashader__noop_d3d12_filter_chain_get_params(
+    const libra_d3d12_filter_chain_t *chain,
+    struct libra_filter_chain_param_list_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d12_filter_chain_reset_params(
+    libra_d3d12_filter_chain_t *chain) {
+    return NULL;
+}
+
 libra_error_t __librashader__noop_d3d12_filter_chain_set_active_pass_count(
     libra_d3d12_filter_chain_t *chain, uint32_t value) {
     return NULL;
@@ -400,6 +657,14 @@ libra_error_t __librashader__noop_d3d9_filter_chain_create(
     return NULL;
 }
 
+libra_error_t __librashader__noop_d3d9_filter_chain_create_from_pack(
+    libra_preset_pack_t *pack, IDirect3DDevice9 *device,
+    const struct filter_chain_d3d9_opt_t *options,
+    libra_d3d9_filter_chain_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
 libra_error_t __librashader__noop_d3d9_filter_chain_frame(
     libra_d3d9_filter_chain_t *chain, size_t frame_count,
     IDirect3DTexture9 *image, IDirect3DSurface9 * out,
@@ -423,6 +688,17 @@ libra_error_t __librashader__noop_d3d9_filter_chain_get_param(
     return NULL;
 }
 
+libra_error_t __librashader__noop_d3d9_filter_chain_get_params(
+    const libra_d3d9_filter_chain_t *chain,
+    struct libra_filter_chain_param_list_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d9_filter_chain_reset_params(
+    libra_d3d9_filter_chain_t *chain) {
+    return NULL;
+}
+
 libra_error_t __librashader__noop_d3d9_filter_chain_set_active_pass_count(
     libra_d3d9_filter_chain_t *chain, uint32_t value) {
     return NULL;
@@ -452,6 +728,23 @@ libra_error_t __librashader__noop_mtl_filter_chain_create_deferred(
     return NULL;
 }
 
+libra_error_t __librashader__noop_mtl_filter_chain_create_from_pack(
+    libra_preset_pack_t *pack, id<MTLCommandQueue> queue,
+    const struct filter_chain_mtl_opt_t *options,
+    libra_mtl_filter_chain_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
+libra_error_t __librashader__noop_mtl_filter_chain_create_from_pack_deferred(
+    libra_preset_pack_t *pack, id<MTLCommandQueue> queue,
+    id<MTLCommandBuffer> command_buffer,
+    const struct filter_chain_mtl_opt_t *options,
+    libra_mtl_filter_chain_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
 libra_error_t __librashader__noop_mtl_filter_chain_frame(
     libra_mtl_filter_chain_t *chain, id<MTLCommandBuffer> command_buffer,
     size_t frame_count, id<MTLTexture> image, id<MTLTexture> output,
@@ -475,6 +768,17 @@ libra_error_t __librashader__noop_mtl_filter_chain_get_param(
     return NULL;
 }
 
+libra_error_t __librashader__noop_mtl_filter_chain_get_params(
+    const libra_mtl_filter_chain_t *chain,
+    struct libra_filter_chain_param_list_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_mtl_filter_chain_reset_params(
+    libra_mtl_filter_chain_t *chain) {
+    return NULL;
+}
+
 libra_error_t __librashader__noop_mtl_filter_chain_set_active_pass_count(
     libra_mtl_filter_chain_t *chain, uint32_t value) {
     return NULL;
@@ -486,6 +790,160 @@ libra_error_t __librashader__noop_mtl_filter_chain_get_active_pass_count(
 }
 #endif
 
+#if defined(LIBRA_RUNTIME_WGPU)
+libra_error_t __librashader__noop_wgpu_filter_chain_create(
+    libra_shader_preset_t *preset,
+    libra_wgpu_device_t device, libra_wgpu_queue_t queue,
+    const struct filter_chain_wgpu_opt_t *options,
+    libra_wgpu_filter_chain_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
+libra_error_t __librashader__noop_wgpu_filter_chain_create_deferred(
+    libra_shader_preset_t *preset,
+    libra_wgpu_device_t device, libra_wgpu_queue_t queue,
+    libra_wgpu_command_encoder_t encoder,
+    const struct filter_chain_wgpu_opt_t *options,
+    libra_wgpu_filter_chain_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
+libra_error_t __librashader__noop_wgpu_filter_chain_create_async(
+    libra_shader_preset_t *preset,
+    libra_wgpu_device_t device, libra_wgpu_queue_t queue,
+    libra_wgpu_command_encoder_t encoder,
+    const struct filter_chain_wgpu_opt_t *options,
+    libra_load_progress_callback_t progress, void *userdata,
+    libra_filter_chain_job_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
+libra_error_t __librashader__noop_wgpu_filter_chain_job_finish(
+    libra_filter_chain_job_t *job, libra_wgpu_filter_chain_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
+libra_error_t __librashader__noop_wgpu_filter_chain_create_from_pack(
+    libra_preset_pack_t *pack,
+    libra_wgpu_device_t device, libra_wgpu_queue_t queue,
+    const struct filter_chain_wgpu_opt_t *options,
+    libra_wgpu_filter_chain_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
+libra_error_t __librashader__noop_wgpu_filter_chain_create_from_pack_deferred(
+    libra_preset_pack_t *pack,
+    libra_wgpu_device_t device, libra_wgpu_queue_t queue,
+    libra_wgpu_command_encoder_t encoder,
+    const struct filter_chain_wgpu_opt_t *options,
+    libra_wgpu_filter_chain_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
+libra_error_t __librashader__noop_wgpu_filter_chain_frame(
+    libra_wgpu_filter_chain_t *chain, libra_wgpu_command_encoder_t encoder,
+    size_t frame_count, libra_wgpu_texture_t image, libra_wgpu_texture_t output,
+    const struct libra_viewport_t *viewport, const float *mvp,
+    const struct frame_wgpu_opt_t *opt) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_wgpu_filter_chain_free(
+    libra_wgpu_filter_chain_t *chain) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_wgpu_filter_chain_set_param(
+    libra_wgpu_filter_chain_t *chain, const char *param_name, float value) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_wgpu_filter_chain_get_param(
+    const libra_wgpu_filter_chain_t *chain, const char *param_name, float *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_wgpu_filter_chain_get_params(
+    const libra_wgpu_filter_chain_t *chain,
+    struct libra_filter_chain_param_list_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_wgpu_filter_chain_reset_params(
+    libra_wgpu_filter_chain_t *chain) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_wgpu_filter_chain_set_active_pass_count(
+    libra_wgpu_filter_chain_t *chain, uint32_t value) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_wgpu_filter_chain_get_active_pass_count(
+    const libra_wgpu_filter_chain_t *chain, uint32_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_wgpu_command_encoder_create(
+    libra_wgpu_device_t device, libra_wgpu_command_encoder_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
+libra_error_t __librashader__noop_wgpu_queue_submit(
+    libra_wgpu_queue_t queue, libra_wgpu_command_encoder_t *encoder) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_wgpu_command_encoder_free(
+    libra_wgpu_command_encoder_t *encoder) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_wgpu_device_free(
+    libra_wgpu_device_t *device) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_wgpu_queue_free(libra_wgpu_queue_t *queue) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_wgpu_texture_free(
+    libra_wgpu_texture_t *texture) {
+    return NULL;
+}
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU_VULKAN)
+libra_error_t __librashader__noop_wgpu_vk_create_device(
+    struct libra_wgpu_vk_device_t vulkan, VkDevice *out) {
+    *out = VK_NULL_HANDLE;
+    return NULL;
+}
+
+libra_error_t __librashader__noop_wgpu_device_create_vk(
+    struct libra_wgpu_vk_device_t vulkan, libra_wgpu_device_t *out_device,
+    libra_wgpu_queue_t *out_queue) {
+    *out_device = NULL;
+    *out_queue = NULL;
+    return NULL;
+}
+
+libra_error_t __librashader__noop_wgpu_texture_create_vk(
+    libra_wgpu_device_t device, struct libra_wgpu_vk_image_t image,
+    libra_wgpu_texture_t *out) {
+    *out = NULL;
+    return NULL;
+}
+#endif
+
 typedef struct libra_instance_t {
     /// Get the supported ABI version of the loaded instance.
     ///
@@ -737,23 +1195,169 @@ typedef struct libra_instance_t {
     /// contained within the input `libra_preset_param_list_t`.
     ///
     /// ## Safety
-    /// - Any pointers rooted at `parameters` becomes invalid after this
-    /// function returns,
-    ///   including any strings accessible via the input
-    ///   `libra_preset_param_list_t`. The caller must ensure that there are no
-    ///   live pointers, aliased or unaliased, to data accessible via the input
-    ///   `libra_preset_param_list_t`.
-    ///
-    /// - Accessing any data pointed to via the input
-    /// `libra_preset_param_list_t` after it
-    ///   has been freed is a use-after-free and is immediate undefined
-    ///   behaviour.
+    /// - Any pointers rooted at `parameters` becomes invalid after this
+    /// function returns,
+    ///   including any strings accessible via the input
+    ///   `libra_preset_param_list_t`. The caller must ensure that there are no
+    ///   live pointers, aliased or unaliased, to data accessible via the input
+    ///   `libra_preset_param_list_t`.
+    ///
+    /// - Accessing any data pointed to via the input
+    /// `libra_preset_param_list_t` after it
+    ///   has been freed is a use-after-free and is immediate undefined
+    ///   behaviour.
+    ///
+    /// - If any struct fields of the input `libra_preset_param_list_t` was
+    /// modified from
+    ///   their values given after `libra_preset_get_runtime_params`, this may
+    ///   result in undefined behaviour.
+    PFN_libra_preset_free_runtime_params preset_free_runtime_params;
+
+    /// Load the passes and lookup textures of a shader preset into a shader
+    /// preset pack.
+    ///
+    /// The shader preset is immediately invalidated and must be recreated
+    /// after the pack is created.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null. If this function returns an error, the state of `out` is
+    /// unspecified.
+    ///
+    /// ## Safety
+    ///  - `preset` must be either null, or valid and aligned.
+    ///  - `out` must be either null, or an aligned pointer to an uninitialized
+    ///  or invalid `libra_preset_pack_t`.
+    PFN_libra_preset_pack_create_from_preset preset_pack_create_from_preset;
+
+    /// Load a shader preset pack from a buffer in memory.
+    ///
+    /// The buffer must contain a pack serialized as MessagePack, such as one
+    /// written by `libra_preset_pack_write`. The buffer is not retained after
+    /// this function returns.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null. If this function returns an error, the state of `out` is
+    /// unspecified.
+    ///
+    /// ## Safety
+    ///  - `buffer` must be either null, or a valid, aligned pointer to at least
+    ///  `length` bytes.
+    ///  - `out` must be either null, or an aligned pointer to an uninitialized
+    ///  or invalid `libra_preset_pack_t`.
+    PFN_libra_preset_pack_load preset_pack_load;
+
+    /// Serialize the shader preset pack into a buffer as MessagePack.
+    ///
+    /// The pack is not invalidated, and can still be used to create a filter
+    /// chain.
+    ///
+    /// ## Safety
+    /// - `pack` must be null or a valid and aligned pointer to a
+    /// `libra_preset_pack_t`.
+    /// - `out` must be an aligned pointer to a `libra_preset_pack_buffer_t`.
+    /// - The output buffer must be freed with `libra_preset_pack_free_buffer`.
+    PFN_libra_preset_pack_write preset_pack_write;
+
+    /// Free a buffer previously written by `libra_preset_pack_write`.
+    ///
+    /// ## Safety
+    /// - `buffer` must have been returned by `libra_preset_pack_write`, with
+    /// its fields unchanged.
+    PFN_libra_preset_pack_free_buffer preset_pack_free_buffer;
+
+    /// Free the shader preset pack.
+    ///
+    /// If `pack` is null, this function does nothing. The resulting value in
+    /// `pack` then becomes null.
+    ///
+    /// ## Safety
+    /// - `pack` must be a valid and aligned pointer to a `libra_preset_pack_t`.
+    PFN_libra_preset_pack_free preset_pack_free;
+
+    /// Load and preprocess a shader source file.
+    ///
+    /// `#include` directives are resolved relative to the location of the
+    /// shader.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null. If this function returns an error, the state of `out` is
+    /// unspecified.
+    ///
+    /// ## Safety
+    ///  - `filename` must be either null or a valid, aligned pointer to a
+    ///  string path to the shader.
+    ///  - `out` must be either null, or an aligned pointer to an uninitialized
+    ///  or invalid `libra_shader_source_t`.
+    PFN_libra_shader_source_create shader_source_create;
+
+    /// Get the preprocessed GLSL text of the vertex and fragment stages of
+    /// the shader.
+    ///
+    /// ## Safety
+    /// - `source` must be null or a valid and aligned pointer to a
+    /// `libra_shader_source_t`.
+    /// - `out` must be an aligned pointer to a `libra_shader_output_t`.
+    /// - The output struct must be freed with `libra_shader_output_free`.
+    PFN_libra_shader_source_get_text shader_source_get_text;
+
+    /// Get the parameters declared in the shader, in the order they are
+    /// declared.
+    ///
+    /// ## Safety
+    /// - `source` must be null or a valid and aligned pointer to a
+    /// `libra_shader_source_t`.
+    /// - `out` must be an aligned pointer to a `libra_preset_param_list_t`.
+    /// - The output struct must be freed with
+    /// `libra_preset_free_runtime_params`.
+    PFN_libra_shader_source_get_params shader_source_get_params;
+
+    /// Transpile the shader to the given target.
+    ///
+    /// Text targets are returned as NUL-terminated strings. SPIR-V is
+    /// returned as the raw module.
+    ///
+    /// ## Safety
+    /// - `source` must be null or a valid and aligned pointer to a
+    /// `libra_shader_source_t`.
+    /// - `options` must be either null, or valid and aligned.
+    /// - `out` must be an aligned pointer to a `libra_shader_output_t`.
+    /// - The output struct must be freed with `libra_shader_output_free`.
+    PFN_libra_shader_source_transpile shader_source_transpile;
+
+    /// Free the shader source.
+    ///
+    /// If `source` is null, this function does nothing. The resulting value
+    /// in `source` then becomes null.
+    ///
+    /// ## Safety
+    /// - `source` must be a valid and aligned pointer to a
+    /// `libra_shader_source_t`.
+    PFN_libra_shader_source_free shader_source_free;
+
+    /// Free the output of `libra_shader_source_get_text` or
+    /// `libra_shader_source_transpile`.
+    ///
+    /// ## Safety
+    /// - `output` must have been returned by `libra_shader_source_get_text` or
+    /// `libra_shader_source_transpile`, with its fields unchanged.
+    PFN_libra_shader_output_free shader_output_free;
+
+    /// Reflect a pass of the shader preset as NUL-terminated JSON, following
+    /// the versioned pass reflection schema.
+    ///
+    /// ## Safety
+    /// - `preset` must be null or a valid and aligned pointer to a
+    /// `libra_shader_preset_t`.
+    /// - `out` must be an aligned pointer to a `libra_shader_blob_t`.
+    /// - The output struct must be freed with `libra_shader_blob_free`.
+    PFN_libra_preset_reflect_pass preset_reflect_pass;
+
+    /// Free a buffer written by `libra_preset_reflect_pass`.
     ///
-    /// - If any struct fields of the input `libra_preset_param_list_t` was
-    /// modified from
-    ///   their values given after `libra_preset_get_runtime_params`, this may
-    ///   result in undefined behaviour.
-    PFN_libra_preset_free_runtime_params preset_free_runtime_params;
+    /// ## Safety
+    /// - `blob` must have been returned by `libra_preset_reflect_pass`, with
+    /// its fields unchanged.
+    PFN_libra_shader_blob_free shader_blob_free;
 
     /// Get the error code corresponding to this error object.
     ///
@@ -798,6 +1402,88 @@ typedef struct libra_instance_t {
     ///     other librashader functions, is immediate Undefined Behaviour.
     PFN_libra_error_free_string error_free_string;
 
+    /// Writes detailed information about the error into `out`.
+    ///
+    /// The details include a sub-code, and where available, the index of the
+    /// shader pass, the file path, and the line and column of the error.
+    ///
+    /// If `error` is null, this function does nothing and returns 1. Otherwise,
+    /// this function returns 0.
+    /// ## Safety
+    ///   - `error` must be a valid and initialized instance of `libra_error_t`.
+    ///   - `out` must be a non-null pointer. The resulting struct must be
+    ///   freed with `libra_error_free_details`.
+    PFN_libra_error_get_details error_get_details;
+
+    /// Frees the path of error details previously written by
+    /// `libra_error_get_details`.
+    ///
+    /// After freeing, the path will be set to null.
+    /// ## Safety
+    ///   - `details` must be null or point to details previously written by
+    ///   `libra_error_get_details`.
+    PFN_libra_error_free_details error_free_details;
+
+    /// Set the function that receives log messages from librashader.
+    ///
+    /// Messages more verbose than `level` are discarded. Passing a null
+    /// `callback` or `LIBRA_LOG_LEVEL_OFF` disables logging. The callback
+    /// may be called concurrently from any thread that calls into
+    /// librashader, and may call back into librashader.
+    /// ## Safety
+    /// - `callback` must be null, or a valid function pointer that is safe
+    ///   to call from any thread until it is replaced, and every call into
+    ///   librashader on other threads that started before then has returned.
+    /// - `userdata` is passed to `callback` as is, and must be safe to use
+    ///   from any thread.
+    PFN_libra_set_log_callback set_log_callback;
+
+    /// Free the parameters of a filter chain returned by one of the
+    /// `*_filter_chain_get_params` functions.
+    ///
+    /// Like `preset_free_runtime_params`, `filter_chain_free_params` takes the
+    /// struct directly.
+    /// ## Safety
+    /// - Any pointers rooted at `parameters` becomes invalid after this
+    /// function returns, including any strings accessible via the input
+    /// `libra_filter_chain_param_list_t`.
+    /// - If any struct fields of the input `libra_filter_chain_param_list_t`
+    /// were modified, this may result in undefined behaviour.
+    PFN_libra_filter_chain_free_params filter_chain_free_params;
+
+    /// Request that the creation of a filter chain be cancelled.
+    ///
+    /// The job stops after the pass it is currently preprocessing or
+    /// compiling, after which `*_filter_chain_job_finish` returns an error with
+    /// the code `LIBRA_ERRNO_CANCELLED`. This function may be called from any
+    /// thread, but must not be called concurrently with a function that frees
+    /// the job.
+    /// ## Safety
+    /// - `job` must be either null or a valid and aligned pointer to an
+    ///   initialized `libra_filter_chain_job_t`.
+    PFN_libra_filter_chain_job_cancel filter_chain_job_cancel;
+
+    /// Get whether the creation of a filter chain has finished, either
+    /// successfully or with an error.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// false.
+    /// ## Safety
+    /// - `job` must be either null or a valid and aligned pointer to an
+    ///   initialized `libra_filter_chain_job_t`.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    PFN_libra_filter_chain_job_is_finished filter_chain_job_is_finished;
+
+    /// Cancel and free a filter chain creation job.
+    ///
+    /// This blocks until the worker threads stop, then frees any filter chain
+    /// that was created by the job. The resulting value in `job` then becomes
+    /// null.
+    /// ## Safety
+    /// - `job` must be either null or a valid and aligned pointer to an
+    ///   initialized `libra_filter_chain_job_t`.
+    PFN_libra_filter_chain_job_free filter_chain_job_free;
+
 #if defined(LIBRA_RUNTIME_OPENGL)
     /// Create the filter chain given the shader preset.
     ///
@@ -814,6 +1500,21 @@ typedef struct libra_instance_t {
     /// - `out` must be aligned, but may be null, invalid, or uninitialized.
     PFN_libra_gl_filter_chain_create gl_filter_chain_create;
 
+    /// Create the filter chain given the shader preset pack.
+    ///
+    /// The shader preset pack is immediately invalidated and must be recreated after
+    /// the filter chain is created.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null. If this function returns an error, the state of `out` is
+    /// unspecified.
+    ///
+    /// ## Safety:
+    /// - `pack` must be either null, or valid and aligned.
+    /// - `options` must be either null, or valid and aligned.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    PFN_libra_gl_filter_chain_create_from_pack gl_filter_chain_create_from_pack;
+
     /// Draw a frame with the given parameters for the given filter chain.
     ///
     /// ## Safety
@@ -861,6 +1562,23 @@ typedef struct libra_instance_t {
     /// - `param_name` must be either null or a null terminated string.
     PFN_libra_gl_filter_chain_get_param gl_filter_chain_get_param;
 
+    /// Gets the metadata and current value of every parameter of the filter
+    /// chain, in the order they are declared by its passes.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_gl_filter_chain_t`.
+    /// - `out` must be an aligned pointer to a
+    /// `libra_filter_chain_param_list_t`, which must be freed with
+    /// `filter_chain_free_params`.
+    PFN_libra_gl_filter_chain_get_params gl_filter_chain_get_params;
+
+    /// Resets every parameter of the filter chain to the value it had when
+    /// the filter chain was loaded.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_gl_filter_chain_t`.
+    PFN_libra_gl_filter_chain_reset_params gl_filter_chain_reset_params;
+
     /// Sets a parameter for the filter chain.
     ///
     /// If the parameter does not exist, returns an error.
@@ -920,6 +1638,97 @@ typedef struct libra_instance_t {
     /// `libra_vk_filter_chain_frame`.
     PFN_libra_vk_filter_chain_create_deferred vk_filter_chain_create_deferred;
 
+    /// Create the filter chain given the shader preset on the librashader
+    /// worker threads, deferring GPU-side initialization to the caller as with
+    /// `vk_filter_chain_create_deferred`.
+    ///
+    /// `progress` is called with `userdata` as each pass is preprocessed and
+    /// compiled, and may be null. The filter chain is retrieved from the
+    /// resulting job with `vk_filter_chain_job_finish`.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null.
+    ///
+    /// ## Safety:
+    /// - `preset` must be either null, or valid and aligned.
+    /// - `options` must be either null, or valid and aligned.
+    /// - `progress` and `userdata` must be safe to use from any thread until
+    ///   the job is finished or freed.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    ///
+    /// The provided command buffer must be ready for recording and contain no
+    /// prior commands, and must not be used until the job is finished or
+    /// freed. After the filter chain is retrieved, the caller is responsible
+    /// for ending the command buffer and immediately submitting it to a
+    /// graphics queue.
+    PFN_libra_vk_filter_chain_create_async vk_filter_chain_create_async;
+
+    /// Wait for the creation of a filter chain to finish, and retrieve the
+    /// filter chain.
+    ///
+    /// Once the job has finished, the resulting value in `job` becomes null,
+    /// even if the filter chain could not be created. If the job was
+    /// cancelled, the returned error has the code `LIBRA_ERRNO_CANCELLED`.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null.
+    ///
+    /// ## Safety
+    /// - `job` must be either null or a valid and aligned pointer to a
+    ///   `libra_filter_chain_job_t` created by
+    ///   `vk_filter_chain_create_async`.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    PFN_libra_vk_filter_chain_job_finish vk_filter_chain_job_finish;
+
+    /// Create the filter chain given the shader preset pack.
+    ///
+    /// The shader preset pack is immediately invalidated and must be recreated after
+    /// the filter chain is created.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null. If this function returns an error, the state of `out` is
+    /// unspecified.
+    ///
+    /// ## Safety:
+    /// - The handles provided in `vulkan` must be valid for the command buffers
+    /// that
+    ///   `libra_vk_filter_chain_frame` will write to. Namely, the VkDevice must
+    ///   have been
+    ///    created with the `VK_KHR_dynamic_rendering` extension.
+    /// - `pack` must be either null, or valid and aligned.
+    /// - `options` must be either null, or valid and aligned.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    PFN_libra_vk_filter_chain_create_from_pack vk_filter_chain_create_from_pack;
+
+    /// Create the filter chain given the shader preset pack deferring and GPU-side
+    /// initialization to the caller. This function therefore requires no
+    /// external synchronization of the device queue.
+    ///
+    /// The shader preset pack is immediately invalidated and must be recreated after
+    /// the filter chain is created.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null. If this function returns an error, the state of `out` is
+    /// unspecified.
+    ///
+    /// ## Safety:
+    /// - The handles provided in `vulkan` must be valid for the command buffers
+    /// that
+    ///   `libra_vk_filter_chain_frame` will write to. Namely, the VkDevice must
+    ///   have been
+    ///    created with the `VK_KHR_dynamic_rendering` extension.
+    /// - `pack` must be either null, or valid and aligned.
+    /// - `options` must be either null, or valid and aligned.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    ///
+    /// The provided command buffer must be ready for recording and contain no
+    /// prior commands. The caller is responsible for ending the command buffer
+    /// and immediately submitting it to a graphics queue. The command buffer
+    /// must be completely executed before calling
+    /// `libra_vk_filter_chain_frame`.
+    PFN_libra_vk_filter_chain_create_from_pack_deferred
+        vk_filter_chain_create_from_pack_deferred;
+
     /// Records rendering commands for a frame with the given parameters for the
     /// given filter chain
     /// to the input command buffer.
@@ -975,6 +1784,23 @@ typedef struct libra_instance_t {
     /// - `param_name` must be either null or a null terminated string.
     PFN_libra_vk_filter_chain_get_param vk_filter_chain_get_param;
 
+    /// Gets the metadata and current value of every parameter of the filter
+    /// chain, in the order they are declared by its passes.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_vk_filter_chain_t`.
+    /// - `out` must be an aligned pointer to a
+    /// `libra_filter_chain_param_list_t`, which must be freed with
+    /// `filter_chain_free_params`.
+    PFN_libra_vk_filter_chain_get_params vk_filter_chain_get_params;
+
+    /// Resets every parameter of the filter chain to the value it had when
+    /// the filter chain was loaded.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_vk_filter_chain_t`.
+    PFN_libra_vk_filter_chain_reset_params vk_filter_chain_reset_params;
+
     /// Sets a parameter for the filter chain.
     ///
     /// If the parameter does not exist, returns an error.
@@ -1039,6 +1865,104 @@ typedef struct libra_instance_t {
     PFN_libra_d3d11_filter_chain_create_deferred
         d3d11_filter_chain_create_deferred;
 
+    /// Create the filter chain given the shader preset on the librashader
+    /// worker threads, deferring GPU-side initialization to the caller as with
+    /// `d3d11_filter_chain_create_deferred`.
+    ///
+    /// `progress` is called with `userdata` as each pass is preprocessed and
+    /// compiled, and may be null. The filter chain is retrieved from the
+    /// resulting job with `d3d11_filter_chain_job_finish`.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null.
+    ///
+    /// ## Safety:
+    /// - `preset` must be either null, or valid and aligned.
+    /// - `options` must be either null, or valid and aligned.
+    /// - `device` must not be null, and must not have been created with
+    ///   `D3D11_CREATE_DEVICE_SINGLETHREADED`.
+    /// - `device_context` must not be null, and must be a deferred context.
+    /// - `progress` and `userdata` must be safe to use from any thread until
+    ///   the job is finished or freed.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    ///
+    /// The deferred context must have no prior commands recorded, and must not
+    /// be used until the job is finished or freed. After the filter chain is
+    /// retrieved, the caller must immediately call `FinishCommandList` and
+    /// execute the command list on the immediate context.
+    PFN_libra_d3d11_filter_chain_create_async d3d11_filter_chain_create_async;
+
+    /// Wait for the creation of a filter chain to finish, and retrieve the
+    /// filter chain.
+    ///
+    /// Once the job has finished, the resulting value in `job` becomes null,
+    /// even if the filter chain could not be created. If the job was
+    /// cancelled, the returned error has the code `LIBRA_ERRNO_CANCELLED`.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null.
+    ///
+    /// ## Safety
+    /// - `job` must be either null or a valid and aligned pointer to a
+    ///   `libra_filter_chain_job_t` created by
+    ///   `d3d11_filter_chain_create_async`.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    PFN_libra_d3d11_filter_chain_job_finish d3d11_filter_chain_job_finish;
+
+    /// Create the filter chain given the shader preset pack.
+    ///
+    /// The shader preset pack is immediately invalidated and must be recreated after
+    /// the filter chain is created.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null. If this function returns an error, the state of `out` is
+    /// unspecified.
+    ///
+    /// ## Safety:
+    /// - `pack` must be either null, or valid and aligned.
+    /// - `options` must be either null, or valid and aligned.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    PFN_libra_d3d11_filter_chain_create_from_pack
+        d3d11_filter_chain_create_from_pack;
+
+    /// Create the filter chain given the shader preset pack, deferring and GPU-side
+    /// initialization
+    /// to the caller. This function is therefore requires no external
+    /// synchronization of the immediate context, as long as the immediate
+    /// context is not used as the input context, nor of the device, as long as
+    /// the device is not single-threaded only.
+    ///
+    /// The shader preset pack is immediately invalidated and must be recreated after
+    /// the filter chain is created.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null. If this function returns an error, the state of `out` is
+    /// unspecified.
+    ///
+    /// ## Safety:
+    /// - `pack` must be either null, or valid and aligned.
+    /// - `options` must be either null, or valid and aligned.
+    /// - `device` must not be null.
+    /// - `device_context` not be null.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    ///
+    /// The provided context must either be immediate, or immediately submitted
+    /// after this function returns, **before drawing frames**, or lookup
+    /// textures will fail to load and the filter chain will be in an invalid
+    /// state.
+    ///
+    /// If the context is deferred, it must be ready for command recording, and
+    /// have no prior commands recorded. No commands shall be recorded after,
+    /// the caller must immediately call
+    /// [`FinishCommandList`](https://learn.microsoft.com/en-us/windows/win32/api/d3d11/nf-d3d11-id3d11devicecontext-finishcommandlist)
+    /// and execute the command list on the immediate context after this
+    /// function returns.
+    ///
+    /// If the context is immediate, then access to the immediate context
+    /// requires external synchronization.
+    PFN_libra_d3d11_filter_chain_create_from_pack_deferred
+        d3d11_filter_chain_create_from_pack_deferred;
+
     /// Draw a frame with the given parameters for the given filter chain.
     ///
     /// ## Safety
@@ -1086,6 +2010,23 @@ typedef struct libra_instance_t {
     /// - `param_name` must be either null or a null terminated string.
     PFN_libra_d3d11_filter_chain_get_param d3d11_filter_chain_get_param;
 
+    /// Gets the metadata and current value of every parameter of the filter
+    /// chain, in the order they are declared by its passes.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_d3d11_filter_chain_t`.
+    /// - `out` must be an aligned pointer to a
+    /// `libra_filter_chain_param_list_t`, which must be freed with
+    /// `filter_chain_free_params`.
+    PFN_libra_d3d11_filter_chain_get_params d3d11_filter_chain_get_params;
+
+    /// Resets every parameter of the filter chain to the value it had when
+    /// the filter chain was loaded.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_d3d11_filter_chain_t`.
+    PFN_libra_d3d11_filter_chain_reset_params d3d11_filter_chain_reset_params;
+
     /// Sets a parameter for the filter chain.
     ///
     /// If the parameter does not exist, returns an error.
@@ -1138,6 +2079,92 @@ typedef struct libra_instance_t {
     PFN_libra_d3d12_filter_chain_create_deferred
         d3d12_filter_chain_create_deferred;
 
+    /// Create the filter chain given the shader preset on the librashader
+    /// worker threads, deferring GPU-side initialization to the caller as with
+    /// `d3d12_filter_chain_create_deferred`.
+    ///
+    /// `progress` is called with `userdata` as each pass is preprocessed and
+    /// compiled, and may be null. The filter chain is retrieved from the
+    /// resulting job with `d3d12_filter_chain_job_finish`.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null.
+    ///
+    /// ## Safety:
+    /// - `preset` must be either null, or valid and aligned.
+    /// - `options` must be either null, or valid and aligned.
+    /// - `device` must not be null.
+    /// - `command_list` must not be null.
+    /// - `progress` and `userdata` must be safe to use from any thread until
+    ///   the job is finished or freed.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    ///
+    /// The provided command list must be ready for recording and contain no
+    /// prior commands, and must not be used until the job is finished or
+    /// freed. After the filter chain is retrieved, the caller is responsible
+    /// for ending the command list and immediately submitting it to a graphics
+    /// queue.
+    PFN_libra_d3d12_filter_chain_create_async d3d12_filter_chain_create_async;
+
+    /// Wait for the creation of a filter chain to finish, and retrieve the
+    /// filter chain.
+    ///
+    /// Once the job has finished, the resulting value in `job` becomes null,
+    /// even if the filter chain could not be created. If the job was
+    /// cancelled, the returned error has the code `LIBRA_ERRNO_CANCELLED`.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null.
+    ///
+    /// ## Safety
+    /// - `job` must be either null or a valid and aligned pointer to a
+    ///   `libra_filter_chain_job_t` created by
+    ///   `d3d12_filter_chain_create_async`.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    PFN_libra_d3d12_filter_chain_job_finish d3d12_filter_chain_job_finish;
+
+    /// Create the filter chain given the shader preset pack.
+    ///
+    /// The shader preset pack is immediately invalidated and must be recreated after
+    /// the filter chain is created.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null. If this function returns an error, the state of `out` is
+    /// unspecified.
+    ///
+    /// ## Safety:
+    /// - `pack` must be either null, or valid and aligned.
+    /// - `options` must be either null, or valid and aligned.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    PFN_libra_d3d12_filter_chain_create_from_pack
+        d3d12_filter_chain_create_from_pack;
+
+    /// Create the filter chain given the shader preset pack deferring and GPU-side
+    /// initialization
+    /// to the caller. This function therefore requires no external
+    /// synchronization of the device queue.
+    ///
+    /// The shader preset pack is immediately invalidated and must be recreated after
+    /// the filter chain is created.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null. If this function returns an error, the state of `out` is
+    /// unspecified.
+    ///
+    /// ## Safety:
+    /// - `pack` must be either null, or valid and aligned.
+    /// - `options` must be either null, or valid and aligned.
+    /// - `device` must not be null.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    /// - `cmd` must not be null.
+    ///
+    /// The provided command list must be ready for recording and contain no
+    /// prior commands. The caller is responsible for ending the command list
+    /// and immediately submitting it to a graphics queue. The command list must
+    /// be completely executed before calling `libra_d3d12_filter_chain_frame`
+    PFN_libra_d3d12_filter_chain_create_from_pack_deferred
+        d3d12_filter_chain_create_from_pack_deferred;
+
     /// Draw a frame with the given parameters for the given filter chain.
     ///
     /// ## Safety
@@ -1182,8 +2209,25 @@ typedef struct libra_instance_t {
     /// ## Safety
     /// - `chain` must be either null or a valid and aligned pointer to an
     /// initialized `libra_d3d12_filter_chain_t`.
-    /// - `param_name` must be either null or a null terminated string.
-    PFN_libra_d3d12_filter_chain_get_param d3d12_filter_chain_get_param;
+    /// - `param_name` must be either null or a null terminated string.
+    PFN_libra_d3d12_filter_chain_get_param d3d12_filter_chain_get_param;
+
+    /// Gets the metadata and current value of every parameter of the filter
+    /// chain, in the order they are declared by its passes.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_d3d12_filter_chain_t`.
+    /// - `out` must be an aligned pointer to a
+    /// `libra_filter_chain_param_list_t`, which must be freed with
+    /// `filter_chain_free_params`.
+    PFN_libra_d3d12_filter_chain_get_params d3d12_filter_chain_get_params;
+
+    /// Resets every parameter of the filter chain to the value it had when
+    /// the filter chain was loaded.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_d3d12_filter_chain_t`.
+    PFN_libra_d3d12_filter_chain_reset_params d3d12_filter_chain_reset_params;
 
     /// Sets a parameter for the filter chain.
     ///
@@ -1211,6 +2255,22 @@ typedef struct libra_instance_t {
     /// - `out` must be aligned, but may be null, invalid, or uninitialized.
     PFN_libra_d3d9_filter_chain_create d3d9_filter_chain_create;
 
+    /// Create the filter chain given the shader preset pack.
+    ///
+    /// The shader preset pack is immediately invalidated and must be recreated after
+    /// the filter chain is created.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null. If this function returns an error, the state of `out` is
+    /// unspecified.
+    ///
+    /// ## Safety:
+    /// - `pack` must be either null, or valid and aligned.
+    /// - `options` must be either null, or valid and aligned.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    PFN_libra_d3d9_filter_chain_create_from_pack
+        d3d9_filter_chain_create_from_pack;
+
     /// Draw a frame with the given parameters for the given filter chain.
     ///
     /// ## Safety
@@ -1258,6 +2318,23 @@ typedef struct libra_instance_t {
     /// - `param_name` must be either null or a null terminated string.
     PFN_libra_d3d9_filter_chain_get_param d3d9_filter_chain_get_param;
 
+    /// Gets the metadata and current value of every parameter of the filter
+    /// chain, in the order they are declared by its passes.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_d3d9_filter_chain_t`.
+    /// - `out` must be an aligned pointer to a
+    /// `libra_filter_chain_param_list_t`, which must be freed with
+    /// `filter_chain_free_params`.
+    PFN_libra_d3d9_filter_chain_get_params d3d9_filter_chain_get_params;
+
+    /// Resets every parameter of the filter chain to the value it had when
+    /// the filter chain was loaded.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_d3d9_filter_chain_t`.
+    PFN_libra_d3d9_filter_chain_reset_params d3d9_filter_chain_reset_params;
+
     /// Sets a parameter for the filter chain.
     ///
     /// If the parameter does not exist, returns an error.
@@ -1309,6 +2386,48 @@ typedef struct libra_instance_t {
     /// be completely executed before calling `libra_mtl_filter_chain_frame`
     PFN_libra_mtl_filter_chain_create_deferred mtl_filter_chain_create_deferred;
 
+    /// Create the filter chain given the shader preset pack.
+    ///
+    /// The shader preset pack is immediately invalidated and must be recreated after
+    /// the filter chain is created.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null. If this function returns an error, the state of `out` is
+    /// unspecified.
+    ///
+    /// ## Safety:
+    /// - `pack` must be either null, or valid and aligned.
+    /// - `options` must be either null, or valid and aligned.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    PFN_libra_mtl_filter_chain_create_from_pack
+        mtl_filter_chain_create_from_pack;
+
+    /// Create the filter chain given the shader preset pack deferring and GPU-side
+    /// initialization
+    /// to the caller. This function therefore requires no external
+    /// synchronization of the device queue.
+    ///
+    /// The shader preset pack is immediately invalidated and must be recreated after
+    /// the filter chain is created.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null. If this function returns an error, the state of `out` is
+    /// unspecified.
+    ///
+    /// ## Safety:
+    /// - `pack` must be either null, or valid and aligned.
+    /// - `options` must be either null, or valid and aligned.
+    /// - `device` must not be null.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    /// - `cmd` must not be null.
+    ///
+    /// The provided command list must be ready for recording and contain no
+    /// prior commands. The caller is responsible for ending the command list
+    /// and immediately submitting it to a graphics queue. The command list must
+    /// be completely executed before calling `libra_mtl_filter_chain_frame`
+    PFN_libra_mtl_filter_chain_create_from_pack_deferred
+        mtl_filter_chain_create_from_pack_deferred;
+
     /// Draw a frame with the given parameters for the given filter chain.
     ///
     /// ## Safety
@@ -1356,6 +2475,23 @@ typedef struct libra_instance_t {
     /// - `param_name` must be either null or a null terminated string.
     PFN_libra_mtl_filter_chain_get_param mtl_filter_chain_get_param;
 
+    /// Gets the metadata and current value of every parameter of the filter
+    /// chain, in the order they are declared by its passes.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_mtl_filter_chain_t`.
+    /// - `out` must be an aligned pointer to a
+    /// `libra_filter_chain_param_list_t`, which must be freed with
+    /// `filter_chain_free_params`.
+    PFN_libra_mtl_filter_chain_get_params mtl_filter_chain_get_params;
+
+    /// Resets every parameter of the filter chain to the value it had when
+    /// the filter chain was loaded.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_mtl_filter_chain_t`.
+    PFN_libra_mtl_filter_chain_reset_params mtl_filter_chain_reset_params;
+
     /// Sets a parameter for the filter chain.
     ///
     /// If the parameter does not exist, returns an error.
@@ -1366,6 +2502,335 @@ typedef struct libra_instance_t {
     PFN_libra_mtl_filter_chain_set_param mtl_filter_chain_set_param;
 #endif
 
+#if defined(LIBRA_RUNTIME_WGPU)
+    /// Create the filter chain given the shader preset.
+    ///
+    /// The shader preset is immediately invalidated and must be recreated after
+    /// the filter chain is created.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null. If this function returns an error, the state of `out` is
+    /// unspecified.
+    ///
+    /// ## Safety:
+    /// - `preset` must be either null, or valid and aligned.
+    /// - `options` must be either null, or valid and aligned.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    PFN_libra_wgpu_filter_chain_create wgpu_filter_chain_create;
+
+    /// Create the filter chain given the shader preset deferring and GPU-side
+    /// initialization
+    /// to the caller. This function therefore requires no external
+    /// synchronization of the device queue.
+    ///
+    /// The shader preset is immediately invalidated and must be recreated after
+    /// the filter chain is created.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null. If this function returns an error, the state of `out` is
+    /// unspecified.
+    ///
+    /// ## Safety:
+    /// - `preset` must be either null, or valid and aligned.
+    /// - `options` must be either null, or valid and aligned.
+    /// - `device` must not be null.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    /// - `encoder` must not be null.
+    ///
+    /// The provided command encoder must be ready for recording and contain no
+    /// prior commands. The caller is responsible for immediately submitting the
+    /// command encoder to `queue` with `wgpu_queue_submit`. The command encoder
+    /// must be completely executed before calling `libra_wgpu_filter_chain_frame`
+    PFN_libra_wgpu_filter_chain_create_deferred wgpu_filter_chain_create_deferred;
+
+    /// Create the filter chain given the shader preset on the librashader
+    /// worker threads, deferring GPU-side initialization to the caller as with
+    /// `wgpu_filter_chain_create_deferred`.
+    ///
+    /// `progress` is called with `userdata` as each pass is preprocessed and
+    /// compiled, and may be null. The filter chain is retrieved from the
+    /// resulting job with `wgpu_filter_chain_job_finish`.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null.
+    ///
+    /// ## Safety:
+    /// - `preset` must be either null, or valid and aligned.
+    /// - `options` must be either null, or valid and aligned.
+    /// - `device`, `queue`, and `encoder` must not be null.
+    /// - `progress` and `userdata` must be safe to use from any thread until
+    ///   the job is finished or freed.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    ///
+    /// The provided command encoder must be ready for recording and contain no
+    /// prior commands, and must not be used until the job is finished or
+    /// freed. After the filter chain is retrieved, the caller is responsible
+    /// for immediately submitting the command encoder to `queue` with
+    /// `wgpu_queue_submit`.
+    PFN_libra_wgpu_filter_chain_create_async wgpu_filter_chain_create_async;
+
+    /// Wait for the creation of a filter chain to finish, and retrieve the
+    /// filter chain.
+    ///
+    /// Once the job has finished, the resulting value in `job` becomes null,
+    /// even if the filter chain could not be created. If the job was
+    /// cancelled, the returned error has the code `LIBRA_ERRNO_CANCELLED`.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null.
+    ///
+    /// ## Safety
+    /// - `job` must be either null or a valid and aligned pointer to a
+    ///   `libra_filter_chain_job_t` created by
+    ///   `wgpu_filter_chain_create_async`.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    PFN_libra_wgpu_filter_chain_job_finish wgpu_filter_chain_job_finish;
+
+    /// Create the filter chain given the shader preset pack.
+    ///
+    /// The shader preset pack is immediately invalidated and must be recreated after
+    /// the filter chain is created.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null. If this function returns an error, the state of `out` is
+    /// unspecified.
+    ///
+    /// ## Safety:
+    /// - `pack` must be either null, or valid and aligned.
+    /// - `options` must be either null, or valid and aligned.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    PFN_libra_wgpu_filter_chain_create_from_pack
+        wgpu_filter_chain_create_from_pack;
+
+    /// Create the filter chain given the shader preset pack deferring and GPU-side
+    /// initialization
+    /// to the caller. This function therefore requires no external
+    /// synchronization of the device queue.
+    ///
+    /// The shader preset pack is immediately invalidated and must be recreated after
+    /// the filter chain is created.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null. If this function returns an error, the state of `out` is
+    /// unspecified.
+    ///
+    /// ## Safety:
+    /// - `pack` must be either null, or valid and aligned.
+    /// - `options` must be either null, or valid and aligned.
+    /// - `device` must not be null.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    /// - `encoder` must not be null.
+    ///
+    /// The provided command encoder must be ready for recording and contain no
+    /// prior commands. The caller is responsible for immediately submitting the
+    /// command encoder to `queue` with `wgpu_queue_submit`. The command encoder
+    /// must be completely executed before calling `libra_wgpu_filter_chain_frame`
+    PFN_libra_wgpu_filter_chain_create_from_pack_deferred
+        wgpu_filter_chain_create_from_pack_deferred;
+
+    /// Draw a frame with the given parameters for the given filter chain.
+    ///
+    /// ## Safety
+    /// - `chain` may be null, invalid, but not uninitialized. If `chain` is
+    /// null or invalid, this
+    ///    function will return an error.
+    /// - `mvp` may be null, or if it is not null, must be an aligned pointer to
+    /// 16 consecutive `float`
+    ///    values for the model view projection matrix.
+    /// - `opt` may be null, or if it is not null, must be an aligned pointer to
+    /// a valid `frame_wgpu_opt_t`
+    ///    struct.
+    PFN_libra_wgpu_filter_chain_frame wgpu_filter_chain_frame;
+
+    /// Free a wgpu filter chain.
+    ///
+    /// The resulting value in `chain` then becomes null.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_wgpu_filter_chain_t`.
+    PFN_libra_wgpu_filter_chain_free wgpu_filter_chain_free;
+
+    /// Gets the number of active passes for this chain.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_wgpu_filter_chain_t`.
+    PFN_libra_wgpu_filter_chain_get_active_pass_count
+        wgpu_filter_chain_get_active_pass_count;
+
+    /// Sets the number of active passes for this chain.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_wgpu_filter_chain_t`.
+    PFN_libra_wgpu_filter_chain_set_active_pass_count
+        wgpu_filter_chain_set_active_pass_count;
+
+    /// Gets a parameter for the filter chain.
+    ///
+    /// If the parameter does not exist, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_wgpu_filter_chain_t`.
+    /// - `param_name` must be either null or a null terminated string.
+    PFN_libra_wgpu_filter_chain_get_param wgpu_filter_chain_get_param;
+
+    /// Gets the metadata and current value of every parameter of the filter
+    /// chain, in the order they are declared by its passes.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_wgpu_filter_chain_t`.
+    /// - `out` must be an aligned pointer to a
+    /// `libra_filter_chain_param_list_t`, which must be freed with
+    /// `filter_chain_free_params`.
+    PFN_libra_wgpu_filter_chain_get_params wgpu_filter_chain_get_params;
+
+    /// Resets every parameter of the filter chain to the value it had when
+    /// the filter chain was loaded.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_wgpu_filter_chain_t`.
+    PFN_libra_wgpu_filter_chain_reset_params wgpu_filter_chain_reset_params;
+
+    /// Sets a parameter for the filter chain.
+    ///
+    /// If the parameter does not exist, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_wgpu_filter_chain_t`.
+    /// - `param_name` must be either null or a null terminated string.
+    PFN_libra_wgpu_filter_chain_set_param wgpu_filter_chain_set_param;
+
+    /// Create a command encoder to record filter chain commands to.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null.
+    ///
+    /// ## Safety
+    /// - `device` must be a valid device handle.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    PFN_libra_wgpu_command_encoder_create wgpu_command_encoder_create;
+
+    /// Finish recording the command encoder and submit its commands to the
+    /// queue.
+    ///
+    /// The command encoder is consumed, and the resulting value in `encoder`
+    /// then becomes null.
+    ///
+    /// ## Safety
+    /// - `queue` must be a valid queue handle of the device the command
+    /// encoder was created with.
+    /// - `encoder` must be either null or a valid and aligned pointer to a
+    /// command encoder handle.
+    PFN_libra_wgpu_queue_submit wgpu_queue_submit;
+
+    /// Free a command encoder without submitting its commands.
+    ///
+    /// The resulting value in `encoder` then becomes null.
+    /// ## Safety
+    /// - `encoder` must be either null or a valid and aligned pointer to a
+    /// command encoder handle.
+    PFN_libra_wgpu_command_encoder_free wgpu_command_encoder_free;
+
+    /// Free a device handle.
+    ///
+    /// Filter chains created with the device keep their own reference to it,
+    /// so the device handle may be freed before them. The resulting value in
+    /// `device` then becomes null.
+    /// ## Safety
+    /// - `device` must be either null or a valid and aligned pointer to a
+    /// device handle.
+    PFN_libra_wgpu_device_free wgpu_device_free;
+
+    /// Free a queue handle.
+    ///
+    /// Filter chains created with the queue keep their own reference to it,
+    /// so the queue handle may be freed before them. The resulting value in
+    /// `queue` then becomes null.
+    /// ## Safety
+    /// - `queue` must be either null or a valid and aligned pointer to a
+    /// queue handle.
+    PFN_libra_wgpu_queue_free wgpu_queue_free;
+
+    /// Free a texture handle.
+    ///
+    /// A filter chain may keep its own reference to its last input texture
+    /// until the next frame. The image the texture was created over must
+    /// outlive every reference to the texture. The resulting value in
+    /// `texture` then becomes null.
+    /// ## Safety
+    /// - `texture` must be either null or a valid and aligned pointer to a
+    /// texture handle.
+    PFN_libra_wgpu_texture_free wgpu_texture_free;
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU_VULKAN)
+    /// Create a Vulkan device that a wgpu device can be created over with
+    /// `wgpu_device_create_vk`.
+    ///
+    /// The device is created from `vulkan.physical_device` with the
+    /// extensions and features that wgpu requires, the extensions in
+    /// `vulkan.device_extensions`, and `vulkan.queue_index + 1` queues of
+    /// `vulkan.queue_family_index`. `vulkan.device` is ignored.
+    ///
+    /// The caller owns the device, and must destroy it with `vkDestroyDevice`
+    /// after every wgpu object created over it is freed.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// `VK_NULL_HANDLE`.
+    ///
+    /// ## Safety
+    /// - `vulkan.entry` must be the `vkGetInstanceProcAddr` of the Vulkan
+    /// library the instance was created with.
+    /// - `vulkan.instance` must have been created with
+    /// `vulkan.instance_api_version` and the extensions in
+    /// `vulkan.instance_extensions`.
+    /// - `vulkan.instance_extensions` and `vulkan.device_extensions` must be
+    /// null if their count is zero, or point to that many null-terminated
+    /// strings.
+    /// - `vulkan.queue_family_index` must be a graphics queue family of the
+    /// physical device.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    PFN_libra_wgpu_vk_create_device wgpu_vk_create_device;
+
+    /// Create a wgpu device and queue over a Vulkan device.
+    ///
+    /// The Vulkan instance and device are not destroyed when the wgpu device
+    /// is freed, and must outlive the wgpu device, its queue, and every object
+    /// created with them, including filter chains.
+    ///
+    /// If this function is not loaded, `out_device` and `out_queue` will
+    /// unconditionally be set to null.
+    ///
+    /// ## Safety
+    /// - `vulkan.device` must have been created by `wgpu_vk_create_device`
+    /// with the same instance, physical device, device extensions, and queue
+    /// as `vulkan`.
+    /// - Access to the Vulkan queue must be externally synchronized with the
+    /// queue of the wgpu device.
+    /// - `out_device` and `out_queue` must be aligned, but may be null,
+    /// invalid, or uninitialized.
+    PFN_libra_wgpu_device_create_vk wgpu_device_create_vk;
+
+    /// Create a wgpu texture over a Vulkan image.
+    ///
+    /// The image is not destroyed when the texture is freed, and must outlive
+    /// the texture. wgpu transitions the image from
+    /// `VK_IMAGE_LAYOUT_UNDEFINED` the first time the texture is used.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null.
+    ///
+    /// ## Safety
+    /// - `device` must be a device handle created with
+    /// `wgpu_device_create_vk`.
+    /// - `image.handle` must be a `VkImage` of the Vulkan device, created with
+    /// a single mip level and array layer, and with `image.format`,
+    /// `image.width`, `image.height`, and `image.usage`.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    PFN_libra_wgpu_texture_create_vk wgpu_texture_create_vk;
+#endif
+
     /// Helper flag for if the librashader instance was loaded.
     ///
     /// This flag is not indicative of whether any functions were loaded
@@ -1427,15 +2892,47 @@ libra_instance_t __librashader_make_null_instance(void) {
     instance.preset_free_runtime_params =
         __librashader__noop_preset_free_runtime_params;
 
+    instance.preset_pack_create_from_preset =
+        __librashader__noop_preset_pack_create_from_preset;
+    instance.preset_pack_load = __librashader__noop_preset_pack_load;
+    instance.preset_pack_write = __librashader__noop_preset_pack_write;
+    instance.preset_pack_free_buffer =
+        __librashader__noop_preset_pack_free_buffer;
+    instance.preset_pack_free = __librashader__noop_preset_pack_free;
+
+    instance.shader_source_create = __librashader__noop_shader_source_create;
+    instance.shader_source_get_text =
+        __librashader__noop_shader_source_get_text;
+    instance.shader_source_get_params =
+        __librashader__noop_shader_source_get_params;
+    instance.shader_source_transpile =
+        __librashader__noop_shader_source_transpile;
+    instance.shader_source_free = __librashader__noop_shader_source_free;
+    instance.shader_output_free = __librashader__noop_shader_output_free;
+    instance.preset_reflect_pass = __librashader__noop_preset_reflect_pass;
+    instance.shader_blob_free = __librashader__noop_shader_blob_free;
+
     instance.error_errno = __librashader__noop_error_errno;
     instance.error_print = __librashader__noop_error_print;
     instance.error_free = __librashader__noop_error_free;
     instance.error_write = __librashader__noop_error_write;
     instance.error_free_string = __librashader__noop_error_free_string;
+    instance.error_get_details = __librashader__noop_error_get_details;
+    instance.error_free_details = __librashader__noop_error_free_details;
+    instance.set_log_callback = __librashader__noop_set_log_callback;
+    instance.filter_chain_free_params =
+        __librashader__noop_filter_chain_free_params;
+    instance.filter_chain_job_cancel =
+        __librashader__noop_filter_chain_job_cancel;
+    instance.filter_chain_job_is_finished =
+        __librashader__noop_filter_chain_job_is_finished;
+    instance.filter_chain_job_free = __librashader__noop_filter_chain_job_free;
 
 #if defined(LIBRA_RUNTIME_OPENGL)
     instance.gl_filter_chain_create =
         __librashader__noop_gl_filter_chain_create;
+    instance.gl_filter_chain_create_from_pack =
+        __librashader__noop_gl_filter_chain_create_from_pack;
     instance.gl_filter_chain_frame = __librashader__noop_gl_filter_chain_frame;
     instance.gl_filter_chain_free = __librashader__noop_gl_filter_chain_free;
     instance.gl_filter_chain_get_active_pass_count =
@@ -1444,6 +2941,10 @@ libra_instance_t __librashader_make_null_instance(void) {
         __librashader__noop_gl_filter_chain_set_active_pass_count;
     instance.gl_filter_chain_get_param =
         __librashader__noop_gl_filter_chain_get_param;
+    instance.gl_filter_chain_get_params =
+        __librashader__noop_gl_filter_chain_get_params;
+    instance.gl_filter_chain_reset_params =
+        __librashader__noop_gl_filter_chain_reset_params;
     instance.gl_filter_chain_set_param =
         __librashader__noop_gl_filter_chain_set_param;
 #endif
@@ -1453,6 +2954,14 @@ libra_instance_t __librashader_make_null_instance(void) {
         __librashader__noop_vk_filter_chain_create;
     instance.vk_filter_chain_create_deferred =
         __librashader__noop_vk_filter_chain_create_deferred;
+    instance.vk_filter_chain_create_async =
+        __librashader__noop_vk_filter_chain_create_async;
+    instance.vk_filter_chain_job_finish =
+        __librashader__noop_vk_filter_chain_job_finish;
+    instance.vk_filter_chain_create_from_pack =
+        __librashader__noop_vk_filter_chain_create_from_pack;
+    instance.vk_filter_chain_create_from_pack_deferred =
+        __librashader__noop_vk_filter_chain_create_from_pack_deferred;
     instance.vk_filter_chain_frame = __librashader__noop_vk_filter_chain_frame;
     instance.vk_filter_chain_free = __librashader__noop_vk_filter_chain_free;
     instance.vk_filter_chain_get_active_pass_count =
@@ -1461,6 +2970,10 @@ libra_instance_t __librashader_make_null_instance(void) {
         __librashader__noop_vk_filter_chain_set_active_pass_count;
     instance.vk_filter_chain_get_param =
         __librashader__noop_vk_filter_chain_get_param;
+    instance.vk_filter_chain_get_params =
+        __librashader__noop_vk_filter_chain_get_params;
+    instance.vk_filter_chain_reset_params =
+        __librashader__noop_vk_filter_chain_reset_params;
     instance.vk_filter_chain_set_param =
         __librashader__noop_vk_filter_chain_set_param;
 #endif
@@ -1470,6 +2983,14 @@ libra_instance_t __librashader_make_null_instance(void) {
         __librashader__noop_d3d11_filter_chain_create;
     instance.d3d11_filter_chain_create_deferred =
         __librashader__noop_d3d11_filter_chain_create_deferred;
+    instance.d3d11_filter_chain_create_async =
+        __librashader__noop_d3d11_filter_chain_create_async;
+    instance.d3d11_filter_chain_job_finish =
+        __librashader__noop_d3d11_filter_chain_job_finish;
+    instance.d3d11_filter_chain_create_from_pack =
+        __librashader__noop_d3d11_filter_chain_create_from_pack;
+    instance.d3d11_filter_chain_create_from_pack_deferred =
+        __librashader__noop_d3d11_filter_chain_create_from_pack_deferred;
     instance.d3d11_filter_chain_frame =
         __librashader__noop_d3d11_filter_chain_frame;
     instance.d3d11_filter_chain_free =
@@ -1480,6 +3001,10 @@ libra_instance_t __librashader_make_null_instance(void) {
         __librashader__noop_d3d11_filter_chain_set_active_pass_count;
     instance.d3d11_filter_chain_get_param =
         __librashader__noop_d3d11_filter_chain_get_param;
+    instance.d3d11_filter_chain_get_params =
+        __librashader__noop_d3d11_filter_chain_get_params;
+    instance.d3d11_filter_chain_reset_params =
+        __librashader__noop_d3d11_filter_chain_reset_params;
     instance.d3d11_filter_chain_set_param =
         __librashader__noop_d3d11_filter_chain_set_param;
 #endif
@@ -1489,6 +3014,14 @@ libra_instance_t __librashader_make_null_instance(void) {
         __librashader__noop_d3d12_filter_chain_create;
     instance.d3d12_filter_chain_create_deferred =
         __librashader__noop_d3d12_filter_chain_create_deferred;
+    instance.d3d12_filter_chain_create_async =
+        __librashader__noop_d3d12_filter_chain_create_async;
+    instance.d3d12_filter_chain_job_finish =
+        __librashader__noop_d3d12_filter_chain_job_finish;
+    instance.d3d12_filter_chain_create_from_pack =
+        __librashader__noop_d3d12_filter_chain_create_from_pack;
+    instance.d3d12_filter_chain_create_from_pack_deferred =
+        __librashader__noop_d3d12_filter_chain_create_from_pack_deferred;
     instance.d3d12_filter_chain_frame =
         __librashader__noop_d3d12_filter_chain_frame;
     instance.d3d12_filter_chain_free =
@@ -1499,6 +3032,10 @@ libra_instance_t __librashader_make_null_instance(void) {
         __librashader__noop_d3d12_filter_chain_set_active_pass_count;
     instance.d3d12_filter_chain_get_param =
         __librashader__noop_d3d12_filter_chain_get_param;
+    instance.d3d12_filter_chain_get_params =
+        __librashader__noop_d3d12_filter_chain_get_params;
+    instance.d3d12_filter_chain_reset_params =
+        __librashader__noop_d3d12_filter_chain_reset_params;
     instance.d3d12_filter_chain_set_param =
         __librashader__noop_d3d12_filter_chain_set_param;
 #endif
@@ -1506,6 +3043,8 @@ libra_instance_t __librashader_make_null_instance(void) {
 #if defined(LIBRA_RUNTIME_D3D9)
     instance.d3d9_filter_chain_create =
         __librashader__noop_d3d9_filter_chain_create;
+    instance.d3d9_filter_chain_create_from_pack =
+        __librashader__noop_d3d9_filter_chain_create_from_pack;
     instance.d3d9_filter_chain_create_deferred =
         __librashader__noop_d3d9_filter_chain_create_deferred;
     instance.d3d9_filter_chain_frame =
@@ -1518,6 +3057,10 @@ libra_instance_t __librashader_make_null_instance(void) {
         __librashader__noop_d3d9_filter_chain_set_active_pass_count;
     instance.d3d9_filter_chain_get_param =
         __librashader__noop_d3d9_filter_chain_get_param;
+    instance.d3d9_filter_chain_get_params =
+        __librashader__noop_d3d9_filter_chain_get_params;
+    instance.d3d9_filter_chain_reset_params =
+        __librashader__noop_d3d9_filter_chain_reset_params;
     instance.d3d9_filter_chain_set_param =
         __librashader__noop_d3d9_filter_chain_set_param;
 #endif
@@ -1527,6 +3070,10 @@ libra_instance_t __librashader_make_null_instance(void) {
         __librashader__noop_mtl_filter_chain_create;
     instance.mtl_filter_chain_create_deferred =
         __librashader__noop_mtl_filter_chain_create_deferred;
+    instance.mtl_filter_chain_create_from_pack =
+        __librashader__noop_mtl_filter_chain_create_from_pack;
+    instance.mtl_filter_chain_create_from_pack_deferred =
+        __librashader__noop_mtl_filter_chain_create_from_pack_deferred;
     instance.mtl_filter_chain_frame =
         __librashader__noop_mtl_filter_chain_frame;
     instance.mtl_filter_chain_free = __librashader__noop_mtl_filter_chain_free;
@@ -1536,9 +3083,58 @@ libra_instance_t __librashader_make_null_instance(void) {
         __librashader__noop_mtl_filter_chain_set_active_pass_count;
     instance.mtl_filter_chain_get_param =
         __librashader__noop_mtl_filter_chain_get_param;
+    instance.mtl_filter_chain_get_params =
+        __librashader__noop_mtl_filter_chain_get_params;
+    instance.mtl_filter_chain_reset_params =
+        __librashader__noop_mtl_filter_chain_reset_params;
     instance.mtl_filter_chain_set_param =
         __librashader__noop_mtl_filter_chain_set_param;
 #endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+    instance.wgpu_filter_chain_create =
+        __librashader__noop_wgpu_filter_chain_create;
+    instance.wgpu_filter_chain_create_deferred =
+        __librashader__noop_wgpu_filter_chain_create_deferred;
+    instance.wgpu_filter_chain_create_async =
+        __librashader__noop_wgpu_filter_chain_create_async;
+    instance.wgpu_filter_chain_job_finish =
+        __librashader__noop_wgpu_filter_chain_job_finish;
+    instance.wgpu_filter_chain_create_from_pack =
+        __librashader__noop_wgpu_filter_chain_create_from_pack;
+    instance.wgpu_filter_chain_create_from_pack_deferred =
+        __librashader__noop_wgpu_filter_chain_create_from_pack_deferred;
+    instance.wgpu_filter_chain_frame =
+        __librashader__noop_wgpu_filter_chain_frame;
+    instance.wgpu_filter_chain_free = __librashader__noop_wgpu_filter_chain_free;
+    instance.wgpu_filter_chain_get_active_pass_count =
+        __librashader__noop_wgpu_filter_chain_get_active_pass_count;
+    instance.wgpu_filter_chain_set_active_pass_count =
+        __librashader__noop_wgpu_filter_chain_set_active_pass_count;
+    instance.wgpu_filter_chain_get_param =
+        __librashader__noop_wgpu_filter_chain_get_param;
+    instance.wgpu_filter_chain_get_params =
+        __librashader__noop_wgpu_filter_chain_get_params;
+    instance.wgpu_filter_chain_reset_params =
+        __librashader__noop_wgpu_filter_chain_reset_params;
+    instance.wgpu_filter_chain_set_param =
+        __librashader__noop_wgpu_filter_chain_set_param;
+    instance.wgpu_command_encoder_create =
+        __librashader__noop_wgpu_command_encoder_create;
+    instance.wgpu_queue_submit = __librashader__noop_wgpu_queue_submit;
+    instance.wgpu_command_encoder_free =
+        __librashader__noop_wgpu_command_encoder_free;
+    instance.wgpu_device_free = __librashader__noop_wgpu_device_free;
+    instance.wgpu_queue_free = __librashader__noop_wgpu_queue_free;
+    instance.wgpu_texture_free = __librashader__noop_wgpu_texture_free;
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU_VULKAN)
+    instance.wgpu_vk_create_device = __librashader__noop_wgpu_vk_create_device;
+    instance.wgpu_device_create_vk = __librashader__noop_wgpu_device_create_vk;
+    instance.wgpu_texture_create_vk =
+        __librashader__noop_wgpu_texture_create_vk;
+#endif
     instance.instance_loaded = false;
 
     return instance;
@@ -1601,17 +3197,43 @@ libra_instance_t librashader_load_instance(void) {
     _LIBRASHADER_ASSIGN(librashader, instance, preset_get_runtime_params);
     _LIBRASHADER_ASSIGN(librashader, instance, preset_free_runtime_params);
 
+    _LIBRASHADER_ASSIGN(librashader, instance, preset_pack_create_from_preset);
+    _LIBRASHADER_ASSIGN(librashader, instance, preset_pack_load);
+    _LIBRASHADER_ASSIGN(librashader, instance, preset_pack_write);
+    _LIBRASHADER_ASSIGN(librashader, instance, preset_pack_free_buffer);
+    _LIBRASHADER_ASSIGN(librashader, instance, preset_pack_free);
+
+    _LIBRASHADER_ASSIGN(librashader, instance, shader_source_create);
+    _LIBRASHADER_ASSIGN(librashader, instance, shader_source_get_text);
+    _LIBRASHADER_ASSIGN(librashader, instance, shader_source_get_params);
+    _LIBRASHADER_ASSIGN(librashader, instance, shader_source_transpile);
+    _LIBRASHADER_ASSIGN(librashader, instance, shader_source_free);
+    _LIBRASHADER_ASSIGN(librashader, instance, shader_output_free);
+    _LIBRASHADER_ASSIGN(librashader, instance, preset_reflect_pass);
+    _LIBRASHADER_ASSIGN(librashader, instance, shader_blob_free);
+
     _LIBRASHADER_ASSIGN(librashader, instance, error_errno);
     _LIBRASHADER_ASSIGN(librashader, instance, error_print);
     _LIBRASHADER_ASSIGN(librashader, instance, error_free);
     _LIBRASHADER_ASSIGN(librashader, instance, error_write);
     _LIBRASHADER_ASSIGN(librashader, instance, error_free_string);
+    _LIBRASHADER_ASSIGN(librashader, instance, error_get_details);
+    _LIBRASHADER_ASSIGN(librashader, instance, error_free_details);
+    _LIBRASHADER_ASSIGN(librashader, instance, set_log_callback);
+    _LIBRASHADER_ASSIGN(librashader, instance, filter_chain_free_params);
+    _LIBRASHADER_ASSIGN(librashader, instance, filter_chain_job_cancel);
+    _LIBRASHADER_ASSIGN(librashader, instance, filter_chain_job_is_finished);
+    _LIBRASHADER_ASSIGN(librashader, instance, filter_chain_job_free);
 
 #if defined(LIBRA_RUNTIME_OPENGL)
     _LIBRASHADER_ASSIGN(librashader, instance, gl_filter_chain_create);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        gl_filter_chain_create_from_pack);
     _LIBRASHADER_ASSIGN(librashader, instance, gl_filter_chain_frame);
     _LIBRASHADER_ASSIGN(librashader, instance, gl_filter_chain_free);
     _LIBRASHADER_ASSIGN(librashader, instance, gl_filter_chain_get_param);
+    _LIBRASHADER_ASSIGN(librashader, instance, gl_filter_chain_get_params);
+    _LIBRASHADER_ASSIGN(librashader, instance, gl_filter_chain_reset_params);
     _LIBRASHADER_ASSIGN(librashader, instance, gl_filter_chain_set_param);
     _LIBRASHADER_ASSIGN(librashader, instance,
                         gl_filter_chain_get_active_pass_count);
@@ -1623,9 +3245,17 @@ libra_instance_t librashader_load_instance(void) {
 #if defined(LIBRA_RUNTIME_VULKAN)
     _LIBRASHADER_ASSIGN(librashader, instance, vk_filter_chain_create);
     _LIBRASHADER_ASSIGN(librashader, instance, vk_filter_chain_create_deferred);
+    _LIBRASHADER_ASSIGN(librashader, instance, vk_filter_chain_create_async);
+    _LIBRASHADER_ASSIGN(librashader, instance, vk_filter_chain_job_finish);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        vk_filter_chain_create_from_pack);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        vk_filter_chain_create_from_pack_deferred);
     _LIBRASHADER_ASSIGN(librashader, instance, vk_filter_chain_frame);
     _LIBRASHADER_ASSIGN(librashader, instance, vk_filter_chain_free);
     _LIBRASHADER_ASSIGN(librashader, instance, vk_filter_chain_get_param);
+    _LIBRASHADER_ASSIGN(librashader, instance, vk_filter_chain_get_params);
+    _LIBRASHADER_ASSIGN(librashader, instance, vk_filter_chain_reset_params);
     _LIBRASHADER_ASSIGN(librashader, instance, vk_filter_chain_set_param);
     _LIBRASHADER_ASSIGN(librashader, instance,
                         vk_filter_chain_get_active_pass_count);
@@ -1637,9 +3267,18 @@ libra_instance_t librashader_load_instance(void) {
     _LIBRASHADER_ASSIGN(librashader, instance, d3d11_filter_chain_create);
     _LIBRASHADER_ASSIGN(librashader, instance,
                         d3d11_filter_chain_create_deferred);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d11_filter_chain_create_async);
+    _LIBRASHADER_ASSIGN(librashader, instance, d3d11_filter_chain_job_finish);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d11_filter_chain_create_from_pack);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d11_filter_chain_create_from_pack_deferred);
     _LIBRASHADER_ASSIGN(librashader, instance, d3d11_filter_chain_frame);
     _LIBRASHADER_ASSIGN(librashader, instance, d3d11_filter_chain_free);
     _LIBRASHADER_ASSIGN(librashader, instance, d3d11_filter_chain_get_param);
+    _LIBRASHADER_ASSIGN(librashader, instance, d3d11_filter_chain_get_params);
+    _LIBRASHADER_ASSIGN(librashader, instance, d3d11_filter_chain_reset_params);
     _LIBRASHADER_ASSIGN(librashader, instance, d3d11_filter_chain_set_param);
     _LIBRASHADER_ASSIGN(librashader, instance,
                         d3d11_filter_chain_get_active_pass_count);
@@ -1651,9 +3290,18 @@ libra_instance_t librashader_load_instance(void) {
     _LIBRASHADER_ASSIGN(librashader, instance, d3d12_filter_chain_create);
     _LIBRASHADER_ASSIGN(librashader, instance,
                         d3d12_filter_chain_create_deferred);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d12_filter_chain_create_async);
+    _LIBRASHADER_ASSIGN(librashader, instance, d3d12_filter_chain_job_finish);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d12_filter_chain_create_from_pack);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d12_filter_chain_create_from_pack_deferred);
     _LIBRASHADER_ASSIGN(librashader, instance, d3d12_filter_chain_frame);
     _LIBRASHADER_ASSIGN(librashader, instance, d3d12_filter_chain_free);
     _LIBRASHADER_ASSIGN(librashader, instance, d3d12_filter_chain_get_param);
+    _LIBRASHADER_ASSIGN(librashader, instance, d3d12_filter_chain_get_params);
+    _LIBRASHADER_ASSIGN(librashader, instance, d3d12_filter_chain_reset_params);
     _LIBRASHADER_ASSIGN(librashader, instance, d3d12_filter_chain_set_param);
     _LIBRASHADER_ASSIGN(librashader, instance,
                         d3d12_filter_chain_get_active_pass_count);
@@ -1663,9 +3311,13 @@ libra_instance_t librashader_load_instance(void) {
 
 #if defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9)
     _LIBRASHADER_ASSIGN(librashader, instance, d3d9_filter_chain_create);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d9_filter_chain_create_from_pack);
     _LIBRASHADER_ASSIGN(librashader, instance, d3d9_filter_chain_frame);
     _LIBRASHADER_ASSIGN(librashader, instance, d3d9_filter_chain_free);
     _LIBRASHADER_ASSIGN(librashader, instance, d3d9_filter_chain_get_param);
+    _LIBRASHADER_ASSIGN(librashader, instance, d3d9_filter_chain_get_params);
+    _LIBRASHADER_ASSIGN(librashader, instance, d3d9_filter_chain_reset_params);
     _LIBRASHADER_ASSIGN(librashader, instance, d3d9_filter_chain_set_param);
     _LIBRASHADER_ASSIGN(librashader, instance,
                         d3d9_filter_chain_get_active_pass_count);
@@ -1677,15 +3329,55 @@ libra_instance_t librashader_load_instance(void) {
     _LIBRASHADER_ASSIGN(librashader, instance, mtl_filter_chain_create);
     _LIBRASHADER_ASSIGN(librashader, instance,
                         mtl_filter_chain_create_deferred);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        mtl_filter_chain_create_from_pack);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        mtl_filter_chain_create_from_pack_deferred);
     _LIBRASHADER_ASSIGN(librashader, instance, mtl_filter_chain_frame);
     _LIBRASHADER_ASSIGN(librashader, instance, mtl_filter_chain_free);
     _LIBRASHADER_ASSIGN(librashader, instance, mtl_filter_chain_get_param);
+    _LIBRASHADER_ASSIGN(librashader, instance, mtl_filter_chain_get_params);
+    _LIBRASHADER_ASSIGN(librashader, instance, mtl_filter_chain_reset_params);
     _LIBRASHADER_ASSIGN(librashader, instance, mtl_filter_chain_set_param);
     _LIBRASHADER_ASSIGN(librashader, instance,
                         mtl_filter_chain_get_active_pass_count);
     _LIBRASHADER_ASSIGN(librashader, instance,
                         mtl_filter_chain_set_active_pass_count);
 #endif
+
+#if defined(LIBRA_RUNTIME_WGPU)
+    _LIBRASHADER_ASSIGN(librashader, instance, wgpu_filter_chain_create);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        wgpu_filter_chain_create_deferred);
+    _LIBRASHADER_ASSIGN(librashader, instance, wgpu_filter_chain_create_async);
+    _LIBRASHADER_ASSIGN(librashader, instance, wgpu_filter_chain_job_finish);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        wgpu_filter_chain_create_from_pack);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        wgpu_filter_chain_create_from_pack_deferred);
+    _LIBRASHADER_ASSIGN(librashader, instance, wgpu_filter_chain_frame);
+    _LIBRASHADER_ASSIGN(librashader, instance, wgpu_filter_chain_free);
+    _LIBRASHADER_ASSIGN(librashader, instance, wgpu_filter_chain_get_param);
+    _LIBRASHADER_ASSIGN(librashader, instance, wgpu_filter_chain_get_params);
+    _LIBRASHADER_ASSIGN(librashader, instance, wgpu_filter_chain_reset_params);
+    _LIBRASHADER_ASSIGN(librashader, instance, wgpu_filter_chain_set_param);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        wgpu_filter_chain_get_active_pass_count);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        wgpu_filter_chain_set_active_pass_count);
+    _LIBRASHADER_ASSIGN(librashader, instance, wgpu_command_encoder_create);
+    _LIBRASHADER_ASSIGN(librashader, instance, wgpu_queue_submit);
+    _LIBRASHADER_ASSIGN(librashader, instance, wgpu_command_encoder_free);
+    _LIBRASHADER_ASSIGN(librashader, instance, wgpu_device_free);
+    _LIBRASHADER_ASSIGN(librashader, instance, wgpu_queue_free);
+    _LIBRASHADER_ASSIGN(librashader, instance, wgpu_texture_free);
+#endif
+
+#if defined(LIBRA_RUNTIME_WGPU_VULKAN)
+    _LIBRASHADER_ASSIGN(librashader, instance, wgpu_vk_create_device);
+    _LIBRASHADER_ASSIGN(librashader, instance, wgpu_device_create_vk);
+    _LIBRASHADER_ASSIGN(librashader, instance, wgpu_texture_create_vk);
+#endif
     instance.instance_loaded = true;
     return instance;
 }
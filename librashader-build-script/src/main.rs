@@ -1,10 +1,15 @@
+mod package;
+mod target;
+
 use carlog::*;
 use clap::Parser;
+use package::{InstallDirs, Package};
 use std::fs::File;
 use std::io::{BufWriter, Write};
 use std::path::{Path, PathBuf};
 use std::process::{Command, ExitCode};
 use std::{env, fs};
+use target::Target;
 
 #[derive(Parser, Debug)]
 #[command(version, about)]
@@ -15,6 +20,21 @@ struct Args {
     target: Option<String>,
     #[arg(long, default_value_t = false, global = true)]
     stable: bool,
+    /// Install the library, headers, pkg-config file and CMake package after building.
+    #[arg(long, default_value_t = false, global = true)]
+    install: bool,
+    /// The installation prefix.
+    #[arg(long, default_value = "/usr/local", global = true)]
+    prefix: PathBuf,
+    /// The directory to install the library to, relative to the prefix unless absolute.
+    #[arg(long, default_value = "lib", global = true)]
+    libdir: PathBuf,
+    /// The directory to install the headers to, relative to the prefix unless absolute.
+    #[arg(long, default_value = "include", global = true)]
+    includedir: PathBuf,
+    /// A staging directory to install into, with the prefix placed under it.
+    #[arg(long, global = true)]
+    destdir: Option<PathBuf>,
     #[arg(last = true)]
     cargoflags: Vec<String>,
 }
@@ -29,14 +49,37 @@ pub fn main() -> ExitCode {
 
     let profile = args.profile;
 
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
     let crate_dir = Path::new("librashader-capi");
+    let Ok(package) = Package::read(&cargo, crate_dir).inspect_err(|err| {
+        carlog_error!("unable to read the version of librashader-capi");
+        carlog_error!(format!("{err}"));
+    }) else {
+        println!("help: are you running the build script from the repository root?");
+        return ExitCode::FAILURE;
+    };
+
+    let Ok(target) = Target::query(args.target.as_deref()).inspect_err(|err| {
+        carlog_error!("unable to query the configuration of the target");
+        carlog_error!(format!("{err}"));
+    }) else {
+        return ExitCode::FAILURE;
+    };
+
+    // The soname, pkg-config file and CMake package are only produced for ELF shared libraries.
+    let is_elf = target.is_elf();
+    if args.install && !is_elf {
+        carlog_error!("installing librashader is only supported on Linux and other ELF platforms");
+        return ExitCode::FAILURE;
+    }
+
     carlog_info!("Building", "librashader C API");
 
-    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
     let mut cmd = Command::new(&cargo);
 
-    cmd.arg("build");
-    cmd.args(["--package", "librashader-capi"]);
+    // cargo rustc passes the link arguments below to librashader-capi only.
+    cmd.arg("rustc");
+    cmd.args(["--package", "librashader-capi", "--lib"]);
     cmd.arg(format!(
         "--profile={}",
         if profile == "debug" { "dev" } else { &profile }
@@ -60,6 +103,14 @@ pub fn main() -> ExitCode {
         cmd.args(args.cargoflags);
     }
 
+    if is_elf {
+        cmd.args([
+            "--",
+            "-C",
+            &format!("link-arg=-Wl,-soname,{}", package.soname()),
+        ]);
+    }
+
     let Ok(status) = cmd.status().inspect_err(|err| {
         carlog_error!("failed to build librashader-capi");
         carlog_error!(format!("{err}"));
@@ -116,7 +167,7 @@ pub fn main() -> ExitCode {
     }
 
     carlog_info!("Moving", "built artifacts");
-    if cfg!(target_os = "macos") {
+    if target.os == "macos" {
         let artifacts = &["liblibrashader_capi.dylib", "liblibrashader_capi.a"];
         for artifact in artifacts {
             let ext = artifact.strip_prefix("lib").unwrap();
@@ -132,7 +183,7 @@ pub fn main() -> ExitCode {
             };
             carlog_ok!("Renamed", format!("{artifact} to {}", &ext));
         }
-    } else if cfg!(target_family = "unix") {
+    } else if target.is_family("unix") {
         let artifacts = &["liblibrashader_capi.so", "liblibrashader_capi.a"];
         for artifact in artifacts {
             let ext = artifact.strip_prefix("lib").unwrap();
@@ -149,7 +200,7 @@ pub fn main() -> ExitCode {
         }
     }
 
-    if cfg!(target_os = "windows") {
+    if target.os == "windows" {
         let artifacts = &[
             "librashader_capi.dll",
             "librashader_capi.lib",
@@ -180,5 +231,64 @@ pub fn main() -> ExitCode {
         }
     }
 
+    if is_elf {
+        let Ok(_) = package::link_soname(&output_dir, &package).inspect_err(|err| {
+            carlog_error!(format!(
+                "unable to link {} to librashader.so",
+                package.soname()
+            ));
+            carlog_error!(format!("{err}"));
+        }) else {
+            return ExitCode::FAILURE;
+        };
+        carlog_ok!("Linked", format!("{} to librashader.so", package.soname()));
+
+        carlog_info!("Generating", "pkg-config file and CMake package");
+        let dirs = InstallDirs {
+            prefix: args.prefix,
+            libdir: args.libdir,
+            includedir: args.includedir,
+        };
+
+        let Ok(_) = package::write_build_files(&output_dir, &package, &dirs).inspect_err(|err| {
+            carlog_error!("unable to write librashader.pc and librashaderConfig.cmake");
+            carlog_error!(format!("{err}"));
+        }) else {
+            return ExitCode::FAILURE;
+        };
+
+        if args.install {
+            carlog_info!(
+                "Installing",
+                format!("librashader to {}", dirs.prefix.display())
+            );
+
+            // Headers are not regenerated on stable, so install the checked in headers instead.
+            let header = output_dir.join("librashader.h");
+            let headers = [
+                if header.exists() {
+                    header
+                } else {
+                    PathBuf::from("include/librashader.h")
+                },
+                PathBuf::from("include/librashader_ld.h"),
+            ];
+
+            let Ok(_) = package::install(
+                &output_dir,
+                &headers,
+                &package,
+                &dirs,
+                args.destdir.as_deref(),
+            )
+            .inspect_err(|err| {
+                carlog_error!("unable to install librashader");
+                carlog_error!(format!("{err}"));
+            }) else {
+                return ExitCode::FAILURE;
+            };
+        }
+    }
+
     ExitCode::SUCCESS
 }
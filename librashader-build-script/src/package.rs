@@ -0,0 +1,426 @@
+//! Files for consuming the C API from build systems, and the install step.
+//!
+//! The pkg-config file and the CMake package describe the layout created by [`install`], and are
+//! only generated for the shared library built on Linux and other ELF platforms.
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+
+/// The version of the C API package and the ABI version its soname is derived from.
+pub struct Package {
+    pub version: String,
+    pub abi: u32,
+}
+
+impl Package {
+    /// Read the crate version of `librashader-capi` from `cargo metadata`, and
+    /// `LIBRASHADER_CURRENT_ABI` from its sources.
+    pub fn read(cargo: &str, crate_dir: &Path) -> io::Result<Package> {
+        let output = Command::new(cargo)
+            .args([
+                "metadata",
+                "--no-deps",
+                "--format-version=1",
+                "--manifest-path",
+            ])
+            .arg(crate_dir.join("Cargo.toml"))
+            .output()?;
+        if !output.status.success() {
+            return Err(io::Error::other(
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ));
+        }
+        let version = Package::version_from_metadata(&output.stdout)?;
+
+        let version_rs = fs::read_to_string(crate_dir.join("src/version.rs"))?;
+        let abi = Package::abi_from_source(&version_rs)?;
+
+        Ok(Package { version, abi })
+    }
+
+    /// Find the version of `librashader-capi` in the output of `cargo metadata`.
+    fn version_from_metadata(metadata: &[u8]) -> io::Result<String> {
+        let metadata: serde_json::Value = serde_json::from_slice(metadata)?;
+        metadata["packages"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find(|package| package["name"] == "librashader-capi")
+            .and_then(|package| package["version"].as_str())
+            .map(str::to_string)
+            .ok_or_else(|| invalid_data("could not find the version of librashader-capi"))
+    }
+
+    /// Find the value of `LIBRASHADER_CURRENT_ABI` in `version.rs`.
+    fn abi_from_source(version_rs: &str) -> io::Result<u32> {
+        version_rs
+            .lines()
+            .find_map(|line| {
+                line.trim()
+                    .strip_prefix("pub const LIBRASHADER_CURRENT_ABI:")
+            })
+            .and_then(|line| line.split_once('='))
+            .and_then(|(_, abi)| abi.trim().trim_end_matches(';').trim().parse().ok())
+            .ok_or_else(|| invalid_data("could not find LIBRASHADER_CURRENT_ABI"))
+    }
+
+    /// The soname of the shared library, which changes whenever the ABI version does.
+    pub fn soname(&self) -> String {
+        format!("librashader.so.{}", self.abi)
+    }
+}
+
+/// The directories librashader is installed to.
+///
+/// `libdir` and `includedir` may be relative to `prefix`, or absolute.
+pub struct InstallDirs {
+    pub prefix: PathBuf,
+    pub libdir: PathBuf,
+    pub includedir: PathBuf,
+}
+
+impl InstallDirs {
+    fn libdir(&self) -> PathBuf {
+        self.prefix.join(&self.libdir)
+    }
+
+    fn includedir(&self) -> PathBuf {
+        self.prefix.join(&self.includedir).join("librashader")
+    }
+
+    fn cmakedir(&self) -> PathBuf {
+        self.libdir().join("cmake").join("librashader")
+    }
+}
+
+/// Generate `librashader.pc`.
+pub fn pkg_config(package: &Package, dirs: &InstallDirs) -> String {
+    let relative_to = |variable: &str, path: &Path| {
+        if path.is_absolute() {
+            path.display().to_string()
+        } else {
+            format!("${{{variable}}}/{}", path.display())
+        }
+    };
+
+    format!(
+        "prefix={prefix}\n\
+         exec_prefix=${{prefix}}\n\
+         libdir={libdir}\n\
+         includedir={includedir}\n\
+         \n\
+         Name: librashader\n\
+         Description: RetroArch shaders for all\n\
+         URL: https://github.com/SnowflakePowered/librashader\n\
+         Version: {version}\n\
+         Libs: -L${{libdir}} -lrashader\n\
+         Cflags: -I${{includedir}}/librashader\n",
+        prefix = dirs.prefix.display(),
+        libdir = relative_to("exec_prefix", &dirs.libdir),
+        includedir = relative_to("prefix", &dirs.includedir),
+        version = package.version,
+    )
+}
+
+/// Generate `librashaderConfig.cmake`.
+///
+/// When the library directory is relative to the prefix, the prefix is found relative to the
+/// location of the package, so that the installation can be relocated.
+pub fn cmake_config(package: &Package, dirs: &InstallDirs) -> String {
+    let prefix = if dirs.libdir.is_absolute() {
+        dirs.prefix.display().to_string()
+    } else {
+        // The package is in <libdir>/cmake/librashader.
+        let depth = dirs
+            .libdir
+            .components()
+            .filter(|c| matches!(c, Component::Normal(_)))
+            .count()
+            + 2;
+        format!("${{CMAKE_CURRENT_LIST_DIR}}{}", "/..".repeat(depth))
+    };
+
+    let relative_to_prefix = |path: &Path| {
+        if path.is_absolute() {
+            path.display().to_string()
+        } else {
+            format!("${{_librashader_prefix}}/{}", path.display())
+        }
+    };
+
+    format!(
+        r#"# CMake package for librashader, generated by librashader-build-script.
+#
+# Provides the following imported targets:
+#   librashader::librashader  The librashader shared library.
+#   librashader::loader       The librashader_ld.h header, which loads librashader at runtime.
+
+get_filename_component(_librashader_prefix "{prefix}" ABSOLUTE)
+set(_librashader_libdir "{libdir}")
+set(_librashader_includedir "{includedir}/librashader")
+
+set(librashader_ABI_VERSION {abi})
+
+if(NOT TARGET librashader::librashader)
+  add_library(librashader::librashader SHARED IMPORTED)
+  set_target_properties(librashader::librashader PROPERTIES
+    IMPORTED_LOCATION "${{_librashader_libdir}}/{soname}"
+    IMPORTED_SONAME "{soname}"
+    INTERFACE_INCLUDE_DIRECTORIES "${{_librashader_includedir}}"
+  )
+endif()
+
+if(NOT TARGET librashader::loader)
+  add_library(librashader::loader INTERFACE IMPORTED)
+  set_target_properties(librashader::loader PROPERTIES
+    INTERFACE_INCLUDE_DIRECTORIES "${{_librashader_includedir}}"
+    INTERFACE_LINK_LIBRARIES "${{CMAKE_DL_LIBS}}"
+  )
+endif()
+
+unset(_librashader_prefix)
+unset(_librashader_libdir)
+unset(_librashader_includedir)
+"#,
+        libdir = relative_to_prefix(&dirs.libdir),
+        includedir = relative_to_prefix(&dirs.includedir),
+        abi = package.abi,
+        soname = package.soname(),
+    )
+}
+
+/// Generate `librashaderConfigVersion.cmake`.
+///
+/// librashader is not yet 1.0, so a requested version is compatible if it has the same major and
+/// minor version and is not newer than the installed one.
+pub fn cmake_config_version(package: &Package) -> String {
+    format!(
+        r#"set(PACKAGE_VERSION "{version}")
+
+if(PACKAGE_FIND_VERSION VERSION_GREATER PACKAGE_VERSION)
+  set(PACKAGE_VERSION_COMPATIBLE FALSE)
+elseif(NOT PACKAGE_FIND_VERSION_MAJOR STREQUAL "{major}" OR NOT PACKAGE_FIND_VERSION_MINOR STREQUAL "{minor}")
+  set(PACKAGE_VERSION_COMPATIBLE FALSE)
+else()
+  set(PACKAGE_VERSION_COMPATIBLE TRUE)
+  if(PACKAGE_FIND_VERSION STREQUAL PACKAGE_VERSION)
+    set(PACKAGE_VERSION_EXACT TRUE)
+  endif()
+endif()
+"#,
+        version = package.version,
+        major = package.version.split('.').next().unwrap_or("0"),
+        minor = package.version.split('.').nth(1).unwrap_or("0"),
+    )
+}
+
+/// Write the pkg-config file and CMake package to the output directory.
+pub fn write_build_files(
+    output_dir: &Path,
+    package: &Package,
+    dirs: &InstallDirs,
+) -> io::Result<()> {
+    fs::write(output_dir.join("librashader.pc"), pkg_config(package, dirs))?;
+    fs::write(
+        output_dir.join("librashaderConfig.cmake"),
+        cmake_config(package, dirs),
+    )?;
+    fs::write(
+        output_dir.join("librashaderConfigVersion.cmake"),
+        cmake_config_version(package),
+    )?;
+    Ok(())
+}
+
+/// Link the soname of the shared library in the output directory to `librashader.so`, so that
+/// programs linked against the library in the output directory can be run from there.
+#[cfg(unix)]
+pub fn link_soname(output_dir: &Path, package: &Package) -> io::Result<()> {
+    let link = output_dir.join(package.soname());
+    if link.symlink_metadata().is_ok() {
+        fs::remove_file(&link)?;
+    }
+    std::os::unix::fs::symlink("librashader.so", link)
+}
+
+#[cfg(not(unix))]
+pub fn link_soname(_output_dir: &Path, _package: &Package) -> io::Result<()> {
+    Err(io::ErrorKind::Unsupported.into())
+}
+
+/// Install the shared library, headers, pkg-config file and CMake package.
+///
+/// The library is installed as its soname, with a `librashader.so` symlink for linking. If
+/// `destdir` is set, the prefix is placed under it, as is usual for staging a package.
+#[cfg(unix)]
+pub fn install(
+    output_dir: &Path,
+    headers: &[PathBuf],
+    package: &Package,
+    dirs: &InstallDirs,
+    destdir: Option<&Path>,
+) -> io::Result<()> {
+    use std::os::unix::fs::{symlink, PermissionsExt};
+
+    let staged = |path: PathBuf| match destdir {
+        Some(destdir) => destdir.join(path.strip_prefix("/").unwrap_or(&path)),
+        None => path,
+    };
+
+    let libdir = staged(dirs.libdir());
+    let includedir = staged(dirs.includedir());
+    let pkgconfigdir = libdir.join("pkgconfig");
+    let cmakedir = staged(dirs.cmakedir());
+
+    for dir in [&libdir, &includedir, &pkgconfigdir, &cmakedir] {
+        fs::create_dir_all(dir)?;
+    }
+
+    let soname = package.soname();
+    let library = libdir.join(&soname);
+    fs::copy(output_dir.join("librashader.so"), &library)?;
+    fs::set_permissions(&library, fs::Permissions::from_mode(0o755))?;
+
+    let link = libdir.join("librashader.so");
+    if link.symlink_metadata().is_ok() {
+        fs::remove_file(&link)?;
+    }
+    symlink(&soname, &link)?;
+
+    for header in headers {
+        let Some(name) = header.file_name() else {
+            continue;
+        };
+        fs::copy(header, includedir.join(name))?;
+    }
+
+    fs::copy(
+        output_dir.join("librashader.pc"),
+        pkgconfigdir.join("librashader.pc"),
+    )?;
+    for file in ["librashaderConfig.cmake", "librashaderConfigVersion.cmake"] {
+        fs::copy(output_dir.join(file), cmakedir.join(file))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn install(
+    _output_dir: &Path,
+    _headers: &[PathBuf],
+    _package: &Package,
+    _dirs: &InstallDirs,
+    _destdir: Option<&Path>,
+) -> io::Result<()> {
+    Err(io::ErrorKind::Unsupported.into())
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package() -> Package {
+        Package {
+            version: "0.5.1".to_string(),
+            abi: 2,
+        }
+    }
+
+    fn dirs(prefix: &str, libdir: &str, includedir: &str) -> InstallDirs {
+        InstallDirs {
+            prefix: PathBuf::from(prefix),
+            libdir: PathBuf::from(libdir),
+            includedir: PathBuf::from(includedir),
+        }
+    }
+
+    #[test]
+    fn pkg_config_relative_dirs() {
+        let pc = pkg_config(&package(), &dirs("/usr/local", "lib", "include"));
+        assert!(pc.contains("prefix=/usr/local\n"));
+        assert!(pc.contains("libdir=${exec_prefix}/lib\n"));
+        assert!(pc.contains("includedir=${prefix}/include\n"));
+        assert!(pc.contains("Version: 0.5.1\n"));
+        assert!(pc.contains("Libs: -L${libdir} -lrashader\n"));
+        assert!(pc.contains("Cflags: -I${includedir}/librashader\n"));
+    }
+
+    #[test]
+    fn pkg_config_absolute_dirs() {
+        let pc = pkg_config(
+            &package(),
+            &dirs("/usr", "/usr/lib/x86_64-linux-gnu", "/usr/include"),
+        );
+        assert!(pc.contains("libdir=/usr/lib/x86_64-linux-gnu\n"));
+        assert!(pc.contains("includedir=/usr/include\n"));
+    }
+
+    #[test]
+    fn cmake_config_relocatable() {
+        let cmake = cmake_config(&package(), &dirs("/usr", "lib/x86_64-linux-gnu", "include"));
+        // lib/x86_64-linux-gnu/cmake/librashader is four levels below the prefix.
+        assert!(cmake.contains(
+            r#"get_filename_component(_librashader_prefix "${CMAKE_CURRENT_LIST_DIR}/../../../.." ABSOLUTE)"#
+        ));
+        assert!(cmake
+            .contains(r#"set(_librashader_libdir "${_librashader_prefix}/lib/x86_64-linux-gnu")"#));
+        assert!(cmake.contains(
+            r#"set(_librashader_includedir "${_librashader_prefix}/include/librashader")"#
+        ));
+        assert!(cmake.contains("set(librashader_ABI_VERSION 2)"));
+        assert!(cmake.contains(r#"IMPORTED_SONAME "librashader.so.2""#));
+        assert!(cmake.contains(r#"IMPORTED_LOCATION "${_librashader_libdir}/librashader.so.2""#));
+    }
+
+    #[test]
+    fn cmake_config_absolute_libdir() {
+        let cmake = cmake_config(&package(), &dirs("/opt/librashader", "/usr/lib", "include"));
+        assert!(cmake.contains(
+            r#"get_filename_component(_librashader_prefix "/opt/librashader" ABSOLUTE)"#
+        ));
+        assert!(cmake.contains(r#"set(_librashader_libdir "/usr/lib")"#));
+        assert!(cmake.contains(
+            r#"set(_librashader_includedir "${_librashader_prefix}/include/librashader")"#
+        ));
+    }
+
+    #[test]
+    fn cmake_config_version_compares_major_and_minor() {
+        let version = cmake_config_version(&package());
+        assert!(version.contains(r#"set(PACKAGE_VERSION "0.5.1")"#));
+        assert!(version.contains(r#"STREQUAL "0" OR NOT PACKAGE_FIND_VERSION_MINOR STREQUAL "5""#));
+    }
+
+    #[test]
+    fn reads_version_from_metadata() {
+        let metadata = br#"{"packages": [
+            {"name": "librashader", "version": "0.5.0"},
+            {"name": "librashader-capi", "version": "0.5.1"}
+        ]}"#;
+        assert_eq!(Package::version_from_metadata(metadata).unwrap(), "0.5.1");
+        assert!(Package::version_from_metadata(br#"{"packages": []}"#).is_err());
+    }
+
+    #[test]
+    fn reads_abi_from_source() {
+        let source = "/// The current ABI version.\n\
+                      pub const LIBRASHADER_CURRENT_ABI: LIBRASHADER_ABI_VERSION = 2;\n";
+        assert_eq!(Package::abi_from_source(source).unwrap(), 2);
+        assert!(
+            Package::abi_from_source("pub const LIBRASHADER_CURRENT_VERSION: u32 = 1;").is_err()
+        );
+    }
+
+    #[test]
+    fn reads_librashader_capi() {
+        let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+        let package = Package::read(&cargo, Path::new("../librashader-capi")).unwrap();
+        assert_eq!(package.soname(), format!("librashader.so.{}", package.abi));
+    }
+}
@@ -0,0 +1,129 @@
+//! The configuration of the target librashader-capi is built for.
+//!
+//! The build script may run on a different platform than the one the library is built for, so
+//! the target is queried from rustc instead of using the `cfg!` of the build script itself.
+use std::env;
+use std::io;
+use std::process::Command;
+
+/// The `target_os` and `target_family` of the target, as reported by `rustc --print cfg`.
+///
+/// These are the same values that cargo passes to build scripts as `CARGO_CFG_TARGET_OS` and
+/// `CARGO_CFG_TARGET_FAMILY`.
+#[derive(Debug)]
+pub struct Target {
+    pub os: String,
+    pub family: Vec<String>,
+}
+
+impl Target {
+    /// Query the configuration of the target triple, or of the host if no target is given.
+    pub fn query(target: Option<&str>) -> io::Result<Target> {
+        let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+        let mut cmd = Command::new(rustc);
+        cmd.args(["--print", "cfg"]);
+        if let Some(target) = target {
+            cmd.arg(format!("--target={target}"));
+        }
+
+        let output = cmd.output()?;
+        if !output.status.success() {
+            return Err(io::Error::other(
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ));
+        }
+
+        Target::from_cfg(&String::from_utf8_lossy(&output.stdout))
+            .ok_or_else(|| io::Error::other("rustc did not report the target_os of the target"))
+    }
+
+    /// Parse the output of `rustc --print cfg`.
+    pub fn from_cfg(cfg: &str) -> Option<Target> {
+        let value = |line: &str, key: &str| {
+            line.strip_prefix(key)?
+                .strip_prefix('=')
+                .map(|value| value.trim_matches('"').to_string())
+        };
+
+        let os = cfg.lines().find_map(|line| value(line, "target_os"))?;
+        let family = cfg
+            .lines()
+            .filter_map(|line| value(line, "target_family"))
+            .collect();
+        Some(Target { os, family })
+    }
+
+    pub fn is_family(&self, family: &str) -> bool {
+        self.family.iter().any(|f| f == family)
+    }
+
+    pub fn is_apple(&self) -> bool {
+        matches!(
+            self.os.as_str(),
+            "macos" | "ios" | "tvos" | "watchos" | "visionos"
+        )
+    }
+
+    /// Whether the shared library is an ELF object, which gets a soname, pkg-config file and
+    /// CMake package.
+    pub fn is_elf(&self) -> bool {
+        self.is_family("unix") && !self.is_family("wasm") && !self.is_apple()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_linux() {
+        let target = Target::from_cfg(
+            "debug_assertions\n\
+             target_arch=\"x86_64\"\n\
+             target_env=\"gnu\"\n\
+             target_family=\"unix\"\n\
+             target_os=\"linux\"\n",
+        )
+        .unwrap();
+        assert_eq!(target.os, "linux");
+        assert!(target.is_elf());
+    }
+
+    #[test]
+    fn apple_is_not_elf() {
+        let target = Target::from_cfg(
+            "target_family=\"unix\"\ntarget_os=\"macos\"\ntarget_vendor=\"apple\"\n",
+        )
+        .unwrap();
+        assert!(target.is_apple());
+        assert!(!target.is_elf());
+    }
+
+    #[test]
+    fn windows_is_not_elf() {
+        let target =
+            Target::from_cfg("target_family=\"windows\"\ntarget_os=\"windows\"\n").unwrap();
+        assert!(!target.is_elf());
+    }
+
+    #[test]
+    fn wasm_is_not_elf() {
+        let target = Target::from_cfg(
+            "target_family=\"unix\"\ntarget_family=\"wasm\"\ntarget_os=\"emscripten\"\n",
+        )
+        .unwrap();
+        assert!(target.is_family("unix") && target.is_family("wasm"));
+        assert!(!target.is_elf());
+    }
+
+    #[test]
+    fn requires_target_os() {
+        assert!(Target::from_cfg("target_family=\"unix\"\n").is_none());
+    }
+
+    #[test]
+    fn queries_host() {
+        let target = Target::query(None).unwrap();
+        assert_eq!(target.os, env::consts::OS);
+    }
+}
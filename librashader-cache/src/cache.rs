@@ -57,6 +57,7 @@ pub(crate) mod internal {
             Ok(conn) => Ok(conn),
             Err(e) => {
                 let path = &cache_dir.join("librashader.db.1");
+                log::warn!("failed to open shader cache at {path:?}, removing it: {e}");
                 let _ = std::fs::remove_file(path).ok();
                 Err(e)?
             }
@@ -110,13 +111,16 @@ where
     T: Cacheable,
 {
     if bypass_cache {
+        log::debug!("bypassing shader cache for {index}");
         return Ok(load(factory(keys)?)?);
     }
 
-    let cache = internal::get_cache();
-
-    let Ok(cache) = cache else {
-        return Ok(load(factory(keys)?)?);
+    let cache = match internal::get_cache() {
+        Ok(cache) => cache,
+        Err(e) => {
+            log::warn!("shader cache is unavailable, compiling {index} without it: {e}");
+            return factory(keys).and_then(load);
+        }
     };
 
     let hashkey = {
@@ -129,21 +133,32 @@ where
     };
 
     'attempt: {
-        if let Ok(Some(blob)) = internal::get_blob(&cache, index, hashkey.as_bytes()) {
-            let cached = T::from_bytes(&blob).map(&load);
-
-            match cached {
-                None => break 'attempt,
-                Some(Err(_)) => break 'attempt,
-                Some(Ok(res)) => return Ok(res),
+        match internal::get_blob(&cache, index, hashkey.as_bytes()) {
+            Ok(Some(blob)) => {
+                let cached = T::from_bytes(&blob).map(&load);
+
+                match cached {
+                    None | Some(Err(_)) => {
+                        log::debug!("cached {index} object {hashkey} is invalid, recompiling");
+                        break 'attempt;
+                    }
+                    Some(Ok(res)) => {
+                        log::debug!("cache hit for {index} object {hashkey}");
+                        return Ok(res);
+                    }
+                }
             }
+            Ok(None) => log::debug!("cache miss for {index} object {hashkey}"),
+            Err(e) => log::warn!("failed to read {index} object {hashkey} from the cache: {e}"),
         }
     };
 
     let blob = factory(keys)?;
 
     if let Some(slice) = T::to_bytes(&blob) {
-        let _ = internal::set_blob(&cache, index, hashkey.as_bytes(), &slice);
+        if let Err(e) = internal::set_blob(&cache, index, hashkey.as_bytes(), &slice) {
+            log::warn!("failed to write {index} object {hashkey} to the cache: {e}");
+        }
     }
     Ok(load(blob)?)
 }
@@ -166,13 +181,16 @@ where
     T: Cacheable,
 {
     if bypass_cache {
+        log::debug!("bypassing pipeline cache for {index}");
         return Ok(restore_pipeline(None)?);
     }
 
-    let cache = internal::get_cache();
-
-    let Ok(cache) = cache else {
-        return Ok(restore_pipeline(None)?);
+    let cache = match internal::get_cache() {
+        Ok(cache) => cache,
+        Err(e) => {
+            log::warn!("pipeline cache is unavailable, creating {index} without it: {e}");
+            return restore_pipeline(None);
+        }
     };
 
     let hashkey = {
@@ -185,14 +203,19 @@ where
     };
 
     let pipeline = 'attempt: {
-        if let Ok(Some(blob)) = internal::get_blob(&cache, index, hashkey.as_bytes()) {
-            let cached = restore_pipeline(Some(blob));
-            match cached {
-                Ok(res) => {
-                    break 'attempt res;
+        match internal::get_blob(&cache, index, hashkey.as_bytes()) {
+            Ok(Some(blob)) => {
+                let cached = restore_pipeline(Some(blob));
+                match cached {
+                    Ok(res) => {
+                        log::debug!("cache hit for {index} pipeline {hashkey}");
+                        break 'attempt res;
+                    }
+                    _ => log::debug!("cached {index} pipeline {hashkey} is invalid, recreating"),
                 }
-                _ => (),
             }
+            Ok(None) => log::debug!("cache miss for {index} pipeline {hashkey}"),
+            Err(e) => log::warn!("failed to read {index} pipeline {hashkey} from the cache: {e}"),
         }
 
         restore_pipeline(None)?
@@ -202,7 +225,9 @@ where
     if let Ok(state) = fetch_pipeline_state(&pipeline) {
         if let Some(slice) = T::to_bytes(&state) {
             // We don't really care if the transaction fails, just try again next time.
-            let _ = internal::set_blob(&cache, index, hashkey.as_bytes(), &slice);
+            if let Err(e) = internal::set_blob(&cache, index, hashkey.as_bytes(), &slice) {
+                log::debug!("failed to write {index} pipeline {hashkey} to the cache: {e}");
+            }
         }
     }
 
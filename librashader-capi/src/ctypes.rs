@@ -1,19 +1,31 @@
 //! Binding types for the librashader C API.
 use crate::error::LibrashaderError;
+use crate::runtime::job::FilterChainJob;
+use librashader::preprocess::ShaderSource;
 use librashader::presets::context::{Orientation, VideoDriver, WildcardContext};
-use librashader::presets::ShaderPreset;
+use librashader::presets::{ShaderPreset, ShaderPresetPack};
+use librashader::runtime::HdrOutputEncoding;
 use std::mem::MaybeUninit;
 use std::ptr::NonNull;
 
 /// A handle to a shader preset object.
 pub type libra_shader_preset_t = Option<NonNull<ShaderPreset>>;
 
+/// A handle to a shader preset pack object.
+pub type libra_preset_pack_t = Option<NonNull<ShaderPresetPack>>;
+
+/// A handle to a preprocessed shader source object.
+pub type libra_shader_source_t = Option<NonNull<ShaderSource>>;
+
 /// A handle to a preset wildcard context object.
 pub type libra_preset_ctx_t = Option<NonNull<WildcardContext>>;
 
 /// A handle to a librashader error object.
 pub type libra_error_t = Option<NonNull<LibrashaderError>>;
 
+/// A handle to a filter chain that is being created asynchronously.
+pub type libra_filter_chain_job_t = Option<NonNull<FilterChainJob>>;
+
 /// An enum representing orientation for use in preset contexts.
 #[repr(u32)]
 #[derive(Debug, Copy, Clone)]
@@ -66,6 +78,31 @@ impl From<LIBRA_PRESET_CTX_RUNTIME> for VideoDriver {
     }
 }
 
+/// An enum representing the encoding of the built-in HDR output pass for use in filter chain options.
+#[repr(u32)]
+#[derive(Debug, Default, Copy, Clone)]
+pub enum LIBRA_HDR_OUTPUT {
+    /// Do not append the HDR output pass.
+    #[default]
+    None = 0,
+    /// Linear scRGB with Rec. 709 primaries, where 1.0 corresponds to 80 nits.
+    /// The filter chain should be drawn to an `R16G16B16A16_SFLOAT` output.
+    Scrgb,
+    /// Rec. 2020 primaries encoded with the SMPTE ST 2084 (PQ) transfer function.
+    /// The filter chain should be drawn to an `A2B10G10R10_UNORM_PACK32` output.
+    Hdr10,
+}
+
+impl From<LIBRA_HDR_OUTPUT> for Option<HdrOutputEncoding> {
+    fn from(value: LIBRA_HDR_OUTPUT) -> Self {
+        match value {
+            LIBRA_HDR_OUTPUT::None => None,
+            LIBRA_HDR_OUTPUT::Scrgb => Some(HdrOutputEncoding::ScRgb),
+            LIBRA_HDR_OUTPUT::Hdr10 => Some(HdrOutputEncoding::Hdr10),
+        }
+    }
+}
+
 #[cfg(feature = "runtime-opengl")]
 use librashader::runtime::gl::FilterChain as FilterChainGL;
 
@@ -129,6 +166,13 @@ use librashader::runtime::vk::FilterChain as FilterChainVulkan;
 #[cfg_attr(feature = "docsrs", doc(cfg(feature = "runtime-vulkan")))]
 pub type libra_vk_filter_chain_t = Option<NonNull<FilterChainVulkan>>;
 
+#[cfg(feature = "runtime-wgpu")]
+use librashader::runtime::wgpu::FilterChain as FilterChainWgpu;
+/// A handle to a wgpu filter chain.
+#[cfg(feature = "runtime-wgpu")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "runtime-wgpu")))]
+pub type libra_wgpu_filter_chain_t = Option<NonNull<FilterChainWgpu>>;
+
 #[cfg(all(target_os = "macos", feature = "runtime-metal"))]
 use librashader::runtime::mtl::FilterChain as FilterChainMetal;
 
@@ -171,7 +215,8 @@ where
 
 macro_rules! config_set_field {
     (@POINTER $options:ident.$field:ident <- $ptr:ident) => {
-        $options.$field = unsafe { ::std::ptr::addr_of!((*$ptr).$field).read() };
+        // Fields that are not FFI-safe on the Rust side are converted from their C representation.
+        $options.$field = unsafe { ::std::ptr::addr_of!((*$ptr).$field).read() }.into();
     };
     (@POINTER @NEGATIVE $options:ident.$field:ident <- $ptr:ident) => {
         $options.$field = unsafe { !::std::ptr::addr_of!((*$ptr).$field).read() };
@@ -281,6 +326,10 @@ mod __cbindgen_opaque_forward_declarations {
         WildcardContext;
         /// Opaque struct for a shader preset.
         ShaderPreset;
+        /// Opaque struct for a shader preset pack.
+        ShaderPresetPack;
+        /// Opaque struct for a preprocessed shader source.
+        ShaderSource;
         /// Opaque struct for an OpenGL filter chain.
         FilterChainGL;
         /// Opaque struct for a Direct3D 11 filter chain.
@@ -293,5 +342,7 @@ mod __cbindgen_opaque_forward_declarations {
         FilterChainVulkan;
         /// Opaque struct for a Metal filter chain.
         FilterChainMetal;
+        /// Opaque struct for a wgpu filter chain.
+        FilterChainWgpu;
     }
 }
@@ -1,7 +1,11 @@
 //! librashader error C API. (`libra_error_*`).
+use librashader::preprocess::PreprocessError;
+use librashader::presets::{LoadableResource, ParsePresetError, TextureMeta};
+use librashader::reflect::{DiagnosticSeverity, ShaderCompileError, ShaderReflectError};
 use std::any::Any;
 use std::ffi::{c_char, CString};
 use std::mem::MaybeUninit;
+use std::path::{Path, PathBuf};
 use std::ptr::NonNull;
 use thiserror::Error;
 
@@ -37,6 +41,18 @@ pub enum LibrashaderError {
     #[error("There was an error reflecting the shader source.")]
     ShaderReflectError(#[from] librashader::reflect::ShaderReflectError),
 
+    /// An error occurred loading a lookup texture of the preset.
+    #[error("There was an error loading a texture of the preset.")]
+    TextureLoadError(#[source] <TextureMeta as LoadableResource>::Error),
+
+    /// An error occurred reading a shader preset pack.
+    #[error("There was an error reading the shader preset pack.")]
+    PackReadError(#[from] rmp_serde::decode::Error),
+
+    /// An error occurred writing a shader preset pack.
+    #[error("There was an error writing the shader preset pack.")]
+    PackWriteError(#[from] rmp_serde::encode::Error),
+
     /// An invalid shader parameter name was provided.
     #[error("The provided parameter name was invalid.")]
     UnknownShaderParameter(*const c_char),
@@ -89,6 +105,23 @@ pub enum LibrashaderError {
     #[cfg(all(target_vendor = "apple", feature = "runtime-metal"))]
     #[error("There was an error in the Metal filter chain.")]
     MetalFilterError(#[from] librashader::runtime::mtl::error::FilterChainError),
+
+    /// An error occurred with the wgpu filter chain.
+    #[cfg(feature = "runtime-wgpu")]
+    #[cfg_attr(feature = "docsrs", doc(cfg(feature = "runtime-wgpu")))]
+    #[error("There was an error in the wgpu filter chain.")]
+    WgpuFilterError(#[from] librashader::runtime::wgpu::error::FilterChainError),
+
+    /// An error occurred creating wgpu objects over Vulkan objects.
+    #[cfg(feature = "runtime-wgpu-vulkan")]
+    #[cfg_attr(feature = "docsrs", doc(cfg(feature = "runtime-wgpu-vulkan")))]
+    #[error("There was an error creating wgpu objects over Vulkan objects.")]
+    WgpuVulkanError(#[from] crate::runtime::wgpu::WgpuVulkanError),
+
+    /// The operation was cancelled.
+    #[error("The operation was cancelled.")]
+    Cancelled,
+
     /// This error is unreachable.
     #[error("This error is not reachable")]
     Infallible(#[from] std::convert::Infallible),
@@ -106,7 +139,7 @@ pub enum LIBRA_ERRNO {
     /// Error code for an invalid (non-UTF8) string.
     INVALID_STRING = 2,
 
-    /// Error code for a preset parser error.
+    /// Error code for a preset parser or preset pack error.
     PRESET_ERROR = 3,
 
     /// Error code for a preprocessor error.
@@ -120,6 +153,66 @@ pub enum LIBRA_ERRNO {
 
     /// Error code for a runtime error.
     RUNTIME_ERROR = 7,
+
+    /// Error code for a cancelled operation.
+    CANCELLED = 8,
+}
+
+/// Sub-codes that refine the error code of an error.
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum LIBRA_ERROR_SUBCODE {
+    /// No further information is available.
+    NONE = 0,
+    /// A file could not be found.
+    FILE_NOT_FOUND = 1,
+    /// A file could not be read.
+    IO_ERROR = 2,
+    /// A file was not valid UTF-8 or Latin-1 text.
+    INVALID_ENCODING = 3,
+    /// The shader preset could not be parsed.
+    PRESET_SYNTAX_ERROR = 4,
+    /// Shader preset references were nested too deeply.
+    PRESET_REFERENCE_DEPTH = 5,
+    /// The shader source was missing its `#version` header.
+    MISSING_VERSION_HEADER = 6,
+    /// A `#pragma` or `#include` directive in the shader source was invalid.
+    INVALID_DIRECTIVE = 7,
+    /// The image format requested by the shader is unknown or unsupported.
+    UNSUPPORTED_FORMAT = 8,
+    /// The shader source failed to compile.
+    SHADER_COMPILE_ERROR = 9,
+    /// The shader could not be transpiled to the target shader language.
+    SHADER_TRANSPILE_ERROR = 10,
+    /// The bindings or uniforms declared by the shader were invalid.
+    SHADER_SEMANTIC_ERROR = 11,
+    /// The uniform layouts of the vertex and fragment shader did not match.
+    SHADER_LAYOUT_MISMATCH = 12,
+    /// A pass tried to read the output of a pass that is not rendered before it.
+    NON_CAUSAL_FILTER_CHAIN = 13,
+    /// A lookup texture could not be loaded.
+    TEXTURE_LOAD_ERROR = 14,
+    /// The graphics device was lost or removed.
+    DEVICE_LOST = 15,
+}
+
+/// Detailed information about an error.
+///
+/// Fields that are not available for an error are set to `-1`, null, or `0`.
+#[repr(C)]
+pub struct libra_error_details_t {
+    /// The error code, the same as returned by `libra_error_errno`.
+    pub code: LIBRA_ERRNO,
+    /// The sub-code of the error.
+    pub subcode: LIBRA_ERROR_SUBCODE,
+    /// The index of the shader pass the error occurred in, or `-1` if not known.
+    pub pass_index: i32,
+    /// The path of the file the error occurred in, or null if not known.
+    pub path: *mut c_char,
+    /// The line the error occurred on, starting at 1, or `0` if not known.
+    pub line: u32,
+    /// The column the error occurred on, starting at 1, or `0` if not known.
+    pub column: u32,
 }
 
 // Nothing here can use extern_fn because they are lower level than libra_error_t.
@@ -233,13 +326,334 @@ pub unsafe extern "C" fn libra_error_free_string(out: *mut *mut c_char) -> i32 {
     0
 }
 
+/// Function pointer definition for libra_error_get_details
+pub type PFN_libra_error_get_details =
+    extern "C" fn(error: libra_error_t, out: *mut MaybeUninit<libra_error_details_t>) -> i32;
+#[no_mangle]
+/// Writes detailed information about the error into `out`.
+///
+/// Details are taken from the preset parser, preprocessor, shader compiler, and
+/// reflection errors that caused this error, where they record it. In particular,
+/// the pass index is only known for errors found when validating the filter chain,
+/// and the line is only known for preset syntax errors, invalid directives, and
+/// errors reported by the shader compiler.
+///
+/// If `error` is null, this function does nothing and returns 1. Otherwise, this function returns 0.
+/// ## Safety
+///   - `error` must be a valid and initialized instance of `libra_error_t`.
+///   - `out` must be a non-null pointer. The resulting struct must be freed with `libra_error_free_details`.
+pub unsafe extern "C" fn libra_error_get_details(
+    error: libra_error_t,
+    out: *mut MaybeUninit<libra_error_details_t>,
+) -> i32 {
+    let Some(error) = error else { return 1 };
+    if out.is_null() {
+        return 1;
+    }
+
+    unsafe {
+        let error = error.as_ref();
+        let details = error.get_details();
+        let path = details
+            .path
+            .and_then(|path| CString::new(path.to_string_lossy().into_owned()).ok())
+            .map_or(std::ptr::null_mut(), CString::into_raw);
+
+        out.write(MaybeUninit::new(libra_error_details_t {
+            code: error.get_code(),
+            subcode: details.subcode,
+            pass_index: details
+                .pass_index
+                .and_then(|index| i32::try_from(index).ok())
+                .unwrap_or(-1),
+            path,
+            line: details.line,
+            column: details.column,
+        }))
+    }
+    0
+}
+
+/// Function pointer definition for libra_error_free_details
+pub type PFN_libra_error_free_details = extern "C" fn(details: *mut libra_error_details_t) -> i32;
+#[no_mangle]
+/// Frees the path of error details previously written by `libra_error_get_details`.
+///
+/// After freeing, the path will be set to null.
+/// If `details` is null, this function does nothing and returns 1. Otherwise, this function returns 0.
+/// ## Safety
+///   - `details` must be null or point to details previously written by `libra_error_get_details`.
+pub unsafe extern "C" fn libra_error_free_details(details: *mut libra_error_details_t) -> i32 {
+    if details.is_null() {
+        return 1;
+    }
+
+    unsafe {
+        let details = &mut *details;
+        if !details.path.is_null() {
+            drop(CString::from_raw(details.path));
+            details.path = std::ptr::null_mut();
+        }
+    }
+    0
+}
+
+/// The error type returned when loading a lookup texture.
+type ImageError = <TextureMeta as LoadableResource>::Error;
+
+/// Details of an error, before they are exported into a `libra_error_details_t`.
+struct ErrorDetails {
+    subcode: LIBRA_ERROR_SUBCODE,
+    pass_index: Option<usize>,
+    path: Option<PathBuf>,
+    line: u32,
+    column: u32,
+}
+
+impl ErrorDetails {
+    const fn new(subcode: LIBRA_ERROR_SUBCODE) -> Self {
+        ErrorDetails {
+            subcode,
+            pass_index: None,
+            path: None,
+            line: 0,
+            column: 0,
+        }
+    }
+
+    fn io(path: &Path, error: &std::io::Error) -> Self {
+        let subcode = if error.kind() == std::io::ErrorKind::NotFound {
+            LIBRA_ERROR_SUBCODE::FILE_NOT_FOUND
+        } else {
+            LIBRA_ERROR_SUBCODE::IO_ERROR
+        };
+
+        ErrorDetails {
+            path: Some(path.to_path_buf()),
+            ..ErrorDetails::new(subcode)
+        }
+    }
+
+    fn from_preset(error: &ParsePresetError) -> Self {
+        match error {
+            ParsePresetError::LexerError { row, col, .. }
+            | ParsePresetError::ParserError { row, col, .. } => ErrorDetails {
+                line: *row,
+                column: u32::try_from(*col).unwrap_or(0),
+                ..ErrorDetails::new(LIBRA_ERROR_SUBCODE::PRESET_SYNTAX_ERROR)
+            },
+            ParsePresetError::InvalidScaleType(_) => {
+                ErrorDetails::new(LIBRA_ERROR_SUBCODE::PRESET_SYNTAX_ERROR)
+            }
+            ParsePresetError::ExceededReferenceDepth => {
+                ErrorDetails::new(LIBRA_ERROR_SUBCODE::PRESET_REFERENCE_DEPTH)
+            }
+            ParsePresetError::RootPathWasNotAbsolute => {
+                ErrorDetails::new(LIBRA_ERROR_SUBCODE::IO_ERROR)
+            }
+            ParsePresetError::IOError(path, error) => ErrorDetails::io(path, error),
+            ParsePresetError::Utf8Error(_) => {
+                ErrorDetails::new(LIBRA_ERROR_SUBCODE::INVALID_ENCODING)
+            }
+        }
+    }
+
+    fn from_preprocess(error: &PreprocessError) -> Self {
+        match error {
+            PreprocessError::MissingVersionHeader => {
+                ErrorDetails::new(LIBRA_ERROR_SUBCODE::MISSING_VERSION_HEADER)
+            }
+            PreprocessError::IOError(path, error) => ErrorDetails::io(path, error),
+            PreprocessError::EncodingError(path) => ErrorDetails {
+                path: Some(path.clone()),
+                ..ErrorDetails::new(LIBRA_ERROR_SUBCODE::INVALID_ENCODING)
+            },
+            PreprocessError::UnexpectedEol(line) => ErrorDetails {
+                line: u32::try_from(*line).unwrap_or(0),
+                ..ErrorDetails::new(LIBRA_ERROR_SUBCODE::INVALID_DIRECTIVE)
+            },
+            PreprocessError::UnexpectedEof
+            | PreprocessError::PragmaParseError(_)
+            | PreprocessError::DuplicatePragmaError(_)
+            | PreprocessError::InvalidStage => {
+                ErrorDetails::new(LIBRA_ERROR_SUBCODE::INVALID_DIRECTIVE)
+            }
+            PreprocessError::UnknownImageFormat => {
+                ErrorDetails::new(LIBRA_ERROR_SUBCODE::UNSUPPORTED_FORMAT)
+            }
+        }
+    }
+
+    fn from_compile(error: &ShaderCompileError) -> Self {
+        let details = match error.without_pass() {
+            ShaderCompileError::GlslangError(_) => {
+                let location = error
+                    .diagnostics()
+                    .into_iter()
+                    .find(|diagnostic| diagnostic.severity == DiagnosticSeverity::Error);
+                let (path, line, column) = location.map_or((None, 0, 0), |diagnostic| {
+                    (
+                        diagnostic.file.map(PathBuf::from),
+                        diagnostic.line.unwrap_or(0),
+                        diagnostic.column.unwrap_or(0),
+                    )
+                });
+                ErrorDetails {
+                    path,
+                    line,
+                    column,
+                    ..ErrorDetails::new(LIBRA_ERROR_SUBCODE::SHADER_COMPILE_ERROR)
+                }
+            }
+            ShaderCompileError::CompilerInitError => {
+                ErrorDetails::new(LIBRA_ERROR_SUBCODE::SHADER_COMPILE_ERROR)
+            }
+            _ => ErrorDetails::new(LIBRA_ERROR_SUBCODE::SHADER_TRANSPILE_ERROR),
+        };
+
+        ErrorDetails {
+            pass_index: error.pass_index(),
+            ..details
+        }
+    }
+
+    fn from_reflect(error: &ShaderReflectError) -> Self {
+        match error {
+            ShaderReflectError::NonCausalFilterChain { pass, .. } => ErrorDetails {
+                pass_index: Some(*pass),
+                ..ErrorDetails::new(LIBRA_ERROR_SUBCODE::NON_CAUSAL_FILTER_CHAIN)
+            },
+            ShaderReflectError::MismatchedOffset { pass, .. }
+            | ShaderReflectError::MismatchedSize { pass, .. } => ErrorDetails {
+                pass_index: Some(*pass),
+                ..ErrorDetails::new(LIBRA_ERROR_SUBCODE::SHADER_LAYOUT_MISMATCH)
+            },
+            ShaderReflectError::MismatchedUniformBuffer { .. } => {
+                ErrorDetails::new(LIBRA_ERROR_SUBCODE::SHADER_LAYOUT_MISMATCH)
+            }
+            _ => ErrorDetails::new(LIBRA_ERROR_SUBCODE::SHADER_SEMANTIC_ERROR),
+        }
+    }
+
+    #[cfg(all(
+        target_os = "windows",
+        any(
+            feature = "runtime-d3d11",
+            feature = "runtime-d3d12",
+            feature = "runtime-d3d9"
+        )
+    ))]
+    fn from_hresult(error: &windows::core::Error) -> Self {
+        use windows::core::HRESULT;
+        const DEVICE_LOST: [HRESULT; 4] = [
+            // DXGI_ERROR_DEVICE_REMOVED
+            HRESULT(0x887A0005_u32 as i32),
+            // DXGI_ERROR_DEVICE_HUNG
+            HRESULT(0x887A0006_u32 as i32),
+            // DXGI_ERROR_DEVICE_RESET
+            HRESULT(0x887A0007_u32 as i32),
+            // D3DERR_DEVICELOST
+            HRESULT(0x88760868_u32 as i32),
+        ];
+
+        if DEVICE_LOST.contains(&error.code()) {
+            ErrorDetails::new(LIBRA_ERROR_SUBCODE::DEVICE_LOST)
+        } else {
+            ErrorDetails::new(LIBRA_ERROR_SUBCODE::NONE)
+        }
+    }
+
+    fn from_image(error: &ImageError) -> Self {
+        match error {
+            ImageError::IoError(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                ErrorDetails::new(LIBRA_ERROR_SUBCODE::FILE_NOT_FOUND)
+            }
+            ImageError::Unsupported(_) => {
+                ErrorDetails::new(LIBRA_ERROR_SUBCODE::UNSUPPORTED_FORMAT)
+            }
+            _ => ErrorDetails::new(LIBRA_ERROR_SUBCODE::TEXTURE_LOAD_ERROR),
+        }
+    }
+}
+
+/// Get the details of the `FilterChainError` of a runtime.
+///
+/// Every runtime wraps the same preset, preprocessor, compiler, reflection and image errors.
+/// Errors specific to a runtime can be matched by passing extra match arms in braces.
+#[allow(unused_macros)]
+macro_rules! filter_chain_details {
+    ($runtime:ident, $error:expr $(, { $($pattern:pat => $details:expr),* $(,)? })?) => {{
+        use librashader::runtime::$runtime::error::FilterChainError;
+        match $error {
+            FilterChainError::ShaderPresetError(error) => ErrorDetails::from_preset(error),
+            FilterChainError::ShaderPreprocessError(error) => ErrorDetails::from_preprocess(error),
+            FilterChainError::ShaderCompileError(error) => ErrorDetails::from_compile(error),
+            FilterChainError::ShaderReflectError(error) => ErrorDetails::from_reflect(error),
+            FilterChainError::LutLoadError(error) => ErrorDetails::from_image(error),
+            $($($pattern => $details,)*)?
+            _ => ErrorDetails::new(LIBRA_ERROR_SUBCODE::NONE),
+        }
+    }};
+}
+
 impl LibrashaderError {
+    fn get_details(&self) -> ErrorDetails {
+        match self {
+            LibrashaderError::PresetError(error) => ErrorDetails::from_preset(error),
+            LibrashaderError::PreprocessError(error) => ErrorDetails::from_preprocess(error),
+            LibrashaderError::ShaderCompileError(error) => ErrorDetails::from_compile(error),
+            LibrashaderError::ShaderReflectError(error) => ErrorDetails::from_reflect(error),
+            LibrashaderError::TextureLoadError(error) => ErrorDetails::from_image(error),
+            #[cfg(feature = "runtime-opengl")]
+            LibrashaderError::OpenGlFilterError(error) => filter_chain_details!(gl, error),
+            #[cfg(all(target_os = "windows", feature = "runtime-d3d11"))]
+            LibrashaderError::D3D11FilterError(error) => filter_chain_details!(d3d11, error, {
+                FilterChainError::Direct3DError(error) => ErrorDetails::from_hresult(error),
+            }),
+            #[cfg(all(target_os = "windows", feature = "runtime-d3d12"))]
+            LibrashaderError::D3D12FilterError(error) => filter_chain_details!(d3d12, error, {
+                FilterChainError::Direct3DError(error) => ErrorDetails::from_hresult(error),
+            }),
+            #[cfg(all(target_os = "windows", feature = "runtime-d3d9"))]
+            LibrashaderError::D3D9FilterError(error) => filter_chain_details!(d3d9, error, {
+                FilterChainError::Direct3DError(error) => ErrorDetails::from_hresult(error),
+            }),
+            #[cfg(feature = "runtime-vulkan")]
+            LibrashaderError::VulkanFilterError(error) => filter_chain_details!(vk, error, {
+                FilterChainError::VulkanResult(ash::vk::Result::ERROR_DEVICE_LOST) => {
+                    ErrorDetails::new(LIBRA_ERROR_SUBCODE::DEVICE_LOST)
+                }
+            }),
+            #[cfg(all(target_vendor = "apple", feature = "runtime-metal"))]
+            LibrashaderError::MetalFilterError(error) => filter_chain_details!(mtl, error),
+            #[cfg(feature = "runtime-wgpu")]
+            LibrashaderError::WgpuFilterError(error) => filter_chain_details!(wgpu, error),
+            #[cfg(feature = "runtime-wgpu-vulkan")]
+            LibrashaderError::WgpuVulkanError(error) => {
+                use crate::runtime::wgpu::WgpuVulkanError;
+                match error {
+                    WgpuVulkanError::DeviceError(wgpu::hal::DeviceError::Lost) => {
+                        ErrorDetails::new(LIBRA_ERROR_SUBCODE::DEVICE_LOST)
+                    }
+                    WgpuVulkanError::UnsupportedFormat(_) => {
+                        ErrorDetails::new(LIBRA_ERROR_SUBCODE::UNSUPPORTED_FORMAT)
+                    }
+                    _ => ErrorDetails::new(LIBRA_ERROR_SUBCODE::NONE),
+                }
+            }
+            _ => ErrorDetails::new(LIBRA_ERROR_SUBCODE::NONE),
+        }
+    }
+
     pub(crate) const fn get_code(&self) -> LIBRA_ERRNO {
         match self {
             LibrashaderError::UnknownError(_) => LIBRA_ERRNO::UNKNOWN_ERROR,
             LibrashaderError::InvalidParameter(_) => LIBRA_ERRNO::INVALID_PARAMETER,
             LibrashaderError::InvalidString(_) => LIBRA_ERRNO::INVALID_STRING,
-            LibrashaderError::PresetError(_) => LIBRA_ERRNO::PRESET_ERROR,
+            LibrashaderError::PresetError(_)
+            | LibrashaderError::TextureLoadError(_)
+            | LibrashaderError::PackReadError(_)
+            | LibrashaderError::PackWriteError(_) => LIBRA_ERRNO::PRESET_ERROR,
             LibrashaderError::PreprocessError(_) => LIBRA_ERRNO::PREPROCESS_ERROR,
             LibrashaderError::ShaderCompileError(_) | LibrashaderError::ShaderReflectError(_) => {
                 LIBRA_ERRNO::REFLECT_ERROR
@@ -257,6 +671,11 @@ impl LibrashaderError {
             LibrashaderError::VulkanFilterError(_) => LIBRA_ERRNO::RUNTIME_ERROR,
             #[cfg(all(target_vendor = "apple", feature = "runtime-metal"))]
             LibrashaderError::MetalFilterError(_) => LIBRA_ERRNO::RUNTIME_ERROR,
+            #[cfg(feature = "runtime-wgpu")]
+            LibrashaderError::WgpuFilterError(_) => LIBRA_ERRNO::RUNTIME_ERROR,
+            #[cfg(feature = "runtime-wgpu-vulkan")]
+            LibrashaderError::WgpuVulkanError(_) => LIBRA_ERRNO::RUNTIME_ERROR,
+            LibrashaderError::Cancelled => LIBRA_ERRNO::CANCELLED,
             LibrashaderError::Infallible(_) => LIBRA_ERRNO::UNKNOWN_ERROR,
         }
     }
@@ -310,3 +729,47 @@ pub(crate) use assert_non_null;
 
 // pub(crate) use assert_some;
 pub(crate) use assert_some_ptr;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use librashader::presets::{ShaderPreset, ShaderPresetPack};
+    use librashader::reflect::cross::SpirvCross;
+    use librashader::reflect::targets::SPIRV;
+    use librashader::reflect::{CompilePresetTarget, SpirvCompilation};
+    use std::ffi::CStr;
+
+    #[test]
+    fn compile_error_details() {
+        let preset = ShaderPreset::try_parse("../test/diagnostics/pass_error.slangp").unwrap();
+        let pack =
+            ShaderPresetPack::load_from_preset::<Box<dyn std::error::Error + Send + Sync>>(preset)
+                .unwrap();
+        let Err(error) =
+            SPIRV::compile_preset_passes::<SpirvCompilation, SpirvCross, LibrashaderError>(
+                pack.passes,
+                pack.textures.iter().map(|texture| &texture.meta),
+            )
+        else {
+            panic!("the second pass should fail to compile");
+        };
+
+        let mut error = error.export();
+        let mut details = MaybeUninit::uninit();
+        unsafe {
+            assert_eq!(libra_error_get_details(error, &mut details), 0);
+            let mut details = details.assume_init();
+            assert!(matches!(details.code, LIBRA_ERRNO::REFLECT_ERROR));
+            assert_eq!(details.subcode, LIBRA_ERROR_SUBCODE::SHADER_COMPILE_ERROR);
+            assert_eq!(details.pass_index, 1);
+            assert_eq!(details.line, 28);
+            assert!(CStr::from_ptr(details.path)
+                .to_str()
+                .unwrap()
+                .ends_with("pass_error.slang"));
+
+            assert_eq!(libra_error_free_details(&mut details), 0);
+            assert_eq!(libra_error_free(&mut error), 0);
+        }
+    }
+}
@@ -48,10 +48,18 @@
 //! }
 //! ```
 //!
+//! Hosts that need to react to specific failures can use `libra_error_get_details`, which refines the error code with a
+//! sub-code such as `LIBRA_ERROR_SUBCODE_FILE_NOT_FOUND` or `LIBRA_ERROR_SUBCODE_DEVICE_LOST`, and reports the
+//! pass index, file path, line, and column of the error where they are known.
+//!
 //! There is a case to be made for skipping error checking for `*_filter_chain_frame` due to performance reasons,
 //! but only if you are certain that the safety invariants are upheld on each call. Failure to check for errors
 //! may result in **undefined behaviour** stemming from failure to uphold safety invariants.
 //!
+//! ## Logging
+//! Diagnostic messages such as cache hits and misses, shader compile timings, and shader compiler warnings can be
+//! received by setting a callback with `libra_set_log_callback`. Logging is disabled until a callback is set.
+//!
 //! ## Thread safety
 //!
 //! Except for the metal runtime, it is in general, **safe** to create a filter chain instance from a different thread,
@@ -65,6 +73,31 @@
 //!
 //! You must ensure that only thread has access to a created filter pass **before** you call `*_frame`. `*_frame` may only be
 //! called from one thread at a time.
+//!
+//! ## Asynchronous filter chain creation
+//!
+//! The Vulkan, Direct3D 11, Direct3D 12, and wgpu runtimes can create filter chains on the librashader worker threads
+//! with `*_filter_chain_create_async`, which reports the progress of each pass and can be cancelled with the
+//! `libra_filter_chain_job_*` functions. The other runtimes do not provide asynchronous creation, because their
+//! filter chains can not be created away from the thread they are used on:
+//!
+//! * An OpenGL filter chain must be created on a thread where the OpenGL context of the drawing thread is current.
+//! * Direct3D 9 has no deferred filter chain creation, and its devices are not free-threaded unless created with
+//!   `D3DCREATE_MULTITHREADED`.
+//! * Metal objects can not be sent across threads, so a Metal filter chain can not be returned from a worker thread.
+//!
+//! ## Runtimes
+//!
+//! The C API provides the OpenGL, Vulkan, Direct3D 9, 11 and 12, Metal, and wgpu runtimes.
+//!
+//! The `libra_wgpu_*` functions take handles to `wgpu` objects owned by librashader. With the `runtime-wgpu-vulkan`
+//! feature, C hosts create these handles over their own Vulkan instance, device, and images with
+//! `libra_wgpu_vk_create_device`, `libra_wgpu_device_create_vk`, and `libra_wgpu_texture_create_vk`. Hosts written
+//! in Rust may instead pass `wgpu` objects shared with `Arc::into_raw`, which must be created by the same version of
+//! wgpu that librashader is built with.
+//!
+//! The handles are not interchangeable with the handles of `webgpu.h`, which point into the internal state of the
+//! wgpu-native library that created them. Hosts using wgpu-native should share the underlying Vulkan objects instead.
 #![cfg_attr(feature = "docsrs", feature(doc_cfg))]
 #![allow(non_camel_case_types)]
 #![deny(unsafe_op_in_unsafe_fn)]
@@ -75,12 +108,10 @@ extern crate alloc;
 pub mod ctypes;
 pub mod error;
 mod ffi;
+pub mod logging;
+pub mod pack;
 pub mod presets;
-
-#[cfg(feature = "reflect-unstable")]
-#[doc(hidden)]
 pub mod reflect;
-
 pub mod runtime;
 pub mod version;
 pub mod wildcard;
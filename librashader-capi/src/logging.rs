@@ -0,0 +1,166 @@
+//! librashader logging C API (`libra_set_log_callback`).
+use crate::error::LibrashaderError;
+use crate::ffi::extern_fn;
+use std::ffi::{c_char, c_void, CString};
+use std::sync::{OnceLock, RwLock};
+
+/// The verbosity of a log message.
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum LIBRA_LOG_LEVEL {
+    /// No messages are logged.
+    OFF = 0,
+    /// Errors that librashader could not recover from.
+    ERROR = 1,
+    /// Unexpected conditions that librashader recovered from, such as shader compiler
+    /// warnings or an unavailable cache.
+    WARN = 2,
+    /// General information.
+    INFO = 3,
+    /// Diagnostic information, such as cache hits and misses, compile timings, and pipeline
+    /// recompilations.
+    DEBUG = 4,
+    /// Very verbose diagnostic information, such as the files that are read.
+    TRACE = 5,
+}
+
+impl From<LIBRA_LOG_LEVEL> for log::LevelFilter {
+    fn from(value: LIBRA_LOG_LEVEL) -> Self {
+        match value {
+            LIBRA_LOG_LEVEL::OFF => log::LevelFilter::Off,
+            LIBRA_LOG_LEVEL::ERROR => log::LevelFilter::Error,
+            LIBRA_LOG_LEVEL::WARN => log::LevelFilter::Warn,
+            LIBRA_LOG_LEVEL::INFO => log::LevelFilter::Info,
+            LIBRA_LOG_LEVEL::DEBUG => log::LevelFilter::Debug,
+            LIBRA_LOG_LEVEL::TRACE => log::LevelFilter::Trace,
+        }
+    }
+}
+
+impl From<log::Level> for LIBRA_LOG_LEVEL {
+    fn from(value: log::Level) -> Self {
+        match value {
+            log::Level::Error => LIBRA_LOG_LEVEL::ERROR,
+            log::Level::Warn => LIBRA_LOG_LEVEL::WARN,
+            log::Level::Info => LIBRA_LOG_LEVEL::INFO,
+            log::Level::Debug => LIBRA_LOG_LEVEL::DEBUG,
+            log::Level::Trace => LIBRA_LOG_LEVEL::TRACE,
+        }
+    }
+}
+
+/// A function that receives log messages from librashader.
+///
+/// `target` is the name of the module that emitted the message, such as `librashader_cache::cache`,
+/// and `message` is the formatted message without a trailing newline. Both strings are only valid
+/// for the duration of the call. `userdata` is the pointer that was passed to `libra_set_log_callback`.
+pub type libra_log_callback_t = Option<
+    unsafe extern "C" fn(
+        level: LIBRA_LOG_LEVEL,
+        target: *const c_char,
+        message: *const c_char,
+        userdata: *mut c_void,
+    ),
+>;
+
+#[derive(Copy, Clone)]
+struct LogSink {
+    callback: unsafe extern "C" fn(LIBRA_LOG_LEVEL, *const c_char, *const c_char, *mut c_void),
+    userdata: *mut c_void,
+}
+
+// SAFETY: the host guarantees that the callback and userdata may be used from any thread.
+unsafe impl Send for LogSink {}
+unsafe impl Sync for LogSink {}
+
+static SINK: RwLock<Option<LogSink>> = RwLock::new(None);
+static INSTALLED: OnceLock<bool> = OnceLock::new();
+
+struct CallbackLogger;
+
+impl log::Log for CallbackLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &log::Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        // Copy the sink out so the lock is not held while the callback runs, which would
+        // deadlock if the callback calls back into librashader.
+        let Some(sink) = SINK.read().ok().and_then(|sink| *sink) else {
+            return;
+        };
+
+        let target = to_c_string(record.target().to_string());
+        let message = to_c_string(record.args().to_string());
+        unsafe {
+            (sink.callback)(
+                record.level().into(),
+                target.as_ptr(),
+                message.as_ptr(),
+                sink.userdata,
+            )
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+fn to_c_string(string: String) -> CString {
+    CString::new(string).unwrap_or_else(|e| {
+        let mut string = e.into_vec();
+        string.retain(|&c| c != 0);
+        // SAFETY: all interior NULs were removed.
+        unsafe { CString::from_vec_unchecked(string) }
+    })
+}
+
+static LOGGER: CallbackLogger = CallbackLogger;
+
+extern_fn! {
+    /// Set the function that receives log messages from librashader.
+    ///
+    /// Messages more verbose than `level` are discarded before they are formatted. Passing a null
+    /// `callback` or `LIBRA_LOG_LEVEL_OFF` disables logging. Subsequent calls replace the previous callback
+    /// and level.
+    ///
+    /// The callback may be called from any thread that calls into librashader, including threads
+    /// internal to librashader, and may be called concurrently. The callback may call back into
+    /// librashader. A message that was being logged on another thread while the callback was
+    /// replaced may still be delivered to the previous callback.
+    ///
+    /// ## Safety
+    /// - `callback` must be null, or a valid function pointer that is safe to call from any thread
+    ///   until it is replaced by a subsequent call to `libra_set_log_callback`, and every call
+    ///   into librashader on other threads that started before then has returned.
+    /// - `userdata` is passed to `callback` as is, and must be safe to use from any thread.
+    /// ## Returns
+    /// - If librashader was linked into a Rust program that already set up its own logger,
+    ///   this function returns `LIBRA_ERR_UNKNOWN_ERROR`.
+    fn libra_set_log_callback(
+        level: LIBRA_LOG_LEVEL,
+        callback: libra_log_callback_t,
+        userdata: *mut c_void
+    ) {
+        if !*INSTALLED.get_or_init(|| log::set_logger(&LOGGER).is_ok()) {
+            return Err(LibrashaderError::UnknownError(Box::new(
+                "a logger was already installed",
+            )));
+        }
+
+        let sink = callback.map(|callback| LogSink { callback, userdata });
+        let level = if sink.is_some() {
+            level.into()
+        } else {
+            log::LevelFilter::Off
+        };
+
+        if let Ok(mut current) = SINK.write() {
+            *current = sink;
+        }
+        log::set_max_level(level);
+    }
+}
@@ -0,0 +1,162 @@
+//! librashader preset pack C API (`libra_preset_pack_*`).
+//!
+//! A shader preset pack holds a shader preset with the source of every pass and the contents of
+//! every lookup texture loaded in memory. Packs are stored as MessagePack, the same format
+//! written by `librashader-cli pack --format msgpack`, and can be used to create a filter chain
+//! without any filesystem access.
+use crate::ctypes::{libra_preset_pack_t, libra_shader_preset_t};
+use crate::error::{assert_non_null, assert_some_ptr, LibrashaderError};
+use crate::ffi::extern_fn;
+use librashader::presets::{LoadableResource, PassMeta, ShaderPresetPack, TextureMeta};
+use std::mem::MaybeUninit;
+use std::ptr::NonNull;
+use std::slice;
+
+const _: () = crate::assert_thread_safe::<ShaderPresetPack>();
+
+/// A buffer holding a serialized shader preset pack.
+#[repr(C)]
+pub struct libra_preset_pack_buffer_t {
+    /// A pointer to the bytes of the serialized pack.
+    pub data: *const u8,
+    /// The number of bytes in the buffer. This field
+    /// is readonly, and changing it will lead to undefined
+    /// behaviour on free.
+    pub length: u64,
+}
+
+extern_fn! {
+    /// Load the passes and lookup textures of a shader preset into a shader preset pack.
+    ///
+    /// The shader preset is immediately invalidated and must be recreated after
+    /// the pack is created.
+    ///
+    /// ## Safety
+    ///  - `preset` must be either null, or valid and aligned.
+    ///  - `out` must be either null, or an aligned pointer to an uninitialized or invalid `libra_preset_pack_t`.
+    /// ## Returns
+    ///  - If any parameters are null, `out` is unchanged, and this function returns `LIBRA_ERR_INVALID_PARAMETER`.
+    fn libra_preset_pack_create_from_preset(
+        preset: *mut libra_shader_preset_t,
+        out: *mut MaybeUninit<libra_preset_pack_t>
+    ) {
+        assert_non_null!(preset);
+        assert_non_null!(out);
+
+        let preset = unsafe {
+            let preset_ptr = &mut *preset;
+            let preset = preset_ptr.take();
+            Box::from_raw(preset.unwrap().as_ptr())
+        };
+
+        let pack = ShaderPresetPack::load_from_preset_with(
+            *preset,
+            |_, path| PassMeta::load(path).map_err(LibrashaderError::from),
+            |_, path| TextureMeta::load(path).map_err(LibrashaderError::TextureLoadError),
+        )?;
+
+        unsafe {
+            out.write(MaybeUninit::new(NonNull::new(Box::into_raw(Box::new(
+                pack,
+            )))))
+        }
+    }
+}
+
+extern_fn! {
+    /// Load a shader preset pack from a buffer in memory.
+    ///
+    /// The buffer must contain a pack serialized as MessagePack, such as one written by
+    /// `libra_preset_pack_write`. The buffer is not retained after this function returns.
+    ///
+    /// ## Safety
+    ///  - `buffer` must be either null, or a valid, aligned pointer to at least `length` bytes.
+    ///  - `out` must be either null, or an aligned pointer to an uninitialized or invalid `libra_preset_pack_t`.
+    /// ## Returns
+    ///  - If any parameters are null, `out` is unchanged, and this function returns `LIBRA_ERR_INVALID_PARAMETER`.
+    fn libra_preset_pack_load(
+        buffer: *const u8,
+        length: usize,
+        out: *mut MaybeUninit<libra_preset_pack_t>
+    ) {
+        assert_non_null!(buffer);
+        assert_non_null!(out);
+
+        let buffer = unsafe { slice::from_raw_parts(buffer, length) };
+        let pack: ShaderPresetPack = rmp_serde::from_slice(buffer)?;
+
+        unsafe {
+            out.write(MaybeUninit::new(NonNull::new(Box::into_raw(Box::new(
+                pack,
+            )))))
+        }
+    }
+}
+
+extern_fn! {
+    /// Serialize the shader preset pack into a buffer as MessagePack.
+    ///
+    /// The pack is not invalidated, and can still be used to create a filter chain.
+    ///
+    /// ## Safety
+    /// - `pack` must be null or a valid and aligned pointer to a `libra_preset_pack_t`.
+    /// - `out` must be an aligned pointer to a `libra_preset_pack_buffer_t`.
+    /// - The output struct should be treated as immutable. Mutating any struct fields
+    ///   in the returned struct may at best cause memory leaks, and at worse
+    ///   cause undefined behaviour when later freed.
+    /// - The output buffer must be freed with `libra_preset_pack_free_buffer`.
+    fn libra_preset_pack_write(
+        pack: *const libra_preset_pack_t,
+        out: *mut MaybeUninit<libra_preset_pack_buffer_t>
+    ) |pack| {
+        assert_some_ptr!(pack);
+        assert_non_null!(out);
+
+        let bytes = rmp_serde::to_vec(pack)?.into_boxed_slice();
+        let (data, len) = crate::ffi::boxed_slice_into_raw_parts(bytes);
+
+        unsafe {
+            out.write(MaybeUninit::new(libra_preset_pack_buffer_t {
+                data: data.cast_const(),
+                length: len as u64,
+            }));
+        }
+    }
+}
+
+extern_fn! {
+    /// Free a buffer previously written by `libra_preset_pack_write`.
+    ///
+    /// Like `libra_preset_free_runtime_params`, this function takes the struct directly.
+    ///
+    /// ## Safety
+    /// - `buffer` must have been returned by `libra_preset_pack_write`, with its fields unchanged.
+    /// - Accessing the data of the buffer after it has been freed is a use-after-free and is
+    ///   immediate undefined behaviour.
+    fn libra_preset_pack_free_buffer(buffer: libra_preset_pack_buffer_t) {
+        unsafe {
+            drop(crate::ffi::boxed_slice_from_raw_parts(
+                buffer.data.cast_mut(),
+                buffer.length as usize,
+            ));
+        }
+    }
+}
+
+extern_fn! {
+    /// Free the shader preset pack.
+    ///
+    /// If `pack` is null, this function does nothing. The resulting value in `pack` then becomes
+    /// null.
+    ///
+    /// ## Safety
+    /// - `pack` must be a valid and aligned pointer to a `libra_preset_pack_t`.
+    fn libra_preset_pack_free(pack: *mut libra_preset_pack_t) {
+        assert_non_null!(pack);
+        unsafe {
+            let pack_ptr = &mut *pack;
+            let pack = pack_ptr.take();
+            drop(Box::from_raw(pack.unwrap().as_ptr()));
+        }
+    }
+}
@@ -2,6 +2,7 @@
 use crate::ctypes::{libra_preset_ctx_t, libra_shader_preset_t};
 use crate::error::{assert_non_null, assert_some_ptr, LibrashaderError};
 use crate::ffi::extern_fn;
+use librashader::preprocess::ShaderParameter;
 use librashader::presets::ShaderPreset;
 use std::ffi::{c_char, CStr, CString};
 use std::mem::MaybeUninit;
@@ -37,6 +38,38 @@ pub struct libra_preset_param_t {
     pub step: f32,
 }
 
+impl libra_preset_param_list_t {
+    /// Collect shader parameters into a parameter list that must be freed with
+    /// `libra_preset_free_runtime_params`.
+    pub(crate) fn from_params(
+        params: impl IntoIterator<Item = ShaderParameter>,
+    ) -> Result<Self, LibrashaderError> {
+        let mut values = Vec::new();
+        for param in params {
+            let name = CString::new(param.id.to_string())
+                .map_err(|err| LibrashaderError::UnknownError(Box::new(err)))?;
+            let description = CString::new(param.description)
+                .map_err(|err| LibrashaderError::UnknownError(Box::new(err)))?;
+            values.push(libra_preset_param_t {
+                name: name.into_raw().cast_const(),
+                description: description.into_raw().cast_const(),
+                initial: param.initial,
+                minimum: param.minimum,
+                maximum: param.maximum,
+                step: param.step,
+            })
+        }
+
+        let values = values.into_boxed_slice();
+        let (parts, len) = crate::ffi::boxed_slice_into_raw_parts(values);
+
+        Ok(libra_preset_param_list_t {
+            parameters: parts,
+            length: len as u64,
+        })
+    }
+}
+
 extern_fn! {
     /// Load a preset.
     ///
@@ -202,30 +235,10 @@ extern_fn! {
         assert_non_null!(out);
 
         let iter = librashader::presets::get_parameter_meta(preset)?;
-        let mut values = Vec::new();
-        for param in iter {
-            let name = CString::new(param.id.to_string())
-            .map_err(|err| LibrashaderError::UnknownError(Box::new(err)))?;
-            let description = CString::new(param.description)
-            .map_err(|err| LibrashaderError::UnknownError(Box::new(err)))?;
-            values.push(libra_preset_param_t {
-                name: name.into_raw().cast_const(),
-                description: description.into_raw().cast_const(),
-                initial: param.initial,
-                minimum: param.minimum,
-                maximum: param.maximum,
-                step: param.step
-            })
-        }
-
-        let values = values.into_boxed_slice();
-        let (parts, len) = crate::ffi::boxed_slice_into_raw_parts(values);
+        let list = libra_preset_param_list_t::from_params(iter)?;
 
         unsafe {
-            out.write(MaybeUninit::new(libra_preset_param_list_t {
-                parameters: parts,
-                length: len as u64,
-            }));
+            out.write(MaybeUninit::new(list));
         }
     }
 }
@@ -1,79 +1,419 @@
-use crate::error;
-
-use librashader::presets::{PassConfig, ShaderPreset};
+//! librashader shader preprocessing, transpilation and reflection C API (`libra_shader_*`).
+//!
+//! These functions expose the shader compiler used by the filter chains, for hosts that
+//! drive their own graphics pipeline or want to inspect a shader preset ahead of time.
+use crate::ctypes::{config_struct, libra_shader_preset_t, libra_shader_source_t, FromUninit};
+use crate::error::{assert_non_null, assert_some_ptr, LibrashaderError};
+use crate::ffi::extern_fn;
+use crate::presets::libra_preset_param_list_t;
+use crate::LIBRASHADER_API_VERSION;
+use librashader::preprocess::ShaderSource;
+use librashader::reflect::cross::{
+    glsl_version_from_number, hlsl_shader_model_from_number, msl_version_from_number, SpirvCross,
+};
+use librashader::reflect::naga::NagaLoweringOptions;
+use librashader::reflect::schema::PassReflection;
 use librashader::reflect::semantics::ShaderSemantics;
 use librashader::reflect::targets::SPIRV;
-use librashader::reflect::{CompileShader, ReflectShader, ShaderCompilerOutput, ShaderReflection};
-use librashader::{FilterMode, WrapMode};
-
-use librashader::reflect::helper::image::{Image, UVDirection, RGBA8};
-use librashader::reflect::SpirvCompilation;
-
-pub(crate) struct LookupTexture {
-    wrap_mode: WrapMode,
-    /// The filter mode to use when sampling the texture.
-    filter_mode: FilterMode,
-    /// Whether or not to generate mipmaps for this texture.
-    mipmap: bool,
-    /// The image data of the texture
-    image: Image,
-}
-
-pub(crate) struct PassReflection {
-    reflection: ShaderReflection,
-    config: PassConfig,
-    spirv: ShaderCompilerOutput<Vec<u32>>,
-}
-pub(crate) struct FilterReflection {
-    semantics: ShaderSemantics,
-    passes: Vec<PassReflection>,
-    textures: Vec<LookupTexture>,
-}
-
-impl FilterReflection {
-    pub fn load_from_preset(
-        preset: ShaderPreset,
-        direction: UVDirection,
-    ) -> Result<FilterReflection, error::LibrashaderError> {
-        let (passes, textures) = (preset.passes, preset.textures);
-
-        let (passes, semantics) = librashader::reflect::helper::compile_preset_passes::<
-            Glslang,
-            SPIRV,
-            SpirvCompilation,
-            error::LibrashaderError,
-        >(passes, &textures)?;
-
-        let mut reflects = Vec::new();
-
-        for (index, (config, _source, mut compiler)) in passes.into_iter().enumerate() {
-            let reflection = compiler.reflect(index, &semantics)?;
-            let words = compiler.compile(None)?;
-            reflects.push(PassReflection {
-                reflection,
-                config,
-                spirv: words,
-            })
-        }
+use librashader::reflect::transpile::{self, TranspileOutput, TranspileTarget};
+use librashader::reflect::{
+    FromCompilation, ReflectShader, ShaderCompilerOutput, SpirvCompilation,
+};
+use std::ffi::{c_char, CStr};
+use std::mem::MaybeUninit;
+use std::ptr::NonNull;
+
+const _: () = crate::assert_thread_safe::<ShaderSource>();
+
+/// An enum representing the shader languages a shader can be transpiled to.
+#[repr(u32)]
+#[derive(Debug, Copy, Clone)]
+pub enum LIBRA_SHADER_TARGET {
+    /// GLSL, for OpenGL.
+    GLSL = 0,
+    /// HLSL, for Direct3D.
+    HLSL,
+    /// Metal Shading Language.
+    MSL,
+    /// WGSL, for WebGPU.
+    WGSL,
+    /// SPIR-V, for Vulkan.
+    SPIRV,
+}
+
+/// A buffer of shader output.
+///
+/// The buffer is always followed by a NUL terminator that is not included in `length`,
+/// so text output can be read as a C string. SPIR-V output is the raw module in native
+/// byte order.
+#[repr(C)]
+pub struct libra_shader_blob_t {
+    /// A pointer to the bytes of the buffer.
+    pub data: *const u8,
+    /// The number of bytes in the buffer. This field
+    /// is readonly, and changing it will lead to undefined
+    /// behaviour on free.
+    pub length: u64,
+}
+
+/// The vertex and fragment output of a shader.
+#[repr(C)]
+pub struct libra_shader_output_t {
+    /// The vertex shader.
+    pub vertex: libra_shader_blob_t,
+    /// The fragment shader.
+    pub fragment: libra_shader_blob_t,
+}
+
+/// Options for shader transpilation.
+///
+/// A zero-initialized `transpile_opt_t` uses the default for every option.
+#[repr(C)]
+#[derive(Default, Debug, Clone)]
+pub struct transpile_opt_t {
+    /// The librashader API version.
+    pub version: LIBRASHADER_API_VERSION,
+    /// The GLSL version to output, such as `330` or `460`. `0` uses the default of `330`.
+    ///
+    /// Only desktop GLSL versions are supported.
+    pub glsl_version: u16,
+    /// The HLSL shader model to output, multiplied by 10, such as `50` for Shader Model 5.0.
+    /// `0` uses the default of `50`.
+    pub hlsl_shader_model: u16,
+    /// The MSL version to output, encoded as `major * 10000 + minor * 100 + patch`,
+    /// such as `20100` for MSL 2.1. `0` uses the default of `10200`.
+    pub msl_version: u32,
+    /// Whether or not to keep the push constant block in WGSL instead of lowering it to a UBO.
+    /// Default is false.
+    pub wgsl_keep_push_constant_block: bool,
+    /// The bind group to place samplers in for WGSL. `0` uses the default of `1`, since
+    /// textures are bound in group 0.
+    pub wgsl_sampler_bind_group: u32,
+}
+
+/// Rust-side transpilation options, read from `transpile_opt_t`.
+#[derive(Default)]
+pub(crate) struct TranspileOptions {
+    glsl_version: u16,
+    hlsl_shader_model: u16,
+    msl_version: u32,
+    wgsl_keep_push_constant_block: bool,
+    wgsl_sampler_bind_group: u32,
+}
+
+config_struct! {
+    impl TranspileOptions => transpile_opt_t {
+        0 => [
+            glsl_version,
+            hlsl_shader_model,
+            msl_version,
+            wgsl_keep_push_constant_block,
+            wgsl_sampler_bind_group
+        ]
+    }
+}
 
-        let textures = textures
-            .into_iter()
-            .map(|texture| {
-                let lut = Image::<RGBA8>::load(&texture.path, direction)
-                    .map_err(|e| error::LibrashaderError::UnknownError(Box::new(e)))?;
-                Ok(LookupTexture {
-                    wrap_mode: texture.wrap_mode,
-                    filter_mode: texture.filter_mode,
-                    mipmap: texture.mipmap,
-                    image: lut,
-                })
-            })
-            .collect::<Result<Vec<LookupTexture>, error::LibrashaderError>>()?;
-
-        Ok(FilterReflection {
-            semantics,
-            passes: reflects,
-            textures,
+impl TranspileOptions {
+    /// Get the transpilation options, using the default for every option that is zero.
+    fn resolve(&self) -> Result<transpile::TranspileOptions, LibrashaderError> {
+        let defaults = transpile::TranspileOptions::default();
+        let invalid = |_| LibrashaderError::InvalidParameter("options");
+        Ok(transpile::TranspileOptions {
+            glsl_version: match self.glsl_version {
+                0 => defaults.glsl_version,
+                version => glsl_version_from_number(version).map_err(invalid)?,
+            },
+            hlsl_shader_model: match self.hlsl_shader_model {
+                0 => defaults.hlsl_shader_model,
+                shader_model => hlsl_shader_model_from_number(shader_model).map_err(invalid)?,
+            },
+            msl_version: match self.msl_version {
+                0 => defaults.msl_version,
+                version => msl_version_from_number(version),
+            },
+            wgsl: NagaLoweringOptions {
+                write_pcb_as_ubo: !self.wgsl_keep_push_constant_block,
+                sampler_bind_group: match self.wgsl_sampler_bind_group {
+                    0 => defaults.wgsl.sampler_bind_group,
+                    group => group,
+                },
+            },
         })
     }
 }
+
+impl From<LIBRA_SHADER_TARGET> for TranspileTarget {
+    fn from(value: LIBRA_SHADER_TARGET) -> Self {
+        match value {
+            LIBRA_SHADER_TARGET::GLSL => TranspileTarget::Glsl,
+            LIBRA_SHADER_TARGET::HLSL => TranspileTarget::Hlsl,
+            LIBRA_SHADER_TARGET::MSL => TranspileTarget::Msl,
+            LIBRA_SHADER_TARGET::WGSL => TranspileTarget::Wgsl,
+            LIBRA_SHADER_TARGET::SPIRV => TranspileTarget::Spirv,
+        }
+    }
+}
+
+impl libra_shader_blob_t {
+    /// Create a blob that is followed by a NUL terminator not counted in `length`.
+    fn from_bytes(mut bytes: Vec<u8>) -> Self {
+        bytes.push(0);
+        let (data, len) = crate::ffi::boxed_slice_into_raw_parts(bytes.into_boxed_slice());
+        libra_shader_blob_t {
+            data: data.cast_const(),
+            length: len as u64 - 1,
+        }
+    }
+
+    fn from_text(text: String) -> Self {
+        Self::from_bytes(text.into_bytes())
+    }
+
+    fn from_words(words: Vec<u32>) -> Self {
+        Self::from_bytes(words.into_iter().flat_map(u32::to_ne_bytes).collect())
+    }
+
+    unsafe fn free(self) {
+        unsafe {
+            drop(crate::ffi::boxed_slice_from_raw_parts(
+                self.data.cast_mut(),
+                self.length as usize + 1,
+            ));
+        }
+    }
+}
+
+impl libra_shader_output_t {
+    fn from_text(output: ShaderCompilerOutput<String, impl Sized>) -> Self {
+        libra_shader_output_t {
+            vertex: libra_shader_blob_t::from_text(output.vertex),
+            fragment: libra_shader_blob_t::from_text(output.fragment),
+        }
+    }
+}
+
+extern_fn! {
+    /// Load and preprocess a shader source file.
+    ///
+    /// `#include` directives are resolved relative to the location of the shader.
+    ///
+    /// ## Safety
+    ///  - `filename` must be either null or a valid, aligned pointer to a string path to the shader.
+    ///  - `out` must be either null, or an aligned pointer to an uninitialized or invalid `libra_shader_source_t`.
+    /// ## Returns
+    ///  - If any parameters are null, `out` is unchanged, and this function returns `LIBRA_ERR_INVALID_PARAMETER`.
+    fn libra_shader_source_create(
+        filename: *const c_char,
+        out: *mut MaybeUninit<libra_shader_source_t>
+    ) {
+        assert_non_null!(filename);
+        assert_non_null!(out);
+
+        let filename = unsafe { CStr::from_ptr(filename) };
+        let filename = filename.to_str()?;
+
+        let source = ShaderSource::load(filename)?;
+        unsafe {
+            out.write(MaybeUninit::new(NonNull::new(Box::into_raw(Box::new(
+                source,
+            )))))
+        }
+    }
+}
+
+extern_fn! {
+    /// Get the preprocessed GLSL text of the vertex and fragment stages of the shader.
+    ///
+    /// ## Safety
+    /// - `source` must be null or a valid and aligned pointer to a `libra_shader_source_t`.
+    /// - `out` must be an aligned pointer to a `libra_shader_output_t`.
+    /// - The output struct should be treated as immutable, and must be freed with
+    ///   `libra_shader_output_free`.
+    fn libra_shader_source_get_text(
+        source: *const libra_shader_source_t,
+        out: *mut MaybeUninit<libra_shader_output_t>
+    ) |source| {
+        assert_some_ptr!(source);
+        assert_non_null!(out);
+
+        let output = libra_shader_output_t {
+            vertex: libra_shader_blob_t::from_text(source.vertex.clone()),
+            fragment: libra_shader_blob_t::from_text(source.fragment.clone()),
+        };
+
+        unsafe {
+            out.write(MaybeUninit::new(output));
+        }
+    }
+}
+
+extern_fn! {
+    /// Get the parameters declared in the shader, in the order they are declared.
+    ///
+    /// ## Safety
+    /// - `source` must be null or a valid and aligned pointer to a `libra_shader_source_t`.
+    /// - `out` must be an aligned pointer to a `libra_preset_param_list_t`.
+    /// - The output struct should be treated as immutable, and must be freed with
+    ///   `libra_preset_free_runtime_params`.
+    fn libra_shader_source_get_params(
+        source: *const libra_shader_source_t,
+        out: *mut MaybeUninit<libra_preset_param_list_t>
+    ) |source| {
+        assert_some_ptr!(source);
+        assert_non_null!(out);
+
+        let list = libra_preset_param_list_t::from_params(source.parameters.values().cloned())?;
+
+        unsafe {
+            out.write(MaybeUninit::new(list));
+        }
+    }
+}
+
+extern_fn! {
+    /// Transpile the shader to the given target.
+    ///
+    /// Text targets are returned as NUL-terminated strings. SPIR-V is returned as the raw module.
+    ///
+    /// ## Safety
+    /// - `source` must be null or a valid and aligned pointer to a `libra_shader_source_t`.
+    /// - `options` must be either null, or valid and aligned.
+    /// - `out` must be an aligned pointer to a `libra_shader_output_t`.
+    /// - The output struct should be treated as immutable, and must be freed with
+    ///   `libra_shader_output_free`.
+    /// ## Returns
+    ///  - If a version in `options` is not supported by the target, `out` is unchanged,
+    ///    and this function returns `LIBRA_ERR_INVALID_PARAMETER`.
+    fn libra_shader_source_transpile(
+        source: *const libra_shader_source_t,
+        target: LIBRA_SHADER_TARGET,
+        options: *const MaybeUninit<transpile_opt_t>,
+        out: *mut MaybeUninit<libra_shader_output_t>
+    ) |source| {
+        assert_some_ptr!(source);
+        assert_non_null!(out);
+
+        let options = if options.is_null() {
+            TranspileOptions::default()
+        } else {
+            FromUninit::from_uninit(unsafe { options.read() })
+        };
+
+        let output = match transpile::transpile::<LibrashaderError>(
+            source,
+            target.into(),
+            &options.resolve()?,
+        )? {
+            TranspileOutput::Text(output) => libra_shader_output_t::from_text(output),
+            TranspileOutput::Spirv(output) => libra_shader_output_t {
+                vertex: libra_shader_blob_t::from_words(output.vertex),
+                fragment: libra_shader_blob_t::from_words(output.fragment),
+            },
+        };
+
+        unsafe {
+            out.write(MaybeUninit::new(output));
+        }
+    }
+}
+
+extern_fn! {
+    /// Reflect a pass of the shader preset.
+    ///
+    /// The reflection is written as NUL-terminated JSON, following the versioned pass reflection
+    /// schema in `librashader-reflect/schema/pass-reflection.schema.json`. It describes the
+    /// layout of the UBO and push constant block, the uniforms bound to each semantic and
+    /// parameter, and the binding of each texture.
+    ///
+    /// ## Safety
+    /// - `preset` must be null or a valid and aligned pointer to a `libra_shader_preset_t`.
+    /// - `out` must be an aligned pointer to a `libra_shader_blob_t`.
+    /// - The output struct should be treated as immutable, and must be freed with
+    ///   `libra_shader_blob_free`.
+    /// ## Returns
+    ///  - If `index` is not a pass of the preset, `out` is unchanged, and this function
+    ///    returns `LIBRA_ERR_INVALID_PARAMETER`.
+    fn libra_preset_reflect_pass(
+        preset: *const libra_shader_preset_t,
+        index: u32,
+        out: *mut MaybeUninit<libra_shader_blob_t>
+    ) |preset| {
+        assert_some_ptr!(preset);
+        assert_non_null!(out);
+
+        let index = index as usize;
+        let Some(pass) = preset.passes.get(index) else {
+            return Err(LibrashaderError::InvalidParameter("index"));
+        };
+
+        let source = ShaderSource::load(&pass.path)?;
+        let compilation = SpirvCompilation::try_from(&source)?;
+        let semantics =
+            ShaderSemantics::create_pass_semantics::<LibrashaderError>(preset, index)?;
+
+        let mut compilation =
+            <SPIRV as FromCompilation<SpirvCompilation, SpirvCross>>::from_compilation(
+                compilation,
+            )?;
+        let reflection = compilation.reflect(index, &semantics)?;
+        let reflection = PassReflection::new(index, &reflection);
+        let json = serde_json::to_string(&reflection)
+            .map_err(|err| LibrashaderError::UnknownError(Box::new(err)))?;
+
+        unsafe {
+            out.write(MaybeUninit::new(libra_shader_blob_t::from_text(json)));
+        }
+    }
+}
+
+extern_fn! {
+    /// Free a buffer written by `libra_preset_reflect_pass`.
+    ///
+    /// Like `libra_preset_free_runtime_params`, this function takes the struct directly.
+    ///
+    /// ## Safety
+    /// - `blob` must have been returned by `libra_preset_reflect_pass`, with its fields unchanged.
+    /// - Accessing the data of the buffer after it has been freed is a use-after-free and is
+    ///   immediate undefined behaviour.
+    fn libra_shader_blob_free(blob: libra_shader_blob_t) {
+        unsafe {
+            blob.free();
+        }
+    }
+}
+
+extern_fn! {
+    /// Free the output of `libra_shader_source_get_text` or `libra_shader_source_transpile`.
+    ///
+    /// Like `libra_preset_free_runtime_params`, this function takes the struct directly.
+    ///
+    /// ## Safety
+    /// - `output` must have been returned by `libra_shader_source_get_text` or
+    ///   `libra_shader_source_transpile`, with its fields unchanged.
+    /// - Accessing the data of the output after it has been freed is a use-after-free and is
+    ///   immediate undefined behaviour.
+    fn libra_shader_output_free(output: libra_shader_output_t) {
+        unsafe {
+            output.vertex.free();
+            output.fragment.free();
+        }
+    }
+}
+
+extern_fn! {
+    /// Free the shader source.
+    ///
+    /// If `source` is null, this function does nothing. The resulting value in `source` then becomes
+    /// null.
+    ///
+    /// ## Safety
+    /// - `source` must be a valid and aligned pointer to a `libra_shader_source_t`.
+    fn libra_shader_source_free(source: *mut libra_shader_source_t) {
+        assert_non_null!(source);
+        unsafe {
+            let source_ptr = &mut *source;
+            let source = source_ptr.take();
+            drop(Box::from_raw(source.unwrap().as_ptr()));
+        }
+    }
+}
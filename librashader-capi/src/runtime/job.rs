@@ -0,0 +1,232 @@
+//! Asynchronous filter chain creation C API (`libra_filter_chain_job_*`).
+//!
+//! Only the Vulkan, Direct3D 11, Direct3D 12, and wgpu runtimes can create filter chains with a job.
+//! See the crate documentation for why OpenGL, Direct3D 9, and Metal are excluded.
+use crate::ctypes::libra_filter_chain_job_t;
+use crate::error::{assert_non_null, assert_some_ptr, LibrashaderError};
+use crate::ffi::extern_fn;
+use librashader::preprocess::PreprocessError;
+use librashader::presets::{LoadableResource, ShaderPreset, ShaderPresetPack, TextureMeta};
+use librashader::reflect::PassObserver;
+use librashader::runtime::{LoadJob, LoadJobError, LoadProgress, LoadStage};
+use std::any::Any;
+use std::ffi::c_void;
+use std::mem::MaybeUninit;
+
+/// A stage of filter chain creation reported to a `libra_load_progress_callback_t`.
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum LIBRA_LOAD_STAGE {
+    /// The source of a pass was loaded and preprocessed.
+    PREPROCESS = 0,
+    /// A pass was compiled and reflected.
+    COMPILE = 1,
+}
+
+impl From<LoadStage> for LIBRA_LOAD_STAGE {
+    fn from(value: LoadStage) -> Self {
+        match value {
+            LoadStage::Preprocess => LIBRA_LOAD_STAGE::PREPROCESS,
+            LoadStage::Compile => LIBRA_LOAD_STAGE::COMPILE,
+        }
+    }
+}
+
+/// A function that receives the progress of an asynchronous filter chain creation.
+///
+/// `completed` is the number of passes that have completed `stage` so far, and `pass_count` is
+/// the number of passes in the shader preset. Passes complete each stage in parallel, so
+/// `completed` counts up from 1 to `pass_count` rather than naming a pass. The callback is called from the worker threads of librashader,
+/// possibly concurrently, and must return quickly. `userdata` is the pointer that was passed
+/// to the `*_filter_chain_create_async` function.
+pub type libra_load_progress_callback_t = Option<
+    unsafe extern "C" fn(
+        stage: LIBRA_LOAD_STAGE,
+        completed: u32,
+        pass_count: u32,
+        userdata: *mut c_void,
+    ),
+>;
+
+struct ProgressSink {
+    callback: libra_load_progress_callback_t,
+    userdata: *mut c_void,
+}
+
+// SAFETY: the host guarantees that the callback and userdata may be used from any thread.
+unsafe impl Send for ProgressSink {}
+unsafe impl Sync for ProgressSink {}
+
+impl ProgressSink {
+    fn report(&self, progress: LoadProgress) {
+        if let Some(callback) = self.callback {
+            unsafe {
+                callback(
+                    progress.stage.into(),
+                    progress.completed as u32,
+                    progress.pass_count as u32,
+                    self.userdata,
+                )
+            }
+        }
+    }
+}
+
+trait ErasedLoadJob: Send + Sync {
+    fn cancel(&self);
+    fn is_finished(&self) -> bool;
+    fn cancel_and_wait(self: Box<Self>);
+    fn as_any(&self) -> &dyn Any;
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+impl<T, E> ErasedLoadJob for LoadJob<T, E>
+where
+    T: Send + 'static,
+    E: Send + 'static,
+{
+    fn cancel(&self) {
+        LoadJob::cancel(self)
+    }
+
+    fn is_finished(&self) -> bool {
+        LoadJob::is_finished(self)
+    }
+
+    fn cancel_and_wait(self: Box<Self>) {
+        LoadJob::cancel(&self);
+        drop(LoadJob::wait(*self));
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+/// Opaque struct for a filter chain that is being created asynchronously.
+pub struct FilterChainJob(Box<dyn ErasedLoadJob>);
+
+impl FilterChainJob {
+    /// Create a filter chain from the shader preset on the worker threads.
+    #[allow(dead_code)]
+    pub(crate) fn spawn<T, E>(
+        preset: ShaderPreset,
+        callback: libra_load_progress_callback_t,
+        userdata: *mut c_void,
+        create: impl FnOnce(ShaderPresetPack, PassObserver<'_>) -> Result<T, E> + Send + 'static,
+    ) -> Self
+    where
+        T: Send + 'static,
+        E: From<PreprocessError> + From<<TextureMeta as LoadableResource>::Error>,
+        E: Send + 'static,
+    {
+        let sink = ProgressSink { callback, userdata };
+        FilterChainJob(Box::new(LoadJob::spawn(
+            preset,
+            move |progress| sink.report(progress),
+            create,
+        )))
+    }
+
+    /// Block until the job has finished and take the created filter chain.
+    ///
+    /// The job is only consumed if it was created for a filter chain of type `T`.
+    #[allow(dead_code)]
+    pub(crate) fn finish<T, E>(job: &mut libra_filter_chain_job_t) -> Result<T, LibrashaderError>
+    where
+        T: Send + 'static,
+        E: Send + 'static,
+        LibrashaderError: From<E>,
+    {
+        let is_runtime = job
+            .as_ref()
+            .is_some_and(|job| unsafe { job.as_ref().0.as_any().is::<LoadJob<T, E>>() });
+        if !is_runtime {
+            return Err(LibrashaderError::InvalidParameter("job"));
+        }
+
+        // SAFETY: the job was checked to be non-null above.
+        let job = unsafe { Box::from_raw(job.take().unwrap_unchecked().as_ptr()) };
+        let Ok(job) = job.0.into_any().downcast::<LoadJob<T, E>>() else {
+            unreachable!("the type of the job was checked above");
+        };
+
+        match job.wait() {
+            Ok(filter_chain) => Ok(filter_chain),
+            Err(LoadJobError::Load(error)) => Err(error.into()),
+            Err(LoadJobError::Cancelled) => Err(LibrashaderError::Cancelled),
+            Err(LoadJobError::Panicked(panic)) => Err(LibrashaderError::UnknownError(panic)),
+        }
+    }
+}
+
+extern_fn! {
+    /// Request that the creation of a filter chain be cancelled.
+    ///
+    /// The job stops after the pass it is currently preprocessing or compiling, after which
+    /// `*_filter_chain_job_finish` returns an error with the code `LIBRA_ERRNO_CANCELLED`.
+    /// If the filter chain was already created, the job finishes as usual.
+    ///
+    /// This function may be called from any thread, but must not be called concurrently with
+    /// a function that frees the job.
+    ///
+    /// ## Safety
+    /// - `job` must be either null or a valid and aligned pointer to an initialized `libra_filter_chain_job_t`.
+    fn libra_filter_chain_job_cancel(
+        job: *const libra_filter_chain_job_t
+    ) |job| {
+        assert_some_ptr!(job);
+        job.0.cancel();
+    }
+}
+
+extern_fn! {
+    /// Get whether the creation of a filter chain has finished, either successfully or
+    /// with an error.
+    ///
+    /// If this returns true, `*_filter_chain_job_finish` will return without blocking.
+    /// This function may be called from any thread, but must not be called concurrently with
+    /// a function that frees the job.
+    ///
+    /// ## Safety
+    /// - `job` must be either null or a valid and aligned pointer to an initialized `libra_filter_chain_job_t`.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    fn libra_filter_chain_job_is_finished(
+        job: *const libra_filter_chain_job_t,
+        out: *mut MaybeUninit<bool>
+    ) |job| {
+        assert_some_ptr!(job);
+        assert_non_null!(out);
+        let finished = job.0.is_finished();
+        unsafe {
+            out.write(MaybeUninit::new(finished))
+        }
+    }
+}
+
+extern_fn! {
+    /// Cancel and free a filter chain creation job.
+    ///
+    /// This blocks until the worker threads stop after the pass they are currently preprocessing
+    /// or compiling, then frees any filter chain that was created by the job. Commands that were
+    /// recorded to the command buffer, command list, or deferred context of the job must be
+    /// discarded.
+    ///
+    /// The resulting value in `job` then becomes null.
+    /// ## Safety
+    /// - `job` must be either null or a valid and aligned pointer to an initialized `libra_filter_chain_job_t`.
+    fn libra_filter_chain_job_free(
+        job: *mut libra_filter_chain_job_t
+    ) {
+        assert_non_null!(job);
+        unsafe {
+            let job_ptr = &mut *job;
+            let job = job_ptr.take();
+            Box::from_raw(job.unwrap().as_ptr()).0.cancel_and_wait()
+        };
+    }
+}
@@ -7,6 +7,10 @@ pub mod gl;
 #[cfg(feature = "runtime-vulkan")]
 pub mod vk;
 
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "runtime-wgpu")))]
+#[cfg(feature = "runtime-wgpu")]
+pub mod wgpu;
+
 #[cfg_attr(
     feature = "docsrs",
     doc(cfg(all(target_os = "windows", feature = "runtime-d3d11")))
@@ -50,3 +54,123 @@ pub mod d3d12;
     )
 ))]
 pub mod mtl;
+
+pub mod job;
+
+use crate::error::LibrashaderError;
+use crate::ffi::extern_fn;
+use librashader::runtime::RuntimeParameters;
+use std::ffi::{c_char, CString};
+
+/// A parameter of a filter chain.
+#[repr(C)]
+pub struct libra_filter_chain_param_t {
+    /// The name of the parameter
+    pub name: *const c_char,
+    /// The description of the parameter.
+    pub description: *const c_char,
+    /// The current value of the parameter.
+    pub value: f32,
+    /// The value the parameter had when the filter chain was loaded, which is the value set
+    /// by the shader preset if it overrides the parameter, and the initial value otherwise.
+    pub default_value: f32,
+    /// The initial value of the parameter declared in the shader source.
+    pub initial: f32,
+    /// The minimum value that the parameter can be set to.
+    pub minimum: f32,
+    /// The maximum value that the parameter can be set to.
+    pub maximum: f32,
+    /// The step by which this parameter can be incremented or decremented.
+    pub step: f32,
+}
+
+/// A list of filter chain parameters.
+#[repr(C)]
+pub struct libra_filter_chain_param_list_t {
+    /// A pointer to the parameter
+    pub parameters: *const libra_filter_chain_param_t,
+    /// The number of parameters in the list. This field
+    /// is readonly, and changing it will lead to undefined
+    /// behaviour on free.
+    pub length: u64,
+}
+
+impl libra_filter_chain_param_list_t {
+    /// Collect the parameters of a filter chain into a parameter list that must be freed with
+    /// `libra_filter_chain_free_params`.
+    #[allow(dead_code)]
+    pub(crate) fn from_parameters(
+        parameters: &RuntimeParameters,
+    ) -> Result<Self, LibrashaderError> {
+        // Convert every string before handing any of them out as raw pointers, so that nothing
+        // is leaked if a parameter has a name or description with an interior nul.
+        let strings = parameters
+            .iter()
+            .map(|(param, value)| {
+                let name = CString::new(param.id.to_string())
+                    .map_err(|err| LibrashaderError::UnknownError(Box::new(err)))?;
+                let description = CString::new(param.description.as_str())
+                    .map_err(|err| LibrashaderError::UnknownError(Box::new(err)))?;
+                Ok((param, value, name, description))
+            })
+            .collect::<Result<Vec<_>, LibrashaderError>>()?;
+
+        let values: Vec<_> = strings
+            .into_iter()
+            .map(|(param, value, name, description)| libra_filter_chain_param_t {
+                name: name.into_raw().cast_const(),
+                description: description.into_raw().cast_const(),
+                value,
+                default_value: param.default,
+                initial: param.initial,
+                minimum: param.minimum,
+                maximum: param.maximum,
+                step: param.step,
+            })
+            .collect();
+
+        let values = values.into_boxed_slice();
+        let (parts, len) = crate::ffi::boxed_slice_into_raw_parts(values);
+
+        Ok(libra_filter_chain_param_list_t {
+            parameters: parts,
+            length: len as u64,
+        })
+    }
+}
+
+extern_fn! {
+    /// Free the parameters of a filter chain.
+    ///
+    /// Like `libra_preset_free_runtime_params`, `libra_filter_chain_free_params`
+    /// takes the struct directly. The caller must take care to maintain the lifetime
+    /// of any pointers contained within the input `libra_filter_chain_param_list_t`.
+    ///
+    /// ## Safety
+    /// - Any pointers rooted at `parameters` becomes invalid after this function returns,
+    ///   including any strings accessible via the input `libra_filter_chain_param_list_t`.
+    ///   The caller must ensure that there are no live pointers, aliased or unaliased,
+    ///   to data accessible via the input `libra_filter_chain_param_list_t`.
+    ///
+    /// - Accessing any data pointed to via the input `libra_filter_chain_param_list_t` after it
+    ///   has been freed is a use-after-free and is immediate undefined behaviour.
+    ///
+    /// - If any struct fields of the input `libra_filter_chain_param_list_t` was modified from
+    ///   their values given after `*_filter_chain_get_params`, this may result
+    ///   in undefined behaviour.
+    fn libra_filter_chain_free_params(list: libra_filter_chain_param_list_t) {
+        unsafe {
+            let values =
+                    crate::ffi::boxed_slice_from_raw_parts(list.parameters.cast_mut(),
+                list.length as usize).into_vec();
+
+            for value in values {
+                let name = CString::from_raw(value.name.cast_mut());
+                let description = CString::from_raw(value.description.cast_mut());
+
+                drop(name);
+                drop(description)
+            }
+        }
+    }
+}
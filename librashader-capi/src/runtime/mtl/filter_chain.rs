@@ -1,8 +1,10 @@
 use crate::ctypes::{
-    config_struct, libra_mtl_filter_chain_t, libra_shader_preset_t, libra_viewport_t, FromUninit,
+    config_struct, libra_mtl_filter_chain_t, libra_preset_pack_t, libra_shader_preset_t,
+    libra_viewport_t, FromUninit, LIBRA_HDR_OUTPUT,
 };
 use crate::error::{assert_non_null, assert_some_ptr, LibrashaderError};
 use crate::ffi::extern_fn;
+use crate::runtime::libra_filter_chain_param_list_t;
 use librashader::runtime::mtl::{FilterChain, FilterChainOptions, FrameOptions};
 use std::ffi::c_char;
 use std::ffi::CStr;
@@ -44,12 +46,65 @@ pub struct frame_mtl_opt_t {
     pub total_subframes: u32,
     /// The current sub frame. Default is 1.
     pub current_subframe: u32,
+    /// The aspect ratio of the original content.
+    /// If zero, the aspect ratio of the input image is used. Default is 0.
+    pub aspect_ratio: f32,
+    /// The refresh rate of the original content, in frames per second. Default is 60.
+    pub original_fps: f32,
+    /// The time elapsed since the previous frame, in microseconds. Default is 0.
+    pub frametime_delta: u32,
+    /// The rate at which frames are currently being produced, in frames per second.
+    /// Default is 60.
+    pub core_fps: f32,
+    /// Whether or not the output is HDR. Default is false.
+    pub enable_hdr: bool,
+    /// The brightness of SDR paper white in nits. Default is 200.
+    pub paper_white_nits: f32,
+    /// The peak brightness of the display in nits. Default is 1000.
+    pub max_nits: f32,
+    /// Whether or not to expand the Rec. 709 gamut when converting to HDR. Default is false.
+    pub expand_gamut: bool,
+    /// Whether or not to inverse tonemap SDR content when converting to HDR. Default is false.
+    pub inverse_tonemap: bool,
+    /// The index of the first pass to render. Default is 0.
+    ///
+    /// Passes before the first pass are skipped, and the first pass reads the original
+    /// image as its `Source`. Framebuffers are still sized as if every pass was rendered.
+    pub first_pass: u32,
+    /// The number of passes to render, starting from the first pass.
+    /// If zero, all enabled passes after the first pass are rendered. Default is 0.
+    ///
+    /// The last pass rendered is drawn to the output viewport.
+    pub pass_count: u32,
+    /// The position of the comparison split line, as a fraction of the viewport width.
+    /// Default is 0.
+    ///
+    /// If greater than zero, the original image is drawn to the left of the split line,
+    /// and the output of the last pass rendered to the right of it.
+    pub compare_split: f32,
+    /// The width of the comparison split line, in pixels. Default is 1.
+    pub compare_line_width: f32,
 }
 
 config_struct! {
     impl FrameOptions => frame_mtl_opt_t {
         0 => [clear_history, frame_direction];
-        1 => [rotation, total_subframes, current_subframe]
+        1 => [rotation, total_subframes, current_subframe];
+        2 => [
+            aspect_ratio,
+            original_fps,
+            frametime_delta,
+            core_fps,
+            enable_hdr,
+            paper_white_nits,
+            max_nits,
+            expand_gamut,
+            inverse_tonemap,
+            first_pass,
+            pass_count,
+            compare_split,
+            compare_line_width
+        ]
     }
 }
 
@@ -61,11 +116,15 @@ pub struct filter_chain_mtl_opt_t {
     pub version: LIBRASHADER_API_VERSION,
     /// Whether or not to explicitly disable mipmap generation regardless of shader preset settings.
     pub force_no_mipmaps: bool,
+    /// The built-in HDR output pass to append to the shader preset, if any. The filter chain
+    /// should then be drawn to an output with the format of the encoding.
+    pub hdr_output: LIBRA_HDR_OUTPUT,
 }
 
 config_struct! {
     impl FilterChainOptions => filter_chain_mtl_opt_t {
         0 => [force_no_mipmaps];
+        2 => [hdr_output];
     }
 }
 
@@ -170,6 +229,107 @@ extern_fn! {
     }
 }
 
+extern_fn! {
+    /// Create the filter chain given the shader preset pack.
+    ///
+    /// The shader preset pack is immediately invalidated and must be recreated after
+    /// the filter chain is created.
+    ///
+    /// ## Safety:
+    /// - `queue` must be valid for the command buffers
+    ///     that `libra_mtl_filter_chain_frame` will write to.
+    /// - `queue` must be a reference to a `id<MTLCommandQueue>`.
+    /// - `pack` must be either null, or valid and aligned.
+    /// - `options` must be either null, or valid and aligned.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    fn libra_mtl_filter_chain_create_from_pack(
+        pack: *mut libra_preset_pack_t,
+        queue: PMTLCommandQueue,
+        options: *const MaybeUninit<filter_chain_mtl_opt_t>,
+        out: *mut MaybeUninit<libra_mtl_filter_chain_t>
+    ) |queue| {
+        assert_non_null!(pack);
+
+        let pack = unsafe {
+            let pack_ptr = &mut *pack;
+            let pack = pack_ptr.take();
+            Box::from_raw(pack.unwrap().as_ptr())
+        };
+
+        let options = if options.is_null() {
+            None
+        } else {
+            Some(unsafe { options.read() })
+        };
+
+        let queue = queue.as_ref();
+        let options = options.map(FromUninit::from_uninit);
+
+        unsafe {
+            let chain = FilterChain::load_from_pack(*pack, queue, options.as_ref())?;
+
+            out.write(MaybeUninit::new(NonNull::new(Box::into_raw(Box::new(
+                chain,
+            )))))
+        }
+    }
+}
+
+extern_fn! {
+    /// Create the filter chain given the shader preset pack deferring and GPU-side initialization
+    /// to the caller. This function therefore requires no external synchronization of the device queue.
+    ///
+    /// The shader preset pack is immediately invalidated and must be recreated after
+    /// the filter chain is created.
+    ///
+    /// ## Safety:
+    /// - `queue` must be valid for the command buffers
+    ///     that `libra_mtl_filter_chain_frame` will write to.
+    /// - `queue` must be a reference to a `id<MTLCommandQueue>`.
+    /// - `command_buffer` must be a valid reference to a `MTLCommandBuffer` that is not already encoding.
+    /// - `pack` must be either null, or valid and aligned.
+    /// - `options` must be either null, or valid and aligned.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    ///
+    /// The provided command buffer must be ready for recording and contain no prior commands.
+    /// The caller is responsible for ending the command buffer and immediately submitting it to a
+    /// graphics queue. The command buffer must be completely executed before calling `libra_mtl_filter_chain_frame`.
+    fn libra_mtl_filter_chain_create_from_pack_deferred(
+        pack: *mut libra_preset_pack_t,
+        queue: PMTLCommandQueue,
+        command_buffer: PMTLCommandBuffer,
+        options: *const MaybeUninit<filter_chain_mtl_opt_t>,
+        out: *mut MaybeUninit<libra_mtl_filter_chain_t>
+    ) |queue, command_buffer| {
+        assert_non_null!(pack);
+
+        let pack = unsafe {
+            let pack_ptr = &mut *pack;
+            let pack = pack_ptr.take();
+            Box::from_raw(pack.unwrap().as_ptr())
+        };
+
+        let options = if options.is_null() {
+            None
+        } else {
+            Some(unsafe { options.read() })
+        };
+
+        let options = options.map(FromUninit::from_uninit);
+
+        unsafe {
+            let chain = FilterChain::load_from_pack_deferred(*pack,
+                queue,
+                command_buffer,
+                options.as_ref())?;
+
+            out.write(MaybeUninit::new(NonNull::new(Box::into_raw(Box::new(
+                chain,
+            )))))
+        }
+    }
+}
+
 extern_fn! {
     /// Records rendering commands for a frame with the given parameters for the given filter chain
     /// to the input command buffer.
@@ -295,6 +455,43 @@ extern_fn! {
     }
 }
 
+extern_fn! {
+    /// Gets the metadata and current value of every parameter of the filter chain.
+    ///
+    /// Parameters are listed in the order they are declared by the passes of the filter chain.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_mtl_filter_chain_t`.
+    /// - `out` must be an aligned pointer to a `libra_filter_chain_param_list_t`.
+    /// - The output struct should be treated as immutable. Mutating any struct fields
+    ///   in the returned struct may at best cause memory leaks, and at worse
+    ///   cause undefined behaviour when later freed.
+    /// - The output struct must be freed with `libra_filter_chain_free_params`.
+    fn libra_mtl_filter_chain_get_params(
+        chain: *const libra_mtl_filter_chain_t,
+        out: *mut MaybeUninit<libra_filter_chain_param_list_t>
+    ) |chain| {
+        assert_some_ptr!(chain);
+        assert_non_null!(out);
+        let list = libra_filter_chain_param_list_t::from_parameters(chain.parameters())?;
+        unsafe {
+            out.write(MaybeUninit::new(list));
+        }
+    }
+}
+
+extern_fn! {
+    /// Resets every parameter of the filter chain to the value it had when the filter chain was loaded.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_mtl_filter_chain_t`.
+    fn libra_mtl_filter_chain_reset_params(
+        chain: *mut libra_mtl_filter_chain_t
+    ) |chain| {
+        assert_some_ptr!(chain);
+        chain.parameters().reset_to_defaults();
+    }
+}
+
 extern_fn! {
     /// Sets the number of active passes for this chain.
     ///
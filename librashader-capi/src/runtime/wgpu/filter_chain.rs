@@ -0,0 +1,720 @@
+use crate::ctypes::{
+    config_struct, libra_filter_chain_job_t, libra_preset_pack_t, libra_shader_preset_t,
+    libra_viewport_t, libra_wgpu_filter_chain_t, FromUninit, LIBRA_HDR_OUTPUT,
+};
+use crate::error::{assert_non_null, assert_some_ptr, LibrashaderError};
+use crate::ffi::extern_fn;
+use crate::runtime::job::{libra_load_progress_callback_t, FilterChainJob};
+use crate::runtime::libra_filter_chain_param_list_t;
+use crate::runtime::wgpu::handles::{
+    arc_from_shared, PWGPUCommandEncoder, PWGPUDevice, PWGPUQueue, PWGPUTexture,
+};
+use librashader::runtime::wgpu::error::FilterChainError;
+use librashader::runtime::wgpu::{FilterChain, FilterChainOptions, FrameOptions, WgpuOutputView};
+use std::ffi::CStr;
+use std::ffi::{c_char, c_void};
+use std::mem::MaybeUninit;
+use std::ptr::NonNull;
+use std::slice;
+
+use librashader::runtime::FilterChainParameters;
+use librashader::runtime::{Size, Viewport};
+
+use crate::LIBRASHADER_API_VERSION;
+
+/// A command encoder that is recorded to by a filter chain creation job.
+struct JobCommandEncoder(PWGPUCommandEncoder);
+
+// SAFETY: `wgpu::CommandEncoder` is `Send`, and the caller of `libra_wgpu_filter_chain_create_async`
+// guarantees that the encoder is not used until the job is finished or freed.
+unsafe impl Send for JobCommandEncoder {}
+
+impl JobCommandEncoder {
+    /// ## Safety
+    /// The encoder must be valid and not used elsewhere for the lifetime of the reference.
+    unsafe fn as_mut(&mut self) -> &mut wgpu::CommandEncoder {
+        unsafe { &mut *self.0 }
+    }
+}
+
+/// Options for each wgpu shader frame.
+#[repr(C)]
+#[derive(Default, Debug, Clone)]
+pub struct frame_wgpu_opt_t {
+    /// The librashader API version.
+    pub version: LIBRASHADER_API_VERSION,
+    /// Whether or not to clear the history buffers.
+    pub clear_history: bool,
+    /// The direction of rendering.
+    /// -1 indicates that the frames are played in reverse order.
+    pub frame_direction: i32,
+    /// The rotation of the output. 0 = 0deg, 1 = 90deg, 2 = 180deg, 3 = 270deg.
+    pub rotation: u32,
+    /// The total number of subframes ran. Default is 1.
+    pub total_subframes: u32,
+    /// The current sub frame. Default is 1.
+    pub current_subframe: u32,
+    /// The aspect ratio of the original content.
+    /// If zero, the aspect ratio of the input image is used. Default is 0.
+    pub aspect_ratio: f32,
+    /// The refresh rate of the original content, in frames per second. Default is 60.
+    pub original_fps: f32,
+    /// The time elapsed since the previous frame, in microseconds. Default is 0.
+    pub frametime_delta: u32,
+    /// The rate at which frames are currently being produced, in frames per second.
+    /// Default is 60.
+    pub core_fps: f32,
+    /// Whether or not the output is HDR. Default is false.
+    pub enable_hdr: bool,
+    /// The brightness of SDR paper white in nits. Default is 200.
+    pub paper_white_nits: f32,
+    /// The peak brightness of the display in nits. Default is 1000.
+    pub max_nits: f32,
+    /// Whether or not to expand the Rec. 709 gamut when converting to HDR. Default is false.
+    pub expand_gamut: bool,
+    /// Whether or not to inverse tonemap SDR content when converting to HDR. Default is false.
+    pub inverse_tonemap: bool,
+    /// The index of the first pass to render. Default is 0.
+    ///
+    /// Passes before the first pass are skipped, and the first pass reads the original
+    /// image as its `Source`. Framebuffers are still sized as if every pass was rendered.
+    pub first_pass: u32,
+    /// The number of passes to render, starting from the first pass.
+    /// If zero, all enabled passes after the first pass are rendered. Default is 0.
+    ///
+    /// The last pass rendered is drawn to the output viewport.
+    pub pass_count: u32,
+    /// The position of the comparison split line, as a fraction of the viewport width.
+    /// Default is 0.
+    ///
+    /// If greater than zero, the original image is drawn to the left of the split line,
+    /// and the output of the last pass rendered to the right of it.
+    pub compare_split: f32,
+    /// The width of the comparison split line, in pixels. Default is 1.
+    pub compare_line_width: f32,
+}
+
+config_struct! {
+    impl FrameOptions => frame_wgpu_opt_t {
+        0 => [clear_history, frame_direction];
+        1 => [rotation, total_subframes, current_subframe];
+        2 => [
+            aspect_ratio,
+            original_fps,
+            frametime_delta,
+            core_fps,
+            enable_hdr,
+            paper_white_nits,
+            max_nits,
+            expand_gamut,
+            inverse_tonemap,
+            first_pass,
+            pass_count,
+            compare_split,
+            compare_line_width
+        ]
+    }
+}
+
+/// Options for filter chain creation.
+#[repr(C)]
+#[derive(Default, Debug, Clone)]
+pub struct filter_chain_wgpu_opt_t {
+    /// The librashader API version.
+    pub version: LIBRASHADER_API_VERSION,
+    /// Whether or not to explicitly disable mipmap generation regardless of shader preset settings.
+    pub force_no_mipmaps: bool,
+    /// Enable the shader object cache. Shaders will be loaded from the cache
+    /// if this is enabled.
+    pub enable_cache: bool,
+    /// The built-in HDR output pass to append to the shader preset, if any. The filter chain
+    /// should then be drawn to an output with the format of the encoding.
+    pub hdr_output: LIBRA_HDR_OUTPUT,
+}
+
+config_struct! {
+    impl FilterChainOptions => filter_chain_wgpu_opt_t {
+        0 => [force_no_mipmaps, enable_cache];
+        2 => [hdr_output];
+    }
+}
+
+extern_fn! {
+    /// Create the filter chain given the shader preset.
+    ///
+    /// The shader preset is immediately invalidated and must be recreated after
+    /// the filter chain is created.
+    ///
+    /// ## Safety:
+    /// - `device` and `queue` must be valid device and queue handles. The filter chain takes its
+    ///   own reference to both, so the caller keeps ownership of theirs.
+    /// - `queue` must be a queue of `device`.
+    /// - `preset` must be either null, or valid and aligned.
+    /// - `options` must be either null, or valid and aligned.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    fn libra_wgpu_filter_chain_create(
+        preset: *mut libra_shader_preset_t,
+        device: PWGPUDevice,
+        queue: PWGPUQueue,
+        options: *const MaybeUninit<filter_chain_wgpu_opt_t>,
+        out: *mut MaybeUninit<libra_wgpu_filter_chain_t>
+    ) {
+        assert_non_null!(preset);
+        assert_non_null!(device);
+        assert_non_null!(queue);
+
+        let preset = unsafe {
+            let preset_ptr = &mut *preset;
+            let preset = preset_ptr.take();
+            Box::from_raw(preset.unwrap().as_ptr())
+        };
+
+        let options = if options.is_null() {
+            None
+        } else {
+            Some(unsafe { options.read() })
+        };
+
+        let options = options.map(FromUninit::from_uninit);
+
+        unsafe {
+            let device = arc_from_shared(device);
+            let queue = arc_from_shared(queue);
+            let chain = FilterChain::load_from_preset(*preset, device, queue, options.as_ref())?;
+
+            out.write(MaybeUninit::new(NonNull::new(Box::into_raw(Box::new(
+                chain,
+            )))))
+        }
+    }
+}
+
+extern_fn! {
+    /// Create the filter chain given the shader preset deferring and GPU-side initialization
+    /// to the caller. This function therefore requires no external synchronization of the device queue.
+    ///
+    /// The shader preset is immediately invalidated and must be recreated after
+    /// the filter chain is created.
+    ///
+    /// ## Safety:
+    /// - `device` and `queue` must be valid device and queue handles. The filter chain takes its
+    ///   own reference to both, so the caller keeps ownership of theirs.
+    /// - `queue` must be a queue of `device`.
+    /// - `encoder` must be a valid command encoder handle of `device`.
+    /// - `preset` must be either null, or valid and aligned.
+    /// - `options` must be either null, or valid and aligned.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    ///
+    /// The provided command encoder must be ready for recording and contain no prior commands.
+    /// The caller is responsible for immediately submitting the command encoder to `queue` with
+    /// `libra_wgpu_queue_submit`. The command buffer must be completely executed before calling
+    /// `libra_wgpu_filter_chain_frame`.
+    fn libra_wgpu_filter_chain_create_deferred(
+        preset: *mut libra_shader_preset_t,
+        device: PWGPUDevice,
+        queue: PWGPUQueue,
+        encoder: PWGPUCommandEncoder,
+        options: *const MaybeUninit<filter_chain_wgpu_opt_t>,
+        out: *mut MaybeUninit<libra_wgpu_filter_chain_t>
+    ) mut |encoder| {
+        assert_non_null!(preset);
+        assert_non_null!(device);
+        assert_non_null!(queue);
+
+        let preset = unsafe {
+            let preset_ptr = &mut *preset;
+            let preset = preset_ptr.take();
+            Box::from_raw(preset.unwrap().as_ptr())
+        };
+
+        let options = if options.is_null() {
+            None
+        } else {
+            Some(unsafe { options.read() })
+        };
+
+        let options = options.map(FromUninit::from_uninit);
+
+        unsafe {
+            let device = arc_from_shared(device);
+            let queue = arc_from_shared(queue);
+            let chain = FilterChain::load_from_preset_deferred(*preset,
+                device,
+                queue,
+                encoder,
+                options.as_ref())?;
+
+            out.write(MaybeUninit::new(NonNull::new(Box::into_raw(Box::new(
+                chain,
+            )))))
+        }
+    }
+}
+
+extern_fn! {
+    /// Create the filter chain given the shader preset on the librashader worker threads,
+    /// deferring GPU-side initialization to the caller as with `libra_wgpu_filter_chain_create_deferred`.
+    ///
+    /// The passes of the shader preset are preprocessed and compiled without blocking the calling thread.
+    /// `progress` is called with `userdata` as each pass is preprocessed and compiled, and may be null.
+    /// The filter chain is retrieved from the resulting job with `libra_wgpu_filter_chain_job_finish`, or
+    /// the job can be cancelled with `libra_filter_chain_job_cancel` and freed with `libra_filter_chain_job_free`.
+    ///
+    /// The shader preset is immediately invalidated and must be recreated after
+    /// the job is created.
+    ///
+    /// ## Safety:
+    /// - `device` and `queue` must be valid device and queue handles. The filter chain takes its
+    ///   own reference to both, so the caller keeps ownership of theirs.
+    /// - `queue` must be a queue of `device`.
+    /// - `encoder` must be a valid command encoder handle of `device`.
+    /// - `preset` must be either null, or valid and aligned.
+    /// - `options` must be either null, or valid and aligned.
+    /// - `progress` and `userdata` must be safe to use from any thread until the job is finished or freed.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    ///
+    /// The provided command encoder must be ready for recording and contain no prior commands.
+    /// The command encoder must not be used or freed until the job is finished or freed. After
+    /// `libra_wgpu_filter_chain_job_finish` returns the filter chain, the caller is responsible for
+    /// immediately submitting the command encoder to `queue` with `libra_wgpu_queue_submit`. The
+    /// command buffer must be completely executed before calling `libra_wgpu_filter_chain_frame`.
+    fn libra_wgpu_filter_chain_create_async(
+        preset: *mut libra_shader_preset_t,
+        device: PWGPUDevice,
+        queue: PWGPUQueue,
+        encoder: PWGPUCommandEncoder,
+        options: *const MaybeUninit<filter_chain_wgpu_opt_t>,
+        progress: libra_load_progress_callback_t,
+        userdata: *mut c_void,
+        out: *mut MaybeUninit<libra_filter_chain_job_t>
+    ) {
+        assert_non_null!(preset);
+        assert_non_null!(device);
+        assert_non_null!(queue);
+        assert_non_null!(encoder);
+
+        let preset = unsafe {
+            let preset_ptr = &mut *preset;
+            let preset = preset_ptr.take();
+            Box::from_raw(preset.unwrap().as_ptr())
+        };
+
+        let options = if options.is_null() {
+            None
+        } else {
+            Some(unsafe { options.read() })
+        };
+
+        let options: Option<FilterChainOptions> = options.map(FromUninit::from_uninit);
+        let device = unsafe { arc_from_shared(device) };
+        let queue = unsafe { arc_from_shared(queue) };
+        let mut encoder = JobCommandEncoder(encoder);
+
+        let job = FilterChainJob::spawn(*preset, progress, userdata, move |pack, observer| {
+            FilterChain::load_from_pack_deferred_with_observer(
+                pack,
+                device,
+                queue,
+                unsafe { encoder.as_mut() },
+                options.as_ref(),
+                observer,
+            )
+        });
+
+        unsafe {
+            out.write(MaybeUninit::new(NonNull::new(Box::into_raw(Box::new(job)))))
+        }
+    }
+}
+
+extern_fn! {
+    /// Wait for the creation of a filter chain to finish, and retrieve the filter chain.
+    ///
+    /// This blocks until the job created by `libra_wgpu_filter_chain_create_async` has finished.
+    /// Once the job has finished, the resulting value in `job` becomes null, even if the filter
+    /// chain could not be created. If the job was cancelled, the returned error has the code
+    /// `LIBRA_ERRNO_CANCELLED`.
+    ///
+    /// ## Safety
+    /// - `job` must be either null or a valid and aligned pointer to a `libra_filter_chain_job_t`
+    ///   created by `libra_wgpu_filter_chain_create_async`.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    fn libra_wgpu_filter_chain_job_finish(
+        job: *mut libra_filter_chain_job_t,
+        out: *mut MaybeUninit<libra_wgpu_filter_chain_t>
+    ) {
+        assert_non_null!(job);
+        let chain = FilterChainJob::finish::<FilterChain, FilterChainError>(unsafe { &mut *job })?;
+        unsafe {
+            out.write(MaybeUninit::new(NonNull::new(Box::into_raw(Box::new(
+                chain,
+            )))))
+        }
+    }
+}
+
+extern_fn! {
+    /// Create the filter chain given the shader preset pack.
+    ///
+    /// The shader preset pack is immediately invalidated and must be recreated after
+    /// the filter chain is created.
+    ///
+    /// ## Safety:
+    /// - `device` and `queue` must be valid device and queue handles. The filter chain takes its
+    ///   own reference to both, so the caller keeps ownership of theirs.
+    /// - `queue` must be a queue of `device`.
+    /// - `pack` must be either null, or valid and aligned.
+    /// - `options` must be either null, or valid and aligned.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    fn libra_wgpu_filter_chain_create_from_pack(
+        pack: *mut libra_preset_pack_t,
+        device: PWGPUDevice,
+        queue: PWGPUQueue,
+        options: *const MaybeUninit<filter_chain_wgpu_opt_t>,
+        out: *mut MaybeUninit<libra_wgpu_filter_chain_t>
+    ) {
+        assert_non_null!(pack);
+        assert_non_null!(device);
+        assert_non_null!(queue);
+
+        let pack = unsafe {
+            let pack_ptr = &mut *pack;
+            let pack = pack_ptr.take();
+            Box::from_raw(pack.unwrap().as_ptr())
+        };
+
+        let options = if options.is_null() {
+            None
+        } else {
+            Some(unsafe { options.read() })
+        };
+
+        let options = options.map(FromUninit::from_uninit);
+
+        unsafe {
+            let device = arc_from_shared(device);
+            let queue = arc_from_shared(queue);
+            let chain = FilterChain::load_from_pack(*pack, device, queue, options.as_ref())?;
+
+            out.write(MaybeUninit::new(NonNull::new(Box::into_raw(Box::new(
+                chain,
+            )))))
+        }
+    }
+}
+
+extern_fn! {
+    /// Create the filter chain given the shader preset pack deferring and GPU-side initialization
+    /// to the caller. This function therefore requires no external synchronization of the device queue.
+    ///
+    /// The shader preset pack is immediately invalidated and must be recreated after
+    /// the filter chain is created.
+    ///
+    /// ## Safety:
+    /// - `device` and `queue` must be valid device and queue handles. The filter chain takes its
+    ///   own reference to both, so the caller keeps ownership of theirs.
+    /// - `queue` must be a queue of `device`.
+    /// - `encoder` must be a valid command encoder handle of `device`.
+    /// - `pack` must be either null, or valid and aligned.
+    /// - `options` must be either null, or valid and aligned.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    ///
+    /// The provided command encoder must be ready for recording and contain no prior commands.
+    /// The caller is responsible for immediately submitting the command encoder to `queue` with
+    /// `libra_wgpu_queue_submit`. The command buffer must be completely executed before calling
+    /// `libra_wgpu_filter_chain_frame`.
+    fn libra_wgpu_filter_chain_create_from_pack_deferred(
+        pack: *mut libra_preset_pack_t,
+        device: PWGPUDevice,
+        queue: PWGPUQueue,
+        encoder: PWGPUCommandEncoder,
+        options: *const MaybeUninit<filter_chain_wgpu_opt_t>,
+        out: *mut MaybeUninit<libra_wgpu_filter_chain_t>
+    ) mut |encoder| {
+        assert_non_null!(pack);
+        assert_non_null!(device);
+        assert_non_null!(queue);
+
+        let pack = unsafe {
+            let pack_ptr = &mut *pack;
+            let pack = pack_ptr.take();
+            Box::from_raw(pack.unwrap().as_ptr())
+        };
+
+        let options = if options.is_null() {
+            None
+        } else {
+            Some(unsafe { options.read() })
+        };
+
+        let options = options.map(FromUninit::from_uninit);
+
+        unsafe {
+            let device = arc_from_shared(device);
+            let queue = arc_from_shared(queue);
+            let chain = FilterChain::load_from_pack_deferred(*pack,
+                device,
+                queue,
+                encoder,
+                options.as_ref())?;
+
+            out.write(MaybeUninit::new(NonNull::new(Box::into_raw(Box::new(
+                chain,
+            )))))
+        }
+    }
+}
+
+extern_fn! {
+    /// Records rendering commands for a frame with the given parameters for the given filter chain
+    /// to the input command encoder.
+    /// ## Parameters
+    ///
+    /// - `chain` is a handle to the filter chain.
+    /// - `encoder` is a command encoder handle to record draw commands to.
+    /// - `frame_count` is the number of frames passed to the shader
+    /// - `image` is a texture handle that will serve as the source image for the frame. It must have
+    ///    been created with the `TEXTURE_BINDING` usage.
+    /// - `output` is a texture handle that is the render target of the frame. It must have
+    ///    been created with the `RENDER_ATTACHMENT` usage.
+    ///
+    /// - `viewport` is a pointer to a `libra_viewport_t` that specifies the area onto which scissor and viewport
+    ///    will be applied to the render target. It may be null, in which case a default viewport spanning the
+    ///    entire render target will be used.
+    /// - `mvp` is a pointer to an array of 16 `float` values to specify the model view projection matrix to
+    ///    be passed to the shader.
+    /// - `options` is a pointer to options for the frame. Valid options are dependent on the `LIBRASHADER_API_VERSION`
+    ///    passed in. It may be null, in which case default options for the filter chain are used.
+    ///
+    /// ## Safety
+    /// - `encoder` must be a valid command encoder handle of the device the filter chain was created with.
+    /// - `image` must be a valid texture handle. The filter chain may keep its own reference to the
+    ///    texture until the next frame.
+    /// - `output` must be a valid texture handle.
+    /// - `chain` may be null, invalid, but not uninitialized. If `chain` is null or invalid, this
+    ///    function will return an error.
+    /// - `mvp` may be null, or if it is not null, must be an aligned pointer to 16 consecutive `float`
+    ///    values for the model view projection matrix.
+    /// - `opt` may be null, or if it is not null, must be an aligned pointer to a valid `frame_wgpu_opt_t`
+    ///    struct.
+    /// - You must ensure that only one thread has access to `chain` before you call this function. Only one
+    ///   thread at a time may call this function.
+    nopanic fn libra_wgpu_filter_chain_frame(
+        chain: *mut libra_wgpu_filter_chain_t,
+        encoder: PWGPUCommandEncoder,
+        frame_count: usize,
+        image: PWGPUTexture,
+        output: PWGPUTexture,
+        viewport: *const libra_viewport_t,
+        mvp: *const f32,
+        opt: *const MaybeUninit<frame_wgpu_opt_t>
+    ) |output|; mut |chain, encoder|  {
+        assert_some_ptr!(mut chain);
+        if image.is_null() {
+            return Err(LibrashaderError::InvalidParameter("image"));
+        }
+
+        let mvp = if mvp.is_null() {
+            None
+        } else {
+            Some(<&[f32; 16]>::try_from(unsafe { slice::from_raw_parts(mvp, 16) }).unwrap())
+        };
+        let opt = if opt.is_null() {
+            None
+        } else {
+            Some(unsafe { opt.read() })
+        };
+        let opt = opt.map(FromUninit::from_uninit);
+
+        let image = unsafe { arc_from_shared(image) };
+        let view = output.create_view(&wgpu::TextureViewDescriptor::default());
+        let output = WgpuOutputView::new_from_raw(&view, output.size().into(), output.format());
+
+        let viewport = if viewport.is_null() {
+            Viewport::new_render_target_sized_origin(output, mvp)?
+        } else {
+            let viewport = unsafe { viewport.read() };
+            Viewport {
+                x: viewport.x,
+                y: viewport.y,
+                output,
+                size: Size {
+                    height: viewport.height,
+                    width: viewport.width
+                },
+                mvp,
+            }
+        };
+
+        chain.frame(image, &viewport, encoder, frame_count, opt.as_ref())?;
+    }
+}
+
+extern_fn! {
+    /// Sets a parameter for the filter chain.
+    ///
+    /// If the parameter does not exist, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_wgpu_filter_chain_t`.
+    /// - `param_name` must be either null or a null terminated string.
+    fn libra_wgpu_filter_chain_set_param(
+        chain: *mut libra_wgpu_filter_chain_t,
+        param_name: *const c_char,
+        value: f32
+    ) |chain| {
+        assert_some_ptr!(chain);
+        unsafe {
+            let name = CStr::from_ptr(param_name);
+            let name = name.to_str()?;
+
+            if chain.parameters().set_parameter_value(name, value).is_none() {
+                return Err(LibrashaderError::UnknownShaderParameter(param_name))
+            }
+        }
+    }
+}
+
+extern_fn! {
+    /// Gets a parameter for the filter chain.
+    ///
+    /// If the parameter does not exist, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_wgpu_filter_chain_t`.
+    /// - `param_name` must be either null or a null terminated string.
+    fn libra_wgpu_filter_chain_get_param(
+        chain: *const libra_wgpu_filter_chain_t,
+        param_name: *const c_char,
+        out: *mut MaybeUninit<f32>
+    ) |chain| {
+        assert_some_ptr!(chain);
+
+        unsafe {
+            let name = CStr::from_ptr(param_name);
+            let name = name.to_str()?;
+
+            let Some(value) = chain.parameters().parameter_value(name) else {
+                return Err(LibrashaderError::UnknownShaderParameter(param_name))
+            };
+
+            out.write(MaybeUninit::new(value));
+        }
+    }
+}
+
+extern_fn! {
+    /// Gets the metadata and current value of every parameter of the filter chain.
+    ///
+    /// Parameters are listed in the order they are declared by the passes of the filter chain.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_wgpu_filter_chain_t`.
+    /// - `out` must be an aligned pointer to a `libra_filter_chain_param_list_t`.
+    /// - The output struct should be treated as immutable. Mutating any struct fields
+    ///   in the returned struct may at best cause memory leaks, and at worse
+    ///   cause undefined behaviour when later freed.
+    /// - The output struct must be freed with `libra_filter_chain_free_params`.
+    fn libra_wgpu_filter_chain_get_params(
+        chain: *const libra_wgpu_filter_chain_t,
+        out: *mut MaybeUninit<libra_filter_chain_param_list_t>
+    ) |chain| {
+        assert_some_ptr!(chain);
+        assert_non_null!(out);
+        let list = libra_filter_chain_param_list_t::from_parameters(chain.parameters())?;
+        unsafe {
+            out.write(MaybeUninit::new(list));
+        }
+    }
+}
+
+extern_fn! {
+    /// Resets every parameter of the filter chain to the value it had when the filter chain was loaded.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_wgpu_filter_chain_t`.
+    fn libra_wgpu_filter_chain_reset_params(
+        chain: *mut libra_wgpu_filter_chain_t
+    ) |chain| {
+        assert_some_ptr!(chain);
+        chain.parameters().reset_to_defaults();
+    }
+}
+
+extern_fn! {
+    /// Sets the number of active passes for this chain.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_wgpu_filter_chain_t`.
+    fn libra_wgpu_filter_chain_set_active_pass_count(
+        chain: *mut libra_wgpu_filter_chain_t,
+        value: u32
+    ) |chain| {
+        assert_some_ptr!(chain);
+        chain.parameters().set_passes_enabled(value as usize);
+    }
+}
+
+extern_fn! {
+    /// Gets the number of active passes for this chain.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_wgpu_filter_chain_t`.
+    fn libra_wgpu_filter_chain_get_active_pass_count(
+        chain: *const libra_wgpu_filter_chain_t,
+        out: *mut MaybeUninit<u32>
+    ) |chain| {
+        assert_some_ptr!(chain);
+        let value = chain.parameters().passes_enabled();
+        unsafe {
+            out.write(MaybeUninit::new(value as u32))
+        }
+    }
+}
+
+extern_fn! {
+    /// Free a wgpu filter chain.
+    ///
+    /// The resulting value in `chain` then becomes null.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_wgpu_filter_chain_t`.
+    fn libra_wgpu_filter_chain_free(
+        chain: *mut libra_wgpu_filter_chain_t
+    ) {
+        assert_non_null!(chain);
+        unsafe {
+            let chain_ptr = &mut *chain;
+            let chain = chain_ptr.take();
+            drop(Box::from_raw(chain.unwrap().as_ptr()))
+        };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ctypes::{FromUninit, LIBRA_HDR_OUTPUT};
+    use crate::runtime::wgpu::filter_chain::filter_chain_wgpu_opt_t;
+    use librashader::runtime::HdrOutputEncoding;
+    use std::mem::MaybeUninit;
+
+    fn options(version: usize, hdr_output: LIBRA_HDR_OUTPUT) -> Option<HdrOutputEncoding> {
+        let options = filter_chain_wgpu_opt_t {
+            version,
+            hdr_output,
+            ..Default::default()
+        };
+        filter_chain_wgpu_opt_t::from_uninit(MaybeUninit::new(options)).hdr_output
+    }
+
+    #[test]
+    fn reads_hdr_output() {
+        assert_eq!(options(2, LIBRA_HDR_OUTPUT::None), None);
+        assert_eq!(
+            options(2, LIBRA_HDR_OUTPUT::Scrgb),
+            Some(HdrOutputEncoding::ScRgb)
+        );
+        assert_eq!(
+            options(2, LIBRA_HDR_OUTPUT::Hdr10),
+            Some(HdrOutputEncoding::Hdr10)
+        );
+        // hdr_output was added in API version 2.
+        assert_eq!(options(1, LIBRA_HDR_OUTPUT::Hdr10), None);
+    }
+}
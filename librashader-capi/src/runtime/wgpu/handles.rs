@@ -0,0 +1,168 @@
+use crate::error::{assert_non_null, LibrashaderError};
+use crate::ffi::extern_fn;
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::sync::Arc;
+
+/// A handle to a `wgpu::Device`.
+///
+/// Device handles are created over the device of another graphics API, such as
+/// with `libra_wgpu_device_create_vk`, and freed with `libra_wgpu_device_free`.
+/// Hosts written in Rust may instead pass a pointer returned by `Arc::into_raw`.
+pub type PWGPUDevice = *const wgpu::Device;
+
+/// A handle to a `wgpu::Queue`.
+///
+/// Queue handles are created along with their device, and freed with `libra_wgpu_queue_free`.
+/// Hosts written in Rust may instead pass a pointer returned by `Arc::into_raw`.
+pub type PWGPUQueue = *const wgpu::Queue;
+
+/// A handle to a `wgpu::Texture`.
+///
+/// Texture handles are created over the image of another graphics API, such as
+/// with `libra_wgpu_texture_create_vk`, and freed with `libra_wgpu_texture_free`.
+/// Hosts written in Rust may instead pass a pointer returned by `Arc::into_raw`.
+pub type PWGPUTexture = *const wgpu::Texture;
+
+/// A handle to a `wgpu::CommandEncoder`.
+///
+/// Command encoder handles are created with `libra_wgpu_command_encoder_create`, and
+/// consumed by `libra_wgpu_queue_submit` or freed with `libra_wgpu_command_encoder_free`.
+pub type PWGPUCommandEncoder = *mut wgpu::CommandEncoder;
+
+/// Take a new reference to an object shared with `Arc::into_raw`, leaving the reference
+/// held by the caller intact.
+///
+/// ## Safety
+/// `ptr` must have been returned by `Arc::into_raw`, and the object must still be alive.
+pub(crate) unsafe fn arc_from_shared<T>(ptr: *const T) -> Arc<T> {
+    unsafe {
+        Arc::increment_strong_count(ptr);
+        Arc::from_raw(ptr)
+    }
+}
+
+/// Release the reference held by a handle shared with `Arc::into_raw`, and set the handle to null.
+///
+/// ## Safety
+/// `handle` must be a valid and aligned pointer to either null, or a pointer returned by `Arc::into_raw`.
+unsafe fn free_shared<T>(
+    handle: *mut *const T,
+    name: &'static str,
+) -> Result<(), LibrashaderError> {
+    let ptr = unsafe { handle.replace(ptr::null()) };
+    if ptr.is_null() {
+        return Err(LibrashaderError::InvalidParameter(name));
+    }
+    drop(unsafe { Arc::from_raw(ptr) });
+    Ok(())
+}
+
+extern_fn! {
+    /// Create a command encoder to record filter chain commands to.
+    ///
+    /// ## Safety
+    /// - `device` must be a valid device handle.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    fn libra_wgpu_command_encoder_create(
+        device: PWGPUDevice,
+        out: *mut MaybeUninit<PWGPUCommandEncoder>
+    ) |device| {
+        assert_non_null!(out);
+        let encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("librashader"),
+        });
+
+        unsafe {
+            out.write(MaybeUninit::new(Box::into_raw(Box::new(encoder))))
+        }
+    }
+}
+
+extern_fn! {
+    /// Finish recording the command encoder and submit its commands to the queue.
+    ///
+    /// The command encoder is consumed, and the resulting value in `encoder` then becomes null.
+    ///
+    /// ## Safety
+    /// - `queue` must be a valid queue handle of the device the command encoder was created with.
+    /// - `encoder` must be either null or a valid and aligned pointer to a command encoder handle.
+    fn libra_wgpu_queue_submit(
+        queue: PWGPUQueue,
+        encoder: *mut PWGPUCommandEncoder
+    ) |queue| {
+        assert_non_null!(encoder);
+        let encoder = unsafe { encoder.replace(ptr::null_mut()) };
+        if encoder.is_null() {
+            return Err(LibrashaderError::InvalidParameter("encoder"));
+        }
+
+        let encoder = unsafe { Box::from_raw(encoder) };
+        queue.submit([encoder.finish()]);
+    }
+}
+
+extern_fn! {
+    /// Free a command encoder without submitting its commands.
+    ///
+    /// The resulting value in `encoder` then becomes null.
+    /// ## Safety
+    /// - `encoder` must be either null or a valid and aligned pointer to a command encoder handle.
+    fn libra_wgpu_command_encoder_free(
+        encoder: *mut PWGPUCommandEncoder
+    ) {
+        assert_non_null!(encoder);
+        let encoder = unsafe { encoder.replace(ptr::null_mut()) };
+        if encoder.is_null() {
+            return Err(LibrashaderError::InvalidParameter("encoder"));
+        }
+
+        drop(unsafe { Box::from_raw(encoder) });
+    }
+}
+
+extern_fn! {
+    /// Free a device handle.
+    ///
+    /// Filter chains created with the device keep their own reference to it, so the device
+    /// handle may be freed before them. The resulting value in `device` then becomes null.
+    /// ## Safety
+    /// - `device` must be either null or a valid and aligned pointer to a device handle.
+    fn libra_wgpu_device_free(
+        device: *mut PWGPUDevice
+    ) {
+        assert_non_null!(device);
+        unsafe { free_shared(device, "device")? }
+    }
+}
+
+extern_fn! {
+    /// Free a queue handle.
+    ///
+    /// Filter chains created with the queue keep their own reference to it, so the queue
+    /// handle may be freed before them. The resulting value in `queue` then becomes null.
+    /// ## Safety
+    /// - `queue` must be either null or a valid and aligned pointer to a queue handle.
+    fn libra_wgpu_queue_free(
+        queue: *mut PWGPUQueue
+    ) {
+        assert_non_null!(queue);
+        unsafe { free_shared(queue, "queue")? }
+    }
+}
+
+extern_fn! {
+    /// Free a texture handle.
+    ///
+    /// A filter chain may keep its own reference to its last input texture until the next frame.
+    /// The image the texture was created over must outlive every reference to the texture.
+    /// The resulting value in `texture` then becomes null.
+    /// ## Safety
+    /// - `texture` must be either null or a valid and aligned pointer to a texture handle.
+    fn libra_wgpu_texture_free(
+        texture: *mut PWGPUTexture
+    ) {
+        assert_non_null!(texture);
+        unsafe { free_shared(texture, "texture")? }
+    }
+}
@@ -0,0 +1,14 @@
+//! C API for the librashader wgpu Runtime (`libra_wgpu_*`).
+
+mod filter_chain;
+mod handles;
+pub use filter_chain::*;
+pub use handles::*;
+
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "runtime-wgpu-vulkan")))]
+#[cfg(feature = "runtime-wgpu-vulkan")]
+mod vulkan;
+#[cfg(feature = "runtime-wgpu-vulkan")]
+pub use vulkan::*;
+
+const _: () = crate::assert_thread_safe::<librashader::runtime::wgpu::FilterChain>();
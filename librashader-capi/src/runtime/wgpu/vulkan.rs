@@ -0,0 +1,630 @@
+use crate::error::{assert_non_null, LibrashaderError};
+use crate::ffi::extern_fn;
+use crate::runtime::wgpu::handles::{PWGPUDevice, PWGPUQueue, PWGPUTexture};
+use ash::vk;
+use librashader::ImageFormat;
+use std::ffi::{c_char, CStr};
+use std::mem::MaybeUninit;
+use std::slice;
+use std::sync::Arc;
+use thiserror::Error;
+use wgpu::hal;
+use wgpu::hal::api::Vulkan;
+
+/// The optional features that filter chains use if the physical device supports them.
+const FEATURES: wgpu::Features = wgpu::Features::ADDRESS_MODE_CLAMP_TO_BORDER
+    .union(wgpu::Features::FLOAT32_FILTERABLE)
+    .union(wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES);
+
+/// An error that occurred creating wgpu objects over Vulkan objects.
+#[non_exhaustive]
+#[derive(Error, Debug)]
+pub enum WgpuVulkanError {
+    /// The Vulkan instance could not be used by wgpu.
+    #[error("The Vulkan instance could not be used by wgpu.")]
+    InstanceError(#[from] hal::InstanceError),
+    /// The physical device is not supported by wgpu.
+    #[error("The physical device is not supported by wgpu.")]
+    UnsupportedPhysicalDevice,
+    /// The Vulkan device could not be created.
+    #[error("The Vulkan device could not be created.")]
+    VulkanResult(#[from] vk::Result),
+    /// The Vulkan device could not be used by wgpu.
+    #[error("The Vulkan device could not be used by wgpu.")]
+    DeviceError(#[from] hal::DeviceError),
+    /// The wgpu device could not be created.
+    #[error("The wgpu device could not be created.")]
+    RequestDeviceError(#[from] wgpu::RequestDeviceError),
+    /// The format of the image is not supported by wgpu.
+    #[error("The image format {0:?} is not supported by wgpu.")]
+    UnsupportedFormat(vk::Format),
+}
+
+/// Handles required to create a wgpu device over a Vulkan device.
+#[repr(C)]
+pub struct libra_wgpu_vk_device_t {
+    /// The entry loader for the Vulkan library.
+    pub entry: Option<vk::PFN_vkGetInstanceProcAddr>,
+    /// A raw `VkInstance` handle for the Vulkan instance that will perform rendering.
+    pub instance: vk::Instance,
+    /// The Vulkan API version the instance was created with.
+    pub instance_api_version: u32,
+    /// The names of the extensions enabled for the instance.
+    pub instance_extensions: *const *const c_char,
+    /// The number of names in `instance_extensions`.
+    pub instance_extension_count: usize,
+    /// A raw `VkPhysicalDevice` handle for the physical device that will perform rendering.
+    pub physical_device: vk::PhysicalDevice,
+    /// A raw `VkDevice` handle created with `libra_wgpu_vk_create_device`
+    /// for the device attached to the instance that will perform rendering.
+    pub device: vk::Device,
+    /// The names of the extensions to enable for the device, in addition to the extensions that wgpu requires.
+    pub device_extensions: *const *const c_char,
+    /// The number of names in `device_extensions`.
+    pub device_extension_count: usize,
+    /// The queue family of the queue to submit commands to. This must be a graphics queue family.
+    pub queue_family_index: u32,
+    /// The index of the queue to submit commands to within its queue family.
+    pub queue_index: u32,
+}
+
+/// A Vulkan image to create a wgpu texture over.
+#[repr(C)]
+pub struct libra_wgpu_vk_image_t {
+    /// A raw `VkImage` handle.
+    pub handle: vk::Image,
+    /// The `VkFormat` of the `VkImage`.
+    pub format: vk::Format,
+    /// The width of the `VkImage`.
+    pub width: u32,
+    /// The height of the `VkImage`.
+    pub height: u32,
+    /// The `VkImageUsageFlags` the `VkImage` was created with.
+    pub usage: vk::ImageUsageFlags,
+}
+
+/// Read a list of extension names passed by the caller.
+///
+/// ## Safety
+/// `names` must be null if `count` is zero, or point to `count` null-terminated strings.
+unsafe fn extension_names<'a>(
+    names: *const *const c_char,
+    count: usize,
+) -> Result<Vec<&'a CStr>, LibrashaderError> {
+    if count == 0 {
+        return Ok(Vec::new());
+    }
+
+    if names.is_null() {
+        return Err(LibrashaderError::InvalidParameter("extensions"));
+    }
+
+    Ok(unsafe { slice::from_raw_parts(names, count) }
+        .iter()
+        .map(|&name| unsafe { CStr::from_ptr(name) })
+        .collect())
+}
+
+/// Wrap the Vulkan instance of the caller, and expose its physical device to wgpu.
+///
+/// ## Safety
+/// The instance and physical device handles of `vulkan` must be valid.
+unsafe fn expose_adapter(
+    vulkan: &libra_wgpu_vk_device_t,
+) -> Result<(hal::vulkan::Instance, hal::ExposedAdapter<Vulkan>), LibrashaderError> {
+    let Some(get_instance_proc_addr) = vulkan.entry else {
+        return Err(LibrashaderError::InvalidParameter("entry"));
+    };
+    if vulkan.instance == vk::Instance::null() {
+        return Err(LibrashaderError::InvalidParameter("instance"));
+    }
+    if vulkan.physical_device == vk::PhysicalDevice::null() {
+        return Err(LibrashaderError::InvalidParameter("physical_device"));
+    }
+
+    let enabled =
+        unsafe { extension_names(vulkan.instance_extensions, vulkan.instance_extension_count)? };
+
+    let static_fn = ash::StaticFn {
+        get_instance_proc_addr,
+    };
+    let entry = unsafe { ash::Entry::from_static_fn(static_fn.clone()) };
+    let raw_instance = unsafe { ash::Instance::load(&static_fn, vulkan.instance) };
+
+    // wgpu-hal only takes extension names with a static lifetime, so use its own names
+    // for the extensions it knows of that the caller has enabled.
+    let flags = wgpu::InstanceFlags::empty();
+    let mut extensions =
+        hal::vulkan::Instance::desired_extensions(&entry, vulkan.instance_api_version, flags)
+            .map_err(WgpuVulkanError::from)?;
+    extensions.retain(|extension| enabled.contains(extension));
+
+    // Without a drop guard, wgpu-hal leaves destroying the instance to the caller.
+    let instance = unsafe {
+        hal::vulkan::Instance::from_raw(
+            entry,
+            raw_instance,
+            vulkan.instance_api_version,
+            0,
+            None,
+            extensions,
+            flags,
+            false,
+            None,
+        )
+    }
+    .map_err(WgpuVulkanError::from)?;
+
+    let Some(adapter) = instance.expose_adapter(vulkan.physical_device) else {
+        return Err(WgpuVulkanError::UnsupportedPhysicalDevice.into());
+    };
+
+    Ok((instance, adapter))
+}
+
+extern_fn! {
+    /// Create a Vulkan device that a wgpu device can be created over with `libra_wgpu_device_create_vk`.
+    ///
+    /// The device is created from `vulkan.physical_device` with the extensions and features that wgpu
+    /// requires, the extensions in `vulkan.device_extensions`, and `vulkan.queue_index + 1` queues of
+    /// `vulkan.queue_family_index`. `vulkan.device` is ignored.
+    ///
+    /// The caller owns the device, and must destroy it with `vkDestroyDevice` after every wgpu object
+    /// created over it is freed.
+    ///
+    /// ## Safety
+    /// - `vulkan.entry` must be the `vkGetInstanceProcAddr` of the Vulkan library the instance was created with.
+    /// - `vulkan.instance` must have been created with `vulkan.instance_api_version` and the extensions
+    ///   in `vulkan.instance_extensions`.
+    /// - `vulkan.instance_extensions` and `vulkan.device_extensions` must be null if their count is zero,
+    ///   or point to that many null-terminated strings.
+    /// - `vulkan.queue_family_index` must be a graphics queue family of the physical device.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    fn libra_wgpu_vk_create_device(
+        vulkan: libra_wgpu_vk_device_t,
+        out: *mut MaybeUninit<vk::Device>
+    ) {
+        assert_non_null!(out);
+        let enabled = unsafe {
+            extension_names(vulkan.device_extensions, vulkan.device_extension_count)?
+        };
+        let (instance, adapter) = unsafe { expose_adapter(&vulkan)? };
+
+        let features = adapter.features & FEATURES;
+        let required = adapter.adapter.required_device_extensions(features);
+        let extensions: Vec<_> = required
+            .iter()
+            .chain(enabled.iter().filter(|extension| !required.contains(extension)))
+            .map(|extension| extension.as_ptr())
+            .collect();
+
+        let priorities = vec![1.0; vulkan.queue_index as usize + 1];
+        let queues = [vk::DeviceQueueCreateInfo::default()
+            .queue_family_index(vulkan.queue_family_index)
+            .queue_priorities(&priorities)];
+
+        let mut phd_features = adapter.adapter.physical_device_features(&required, features);
+        let info = phd_features.add_to_device_create(
+            vk::DeviceCreateInfo::default()
+                .queue_create_infos(&queues)
+                .enabled_extension_names(&extensions),
+        );
+
+        let device = unsafe {
+            instance
+                .shared_instance()
+                .raw_instance()
+                .create_device(vulkan.physical_device, &info, None)
+        }
+        .map_err(WgpuVulkanError::from)?;
+
+        unsafe {
+            out.write(MaybeUninit::new(device.handle()));
+        }
+    }
+}
+
+extern_fn! {
+    /// Create a wgpu device and queue over a Vulkan device.
+    ///
+    /// Commands submitted to the queue are submitted to the Vulkan queue at `queue_index` of `queue_family_index`.
+    /// The Vulkan instance and device are not destroyed when the wgpu device is freed, and must outlive
+    /// the wgpu device, its queue, and every object created with them, including filter chains.
+    ///
+    /// The device and queue are freed with `libra_wgpu_device_free` and `libra_wgpu_queue_free`.
+    ///
+    /// ## Safety
+    /// - `vulkan.entry` must be the `vkGetInstanceProcAddr` of the Vulkan library the instance was created with.
+    /// - `vulkan.instance` must have been created with `vulkan.instance_api_version` and the extensions
+    ///   in `vulkan.instance_extensions`.
+    /// - `vulkan.device` must have been created by `libra_wgpu_vk_create_device` with the same instance,
+    ///   physical device, device extensions, and queue as `vulkan`.
+    /// - `vulkan.instance_extensions` and `vulkan.device_extensions` must be null if their count is zero,
+    ///   or point to that many null-terminated strings.
+    /// - Access to the Vulkan queue must be externally synchronized with the queue of the wgpu device.
+    /// - `out_device` and `out_queue` must be aligned, but may be null, invalid, or uninitialized.
+    fn libra_wgpu_device_create_vk(
+        vulkan: libra_wgpu_vk_device_t,
+        out_device: *mut MaybeUninit<PWGPUDevice>,
+        out_queue: *mut MaybeUninit<PWGPUQueue>
+    ) {
+        assert_non_null!(out_device);
+        assert_non_null!(out_queue);
+        if vulkan.device == vk::Device::null() {
+            return Err(LibrashaderError::InvalidParameter("device"));
+        }
+
+        let (instance, adapter) = unsafe { expose_adapter(&vulkan)? };
+
+        // The device was created with the extensions wgpu requires, and wgpu does not use
+        // any of the other extensions of the device.
+        let features = adapter.features & FEATURES;
+        let extensions = adapter.adapter.required_device_extensions(features);
+
+        let raw_device = unsafe {
+            ash::Device::load(instance.shared_instance().raw_instance().fp_v1_0(), vulkan.device)
+        };
+        let device = unsafe {
+            adapter.adapter.device_from_raw(
+                raw_device,
+                false,
+                &extensions,
+                features,
+                &wgpu::MemoryHints::default(),
+                vulkan.queue_family_index,
+                vulkan.queue_index,
+            )
+        }
+        .map_err(WgpuVulkanError::from)?;
+
+        let instance = unsafe { wgpu::Instance::from_hal::<Vulkan>(instance) };
+        let adapter = unsafe { instance.create_adapter_from_hal(adapter) };
+        let (device, queue) = unsafe {
+            adapter.create_device_from_hal(
+                device,
+                &wgpu::DeviceDescriptor {
+                    label: Some("librashader"),
+                    required_features: features,
+                    required_limits: adapter.limits(),
+                    memory_hints: Default::default(),
+                },
+                None,
+            )
+        }
+        .map_err(WgpuVulkanError::from)?;
+
+        unsafe {
+            out_device.write(MaybeUninit::new(Arc::into_raw(Arc::new(device))));
+            out_queue.write(MaybeUninit::new(Arc::into_raw(Arc::new(queue))));
+        }
+    }
+}
+
+extern_fn! {
+    /// Create a wgpu texture over a Vulkan image.
+    ///
+    /// The image is not destroyed when the texture is freed, and must outlive the texture. A filter
+    /// chain may keep its own reference to its input texture until the next frame.
+    ///
+    /// wgpu does not know the layout of the image, and transitions it from `VK_IMAGE_LAYOUT_UNDEFINED`
+    /// the first time the texture is used, which may discard the contents of the image. After commands
+    /// using the texture are submitted, the image is left in the layout of its last use by wgpu.
+    ///
+    /// The texture is freed with `libra_wgpu_texture_free`.
+    ///
+    /// ## Safety
+    /// - `device` must be a device handle created with `libra_wgpu_device_create_vk`.
+    /// - `image.handle` must be a `VkImage` of the Vulkan device, created with a single mip level and
+    ///   array layer, and with `image.format`, `image.width`, `image.height`, and `image.usage`.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    fn libra_wgpu_texture_create_vk(
+        device: PWGPUDevice,
+        image: libra_wgpu_vk_image_t,
+        out: *mut MaybeUninit<PWGPUTexture>
+    ) |device| {
+        assert_non_null!(out);
+        if image.handle == vk::Image::null() {
+            return Err(LibrashaderError::InvalidParameter("image"));
+        }
+
+        let format: Option<wgpu::TextureFormat> = ImageFormat::from(image.format).into();
+        let Some(format) = format else {
+            return Err(WgpuVulkanError::UnsupportedFormat(image.format).into());
+        };
+
+        let size = wgpu::Extent3d {
+            width: image.width,
+            height: image.height,
+            depth_or_array_layers: 1,
+        };
+
+        let (usage, uses) = texture_usage(image.usage);
+
+        // With a drop guard, wgpu-hal leaves destroying the image to the caller.
+        let texture = unsafe {
+            hal::vulkan::Device::texture_from_raw(
+                image.handle,
+                &hal::TextureDescriptor {
+                    label: None,
+                    size,
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format,
+                    usage: uses,
+                    memory_flags: hal::MemoryFlags::empty(),
+                    view_formats: vec![],
+                },
+                Some(Box::new(())),
+            )
+        };
+
+        let texture = unsafe {
+            device.create_texture_from_hal::<Vulkan>(
+                texture,
+                &wgpu::TextureDescriptor {
+                    label: Some("librashader"),
+                    size,
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format,
+                    usage,
+                    view_formats: &[],
+                },
+            )
+        };
+
+        unsafe {
+            out.write(MaybeUninit::new(Arc::into_raw(Arc::new(texture))));
+        }
+    }
+}
+
+/// Get the wgpu usages of an image with the given Vulkan usages.
+fn texture_usage(usage: vk::ImageUsageFlags) -> (wgpu::TextureUsages, hal::TextureUses) {
+    let mut usages = wgpu::TextureUsages::empty();
+    let mut uses = hal::TextureUses::empty();
+
+    if usage.contains(vk::ImageUsageFlags::TRANSFER_SRC) {
+        usages |= wgpu::TextureUsages::COPY_SRC;
+        uses |= hal::TextureUses::COPY_SRC;
+    }
+    if usage.contains(vk::ImageUsageFlags::TRANSFER_DST) {
+        usages |= wgpu::TextureUsages::COPY_DST;
+        uses |= hal::TextureUses::COPY_DST;
+    }
+    if usage.contains(vk::ImageUsageFlags::SAMPLED) {
+        usages |= wgpu::TextureUsages::TEXTURE_BINDING;
+        uses |= hal::TextureUses::RESOURCE;
+    }
+    if usage.contains(vk::ImageUsageFlags::STORAGE) {
+        usages |= wgpu::TextureUsages::STORAGE_BINDING;
+        uses |= hal::TextureUses::STORAGE_READ_WRITE;
+    }
+    if usage.contains(vk::ImageUsageFlags::COLOR_ATTACHMENT) {
+        usages |= wgpu::TextureUsages::RENDER_ATTACHMENT;
+        uses |= hal::TextureUses::COLOR_TARGET;
+    }
+
+    (usages, uses)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error::{libra_error_errno, libra_error_free, LIBRA_ERRNO};
+    use crate::presets::libra_preset_create;
+    use crate::runtime::wgpu::{
+        libra_wgpu_command_encoder_create, libra_wgpu_device_free, libra_wgpu_filter_chain_create,
+        libra_wgpu_filter_chain_frame, libra_wgpu_filter_chain_free, libra_wgpu_queue_free,
+        libra_wgpu_queue_submit, libra_wgpu_texture_free,
+    };
+    use std::ptr;
+
+    fn device_info(
+        entry: Option<vk::PFN_vkGetInstanceProcAddr>,
+        instance: vk::Instance,
+        physical_device: vk::PhysicalDevice,
+        device: vk::Device,
+        queue_family_index: u32,
+    ) -> libra_wgpu_vk_device_t {
+        libra_wgpu_vk_device_t {
+            entry,
+            instance,
+            instance_api_version: vk::API_VERSION_1_2,
+            instance_extensions: ptr::null(),
+            instance_extension_count: 0,
+            physical_device,
+            device,
+            device_extensions: ptr::null(),
+            device_extension_count: 0,
+            queue_family_index,
+            queue_index: 0,
+        }
+    }
+
+    /// Create a sampled and renderable image, as a C host would, with memory bound to it.
+    unsafe fn create_image(
+        instance: &ash::Instance,
+        physical_device: vk::PhysicalDevice,
+        device: &ash::Device,
+    ) -> (libra_wgpu_vk_image_t, vk::DeviceMemory) {
+        let usage = vk::ImageUsageFlags::SAMPLED
+            | vk::ImageUsageFlags::COLOR_ATTACHMENT
+            | vk::ImageUsageFlags::TRANSFER_SRC
+            | vk::ImageUsageFlags::TRANSFER_DST;
+        let info = vk::ImageCreateInfo::default()
+            .image_type(vk::ImageType::TYPE_2D)
+            .format(vk::Format::R8G8B8A8_UNORM)
+            .extent(vk::Extent3D {
+                width: 64,
+                height: 64,
+                depth: 1,
+            })
+            .mip_levels(1)
+            .array_layers(1)
+            .samples(vk::SampleCountFlags::TYPE_1)
+            .tiling(vk::ImageTiling::OPTIMAL)
+            .usage(usage)
+            .initial_layout(vk::ImageLayout::UNDEFINED);
+
+        unsafe {
+            let image = device.create_image(&info, None).unwrap();
+            let requirements = device.get_image_memory_requirements(image);
+            let properties = instance.get_physical_device_memory_properties(physical_device);
+            let memory_type_index = (0..properties.memory_type_count)
+                .find(|&index| {
+                    requirements.memory_type_bits & (1 << index) != 0
+                        && properties.memory_types[index as usize]
+                            .property_flags
+                            .contains(vk::MemoryPropertyFlags::DEVICE_LOCAL)
+                })
+                .unwrap();
+            let memory = device
+                .allocate_memory(
+                    &vk::MemoryAllocateInfo::default()
+                        .allocation_size(requirements.size)
+                        .memory_type_index(memory_type_index),
+                    None,
+                )
+                .unwrap();
+            device.bind_image_memory(image, memory, 0).unwrap();
+
+            let image = libra_wgpu_vk_image_t {
+                handle: image,
+                format: vk::Format::R8G8B8A8_UNORM,
+                width: 64,
+                height: 64,
+                usage,
+            };
+            (image, memory)
+        }
+    }
+
+    #[test]
+    fn rejects_missing_entry() {
+        let vulkan = device_info(
+            None,
+            vk::Instance::null(),
+            vk::PhysicalDevice::null(),
+            vk::Device::null(),
+            0,
+        );
+        let mut device = MaybeUninit::uninit();
+
+        unsafe {
+            let mut error = libra_wgpu_vk_create_device(vulkan, &mut device);
+            assert!(matches!(
+                libra_error_errno(error),
+                LIBRA_ERRNO::INVALID_PARAMETER
+            ));
+            libra_error_free(&mut error);
+        }
+    }
+
+    #[test]
+    #[ignore = "requires a Vulkan device"]
+    fn renders_to_vulkan_images() {
+        unsafe {
+            let entry = ash::Entry::load().unwrap();
+            let application = vk::ApplicationInfo::default().api_version(vk::API_VERSION_1_2);
+            let instance = entry
+                .create_instance(
+                    &vk::InstanceCreateInfo::default().application_info(&application),
+                    None,
+                )
+                .unwrap();
+            let physical_device = instance.enumerate_physical_devices().unwrap()[0];
+            let queue_family_index = instance
+                .get_physical_device_queue_family_properties(physical_device)
+                .iter()
+                .position(|family| family.queue_flags.contains(vk::QueueFlags::GRAPHICS))
+                .unwrap() as u32;
+            let info = |device| {
+                device_info(
+                    Some(entry.static_fn().get_instance_proc_addr),
+                    instance.handle(),
+                    physical_device,
+                    device,
+                    queue_family_index,
+                )
+            };
+
+            let mut raw_device = MaybeUninit::uninit();
+            assert!(
+                libra_wgpu_vk_create_device(info(vk::Device::null()), &mut raw_device).is_none()
+            );
+            let raw_device = ash::Device::load(instance.fp_v1_0(), raw_device.assume_init());
+
+            let mut device = MaybeUninit::uninit();
+            let mut queue = MaybeUninit::uninit();
+            assert!(libra_wgpu_device_create_vk(
+                info(raw_device.handle()),
+                &mut device,
+                &mut queue
+            )
+            .is_none());
+            let mut device = device.assume_init();
+            let mut queue = queue.assume_init();
+
+            let (input_image, input_memory) = create_image(&instance, physical_device, &raw_device);
+            let (output_image, output_memory) =
+                create_image(&instance, physical_device, &raw_device);
+            let (input_handle, output_handle) = (input_image.handle, output_image.handle);
+            let mut input = MaybeUninit::uninit();
+            let mut output = MaybeUninit::uninit();
+            assert!(libra_wgpu_texture_create_vk(device, input_image, &mut input).is_none());
+            assert!(libra_wgpu_texture_create_vk(device, output_image, &mut output).is_none());
+            let mut input = input.assume_init();
+            let mut output = output.assume_init();
+
+            let mut preset = MaybeUninit::uninit();
+            assert!(libra_preset_create(c"../test/basic.slangp".as_ptr(), &mut preset).is_none());
+            let mut preset = preset.assume_init();
+
+            let mut chain = MaybeUninit::uninit();
+            assert!(libra_wgpu_filter_chain_create(
+                &mut preset,
+                device,
+                queue,
+                ptr::null(),
+                &mut chain
+            )
+            .is_none());
+            let mut chain = chain.assume_init();
+
+            let mut encoder = MaybeUninit::uninit();
+            assert!(libra_wgpu_command_encoder_create(device, &mut encoder).is_none());
+            let mut encoder = encoder.assume_init();
+            assert!(libra_wgpu_filter_chain_frame(
+                &mut chain,
+                encoder,
+                0,
+                input,
+                output,
+                ptr::null(),
+                ptr::null(),
+                ptr::null()
+            )
+            .is_none());
+            assert!(libra_wgpu_queue_submit(queue, &mut encoder).is_none());
+            assert!(encoder.is_null());
+
+            raw_device.device_wait_idle().unwrap();
+            assert!(libra_wgpu_filter_chain_free(&mut chain).is_none());
+            assert!(libra_wgpu_texture_free(&mut input).is_none());
+            assert!(libra_wgpu_texture_free(&mut output).is_none());
+            assert!(libra_wgpu_queue_free(&mut queue).is_none());
+            assert!(libra_wgpu_device_free(&mut device).is_none());
+            assert!(device.is_null());
+
+            raw_device.destroy_image(input_handle, None);
+            raw_device.destroy_image(output_handle, None);
+            raw_device.free_memory(input_memory, None);
+            raw_device.free_memory(output_memory, None);
+            raw_device.destroy_device(None);
+            instance.destroy_instance(None);
+        }
+    }
+}
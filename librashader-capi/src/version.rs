@@ -18,7 +18,21 @@ pub type LIBRASHADER_ABI_VERSION = usize;
 ///     - Added rotation, total_subframes, current_subframes to frame options
 ///     - Added preset context API
 ///     - Added Metal runtime API
-pub const LIBRASHADER_CURRENT_VERSION: LIBRASHADER_API_VERSION = 1;
+/// - API version 2: 0.6.0
+///     - Added aspect_ratio, original_fps, frametime_delta, core_fps to frame options
+///     - Added enable_hdr, paper_white_nits, max_nits, expand_gamut, inverse_tonemap to frame options
+///     - Added first_pass, pass_count to frame options
+///     - Added preset pack API, and `*_filter_chain_create_from_pack` for each runtime
+///     - Added shader source API for preprocessing, transpilation, and preset reflection
+///     - Added `libra_error_get_details` and `libra_error_free_details`
+///     - Added `libra_set_log_callback`
+///     - Added `*_filter_chain_get_params`, `*_filter_chain_reset_params`, and `libra_filter_chain_free_params`
+///     - Added `*_filter_chain_create_async`, `*_filter_chain_job_finish`, and the `libra_filter_chain_job_*` functions
+///     - Added compare_split, compare_line_width to frame options
+///     - Added hdr_output to filter chain options
+///     - Added wgpu runtime API
+///     - Added wgpu handle functions, and `libra_wgpu_vk_create_device`, `libra_wgpu_device_create_vk`, and `libra_wgpu_texture_create_vk`
+pub const LIBRASHADER_CURRENT_VERSION: LIBRASHADER_API_VERSION = 2;
 
 /// The current version of the librashader ABI.
 /// Used by the loader to check ABI compatibility.
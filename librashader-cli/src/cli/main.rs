@@ -1,19 +1,27 @@
 use anyhow::anyhow;
 use clap::{Parser, Subcommand};
 use image::codecs::png::PngEncoder;
+use image::RgbaImage;
 use librashader::presets::context::ContextItem;
 use librashader::presets::{ShaderPreset, ShaderPresetPack, WildcardContext};
-use librashader::reflect::cross::{GlslVersion, HlslShaderModel, MslVersion, SpirvCross};
-use librashader::reflect::naga::{Naga, NagaLoweringOptions};
+use librashader::reflect::cross::{
+    parse_glsl_version, parse_hlsl_shader_model, parse_msl_version, SpirvCross,
+};
+use librashader::reflect::naga::Naga;
+use librashader::reflect::schema::PassReflection;
 use librashader::reflect::semantics::ShaderSemantics;
-use librashader::reflect::{CompileShader, FromCompilation, ReflectShader, SpirvCompilation};
-use librashader::runtime::Size;
+use librashader::reflect::transpile::{self, TranspileOptions, TranspileTarget};
+use librashader::reflect::{FromCompilation, ReflectShader, SpirvCompilation};
+use librashader::runtime::{FrameStatistics, Interpolation, Keyframe, ParameterAutomation, Size};
+use librashader::watch::{PresetWatcher, WatchEvent};
 use librashader::{FastHashMap, ShortString};
 use librashader_runtime::parameters::RuntimeParameters;
 use librashader_test::render::{CommonFrameOptions, RenderTest};
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 /// Helpers and utilities to reflect and debug 'slang' shaders and presets.
 #[derive(Parser, Debug)]
@@ -58,6 +66,12 @@ struct RenderArgs {
     /// Set the number of passes enabled for the preset.
     #[arg(long)]
     passes_enabled: Option<usize>,
+    /// The path to a JSON file of keyframes that animate parameters by frame count.
+    ///
+    /// The file maps parameter names to a list of keyframes, each with a `frame`, a `value`,
+    /// and an optional `interpolation` to the next keyframe of `linear`, `step` or `smoothstep`.
+    #[arg(long)]
+    automation: Option<PathBuf>,
     /// The path to the input image.
     #[arg(short, long)]
     image: PathBuf,
@@ -73,6 +87,19 @@ impl From<FrameOptionsArgs> for CommonFrameOptions {
             rotation: value.rotation,
             total_subframes: value.total_subframes,
             current_subframe: value.current_subframe,
+            aspect_ratio: value.aspect_ratio,
+            original_fps: value.original_fps,
+            frametime_delta: value.frametime_delta,
+            core_fps: value.core_fps,
+            enable_hdr: value.enable_hdr,
+            paper_white_nits: value.paper_white_nits,
+            max_nits: value.max_nits,
+            expand_gamut: value.expand_gamut,
+            inverse_tonemap: value.inverse_tonemap,
+            first_pass: value.first_pass,
+            pass_count: value.pass_count,
+            compare_split: value.compare_split,
+            compare_line_width: value.compare_line_width,
         }
     }
 }
@@ -92,6 +119,49 @@ struct FrameOptionsArgs {
     /// The current sub frame. Default is 1.
     #[arg(long, default_value_t = 1)]
     pub current_subframe: u32,
+    /// The aspect ratio of the original content.
+    /// If zero, the aspect ratio of the input image is used.
+    #[arg(long, default_value_t = 0.0)]
+    pub aspect_ratio: f32,
+    /// The refresh rate of the original content, in frames per second.
+    #[arg(long, default_value_t = 60.0)]
+    pub original_fps: f32,
+    /// The time elapsed since the previous frame, in microseconds.
+    #[arg(long, default_value_t = 0)]
+    pub frametime_delta: u32,
+    /// The rate at which frames are currently being produced, in frames per second.
+    #[arg(long, default_value_t = 60.0)]
+    pub core_fps: f32,
+    /// Whether or not the output is HDR.
+    #[arg(long)]
+    pub enable_hdr: bool,
+    /// The brightness of SDR paper white in nits.
+    #[arg(long, default_value_t = 200.0)]
+    pub paper_white_nits: f32,
+    /// The peak brightness of the display in nits.
+    #[arg(long, default_value_t = 1000.0)]
+    pub max_nits: f32,
+    /// Whether or not to expand the Rec. 709 gamut when converting to HDR.
+    #[arg(long)]
+    pub expand_gamut: bool,
+    /// Whether or not to inverse tonemap SDR content when converting to HDR.
+    #[arg(long)]
+    pub inverse_tonemap: bool,
+    /// The index of the first pass to render.
+    /// The first pass reads the original image as its source.
+    #[arg(long, default_value_t = 0)]
+    pub first_pass: u32,
+    /// The number of passes to render, starting from the first pass.
+    /// If zero, all enabled passes after the first pass are rendered.
+    #[arg(long, default_value_t = 0)]
+    pub pass_count: u32,
+    /// The position of the comparison split line, as a fraction of the viewport width.
+    /// If greater than zero, the original image is drawn to the left of the split line.
+    #[arg(long, default_value_t = 0.0)]
+    pub compare_split: f32,
+    /// The width of the comparison split line, in pixels.
+    #[arg(long, default_value_t = 1.0)]
+    pub compare_line_width: f32,
 }
 
 #[derive(Subcommand, Debug)]
@@ -110,6 +180,27 @@ enum Commands {
         /// The runtime to use to render the shader preset.
         #[arg(value_enum, short, long)]
         runtime: Runtime,
+        /// Watch the preset and the files it depends on, rendering the
+        /// output image again whenever any of them change.
+        ///
+        /// Compilation errors are printed without exiting.
+        #[arg(long)]
+        watch: bool,
+        /// Write the output of every intermediate pass into the given directory.
+        ///
+        /// Each pass is named by its index and alias. Passes with 8-bit framebuffers
+        /// are written as PNG, and all other framebuffer formats as OpenEXR. The final
+        /// pass is written to the output image.
+        ///
+        /// Only supported by the OpenGL, Vulkan and wgpu runtimes.
+        #[arg(long)]
+        dump_passes: Option<PathBuf>,
+        /// Render a sequence of this many frames, starting from the frame given by `--frame`.
+        ///
+        /// Each frame is written to the output path with its frame count appended to the
+        /// file name, for example `out-0012.png`.
+        #[arg(long)]
+        sequence: Option<usize>,
     },
     /// Compare two runtimes and get a similarity score between the two
     /// runtimes rendering the same frame
@@ -130,6 +221,20 @@ enum Commands {
         #[arg(short, long)]
         out: Option<PathBuf>,
     },
+    /// Render a shader preset against an image with GPU timestamp queries,
+    /// and print the average time taken by each pass.
+    Bench {
+        #[clap(flatten)]
+        preset: PresetArgs,
+        #[clap(flatten)]
+        render: RenderArgs,
+        /// The runtime to use to render the shader preset.
+        #[arg(value_enum, short, long)]
+        runtime: Runtime,
+        /// The number of frames to time, starting from the frame given by `--frame`.
+        #[arg(long, default_value_t = 120)]
+        frames: usize,
+    },
     /// Parse a preset and get a JSON representation of the data.
     Parse {
         #[clap(flatten)]
@@ -197,6 +302,17 @@ enum Commands {
 
         #[arg(value_enum, short, long, default_value = "cross")]
         backend: ReflectionBackend,
+
+        /// Output the versioned reflection schema instead of the internal reflection structure.
+        ///
+        /// Unlike the internal structure, the schema is stable between releases.
+        #[arg(long)]
+        schema: bool,
+    },
+    /// Lint the shaders in a preset, reporting unknown semantics and unused uniforms, parameters and textures.
+    Lint {
+        #[clap(flatten)]
+        preset: PresetArgs,
     },
 }
 
@@ -272,6 +388,22 @@ enum Runtime {
     Metal,
 }
 
+impl Runtime {
+    /// Whether the runtime can read back the framebuffers of intermediate passes.
+    fn can_dump_passes(&self) -> bool {
+        match self {
+            #[cfg(feature = "opengl")]
+            Runtime::OpenGL3 | Runtime::OpenGL4 => true,
+            #[cfg(feature = "vulkan")]
+            Runtime::Vulkan => true,
+            #[cfg(feature = "wgpu")]
+            Runtime::Wgpu => true,
+            #[allow(unreachable_patterns)]
+            _ => false,
+        }
+    }
+}
+
 #[derive(clap::ValueEnum, Clone, Debug)]
 enum ReflectionBackend {
     #[clap(name = "cross")]
@@ -308,6 +440,52 @@ macro_rules! get_runtime {
         }
     };
 }
+/// Print the average, minimum and maximum GPU time of each pass over the timed frames.
+fn print_frame_statistics(statistics: &[FrameStatistics]) -> anyhow::Result<()> {
+    let Some(first) = statistics.first() else {
+        return Err(anyhow!("no frame timings were available"));
+    };
+
+    let mut out = std::io::stdout().lock();
+    writeln!(out, "{} frames timed", statistics.len())?;
+    writeln!(
+        out,
+        "{:>5}  {:<24} {:>12} {:>12} {:>12}",
+        "pass", "alias", "avg", "min", "max"
+    )?;
+
+    let row =
+        |out: &mut std::io::StdoutLock, index: &str, alias: &str, durations: Vec<Duration>| {
+            let average = durations.iter().sum::<Duration>() / durations.len() as u32;
+            let min = durations.iter().min().copied().unwrap_or_default();
+            let max = durations.iter().max().copied().unwrap_or_default();
+            writeln!(
+                out,
+                "{index:>5}  {alias:<24} {:>12} {:>12} {:>12}",
+                format!("{average:.2?}"),
+                format!("{min:.2?}"),
+                format!("{max:.2?}"),
+            )
+        };
+
+    for (index, (alias, _)) in first.per_pass.iter().enumerate() {
+        let durations = statistics
+            .iter()
+            .filter_map(|frame| frame.per_pass.get(index).map(|(_, duration)| *duration))
+            .collect();
+        row(
+            &mut out,
+            &index.to_string(),
+            alias.as_deref().unwrap_or(""),
+            durations,
+        )?;
+    }
+
+    let totals = statistics.iter().map(FrameStatistics::total).collect();
+    row(&mut out, "", "total", totals)?;
+    Ok(())
+}
+
 pub fn main() -> Result<(), anyhow::Error> {
     let args = Args::parse();
 
@@ -317,6 +495,9 @@ pub fn main() -> Result<(), anyhow::Error> {
             render,
             out,
             runtime,
+            watch,
+            dump_passes,
+            sequence,
         } => {
             let PresetArgs { preset, wildcards } = preset;
             let RenderArgs {
@@ -324,21 +505,91 @@ pub fn main() -> Result<(), anyhow::Error> {
                 dimensions,
                 params,
                 passes_enabled,
+                automation,
                 image,
                 options,
             } = render;
 
+            if dump_passes.is_some() && !runtime.can_dump_passes() {
+                return Err(anyhow!(
+                    "Dumping passes is not supported for the {runtime:?} runtime"
+                ));
+            }
+
             let test: &mut dyn RenderTest = get_runtime!(runtime, image);
             let dimensions = parse_dimension(dimensions, test.image_size())?;
-            let preset = get_shader_preset(preset, wildcards)?;
             let params = parse_params(params)?;
+            let automation = load_automation(automation)?;
+
+            if sequence.is_some() && (watch || dump_passes.is_some()) {
+                return Err(anyhow!(
+                    "Can not render a sequence when watching a preset or dumping passes"
+                ));
+            }
+
+            if watch {
+                if out.as_path() == Path::new("-") {
+                    return Err(anyhow!("Can not write to stdout when watching a preset"));
+                }
 
-            let image = test.render_with_preset_and_params(
+                let options = options.map(CommonFrameOptions::from);
+                let context = get_wildcard_context(&preset, wildcards)?;
+                let mut watcher = PresetWatcher::new(&preset, context);
+                loop {
+                    let reloaded = match watcher.wait(Duration::from_millis(250)) {
+                        WatchEvent::Reloaded(reloaded) => reloaded,
+                        WatchEvent::Failed(failure) => {
+                            eprint!("{failure}");
+                            continue;
+                        }
+                    };
+
+                    let image = render_preset(
+                        test,
+                        reloaded.preset,
+                        frame,
+                        dimensions,
+                        &|rp| set_params(rp, &params, passes_enabled, &automation),
+                        options.clone(),
+                        dump_passes.as_deref(),
+                    );
+
+                    match image.and_then(|image| Ok(image.save(&out)?)) {
+                        Ok(()) => eprintln!("Rendered {}", out.display()),
+                        Err(error) => eprintln!("{error:?}"),
+                    }
+                }
+            }
+
+            let preset = get_shader_preset(preset, wildcards)?;
+
+            if let Some(sequence) = sequence {
+                if out.as_path() == Path::new("-") {
+                    return Err(anyhow!("Can not write a sequence to stdout"));
+                }
+
+                let images = test.render_sequence_with_preset_and_params(
+                    preset,
+                    frame..frame + sequence,
+                    Some(dimensions),
+                    Some(&|rp| set_params(rp, &params, passes_enabled, &automation)),
+                    options.map(CommonFrameOptions::from),
+                )?;
+
+                for (image, frame) in images.into_iter().zip(frame..) {
+                    image.save(sequence_path(&out, frame))?;
+                }
+                return Ok(());
+            }
+
+            let image = render_preset(
+                test,
                 preset,
                 frame,
-                Some(dimensions),
-                Some(&|rp| set_params(rp, &params, passes_enabled)),
+                dimensions,
+                &|rp| set_params(rp, &params, passes_enabled, &automation),
                 options.map(CommonFrameOptions::from),
+                dump_passes.as_deref(),
             )?;
 
             if out.as_path() == Path::new("-") {
@@ -361,6 +612,7 @@ pub fn main() -> Result<(), anyhow::Error> {
                 dimensions,
                 params,
                 passes_enabled,
+                automation,
                 image,
                 options,
             } = render;
@@ -370,13 +622,14 @@ pub fn main() -> Result<(), anyhow::Error> {
 
             let dimensions = parse_dimension(dimensions, left.image_size())?;
             let params = parse_params(params)?;
+            let automation = load_automation(automation)?;
 
             let left_preset = get_shader_preset(preset.clone(), wildcards.clone())?;
             let left_image = left.render_with_preset_and_params(
                 left_preset,
                 frame,
                 Some(dimensions),
-                Some(&|rp| set_params(rp, &params, passes_enabled)),
+                Some(&|rp| set_params(rp, &params, passes_enabled, &automation)),
                 None,
             )?;
 
@@ -385,7 +638,7 @@ pub fn main() -> Result<(), anyhow::Error> {
                 right_preset,
                 frame,
                 Some(dimensions),
-                Some(&|rp| set_params(rp, &params, passes_enabled)),
+                Some(&|rp| set_params(rp, &params, passes_enabled, &automation)),
                 options.map(CommonFrameOptions::from),
             )?;
 
@@ -402,6 +655,39 @@ pub fn main() -> Result<(), anyhow::Error> {
                 }
             }
         }
+        Commands::Bench {
+            preset,
+            render,
+            runtime,
+            frames,
+        } => {
+            let PresetArgs { preset, wildcards } = preset;
+            let RenderArgs {
+                frame,
+                dimensions,
+                params,
+                passes_enabled,
+                automation,
+                image,
+                options,
+            } = render;
+
+            let test: &mut dyn RenderTest = get_runtime!(runtime, image);
+            let dimensions = parse_dimension(dimensions, test.image_size())?;
+            let params = parse_params(params)?;
+            let automation = load_automation(automation)?;
+            let preset = get_shader_preset(preset, wildcards)?;
+
+            let statistics = test.bench_with_preset_and_params(
+                preset,
+                frame..frame + frames,
+                Some(dimensions),
+                Some(&|rp| set_params(rp, &params, passes_enabled, &automation)),
+                options.map(CommonFrameOptions::from),
+            )?;
+
+            print_frame_statistics(&statistics)?;
+        }
         Commands::Parse { preset } => {
             let PresetArgs { preset, wildcards } = preset;
 
@@ -428,79 +714,36 @@ pub fn main() -> Result<(), anyhow::Error> {
             version,
         } => {
             let source = librashader::preprocess::ShaderSource::load(shader.as_path())?;
-            let compilation = SpirvCompilation::try_from(&source)?;
-            let output = match format {
+            let mut options = TranspileOptions::default();
+            let target = match format {
                 TranspileFormat::GLSL => {
-                    let mut compilation =
-                        librashader::reflect::targets::GLSL::from_compilation(compilation)?;
-                    compilation.validate()?;
-
-                    let version = version
-                        .map(|s| parse_glsl_version(&s))
-                        .unwrap_or(Ok(GlslVersion::Glsl330))?;
-
-                    let output = compilation.compile(version)?;
-                    TranspileOutput {
-                        vertex: output.vertex,
-                        fragment: output.fragment,
+                    if let Some(version) = &version {
+                        options.glsl_version = parse_glsl_version(version)?;
                     }
+                    TranspileTarget::Glsl
                 }
                 TranspileFormat::HLSL => {
-                    let mut compilation =
-                        librashader::reflect::targets::HLSL::from_compilation(compilation)?;
-                    compilation.validate()?;
-
-                    let shader_model = version
-                        .map(|s| parse_hlsl_version(&s))
-                        .unwrap_or(Ok(HlslShaderModel::ShaderModel5_0))?;
-
-                    let output = compilation.compile(Some(shader_model))?;
-                    TranspileOutput {
-                        vertex: output.vertex,
-                        fragment: output.fragment,
-                    }
-                }
-                TranspileFormat::WGSL => {
-                    let mut compilation =
-                        librashader::reflect::targets::WGSL::from_compilation(compilation)?;
-                    compilation.validate()?;
-                    let output = compilation.compile(NagaLoweringOptions {
-                        write_pcb_as_ubo: true,
-                        sampler_bind_group: 1,
-                    })?;
-                    TranspileOutput {
-                        vertex: output.vertex,
-                        fragment: output.fragment,
+                    if let Some(version) = &version {
+                        options.hlsl_shader_model = parse_hlsl_shader_model(version)?;
                     }
+                    TranspileTarget::Hlsl
                 }
+                TranspileFormat::WGSL => TranspileTarget::Wgsl,
                 TranspileFormat::MSL => {
-                    let mut compilation =
-                        <librashader::reflect::targets::MSL as FromCompilation<
-                            SpirvCompilation,
-                            SpirvCross,
-                        >>::from_compilation(compilation)?;
-                    compilation.validate()?;
-
-                    let version = version
-                        .map(|s| parse_msl_version(&s))
-                        .unwrap_or(Ok(MslVersion::new(1, 2, 0)))?;
-
-                    let output = compilation.compile(Some(version))?;
-
-                    TranspileOutput {
-                        vertex: output.vertex,
-                        fragment: output.fragment,
+                    if let Some(version) = &version {
+                        options.msl_version = parse_msl_version(version)?;
                     }
+                    TranspileTarget::Msl
                 }
-                TranspileFormat::SPIRV => {
-                    let mut compilation =
-                        <librashader::reflect::targets::SPIRV as FromCompilation<
-                            SpirvCompilation,
-                            SpirvCross,
-                        >>::from_compilation(compilation)?;
-                    compilation.validate()?;
-                    let output = compilation.compile(None)?;
+                TranspileFormat::SPIRV => TranspileTarget::Spirv,
+            };
 
+            let output = match transpile::transpile::<anyhow::Error>(&source, target, &options)? {
+                transpile::TranspileOutput::Text(output) => TranspileOutput {
+                    vertex: output.vertex,
+                    fragment: output.fragment,
+                },
+                transpile::TranspileOutput::Spirv(output) => {
                     let raw = version.is_some_and(|s| s == "raw-id");
                     TranspileOutput {
                         vertex: spirv_to_dis(output.vertex, raw)?,
@@ -520,6 +763,7 @@ pub fn main() -> Result<(), anyhow::Error> {
             preset,
             index,
             backend,
+            schema,
         } => {
             let PresetArgs { preset, wildcards } = preset;
 
@@ -553,7 +797,24 @@ pub fn main() -> Result<(), anyhow::Error> {
                 }
             };
 
-            print!("{}", serde_json::to_string_pretty(&reflection)?);
+            if schema {
+                let reflection = PassReflection::new(index, &reflection);
+                print!("{}", serde_json::to_string_pretty(&reflection)?);
+            } else {
+                print!("{}", serde_json::to_string_pretty(&reflection)?);
+            }
+        }
+        Commands::Lint { preset } => {
+            let PresetArgs { preset, wildcards } = preset;
+            let preset = get_shader_preset(preset, wildcards)?;
+            let pack = ShaderPresetPack::load_from_preset::<anyhow::Error>(preset)?;
+
+            let lints = librashader::reflect::lint::lint_preset_passes::<anyhow::Error>(
+                pack.passes,
+                pack.textures.iter().map(|texture| &texture.meta),
+            )?;
+
+            print!("{}", serde_json::to_string_pretty(&lints)?);
         }
         Commands::Pack {
             preset,
@@ -586,12 +847,55 @@ struct TranspileOutput {
     fragment: String,
 }
 
+fn render_preset(
+    test: &mut dyn RenderTest,
+    preset: ShaderPreset,
+    frame: usize,
+    dimensions: Size<u32>,
+    param_setter: &dyn Fn(&RuntimeParameters),
+    options: Option<CommonFrameOptions>,
+    dump_passes: Option<&Path>,
+) -> anyhow::Result<RgbaImage> {
+    let Some(directory) = dump_passes else {
+        return test.render_with_preset_and_params(
+            preset,
+            frame,
+            Some(dimensions),
+            Some(param_setter),
+            options,
+        );
+    };
+
+    let (image, passes) = test.render_passes_with_preset_and_params(
+        preset,
+        frame,
+        Some(dimensions),
+        Some(param_setter),
+        options,
+    )?;
+
+    std::fs::create_dir_all(directory)?;
+    for pass in passes {
+        pass.save(directory)?;
+    }
+    Ok(image)
+}
+
 fn get_shader_preset(
     preset: PathBuf,
     wildcards: Option<Vec<String>>,
 ) -> anyhow::Result<ShaderPreset> {
+    let context = get_wildcard_context(&preset, wildcards)?;
+    let preset = ShaderPreset::try_parse_with_context(preset, context)?;
+    Ok(preset)
+}
+
+fn get_wildcard_context(
+    preset: &Path,
+    wildcards: Option<Vec<String>>,
+) -> anyhow::Result<WildcardContext> {
     let mut context = WildcardContext::new();
-    context.add_path_defaults(preset.as_path());
+    context.add_path_defaults(preset);
     if let Some(wildcards) = wildcards {
         for string in wildcards {
             let Some((left, right)) = string.split_once("=") else {
@@ -604,8 +908,7 @@ fn get_shader_preset(
             ))
         }
     }
-    let preset = ShaderPreset::try_parse_with_context(preset, context)?;
-    Ok(preset)
+    Ok(context)
 }
 
 fn parse_params(
@@ -631,15 +934,78 @@ fn parse_params(
     Ok(Some(map))
 }
 
+/// A keyframe in a parameter automation file.
+#[derive(serde::Deserialize)]
+struct AutomationKeyframe {
+    frame: usize,
+    value: f32,
+    #[serde(default)]
+    interpolation: AutomationInterpolation,
+}
+
+#[derive(serde::Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+enum AutomationInterpolation {
+    #[default]
+    Linear,
+    Step,
+    Smoothstep,
+}
+
+fn load_automation(path: Option<PathBuf>) -> anyhow::Result<Option<ParameterAutomation>> {
+    let Some(path) = path else {
+        return Ok(None);
+    };
+
+    let file = File::open(&path)?;
+    let curves: HashMap<String, Vec<AutomationKeyframe>> = serde_json::from_reader(file)
+        .map_err(|err| anyhow!("Invalid automation file {}: {err}", path.display()))?;
+
+    let mut automation = ParameterAutomation::new();
+    for (name, keyframes) in curves {
+        for keyframe in keyframes {
+            let interpolation = match keyframe.interpolation {
+                AutomationInterpolation::Linear => Interpolation::Linear,
+                AutomationInterpolation::Step => Interpolation::Step,
+                AutomationInterpolation::Smoothstep => Interpolation::Smoothstep,
+            };
+            automation.add_keyframe(
+                &name,
+                Keyframe {
+                    frame: keyframe.frame,
+                    value: keyframe.value,
+                    interpolation,
+                },
+            );
+        }
+    }
+
+    Ok(Some(automation))
+}
+
+/// Get the path of a frame in a sequence, appending the frame count to the file name.
+fn sequence_path(out: &Path, frame: usize) -> PathBuf {
+    let stem = out.file_stem().unwrap_or_default().to_string_lossy();
+    let mut file_name = format!("{stem}-{frame:04}");
+    if let Some(extension) = out.extension() {
+        file_name.push('.');
+        file_name.push_str(&extension.to_string_lossy());
+    }
+    out.with_file_name(file_name)
+}
+
 fn set_params(
     params: &RuntimeParameters,
     assignments: &Option<FastHashMap<ShortString, f32>>,
     passes_enabled: Option<usize>,
+    automation: &Option<ParameterAutomation>,
 ) {
     if let Some(passes_enabled) = passes_enabled {
         params.set_passes_enabled(passes_enabled)
     };
 
+    params.set_automation(automation.clone());
+
     let Some(assignments) = assignments else {
         return;
     };
@@ -662,102 +1028,6 @@ fn spirv_to_dis(spirv: Vec<u32>, raw: bool) -> anyhow::Result<String> {
         .disassemble(&binary)
 }
 
-fn parse_glsl_version(version_str: &str) -> anyhow::Result<GlslVersion> {
-    if version_str.contains("es") {
-        let Some(version) = version_str.strip_suffix("es").map(|s| s.trim()) else {
-            return Err(anyhow!("Unknown GLSL version"));
-        };
-
-        Ok(match version {
-            "100" => GlslVersion::Glsl100Es,
-            "300" => GlslVersion::Glsl300Es,
-            "310" => GlslVersion::Glsl310Es,
-            "320" => GlslVersion::Glsl320Es,
-            _ => return Err(anyhow!("Unknown GLSL version")),
-        })
-    } else {
-        Ok(match version_str {
-            "100" => GlslVersion::Glsl100Es,
-            "110" => GlslVersion::Glsl110,
-            "120" => GlslVersion::Glsl120,
-            "130" => GlslVersion::Glsl130,
-            "140" => GlslVersion::Glsl140,
-            "150" => GlslVersion::Glsl150,
-            "300" => GlslVersion::Glsl300Es,
-            "330" => GlslVersion::Glsl330,
-            "310" => GlslVersion::Glsl310Es,
-            "320" => GlslVersion::Glsl320Es,
-            "400" => GlslVersion::Glsl400,
-            "410" => GlslVersion::Glsl410,
-            "420" => GlslVersion::Glsl420,
-            "430" => GlslVersion::Glsl430,
-            "440" => GlslVersion::Glsl440,
-            "450" => GlslVersion::Glsl450,
-            "460" => GlslVersion::Glsl460,
-            _ => return Err(anyhow!("Unknown GLSL version")),
-        })
-    }
-}
-
-fn version_to_usize(version_str: &str) -> anyhow::Result<usize> {
-    let version: &str = if version_str.contains("_") {
-        &version_str.replace("_", "")
-    } else if version_str.contains(".") {
-        &version_str.replace(".", "")
-    } else {
-        version_str
-    };
-
-    let version = version
-        .parse::<usize>()
-        .map_err(|_| anyhow!("Invalid version string"))?;
-    Ok(version)
-}
-
-fn parse_hlsl_version(version_str: &str) -> anyhow::Result<HlslShaderModel> {
-    let version = version_to_usize(version_str)?;
-    Ok(match version {
-        30 => HlslShaderModel::ShaderModel3_0,
-        40 => HlslShaderModel::ShaderModel4_0,
-        50 => HlslShaderModel::ShaderModel5_0,
-        51 => HlslShaderModel::ShaderModel5_1,
-        60 => HlslShaderModel::ShaderModel6_0,
-        61 => HlslShaderModel::ShaderModel6_1,
-        62 => HlslShaderModel::ShaderModel6_2,
-        63 => HlslShaderModel::ShaderModel6_3,
-        64 => HlslShaderModel::ShaderModel6_4,
-        65 => HlslShaderModel::ShaderModel6_5,
-        66 => HlslShaderModel::ShaderModel6_6,
-        67 => HlslShaderModel::ShaderModel6_7,
-        68 => HlslShaderModel::ShaderModel6_8,
-        _ => return Err(anyhow!("Unknown Shader Model")),
-    })
-}
-
-fn parse_msl_version(version_str: &str) -> anyhow::Result<MslVersion> {
-    let version = version_to_usize(version_str)?;
-    Ok(match version {
-        10 => MslVersion::new(1, 0, 0),
-        11 => MslVersion::new(1, 1, 0),
-        12 => MslVersion::new(1, 2, 0),
-        20 => MslVersion::new(2, 0, 0),
-        21 => MslVersion::new(2, 1, 0),
-        22 => MslVersion::new(2, 2, 0),
-        23 => MslVersion::new(2, 3, 0),
-        24 => MslVersion::new(2, 4, 0),
-        30 => MslVersion::new(3, 0, 0),
-        31 => MslVersion::new(3, 1, 0),
-        32 => MslVersion::new(3, 2, 0),
-        n if n >= 10000 => {
-            let major = n / 10000;
-            let minor = (n - (major * 10000)) / 100;
-            let patch = n - ((major * 10000) + (minor * 100));
-            MslVersion::new(major as u32, minor as u32, patch as u32)
-        }
-        _ => return Err(anyhow!("Unknown MSL version")),
-    })
-}
-
 fn parse_dimension(dimstr: Option<String>, image_dim: Size<u32>) -> anyhow::Result<Size<u32>> {
     let Some(dimstr) = dimstr else {
         return Ok(image_dim);
@@ -89,6 +89,7 @@ impl RenderTest for Direct3D12 {
                     force_hlsl_pipeline: false,
                     force_no_mipmaps: false,
                     disable_cache: false,
+                    hdr_output: None,
                 }),
             )?;
 
@@ -152,6 +153,19 @@ impl RenderTest for Direct3D12 {
                 rotation: options.rotation,
                 total_subframes: options.total_subframes,
                 current_subframe: options.current_subframe,
+                aspect_ratio: options.aspect_ratio,
+                original_fps: options.original_fps,
+                frametime_delta: options.frametime_delta,
+                core_fps: options.core_fps,
+                enable_hdr: options.enable_hdr,
+                paper_white_nits: options.paper_white_nits,
+                max_nits: options.max_nits,
+                expand_gamut: options.expand_gamut,
+                inverse_tonemap: options.inverse_tonemap,
+                first_pass: options.first_pass,
+                pass_count: options.pass_count,
+                compare_split: options.compare_split,
+                compare_line_width: options.compare_line_width,
             });
 
             let image = self.texture.to_ref();
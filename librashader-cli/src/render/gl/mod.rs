@@ -1,14 +1,16 @@
 mod context;
 
 use crate::render::gl::context::{GLVersion, GlfwContext};
+use crate::render::passes::PassOutput;
 use crate::render::{CommonFrameOptions, RenderTest};
 use anyhow::anyhow;
 use glow::{HasContext, PixelPackData, PixelUnpackData};
 use image::RgbaImage;
 use librashader::presets::ShaderPreset;
 use librashader::runtime::gl::{FilterChain, FilterChainOptions, FrameOptions, GLImage};
-use librashader::runtime::{FilterChainParameters, RuntimeParameters};
+use librashader::runtime::{FilterChainDebug, FilterChainParameters, RuntimeParameters};
 use librashader::runtime::{Size, Viewport};
+use librashader::ImageFormat;
 use librashader_runtime::image::{Image, UVDirection, RGBA8};
 use std::path::Path;
 use std::sync::Arc;
@@ -42,37 +44,47 @@ impl RenderTest for OpenGl3 {
         param_setter: Option<&dyn Fn(&RuntimeParameters)>,
         frame_options: Option<CommonFrameOptions>,
     ) -> anyhow::Result<image::RgbaImage> {
-        let mut filter_chain = unsafe {
-            FilterChain::load_from_preset(
-                preset,
-                Arc::clone(&self.0.context.gl),
-                Some(&FilterChainOptions {
-                    glsl_version: 330,
-                    use_dsa: false,
-                    force_no_mipmaps: false,
-                    disable_cache: false,
-                }),
-            )
-        }?;
-
-        if let Some(setter) = param_setter {
-            setter(filter_chain.parameters());
-        }
+        let (image, _) = self.0.render_preset(
+            preset,
+            &FilterChainOptions {
+                glsl_version: 330,
+                use_dsa: false,
+                force_no_mipmaps: false,
+                disable_cache: false,
+                hdr_output: None,
+            },
+            frame_count,
+            output_size,
+            param_setter,
+            frame_options,
+            false,
+        )?;
+        Ok(image)
+    }
 
-        Ok(self.0.render(
-            &mut filter_chain,
+    fn render_passes_with_preset_and_params(
+        &mut self,
+        preset: ShaderPreset,
+        frame_count: usize,
+        output_size: Option<Size<u32>>,
+        param_setter: Option<&dyn Fn(&RuntimeParameters)>,
+        frame_options: Option<CommonFrameOptions>,
+    ) -> anyhow::Result<(image::RgbaImage, Vec<PassOutput>)> {
+        self.0.render_preset(
+            preset,
+            &FilterChainOptions {
+                glsl_version: 330,
+                use_dsa: false,
+                force_no_mipmaps: false,
+                disable_cache: false,
+                hdr_output: None,
+            },
             frame_count,
             output_size,
-            frame_options
-                .map(|options| FrameOptions {
-                    clear_history: options.clear_history,
-                    frame_direction: options.frame_direction,
-                    rotation: options.rotation,
-                    total_subframes: options.total_subframes,
-                    current_subframe: options.current_subframe,
-                })
-                .as_ref(),
-        )?)
+            param_setter,
+            frame_options,
+            true,
+        )
     }
 }
 
@@ -96,37 +108,47 @@ impl RenderTest for OpenGl4 {
         param_setter: Option<&dyn Fn(&RuntimeParameters)>,
         frame_options: Option<CommonFrameOptions>,
     ) -> anyhow::Result<image::RgbaImage> {
-        let mut filter_chain = unsafe {
-            FilterChain::load_from_preset(
-                preset,
-                Arc::clone(&self.0.context.gl),
-                Some(&FilterChainOptions {
-                    glsl_version: 460,
-                    use_dsa: true,
-                    force_no_mipmaps: false,
-                    disable_cache: true,
-                }),
-            )
-        }?;
-
-        if let Some(setter) = param_setter {
-            setter(filter_chain.parameters());
-        }
+        let (image, _) = self.0.render_preset(
+            preset,
+            &FilterChainOptions {
+                glsl_version: 460,
+                use_dsa: true,
+                force_no_mipmaps: false,
+                disable_cache: true,
+                hdr_output: None,
+            },
+            frame_count,
+            output_size,
+            param_setter,
+            frame_options,
+            false,
+        )?;
+        Ok(image)
+    }
 
-        Ok(self.0.render(
-            &mut filter_chain,
+    fn render_passes_with_preset_and_params(
+        &mut self,
+        preset: ShaderPreset,
+        frame_count: usize,
+        output_size: Option<Size<u32>>,
+        param_setter: Option<&dyn Fn(&RuntimeParameters)>,
+        frame_options: Option<CommonFrameOptions>,
+    ) -> anyhow::Result<(image::RgbaImage, Vec<PassOutput>)> {
+        self.0.render_preset(
+            preset,
+            &FilterChainOptions {
+                glsl_version: 460,
+                use_dsa: true,
+                force_no_mipmaps: false,
+                disable_cache: true,
+                hdr_output: None,
+            },
             frame_count,
             output_size,
-            frame_options
-                .map(|options| FrameOptions {
-                    clear_history: options.clear_history,
-                    frame_direction: options.frame_direction,
-                    rotation: options.rotation,
-                    total_subframes: options.total_subframes,
-                    current_subframe: options.current_subframe,
-                })
-                .as_ref(),
-        )?)
+            param_setter,
+            frame_options,
+            true,
+        )
     }
 }
 
@@ -197,6 +219,100 @@ impl OpenGl {
         })
     }
 
+    #[allow(clippy::too_many_arguments)]
+    fn render_preset(
+        &self,
+        preset: ShaderPreset,
+        chain_options: &FilterChainOptions,
+        frame_count: usize,
+        output_size: Option<Size<u32>>,
+        param_setter: Option<&dyn Fn(&RuntimeParameters)>,
+        frame_options: Option<CommonFrameOptions>,
+        read_passes: bool,
+    ) -> anyhow::Result<(RgbaImage, Vec<PassOutput>)> {
+        let mut filter_chain = unsafe {
+            FilterChain::load_from_preset(preset, Arc::clone(&self.context.gl), Some(chain_options))
+        }?;
+
+        if let Some(setter) = param_setter {
+            setter(filter_chain.parameters());
+        }
+
+        let options = frame_options.map(|options| FrameOptions {
+            clear_history: options.clear_history,
+            frame_direction: options.frame_direction,
+            rotation: options.rotation,
+            total_subframes: options.total_subframes,
+            current_subframe: options.current_subframe,
+            aspect_ratio: options.aspect_ratio,
+            original_fps: options.original_fps,
+            frametime_delta: options.frametime_delta,
+            core_fps: options.core_fps,
+            enable_hdr: options.enable_hdr,
+            paper_white_nits: options.paper_white_nits,
+            max_nits: options.max_nits,
+            expand_gamut: options.expand_gamut,
+            inverse_tonemap: options.inverse_tonemap,
+            first_pass: options.first_pass,
+            pass_count: options.pass_count,
+            compare_split: options.compare_split,
+            compare_line_width: options.compare_line_width,
+        });
+
+        let image = self.render(
+            &mut filter_chain,
+            frame_count,
+            output_size,
+            options.as_ref(),
+        )?;
+
+        let mut passes = Vec::new();
+        if read_passes {
+            for index in 0..filter_chain.pass_count() {
+                let Some(image) = filter_chain.pass_output(index) else {
+                    continue;
+                };
+                passes.push(self.read_pass(index, filter_chain.pass_alias(index), &image)?);
+            }
+        }
+
+        Ok((image, passes))
+    }
+
+    fn read_pass(
+        &self,
+        index: usize,
+        alias: Option<&str>,
+        image: &GLImage,
+    ) -> anyhow::Result<PassOutput> {
+        let format = ImageFormat::from_gl_internal_format(image.format);
+        let (Some(bytes_per_pixel), Some((pixel_format, pixel_type))) =
+            (format.bytes_per_pixel(), format.gl_pixel_transfer_format())
+        else {
+            return Err(anyhow!("pass {index} has an unknown framebuffer format"));
+        };
+
+        let mut data =
+            vec![0u8; image.size.width as usize * image.size.height as usize * bytes_per_pixel];
+
+        unsafe {
+            self.context.gl.pixel_store_i32(glow::PACK_ALIGNMENT, 1);
+            self.context.gl.bind_buffer(glow::PIXEL_PACK_BUFFER, None);
+            self.context.gl.bind_texture(glow::TEXTURE_2D, image.handle);
+            self.context.gl.get_tex_image(
+                glow::TEXTURE_2D,
+                0,
+                pixel_format,
+                pixel_type,
+                PixelPackData::Slice(&mut data),
+            );
+            self.context.gl.bind_texture(glow::TEXTURE_2D, None);
+            self.context.gl.pixel_store_i32(glow::PACK_ALIGNMENT, 4);
+        }
+
+        PassOutput::decode(index, alias, format, image.size, &data)
+    }
+
     pub fn render(
         &self,
         chain: &mut FilterChain,
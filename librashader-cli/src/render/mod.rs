@@ -19,10 +19,17 @@ pub mod wgpu;
 #[cfg(all(target_vendor = "apple", feature = "metal"))]
 pub mod mtl;
 
+/// Read back of intermediate pass outputs.
+pub mod passes;
+
+use crate::render::passes::PassOutput;
+use anyhow::anyhow;
 use librashader::presets::ShaderPreset;
 use librashader::runtime::Size;
 use librashader_runtime::impl_default_frame_options;
 use librashader_runtime::parameters::RuntimeParameters;
+use librashader_runtime::profiling::FrameStatistics;
+use std::ops::Range;
 use std::path::Path;
 
 /// Test harness to set up a device, render a triangle, and apply a shader
@@ -86,6 +93,67 @@ pub trait RenderTest {
         param_setter: Option<&dyn Fn(&RuntimeParameters)>,
         frame_options: Option<CommonFrameOptions>,
     ) -> anyhow::Result<image::RgbaImage>;
+
+    /// Render a shader onto an image buffer, applying the provided shader, and read back
+    /// the framebuffer of every intermediate pass after the last frame.
+    ///
+    /// The final pass renders directly to the returned image, so it is not included in the
+    /// pass outputs. Runtimes that can not read back their framebuffers return an error.
+    fn render_passes_with_preset_and_params(
+        &mut self,
+        _preset: ShaderPreset,
+        _frame_count: usize,
+        _output_size: Option<Size<u32>>,
+        _param_setter: Option<&dyn Fn(&RuntimeParameters)>,
+        _frame_options: Option<CommonFrameOptions>,
+    ) -> anyhow::Result<(image::RgbaImage, Vec<PassOutput>)> {
+        Err(anyhow!(
+            "reading back intermediate passes is not supported for this runtime"
+        ))
+    }
+
+    /// Render a shader onto an image buffer for the given range of frames, returning the
+    /// output of every frame in the range.
+    ///
+    /// Frames before the range are rendered to fill the history and feedback framebuffers,
+    /// but are not returned. By default, the preset is loaded again for every frame in the
+    /// range, and every frame up to it is rendered.
+    fn render_sequence_with_preset_and_params(
+        &mut self,
+        preset: ShaderPreset,
+        frames: Range<usize>,
+        output_size: Option<Size<u32>>,
+        param_setter: Option<&dyn Fn(&RuntimeParameters)>,
+        frame_options: Option<CommonFrameOptions>,
+    ) -> anyhow::Result<Vec<image::RgbaImage>> {
+        frames
+            .map(|frame| {
+                self.render_with_preset_and_params(
+                    preset.clone(),
+                    frame,
+                    output_size,
+                    param_setter,
+                    frame_options.clone(),
+                )
+            })
+            .collect()
+    }
+
+    /// Render a shader preset for the given range of frames with profiling enabled, and
+    /// collect the per-pass GPU timings of every frame whose results became available.
+    ///
+    /// Each frame is submitted and waited on separately. Runtimes that can not time their
+    /// passes return an error.
+    fn bench_with_preset_and_params(
+        &mut self,
+        _preset: ShaderPreset,
+        _frames: Range<usize>,
+        _output_size: Option<Size<u32>>,
+        _param_setter: Option<&dyn Fn(&RuntimeParameters)>,
+        _frame_options: Option<CommonFrameOptions>,
+    ) -> anyhow::Result<Vec<FrameStatistics>> {
+        Err(anyhow!("profiling is not supported for this runtime"))
+    }
 }
 
 impl_default_frame_options!(CommonFrameOptions);
@@ -105,7 +173,7 @@ mod test {
 
     fn do_test<T: RenderTest>() -> anyhow::Result<()> {
         let mut test = T::new(IMAGE_PATH.as_ref())?;
-        let image = test.render(FILTER_PATH.as_ref(), 100)?;
+        let image = test.render(FILTER_PATH.as_ref(), 100, None)?;
 
         let out = File::create("out.png")?;
         image.write_with_encoder(PngEncoder::new(out))?;
@@ -164,8 +232,8 @@ mod test {
         let mut a = A::new(IMAGE_PATH.as_ref())?;
         let mut b = B::new(IMAGE_PATH.as_ref())?;
 
-        let a_image = a.render(FILTER_PATH.as_ref(), 100)?;
-        let b_image = b.render(FILTER_PATH.as_ref(), 100)?;
+        let a_image = a.render(FILTER_PATH.as_ref(), 100, None)?;
+        let b_image = b.render(FILTER_PATH.as_ref(), 100, None)?;
 
         let similarity = image_compare::rgba_hybrid_compare(&a_image, &b_image)?;
         assert!(similarity.score > 0.95);
@@ -57,6 +57,7 @@ impl RenderTest for Metal {
             &queue,
             Some(&FilterChainOptions {
                 force_no_mipmaps: false,
+                hdr_output: None,
             }),
         )?;
 
@@ -100,6 +101,19 @@ impl RenderTest for Metal {
             rotation: options.rotation,
             total_subframes: options.total_subframes,
             current_subframe: options.current_subframe,
+            aspect_ratio: options.aspect_ratio,
+            original_fps: options.original_fps,
+            frametime_delta: options.frametime_delta,
+            core_fps: options.core_fps,
+            enable_hdr: options.enable_hdr,
+            paper_white_nits: options.paper_white_nits,
+            max_nits: options.max_nits,
+            expand_gamut: options.expand_gamut,
+            inverse_tonemap: options.inverse_tonemap,
+            first_pass: options.first_pass,
+            pass_count: options.pass_count,
+            compare_split: options.compare_split,
+            compare_line_width: options.compare_line_width,
         });
 
         for frame in 0..=frame_count {
@@ -0,0 +1,217 @@
+use anyhow::anyhow;
+use image::{DynamicImage, Rgba32FImage, RgbaImage};
+use librashader::runtime::Size;
+use librashader::ImageFormat;
+use std::path::{Path, PathBuf};
+
+/// The output of a single pass of a filter chain, read back from the GPU.
+pub struct PassOutput {
+    /// The index of the pass.
+    pub index: usize,
+    /// The alias of the pass, if it has one.
+    pub alias: Option<String>,
+    /// The contents of the framebuffer of the pass.
+    ///
+    /// Framebuffers with 8-bit normalized formats are read back as RGBA8,
+    /// and all other formats are widened to 32-bit float RGBA.
+    pub image: DynamicImage,
+}
+
+impl PassOutput {
+    /// Decode the raw contents of a framebuffer with tightly packed rows.
+    pub fn decode(
+        index: usize,
+        alias: Option<&str>,
+        format: ImageFormat,
+        size: Size<u32>,
+        bytes: &[u8],
+    ) -> anyhow::Result<PassOutput> {
+        let pixel_count = size.width as usize * size.height as usize;
+        let bytes_per_pixel = format
+            .bytes_per_pixel()
+            .ok_or_else(|| anyhow!("pass {index} has an unknown framebuffer format"))?;
+        if bytes.len() < pixel_count * bytes_per_pixel {
+            return Err(anyhow!("the framebuffer of pass {index} was not read back"));
+        }
+
+        let pixels = bytes[..pixel_count * bytes_per_pixel].chunks_exact(bytes_per_pixel);
+        let image = match format {
+            ImageFormat::R8Unorm
+            | ImageFormat::R8G8Unorm
+            | ImageFormat::R8G8B8A8Unorm
+            | ImageFormat::R8G8B8A8Srgb => {
+                let mut rgba = Vec::with_capacity(pixel_count * 4);
+                for pixel in pixels {
+                    let mut texel = [0, 0, 0, u8::MAX];
+                    texel[..pixel.len()].copy_from_slice(pixel);
+                    rgba.extend_from_slice(&texel);
+                }
+                RgbaImage::from_raw(size.width, size.height, rgba).map(DynamicImage::ImageRgba8)
+            }
+            _ => {
+                let mut rgba = Vec::with_capacity(pixel_count * 4);
+                for pixel in pixels {
+                    rgba.extend_from_slice(&decode_float(format, pixel));
+                }
+                Rgba32FImage::from_raw(size.width, size.height, rgba)
+                    .map(DynamicImage::ImageRgba32F)
+            }
+        }
+        .ok_or_else(|| anyhow!("unable to create image for pass {index}"))?;
+
+        Ok(PassOutput {
+            index,
+            alias: alias.map(String::from),
+            image,
+        })
+    }
+
+    /// The file name of the pass output, from the index and alias of the pass.
+    ///
+    /// 8-bit images are written as PNG, and float images are written as OpenEXR.
+    pub fn file_name(&self) -> String {
+        let extension = match self.image {
+            DynamicImage::ImageRgba8(_) => "png",
+            _ => "exr",
+        };
+        match &self.alias {
+            Some(alias) => format!("{}-{alias}.{extension}", self.index),
+            None => format!("{}.{extension}", self.index),
+        }
+    }
+
+    /// Write the pass output into the given directory, returning the path of the written file.
+    pub fn save(&self, directory: &Path) -> anyhow::Result<PathBuf> {
+        let path = directory.join(self.file_name());
+        match &self.image {
+            DynamicImage::ImageRgba8(image) => image.save(&path)?,
+            DynamicImage::ImageRgba32F(image) => image.save(&path)?,
+            _ => return Err(anyhow!("unsupported image for pass {}", self.index)),
+        }
+        Ok(path)
+    }
+}
+
+fn decode_float(format: ImageFormat, pixel: &[u8]) -> [f32; 4] {
+    fn components<const N: usize>(pixel: &[u8], decode: impl Fn([u8; N]) -> f32) -> Vec<f32> {
+        pixel
+            .chunks_exact(N)
+            .map(|component| decode(component.try_into().unwrap()))
+            .collect()
+    }
+
+    let components = match format {
+        ImageFormat::R8Uint | ImageFormat::R8G8Uint | ImageFormat::R8G8B8A8Uint => {
+            components(pixel, |[value]: [u8; 1]| value as f32)
+        }
+        ImageFormat::R8Sint | ImageFormat::R8G8Sint | ImageFormat::R8G8B8A8Sint => {
+            components(pixel, |[value]: [u8; 1]| value as i8 as f32)
+        }
+        ImageFormat::A2B10G10R10UnormPack32 | ImageFormat::A2B10G10R10UintPack32 => {
+            let packed = u32::from_le_bytes(pixel.try_into().unwrap());
+            let mut texel = [
+                (packed & 0x3ff) as f32,
+                ((packed >> 10) & 0x3ff) as f32,
+                ((packed >> 20) & 0x3ff) as f32,
+                (packed >> 30) as f32,
+            ];
+            if format == ImageFormat::A2B10G10R10UnormPack32 {
+                texel = [
+                    texel[0] / 1023.0,
+                    texel[1] / 1023.0,
+                    texel[2] / 1023.0,
+                    texel[3] / 3.0,
+                ];
+            }
+            return texel;
+        }
+        ImageFormat::R16Uint | ImageFormat::R16G16Uint | ImageFormat::R16G16B16A16Uint => {
+            components(pixel, |value| u16::from_le_bytes(value) as f32)
+        }
+        ImageFormat::R16Sint | ImageFormat::R16G16Sint | ImageFormat::R16G16B16A16Sint => {
+            components(pixel, |value| i16::from_le_bytes(value) as f32)
+        }
+        ImageFormat::R16Sfloat | ImageFormat::R16G16Sfloat | ImageFormat::R16G16B16A16Sfloat => {
+            components(pixel, |value| half::f16::from_le_bytes(value).to_f32())
+        }
+        ImageFormat::R32Uint | ImageFormat::R32G32Uint | ImageFormat::R32G32B32A32Uint => {
+            components(pixel, |value| u32::from_le_bytes(value) as f32)
+        }
+        ImageFormat::R32Sint | ImageFormat::R32G32Sint | ImageFormat::R32G32B32A32Sint => {
+            components(pixel, |value| i32::from_le_bytes(value) as f32)
+        }
+        ImageFormat::R32Sfloat | ImageFormat::R32G32Sfloat | ImageFormat::R32G32B32A32Sfloat => {
+            components(pixel, f32::from_le_bytes)
+        }
+        _ => components(pixel, |[value]: [u8; 1]| value as f32 / 255.0),
+    };
+
+    let mut texel = [0.0, 0.0, 0.0, 1.0];
+    texel[..components.len()].copy_from_slice(&components);
+    texel
+}
+
+#[cfg(test)]
+mod test {
+    use crate::render::passes::PassOutput;
+    use image::{DynamicImage, Rgba, Rgba32FImage};
+    use librashader::runtime::Size;
+    use librashader::ImageFormat;
+
+    #[test]
+    pub fn decodes_framebuffer_formats() {
+        let size = Size::new(1, 1);
+
+        let unorm =
+            PassOutput::decode(0, Some("First"), ImageFormat::R8G8Unorm, size, &[1, 2]).unwrap();
+        assert_eq!(unorm.file_name(), "0-First.png");
+        let DynamicImage::ImageRgba8(image) = unorm.image else {
+            panic!("8-bit formats should be read back as RGBA8");
+        };
+        assert_eq!(image.get_pixel(0, 0).0, [1, 2, 0, 255]);
+
+        let half = [0x00, 0x3c, 0x00, 0xc0, 0x00, 0x38, 0x00, 0x00];
+        let float =
+            PassOutput::decode(1, None, ImageFormat::R16G16B16A16Sfloat, size, &half).unwrap();
+        assert_eq!(float.file_name(), "1.exr");
+        let DynamicImage::ImageRgba32F(image) = float.image else {
+            panic!("float formats should be read back as RGBA32F");
+        };
+        assert_eq!(image.get_pixel(0, 0).0, [1.0, -2.0, 0.5, 0.0]);
+
+        let packed = (3u32 << 30) | (1023 << 20) | 1023;
+        let packed = PassOutput::decode(
+            2,
+            None,
+            ImageFormat::A2B10G10R10UnormPack32,
+            size,
+            &packed.to_le_bytes(),
+        )
+        .unwrap();
+        let DynamicImage::ImageRgba32F(image) = packed.image else {
+            panic!("packed formats should be read back as RGBA32F");
+        };
+        assert_eq!(image.get_pixel(0, 0).0, [1.0, 0.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    pub fn saves_float_passes_as_exr() {
+        let image = Rgba32FImage::from_fn(3, 2, |x, y| Rgba([x as f32, y as f32, 0.5, 1.0]));
+        let output = PassOutput {
+            index: 3,
+            alias: Some(String::from("Float")),
+            image: DynamicImage::ImageRgba32F(image.clone()),
+        };
+
+        let directory =
+            std::env::temp_dir().join(format!("librashader-passes-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let path = output.save(&directory).unwrap();
+        assert!(path.ends_with("3-Float.exr"));
+
+        let read = image::open(&path).unwrap().into_rgba32f();
+        assert_eq!(read, image);
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
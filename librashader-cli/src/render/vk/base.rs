@@ -11,6 +11,7 @@ pub struct VulkanBase {
     allocator: Arc<Mutex<Allocator>>,
     cmd_buffer: vk::CommandBuffer,
     pool: vk::CommandPool,
+    timestamp_period: f32,
 }
 
 impl From<&VulkanBase> for VulkanObjects {
@@ -19,6 +20,7 @@ impl From<&VulkanBase> for VulkanObjects {
             device: Arc::clone(&value.device),
             alloc: Arc::clone(&value.allocator),
             queue: value.graphics_queue.clone(),
+            timestamp_period: value.timestamp_period,
         }
     }
 }
@@ -48,6 +50,13 @@ impl VulkanBase {
 
         let (device, queue, cmd_pool) = Self::create_device(&instance, &physical_device)?;
 
+        let limits = unsafe { instance.get_physical_device_properties(physical_device) }.limits;
+        let timestamp_period = if limits.timestamp_compute_and_graphics == vk::TRUE {
+            limits.timestamp_period
+        } else {
+            0.0
+        };
+
         let alloc = super::memory::create_allocator(
             device.clone(),
             instance.clone(),
@@ -71,6 +80,7 @@ impl VulkanBase {
             allocator: alloc,
             pool: cmd_pool,
             cmd_buffer: buffers,
+            timestamp_period,
         })
     }
 
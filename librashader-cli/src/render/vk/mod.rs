@@ -1,3 +1,4 @@
+use crate::render::passes::PassOutput;
 use crate::render::vk::base::VulkanBase;
 use crate::render::vk::memory::{VulkanBuffer, VulkanImageMemory};
 use crate::render::{CommonFrameOptions, RenderTest};
@@ -7,9 +8,14 @@ use gpu_allocator::MemoryLocation;
 use image::RgbaImage;
 use librashader::presets::ShaderPreset;
 use librashader::runtime::vk::{FilterChain, FilterChainOptions, FrameOptions, VulkanImage};
-use librashader::runtime::{FilterChainParameters, RuntimeParameters};
+use librashader::runtime::{
+    FilterChainDebug, FilterChainParameters, FilterChainProfiling, FrameStatistics,
+    RuntimeParameters,
+};
 use librashader::runtime::{Size, Viewport};
+use librashader::ImageFormat;
 use librashader_runtime::image::{Image, UVDirection, BGRA8};
+use std::ops::Range;
 use std::path::Path;
 
 mod base;
@@ -44,6 +50,43 @@ impl RenderTest for Vulkan {
         param_setter: Option<&dyn Fn(&RuntimeParameters)>,
         frame_options: Option<CommonFrameOptions>,
     ) -> anyhow::Result<image::RgbaImage> {
+        let (image, _) = self.render_inner(
+            preset,
+            frame_count,
+            output_size,
+            param_setter,
+            frame_options,
+            false,
+        )?;
+        Ok(image)
+    }
+
+    fn render_passes_with_preset_and_params(
+        &mut self,
+        preset: ShaderPreset,
+        frame_count: usize,
+        output_size: Option<Size<u32>>,
+        param_setter: Option<&dyn Fn(&RuntimeParameters)>,
+        frame_options: Option<CommonFrameOptions>,
+    ) -> anyhow::Result<(image::RgbaImage, Vec<PassOutput>)> {
+        self.render_inner(
+            preset,
+            frame_count,
+            output_size,
+            param_setter,
+            frame_options,
+            true,
+        )
+    }
+
+    fn bench_with_preset_and_params(
+        &mut self,
+        preset: ShaderPreset,
+        frames: Range<usize>,
+        output_size: Option<Size<u32>>,
+        param_setter: Option<&dyn Fn(&RuntimeParameters)>,
+        frame_options: Option<CommonFrameOptions>,
+    ) -> anyhow::Result<Vec<FrameStatistics>> {
         unsafe {
             let mut filter_chain = FilterChain::load_from_preset(
                 preset,
@@ -53,6 +96,141 @@ impl RenderTest for Vulkan {
                     force_no_mipmaps: false,
                     use_dynamic_rendering: false,
                     disable_cache: false,
+                    hdr_output: None,
+                }),
+            )?;
+
+            if let Some(setter) = param_setter {
+                setter(filter_chain.parameters());
+            }
+            filter_chain.set_profiling(true);
+
+            let size = output_size.unwrap_or(self.image_bytes.size);
+            let render_texture = self.vk.device().create_image(
+                &vk::ImageCreateInfo::default()
+                    .image_type(vk::ImageType::TYPE_2D)
+                    .format(vk::Format::B8G8R8A8_UNORM)
+                    .extent(size.into())
+                    .mip_levels(1)
+                    .array_layers(1)
+                    .samples(vk::SampleCountFlags::TYPE_1)
+                    .tiling(vk::ImageTiling::OPTIMAL)
+                    .usage(vk::ImageUsageFlags::COLOR_ATTACHMENT)
+                    .initial_layout(vk::ImageLayout::UNDEFINED),
+                None,
+            )?;
+
+            let _memory = {
+                let mem_reqs = self
+                    .vk
+                    .device()
+                    .get_image_memory_requirements(render_texture);
+                VulkanImageMemory::new(
+                    self.vk.device(),
+                    self.vk.allocator(),
+                    mem_reqs,
+                    &render_texture,
+                    MemoryLocation::GpuOnly,
+                )?
+            };
+
+            self.vk.queue_work(|cmd| {
+                util::vulkan_image_layout_transition_levels(
+                    self.vk.device(),
+                    cmd,
+                    render_texture,
+                    vk::REMAINING_MIP_LEVELS,
+                    vk::ImageLayout::UNDEFINED,
+                    vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL,
+                    vk::AccessFlags::COLOR_ATTACHMENT_WRITE,
+                    vk::AccessFlags::COLOR_ATTACHMENT_WRITE,
+                    vk::PipelineStageFlags::ALL_GRAPHICS,
+                    vk::PipelineStageFlags::ALL_GRAPHICS,
+                    vk::QUEUE_FAMILY_IGNORED,
+                    vk::QUEUE_FAMILY_IGNORED,
+                )
+            })?;
+
+            let options = frame_options.map(|options| FrameOptions {
+                clear_history: options.clear_history,
+                frame_direction: options.frame_direction,
+                rotation: options.rotation,
+                total_subframes: options.total_subframes,
+                current_subframe: options.current_subframe,
+                aspect_ratio: options.aspect_ratio,
+                original_fps: options.original_fps,
+                frametime_delta: options.frametime_delta,
+                core_fps: options.core_fps,
+                enable_hdr: options.enable_hdr,
+                paper_white_nits: options.paper_white_nits,
+                max_nits: options.max_nits,
+                expand_gamut: options.expand_gamut,
+                inverse_tonemap: options.inverse_tonemap,
+                first_pass: options.first_pass,
+                pass_count: options.pass_count,
+                compare_split: options.compare_split,
+                compare_line_width: options.compare_line_width,
+            });
+
+            let viewport = Viewport::new_render_target_sized_origin(
+                VulkanImage {
+                    image: render_texture,
+                    size,
+                    format: vk::Format::B8G8R8A8_UNORM,
+                },
+                None,
+            )?;
+
+            let mut statistics = Vec::new();
+            for frame in frames {
+                self.vk.queue_work(|cmd| {
+                    filter_chain.frame(
+                        &VulkanImage {
+                            image: self.image,
+                            size: self.image_bytes.size,
+                            format: vk::Format::B8G8R8A8_UNORM,
+                        },
+                        &viewport,
+                        cmd,
+                        frame,
+                        options.as_ref(),
+                    )
+                })??;
+
+                if !filter_chain.profiling() {
+                    self.vk.device().destroy_image(render_texture, None);
+                    return Err(anyhow!("the device does not support timestamp queries"));
+                }
+
+                statistics.extend(filter_chain.frame_statistics());
+            }
+
+            self.vk.device().destroy_image(render_texture, None);
+            Ok(statistics)
+        }
+    }
+}
+
+impl Vulkan {
+    fn render_inner(
+        &mut self,
+        preset: ShaderPreset,
+        frame_count: usize,
+        output_size: Option<Size<u32>>,
+        param_setter: Option<&dyn Fn(&RuntimeParameters)>,
+        frame_options: Option<CommonFrameOptions>,
+        read_passes: bool,
+    ) -> anyhow::Result<(RgbaImage, Vec<PassOutput>)> {
+        unsafe {
+            let mut filter_chain = FilterChain::load_from_preset(
+                preset,
+                &self.vk,
+                Some(&FilterChainOptions {
+                    frames_in_flight: 3,
+                    force_no_mipmaps: false,
+                    use_dynamic_rendering: false,
+                    disable_cache: false,
+                    hdr_output: None,
                 }),
             )?;
 
@@ -116,7 +294,7 @@ impl RenderTest for Vulkan {
                 )?
             };
 
-            self.vk.queue_work(|cmd| {
+            let pass_buffers = self.vk.queue_work(|cmd| {
                 util::vulkan_image_layout_transition_levels(
                     &self.vk.device(),
                     cmd,
@@ -153,6 +331,19 @@ impl RenderTest for Vulkan {
                     rotation: options.rotation,
                     total_subframes: options.total_subframes,
                     current_subframe: options.current_subframe,
+                    aspect_ratio: options.aspect_ratio,
+                    original_fps: options.original_fps,
+                    frametime_delta: options.frametime_delta,
+                    core_fps: options.core_fps,
+                    enable_hdr: options.enable_hdr,
+                    paper_white_nits: options.paper_white_nits,
+                    max_nits: options.max_nits,
+                    expand_gamut: options.expand_gamut,
+                    inverse_tonemap: options.inverse_tonemap,
+                    first_pass: options.first_pass,
+                    pass_count: options.pass_count,
+                    compare_split: options.compare_split,
+                    compare_line_width: options.compare_line_width,
                 });
 
                 let viewport = Viewport::new_render_target_sized_origin(
@@ -225,7 +416,78 @@ impl RenderTest for Vulkan {
                     vk::Filter::NEAREST,
                 );
 
-                Ok::<_, anyhow::Error>(())
+                let mut pass_buffers = Vec::new();
+                if read_passes {
+                    for index in 0..filter_chain.pass_count() {
+                        let Some(image) = filter_chain.pass_output(index) else {
+                            continue;
+                        };
+                        let format = ImageFormat::from(image.format);
+                        let bytes_per_pixel = format
+                            .bytes_per_pixel()
+                            .ok_or(anyhow!("pass {index} has an unknown framebuffer format"))?;
+                        let buffer = VulkanBuffer::new(
+                            self.vk.device(),
+                            self.vk.allocator(),
+                            vk::BufferUsageFlags::TRANSFER_DST,
+                            image.size.width as usize
+                                * image.size.height as usize
+                                * bytes_per_pixel,
+                        )?;
+
+                        util::vulkan_image_layout_transition_levels(
+                            self.vk.device(),
+                            cmd,
+                            image.image,
+                            vk::REMAINING_MIP_LEVELS,
+                            vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
+                            vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                            vk::AccessFlags::SHADER_READ,
+                            vk::AccessFlags::TRANSFER_READ,
+                            vk::PipelineStageFlags::ALL_GRAPHICS,
+                            vk::PipelineStageFlags::TRANSFER,
+                            vk::QUEUE_FAMILY_IGNORED,
+                            vk::QUEUE_FAMILY_IGNORED,
+                        );
+
+                        let copy = vk::BufferImageCopy::default()
+                            .image_subresource(
+                                vk::ImageSubresourceLayers::default()
+                                    .aspect_mask(vk::ImageAspectFlags::COLOR)
+                                    .mip_level(0)
+                                    .base_array_layer(0)
+                                    .layer_count(1),
+                            )
+                            .image_extent(image.size.into());
+
+                        self.vk.device().cmd_copy_image_to_buffer(
+                            cmd,
+                            image.image,
+                            vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                            buffer.handle,
+                            &[copy],
+                        );
+
+                        util::vulkan_image_layout_transition_levels(
+                            self.vk.device(),
+                            cmd,
+                            image.image,
+                            vk::REMAINING_MIP_LEVELS,
+                            vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                            vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
+                            vk::AccessFlags::TRANSFER_READ,
+                            vk::AccessFlags::SHADER_READ,
+                            vk::PipelineStageFlags::TRANSFER,
+                            vk::PipelineStageFlags::ALL_GRAPHICS,
+                            vk::QUEUE_FAMILY_IGNORED,
+                            vk::QUEUE_FAMILY_IGNORED,
+                        );
+
+                        pass_buffers.push((index, format, image.size, buffer));
+                    }
+                }
+
+                Ok::<_, anyhow::Error>(pass_buffers)
             })??;
 
             // should have read now.
@@ -254,12 +516,21 @@ impl RenderTest for Vulkan {
             self.vk.device().destroy_image(transfer_texture, None);
             self.vk.device().destroy_image(render_texture, None);
 
-            Ok(image?)
+            let mut passes = Vec::new();
+            for (index, format, size, mut buffer) in pass_buffers {
+                passes.push(PassOutput::decode(
+                    index,
+                    filter_chain.pass_alias(index),
+                    format,
+                    size,
+                    buffer.as_mut_slice()?,
+                )?);
+            }
+
+            Ok((image?, passes))
         }
     }
-}
 
-impl Vulkan {
     pub fn new(image_path: &Path) -> anyhow::Result<Self> {
         let vk = VulkanBase::new()?;
 
@@ -1,3 +1,4 @@
+use crate::render::passes::PassOutput;
 use crate::render::{CommonFrameOptions, RenderTest};
 use anyhow::anyhow;
 use image::RgbaImage;
@@ -5,7 +6,7 @@ use librashader::runtime::wgpu::*;
 use librashader::runtime::{Size, Viewport};
 use librashader_runtime::image::{Image, UVDirection};
 use std::io::{Cursor, Write};
-use std::ops::DerefMut;
+use std::ops::{DerefMut, Range};
 use std::path::Path;
 use std::sync::Arc;
 use wgpu::{Adapter, Device, Instance, Queue, Texture};
@@ -15,7 +16,11 @@ use wgpu_types::{
 };
 
 use librashader::presets::ShaderPreset;
-use librashader::runtime::{FilterChainParameters, RuntimeParameters};
+use librashader::runtime::{
+    FilterChainDebug, FilterChainParameters, FilterChainProfiling, FrameStatistics,
+    RuntimeParameters,
+};
+use librashader::ImageFormat;
 use parking_lot::Mutex;
 
 pub struct Wgpu {
@@ -34,8 +39,7 @@ struct BufferDimensions {
 }
 
 impl BufferDimensions {
-    fn new(width: usize, height: usize) -> Self {
-        let bytes_per_pixel = std::mem::size_of::<u32>();
+    fn new(width: usize, height: usize, bytes_per_pixel: usize) -> Self {
         let unpadded_bytes_per_row = width * bytes_per_pixel;
         let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as usize;
         let padded_bytes_per_row_padding = (align - unpadded_bytes_per_row % align) % align;
@@ -68,6 +72,157 @@ impl RenderTest for Wgpu {
         param_setter: Option<&dyn Fn(&RuntimeParameters)>,
         frame_options: Option<CommonFrameOptions>,
     ) -> anyhow::Result<image::RgbaImage> {
+        let (mut images, _) = self.render_inner(
+            preset,
+            frame_count..frame_count + 1,
+            output_size,
+            param_setter,
+            frame_options,
+            false,
+        )?;
+        images.pop().ok_or(anyhow!("no frame was rendered"))
+    }
+
+    fn render_passes_with_preset_and_params(
+        &mut self,
+        preset: ShaderPreset,
+        frame_count: usize,
+        output_size: Option<Size<u32>>,
+        param_setter: Option<&dyn Fn(&RuntimeParameters)>,
+        frame_options: Option<CommonFrameOptions>,
+    ) -> anyhow::Result<(image::RgbaImage, Vec<PassOutput>)> {
+        let (mut images, passes) = self.render_inner(
+            preset,
+            frame_count..frame_count + 1,
+            output_size,
+            param_setter,
+            frame_options,
+            true,
+        )?;
+        let image = images.pop().ok_or(anyhow!("no frame was rendered"))?;
+        Ok((image, passes))
+    }
+
+    fn render_sequence_with_preset_and_params(
+        &mut self,
+        preset: ShaderPreset,
+        frames: Range<usize>,
+        output_size: Option<Size<u32>>,
+        param_setter: Option<&dyn Fn(&RuntimeParameters)>,
+        frame_options: Option<CommonFrameOptions>,
+    ) -> anyhow::Result<Vec<image::RgbaImage>> {
+        let (images, _) = self.render_inner(
+            preset,
+            frames,
+            output_size,
+            param_setter,
+            frame_options,
+            false,
+        )?;
+        Ok(images)
+    }
+
+    fn bench_with_preset_and_params(
+        &mut self,
+        preset: ShaderPreset,
+        frames: Range<usize>,
+        output_size: Option<Size<u32>>,
+        param_setter: Option<&dyn Fn(&RuntimeParameters)>,
+        frame_options: Option<CommonFrameOptions>,
+    ) -> anyhow::Result<Vec<FrameStatistics>> {
+        let mut chain = FilterChain::load_from_preset(
+            preset,
+            Arc::clone(&self.device),
+            Arc::clone(&self.queue),
+            Some(&FilterChainOptions {
+                force_no_mipmaps: false,
+                enable_cache: true,
+                adapter_info: None,
+                hdr_output: None,
+            }),
+        )?;
+        if let Some(setter) = param_setter {
+            setter(chain.parameters());
+        }
+        chain.set_profiling(true);
+
+        let output_tex = self.device.create_texture(&TextureDescriptor {
+            label: None,
+            size: output_size.map_or(self.texture.size(), |size| size.into()),
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Rgba8Unorm,
+            usage: TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[wgpu::TextureFormat::Rgba8Unorm],
+        });
+
+        let view = output_tex.create_view(&wgpu::TextureViewDescriptor::default());
+        let output = WgpuOutputView::new_from_raw(
+            &view,
+            output_tex.size().into(),
+            TextureFormat::Rgba8Unorm,
+        );
+
+        let viewport = Viewport::new_render_target_sized_origin(output, None)?;
+        let options = frame_options.map(|options| FrameOptions {
+            clear_history: options.clear_history,
+            frame_direction: options.frame_direction,
+            rotation: options.rotation,
+            total_subframes: options.total_subframes,
+            current_subframe: options.current_subframe,
+            aspect_ratio: options.aspect_ratio,
+            original_fps: options.original_fps,
+            frametime_delta: options.frametime_delta,
+            core_fps: options.core_fps,
+            enable_hdr: options.enable_hdr,
+            paper_white_nits: options.paper_white_nits,
+            max_nits: options.max_nits,
+            expand_gamut: options.expand_gamut,
+            inverse_tonemap: options.inverse_tonemap,
+            first_pass: options.first_pass,
+            pass_count: options.pass_count,
+            compare_split: options.compare_split,
+            compare_line_width: options.compare_line_width,
+        });
+
+        let mut statistics = Vec::new();
+        for frame in frames {
+            let mut cmd = self
+                .device
+                .create_command_encoder(&CommandEncoderDescriptor { label: None });
+            chain.frame(
+                Arc::clone(&self.texture),
+                &viewport,
+                &mut cmd,
+                frame,
+                options.as_ref(),
+            )?;
+
+            let si = self.queue.submit([cmd.finish()]);
+            self.device.poll(Maintain::WaitForSubmissionIndex(si));
+
+            if !chain.profiling() {
+                return Err(anyhow!("the device does not support timestamp queries"));
+            }
+
+            statistics.extend(chain.frame_statistics());
+        }
+
+        Ok(statistics)
+    }
+}
+
+impl Wgpu {
+    fn render_inner(
+        &mut self,
+        preset: ShaderPreset,
+        frames: Range<usize>,
+        output_size: Option<Size<u32>>,
+        param_setter: Option<&dyn Fn(&RuntimeParameters)>,
+        frame_options: Option<CommonFrameOptions>,
+        read_passes: bool,
+    ) -> anyhow::Result<(Vec<RgbaImage>, Vec<PassOutput>)> {
         let mut chain = FilterChain::load_from_preset(
             preset,
             Arc::clone(&self.device),
@@ -76,6 +231,7 @@ impl RenderTest for Wgpu {
                 force_no_mipmaps: false,
                 enable_cache: true,
                 adapter_info: None,
+                hdr_output: None,
             }),
         )?;
         if let Some(setter) = param_setter {
@@ -96,16 +252,6 @@ impl RenderTest for Wgpu {
             view_formats: &[wgpu::TextureFormat::Rgba8Unorm],
         });
 
-        let buffer_dimensions =
-            BufferDimensions::new(output_tex.width() as usize, output_tex.height() as usize);
-        let output_buf = Arc::new(self.device.create_buffer(&BufferDescriptor {
-            label: None,
-            size: (buffer_dimensions.padded_bytes_per_row * buffer_dimensions.height)
-                as BufferAddress, // 4bpp
-            usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
-            mapped_at_creation: false,
-        }));
-
         let view = output_tex.create_view(&wgpu::TextureViewDescriptor::default());
         let output = WgpuOutputView::new_from_raw(
             &view,
@@ -120,9 +266,23 @@ impl RenderTest for Wgpu {
             rotation: options.rotation,
             total_subframes: options.total_subframes,
             current_subframe: options.current_subframe,
+            aspect_ratio: options.aspect_ratio,
+            original_fps: options.original_fps,
+            frametime_delta: options.frametime_delta,
+            core_fps: options.core_fps,
+            enable_hdr: options.enable_hdr,
+            paper_white_nits: options.paper_white_nits,
+            max_nits: options.max_nits,
+            expand_gamut: options.expand_gamut,
+            inverse_tonemap: options.inverse_tonemap,
+            first_pass: options.first_pass,
+            pass_count: options.pass_count,
+            compare_split: options.compare_split,
+            compare_line_width: options.compare_line_width,
         });
 
-        for frame in 0..=frame_count {
+        let mut images = Vec::new();
+        for frame in 0..frames.end {
             chain.frame(
                 Arc::clone(&self.texture),
                 &viewport,
@@ -130,10 +290,71 @@ impl RenderTest for Wgpu {
                 frame,
                 options.as_ref(),
             )?;
+
+            if !frames.contains(&frame) {
+                continue;
+            }
+
+            let recorded = std::mem::replace(
+                &mut cmd,
+                self.device
+                    .create_command_encoder(&CommandEncoderDescriptor { label: None }),
+            );
+            self.queue.submit([recorded.finish()]);
+
+            let pixels = self.read_texture(&output_tex, std::mem::size_of::<u32>())?;
+            let image = RgbaImage::from_raw(output_tex.width(), output_tex.height(), pixels)
+                .ok_or(anyhow!("Unable to create image from data"))?;
+            images.push(image);
         }
 
+        self.queue.submit([cmd.finish()]);
+
+        let mut passes = Vec::new();
+        if read_passes {
+            for index in 0..chain.pass_count() {
+                let Some(texture) = chain.pass_output(index) else {
+                    continue;
+                };
+                let format = ImageFormat::from(texture.format());
+                let bytes_per_pixel = format
+                    .bytes_per_pixel()
+                    .ok_or(anyhow!("pass {index} has an unknown framebuffer format"))?;
+                let pixels = self.read_texture(&texture, bytes_per_pixel)?;
+                passes.push(PassOutput::decode(
+                    index,
+                    chain.pass_alias(index),
+                    format,
+                    texture.size().into(),
+                    &pixels,
+                )?);
+            }
+        }
+
+        Ok((images, passes))
+    }
+
+    /// Copy the first mip level of a texture into tightly packed rows.
+    fn read_texture(&self, texture: &Texture, bytes_per_pixel: usize) -> anyhow::Result<Vec<u8>> {
+        let buffer_dimensions = BufferDimensions::new(
+            texture.width() as usize,
+            texture.height() as usize,
+            bytes_per_pixel,
+        );
+        let output_buf = Arc::new(self.device.create_buffer(&BufferDescriptor {
+            label: None,
+            size: (buffer_dimensions.padded_bytes_per_row * buffer_dimensions.height)
+                as BufferAddress,
+            usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        }));
+
+        let mut cmd = self
+            .device
+            .create_command_encoder(&CommandEncoderDescriptor { label: None });
+
         cmd.copy_texture_to_buffer(
-            output_tex.as_image_copy(),
+            texture.as_image_copy(),
             ImageCopyBuffer {
                 buffer: &output_buf,
                 layout: ImageDataLayout {
@@ -142,7 +363,7 @@ impl RenderTest for Wgpu {
                     rows_per_image: None,
                 },
             },
-            output_tex.size(),
+            texture.size(),
         );
 
         let si = self.queue.submit([cmd.finish()]);
@@ -179,18 +400,10 @@ impl RenderTest for Wgpu {
             return Err(anyhow!("failed to copy pixels from buffer"));
         }
 
-        let image = RgbaImage::from_raw(
-            output_tex.width(),
-            output_tex.height(),
-            pixels.lock().to_vec(),
-        )
-        .ok_or(anyhow!("Unable to create image from data"))?;
-
-        Ok(image)
+        let pixels = pixels.lock().to_vec();
+        Ok(pixels)
     }
-}
 
-impl Wgpu {
     pub fn new(image: &Path) -> anyhow::Result<Self> {
         pollster::block_on(async {
             let instance = wgpu::Instance::default();
@@ -209,7 +422,10 @@ impl Wgpu {
                         required_features: wgpu::Features::ADDRESS_MODE_CLAMP_TO_BORDER
                             | wgpu::Features::PIPELINE_CACHE
                             | wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES
-                            | wgpu::Features::FLOAT32_FILTERABLE,
+                            | wgpu::Features::FLOAT32_FILTERABLE
+                            | (adapter.features()
+                                & (wgpu::Features::TIMESTAMP_QUERY
+                                    | wgpu::Features::TIMESTAMP_QUERY_INSIDE_ENCODERS)),
                         required_limits: wgpu::Limits::default(),
                         label: None,
                         memory_hints: Default::default(),
@@ -38,6 +38,86 @@ impl From<ImageFormat> for u32 {
     }
 }
 
+impl ImageFormat {
+    /// Get the image format of a sized OpenGL internal format.
+    pub fn from_gl_internal_format(format: u32) -> ImageFormat {
+        match format {
+            glow::R8 => ImageFormat::R8Unorm,
+            glow::R8UI => ImageFormat::R8Uint,
+            glow::R8I => ImageFormat::R8Sint,
+            glow::RG8 => ImageFormat::R8G8Unorm,
+            glow::RG8UI => ImageFormat::R8G8Uint,
+            glow::RG8I => ImageFormat::R8G8Sint,
+            glow::RGBA8 => ImageFormat::R8G8B8A8Unorm,
+            glow::RGBA8UI => ImageFormat::R8G8B8A8Uint,
+            glow::RGBA8I => ImageFormat::R8G8B8A8Sint,
+            glow::SRGB8_ALPHA8 => ImageFormat::R8G8B8A8Srgb,
+            glow::RGB10_A2 => ImageFormat::A2B10G10R10UnormPack32,
+            glow::RGB10_A2UI => ImageFormat::A2B10G10R10UintPack32,
+            glow::R16UI => ImageFormat::R16Uint,
+            glow::R16I => ImageFormat::R16Sint,
+            glow::R16F => ImageFormat::R16Sfloat,
+            glow::RG16UI => ImageFormat::R16G16Uint,
+            glow::RG16I => ImageFormat::R16G16Sint,
+            glow::RG16F => ImageFormat::R16G16Sfloat,
+            glow::RGBA16UI => ImageFormat::R16G16B16A16Uint,
+            glow::RGBA16I => ImageFormat::R16G16B16A16Sint,
+            glow::RGBA16F => ImageFormat::R16G16B16A16Sfloat,
+            glow::R32UI => ImageFormat::R32Uint,
+            glow::R32I => ImageFormat::R32Sint,
+            glow::R32F => ImageFormat::R32Sfloat,
+            glow::RG32UI => ImageFormat::R32G32Uint,
+            glow::RG32I => ImageFormat::R32G32Sint,
+            glow::RG32F => ImageFormat::R32G32Sfloat,
+            glow::RGBA32UI => ImageFormat::R32G32B32A32Uint,
+            glow::RGBA32I => ImageFormat::R32G32B32A32Sint,
+            glow::RGBA32F => ImageFormat::R32G32B32A32Sfloat,
+            _ => ImageFormat::Unknown,
+        }
+    }
+
+    /// Get the pixel format and type to transfer tightly packed texels of this format
+    /// to and from a texture.
+    pub fn gl_pixel_transfer_format(&self) -> Option<(u32, u32)> {
+        Some(match self {
+            ImageFormat::Unknown => return None,
+            ImageFormat::R8Unorm => (glow::RED, glow::UNSIGNED_BYTE),
+            ImageFormat::R8Uint => (glow::RED_INTEGER, glow::UNSIGNED_BYTE),
+            ImageFormat::R8Sint => (glow::RED_INTEGER, glow::BYTE),
+            ImageFormat::R8G8Unorm => (glow::RG, glow::UNSIGNED_BYTE),
+            ImageFormat::R8G8Uint => (glow::RG_INTEGER, glow::UNSIGNED_BYTE),
+            ImageFormat::R8G8Sint => (glow::RG_INTEGER, glow::BYTE),
+            ImageFormat::R8G8B8A8Unorm | ImageFormat::R8G8B8A8Srgb => {
+                (glow::RGBA, glow::UNSIGNED_BYTE)
+            }
+            ImageFormat::R8G8B8A8Uint => (glow::RGBA_INTEGER, glow::UNSIGNED_BYTE),
+            ImageFormat::R8G8B8A8Sint => (glow::RGBA_INTEGER, glow::BYTE),
+            ImageFormat::A2B10G10R10UnormPack32 => (glow::RGBA, glow::UNSIGNED_INT_2_10_10_10_REV),
+            ImageFormat::A2B10G10R10UintPack32 => {
+                (glow::RGBA_INTEGER, glow::UNSIGNED_INT_2_10_10_10_REV)
+            }
+            ImageFormat::R16Uint => (glow::RED_INTEGER, glow::UNSIGNED_SHORT),
+            ImageFormat::R16Sint => (glow::RED_INTEGER, glow::SHORT),
+            ImageFormat::R16Sfloat => (glow::RED, glow::HALF_FLOAT),
+            ImageFormat::R16G16Uint => (glow::RG_INTEGER, glow::UNSIGNED_SHORT),
+            ImageFormat::R16G16Sint => (glow::RG_INTEGER, glow::SHORT),
+            ImageFormat::R16G16Sfloat => (glow::RG, glow::HALF_FLOAT),
+            ImageFormat::R16G16B16A16Uint => (glow::RGBA_INTEGER, glow::UNSIGNED_SHORT),
+            ImageFormat::R16G16B16A16Sint => (glow::RGBA_INTEGER, glow::SHORT),
+            ImageFormat::R16G16B16A16Sfloat => (glow::RGBA, glow::HALF_FLOAT),
+            ImageFormat::R32Uint => (glow::RED_INTEGER, glow::UNSIGNED_INT),
+            ImageFormat::R32Sint => (glow::RED_INTEGER, glow::INT),
+            ImageFormat::R32Sfloat => (glow::RED, glow::FLOAT),
+            ImageFormat::R32G32Uint => (glow::RG_INTEGER, glow::UNSIGNED_INT),
+            ImageFormat::R32G32Sint => (glow::RG_INTEGER, glow::INT),
+            ImageFormat::R32G32Sfloat => (glow::RG, glow::FLOAT),
+            ImageFormat::R32G32B32A32Uint => (glow::RGBA_INTEGER, glow::UNSIGNED_INT),
+            ImageFormat::R32G32B32A32Sint => (glow::RGBA_INTEGER, glow::INT),
+            ImageFormat::R32G32B32A32Sfloat => (glow::RGBA, glow::FLOAT),
+        })
+    }
+}
+
 impl From<WrapMode> for i32 {
     fn from(value: WrapMode) -> Self {
         match value {
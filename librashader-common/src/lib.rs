@@ -36,6 +36,9 @@ mod viewport;
 #[doc(hidden)]
 pub mod map;
 
+/// File system access for loading shader presets and shader sources.
+pub mod vfs;
+
 pub use viewport::Viewport;
 
 use num_traits::{AsPrimitive, Num};
@@ -90,6 +93,78 @@ pub enum ImageFormat {
     R32G32B32A32Sfloat,
 }
 
+impl ImageFormat {
+    /// All image formats, in order of their discriminant.
+    pub const ALL: [ImageFormat; 31] = [
+        ImageFormat::Unknown,
+        ImageFormat::R8Unorm,
+        ImageFormat::R8Uint,
+        ImageFormat::R8Sint,
+        ImageFormat::R8G8Unorm,
+        ImageFormat::R8G8Uint,
+        ImageFormat::R8G8Sint,
+        ImageFormat::R8G8B8A8Unorm,
+        ImageFormat::R8G8B8A8Uint,
+        ImageFormat::R8G8B8A8Sint,
+        ImageFormat::R8G8B8A8Srgb,
+        ImageFormat::A2B10G10R10UnormPack32,
+        ImageFormat::A2B10G10R10UintPack32,
+        ImageFormat::R16Uint,
+        ImageFormat::R16Sint,
+        ImageFormat::R16Sfloat,
+        ImageFormat::R16G16Uint,
+        ImageFormat::R16G16Sint,
+        ImageFormat::R16G16Sfloat,
+        ImageFormat::R16G16B16A16Uint,
+        ImageFormat::R16G16B16A16Sint,
+        ImageFormat::R16G16B16A16Sfloat,
+        ImageFormat::R32Uint,
+        ImageFormat::R32Sint,
+        ImageFormat::R32Sfloat,
+        ImageFormat::R32G32Uint,
+        ImageFormat::R32G32Sint,
+        ImageFormat::R32G32Sfloat,
+        ImageFormat::R32G32B32A32Uint,
+        ImageFormat::R32G32B32A32Sint,
+        ImageFormat::R32G32B32A32Sfloat,
+    ];
+
+    /// The size of a single texel of the format in bytes, or `None` if the format is unknown.
+    pub fn bytes_per_pixel(&self) -> Option<usize> {
+        Some(match self {
+            ImageFormat::Unknown => return None,
+            ImageFormat::R8Unorm | ImageFormat::R8Uint | ImageFormat::R8Sint => 1,
+            ImageFormat::R8G8Unorm
+            | ImageFormat::R8G8Uint
+            | ImageFormat::R8G8Sint
+            | ImageFormat::R16Uint
+            | ImageFormat::R16Sint
+            | ImageFormat::R16Sfloat => 2,
+            ImageFormat::R8G8B8A8Unorm
+            | ImageFormat::R8G8B8A8Uint
+            | ImageFormat::R8G8B8A8Sint
+            | ImageFormat::R8G8B8A8Srgb
+            | ImageFormat::A2B10G10R10UnormPack32
+            | ImageFormat::A2B10G10R10UintPack32
+            | ImageFormat::R16G16Uint
+            | ImageFormat::R16G16Sint
+            | ImageFormat::R16G16Sfloat
+            | ImageFormat::R32Uint
+            | ImageFormat::R32Sint
+            | ImageFormat::R32Sfloat => 4,
+            ImageFormat::R16G16B16A16Uint
+            | ImageFormat::R16G16B16A16Sint
+            | ImageFormat::R16G16B16A16Sfloat
+            | ImageFormat::R32G32Uint
+            | ImageFormat::R32G32Sint
+            | ImageFormat::R32G32Sfloat => 8,
+            ImageFormat::R32G32B32A32Uint
+            | ImageFormat::R32G32B32A32Sint
+            | ImageFormat::R32G32B32A32Sfloat => 16,
+        })
+    }
+}
+
 #[repr(i32)]
 #[derive(Copy, Clone, Default, Debug, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -145,6 +220,26 @@ pub enum WrapMode {
     MirroredRepeat,
 }
 
+// Every format must be in `ImageFormat::ALL` at the index of its discriminant,
+// so that formats converted to `u32` can be converted back.
+const _: () = {
+    assert!(ImageFormat::ALL.len() == ImageFormat::R32G32B32A32Sfloat as usize + 1);
+    let mut index = 0;
+    while index < ImageFormat::ALL.len() {
+        assert!(ImageFormat::ALL[index] as usize == index);
+        index += 1;
+    }
+};
+
+impl TryFrom<u32> for ImageFormat {
+    type Error = u32;
+
+    /// Get the image format with the given discriminant, returning the value if it is not a known format.
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        ImageFormat::ALL.get(value as usize).copied().ok_or(value)
+    }
+}
+
 impl FromStr for ImageFormat {
     type Err = Infallible;
 
@@ -4,5 +4,11 @@
 pub type FastHashMap<K, V> =
     halfbrown::SizedHashMap<K, V, core::hash::BuildHasherDefault<rustc_hash::FxHasher>, 32>;
 
+/// A hashmap that iterates in insertion order, with a fast hash implementation.
+///
+/// Used where the declaration order of entries is significant.
+pub type FastIndexMap<K, V> =
+    indexmap::IndexMap<K, V, core::hash::BuildHasherDefault<rustc_hash::FxHasher>>;
+
 /// A string with small string optimizations up to 23 bytes.
 pub type ShortString = smartstring::SmartString<smartstring::LazyCompact>;
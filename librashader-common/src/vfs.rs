@@ -0,0 +1,144 @@
+use std::collections::BTreeMap;
+use std::io;
+use std::ops::Bound;
+use std::path::{Component, Path, PathBuf};
+
+/// Access to the files that shader presets and shader sources are loaded from.
+///
+/// Loading a preset or a shader source reads the preset, its `#reference` presets, the
+/// sources of its passes and their `#include` files through this trait, which allows them to
+/// be loaded from somewhere other than the file system of the host, such as from memory on
+/// `wasm32-unknown-unknown`.
+pub trait FileSystem {
+    /// Read the entire contents of the file at the given path.
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>>;
+
+    /// Returns the canonical, absolute form of the path, or an error if it does not exist.
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf>;
+
+    /// Returns whether a file or directory exists at the given path.
+    fn exists(&self, path: &Path) -> bool;
+
+    /// Returns whether the path points to an existing directory.
+    fn is_dir(&self, path: &Path) -> bool;
+}
+
+/// The file system of the host, accessed through [`std::fs`].
+#[derive(Debug, Default, Copy, Clone)]
+pub struct StdFileSystem;
+
+impl FileSystem for StdFileSystem {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        std::fs::read(path)
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        path.canonicalize()
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        matches!(path.try_exists(), Ok(true))
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        path.is_dir()
+    }
+}
+
+/// A file system where every file is held in memory.
+///
+/// Paths are resolved lexically against the root directory: relative paths are treated as
+/// relative to the root, and `.` and `..` components are removed. Directories exist
+/// implicitly for every ancestor of a file.
+#[derive(Debug, Default, Clone)]
+pub struct MemoryFileSystem {
+    files: BTreeMap<PathBuf, Vec<u8>>,
+}
+
+impl MemoryFileSystem {
+    /// Create an empty in-memory file system.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a file with the given contents, replacing any file already at the path.
+    pub fn insert(&mut self, path: impl AsRef<Path>, contents: impl Into<Vec<u8>>) {
+        self.files.insert(normalize(path.as_ref()), contents.into());
+    }
+
+    /// Remove the file at the given path, returning its contents if it existed.
+    pub fn remove(&mut self, path: impl AsRef<Path>) -> Option<Vec<u8>> {
+        self.files.remove(&normalize(path.as_ref()))
+    }
+
+    /// Returns an iterator over the normalized paths of every file.
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.files.keys().map(PathBuf::as_path)
+    }
+
+    /// The number of files in the file system.
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Returns whether the file system has no files.
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    fn has_descendants(&self, path: &Path) -> bool {
+        // Paths are ordered by component, so descendants directly follow their ancestor.
+        self.files
+            .range::<Path, _>((Bound::Excluded(path), Bound::Unbounded))
+            .next()
+            .is_some_and(|(file, _)| file.starts_with(path))
+    }
+}
+
+impl FileSystem for MemoryFileSystem {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.files
+            .get(&normalize(path))
+            .cloned()
+            .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        let path = normalize(path);
+        if self.files.contains_key(&path) || self.has_descendants(&path) {
+            Ok(path)
+        } else {
+            Err(io::Error::from(io::ErrorKind::NotFound))
+        }
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.canonicalize(path).is_ok()
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        self.has_descendants(&normalize(path))
+    }
+}
+
+/// Lexically normalize a path against the root directory, keeping any prefix.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    let mut components = path.components().peekable();
+    if let Some(Component::Prefix(prefix)) = components.peek() {
+        normalized.push(prefix.as_os_str());
+        components.next();
+    }
+    normalized.push(Component::RootDir);
+
+    for component in components {
+        match component {
+            Component::Prefix(_) | Component::RootDir | Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            Component::Normal(name) => normalized.push(name),
+        }
+    }
+    normalized
+}
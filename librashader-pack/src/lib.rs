@@ -5,6 +5,7 @@
 //! Also defines abstractly the `.slangpack` shader format implemented via serde derives on [`ShaderPresetPack`].
 //!
 use image::{ImageError, RgbaImage};
+use librashader_common::vfs::FileSystem;
 use librashader_preprocess::{PreprocessError, ShaderSource};
 use librashader_presets::{ParameterMeta, PassMeta, ShaderPreset, TextureMeta};
 use std::path::Path;
@@ -22,6 +23,18 @@ pub struct TextureBuffer {
     height: u32,
 }
 
+impl TextureBuffer {
+    /// The width of the image in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// The height of the image in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+}
+
 impl From<TextureBuffer> for Option<RgbaImage> {
     fn from(value: TextureBuffer) -> Self {
         RgbaImage::from_raw(value.width, value.height, value.image)
@@ -124,7 +137,7 @@ impl ShaderPresetPack {
         let shaders_iter = preset.passes.into_par_iter();
 
         #[cfg(target_arch = "wasm32")]
-        let shaders_iter = preset.shaders.into_iter();
+        let shaders_iter = preset.passes.into_iter();
 
         #[cfg(not(target_arch = "wasm32"))]
         let textures_iter = preset.textures.into_par_iter();
@@ -146,7 +159,6 @@ impl ShaderPresetPack {
                 })
                 .collect::<Result<Vec<_>, _>>()?,
             textures: textures_iter
-                .into_par_iter()
                 .map(|t| {
                     Ok::<_, E>(TextureResource {
                         data: TextureMeta::load(t.path.as_path())?,
@@ -157,6 +169,70 @@ impl ShaderPresetPack {
             parameters: preset.parameters,
         })
     }
+
+    /// Load a `ShaderPack` from a [`ShaderPreset`], loading the resources of each pass and
+    /// texture in order with the provided functions.
+    ///
+    /// The functions are given the index and path of the pass or texture to load.
+    pub fn load_from_preset_with<E>(
+        preset: ShaderPreset,
+        mut load_pass: impl FnMut(usize, &Path) -> Result<ShaderSource, E>,
+        mut load_texture: impl FnMut(usize, &Path) -> Result<TextureBuffer, E>,
+    ) -> Result<ShaderPresetPack, E> {
+        Ok(ShaderPresetPack {
+            #[cfg(feature = "parse_legacy_glsl")]
+            feedback_pass: preset.feedback_pass,
+
+            pass_count: preset.pass_count,
+            passes: preset
+                .passes
+                .into_iter()
+                .enumerate()
+                .map(|(index, v)| {
+                    Ok(PassResource {
+                        data: load_pass(index, v.path.as_path())?,
+                        meta: v.meta,
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            textures: preset
+                .textures
+                .into_iter()
+                .enumerate()
+                .map(|(index, t)| {
+                    Ok(TextureResource {
+                        data: load_texture(index, t.path.as_path())?,
+                        meta: t.meta,
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            parameters: preset.parameters,
+        })
+    }
+
+    /// Load a `ShaderPack` from a [`ShaderPreset`], reading the sources of each pass and the
+    /// images of each texture from the provided file system.
+    ///
+    /// Use this with a preset parsed by [`ShaderPreset::try_parse_with_fs`] when the files of
+    /// the preset are not on the file system of the host, such as on `wasm32-unknown-unknown`.
+    pub fn load_from_preset_with_fs<E>(
+        preset: ShaderPreset,
+        fs: &dyn FileSystem,
+    ) -> Result<ShaderPresetPack, E>
+    where
+        E: From<PreprocessError>,
+        E: From<ImageError>,
+    {
+        Self::load_from_preset_with(
+            preset,
+            |_, path| Ok(ShaderSource::load_with_fs(path, fs)?),
+            |_, path| {
+                let bytes = fs.read(path).map_err(ImageError::IoError)?;
+                let image = image::load_from_memory(&bytes)?;
+                Ok(TextureBuffer::from(image.to_rgba8()))
+            },
+        )
+    }
 }
 
 #[cfg(feature = "serde")]
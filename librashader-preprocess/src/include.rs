@@ -1,7 +1,6 @@
 use crate::{PreprocessError, SourceOutput};
 use encoding_rs::{DecoderResult, WINDOWS_1252};
-use std::fs::File;
-use std::io::Read;
+use librashader_common::vfs::{FileSystem, StdFileSystem};
 use std::path::{Path, PathBuf};
 use std::str::Lines;
 
@@ -9,19 +8,17 @@ use std::str::Lines;
 const GL_GOOGLE_CPP_STYLE_LINE_DIRECTIVE: &str =
     "#extension GL_GOOGLE_cpp_style_line_directive : require";
 
-fn read_file(path: impl AsRef<Path>) -> Result<String, PreprocessError> {
+fn read_file(path: impl AsRef<Path>, fs: &dyn FileSystem) -> Result<String, PreprocessError> {
     let path = path.as_ref();
-    let mut buf = Vec::new();
-    File::open(path)
-        .and_then(|mut f| {
-            f.read_to_end(&mut buf)?;
-            Ok(())
-        })
+    log::trace!("reading shader source {path:?}");
+    let buf = fs
+        .read(path)
         .map_err(|e| PreprocessError::IOError(path.to_path_buf(), e))?;
 
     match String::from_utf8(buf) {
         Ok(s) => Ok(s),
         Err(e) => {
+            log::debug!("{path:?} is not valid UTF-8, decoding as Windows-1252");
             let buf = e.into_bytes();
             let decoder = WINDOWS_1252.new_decoder();
             let Some(len) = decoder.max_utf8_buffer_length_without_replacement(buf.len()) else {
@@ -43,11 +40,21 @@ fn read_file(path: impl AsRef<Path>) -> Result<String, PreprocessError> {
 }
 
 pub fn read_source(path: impl AsRef<Path>) -> Result<String, PreprocessError> {
+    read_source_with_includes(path, &StdFileSystem, &mut Vec::new())
+}
+
+/// Read and preprocess the source file at the given path from the file system, collecting the
+/// paths of every file reached through `#include` or `#pragma include_optional` into `includes`.
+pub fn read_source_with_includes(
+    path: impl AsRef<Path>,
+    fs: &dyn FileSystem,
+    includes: &mut Vec<PathBuf>,
+) -> Result<String, PreprocessError> {
     let path = path.as_ref();
-    let source = read_file(path)?;
+    let source = read_file(path, fs)?;
     let mut output = String::new();
 
-    let source = source.trim();
+    let (source, first_line) = trim_source(&source);
     let mut lines = source.lines();
 
     if let Some(header) = lines.next() {
@@ -62,16 +69,39 @@ pub fn read_source(path: impl AsRef<Path>) -> Result<String, PreprocessError> {
     #[cfg(feature = "line_directives")]
     output.push_line(GL_GOOGLE_CPP_STYLE_LINE_DIRECTIVE);
 
-    output.mark_line(2, path.file_name().and_then(|f| f.to_str()).unwrap_or(""));
-    preprocess(lines, path, &mut output)?;
+    output.mark_line(
+        first_line + 1,
+        path.file_name().and_then(|f| f.to_str()).unwrap_or(""),
+    );
+    preprocess(lines, first_line + 1, path, fs, &mut output, includes)?;
 
     Ok(output)
 }
 
+/// Trims the source, returning the trimmed source and the line number
+/// of its first line in the original source.
+fn trim_source(source: &str) -> (&str, usize) {
+    let trimmed = source.trim_start();
+    let skipped_lines = source[..source.len() - trimmed.len()]
+        .bytes()
+        .filter(|&b| b == b'\n')
+        .count();
+
+    (trimmed.trim_end(), skipped_lines + 1)
+}
+
+/// Preprocess the given lines, where `first_line` is the line number
+/// of the first line in the source file.
+///
+/// The line number passed to `mark_line` is that of the line following the marker,
+/// which lets the shader compiler report locations in terms of the original source files.
 fn preprocess(
     lines: Lines,
+    first_line: usize,
     file_name: impl AsRef<Path>,
+    fs: &dyn FileSystem,
     output: &mut String,
+    includes: &mut Vec<PathBuf>,
 ) -> Result<(), PreprocessError> {
     let file_name = file_name.as_ref();
     let include_path = file_name.parent().unwrap();
@@ -82,22 +112,25 @@ fn preprocess(
         source: String,
         include_path: PathBuf,
         file_name: &str,
-        line_no: usize,
+        next_line: usize,
+        fs: &dyn FileSystem,
+        includes: &mut Vec<PathBuf>,
     ) -> Result<(), PreprocessError> {
-        let source = source.trim();
+        let (source, first_line) = trim_source(&source);
         let lines = source.lines();
 
         let include_file = include_path
             .file_name()
             .and_then(|f| f.to_str())
             .unwrap_or("");
-        output.mark_line(1, include_file);
-        preprocess(lines, include_path, output)?;
-        output.mark_line(line_no + 1, file_name);
+        output.mark_line(first_line, include_file);
+        preprocess(lines, first_line, include_path, fs, output, includes)?;
+        output.mark_line(next_line, file_name);
         Ok(())
     }
 
-    for (line_no, line) in lines.enumerate() {
+    for (index, line) in lines.enumerate() {
+        let line_no = first_line + index;
         if let Some(include_file) = line.strip_prefix("#include ") {
             let include_file = include_file.trim().trim_matches('"');
             if include_file.is_empty() {
@@ -107,8 +140,17 @@ fn preprocess(
             let mut include_path = include_path.to_path_buf();
             include_path.push(include_file);
 
-            let source = read_file(&include_path)?;
-            include_callback(output, source, include_path, file_name, line_no)?;
+            includes.push(include_path.clone());
+            let source = read_file(&include_path, fs)?;
+            include_callback(
+                output,
+                source,
+                include_path,
+                file_name,
+                line_no + 1,
+                fs,
+                includes,
+            )?;
 
             continue;
         }
@@ -123,14 +165,24 @@ fn preprocess(
             let mut include_path = include_path.to_path_buf();
             include_path.push(include_file);
 
-            match read_file(&include_path) {
-                Ok(source) => include_callback(output, source, include_path, file_name, line_no)?,
+            // optional includes are tracked even if missing, so that creating them can be detected.
+            includes.push(include_path.clone());
+            match read_file(&include_path, fs) {
+                Ok(source) => include_callback(
+                    output,
+                    source,
+                    include_path,
+                    file_name,
+                    line_no + 1,
+                    fs,
+                    includes,
+                )?,
                 // ioerror indicates that the file is not found.
                 Err(PreprocessError::IOError(..)) => {
+                    log::debug!("optional include {include_path:?} was not found, skipping");
                     output.push_line(&format!("// include_optional not found: {include_file}"));
-                    output.mark_line(line_no, file_name);
-
-                },
+                    output.mark_line(line_no + 1, file_name);
+                }
                 // other errors should not be ignored.
                 Err(e) => return Err(e),
             }
@@ -140,7 +192,7 @@ fn preprocess(
 
         if line.starts_with("#endif") || line.starts_with("#pragma") {
             output.push_line(line);
-            output.mark_line(line_no + 2, file_name);
+            output.mark_line(line_no + 1, file_name);
             continue;
         }
 
@@ -13,11 +13,12 @@ mod include;
 mod pragma;
 mod stage;
 
-use crate::include::read_source;
+use crate::include::{read_source, read_source_with_includes};
 pub use error::*;
-use librashader_common::map::{FastHashMap, ShortString};
+use librashader_common::map::{FastIndexMap, ShortString};
+use librashader_common::vfs::{FileSystem, StdFileSystem};
 use librashader_common::ImageFormat;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// The source file for a single shader pass.
 #[derive(Debug, Clone, PartialEq)]
@@ -32,8 +33,8 @@ pub struct ShaderSource {
     /// The alias of the shader if available.
     pub name: Option<ShortString>,
 
-    /// The list of shader parameters found in the shader source.
-    pub parameters: FastHashMap<ShortString, ShaderParameter>,
+    /// The list of shader parameters found in the shader source, in the order they are declared.
+    pub parameters: FastIndexMap<ShortString, ShaderParameter>,
 
     /// The image format the shader expects.
     pub format: ImageFormat,
@@ -63,6 +64,42 @@ impl ShaderSource {
     pub fn load(path: impl AsRef<Path>) -> Result<ShaderSource, PreprocessError> {
         load_shader_source(path)
     }
+
+    /// Load the source file at the given path, also returning the paths of every file reached
+    /// through `#include` or `#pragma include_optional`.
+    ///
+    /// Optional includes are returned even if they could not be found.
+    pub fn load_with_includes(
+        path: impl AsRef<Path>,
+    ) -> Result<(ShaderSource, Vec<PathBuf>), PreprocessError> {
+        let mut includes = Vec::new();
+        let source = read_source_with_includes(path, &StdFileSystem, &mut includes)?;
+        Ok((parse_shader_source(&source)?, includes))
+    }
+
+    /// Load the source file at the given path from the provided file system, resolving includes
+    /// relative to the location of the source file within it.
+    pub fn load_with_fs(
+        path: impl AsRef<Path>,
+        fs: &dyn FileSystem,
+    ) -> Result<ShaderSource, PreprocessError> {
+        let source = read_source_with_includes(path, fs, &mut Vec::new())?;
+        parse_shader_source(&source)
+    }
+
+    /// Parse the source of a shader that is already in memory.
+    ///
+    /// `#include` directives are not resolved, so the source must be self-contained.
+    pub fn parse(source: &str) -> Result<ShaderSource, PreprocessError> {
+        let source = source.trim();
+        if source.is_empty() {
+            return Err(PreprocessError::UnexpectedEof);
+        }
+        if !source.starts_with("#version ") {
+            return Err(PreprocessError::MissingVersionHeader);
+        }
+        parse_shader_source(source)
+    }
 }
 
 pub(crate) trait SourceOutput {
@@ -82,10 +119,15 @@ impl SourceOutput for String {
 
 pub(crate) fn load_shader_source(path: impl AsRef<Path>) -> Result<ShaderSource, PreprocessError> {
     let source = read_source(path)?;
-    let meta = pragma::parse_pragma_meta(&source)?;
+    parse_shader_source(&source)
+}
 
-    let text = stage::process_stages(&source)?;
-    let parameters = FastHashMap::from_iter(meta.parameters.into_iter().map(|p| (p.id.clone(), p)));
+fn parse_shader_source(source: &str) -> Result<ShaderSource, PreprocessError> {
+    let meta = pragma::parse_pragma_meta(source)?;
+
+    let text = stage::process_stages(source)?;
+    let parameters =
+        FastIndexMap::from_iter(meta.parameters.into_iter().map(|p| (p.id.clone(), p)));
 
     Ok(ShaderSource {
         vertex: text.vertex,
@@ -99,7 +141,9 @@ pub(crate) fn load_shader_source(path: impl AsRef<Path>) -> Result<ShaderSource,
 #[cfg(test)]
 mod test {
     use crate::include::read_source;
-    use crate::{load_shader_source, pragma};
+    use crate::{load_shader_source, pragma, ShaderSource};
+    use librashader_common::vfs::MemoryFileSystem;
+    use std::path::PathBuf;
 
     #[test]
     pub fn load_file() {
@@ -138,4 +182,36 @@ mod test {
 
         eprintln!("{result}")
     }
+
+    #[test]
+    pub fn tracks_includes() {
+        let (_, includes) =
+            ShaderSource::load_with_includes("../test/include_optional/pass.slang").unwrap();
+
+        let root = PathBuf::from("../test/include_optional");
+        assert_eq!(
+            includes,
+            [
+                root.join("includes/myinclude.inc"),
+                root.join("includes/myinclude.doesntexist.inc")
+            ]
+        );
+    }
+
+    #[test]
+    pub fn load_from_memory() {
+        let mut fs = MemoryFileSystem::new();
+        fs.insert(
+            "shaders/pass.slang",
+            "#version 450\n#include \"../include/common.inc\"\n#pragma include_optional \"missing.inc\"\n#pragma stage vertex\nvoid main() {}\n#pragma stage fragment\nvoid main() {}\n",
+        );
+        fs.insert(
+            "include/common.inc",
+            "#pragma parameter Strength \"Strength\" 0.5 0.0 1.0 0.1\n",
+        );
+
+        let source = ShaderSource::load_with_fs("shaders/pass.slang", &fs).unwrap();
+        assert!(source.parameters.contains_key("Strength"));
+        assert!(source.vertex.contains("include_optional not found: missing.inc"));
+    }
 }
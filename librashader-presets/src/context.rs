@@ -5,6 +5,7 @@
 //! Implements wildcard replacement of shader paths specified in
 //! [RetroArch#15023](https://github.com/libretro/RetroArch/pull/15023).
 use librashader_common::map::FastHashMap;
+use librashader_common::vfs::FileSystem;
 use once_cell::sync::Lazy;
 use regex::bytes::Regex;
 use std::collections::VecDeque;
@@ -365,7 +366,11 @@ impl WildcardContext {
     }
 }
 
-pub(crate) fn apply_context(path: &mut PathBuf, context: &FastHashMap<String, String>) {
+pub(crate) fn apply_context(
+    path: &mut PathBuf,
+    context: &FastHashMap<String, String>,
+    fs: &dyn FileSystem,
+) {
     use std::ffi::{OsStr, OsString};
 
     static WILDCARD_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new("\\$([A-Z-_]+)\\$").unwrap());
@@ -407,7 +412,7 @@ pub(crate) fn apply_context(path: &mut PathBuf, context: &FastHashMap<String, St
     }
 
     // If no wildcards are found within the path, or the path after replacing the wildcards does not exist on disk, the path returned will be unaffected.
-    if let Ok(true) = new_path.try_exists() {
+    if fs.exists(&new_path) {
         *path = new_path;
     }
 }
@@ -15,6 +15,7 @@ use crate::error::ParsePresetError;
 use crate::parse::preset::resolve_values;
 use crate::parse::value::parse_preset;
 use crate::ShaderPreset;
+use librashader_common::vfs::{FileSystem, StdFileSystem};
 
 pub(crate) fn remove_if<T>(values: &mut Vec<T>, f: impl FnMut(&T) -> bool) -> Option<T> {
     values.iter().position(f).map(|idx| values.remove(idx))
@@ -27,7 +28,7 @@ impl ShaderPreset {
     pub fn try_parse(path: impl AsRef<Path>) -> Result<ShaderPreset, ParsePresetError> {
         let mut context = WildcardContext::new();
         context.add_path_defaults(path.as_ref());
-        let values = parse_preset(path, WildcardContext::new())?;
+        let values = parse_preset(path, WildcardContext::new(), &StdFileSystem)?;
         Ok(resolve_values(values))
     }
 
@@ -41,7 +42,7 @@ impl ShaderPreset {
         let mut context = WildcardContext::new();
         context.add_path_defaults(path.as_ref());
         context.add_video_driver_defaults(driver);
-        let values = parse_preset(path, context)?;
+        let values = parse_preset(path, context, &StdFileSystem)?;
         Ok(resolve_values(values))
     }
 
@@ -53,15 +54,31 @@ impl ShaderPreset {
         path: impl AsRef<Path>,
         context: WildcardContext,
     ) -> Result<ShaderPreset, ParsePresetError> {
-        let values = parse_preset(path, context)?;
+        let values = parse_preset(path, context, &StdFileSystem)?;
+        Ok(resolve_values(values))
+    }
+
+    /// Try to parse the shader preset at the given path from the provided file system, with
+    /// the exact provided context.
+    ///
+    /// The preset, its `#reference` presets and the existence of the shaders and textures it
+    /// refers to are all resolved against `fs` rather than the file system of the host.
+    pub fn try_parse_with_fs(
+        path: impl AsRef<Path>,
+        context: WildcardContext,
+        fs: &dyn FileSystem,
+    ) -> Result<ShaderPreset, ParsePresetError> {
+        let values = parse_preset(path, context, fs)?;
         Ok(resolve_values(values))
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::ShaderPreset;
-    use std::path::PathBuf;
+    use crate::{ShaderPreset, WildcardContext};
+    use librashader_common::vfs::{FileSystem, MemoryFileSystem};
+    use librashader_common::FilterMode;
+    use std::path::{Path, PathBuf};
 
     #[test]
     pub fn parse_preset() {
@@ -70,4 +87,36 @@ mod test {
         eprintln!("{basic:#?}");
         assert!(basic.is_ok());
     }
+
+    #[test]
+    pub fn parse_preset_from_memory() {
+        let mut fs = MemoryFileSystem::new();
+        fs.insert(
+            "presets/base.slangp",
+            "shaders = 1\nshader0 = ../shaders/pass.slang\ntextures = Mask\nMask = ../textures/mask.png\n",
+        );
+        fs.insert(
+            "presets/user.slangp",
+            "#reference \"base.slangp\"\nfilter_linear0 = true\n",
+        );
+        fs.insert("shaders/pass.slang", "#version 450");
+        fs.insert("textures/mask.png", []);
+
+        let preset =
+            ShaderPreset::try_parse_with_fs("presets/user.slangp", WildcardContext::new(), &fs)
+                .unwrap();
+        assert_eq!(preset.passes.len(), 1);
+        assert_eq!(
+            fs.canonicalize(&preset.passes[0].path).unwrap(),
+            Path::new("/shaders/pass.slang")
+        );
+        assert_eq!(preset.textures.len(), 1);
+        assert_eq!(preset.passes[0].meta.filter, FilterMode::Linear);
+
+        fs.remove("shaders/pass.slang");
+        assert!(
+            ShaderPreset::try_parse_with_fs("presets/user.slangp", WildcardContext::new(), &fs)
+                .is_err()
+        );
+    }
 }
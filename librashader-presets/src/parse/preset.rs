@@ -3,6 +3,7 @@ use crate::parse::value::Value;
 use crate::{
     ParameterMeta, PassConfig, PassMeta, Scale2D, Scaling, ShaderPreset, TextureConfig, TextureMeta,
 };
+use std::path::PathBuf;
 use vec_extract_if_polyfill::MakeExtractIf;
 
 pub fn resolve_values(mut values: Vec<Value>) -> ShaderPreset {
@@ -42,6 +43,17 @@ pub fn resolve_values(mut values: Vec<Value>) -> ShaderPreset {
             })
             .collect();
 
+    let references: Vec<PathBuf> =
+        MakeExtractIf::extract_if(&mut values, |f| matches!(*f, Value::Reference(_)))
+            .map(|value| {
+                if let Value::Reference(path) = value {
+                    path
+                } else {
+                    unreachable!("values should be all of type reference")
+                }
+            })
+            .collect();
+
     let mut shaders = Vec::new();
     let shader_count =
         remove_if(&mut values, |v| matches!(*v, Value::ShaderCount(_))).map_or(0, |value| {
@@ -192,5 +204,6 @@ pub fn resolve_values(mut values: Vec<Value>) -> ShaderPreset {
         passes: shaders,
         textures,
         parameters,
+        references,
     }
 }
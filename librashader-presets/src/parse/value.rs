@@ -11,9 +11,8 @@ use num_traits::cast::ToPrimitive;
 
 use crate::parse::token::do_lex;
 use librashader_common::map::{FastHashMap, ShortString};
+use librashader_common::vfs::FileSystem;
 use librashader_common::{FilterMode, WrapMode};
-use std::fs::File;
-use std::io::Read;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
@@ -39,6 +38,7 @@ pub enum Value {
     MipmapInput(i32, bool),
     Alias(i32, ShortString),
     Parameter(ShortString, f32),
+    Reference(PathBuf),
     Texture {
         name: ShortString,
         filter_mode: FilterMode,
@@ -157,6 +157,8 @@ fn load_child_reference_strings(
     root_references: Vec<PathBuf>,
     root_path: impl AsRef<Path>,
     context: &FastHashMap<String, String>,
+    fs: &dyn FileSystem,
+    reference_paths: &mut Vec<PathBuf>,
 ) -> Result<Vec<(PathBuf, String)>, ParsePresetError> {
     let root_path = root_path.as_ref();
 
@@ -172,27 +174,24 @@ fn load_child_reference_strings(
         // enter the current root
         reference_depth += 1;
         // canonicalize current root
-        apply_context(&mut reference_root, context);
-        let reference_root = reference_root
-            .canonicalize()
+        apply_context(&mut reference_root, context, fs);
+        let reference_root = fs
+            .canonicalize(&reference_root)
             .map_err(|e| ParsePresetError::IOError(reference_root.to_path_buf(), e))?;
 
         // resolve all referenced paths against root
-        // println!("Resolving {referenced_paths:?} against {reference_root:?}.");
+        log::trace!("resolving {referenced_paths:?} against {reference_root:?}");
 
         for path in referenced_paths {
             let mut path = reference_root.join(path.clone());
-            apply_context(&mut path, context);
+            apply_context(&mut path, context, fs);
 
-            let mut path = path
-                .canonicalize()
-                .map_err(|e| ParsePresetError::IOError(path.clone(), e))?;
-            // println!("Opening {:?}", path);
-            let mut reference_contents = String::new();
-            File::open(&path)
-                .map_err(|e| ParsePresetError::IOError(path.clone(), e))?
-                .read_to_string(&mut reference_contents)
+            let mut path = fs
+                .canonicalize(&path)
                 .map_err(|e| ParsePresetError::IOError(path.clone(), e))?;
+            log::debug!("loading preset reference {path:?}");
+            let reference_contents =
+                read_to_string(&path, fs).map_err(|e| ParsePresetError::IOError(path.clone(), e))?;
 
             let mut new_tokens = do_lex(&reference_contents)?;
             let new_references: Vec<PathBuf> =
@@ -202,6 +201,7 @@ fn load_child_reference_strings(
                 .map(|value| PathBuf::from(*value.value.fragment()))
                 .collect();
 
+            reference_paths.push(path.clone());
             path.pop();
             reference_strings.push_front((path.clone(), reference_contents));
             if !new_references.is_empty() {
@@ -213,27 +213,32 @@ fn load_child_reference_strings(
     Ok(reference_strings.into())
 }
 
+/// Read a file from the file system as UTF-8.
+fn read_to_string(path: &Path, fs: &dyn FileSystem) -> std::io::Result<String> {
+    String::from_utf8(fs.read(path)?)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
 pub(crate) fn parse_preset(
     path: impl AsRef<Path>,
     context: WildcardContext,
+    fs: &dyn FileSystem,
 ) -> Result<Vec<Value>, ParsePresetError> {
     let path = path.as_ref();
     let mut path = path.to_path_buf();
     let context = context.into_hashmap();
 
-    apply_context(&mut path, &context);
+    apply_context(&mut path, &context, fs);
 
-    let path = path
-        .canonicalize()
+    let path = fs
+        .canonicalize(&path)
         .map_err(|e| ParsePresetError::IOError(path.to_path_buf(), e))?;
 
-    let mut contents = String::new();
-    File::open(&path)
-        .and_then(|mut f| f.read_to_string(&mut contents))
-        .map_err(|e| ParsePresetError::IOError(path.to_path_buf(), e))?;
+    let contents =
+        read_to_string(&path, fs).map_err(|e| ParsePresetError::IOError(path.to_path_buf(), e))?;
 
     let tokens = super::token::do_lex(&contents)?;
-    parse_values(tokens, path, context)
+    parse_values(tokens, path, context, fs)
 }
 
 // prereq: root_path must be contextualized
@@ -241,12 +246,13 @@ pub fn parse_values(
     mut tokens: Vec<Token>,
     root_path: impl AsRef<Path>,
     context: FastHashMap<String, String>,
+    fs: &dyn FileSystem,
 ) -> Result<Vec<Value>, ParsePresetError> {
     let mut root_path = root_path.as_ref().to_path_buf();
     if root_path.is_relative() {
         return Err(ParsePresetError::RootPathWasNotAbsolute);
     }
-    if !root_path.is_dir() {
+    if !fs.is_dir(&root_path) {
         // we don't really care if this doesn't do anything because a non-canonical root path will
         // fail at a later stage during resolution.
         root_path.pop();
@@ -260,7 +266,9 @@ pub fn parse_values(
     // unfortunately we need to lex twice because there's no way to know the references ahead of time.
     // the returned references should have context applied
 
-    let child_strings = load_child_reference_strings(references, &root_path, &context)?;
+    let mut reference_paths = Vec::new();
+    let child_strings =
+        load_child_reference_strings(references, &root_path, &context, fs, &mut reference_paths)?;
     let mut all_tokens: Vec<(&Path, Vec<Token>)> = Vec::new();
 
     for (path, string) in child_strings.iter() {
@@ -325,8 +333,7 @@ pub fn parse_values(
 
             let mut relative_path = path.to_path_buf();
             relative_path.push(*token.value.fragment());
-            relative_path
-                .canonicalize()
+            fs.canonicalize(&relative_path)
                 .map_err(|e| ParsePresetError::IOError(relative_path.clone(), e))?;
             values.push(Value::Shader(index, relative_path))
         }
@@ -341,8 +348,7 @@ pub fn parse_values(
             let mut relative_path = path.to_path_buf();
             // Don't trim paths
             relative_path.push(*token.value.fragment());
-            relative_path
-                .canonicalize()
+            fs.canonicalize(&relative_path)
                 .map_err(|e| ParsePresetError::IOError(relative_path.clone(), e))?;
             textures.push((token.key, relative_path))
         }
@@ -573,8 +579,7 @@ pub fn parse_values(
             let mut relative_path = path.to_path_buf();
             // Don't trim paths.
             relative_path.push(*token.value.fragment());
-            relative_path
-                .canonicalize()
+            fs.canonicalize(&relative_path)
                 .map_err(|e| ParsePresetError::IOError(relative_path.clone(), e))?;
             undeclared_textures.push((token.key, relative_path));
         }
@@ -622,6 +627,8 @@ pub fn parse_values(
         })
     }
 
+    values.extend(reference_paths.into_iter().map(Value::Reference));
+
     // all tokens should be ok to process now.
     Ok(values)
 }
@@ -630,13 +637,14 @@ pub fn parse_values(
 mod test {
     use crate::parse::value::parse_preset;
     use crate::WildcardContext;
+    use librashader_common::vfs::StdFileSystem;
     use std::path::PathBuf;
 
     #[test]
     pub fn parse_basic() {
         let root =
             PathBuf::from("../test/shaders_slang/bezel/Mega_Bezel/Presets/Base_CRT_Presets/MBZ__3__STD__MEGATRON-NTSC.slangp");
-        let basic = parse_preset(root, WildcardContext::new());
+        let basic = parse_preset(root, WildcardContext::new(), &StdFileSystem);
         eprintln!("{basic:?}");
         assert!(basic.is_ok());
     }
@@ -214,4 +214,8 @@ pub struct ShaderPreset {
 
     /// Preset information for each user parameter.
     pub parameters: Vec<ParameterMeta>,
+
+    /// The paths of the presets included through `#reference`, in the order they were resolved.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub references: Vec<PathBuf>,
 }
@@ -0,0 +1,105 @@
+//! Python exception types for librashader errors.
+use librashader::presets::{LoadableResource, TextureMeta};
+use pyo3::create_exception;
+use pyo3::exceptions::{PyException, PyIndexError, PyValueError};
+use pyo3::PyErr;
+
+create_exception!(
+    librashader,
+    LibrashaderError,
+    PyException,
+    "Base class for errors raised by librashader."
+);
+create_exception!(
+    librashader,
+    PresetError,
+    LibrashaderError,
+    "An error occurred when parsing a shader preset."
+);
+create_exception!(
+    librashader,
+    PreprocessError,
+    LibrashaderError,
+    "An error occurred when loading or preprocessing a shader source."
+);
+create_exception!(
+    librashader,
+    ShaderCompileError,
+    LibrashaderError,
+    "An error occurred when compiling or transpiling a shader source."
+);
+create_exception!(
+    librashader,
+    ShaderReflectError,
+    LibrashaderError,
+    "An error occurred when validating and reflecting a shader source."
+);
+create_exception!(
+    librashader,
+    PackError,
+    LibrashaderError,
+    "An error occurred when loading, reading or writing a shader preset pack."
+);
+
+/// The error type for the librashader Python bindings.
+#[non_exhaustive]
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// An error occurred parsing the preset.
+    #[error(transparent)]
+    Preset(#[from] librashader::presets::ParsePresetError),
+
+    /// An error occurred preprocessing the shader source.
+    #[error(transparent)]
+    Preprocess(#[from] librashader::preprocess::PreprocessError),
+
+    /// An error occurred compiling the shader source.
+    #[error(transparent)]
+    ShaderCompile(#[from] librashader::reflect::ShaderCompileError),
+
+    /// An error occurred when validating and reflecting the shader.
+    #[error(transparent)]
+    ShaderReflect(#[from] librashader::reflect::ShaderReflectError),
+
+    /// An error occurred loading a lookup texture of the preset.
+    #[error("there was an error loading a texture of the preset: {0}")]
+    TextureLoad(#[source] <TextureMeta as LoadableResource>::Error),
+
+    /// An error occurred reading a shader preset pack.
+    #[error("there was an error reading the shader preset pack: {0}")]
+    PackRead(#[from] rmp_serde::decode::Error),
+
+    /// An error occurred writing a shader preset pack.
+    #[error("there was an error writing the shader preset pack: {0}")]
+    PackWrite(#[from] rmp_serde::encode::Error),
+
+    /// An error occurred serializing a value to JSON.
+    #[error("there was an error serializing to JSON: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// The index of a pass was out of range for the preset.
+    #[error("the preset has no pass at index {0}")]
+    InvalidPassIndex(usize),
+
+    /// The version requested for a shader target is not supported.
+    #[error(transparent)]
+    UnsupportedVersion(#[from] librashader::reflect::UnsupportedVersionError),
+}
+
+impl From<Error> for PyErr {
+    fn from(value: Error) -> Self {
+        let message = value.to_string();
+        match value {
+            Error::Preset(_) => PresetError::new_err(message),
+            Error::Preprocess(_) => PreprocessError::new_err(message),
+            Error::ShaderCompile(_) => ShaderCompileError::new_err(message),
+            Error::ShaderReflect(_) => ShaderReflectError::new_err(message),
+            Error::TextureLoad(_) | Error::PackRead(_) | Error::PackWrite(_) => {
+                PackError::new_err(message)
+            }
+            Error::Json(_) => LibrashaderError::new_err(message),
+            Error::InvalidPassIndex(_) => PyIndexError::new_err(message),
+            Error::UnsupportedVersion(_) => PyValueError::new_err(message),
+        }
+    }
+}
@@ -0,0 +1,94 @@
+//! Python bindings for librashader.
+//!
+//! This crate builds the `librashader` Python extension module, which exposes the CPU-side parts
+//! of librashader for tooling that works with shader presets: parsing presets with wildcard
+//! contexts, preprocessing shader sources, loading shader preset packs, and transpiling shaders
+//! to GLSL, HLSL, MSL, WGSL and SPIR-V. No graphics device is required.
+//!
+//! The module is built with [maturin](https://www.maturin.rs/), which enables the
+//! `extension-module` feature.
+//!
+//! ```sh
+//! maturin build --release -m librashader-python/Cargo.toml
+//! ```
+//!
+//! The smoke tests in `librashader-python/tests` run against the module installed into the
+//! active environment.
+//!
+//! ```sh
+//! cd librashader-python
+//! maturin develop --extras test
+//! pytest
+//! ```
+//!
+//! ```python
+//! import librashader
+//!
+//! context = librashader.WildcardContext()
+//! context.set_runtime(librashader.VideoDriver.VULKAN)
+//! preset = librashader.ShaderPreset.parse("crt/crt-royale.slangp", context)
+//!
+//! for pass_ in preset.passes:
+//!     source = librashader.ShaderSource.load(pass_.path)
+//!     output = source.transpile(librashader.ShaderTarget.WGSL)
+//!     print(output.fragment)
+//! ```
+//!
+//! Functions that parse, preprocess or compile shaders release the GIL, so they can be run
+//! in parallel from a thread pool.
+#![forbid(missing_docs)]
+
+mod error;
+mod pack;
+mod preprocess;
+mod presets;
+mod reflect;
+
+use pyo3::prelude::*;
+
+/// The `librashader` Python module.
+#[pymodule]
+#[pyo3(name = "librashader")]
+fn librashader_python(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = m.py();
+    m.add("LibrashaderError", py.get_type::<error::LibrashaderError>())?;
+    m.add("PresetError", py.get_type::<error::PresetError>())?;
+    m.add("PreprocessError", py.get_type::<error::PreprocessError>())?;
+    m.add(
+        "ShaderCompileError",
+        py.get_type::<error::ShaderCompileError>(),
+    )?;
+    m.add(
+        "ShaderReflectError",
+        py.get_type::<error::ShaderReflectError>(),
+    )?;
+    m.add("PackError", py.get_type::<error::PackError>())?;
+
+    m.add_class::<presets::PyFilterMode>()?;
+    m.add_class::<presets::PyWrapMode>()?;
+    m.add_class::<presets::PyScaleType>()?;
+    m.add_class::<presets::PyVideoDriver>()?;
+    m.add_class::<presets::PyOrientation>()?;
+    m.add_class::<presets::PyWildcardContext>()?;
+    m.add_class::<presets::PyScaling>()?;
+    m.add_class::<presets::PyPassMeta>()?;
+    m.add_class::<presets::PyPassConfig>()?;
+    m.add_class::<presets::PyTextureMeta>()?;
+    m.add_class::<presets::PyTextureConfig>()?;
+    m.add_class::<presets::PyParameterMeta>()?;
+    m.add_class::<presets::PyShaderPreset>()?;
+
+    m.add_class::<preprocess::PyShaderParameter>()?;
+    m.add_class::<preprocess::PyShaderSource>()?;
+
+    m.add_class::<reflect::PyShaderTarget>()?;
+    m.add_class::<reflect::PyTranspileOptions>()?;
+    m.add_class::<reflect::PyShaderOutput>()?;
+
+    m.add_class::<pack::PyPassResource>()?;
+    m.add_class::<pack::PyTextureResource>()?;
+    m.add_class::<pack::PyShaderPresetPack>()?;
+
+    m.add("__version__", env!("CARGO_PKG_VERSION"))?;
+    Ok(())
+}
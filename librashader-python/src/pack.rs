@@ -0,0 +1,111 @@
+//! Shader preset packs, with every resource of a preset loaded into memory.
+use crate::error::Error;
+use crate::preprocess::PyShaderSource;
+use crate::presets::{PyParameterMeta, PyPassMeta, PyShaderPreset, PyTextureMeta};
+use librashader::presets::{LoadableResource, PassMeta, ShaderPresetPack, TextureMeta};
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+/// A shader pass of a shader preset pack.
+#[pyclass(name = "PassResource", module = "librashader", frozen, get_all)]
+pub struct PyPassResource {
+    /// Meta information about the pass.
+    meta: PyPassMeta,
+    /// The preprocessed source of the pass.
+    source: PyShaderSource,
+}
+
+/// A lookup texture of a shader preset pack.
+#[pyclass(name = "TextureResource", module = "librashader", frozen, get_all)]
+pub struct PyTextureResource {
+    /// Meta information about the texture.
+    meta: PyTextureMeta,
+    /// The width of the image in pixels.
+    width: u32,
+    /// The height of the image in pixels.
+    height: u32,
+    /// The RGBA8 pixels of the image.
+    data: Py<PyBytes>,
+}
+
+/// A shader preset with the sources of its passes and its lookup textures loaded into memory.
+#[pyclass(name = "ShaderPresetPack", module = "librashader", frozen)]
+pub struct PyShaderPresetPack(ShaderPresetPack);
+
+#[pymethods]
+impl PyShaderPresetPack {
+    /// Load the passes and lookup textures of a shader preset.
+    ///
+    /// The passes are preprocessed and the textures are decoded in parallel.
+    #[staticmethod]
+    fn from_preset(py: Python<'_>, preset: &PyShaderPreset) -> Result<Self, Error> {
+        let preset = preset.0.clone();
+        let pack = py.allow_threads(|| {
+            ShaderPresetPack::load_from_preset_with(
+                preset,
+                |_, path| PassMeta::load(path).map_err(Error::from),
+                |_, path| TextureMeta::load(path).map_err(Error::TextureLoad),
+            )
+        })?;
+        Ok(PyShaderPresetPack(pack))
+    }
+
+    /// Load a shader preset pack serialized as MessagePack, such as one written by `to_msgpack`
+    /// or `librashader-cli pack`.
+    #[staticmethod]
+    fn from_msgpack(py: Python<'_>, data: &[u8]) -> Result<Self, Error> {
+        let pack = py.allow_threads(|| rmp_serde::from_slice(data))?;
+        Ok(PyShaderPresetPack(pack))
+    }
+
+    /// Serialize the shader preset pack as MessagePack.
+    fn to_msgpack<'py>(&self, py: Python<'py>) -> Result<Bound<'py, PyBytes>, Error> {
+        let bytes = py.allow_threads(|| rmp_serde::to_vec(&self.0))?;
+        Ok(PyBytes::new(py, &bytes))
+    }
+
+    /// Serialize the shader preset pack as JSON, in the same format as `librashader-cli pack`.
+    fn to_json(&self, py: Python<'_>) -> Result<String, Error> {
+        Ok(py.allow_threads(|| serde_json::to_string(&self.0))?)
+    }
+
+    /// The number of shader passes enabled in the preset.
+    #[getter]
+    fn pass_count(&self) -> i32 {
+        self.0.pass_count
+    }
+
+    /// The shader passes of the preset.
+    #[getter]
+    fn passes(&self) -> Vec<PyPassResource> {
+        self.0
+            .passes
+            .iter()
+            .map(|pass| PyPassResource {
+                meta: (&pass.meta).into(),
+                source: PyShaderSource(pass.data.clone()),
+            })
+            .collect()
+    }
+
+    /// The lookup textures of the preset.
+    #[getter]
+    fn textures(&self, py: Python<'_>) -> Vec<PyTextureResource> {
+        self.0
+            .textures
+            .iter()
+            .map(|texture| PyTextureResource {
+                meta: (&texture.meta).into(),
+                width: texture.data.width(),
+                height: texture.data.height(),
+                data: PyBytes::new(py, texture.data.as_ref()).unbind(),
+            })
+            .collect()
+    }
+
+    /// The values of the shader parameters set by the preset.
+    #[getter]
+    fn parameters(&self) -> Vec<PyParameterMeta> {
+        self.0.parameters.iter().map(Into::into).collect()
+    }
+}
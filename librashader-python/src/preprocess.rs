@@ -0,0 +1,103 @@
+//! Loading and preprocessing of shader sources.
+use crate::error::Error;
+use crate::reflect::{PyShaderOutput, PyShaderTarget, PyTranspileOptions};
+use librashader::preprocess::ShaderSource;
+use librashader::reflect::transpile::transpile;
+use pyo3::prelude::*;
+use std::path::PathBuf;
+
+/// A user tweakable parameter of a shader as declared in its source.
+#[pyclass(name = "ShaderParameter", module = "librashader", frozen, get_all)]
+#[derive(Debug, Clone)]
+pub struct PyShaderParameter {
+    /// The name of the parameter.
+    id: String,
+    /// The description of the parameter.
+    description: String,
+    /// The initial value the parameter is set to.
+    initial: f32,
+    /// The minimum value that the parameter can be set to.
+    minimum: f32,
+    /// The maximum value that the parameter can be set to.
+    maximum: f32,
+    /// The step by which this parameter can be incremented or decremented.
+    step: f32,
+}
+
+impl From<&librashader::preprocess::ShaderParameter> for PyShaderParameter {
+    fn from(value: &librashader::preprocess::ShaderParameter) -> Self {
+        PyShaderParameter {
+            id: value.id.to_string(),
+            description: value.description.clone(),
+            initial: value.initial,
+            minimum: value.minimum,
+            maximum: value.maximum,
+            step: value.step,
+        }
+    }
+}
+
+/// The preprocessed source of a single shader pass.
+#[pyclass(name = "ShaderSource", module = "librashader", frozen)]
+#[derive(Debug, Clone)]
+pub struct PyShaderSource(pub(crate) ShaderSource);
+
+#[pymethods]
+impl PyShaderSource {
+    /// Load and preprocess the `.slang` shader source at the given path.
+    ///
+    /// `#include` directives are resolved relative to the path, and `#pragma` directives
+    /// are parsed.
+    #[staticmethod]
+    fn load(py: Python<'_>, path: PathBuf) -> Result<Self, Error> {
+        let source = py.allow_threads(|| ShaderSource::load(path))?;
+        Ok(PyShaderSource(source))
+    }
+
+    /// The source of the vertex shader.
+    #[getter]
+    fn vertex(&self) -> &str {
+        &self.0.vertex
+    }
+
+    /// The source of the fragment shader.
+    #[getter]
+    fn fragment(&self) -> &str {
+        &self.0.fragment
+    }
+
+    /// The alias of the shader if available.
+    #[getter]
+    fn name(&self) -> Option<&str> {
+        self.0.name.as_deref()
+    }
+
+    /// The parameters declared by the shader, in the order they were declared.
+    #[getter]
+    fn parameters(&self) -> Vec<PyShaderParameter> {
+        self.0.parameters.values().map(Into::into).collect()
+    }
+
+    /// The name of the image format of the output framebuffer, such as `R8G8B8A8Unorm`.
+    #[getter]
+    fn format(&self) -> String {
+        format!("{:?}", self.0.format)
+    }
+
+    /// Compile the shader source and transpile it to the given target.
+    ///
+    /// Text targets return the vertex and fragment shaders as `str`. SPIR-V returns the
+    /// modules as `bytes` in native byte order. If `options` is not given, the default
+    /// `TranspileOptions` are used.
+    #[pyo3(signature = (target, options = None))]
+    fn transpile(
+        &self,
+        py: Python<'_>,
+        target: PyShaderTarget,
+        options: Option<PyRef<'_, PyTranspileOptions>>,
+    ) -> Result<PyShaderOutput, Error> {
+        let options = options.map(|options| options.0.clone()).unwrap_or_default();
+        let output = py.allow_threads(|| transpile::<Error>(&self.0, target.into(), &options))?;
+        Ok(PyShaderOutput::new(py, output))
+    }
+}
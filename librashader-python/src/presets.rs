@@ -0,0 +1,463 @@
+//! Shader preset parsing and wildcard contexts.
+use crate::error::Error;
+use librashader::preprocess::ShaderSource;
+use librashader::presets::context::{
+    ContextItem, Orientation, PresetExtension, Rotation, ShaderExtension, VideoDriver,
+};
+use librashader::presets::{ScaleFactor, ScaleType};
+use librashader::reflect::cross::SpirvCross;
+use librashader::reflect::schema::PassReflection;
+use librashader::reflect::semantics::ShaderSemantics;
+use librashader::reflect::targets::SPIRV;
+use librashader::reflect::{FromCompilation, ReflectShader, SpirvCompilation};
+use librashader::{FilterMode, WrapMode};
+use pyo3::prelude::*;
+use std::path::PathBuf;
+
+/// The filtering mode for a texture sampler.
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+#[pyclass(name = "FilterMode", module = "librashader", eq, eq_int, frozen)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PyFilterMode {
+    /// Linear filtering.
+    LINEAR,
+    /// Nearest-neighbour (point) filtering.
+    NEAREST,
+}
+
+impl From<FilterMode> for PyFilterMode {
+    fn from(value: FilterMode) -> Self {
+        match value {
+            FilterMode::Linear => PyFilterMode::LINEAR,
+            FilterMode::Nearest => PyFilterMode::NEAREST,
+        }
+    }
+}
+
+/// The wrapping (address) mode for a texture sampler.
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+#[pyclass(name = "WrapMode", module = "librashader", eq, eq_int, frozen)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PyWrapMode {
+    /// Clamp texture to border.
+    CLAMP_TO_BORDER,
+    /// Clamp texture to edge.
+    CLAMP_TO_EDGE,
+    /// Repeat addressing mode.
+    REPEAT,
+    /// Mirrored repeat addressing mode.
+    MIRRORED_REPEAT,
+}
+
+impl From<WrapMode> for PyWrapMode {
+    fn from(value: WrapMode) -> Self {
+        match value {
+            WrapMode::ClampToBorder => PyWrapMode::CLAMP_TO_BORDER,
+            WrapMode::ClampToEdge => PyWrapMode::CLAMP_TO_EDGE,
+            WrapMode::Repeat => PyWrapMode::REPEAT,
+            WrapMode::MirroredRepeat => PyWrapMode::MIRRORED_REPEAT,
+        }
+    }
+}
+
+/// The scaling type for a shader pass.
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+#[pyclass(name = "ScaleType", module = "librashader", eq, eq_int, frozen)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PyScaleType {
+    /// Scale by the size of the input quad.
+    INPUT,
+    /// Scale the framebuffer in absolute units.
+    ABSOLUTE,
+    /// Scale by the size of the viewport.
+    VIEWPORT,
+    /// Scale by the size of the original input quad.
+    ORIGINAL,
+}
+
+impl From<ScaleType> for PyScaleType {
+    fn from(value: ScaleType) -> Self {
+        match value {
+            ScaleType::Input => PyScaleType::INPUT,
+            ScaleType::Absolute => PyScaleType::ABSOLUTE,
+            ScaleType::Viewport => PyScaleType::VIEWPORT,
+            ScaleType::Original => PyScaleType::ORIGINAL,
+        }
+    }
+}
+
+/// A graphics runtime (video driver) for use in wildcard contexts.
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+#[pyclass(name = "VideoDriver", module = "librashader", eq, eq_int, frozen)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PyVideoDriver {
+    /// No runtime.
+    NONE,
+    /// OpenGL 3.3+
+    GLCORE,
+    /// Vulkan
+    VULKAN,
+    /// Direct3D 11
+    D3D11,
+    /// Direct3D 12
+    D3D12,
+    /// Metal
+    METAL,
+    /// Direct3D 9
+    D3D9_HLSL,
+}
+
+impl From<PyVideoDriver> for VideoDriver {
+    fn from(value: PyVideoDriver) -> Self {
+        match value {
+            PyVideoDriver::NONE => VideoDriver::None,
+            PyVideoDriver::GLCORE => VideoDriver::GlCore,
+            PyVideoDriver::VULKAN => VideoDriver::Vulkan,
+            PyVideoDriver::D3D11 => VideoDriver::Direct3D11,
+            PyVideoDriver::D3D12 => VideoDriver::Direct3D12,
+            PyVideoDriver::METAL => VideoDriver::Metal,
+            PyVideoDriver::D3D9_HLSL => VideoDriver::Direct3D9Hlsl,
+        }
+    }
+}
+
+/// An aspect ratio orientation for use in wildcard contexts.
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+#[pyclass(name = "Orientation", module = "librashader", eq, eq_int, frozen)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PyOrientation {
+    /// Vertical orientation.
+    VERTICAL,
+    /// Horizontal orientation.
+    HORIZONTAL,
+}
+
+impl From<PyOrientation> for Orientation {
+    fn from(value: PyOrientation) -> Self {
+        match value {
+            PyOrientation::VERTICAL => Orientation::Vertical,
+            PyOrientation::HORIZONTAL => Orientation::Horizontal,
+        }
+    }
+}
+
+/// The variables used to replace wildcards in the paths of a shader preset.
+///
+/// `PRESET_DIR` and `PRESET` are inferred from the path of the preset when it is parsed.
+/// Every variable, including inferred variables, can be overridden with `set_param`.
+/// See <https://github.com/libretro/RetroArch/pull/15023> for the expected string values.
+#[pyclass(name = "WildcardContext", module = "librashader")]
+#[derive(Debug, Clone)]
+pub struct PyWildcardContext(pub(crate) librashader::presets::WildcardContext);
+
+#[pymethods]
+impl PyWildcardContext {
+    #[new]
+    fn new() -> Self {
+        PyWildcardContext(librashader::presets::WildcardContext::new())
+    }
+
+    /// Set the core name (`CORE`) variable.
+    fn set_core_name(&mut self, name: String) {
+        self.0.append_item(ContextItem::CoreName(name));
+    }
+
+    /// Set the content directory (`CONTENT-DIR`) variable.
+    fn set_content_dir(&mut self, name: String) {
+        self.0.append_item(ContextItem::ContentDirectory(name));
+    }
+
+    /// Set the game name (`GAME`) variable.
+    fn set_game_name(&mut self, name: String) {
+        self.0.append_item(ContextItem::GameName(name));
+    }
+
+    /// Set a custom string variable.
+    ///
+    /// If a path of the preset contains this variable, it will be replaced with `value`.
+    fn set_param(&mut self, name: String, value: String) {
+        self.0.append_item(ContextItem::ExternContext(name, value));
+    }
+
+    /// Set the graphics runtime (`VID-DRV`) variable.
+    ///
+    /// This also sets `VID-DRV-SHADER-EXT` and `VID-DRV-PRESET-EXT` to `slang` and `slangp`.
+    fn set_runtime(&mut self, runtime: PyVideoDriver) {
+        self.0.append_item(ContextItem::VideoDriverPresetExtension(
+            PresetExtension::Slangp,
+        ));
+        self.0.append_item(ContextItem::VideoDriverShaderExtension(
+            ShaderExtension::Slang,
+        ));
+        self.0.append_item(ContextItem::VideoDriver(runtime.into()));
+    }
+
+    /// Set the core requested rotation (`CORE-REQ-ROT`) variable, in quarter rotations.
+    fn set_core_rotation(&mut self, value: u32) {
+        self.0
+            .append_item(ContextItem::CoreRequestedRotation(Rotation::from(value)));
+    }
+
+    /// Set the user rotation (`VID-USER-ROT`) variable, in quarter rotations.
+    fn set_user_rotation(&mut self, value: u32) {
+        self.0
+            .append_item(ContextItem::UserRotation(Rotation::from(value)));
+    }
+
+    /// Set the screen orientation (`SCREEN-ORIENT`) variable, in quarter rotations.
+    fn set_screen_orientation(&mut self, value: u32) {
+        self.0
+            .append_item(ContextItem::ScreenOrientation(Rotation::from(value)));
+    }
+
+    /// Set whether or not to allow rotation (`VID-ALLOW-CORE-ROT`).
+    fn set_allow_rotation(&mut self, value: bool) {
+        self.0.append_item(ContextItem::AllowCoreRotation(value));
+    }
+
+    /// Set the view aspect orientation (`VIEW-ASPECT-ORIENT`) variable.
+    fn set_view_aspect_orientation(&mut self, value: PyOrientation) {
+        self.0
+            .append_item(ContextItem::ViewAspectOrientation(value.into()));
+    }
+
+    /// Set the core aspect orientation (`CORE-ASPECT-ORIENT`) variable.
+    fn set_core_aspect_orientation(&mut self, value: PyOrientation) {
+        self.0
+            .append_item(ContextItem::CoreAspectOrientation(value.into()));
+    }
+}
+
+/// The scaling of the output framebuffer of a shader pass along one axis.
+#[pyclass(name = "Scaling", module = "librashader", frozen, get_all)]
+#[derive(Debug, Clone)]
+pub struct PyScaling {
+    /// The method to scale the framebuffer with.
+    scale_type: PyScaleType,
+    /// The factor to scale by, in pixels if `scale_type` is `ABSOLUTE`.
+    factor: f32,
+}
+
+impl From<&librashader::presets::Scaling> for PyScaling {
+    fn from(value: &librashader::presets::Scaling) -> Self {
+        PyScaling {
+            scale_type: value.scale_type.into(),
+            factor: match value.factor {
+                ScaleFactor::Float(factor) => factor,
+                ScaleFactor::Absolute(factor) => factor as f32,
+            },
+        }
+    }
+}
+
+/// Meta information about a shader pass.
+#[pyclass(name = "PassMeta", module = "librashader", frozen, get_all)]
+#[derive(Debug, Clone)]
+pub struct PyPassMeta {
+    /// The index of the shader pass relative to its parent preset.
+    id: i32,
+    /// The alias of the shader pass if available.
+    alias: Option<String>,
+    /// The filtering mode that this shader pass should expect.
+    filter: PyFilterMode,
+    /// The texture addressing (wrap) mode that this shader pass expects.
+    wrap_mode: PyWrapMode,
+    /// The number to which to wrap the frame count before passing it to the uniforms.
+    frame_count_mod: u32,
+    /// Whether or not this shader pass expects an SRGB framebuffer output.
+    srgb_framebuffer: bool,
+    /// Whether or not this shader pass expects a float framebuffer output.
+    float_framebuffer: bool,
+    /// Whether or not to generate mipmaps for the input texture before passing to the shader.
+    mipmap_input: bool,
+    /// Whether or not the scaling of the output framebuffer was specified.
+    scale_valid: bool,
+    /// The scaling of the output framebuffer along the X axis.
+    scale_x: PyScaling,
+    /// The scaling of the output framebuffer along the Y axis.
+    scale_y: PyScaling,
+}
+
+impl From<&librashader::presets::PassMeta> for PyPassMeta {
+    fn from(value: &librashader::presets::PassMeta) -> Self {
+        PyPassMeta {
+            id: value.id,
+            alias: value.alias.as_ref().map(|alias| alias.to_string()),
+            filter: value.filter.into(),
+            wrap_mode: value.wrap_mode.into(),
+            frame_count_mod: value.frame_count_mod,
+            srgb_framebuffer: value.srgb_framebuffer,
+            float_framebuffer: value.float_framebuffer,
+            mipmap_input: value.mipmap_input,
+            scale_valid: value.scaling.valid,
+            scale_x: (&value.scaling.x).into(),
+            scale_y: (&value.scaling.y).into(),
+        }
+    }
+}
+
+/// The configuration for a single shader pass.
+#[pyclass(name = "PassConfig", module = "librashader", frozen, get_all)]
+#[derive(Debug, Clone)]
+pub struct PyPassConfig {
+    /// The path to the shader source of the pass.
+    path: PathBuf,
+    /// Meta information about the pass.
+    meta: PyPassMeta,
+}
+
+/// Configuration options for a lookup texture.
+#[pyclass(name = "TextureMeta", module = "librashader", frozen, get_all)]
+#[derive(Debug, Clone)]
+pub struct PyTextureMeta {
+    /// The name of the texture.
+    name: String,
+    /// The wrap (addressing) mode to use when sampling the texture.
+    wrap_mode: PyWrapMode,
+    /// The filter mode to use when sampling the texture.
+    filter_mode: PyFilterMode,
+    /// Whether to generate mipmaps for this texture.
+    mipmap: bool,
+}
+
+impl From<&librashader::presets::TextureMeta> for PyTextureMeta {
+    fn from(value: &librashader::presets::TextureMeta) -> Self {
+        PyTextureMeta {
+            name: value.name.to_string(),
+            wrap_mode: value.wrap_mode.into(),
+            filter_mode: value.filter_mode.into(),
+            mipmap: value.mipmap,
+        }
+    }
+}
+
+/// The configuration for a lookup texture.
+#[pyclass(name = "TextureConfig", module = "librashader", frozen, get_all)]
+#[derive(Debug, Clone)]
+pub struct PyTextureConfig {
+    /// The path to the image of the texture.
+    path: PathBuf,
+    /// Meta information about the texture.
+    meta: PyTextureMeta,
+}
+
+/// The value of a shader parameter set by a preset.
+#[pyclass(name = "ParameterMeta", module = "librashader", frozen, get_all)]
+#[derive(Debug, Clone)]
+pub struct PyParameterMeta {
+    /// The name of the parameter.
+    name: String,
+    /// The value it is set to in the preset.
+    value: f32,
+}
+
+impl From<&librashader::presets::ParameterMeta> for PyParameterMeta {
+    fn from(value: &librashader::presets::ParameterMeta) -> Self {
+        PyParameterMeta {
+            name: value.name.to_string(),
+            value: value.value,
+        }
+    }
+}
+
+/// A parsed shader preset, including the paths of its passes and lookup textures.
+#[pyclass(name = "ShaderPreset", module = "librashader", frozen)]
+#[derive(Debug, Clone)]
+pub struct PyShaderPreset(pub(crate) librashader::presets::ShaderPreset);
+
+#[pymethods]
+impl PyShaderPreset {
+    /// Parse the shader preset at the given path.
+    ///
+    /// If `context` is given, wildcards in the paths of the preset are replaced with the
+    /// variables of the context.
+    #[staticmethod]
+    #[pyo3(signature = (path, context = None))]
+    fn parse(
+        py: Python<'_>,
+        path: PathBuf,
+        context: Option<PyWildcardContext>,
+    ) -> Result<Self, Error> {
+        let preset = py.allow_threads(|| match context {
+            None => librashader::presets::ShaderPreset::try_parse(path),
+            Some(context) => {
+                librashader::presets::ShaderPreset::try_parse_with_context(path, context.0)
+            }
+        })?;
+        Ok(PyShaderPreset(preset))
+    }
+
+    /// The number of shader passes enabled in the preset.
+    #[getter]
+    fn pass_count(&self) -> i32 {
+        self.0.pass_count
+    }
+
+    /// The configuration of each shader pass.
+    #[getter]
+    fn passes(&self) -> Vec<PyPassConfig> {
+        self.0
+            .passes
+            .iter()
+            .map(|pass| PyPassConfig {
+                path: pass.path.clone(),
+                meta: (&pass.meta).into(),
+            })
+            .collect()
+    }
+
+    /// The configuration of each lookup texture.
+    #[getter]
+    fn textures(&self) -> Vec<PyTextureConfig> {
+        self.0
+            .textures
+            .iter()
+            .map(|texture| PyTextureConfig {
+                path: texture.path.clone(),
+                meta: (&texture.meta).into(),
+            })
+            .collect()
+    }
+
+    /// The values of the shader parameters set by the preset.
+    #[getter]
+    fn parameters(&self) -> Vec<PyParameterMeta> {
+        self.0.parameters.iter().map(Into::into).collect()
+    }
+
+    /// The paths of the presets included through `#reference`, in the order they were resolved.
+    #[getter]
+    fn references(&self) -> Vec<PathBuf> {
+        self.0.references.clone()
+    }
+
+    /// Reflect a pass of the preset.
+    ///
+    /// The reflection is returned as JSON, following the versioned pass reflection schema in
+    /// `librashader-reflect/schema/pass-reflection.schema.json`.
+    fn reflect_pass(&self, py: Python<'_>, index: usize) -> Result<String, Error> {
+        let preset = &self.0;
+        py.allow_threads(|| {
+            let Some(pass) = preset.passes.get(index) else {
+                return Err(Error::InvalidPassIndex(index));
+            };
+
+            let source = ShaderSource::load(&pass.path)?;
+            let compilation = SpirvCompilation::try_from(&source)?;
+            let semantics = ShaderSemantics::create_pass_semantics::<Error>(preset, index)?;
+
+            let mut compilation =
+                <SPIRV as FromCompilation<SpirvCompilation, SpirvCross>>::from_compilation(
+                    compilation,
+                )?;
+            let reflection = compilation.reflect(index, &semantics)?;
+            let reflection = PassReflection::new(index, &reflection);
+            Ok(serde_json::to_string(&reflection)?)
+        })
+    }
+
+    /// Serialize the preset as JSON, in the same format as `librashader-cli parse`.
+    fn to_json(&self) -> Result<String, Error> {
+        Ok(serde_json::to_string(&self.0)?)
+    }
+}
@@ -0,0 +1,111 @@
+//! Shader transpilation to the targets of the shader compiler.
+use crate::error::Error;
+use librashader::reflect::cross::{
+    glsl_version_from_number, hlsl_shader_model_from_number, MslVersion,
+};
+use librashader::reflect::naga::NagaLoweringOptions;
+use librashader::reflect::transpile::{TranspileOptions, TranspileOutput, TranspileTarget};
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyString};
+
+/// A shader language a shader source can be transpiled to.
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+#[pyclass(name = "ShaderTarget", module = "librashader", eq, eq_int, frozen)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PyShaderTarget {
+    /// GLSL, for OpenGL.
+    GLSL,
+    /// HLSL, for Direct3D.
+    HLSL,
+    /// Metal Shading Language.
+    MSL,
+    /// WGSL, for WebGPU.
+    WGSL,
+    /// SPIR-V, for Vulkan.
+    SPIRV,
+}
+
+impl From<PyShaderTarget> for TranspileTarget {
+    fn from(value: PyShaderTarget) -> Self {
+        match value {
+            PyShaderTarget::GLSL => TranspileTarget::Glsl,
+            PyShaderTarget::HLSL => TranspileTarget::Hlsl,
+            PyShaderTarget::MSL => TranspileTarget::Msl,
+            PyShaderTarget::WGSL => TranspileTarget::Wgsl,
+            PyShaderTarget::SPIRV => TranspileTarget::Spirv,
+        }
+    }
+}
+
+/// Options for shader transpilation.
+///
+/// Versions that are not given use the same defaults as the filter chains: GLSL 330,
+/// Shader Model 5.0 and MSL 1.2.
+#[pyclass(name = "TranspileOptions", module = "librashader", frozen)]
+#[derive(Debug, Clone, Default)]
+pub struct PyTranspileOptions(pub(crate) TranspileOptions);
+
+#[pymethods]
+impl PyTranspileOptions {
+    /// Create transpilation options.
+    ///
+    /// `glsl_version` is a desktop GLSL version such as `330` or `460`, `hlsl_shader_model`
+    /// is the shader model multiplied by 10 such as `50` for Shader Model 5.0, and
+    /// `msl_version` is a `(major, minor, patch)` tuple.
+    #[new]
+    #[pyo3(signature = (
+        *,
+        glsl_version = 330,
+        hlsl_shader_model = 50,
+        msl_version = (1, 2, 0),
+        wgsl_write_pcb_as_ubo = true,
+        wgsl_sampler_bind_group = 1,
+    ))]
+    fn new(
+        glsl_version: u16,
+        hlsl_shader_model: u16,
+        msl_version: (u32, u32, u32),
+        wgsl_write_pcb_as_ubo: bool,
+        wgsl_sampler_bind_group: u32,
+    ) -> Result<Self, Error> {
+        let (major, minor, patch) = msl_version;
+        Ok(PyTranspileOptions(TranspileOptions {
+            glsl_version: glsl_version_from_number(glsl_version)?,
+            hlsl_shader_model: hlsl_shader_model_from_number(hlsl_shader_model)?,
+            msl_version: MslVersion::new(major, minor, patch),
+            wgsl: NagaLoweringOptions {
+                write_pcb_as_ubo: wgsl_write_pcb_as_ubo,
+                sampler_bind_group: wgsl_sampler_bind_group,
+            },
+        }))
+    }
+}
+
+/// The vertex and fragment output of a transpiled shader.
+#[pyclass(name = "ShaderOutput", module = "librashader", frozen, get_all)]
+pub struct PyShaderOutput {
+    /// The vertex shader, as `bytes` for SPIR-V and `str` otherwise.
+    vertex: PyObject,
+    /// The fragment shader, as `bytes` for SPIR-V and `str` otherwise.
+    fragment: PyObject,
+}
+
+impl PyShaderOutput {
+    pub(crate) fn new(py: Python<'_>, output: TranspileOutput) -> Self {
+        fn words_to_bytes<'py>(py: Python<'py>, words: &[u32]) -> Bound<'py, PyBytes> {
+            let bytes: Vec<u8> = words.iter().flat_map(|word| word.to_ne_bytes()).collect();
+            PyBytes::new(py, &bytes)
+        }
+
+        match output {
+            TranspileOutput::Text(output) => PyShaderOutput {
+                vertex: PyString::new(py, &output.vertex).into_any().unbind(),
+                fragment: PyString::new(py, &output.fragment).into_any().unbind(),
+            },
+            TranspileOutput::Spirv(output) => PyShaderOutput {
+                vertex: words_to_bytes(py, &output.vertex).into_any().unbind(),
+                fragment: words_to_bytes(py, &output.fragment).into_any().unbind(),
+            },
+        }
+    }
+}
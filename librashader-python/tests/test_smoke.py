@@ -0,0 +1,91 @@
+"""Smoke tests for the librashader Python bindings.
+
+Build the extension into the active environment and run the tests with
+
+    maturin develop
+    pytest
+"""
+
+from pathlib import Path
+
+import pytest
+
+import librashader
+
+TEST_DIR = Path(__file__).resolve().parents[2] / "test"
+
+
+def test_parse_preset():
+    preset = librashader.ShaderPreset.parse(TEST_DIR / "reflect" / "reflect.slangp")
+    assert preset.pass_count == 2
+    assert len(preset.passes) == 2
+    assert preset.passes[0].meta.alias == "First"
+    assert preset.passes[1].meta.filter == librashader.FilterMode.LINEAR
+    assert [texture.meta.name for texture in preset.textures] == ["Mask"]
+
+
+def test_parse_preset_with_context():
+    context = librashader.WildcardContext()
+    context.set_core_name("smoke")
+    context.set_runtime(librashader.VideoDriver.VULKAN)
+    preset = librashader.ShaderPreset.parse(TEST_DIR / "basic.slangp", context)
+    assert preset.pass_count == 2
+    assert [parameter.name for parameter in preset.parameters] == ["ColorMod"]
+
+
+def test_parse_missing_preset():
+    with pytest.raises(librashader.PresetError):
+        librashader.ShaderPreset.parse(TEST_DIR / "missing.slangp")
+
+
+def test_preprocess():
+    source = librashader.ShaderSource.load(TEST_DIR / "basic.slang")
+    assert source.vertex
+    assert source.fragment
+    assert "ColorMod" in [parameter.id for parameter in source.parameters]
+
+
+def test_pack_roundtrip():
+    preset = librashader.ShaderPreset.parse(TEST_DIR / "reflect" / "reflect.slangp")
+    pack = librashader.ShaderPresetPack.from_preset(preset)
+    assert pack.pass_count == 2
+    assert [texture.meta.name for texture in pack.textures] == ["Mask"]
+    texture = pack.textures[0]
+    assert len(texture.data) == texture.width * texture.height * 4
+
+    unpacked = librashader.ShaderPresetPack.from_msgpack(pack.to_msgpack())
+    assert unpacked.to_json() == pack.to_json()
+
+
+@pytest.mark.parametrize(
+    "target",
+    [
+        librashader.ShaderTarget.GLSL,
+        librashader.ShaderTarget.HLSL,
+        librashader.ShaderTarget.MSL,
+        librashader.ShaderTarget.WGSL,
+    ],
+)
+def test_transpile_text(target):
+    source = librashader.ShaderSource.load(TEST_DIR / "basic.slang")
+    output = source.transpile(target)
+    assert isinstance(output.vertex, str) and output.vertex
+    assert isinstance(output.fragment, str) and output.fragment
+
+
+def test_transpile_spirv():
+    source = librashader.ShaderSource.load(TEST_DIR / "basic.slang")
+    output = source.transpile(librashader.ShaderTarget.SPIRV)
+    magic = (0x07230203).to_bytes(4, "little")
+    assert output.vertex[:4] == magic
+    assert output.fragment[:4] == magic
+
+
+def test_transpile_options():
+    source = librashader.ShaderSource.load(TEST_DIR / "basic.slang")
+    options = librashader.TranspileOptions(glsl_version=460)
+    output = source.transpile(librashader.ShaderTarget.GLSL, options)
+    assert output.vertex.startswith("#version 460")
+
+    with pytest.raises(ValueError):
+        librashader.TranspileOptions(glsl_version=123)
@@ -1,6 +1,6 @@
 use crate::back::targets::GLSL;
 use crate::back::{CompileReflectShader, CompilerBackend, FromCompilation};
-use crate::error::ShaderReflectError;
+use crate::error::{ShaderReflectError, UnsupportedVersionError};
 use crate::front::SpirvCompilation;
 use crate::reflect::cross::{CompiledProgram, SpirvCross};
 
@@ -9,6 +9,59 @@ pub use spirv_cross2::compile::glsl::GlslVersion;
 
 use crate::reflect::cross::glsl::GlslReflect;
 
+/// Get the desktop GLSL version with the given version number, such as `330` or `460`.
+pub fn glsl_version_from_number(version: u16) -> Result<GlslVersion, UnsupportedVersionError> {
+    Ok(match version {
+        110 => GlslVersion::Glsl110,
+        120 => GlslVersion::Glsl120,
+        130 => GlslVersion::Glsl130,
+        140 => GlslVersion::Glsl140,
+        150 => GlslVersion::Glsl150,
+        330 => GlslVersion::Glsl330,
+        400 => GlslVersion::Glsl400,
+        410 => GlslVersion::Glsl410,
+        420 => GlslVersion::Glsl420,
+        430 => GlslVersion::Glsl430,
+        440 => GlslVersion::Glsl440,
+        450 => GlslVersion::Glsl450,
+        460 => GlslVersion::Glsl460,
+        _ => return Err(UnsupportedVersionError::new("GLSL", version)),
+    })
+}
+
+/// Get the GLSL ES version with the given version number, such as `300` or `310`.
+pub fn glsl_es_version_from_number(version: u16) -> Result<GlslVersion, UnsupportedVersionError> {
+    Ok(match version {
+        100 => GlslVersion::Glsl100Es,
+        300 => GlslVersion::Glsl300Es,
+        310 => GlslVersion::Glsl310Es,
+        320 => GlslVersion::Glsl320Es,
+        _ => return Err(UnsupportedVersionError::new("GLSL ES", version)),
+    })
+}
+
+/// Parse a GLSL version string, such as `330` or `300 es`.
+///
+/// Version numbers that only exist as GLSL ES, such as `310`, are parsed as GLSL ES
+/// without the `es` suffix.
+pub fn parse_glsl_version(version: &str) -> Result<GlslVersion, UnsupportedVersionError> {
+    let (number, es) = match version.trim().strip_suffix("es") {
+        Some(number) => (number.trim(), true),
+        None => (version.trim(), false),
+    };
+
+    let Ok(number) = number.parse::<u16>() else {
+        return Err(UnsupportedVersionError::new("GLSL", version));
+    };
+
+    if es {
+        glsl_es_version_from_number(number)
+    } else {
+        glsl_version_from_number(number).or_else(|_| glsl_es_version_from_number(number))
+    }
+    .map_err(|_| UnsupportedVersionError::new("GLSL", version))
+}
+
 /// The context for a GLSL compilation via spirv-cross.
 pub struct CrossGlslContext {
     /// A map of bindings of sampler names to binding locations.
@@ -48,3 +101,21 @@ impl FromCompilation<SpirvCompilation, SpirvCross> for GLSL {
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_glsl_versions() {
+        assert_eq!(parse_glsl_version("330").unwrap(), GlslVersion::Glsl330);
+        assert_eq!(
+            parse_glsl_version("300 es").unwrap(),
+            GlslVersion::Glsl300Es
+        );
+        assert_eq!(parse_glsl_version("310").unwrap(), GlslVersion::Glsl310Es);
+        assert!(parse_glsl_version("330 es").is_err());
+        assert!(parse_glsl_version("gl").is_err());
+        assert!(glsl_version_from_number(300).is_err());
+    }
+}
@@ -1,6 +1,6 @@
 use crate::back::targets::HLSL;
 use crate::back::{CompileReflectShader, CompilerBackend, FromCompilation};
-use crate::error::ShaderReflectError;
+use crate::error::{ShaderReflectError, UnsupportedVersionError};
 use crate::front::SpirvCompilation;
 use crate::reflect::cross::hlsl::HlslReflect;
 use crate::reflect::cross::{CompiledProgram, SpirvCross};
@@ -8,6 +8,51 @@ use crate::reflect::cross::{CompiledProgram, SpirvCross};
 /// The HLSL shader model version to target.
 pub use spirv_cross2::compile::hlsl::HlslShaderModel;
 
+/// Get the HLSL shader model with the given number multiplied by 10, such as `50` for
+/// Shader Model 5.0.
+pub fn hlsl_shader_model_from_number(
+    shader_model: u16,
+) -> Result<HlslShaderModel, UnsupportedVersionError> {
+    Ok(match shader_model {
+        30 => HlslShaderModel::ShaderModel3_0,
+        40 => HlslShaderModel::ShaderModel4_0,
+        50 => HlslShaderModel::ShaderModel5_0,
+        51 => HlslShaderModel::ShaderModel5_1,
+        60 => HlslShaderModel::ShaderModel6_0,
+        61 => HlslShaderModel::ShaderModel6_1,
+        62 => HlslShaderModel::ShaderModel6_2,
+        63 => HlslShaderModel::ShaderModel6_3,
+        64 => HlslShaderModel::ShaderModel6_4,
+        65 => HlslShaderModel::ShaderModel6_5,
+        66 => HlslShaderModel::ShaderModel6_6,
+        67 => HlslShaderModel::ShaderModel6_7,
+        68 => HlslShaderModel::ShaderModel6_8,
+        _ => {
+            return Err(UnsupportedVersionError::new(
+                "HLSL shader model",
+                shader_model,
+            ))
+        }
+    })
+}
+
+/// Parse an HLSL shader model string, such as `5.0`, `5_0` or `50`.
+pub fn parse_hlsl_shader_model(
+    shader_model: &str,
+) -> Result<HlslShaderModel, UnsupportedVersionError> {
+    let number: String = shader_model
+        .trim()
+        .chars()
+        .filter(|c| *c != '.' && *c != '_')
+        .collect();
+
+    number
+        .parse::<u16>()
+        .ok()
+        .and_then(|number| hlsl_shader_model_from_number(number).ok())
+        .ok_or_else(|| UnsupportedVersionError::new("HLSL shader model", shader_model))
+}
+
 /// Buffer assignment information
 #[derive(Debug, Clone)]
 pub struct HlslBufferAssignment {
@@ -137,7 +182,10 @@ impl FromCompilation<SpirvCompilation, SpirvCross> for HLSL {
 
 #[cfg(test)]
 mod test {
-    use crate::back::hlsl::HlslBufferAssignments;
+    use crate::back::hlsl::{
+        hlsl_shader_model_from_number, parse_hlsl_shader_model, HlslBufferAssignments,
+        HlslShaderModel,
+    };
 
     #[test]
     pub fn mangled_id_test() {
@@ -160,4 +208,16 @@ mod test {
             "params_MVP"
         ));
     }
+
+    #[test]
+    pub fn shader_model_test() {
+        for model in ["5.1", "5_1", "51"] {
+            assert_eq!(
+                parse_hlsl_shader_model(model).unwrap(),
+                HlslShaderModel::ShaderModel5_1
+            );
+        }
+        assert!(parse_hlsl_shader_model("5.2").is_err());
+        assert!(hlsl_shader_model_from_number(0).is_err());
+    }
 }
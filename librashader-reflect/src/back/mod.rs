@@ -1,10 +1,15 @@
 #[cfg(all(target_os = "windows", feature = "dxil"))]
 pub mod dxil;
+#[cfg(feature = "cross")]
 pub mod glsl;
+#[cfg(feature = "cross")]
 pub mod hlsl;
+#[cfg(feature = "msl")]
 pub mod msl;
 pub mod spirv;
 pub mod targets;
+#[cfg(all(feature = "msl", feature = "wgsl"))]
+pub mod transpile;
 pub mod wgsl;
 
 use crate::back::targets::OutputTarget;
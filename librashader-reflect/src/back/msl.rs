@@ -1,6 +1,6 @@
 use crate::back::targets::MSL;
 use crate::back::{CompileReflectShader, CompilerBackend, FromCompilation};
-use crate::error::ShaderReflectError;
+use crate::error::{ShaderReflectError, UnsupportedVersionError};
 use crate::front::SpirvCompilation;
 use crate::reflect::cross::msl::MslReflect;
 use crate::reflect::cross::{CompiledProgram, SpirvCross};
@@ -11,6 +11,29 @@ use naga::Module;
 /// The MSL language version to target.
 pub use spirv_cross2::compile::msl::MslVersion;
 
+/// Get the MSL version encoded as `major * 10000 + minor * 100 + patch`, such as `20100`
+/// for MSL 2.1.
+pub fn msl_version_from_number(version: u32) -> MslVersion {
+    MslVersion::new(version / 10000, version / 100 % 100, version % 100)
+}
+
+/// Parse an MSL version string, such as `2.1`, `2_1`, `21` or `20100`.
+pub fn parse_msl_version(version: &str) -> Result<MslVersion, UnsupportedVersionError> {
+    let number: String = version
+        .trim()
+        .chars()
+        .filter(|c| *c != '.' && *c != '_')
+        .collect();
+
+    Ok(match number.parse::<u32>() {
+        Ok(number @ (10 | 11 | 12 | 20 | 21 | 22 | 23 | 24 | 30 | 31 | 32)) => {
+            MslVersion::new(number / 10, number % 10, 0)
+        }
+        Ok(number) if number >= 10000 => msl_version_from_number(number),
+        _ => return Err(UnsupportedVersionError::new("MSL", version)),
+    })
+}
+
 /// Compiler options for MSL
 #[derive(Debug, Default, Clone)]
 pub struct MslNagaCompileOptions {
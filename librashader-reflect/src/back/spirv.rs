@@ -1,16 +1,25 @@
 use crate::back::targets::SPIRV;
-use crate::back::{
-    CompileReflectShader, CompileShader, CompilerBackend, FromCompilation, ShaderCompilerOutput,
-};
-use crate::error::{ShaderCompileError, ShaderReflectError};
+use crate::back::{CompileReflectShader, CompilerBackend, FromCompilation};
+#[cfg(feature = "cross")]
+use crate::back::{CompileShader, ShaderCompilerOutput};
+#[cfg(feature = "cross")]
+use crate::error::ShaderCompileError;
+use crate::error::ShaderReflectError;
 use crate::front::SpirvCompilation;
+#[cfg(feature = "cross")]
 use crate::reflect::cross::glsl::GlslReflect;
+#[cfg(feature = "cross")]
 use crate::reflect::cross::SpirvCross;
+#[cfg(feature = "naga")]
 use crate::reflect::naga::{Naga, NagaLoweringOptions, NagaReflect};
+#[cfg(feature = "cross")]
 use crate::reflect::semantics::ShaderSemantics;
+#[cfg(feature = "cross")]
 use crate::reflect::{ReflectShader, ShaderReflection};
+#[cfg(feature = "naga")]
 use naga::Module;
 
+#[cfg(feature = "cross")]
 pub(crate) struct WriteSpirV {
     // rely on GLSL to provide out reflection but we don't actually need the AST.
     pub(crate) reflect: GlslReflect,
@@ -18,7 +27,7 @@ pub(crate) struct WriteSpirV {
     pub(crate) fragment: Vec<u32>,
 }
 
-#[cfg(not(feature = "stable"))]
+#[cfg(all(feature = "cross", not(feature = "stable")))]
 impl FromCompilation<SpirvCompilation, SpirvCross> for SPIRV {
     type Target = SPIRV;
     type Options = Option<()>;
@@ -41,7 +50,7 @@ impl FromCompilation<SpirvCompilation, SpirvCross> for SPIRV {
     }
 }
 
-#[cfg(feature = "stable")]
+#[cfg(all(feature = "cross", feature = "stable"))]
 impl FromCompilation<SpirvCompilation, SpirvCross> for SPIRV {
     type Target = SPIRV;
     type Options = Option<()>;
@@ -64,6 +73,7 @@ impl FromCompilation<SpirvCompilation, SpirvCross> for SPIRV {
     }
 }
 
+#[cfg(feature = "cross")]
 impl ReflectShader for WriteSpirV {
     fn reflect(
         &mut self,
@@ -78,6 +88,7 @@ impl ReflectShader for WriteSpirV {
     }
 }
 
+#[cfg(feature = "cross")]
 impl CompileShader<SPIRV> for WriteSpirV {
     type Options = Option<()>;
     type Context = ();
@@ -106,12 +117,13 @@ impl CompileShader<SPIRV> for WriteSpirV {
 }
 
 /// The context for a SPIRV compilation via Naga
+#[cfg(feature = "naga")]
 pub struct NagaSpirvContext {
     pub fragment: Module,
     pub vertex: Module,
 }
 
-#[cfg(not(feature = "stable"))]
+#[cfg(all(feature = "naga", not(feature = "stable")))]
 impl FromCompilation<SpirvCompilation, Naga> for SPIRV {
     type Target = SPIRV;
     type Options = NagaSpirvOptions;
@@ -127,7 +139,7 @@ impl FromCompilation<SpirvCompilation, Naga> for SPIRV {
     }
 }
 
-#[cfg(feature = "stable")]
+#[cfg(all(feature = "naga", feature = "stable"))]
 impl FromCompilation<SpirvCompilation, Naga> for SPIRV {
     type Target = SPIRV;
     type Options = NagaSpirvOptions;
@@ -143,6 +155,7 @@ impl FromCompilation<SpirvCompilation, Naga> for SPIRV {
     }
 }
 
+#[cfg(feature = "naga")]
 pub struct NagaSpirvOptions {
     pub lowering: NagaLoweringOptions,
     pub version: (u8, u8),
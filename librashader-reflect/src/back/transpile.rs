@@ -0,0 +1,163 @@
+//! Transpilation of a single shader source to any supported shader target.
+use crate::back::glsl::GlslVersion;
+use crate::back::hlsl::HlslShaderModel;
+use crate::back::msl::MslVersion;
+use crate::back::targets::{GLSL, HLSL, MSL, SPIRV, WGSL};
+use crate::back::{CompileShader, FromCompilation, ShaderCompilerOutput};
+use crate::error::{ShaderCompileError, ShaderReflectError};
+use crate::front::SpirvCompilation;
+use crate::reflect::cross::SpirvCross;
+use crate::reflect::naga::NagaLoweringOptions;
+use crate::reflect::ReflectShader;
+use librashader_preprocess::ShaderSource;
+
+/// A shader target that a shader source can be transpiled to.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TranspileTarget {
+    /// GLSL, for OpenGL.
+    Glsl,
+    /// HLSL, for Direct3D.
+    Hlsl,
+    /// Metal Shading Language.
+    Msl,
+    /// WGSL, for WebGPU.
+    Wgsl,
+    /// SPIR-V, for Vulkan.
+    Spirv,
+}
+
+/// Options for transpiling a shader source.
+///
+/// The default options match the versions that the filter chains compile to.
+#[derive(Debug, Clone)]
+pub struct TranspileOptions {
+    /// The GLSL version to output. Default is GLSL 330.
+    pub glsl_version: GlslVersion,
+    /// The HLSL shader model to output. Default is Shader Model 5.0.
+    pub hlsl_shader_model: HlslShaderModel,
+    /// The MSL version to output. Default is MSL 1.2.
+    pub msl_version: MslVersion,
+    /// The options to lower the WGSL output with. Default writes the push constant block
+    /// as a UBO, and places samplers in bind group 1.
+    pub wgsl: NagaLoweringOptions,
+}
+
+impl Default for TranspileOptions {
+    fn default() -> Self {
+        TranspileOptions {
+            glsl_version: GlslVersion::Glsl330,
+            hlsl_shader_model: HlslShaderModel::ShaderModel5_0,
+            msl_version: MslVersion::new(1, 2, 0),
+            wgsl: NagaLoweringOptions {
+                write_pcb_as_ubo: true,
+                sampler_bind_group: 1,
+            },
+        }
+    }
+}
+
+/// The vertex and fragment output of a transpiled shader.
+#[derive(Debug)]
+pub enum TranspileOutput {
+    /// The source of a text shading language.
+    Text(ShaderCompilerOutput<String>),
+    /// SPIR-V modules.
+    Spirv(ShaderCompilerOutput<Vec<u32>>),
+}
+
+impl TranspileOutput {
+    fn text<C>(output: ShaderCompilerOutput<String, C>) -> Self {
+        TranspileOutput::Text(ShaderCompilerOutput {
+            vertex: output.vertex,
+            fragment: output.fragment,
+            context: (),
+        })
+    }
+}
+
+/// Compile the shader source, validate it, and transpile it to the given target.
+pub fn transpile<E>(
+    source: &ShaderSource,
+    target: TranspileTarget,
+    options: &TranspileOptions,
+) -> Result<TranspileOutput, E>
+where
+    E: From<ShaderCompileError>,
+    E: From<ShaderReflectError>,
+{
+    let compilation = SpirvCompilation::try_from(source)?;
+    let output = match target {
+        TranspileTarget::Glsl => {
+            let mut compilation = GLSL::from_compilation(compilation)?;
+            compilation.validate()?;
+            TranspileOutput::text(compilation.compile(options.glsl_version)?)
+        }
+        TranspileTarget::Hlsl => {
+            let mut compilation = HLSL::from_compilation(compilation)?;
+            compilation.validate()?;
+            TranspileOutput::text(compilation.compile(Some(options.hlsl_shader_model))?)
+        }
+        TranspileTarget::Msl => {
+            let mut compilation =
+                <MSL as FromCompilation<SpirvCompilation, SpirvCross>>::from_compilation(
+                    compilation,
+                )?;
+            compilation.validate()?;
+            TranspileOutput::text(compilation.compile(Some(options.msl_version))?)
+        }
+        TranspileTarget::Wgsl => {
+            let mut compilation = WGSL::from_compilation(compilation)?;
+            compilation.validate()?;
+            TranspileOutput::text(compilation.compile(options.wgsl.clone())?)
+        }
+        TranspileTarget::Spirv => {
+            let mut compilation =
+                <SPIRV as FromCompilation<SpirvCompilation, SpirvCross>>::from_compilation(
+                    compilation,
+                )?;
+            compilation.validate()?;
+            let output = compilation.compile(None)?;
+            TranspileOutput::Spirv(ShaderCompilerOutput {
+                vertex: output.vertex,
+                fragment: output.fragment,
+                context: (),
+            })
+        }
+    };
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    type Error = Box<dyn std::error::Error>;
+
+    #[test]
+    fn transpiles_to_every_target() {
+        let source = ShaderSource::load("../test/basic.slang").unwrap();
+        let options = TranspileOptions::default();
+        for target in [
+            TranspileTarget::Glsl,
+            TranspileTarget::Hlsl,
+            TranspileTarget::Msl,
+            TranspileTarget::Wgsl,
+        ] {
+            let TranspileOutput::Text(output) =
+                transpile::<Error>(&source, target, &options).unwrap()
+            else {
+                panic!("{target:?} should be transpiled to text");
+            };
+            assert!(!output.vertex.is_empty() && !output.fragment.is_empty());
+        }
+
+        let TranspileOutput::Spirv(output) =
+            transpile::<Error>(&source, TranspileTarget::Spirv, &options).unwrap()
+        else {
+            panic!("SPIR-V should be transpiled to words");
+        };
+        assert_eq!(output.vertex[0], 0x0723_0203);
+        assert_eq!(output.fragment[0], 0x0723_0203);
+    }
+}
@@ -0,0 +1,240 @@
+use crate::error::ShaderCompileError;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// The severity of a shader compiler diagnostic.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DiagnosticSeverity {
+    /// The diagnostic caused compilation to fail.
+    Error,
+    /// The diagnostic is a warning that did not cause compilation to fail.
+    Warning,
+    /// The diagnostic is informational.
+    Note,
+}
+
+/// A single diagnostic message emitted by a shader compiler.
+///
+/// The location of the diagnostic is resolved against the `#line` markers emitted by
+/// the preprocessor, so `file` and `line` refer to the original `.slang` or included file
+/// rather than the flattened shader source. Compilers that operate on SPIR-V, such as
+/// SPIRV-Cross and naga, do not have source locations available, in which case the
+/// location fields are `None`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShaderDiagnostic {
+    /// The severity of the diagnostic.
+    pub severity: DiagnosticSeverity,
+    /// The name of the source file the diagnostic was emitted for, if known.
+    pub file: Option<String>,
+    /// The 1-based line number the diagnostic was emitted for, if known.
+    pub line: Option<u32>,
+    /// The 1-based column number the diagnostic was emitted for, if known.
+    pub column: Option<u32>,
+    /// The diagnostic message.
+    pub message: String,
+}
+
+impl ShaderDiagnostic {
+    fn unlocated(severity: DiagnosticSeverity, message: String) -> Self {
+        ShaderDiagnostic {
+            severity,
+            file: None,
+            line: None,
+            column: None,
+            message,
+        }
+    }
+}
+
+impl Display for DiagnosticSeverity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DiagnosticSeverity::Error => f.write_str("error"),
+            DiagnosticSeverity::Warning => f.write_str("warning"),
+            DiagnosticSeverity::Note => f.write_str("note"),
+        }
+    }
+}
+
+impl Display for ShaderDiagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{file}:")?;
+        }
+        if let Some(line) = self.line {
+            write!(f, "{line}:")?;
+            if let Some(column) = self.column {
+                write!(f, "{column}:")?;
+            }
+        }
+        if self.file.is_some() || self.line.is_some() {
+            f.write_str(" ")?;
+        }
+        write!(f, "{}: {}", self.severity, self.message)
+    }
+}
+
+impl ShaderCompileError {
+    /// Get the list of diagnostics for this error.
+    ///
+    /// Compiler logs are parsed into one diagnostic per message. Errors that do not come
+    /// with a compiler log are returned as a single diagnostic without a location.
+    /// spirv-cross only reports errors, so a transpile error is always a single diagnostic.
+    pub fn diagnostics(&self) -> Vec<ShaderDiagnostic> {
+        match self {
+            ShaderCompileError::PassError { source, .. } => source.diagnostics(),
+            #[cfg(not(target_arch = "wasm32"))]
+            ShaderCompileError::GlslangError(
+                glslang::error::GlslangError::PreprocessError(log)
+                | glslang::error::GlslangError::ParseError(log)
+                | glslang::error::GlslangError::MapIoError(log)
+                | glslang::error::GlslangError::LinkError(log),
+            ) => {
+                let diagnostics = parse_glslang_log(log);
+                if diagnostics.is_empty() {
+                    vec![ShaderDiagnostic::unlocated(
+                        DiagnosticSeverity::Error,
+                        self.to_string(),
+                    )]
+                } else {
+                    diagnostics
+                }
+            }
+            #[cfg(feature = "unstable-naga-in")]
+            ShaderCompileError::NagaCompileError(errors) => errors
+                .iter()
+                .map(|e| ShaderDiagnostic::unlocated(DiagnosticSeverity::Error, e.to_string()))
+                .collect(),
+            _ => vec![ShaderDiagnostic::unlocated(
+                DiagnosticSeverity::Error,
+                error_chain_message(self),
+            )],
+        }
+    }
+}
+
+/// Flatten an error and its sources into a single message.
+fn error_chain_message(error: &dyn Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        let cause_message = cause.to_string();
+        // thiserror transparently forwards the source message for `#[from]` variants.
+        if !message.ends_with(&cause_message) {
+            message.push_str(": ");
+            message.push_str(&cause_message);
+        }
+        source = cause.source();
+    }
+    message
+}
+
+/// Parse a glslang info log into a list of diagnostics.
+///
+/// glslang emits one message per line in the form `SEVERITY: file:line:column: message`.
+/// When the shader source was preprocessed with line directives, glslang resolves the
+/// `#line` markers and reports the original file name; otherwise the source string index
+/// is reported in its place, in which case `file` is `None`.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn parse_glslang_log(log: &str) -> Vec<ShaderDiagnostic> {
+    log.lines().filter_map(parse_glslang_message).collect()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn parse_glslang_message(message: &str) -> Option<ShaderDiagnostic> {
+    let message = message.trim();
+    let (severity, rest) = message.split_once(": ")?;
+    let severity = match severity {
+        "ERROR" | "INTERNAL ERROR" | "UNIMPLEMENTED" => DiagnosticSeverity::Error,
+        "WARNING" => DiagnosticSeverity::Warning,
+        "NOTE" => DiagnosticSeverity::Note,
+        _ => return None,
+    };
+
+    // Summary lines do not carry any useful information on their own.
+    if rest.ends_with("No code generated.") {
+        return None;
+    }
+
+    let located = rest
+        .split_once(": ")
+        .and_then(|(location, text)| Some((parse_glslang_location(location)?, text)));
+
+    let Some(((file, line, column), text)) = located else {
+        return Some(ShaderDiagnostic::unlocated(severity, rest.to_string()));
+    };
+
+    Some(ShaderDiagnostic {
+        severity,
+        file,
+        line: Some(line),
+        column,
+        message: text.trim().to_string(),
+    })
+}
+
+/// Parse a glslang location in the form `file:line[:column]`.
+#[cfg(not(target_arch = "wasm32"))]
+fn parse_glslang_location(location: &str) -> Option<(Option<String>, u32, Option<u32>)> {
+    let (head, last) = location.rsplit_once(':')?;
+    let last = last.parse::<u32>().ok()?;
+
+    let (file, line, column) = match head.rsplit_once(':') {
+        Some((file, line)) if line.parse::<u32>().is_ok() => {
+            (file, line.parse::<u32>().ok()?, Some(last))
+        }
+        _ => (head, last, None),
+    };
+
+    // The source string index is reported if there is no file name.
+    let file = if file.is_empty() || file.parse::<u32>().is_ok() {
+        None
+    } else {
+        Some(file.to_string())
+    };
+
+    Some((file, line, column))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::diagnostics::{parse_glslang_log, DiagnosticSeverity, ShaderDiagnostic};
+
+    #[test]
+    pub fn parses_glslang_log() {
+        let log = "ERROR: bad.inc:3:16: 'unknown_value' : undeclared identifier \n\
+                   WARNING: 0:12: 'x' : extension not supported\n\
+                   ERROR: Linking fragment stage: Missing entry point: Each stage requires one entry point\n\
+                   ERROR: 2 compilation errors.  No code generated.\n\n";
+
+        let diagnostics = parse_glslang_log(log);
+        assert_eq!(
+            diagnostics,
+            vec![
+                ShaderDiagnostic {
+                    severity: DiagnosticSeverity::Error,
+                    file: Some("bad.inc".to_string()),
+                    line: Some(3),
+                    column: Some(16),
+                    message: "'unknown_value' : undeclared identifier".to_string(),
+                },
+                ShaderDiagnostic {
+                    severity: DiagnosticSeverity::Warning,
+                    file: None,
+                    line: Some(12),
+                    column: None,
+                    message: "'x' : extension not supported".to_string(),
+                },
+                ShaderDiagnostic {
+                    severity: DiagnosticSeverity::Error,
+                    file: None,
+                    line: None,
+                    column: None,
+                    message: "Linking fragment stage: Missing entry point: Each stage requires one entry point".to_string(),
+                },
+            ]
+        );
+    }
+}
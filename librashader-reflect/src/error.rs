@@ -7,18 +7,35 @@ use thiserror::Error;
 pub enum ShaderCompileError {
     /// Compile error from naga.
     #[cfg(feature = "unstable-naga-in")]
-    #[error("shader")]
+    #[error("naga error when compiling glsl: {}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))]
     NagaCompileError(Vec<naga::front::glsl::Error>),
 
     /// Compilation error from glslang.
+    #[cfg(not(target_arch = "wasm32"))]
     #[error("error when compiling with glslang: {0}")]
     GlslangError(#[from] glslang::error::GlslangError),
 
     /// Error when initializing the glslang compiler.
+    #[cfg(not(target_arch = "wasm32"))]
     #[error("error when initializing glslang")]
     CompilerInitError,
 
+    /// Compilation was cancelled by a pass observer.
+    #[error("shader compilation was cancelled")]
+    Cancelled,
+
+    /// Error when compiling a pass of a shader preset.
+    #[error("error when compiling pass {pass}: {source}")]
+    PassError {
+        /// The index of the pass in the shader preset.
+        pass: usize,
+        /// The error that occurred when compiling the pass.
+        #[source]
+        source: Box<ShaderCompileError>,
+    },
+
     /// Error when transpiling from spirv-cross.
+    #[cfg(feature = "cross")]
     #[error("spirv-cross error: {0:?}")]
     SpirvCrossCompileError(#[from] spirv_cross2::SpirvCrossError),
 
@@ -28,7 +45,7 @@ pub enum ShaderCompileError {
     SpirvToDxilCompileError(#[from] spirv_to_dxil::SpirvToDxilError),
 
     /// Error when transpiling from naga
-    #[cfg(all(feature = "wgsl", feature = "naga"))]
+    #[cfg(feature = "naga")]
     #[error("naga error when compiling wgsl: {0:?}")]
     NagaWgslError(#[from] naga::back::wgsl::Error),
 
@@ -48,6 +65,56 @@ pub enum ShaderCompileError {
     NagaValidationError(#[from] naga::WithSpan<naga::valid::ValidationError>),
 }
 
+impl ShaderCompileError {
+    /// Attach the index of the pass of a shader preset that failed to compile.
+    ///
+    /// Cancellation and errors that already have a pass index are returned unchanged.
+    pub fn in_pass(self, pass: usize) -> Self {
+        match self {
+            ShaderCompileError::Cancelled | ShaderCompileError::PassError { .. } => self,
+            error => ShaderCompileError::PassError {
+                pass,
+                source: Box::new(error),
+            },
+        }
+    }
+
+    /// The index of the pass of a shader preset that failed to compile, if known.
+    pub fn pass_index(&self) -> Option<usize> {
+        match self {
+            ShaderCompileError::PassError { pass, .. } => Some(*pass),
+            _ => None,
+        }
+    }
+
+    /// The error that occurred, without the pass it occurred in.
+    pub fn without_pass(&self) -> &ShaderCompileError {
+        match self {
+            ShaderCompileError::PassError { source, .. } => source,
+            error => error,
+        }
+    }
+}
+
+/// Error when a version of a shader target is not supported.
+#[derive(Error, Debug)]
+#[error("unsupported {target} version {version}")]
+pub struct UnsupportedVersionError {
+    /// The name of the shader target.
+    pub target: &'static str,
+    /// The version that was requested.
+    pub version: String,
+}
+
+impl UnsupportedVersionError {
+    pub(crate) fn new(target: &'static str, version: impl ToString) -> Self {
+        UnsupportedVersionError {
+            target,
+            version: version.to_string(),
+        }
+    }
+}
+
 /// The error kind encountered when reflecting shader semantics.
 #[derive(Debug)]
 pub enum SemanticsErrorKind {
@@ -86,6 +153,7 @@ pub enum SemanticsErrorKind {
 #[derive(Error, Debug)]
 pub enum ShaderReflectError {
     /// Reflection error from spirv-cross.
+    #[cfg(feature = "cross")]
     #[error("spirv cross error: {0}")]
     SpirvCrossError(#[from] spirv_cross2::SpirvCrossError),
     /// Error when validating vertex shader semantics.
@@ -1,8 +1,13 @@
+use crate::diagnostics::parse_glslang_log;
 use crate::error::ShaderCompileError;
-use glslang::{CompilerOptions, ShaderInput, ShaderMessage};
+use glslang::error::GlslangError;
+use glslang::limits;
+use glslang_sys as sys;
 use librashader_preprocess::ShaderSource;
 use rspirv::binary::Assemble;
 use rspirv::dr::Builder;
+use std::ffi::{c_char, CStr, CString};
+use std::ptr::NonNull;
 
 use crate::front::spirv_passes::{link_input_outputs, load_module};
 use crate::front::{ShaderInputCompiler, SpirvCompilation};
@@ -17,36 +22,15 @@ impl ShaderInputCompiler<SpirvCompilation> for Glslang {
 }
 
 pub(crate) fn compile_spirv(source: &ShaderSource) -> Result<SpirvCompilation, ShaderCompileError> {
-    let compiler = glslang::Compiler::acquire().ok_or(ShaderCompileError::CompilerInitError)?;
-    let options = CompilerOptions {
-        source_language: glslang::SourceLanguage::GLSL,
-        target: glslang::Target::Vulkan {
-            version: glslang::VulkanVersion::Vulkan1_0,
-            spirv_version: glslang::SpirvVersion::SPIRV1_0,
-        },
-        version_profile: None,
-        messages: ShaderMessage::DEFAULT,
-    };
+    let _compiler = glslang::Compiler::acquire().ok_or(ShaderCompileError::CompilerInitError)?;
 
-    let vertex = glslang::ShaderSource::from(source.vertex.as_str());
-    let vertex = ShaderInput::new(&vertex, glslang::ShaderStage::Vertex, &options, None, None)?;
-    let vertex = compiler.create_shader(vertex)?;
-
-    let fragment = glslang::ShaderSource::from(source.fragment.as_str());
-    let fragment = ShaderInput::new(
-        &fragment,
-        glslang::ShaderStage::Fragment,
-        &options,
-        None,
-        None,
-    )?;
-    let fragment = compiler.create_shader(fragment)?;
-
-    let vertex = vertex.compile()?;
-    let fragment = fragment.compile()?;
-
-    let vertex = load_module(&vertex);
-    let fragment = load_module(&fragment);
+    let vertex = compile_stage(&source.vertex, sys::glslang_stage_t::Vertex)?;
+    log_warnings(&vertex.log);
+    let fragment = compile_stage(&source.fragment, sys::glslang_stage_t::Fragment)?;
+    log_warnings(&fragment.log);
+
+    let vertex = load_module(&vertex.spirv);
+    let fragment = load_module(&fragment.spirv);
     let mut fragment = Builder::new_from_module(fragment);
     let mut vertex = Builder::new_from_module(vertex);
 
@@ -59,13 +43,187 @@ pub(crate) fn compile_spirv(source: &ShaderSource) -> Result<SpirvCompilation, S
     Ok(SpirvCompilation { vertex, fragment })
 }
 
+/// The SPIR-V of a single stage, and the info logs of glslang for it.
+struct CompiledStage {
+    spirv: Vec<u32>,
+    log: String,
+}
+
+/// Compile a single stage to SPIR-V.
+///
+/// This is what `glslang::Shader::compile` does, except that the info logs of the shader and
+/// the program are kept when compilation succeeds, since the glslang crate only exposes them
+/// as part of an error. The compiler must be acquired by the caller.
+fn compile_stage(
+    source: &str,
+    stage: sys::glslang_stage_t,
+) -> Result<CompiledStage, ShaderCompileError> {
+    // The sources of a pass are preprocessed from UTF-8 text files, and never contain a nul.
+    let code = CString::new(source)
+        .map_err(|_| GlslangError::ParseError(String::from("shader source contains a nul byte")))?;
+
+    let input = sys::glslang_input_t {
+        language: sys::glslang_source_t::GLSL,
+        stage,
+        client: sys::glslang_client_t::Vulkan,
+        client_version: sys::glslang_target_client_version_t::Vulkan1_0,
+        target_language: sys::glslang_target_language_t::SPIRV,
+        target_language_version: sys::glslang_target_language_version_t::SPIRV1_0,
+        code: code.as_ptr(),
+        default_version: 100,
+        default_profile: sys::glslang_profile_t::None,
+        force_default_version_and_profile: 0,
+        forward_compatible: 0,
+        messages: sys::glslang_messages_t::DEFAULT | sys::glslang_messages_t::DISPLAY_ERROR_COLUMN,
+        // SAFETY: ResourceLimits is a transparent wrapper around glslang_resource_t.
+        resource: std::ptr::from_ref(&limits::DEFAULT_LIMITS).cast(),
+        // Includes are resolved by librashader-preprocess.
+        callbacks: sys::glsl_include_callbacks_t {
+            include_system: None,
+            include_local: None,
+            free_include_result: None,
+        },
+        callbacks_ctx: std::ptr::null_mut(),
+    };
+
+    let shader = GlslangShader::new(&input);
+    // SAFETY: the input, and the source and limits it points to, outlive the shader.
+    unsafe {
+        if sys::glslang_shader_preprocess(shader.0.as_ptr(), &input) == 0 {
+            return Err(GlslangError::PreprocessError(shader.info_log()).into());
+        }
+        if sys::glslang_shader_parse(shader.0.as_ptr(), &input) == 0 {
+            return Err(GlslangError::ParseError(shader.info_log()).into());
+        }
+    }
+
+    let program = GlslangProgram::new();
+    let messages = sys::glslang_messages_t::DEFAULT
+        | sys::glslang_messages_t::VULKAN_RULES
+        | sys::glslang_messages_t::SPV_RULES;
+
+    // SAFETY: the shader outlives the program, which is dropped first.
+    let spirv = unsafe {
+        sys::glslang_program_add_shader(program.0.as_ptr(), shader.0.as_ptr());
+        if sys::glslang_program_link(program.0.as_ptr(), messages.0) == 0 {
+            return Err(GlslangError::LinkError(program.info_log()).into());
+        }
+
+        sys::glslang_program_SPIRV_generate(program.0.as_ptr(), stage);
+        let size = sys::glslang_program_SPIRV_get_size(program.0.as_ptr());
+        let mut spirv = vec![0u32; size];
+        sys::glslang_program_SPIRV_get(program.0.as_ptr(), spirv.as_mut_ptr());
+        spirv
+    };
+
+    let mut log = shader.info_log();
+    log.push_str(&program.info_log());
+    Ok(CompiledStage { spirv, log })
+}
+
+/// Log the messages in the info log of a stage that compiled successfully.
+fn log_warnings(log: &str) {
+    if log.trim().is_empty() {
+        return;
+    }
+
+    let diagnostics = parse_glslang_log(log);
+    if diagnostics.is_empty() {
+        log::warn!("{}", log.trim());
+    }
+    for diagnostic in diagnostics {
+        log::warn!("{diagnostic}");
+    }
+}
+
+/// Read an info log returned by glslang.
+///
+/// SAFETY: `log` must be a valid nul-terminated string, or null.
+unsafe fn read_info_log(log: *const c_char) -> String {
+    if log.is_null() {
+        return String::new();
+    }
+    CStr::from_ptr(log).to_string_lossy().into_owned()
+}
+
+struct GlslangShader(NonNull<sys::glslang_shader_t>);
+
+impl GlslangShader {
+    fn new(input: &sys::glslang_input_t) -> Self {
+        // SAFETY: glslang only reads the stage and target of the input when creating the shader.
+        let shader = unsafe { sys::glslang_shader_create(input) };
+        GlslangShader(NonNull::new(shader).expect("glslang created a null shader"))
+    }
+
+    fn info_log(&self) -> String {
+        unsafe { read_info_log(sys::glslang_shader_get_info_log(self.0.as_ptr())) }
+    }
+}
+
+impl Drop for GlslangShader {
+    fn drop(&mut self) {
+        unsafe { sys::glslang_shader_delete(self.0.as_ptr()) }
+    }
+}
+
+struct GlslangProgram(NonNull<sys::glslang_program_t>);
+
+impl GlslangProgram {
+    fn new() -> Self {
+        let program = unsafe { sys::glslang_program_create() };
+        GlslangProgram(NonNull::new(program).expect("glslang created a null program"))
+    }
+
+    fn info_log(&self) -> String {
+        unsafe { read_info_log(sys::glslang_program_get_info_log(self.0.as_ptr())) }
+    }
+}
+
+impl Drop for GlslangProgram {
+    fn drop(&mut self) {
+        unsafe { sys::glslang_program_delete(self.0.as_ptr()) }
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use crate::front::glslang::compile_spirv;
+    use crate::diagnostics::{parse_glslang_log, DiagnosticSeverity};
+    use crate::front::glslang::{compile_spirv, compile_stage, sys};
     use librashader_preprocess::ShaderSource;
     #[test]
     pub fn compile_shader() {
         let result = ShaderSource::load("../test/basic.slang").unwrap();
         let _spirv = compile_spirv(&result).unwrap();
     }
+
+    #[test]
+    pub fn compile_error_diagnostics() {
+        let result = ShaderSource::load("../test/diagnostics/include_error.slang").unwrap();
+        let diagnostics = compile_spirv(&result).unwrap_err().diagnostics();
+        let first = &diagnostics[0];
+        assert_eq!(first.severity, DiagnosticSeverity::Error);
+        assert_eq!(first.file.as_deref(), Some("error.inc"));
+        assert_eq!(first.line, Some(3));
+
+        let result = ShaderSource::load("../test/diagnostics/pass_error.slang").unwrap();
+        let diagnostics = compile_spirv(&result).unwrap_err().diagnostics();
+        let first = &diagnostics[0];
+        assert_eq!(first.file.as_deref(), Some("pass_error.slang"));
+        assert_eq!(first.line, Some(28));
+    }
+
+    #[test]
+    pub fn keeps_warnings_of_successful_compile() {
+        let _compiler = glslang::Compiler::acquire().unwrap();
+        let source = "#version 450\n\
+                      #extension GL_LIBRASHADER_unknown : warn\n\
+                      layout(location = 0) out vec4 FragColor;\n\
+                      void main() { FragColor = vec4(1.0); }\n";
+        let stage = compile_stage(source, sys::glslang_stage_t::Fragment).unwrap();
+        assert!(!stage.spirv.is_empty());
+
+        let diagnostics = parse_glslang_log(&stage.log);
+        assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Warning);
+        assert_eq!(diagnostics[0].line, Some(2));
+    }
 }
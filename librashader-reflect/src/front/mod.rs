@@ -2,14 +2,19 @@ use crate::error::ShaderCompileError;
 use librashader_preprocess::ShaderSource;
 pub(crate) mod spirv_passes;
 
+#[cfg(not(target_arch = "wasm32"))]
 mod glslang;
 
+#[cfg(feature = "unstable-naga-in")]
+mod naga;
+
 /// The output of a shader compiler that is reflectable.
 pub trait ShaderReflectObject: Sized {
     /// The compiler that produces this reflect object.
     type Compiler;
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 pub use crate::front::glslang::Glslang;
 
 /// Trait for types that can compile shader sources into a compilation unit.
@@ -19,11 +24,18 @@ pub trait ShaderInputCompiler<O: ShaderReflectObject>: Sized {
 }
 
 /// Marker trait for types that are the reflectable outputs of a shader compilation.
+///
+/// SPIR-V is compiled with glslang, except on `wasm32` where glslang is unavailable and the
+/// naga GLSL frontend is used instead.
 impl ShaderReflectObject for SpirvCompilation {
+    #[cfg(not(target_arch = "wasm32"))]
     type Compiler = Glslang;
+
+    #[cfg(all(target_arch = "wasm32", feature = "unstable-naga-in"))]
+    type Compiler = crate::reflect::naga::Naga;
 }
 
-/// A reflectable shader compilation via glslang.
+/// A reflectable shader compilation to SPIR-V.
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SpirvCompilation {
@@ -36,6 +48,6 @@ impl TryFrom<&ShaderSource> for SpirvCompilation {
 
     /// Tries to compile SPIR-V from the provided shader source.
     fn try_from(source: &ShaderSource) -> Result<Self, Self::Error> {
-        Glslang::compile(source)
+        <Self as ShaderReflectObject>::Compiler::compile(source)
     }
 }
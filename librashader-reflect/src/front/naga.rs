@@ -0,0 +1,235 @@
+use crate::error::ShaderCompileError;
+use librashader_preprocess::ShaderSource;
+use naga::back::spv::{PipelineOptions, WriterFlags};
+use naga::front::glsl::{Frontend, Options};
+use naga::valid::{Capabilities, ValidationFlags, Validator};
+use naga::ShaderStage;
+use rspirv::binary::Assemble;
+use rspirv::dr::Builder;
+
+use crate::front::spirv_passes::{link_input_outputs, load_module, unwrap_blocks};
+use crate::front::{ShaderInputCompiler, SpirvCompilation};
+use crate::reflect::naga::Naga;
+
+/// Compile shader sources with the naga GLSL frontend.
+///
+/// Unlike glslang, naga is written in pure Rust, which allows shaders to be compiled on targets
+/// such as `wasm32-unknown-unknown`. The naga GLSL frontend does not support every feature used
+/// by slang shaders, so some shaders that compile with glslang will fail to compile with naga.
+impl ShaderInputCompiler<SpirvCompilation> for Naga {
+    fn compile(source: &ShaderSource) -> Result<SpirvCompilation, ShaderCompileError> {
+        compile_spirv(source)
+    }
+}
+
+pub(crate) fn compile_spirv(source: &ShaderSource) -> Result<SpirvCompilation, ShaderCompileError> {
+    let vertex = compile_stage(&source.vertex, ShaderStage::Vertex)?;
+    let fragment = compile_stage(&source.fragment, ShaderStage::Fragment)?;
+
+    let vertex = load_module(&vertex);
+    let fragment = load_module(&fragment);
+    let mut fragment = Builder::new_from_module(fragment);
+    let mut vertex = Builder::new_from_module(vertex);
+
+    unwrap_blocks::UnwrapBlocksPass::new(&mut vertex).do_pass();
+    unwrap_blocks::UnwrapBlocksPass::new(&mut fragment).do_pass();
+
+    let mut pass = link_input_outputs::LinkInputs::new(&mut vertex, &mut fragment, false);
+    pass.do_pass();
+
+    let vertex = vertex.module().assemble();
+    let fragment = fragment.module().assemble();
+
+    Ok(SpirvCompilation { vertex, fragment })
+}
+
+fn compile_stage(source: &str, stage: ShaderStage) -> Result<Vec<u32>, ShaderCompileError> {
+    let source = prepare_source(source);
+    let module = Frontend::default()
+        .parse(&Options::from(stage), &source)
+        .map_err(|e| e.errors)?;
+
+    // Split combined samplers share the binding of their texture.
+    let flags = ValidationFlags::all() - ValidationFlags::BINDINGS;
+    let info = Validator::new(flags, Capabilities::all()).validate(&module)?;
+
+    // The GLSL frontend keeps the Vulkan coordinate space, so it must not be adjusted again.
+    // Reflection relies on the names of uniforms, so debug names must be kept.
+    let options = naga::back::spv::Options {
+        lang_version: (1, 0),
+        flags: WriterFlags::DEBUG,
+        ..Default::default()
+    };
+
+    let pipeline = PipelineOptions {
+        shader_stage: stage,
+        entry_point: "main".to_string(),
+    };
+
+    Ok(naga::back::spv::write_vec(
+        &module,
+        &info,
+        &options,
+        Some(&pipeline),
+    )?)
+}
+
+/// Rewrite preprocessed GLSL into the subset understood by the naga GLSL frontend.
+///
+/// The `#line` markers and the extension enabling them are removed, as naga does not support
+/// them. naga also has no combined image samplers, so each global `sampler*` uniform is split into
+/// a texture and a sampler `_{name}_sampler` sharing its binding, the same way
+/// [`lower_samplers`](crate::front::spirv_passes::lower_samplers) splits them for reflection. A
+/// macro then reconstructs the combined sampler wherever the uniform is used. Arrays of samplers
+/// and samplers passed as function parameters are not rewritten.
+fn prepare_source(source: &str) -> String {
+    let mut output = String::with_capacity(source.len());
+    for line in source.lines() {
+        let directive = line.trim_start();
+        if directive.starts_with("#line ")
+            || directive.starts_with("#extension GL_GOOGLE_cpp_style_line_directive")
+        {
+            output.push('\n');
+            continue;
+        }
+
+        if let Some((layout, texture_type, sampler_type, name)) = parse_combined_sampler(line) {
+            output.push_str(&format!(
+                "{layout} uniform {texture_type} {name};\n\
+                 {layout} uniform sampler _{name}_sampler;\n\
+                 #define {name} {sampler_type}({name}, _{name}_sampler)\n"
+            ));
+            continue;
+        }
+
+        output.push_str(line);
+        output.push('\n');
+    }
+    output
+}
+
+/// Parse a declaration of a combined image sampler uniform in the form
+/// `layout(...) uniform sampler2D Name;`, returning the layout qualifier, the type of the
+/// texture, the type of the combined sampler and the name of the uniform.
+fn parse_combined_sampler(line: &str) -> Option<(&str, String, &str, &str)> {
+    let line = line.trim();
+    let declaration = line.strip_suffix(';')?.trim_end();
+    let layout_end = if declaration.starts_with("layout") {
+        declaration.find(')')? + 1
+    } else {
+        0
+    };
+    let (layout, declaration) = declaration.split_at(layout_end);
+
+    let mut words = declaration.split_whitespace();
+    if words.next()? != "uniform" {
+        return None;
+    }
+    let sampler_type = words.next()?;
+    let name = words.next()?;
+    if words.next().is_some() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return None;
+    }
+
+    let dimension = sampler_type.strip_prefix("sampler")?;
+    if !matches!(
+        dimension,
+        "1D" | "2D" | "3D" | "Cube" | "1DArray" | "2DArray" | "CubeArray"
+    ) {
+        return None;
+    }
+
+    Some((layout, format!("texture{dimension}"), sampler_type, name))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::back::targets::WGSL;
+    use crate::back::{CompileShader, FromCompilation};
+    use crate::front::naga::compile_spirv;
+    use crate::front::{Glslang, ShaderInputCompiler, SpirvCompilation};
+    use crate::reflect::naga::NagaLoweringOptions;
+    use crate::reflect::semantics::{
+        Semantic, ShaderSemantics, TextureSemantics, UniformSemantic, UniqueSemantics,
+    };
+    use crate::reflect::{ReflectShader, ShaderReflection};
+    use librashader_common::map::FastHashMap;
+    use librashader_preprocess::ShaderSource;
+
+    fn reflect(source: &ShaderSource, compilation: SpirvCompilation) -> ShaderReflection {
+        let uniform_semantics = source
+            .parameters
+            .keys()
+            .map(|id| {
+                (
+                    id.clone(),
+                    UniformSemantic::Unique(Semantic {
+                        semantics: UniqueSemantics::FloatParameter,
+                        index: (),
+                    }),
+                )
+            })
+            .collect();
+        let mut texture_semantics = FastHashMap::default();
+        texture_semantics.insert(
+            "Source".into(),
+            Semantic {
+                semantics: TextureSemantics::Source,
+                index: 0,
+            },
+        );
+
+        let mut wgsl = WGSL::from_compilation(compilation).unwrap();
+        let reflection = wgsl
+            .reflect(
+                0,
+                &ShaderSemantics {
+                    uniform_semantics,
+                    texture_semantics,
+                },
+            )
+            .unwrap();
+        wgsl.compile(NagaLoweringOptions {
+            write_pcb_as_ubo: true,
+            sampler_bind_group: 1,
+        })
+        .unwrap();
+        reflection
+    }
+
+    #[test]
+    pub fn compile_shader() {
+        let result = ShaderSource::load("../test/basic.slang").unwrap();
+        let _spirv = compile_spirv(&result).unwrap();
+    }
+
+    #[test]
+    pub fn reflects_like_glslang() {
+        let source = ShaderSource::load("../test/basic.slang").unwrap();
+        let naga = reflect(&source, compile_spirv(&source).unwrap());
+        let glslang = reflect(&source, Glslang::compile(&source).unwrap());
+
+        assert_eq!(format!("{:?}", naga.ubo), format!("{:?}", glslang.ubo));
+
+        // naga removes blocks that are unused in a stage, so only compare the layout.
+        let naga_push = naga.push_constant.unwrap();
+        let glslang_push = glslang.push_constant.unwrap();
+        assert_eq!(naga_push.binding, glslang_push.binding);
+        assert_eq!(naga_push.size, glslang_push.size);
+
+        let source = Semantic {
+            semantics: TextureSemantics::Source,
+            index: 0,
+        };
+        assert_eq!(
+            naga.meta.texture_meta[&source].binding,
+            glslang.meta.texture_meta[&source].binding
+        );
+        for (id, meta) in glslang.meta.parameter_meta.iter() {
+            assert_eq!(naga.meta.parameter_meta[id].offset, meta.offset);
+        }
+        for (semantic, meta) in glslang.meta.unique_meta.iter() {
+            assert_eq!(naga.meta.unique_meta[semantic].offset, meta.offset);
+        }
+    }
+}
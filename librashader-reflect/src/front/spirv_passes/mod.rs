@@ -1,5 +1,7 @@
 pub mod link_input_outputs;
 pub mod lower_samplers;
+#[cfg(feature = "unstable-naga-in")]
+pub mod unwrap_blocks;
 
 // Load SPIR-V as an rspirv module
 pub(crate) fn load_module(words: &[u32]) -> rspirv::dr::Module {
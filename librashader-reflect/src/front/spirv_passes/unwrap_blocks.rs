@@ -0,0 +1,193 @@
+use rspirv::dr::{Builder, Instruction, Operand};
+use rustc_hash::{FxHashMap, FxHashSet};
+use spirv::{Decoration, Op, Word};
+
+/// Removes the wrapper structs naga places around uniform and push constant blocks.
+///
+/// The naga SPIR-V backend wraps the struct of every buffer in an unnamed struct with a single
+/// member, and decorates the wrapper as the `Block`. Reflection expects the block struct to have
+/// the named members of the uniform, so the wrapper is removed and the `Block` decoration moved to
+/// the inner struct.
+pub struct UnwrapBlocksPass<'a> {
+    pub builder: &'a mut Builder,
+}
+
+struct WrappedBlock {
+    wrapper: Word,
+    inner: Word,
+}
+
+impl<'a> UnwrapBlocksPass<'a> {
+    pub fn new(builder: &'a mut Builder) -> Self {
+        Self { builder }
+    }
+
+    pub(crate) fn do_pass(&mut self) {
+        let blocks = self.collect_wrapped_blocks();
+        if blocks.is_empty() {
+            return;
+        }
+
+        let pointers = self.retype_pointers(&blocks);
+        let variables = self.collect_variables(&pointers);
+        self.rewrite_access_chains(&variables);
+        self.remove_wrappers(&blocks);
+    }
+
+    fn find_global_instruction(&self, word: Word) -> Option<&Instruction> {
+        self.builder
+            .module_ref()
+            .global_inst_iter()
+            .find(|i| i.result_id == Some(word))
+    }
+
+    fn collect_wrapped_blocks(&self) -> Vec<WrappedBlock> {
+        let module = self.builder.module_ref();
+        module
+            .annotations
+            .iter()
+            .filter_map(|i| {
+                if i.class.opcode != Op::Decorate {
+                    return None;
+                }
+
+                let &[Operand::IdRef(wrapper), Operand::Decoration(Decoration::Block)] =
+                    i.operands.as_slice()
+                else {
+                    return None;
+                };
+
+                let wrapper_type = self.find_global_instruction(wrapper)?;
+                let &[Operand::IdRef(inner)] = wrapper_type.operands.as_slice() else {
+                    return None;
+                };
+
+                if self.find_global_instruction(inner)?.class.opcode != Op::TypeStruct {
+                    return None;
+                }
+
+                Some(WrappedBlock { wrapper, inner })
+            })
+            .collect()
+    }
+
+    // Point every pointer to a wrapper to its inner struct instead.
+    fn retype_pointers(&mut self, blocks: &[WrappedBlock]) -> FxHashSet<Word> {
+        let wrappers: FxHashMap<Word, Word> = blocks.iter().map(|b| (b.wrapper, b.inner)).collect();
+        let mut pointers = FxHashSet::default();
+
+        for instr in self.builder.module_mut().types_global_values.iter_mut() {
+            if instr.class.opcode != Op::TypePointer {
+                continue;
+            }
+
+            let Some(Operand::IdRef(pointee)) = instr.operands.get_mut(1) else {
+                continue;
+            };
+
+            if let Some(&inner) = wrappers.get(pointee) {
+                *pointee = inner;
+                pointers.extend(instr.result_id);
+            }
+        }
+
+        pointers
+    }
+
+    fn collect_variables(&self, pointers: &FxHashSet<Word>) -> FxHashSet<Word> {
+        self.builder
+            .module_ref()
+            .types_global_values
+            .iter()
+            .filter(|i| i.class.opcode == Op::Variable)
+            .filter(|i| i.result_type.is_some_and(|ty| pointers.contains(&ty)))
+            .filter_map(|i| i.result_id)
+            .collect()
+    }
+
+    // Remove the leading index into the wrapper from every access chain into a block.
+    fn rewrite_access_chains(&mut self, variables: &FxHashSet<Word>) {
+        let mut replaced_ids = FxHashMap::default();
+
+        for function in self.builder.module_mut().functions.iter_mut() {
+            for block in function.blocks.iter_mut() {
+                block.instructions.retain_mut(|instr| {
+                    if !matches!(
+                        instr.class.opcode,
+                        Op::AccessChain | Op::InBoundsAccessChain
+                    ) {
+                        return true;
+                    }
+
+                    let Some(&Operand::IdRef(base)) = instr.operands.first() else {
+                        return true;
+                    };
+
+                    if !variables.contains(&base) {
+                        return true;
+                    }
+
+                    if instr.operands.len() > 2 {
+                        instr.operands.remove(1);
+                        return true;
+                    }
+
+                    if let Some(result_id) = instr.result_id {
+                        replaced_ids.insert(result_id, base);
+                    }
+                    false
+                });
+            }
+        }
+
+        for function in self.builder.module_mut().functions.iter_mut() {
+            for instr in function.all_inst_iter_mut() {
+                for operand in instr.operands.iter_mut() {
+                    let Operand::IdRef(id) = operand else {
+                        continue;
+                    };
+
+                    if let Some(&base) = replaced_ids.get(id) {
+                        *id = base;
+                    }
+                }
+            }
+        }
+    }
+
+    fn remove_wrappers(&mut self, blocks: &[WrappedBlock]) {
+        let wrappers: FxHashMap<Word, Word> = blocks.iter().map(|b| (b.wrapper, b.inner)).collect();
+        let module = self.builder.module_mut();
+
+        module
+            .types_global_values
+            .retain(|i| !i.result_id.is_some_and(|id| wrappers.contains_key(&id)));
+
+        module.debug_names.retain(|i| {
+            !matches!(i.operands.first(), Some(Operand::IdRef(id)) if wrappers.contains_key(id))
+        });
+
+        module.annotations.retain_mut(|i| {
+            let Some(Operand::IdRef(id)) = i.operands.first() else {
+                return true;
+            };
+
+            let Some(&inner) = wrappers.get(id) else {
+                return true;
+            };
+
+            if i.class.opcode == Op::Decorate
+                && matches!(
+                    i.operands.get(1),
+                    Some(Operand::Decoration(Decoration::Block))
+                )
+            {
+                // Move the Block decoration to the inner struct.
+                i.operands[0] = Operand::IdRef(inner);
+                return true;
+            }
+
+            false
+        });
+    }
+}
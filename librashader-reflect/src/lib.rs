@@ -43,9 +43,21 @@
 //! librashader-reflect is designed to be compiler-agnostic. [naga](https://docs.rs/naga/latest/naga/index.html),
 //! a pure-Rust shader compiler, as well as SPIRV-Cross via [SpirvCompilation](crate::front::SpirvCompilation)
 //! is supported.
+//!
+//! ## WebAssembly
+//! glslang is not available on `wasm32`. With the `unstable-naga-in` feature, shader sources are
+//! compiled to SPIR-V with the naga GLSL frontend instead. Building for `wasm32-unknown-unknown`
+//! requires disabling the default features, which use SPIRV-Cross, and enabling `wgsl` and
+//! `unstable-naga-in`.
 #![cfg_attr(not(feature = "stable"), feature(impl_trait_in_assoc_type))]
+
+#[cfg(all(target_arch = "wasm32", not(feature = "unstable-naga-in")))]
+compile_error!("glslang is unavailable on wasm32, enable the `unstable-naga-in` feature to compile shaders with naga instead");
+
 /// Shader codegen backends.
 pub mod back;
+/// Structured shader compiler diagnostics.
+pub mod diagnostics;
 /// Error types.
 pub mod error;
 /// Shader frontend parsers.
@@ -149,8 +149,10 @@ impl CompileShader<GLSL> for CrossReflect<targets::Glsl> {
             texture_fixups.push((name, binding));
         }
 
-        let vertex_compiled = self.vertex.compile(&options)?;
-        let fragment_compiled = self.fragment.compile(&options)?;
+        let CompiledProgram {
+            vertex: vertex_compiled,
+            fragment: fragment_compiled,
+        } = CompiledProgram::compile(self.vertex, self.fragment, &options, "GLSL")?;
 
         Ok(ShaderCompilerOutput {
             vertex: vertex_compiled.to_string(),
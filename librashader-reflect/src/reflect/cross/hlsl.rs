@@ -111,8 +111,10 @@ impl CompileShader<HLSL> for CrossReflect<targets::Hlsl> {
             }
         }
 
-        let vertex_compiled = self.vertex.compile(&options)?;
-        let fragment_compiled = self.fragment.compile(&options)?;
+        let CompiledProgram {
+            vertex: vertex_compiled,
+            fragment: fragment_compiled,
+        } = CompiledProgram::compile(self.vertex, self.fragment, &options, "HLSL")?;
 
         Ok(ShaderCompilerOutput {
             vertex: vertex_compiled.to_string(),
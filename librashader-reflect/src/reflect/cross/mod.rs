@@ -25,6 +25,7 @@ use spirv_cross2::reflect::{
 use spirv_cross2::spirv::Decoration;
 use spirv_cross2::Compiler;
 use spirv_cross2::Module;
+use spirv_cross2::SpirvCrossError;
 use std::fmt::Debug;
 
 /// Reflect shaders under SPIRV-Cross semantics.
@@ -51,6 +52,34 @@ where
     pub fragment: CompiledArtifact<T>,
 }
 
+impl<T> CompiledProgram<T>
+where
+    T: spirv_cross2::compile::CompilableTarget,
+{
+    /// Compile both stages of a program with SPIRV-Cross.
+    ///
+    /// SPIRV-Cross has no way to report warnings. Its C API only has an error callback, which
+    /// spirv-cross2 turns into the returned error, so only the time taken is logged.
+    pub(crate) fn compile(
+        vertex: Compiler<T>,
+        fragment: Compiler<T>,
+        options: &T::Options,
+        target: &str,
+    ) -> Result<Self, SpirvCrossError> {
+        // Instant is unavailable on wasm32-unknown-unknown.
+        #[cfg(not(target_arch = "wasm32"))]
+        let start = std::time::Instant::now();
+        let vertex = vertex.compile(options)?;
+        let fragment = fragment.compile(options)?;
+        #[cfg(not(target_arch = "wasm32"))]
+        log::debug!(
+            "transpiled to {target} with SPIRV-Cross in {:?}",
+            start.elapsed()
+        );
+        Ok(CompiledProgram { vertex, fragment })
+    }
+}
+
 impl ValidateTypeSemantics<TypeInner<'_>> for UniqueSemantics {
     fn validate_type(&self, ty: &TypeInner) -> Option<TypeInfo> {
         let (TypeInner::Vector { .. } | TypeInner::Scalar { .. } | TypeInner::Matrix { .. }) = *ty
@@ -72,7 +101,11 @@ impl ValidateTypeSemantics<TypeInner<'_>> for UniqueSemantics {
             UniqueSemantics::FrameCount
             | UniqueSemantics::Rotation
             | UniqueSemantics::CurrentSubFrame
-            | UniqueSemantics::TotalSubFrames => {
+            | UniqueSemantics::TotalSubFrames
+            | UniqueSemantics::FrameTimeDelta
+            | UniqueSemantics::EnableHDR
+            | UniqueSemantics::ExpandGamut
+            | UniqueSemantics::InverseTonemap => {
                 // Uint32 == width 4
                 if matches!(ty, TypeInner::Scalar( Scalar { kind, size }) if *kind == ScalarKind::Uint && *size == BitWidth::Word)
                 {
@@ -92,7 +125,13 @@ impl ValidateTypeSemantics<TypeInner<'_>> for UniqueSemantics {
                     });
                 }
             }
-            UniqueSemantics::FloatParameter => {
+            UniqueSemantics::FloatParameter
+            | UniqueSemantics::OriginalAspect
+            | UniqueSemantics::OriginalAspectRotated
+            | UniqueSemantics::OriginalFPS
+            | UniqueSemantics::CoreFPS
+            | UniqueSemantics::PaperWhiteNits
+            | UniqueSemantics::MaxNits => {
                 // Float32 == width 4
                 if matches!(ty, TypeInner::Scalar( Scalar { kind, size }) if *kind == ScalarKind::Float && *size == BitWidth::Word)
                 {
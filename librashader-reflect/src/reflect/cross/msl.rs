@@ -87,8 +87,10 @@ impl CompileShader<MSL> for CrossReflect<targets::Msl> {
 
         set_bindings(&mut self.fragment, spirv::ExecutionModel::Fragment)?;
 
-        let vertex_compiled = self.vertex.compile(&options)?;
-        let fragment_compiled = self.fragment.compile(&options)?;
+        let CompiledProgram {
+            vertex: vertex_compiled,
+            fragment: fragment_compiled,
+        } = CompiledProgram::compile(self.vertex, self.fragment, &options, "MSL")?;
 
         Ok(ShaderCompilerOutput {
             vertex: vertex_compiled.to_string(),
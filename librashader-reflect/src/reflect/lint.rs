@@ -0,0 +1,385 @@
+use crate::error::{ShaderCompileError, ShaderReflectError};
+use crate::front::{Glslang, ShaderInputCompiler, SpirvCompilation};
+use crate::reflect::presets::{
+    insert_lut_semantics, insert_parameter_semantics, insert_pass_semantics,
+};
+use crate::reflect::semantics::{
+    Semantic, ShaderSemantics, TextureSemanticMap, TextureSemantics, UniformMemberBlock,
+    UniformSemantic, UniqueSemanticMap, UniqueSemantics,
+};
+use librashader_common::map::{FastHashMap, FastIndexMap, ShortString};
+use librashader_pack::PassResource;
+use librashader_preprocess::ShaderParameter;
+use librashader_presets::TextureMeta;
+use spirv_cross2::reflect::{Resource, TypeInner};
+use spirv_cross2::{targets, Compiler, Module};
+
+/// A potential issue with a shader pass found by linting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ShaderLint {
+    /// A uniform or texture was declared with a name that has no semantics.
+    ///
+    /// Reflection fails with [`SemanticsErrorKind::UnknownSemantics`](crate::error::SemanticsErrorKind::UnknownSemantics)
+    /// if the uniform is used, or if the texture is declared.
+    UnknownSemantics {
+        /// The name of the uniform or texture.
+        name: ShortString,
+        /// The closest known semantic to the name, if any.
+        suggestion: Option<ShortString>,
+    },
+    /// A member of the UBO or push constant block is never read by either stage.
+    UnusedUniform {
+        /// The name of the uniform.
+        name: ShortString,
+        /// The block the uniform is declared in.
+        block: UniformMemberBlock,
+    },
+    /// A parameter declared with `#pragma parameter` is not a member of the UBO or push constant block.
+    UnreferencedParameter(ShortString),
+    /// A texture is bound, but never sampled.
+    UnusedTexture(ShortString),
+}
+
+/// The lints found for a single shader pass.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PassLints {
+    /// The index of the pass in the shader preset.
+    pub pass_number: usize,
+    /// The alias of the pass, if any.
+    pub alias: Option<ShortString>,
+    /// The lints found for the pass.
+    pub lints: Vec<ShaderLint>,
+}
+
+struct DeclaredUniform {
+    name: ShortString,
+    block: UniformMemberBlock,
+    active: bool,
+}
+
+/// Lint a compiled shader pass against the provided semantics and the parameters declared
+/// in its source.
+///
+/// Unlike [`ReflectShader::reflect`](crate::reflect::ReflectShader::reflect), linting does not
+/// stop at the first unknown semantic, and also reports declarations that are never used.
+pub fn lint_pass(
+    compilation: &SpirvCompilation,
+    semantics: &ShaderSemantics,
+    parameters: &FastIndexMap<ShortString, ShaderParameter>,
+) -> Result<Vec<ShaderLint>, ShaderReflectError> {
+    let vertex = Compiler::<targets::None>::new(Module::from_words(&compilation.vertex))?;
+    let fragment = Compiler::<targets::None>::new(Module::from_words(&compilation.fragment))?;
+
+    let mut lints = Vec::new();
+    let mut uniforms: Vec<DeclaredUniform> = Vec::new();
+
+    for ast in [&vertex, &fragment] {
+        let resources = ast.shader_resources()?.all_resources()?;
+        for (block, resource) in resources
+            .uniform_buffers
+            .first()
+            .map(|ubo| (UniformMemberBlock::Ubo, ubo))
+            .into_iter()
+            .chain(
+                resources
+                    .push_constant_buffers
+                    .first()
+                    .map(|push| (UniformMemberBlock::PushConstant, push)),
+            )
+        {
+            collect_block_members(ast, resource, block, &mut uniforms)?;
+        }
+    }
+
+    for uniform in &uniforms {
+        let known = semantics
+            .uniform_semantics
+            .unique_semantic(&uniform.name)
+            .is_some()
+            || semantics
+                .uniform_semantics
+                .texture_semantic(&uniform.name)
+                .is_some();
+
+        if !known {
+            lints.push(ShaderLint::UnknownSemantics {
+                name: uniform.name.clone(),
+                suggestion: suggest_uniform(&uniform.name, semantics),
+            });
+        } else if !uniform.active {
+            lints.push(ShaderLint::UnusedUniform {
+                name: uniform.name.clone(),
+                block: uniform.block,
+            });
+        }
+    }
+
+    let mut parameters: Vec<&ShortString> = parameters.keys().collect();
+    parameters.sort();
+    for parameter in parameters {
+        if !uniforms.iter().any(|uniform| &uniform.name == parameter) {
+            lints.push(ShaderLint::UnreferencedParameter(parameter.clone()));
+        }
+    }
+
+    let declared = fragment.shader_resources()?.all_resources()?;
+    let active = fragment
+        .shader_resources_for_active_variables(fragment.active_interface_variables()?)?
+        .all_resources()?;
+
+    for texture in &declared.sampled_images {
+        let name = ShortString::from(&*texture.name);
+        if semantics
+            .texture_semantics
+            .texture_semantic(&name)
+            .is_none()
+        {
+            let suggestion = suggest_texture(&name, semantics);
+            lints.push(ShaderLint::UnknownSemantics { name, suggestion });
+        } else if !active.sampled_images.iter().any(|t| t.id == texture.id) {
+            lints.push(ShaderLint::UnusedTexture(name));
+        }
+    }
+
+    Ok(lints)
+}
+
+/// Lint the passes of a shader preset.
+///
+/// Each pass is compiled with glslang, then linted against the semantics available to the
+/// entire preset, including pass aliases, lookup textures, and parameters.
+pub fn lint_preset_passes<'a, E>(
+    passes: impl IntoIterator<Item = PassResource>,
+    textures: impl Iterator<Item = &'a TextureMeta>,
+) -> Result<Vec<PassLints>, E>
+where
+    E: From<ShaderCompileError>,
+    E: From<ShaderReflectError>,
+{
+    let mut uniform_semantics: FastHashMap<ShortString, UniformSemantic> = Default::default();
+    let mut texture_semantics: FastHashMap<ShortString, Semantic<TextureSemantics>> =
+        Default::default();
+
+    let compiled = passes
+        .into_iter()
+        .map(|pass| {
+            let compilation = Glslang::compile(&pass.data)?;
+            insert_parameter_semantics(&mut uniform_semantics, &pass.data);
+            Ok::<_, E>((pass, compilation))
+        })
+        .collect::<Result<Vec<_>, E>>()?;
+
+    for (pass, _) in compiled.iter() {
+        insert_pass_semantics(
+            &mut uniform_semantics,
+            &mut texture_semantics,
+            pass.meta.alias.as_ref(),
+            pass.meta.id as usize,
+        );
+        insert_pass_semantics(
+            &mut uniform_semantics,
+            &mut texture_semantics,
+            pass.data.name.as_ref(),
+            pass.meta.id as usize,
+        );
+    }
+
+    insert_lut_semantics(textures, &mut uniform_semantics, &mut texture_semantics);
+
+    let semantics = ShaderSemantics {
+        uniform_semantics,
+        texture_semantics,
+    };
+
+    compiled
+        .into_iter()
+        .map(|(pass, compilation)| {
+            let lints = lint_pass(&compilation, &semantics, &pass.data.parameters)?;
+            Ok(PassLints {
+                pass_number: pass.meta.id as usize,
+                alias: pass.meta.alias.clone(),
+                lints,
+            })
+        })
+        .collect()
+}
+
+fn collect_block_members(
+    ast: &Compiler<targets::None>,
+    resource: &Resource,
+    block: UniformMemberBlock,
+    uniforms: &mut Vec<DeclaredUniform>,
+) -> Result<(), ShaderReflectError> {
+    let ranges = ast.active_buffer_ranges(resource.id)?;
+    let TypeInner::Struct(block_type) = ast.type_description(resource.base_type_id)?.inner else {
+        return Ok(());
+    };
+
+    for member in block_type.members {
+        let Some(name) = member.name else {
+            continue;
+        };
+        let active = ranges
+            .iter()
+            .any(|range| range.index as usize == member.index);
+
+        if let Some(uniform) = uniforms
+            .iter_mut()
+            .find(|uniform| uniform.block == block && *uniform.name == *name)
+        {
+            uniform.active |= active;
+        } else {
+            uniforms.push(DeclaredUniform {
+                name: ShortString::from(&*name),
+                block,
+                active,
+            });
+        }
+    }
+    Ok(())
+}
+
+fn suggest_uniform(name: &str, semantics: &ShaderSemantics) -> Option<ShortString> {
+    let (_, index) = split_index(name);
+    let builtins = TextureSemantics::TEXTURE_SEMANTICS
+        .iter()
+        .filter(|semantic| **semantic != TextureSemantics::User)
+        .map(|semantic| indexed_name(semantic.size_uniform_name(), *semantic, index));
+
+    let candidates = UniqueSemantics::UNIQUE_SEMANTICS
+        .iter()
+        .filter_map(UniqueSemantics::uniform_name)
+        .map(ShortString::from)
+        .chain(builtins)
+        .chain(semantics.uniform_semantics.keys().cloned());
+
+    closest_match(name, candidates)
+}
+
+fn suggest_texture(name: &str, semantics: &ShaderSemantics) -> Option<ShortString> {
+    let (_, index) = split_index(name);
+    let builtins = TextureSemantics::TEXTURE_SEMANTICS
+        .iter()
+        .filter(|semantic| **semantic != TextureSemantics::User)
+        .map(|semantic| indexed_name(semantic.texture_name(), *semantic, index));
+
+    let candidates = builtins.chain(semantics.texture_semantics.keys().cloned());
+    closest_match(name, candidates)
+}
+
+/// Split a trailing index from a name.
+fn split_index(name: &str) -> (&str, &str) {
+    let split = name.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+    name.split_at(split)
+}
+
+/// Get the name of a builtin texture semantic with the given index.
+///
+/// Indexed semantics take the index as a suffix, such as `PassOutput1` or `PassOutputSize1`.
+fn indexed_name(base: &str, semantic: TextureSemantics, index: &str) -> ShortString {
+    let mut name = ShortString::from(base);
+    if semantic.is_indexed() {
+        name.push_str(if index.is_empty() { "0" } else { index });
+    }
+    name
+}
+
+/// Find the candidate closest to the given name, ignoring case.
+fn closest_match(name: &str, candidates: impl Iterator<Item = ShortString>) -> Option<ShortString> {
+    let threshold = std::cmp::max(2, name.len() / 3);
+    let name = name.to_ascii_lowercase();
+
+    candidates
+        .map(|candidate| {
+            let distance = edit_distance(&name, &candidate.to_ascii_lowercase());
+            (distance, candidate)
+        })
+        .filter(|(distance, _)| *distance <= threshold)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// The Levenshtein distance between two ASCII strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.as_bytes();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.bytes().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous + usize::from(ca != *cb);
+            previous = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod test {
+    use crate::front::{Glslang, ShaderInputCompiler};
+    use crate::reflect::lint::{lint_pass, ShaderLint};
+    use crate::reflect::semantics::{
+        Semantic, ShaderSemantics, TextureSemantics, UniformMemberBlock, UniformSemantic,
+        UniqueSemantics,
+    };
+    use librashader_common::map::{FastHashMap, ShortString};
+    use librashader_preprocess::ShaderSource;
+
+    #[test]
+    pub fn lints_shader() {
+        let source = ShaderSource::load("../test/lint.slang").unwrap();
+        let compilation = Glslang::compile(&source).unwrap();
+
+        let mut uniform_semantics: FastHashMap<ShortString, UniformSemantic> = Default::default();
+        for parameter in source.parameters.values() {
+            uniform_semantics.insert(
+                parameter.id.clone(),
+                UniformSemantic::Unique(Semantic {
+                    semantics: UniqueSemantics::FloatParameter,
+                    index: (),
+                }),
+            );
+        }
+
+        let mut texture_semantics: FastHashMap<ShortString, Semantic<TextureSemantics>> =
+            Default::default();
+        texture_semantics.insert(
+            ShortString::from("MaskTexture"),
+            Semantic {
+                semantics: TextureSemantics::User,
+                index: 0,
+            },
+        );
+
+        let semantics = ShaderSemantics {
+            uniform_semantics,
+            texture_semantics,
+        };
+
+        let lints = lint_pass(&compilation, &semantics, &source.parameters).unwrap();
+        assert_eq!(
+            lints,
+            vec![
+                ShaderLint::UnknownSemantics {
+                    name: ShortString::from("FrameCont"),
+                    suggestion: Some(ShortString::from("FrameCount")),
+                },
+                ShaderLint::UnusedUniform {
+                    name: ShortString::from("Unused"),
+                    block: UniformMemberBlock::PushConstant,
+                },
+                ShaderLint::UnreferencedParameter(ShortString::from("Unreferenced")),
+                ShaderLint::UnknownSemantics {
+                    name: ShortString::from("MaskTextur"),
+                    suggestion: Some(ShortString::from("MaskTexture")),
+                },
+                ShaderLint::UnusedTexture(ShortString::from("PassFeedback0")),
+            ]
+        );
+    }
+}
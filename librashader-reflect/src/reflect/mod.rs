@@ -2,6 +2,7 @@ use crate::error::ShaderReflectError;
 use semantics::ShaderSemantics;
 
 /// Reflection via spirv-cross.
+#[cfg(feature = "cross")]
 pub mod cross;
 
 /// Shader semantics and reflection information.
@@ -10,6 +11,13 @@ pub mod semantics;
 /// Reflection helpers for reflecting and compiling shaders as part of a shader preset.
 pub mod presets;
 
+/// Linting of shader semantics and unused bindings.
+#[cfg(feature = "cross")]
+pub mod lint;
+
+#[cfg(feature = "serde")]
+pub mod schema;
+
 mod helper;
 
 /// Reflection via naga.
@@ -1,3 +1,4 @@
+#[cfg(feature = "msl")]
 #[doc(hidden)]
 pub mod msl;
 
@@ -171,7 +172,11 @@ impl ValidateTypeSemantics<&TypeInner> for UniqueSemantics {
             UniqueSemantics::FrameCount
             | UniqueSemantics::Rotation
             | UniqueSemantics::CurrentSubFrame
-            | UniqueSemantics::TotalSubFrames => {
+            | UniqueSemantics::TotalSubFrames
+            | UniqueSemantics::FrameTimeDelta
+            | UniqueSemantics::EnableHDR
+            | UniqueSemantics::ExpandGamut
+            | UniqueSemantics::InverseTonemap => {
                 // Uint32 == width 4
                 if matches!(ty, TypeInner::Scalar( Scalar { kind, width }) if *kind == ScalarKind::Uint && *width == 4)
                 {
@@ -191,7 +196,13 @@ impl ValidateTypeSemantics<&TypeInner> for UniqueSemantics {
                     });
                 }
             }
-            UniqueSemantics::FloatParameter => {
+            UniqueSemantics::FloatParameter
+            | UniqueSemantics::OriginalAspect
+            | UniqueSemantics::OriginalAspectRotated
+            | UniqueSemantics::OriginalFPS
+            | UniqueSemantics::CoreFPS
+            | UniqueSemantics::PaperWhiteNits
+            | UniqueSemantics::MaxNits => {
                 // Float32 == width 4
                 if matches!(ty, TypeInner::Scalar( Scalar { kind, width }) if *kind == ScalarKind::Float && *width == 4)
                 {
@@ -619,13 +630,17 @@ impl NagaReflect {
             return Err(blame.error(SemanticsErrorKind::InvalidResourceType));
         };
 
-        // struct access is AccessIndex
+        // struct access is AccessIndex, either through the pointer or on the loaded struct
         for (_, fun) in module.functions.iter() {
             for (_, expr) in fun.expressions.iter() {
-                let &Expression::AccessIndex { base, index } = expr else {
+                let &Expression::AccessIndex { mut base, index } = expr else {
                     continue;
                 };
 
+                if let &Expression::Load { pointer } = &fun.expressions[base] {
+                    base = pointer;
+                }
+
                 let &Expression::GlobalVariable(base) = &fun.expressions[base] else {
                     continue;
                 };
@@ -9,6 +9,10 @@ use librashader_common::map::{FastHashMap, ShortString};
 use librashader_pack::PassResource;
 use librashader_preprocess::{PreprocessError, ShaderSource};
 use librashader_presets::{ShaderPreset, TextureMeta};
+use rayon::prelude::*;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
 
 /// Artifacts of a reflected and compiled shader pass.
 ///
@@ -30,6 +34,9 @@ use librashader_presets::{ShaderPreset, TextureMeta};
 /// This allows a runtime to not name the backing type of the compiled artifact if not necessary.
 pub type ShaderPassArtifact<T> = (PassResource, CompilerBackend<T>);
 
+/// The compiled artifacts of the passes of a shader preset, and the semantics of the preset.
+pub type CompiledPresetPasses<T> = (Vec<ShaderPassArtifact<T>>, ShaderSemantics);
+
 impl<T: OutputTarget> CompilePresetTarget for T {}
 
 /// Trait for target shading languages that can compile output with
@@ -40,13 +47,7 @@ pub trait CompilePresetTarget: OutputTarget {
     fn compile_preset_passes<'a, I, R, E>(
         passes: impl IntoIterator<Item = PassResource>,
         textures: impl Iterator<Item = &'a TextureMeta>,
-    ) -> Result<
-        (
-            Vec<ShaderPassArtifact<<Self as FromCompilation<I, R>>::Output>>,
-            ShaderSemantics,
-        ),
-        E,
-    >
+    ) -> Result<CompiledPresetPasses<<Self as FromCompilation<I, R>>::Output>, E>
     where
         I: ShaderReflectObject,
         Self: Sized,
@@ -55,9 +56,46 @@ pub trait CompilePresetTarget: OutputTarget {
         E: From<PreprocessError>,
         E: From<ShaderReflectError>,
         E: From<ShaderCompileError>,
+        <Self as FromCompilation<I, R>>::Output: Send,
     {
-        compile_preset_passes::<Self, I, R, E>(passes, textures)
+        compile_preset_passes::<Self, I, R, E>(passes, textures, None)
     }
+
+    /// Compile passes of a shader preset given the applicable shader output target,
+    /// compilation type, and resulting error, notifying `observer` as each pass is compiled.
+    fn compile_preset_passes_with_observer<'a, I, R, E>(
+        passes: impl IntoIterator<Item = PassResource>,
+        textures: impl Iterator<Item = &'a TextureMeta>,
+        observer: PassObserver<'_>,
+    ) -> Result<CompiledPresetPasses<<Self as FromCompilation<I, R>>::Output>, E>
+    where
+        I: ShaderReflectObject,
+        Self: Sized,
+        Self: FromCompilation<I, R>,
+        I::Compiler: ShaderInputCompiler<I>,
+        E: From<PreprocessError>,
+        E: From<ShaderReflectError>,
+        E: From<ShaderCompileError>,
+        <Self as FromCompilation<I, R>>::Output: Send,
+    {
+        compile_preset_passes::<Self, I, R, E>(passes, textures, Some(observer))
+    }
+}
+
+/// An observer that is notified as each pass of a shader preset is compiled.
+///
+/// Passes are compiled in parallel. Each time a pass finishes compiling, the observer is called
+/// with the number of passes compiled so far and the number of passes in the preset. The
+/// observer may be called from any thread of the rayon thread pool. If the observer returns
+/// `false`, compilation stops with [`ShaderCompileError::Cancelled`], and passes that have not
+/// started compiling yet are not compiled.
+pub type PassObserver<'a> = &'a (dyn Fn(usize, usize) -> bool + Sync);
+
+/// The error of a pass compiled on the rayon thread pool, which is converted into the error
+/// type of the caller once every pass has finished so that it does not need to be [`Send`].
+enum PassError {
+    Compile(ShaderCompileError),
+    Reflect(ShaderReflectError),
 }
 
 /// Compile passes of a shader preset given the applicable
@@ -65,13 +103,8 @@ pub trait CompilePresetTarget: OutputTarget {
 fn compile_preset_passes<'a, T, I, R, E>(
     passes: impl IntoIterator<Item = PassResource>,
     textures: impl Iterator<Item = &'a TextureMeta>,
-) -> Result<
-    (
-        Vec<ShaderPassArtifact<<T as FromCompilation<I, R>>::Output>>,
-        ShaderSemantics,
-    ),
-    E,
->
+    observer: Option<PassObserver<'_>>,
+) -> Result<CompiledPresetPasses<<T as FromCompilation<I, R>>::Output>, E>
 where
     I: ShaderReflectObject,
     T: OutputTarget,
@@ -80,30 +113,61 @@ where
     E: From<PreprocessError>,
     E: From<ShaderReflectError>,
     E: From<ShaderCompileError>,
+    <T as FromCompilation<I, R>>::Output: Send,
 {
     let mut uniform_semantics: FastHashMap<ShortString, UniformSemantic> = Default::default();
     let mut texture_semantics: FastHashMap<ShortString, Semantic<TextureSemantics>> =
         Default::default();
 
+    let passes = passes.into_iter().collect::<Vec<_>>();
+    let pass_count = passes.len();
+
+    // Passes are compiled in parallel, so the observer sees how many passes have finished
+    // rather than which pass finished, and a pass that has not started yet is skipped as soon
+    // as the observer asks to cancel.
+    let compiled = AtomicUsize::new(0);
+    let cancelled = AtomicBool::new(false);
+
     let artifacts = passes
-        .into_iter()
-        .map(|shader| {
+        .into_par_iter()
+        .enumerate()
+        .map(|(index, shader)| {
+            if cancelled.load(Ordering::Acquire) {
+                return Err(PassError::Compile(ShaderCompileError::Cancelled));
+            }
+
             let source = &shader.data;
-            let compiled = I::Compiler::compile(source)?;
-            let reflect = T::from_compilation(compiled)?;
-
-            for parameter in source.parameters.values() {
-                uniform_semantics.insert(
-                    parameter.id.clone(),
-                    UniformSemantic::Unique(Semantic {
-                        semantics: UniqueSemantics::FloatParameter,
-                        index: (),
-                    }),
-                );
+            // Instant is unavailable on wasm32-unknown-unknown.
+            #[cfg(not(target_arch = "wasm32"))]
+            let start = Instant::now();
+            let compiled_pass = I::Compiler::compile(source)
+                .map_err(|error| PassError::Compile(error.in_pass(index)))?;
+            let reflect = T::from_compilation(compiled_pass).map_err(PassError::Reflect)?;
+            #[cfg(not(target_arch = "wasm32"))]
+            log::debug!(
+                "compiled pass {} ({:?}) in {:?}",
+                shader.meta.id,
+                source.name,
+                start.elapsed()
+            );
+
+            let finished = compiled.fetch_add(1, Ordering::AcqRel) + 1;
+            if observer.is_some_and(|observer| !observer(finished, pass_count)) {
+                cancelled.store(true, Ordering::Release);
+                return Err(PassError::Compile(ShaderCompileError::Cancelled));
             }
-            Ok::<_, E>((shader, reflect))
+
+            Ok((shader, reflect))
         })
-        .collect::<Result<Vec<(PassResource, CompilerBackend<_>)>, E>>()?;
+        .collect::<Result<Vec<(PassResource, CompilerBackend<_>)>, PassError>>()
+        .map_err(|error| match error {
+            PassError::Compile(error) => E::from(error),
+            PassError::Reflect(error) => E::from(error),
+        })?;
+
+    for (pass, _) in artifacts.iter() {
+        insert_parameter_semantics(&mut uniform_semantics, &pass.data);
+    }
 
     for (pass, _) in artifacts.iter() {
         insert_pass_semantics(
@@ -130,8 +194,24 @@ where
     Ok((artifacts, semantics))
 }
 
+/// Insert the parameters declared by the shader source into the provided semantic map.
+pub(crate) fn insert_parameter_semantics(
+    uniform_semantics: &mut FastHashMap<ShortString, UniformSemantic>,
+    source: &ShaderSource,
+) {
+    for parameter in source.parameters.values() {
+        uniform_semantics.insert(
+            parameter.id.clone(),
+            UniformSemantic::Unique(Semantic {
+                semantics: UniqueSemantics::FloatParameter,
+                index: (),
+            }),
+        );
+    }
+}
+
 /// Insert the available semantics for the input pass config into the provided semantic maps.
-fn insert_pass_semantics(
+pub(crate) fn insert_pass_semantics(
     uniform_semantics: &mut FastHashMap<ShortString, UniformSemantic>,
     texture_semantics: &mut FastHashMap<ShortString, Semantic<TextureSemantics>>,
     alias: Option<&ShortString>,
@@ -188,7 +268,7 @@ fn insert_pass_semantics(
 }
 
 /// Insert the available semantics for the input texture config into the provided semantic maps.
-fn insert_lut_semantics<'a>(
+pub(crate) fn insert_lut_semantics<'a>(
     textures: impl Iterator<Item = &'a TextureMeta>,
     uniform_semantics: &mut FastHashMap<ShortString, UniformSemantic>,
     texture_semantics: &mut FastHashMap<ShortString, Semantic<TextureSemantics>>,
@@ -238,15 +318,7 @@ impl ShaderSemantics {
 
         let source = ShaderSource::load(&config.path)?;
 
-        for parameter in source.parameters.values() {
-            uniform_semantics.insert(
-                parameter.id.clone(),
-                UniformSemantic::Unique(Semantic {
-                    semantics: UniqueSemantics::FloatParameter,
-                    index: (),
-                }),
-            );
-        }
+        insert_parameter_semantics(&mut uniform_semantics, &source);
 
         insert_pass_semantics(
             &mut uniform_semantics,
@@ -272,3 +344,89 @@ impl ShaderSemantics {
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::back::targets::SPIRV;
+    use crate::error::ShaderCompileError;
+    use crate::front::SpirvCompilation;
+    use crate::reflect::cross::SpirvCross;
+    use crate::reflect::presets::CompilePresetTarget;
+    use librashader_pack::PassResource;
+    use librashader_preprocess::ShaderSource;
+    use librashader_presets::ShaderPreset;
+    use std::error::Error;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    type CompileError = Box<dyn Error + Send + Sync>;
+
+    fn passes() -> Vec<PassResource> {
+        ShaderPreset::try_parse("../test/reflect/reflect.slangp")
+            .unwrap()
+            .passes
+            .into_iter()
+            .map(|pass| PassResource {
+                data: ShaderSource::load(&pass.path).unwrap(),
+                meta: pass.meta,
+            })
+            .collect()
+    }
+
+    #[test]
+    pub fn reports_compiled_pass_count() {
+        let calls = AtomicUsize::new(0);
+        let (artifacts, semantics) = SPIRV::compile_preset_passes_with_observer::<
+            SpirvCompilation,
+            SpirvCross,
+            CompileError,
+        >(
+            passes(),
+            [].into_iter(),
+            &|completed, pass_count| {
+                assert!((1..=pass_count).contains(&completed));
+                calls.fetch_add(1, Ordering::Relaxed);
+                true
+            },
+        )
+        .unwrap();
+
+        assert_eq!(calls.load(Ordering::Relaxed), 2);
+        assert_eq!(artifacts.len(), 2);
+        assert_eq!(artifacts[0].0.meta.id, 0);
+        assert_eq!(artifacts[1].0.meta.id, 1);
+        assert!(semantics.uniform_semantics.contains_key("Brightness"));
+        assert!(semantics.uniform_semantics.contains_key("Strength"));
+        assert!(semantics.texture_semantics.contains_key("First"));
+    }
+
+    #[test]
+    pub fn cancels_before_next_pass_starts() {
+        // With a single worker the second pass can only start after the first one finished, so
+        // cancelling from the observer of the first pass must keep the second from compiling.
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap();
+        let calls = AtomicUsize::new(0);
+        let result =
+            pool.install(|| {
+                SPIRV::compile_preset_passes_with_observer::<
+                    SpirvCompilation,
+                    SpirvCross,
+                    CompileError,
+                >(passes(), [].into_iter(), &|_, _| {
+                    calls.fetch_add(1, Ordering::Relaxed);
+                    false
+                })
+            });
+
+        let Err(error) = result else {
+            panic!("compilation was not cancelled");
+        };
+        assert!(matches!(
+            error.downcast_ref(),
+            Some(ShaderCompileError::Cancelled)
+        ));
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+    }
+}
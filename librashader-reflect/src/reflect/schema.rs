@@ -0,0 +1,306 @@
+//! A stable, versioned representation of shader reflection information.
+//!
+//! [`ShaderReflection`] mirrors the internal layout used by the runtimes and may change
+//! between releases. [`PassReflection`] is instead intended for consumption by external
+//! tooling, and serializes to JSON according to the schema returned by [`json_schema`].
+//! The schema is also available in `librashader-reflect/schema/pass-reflection.schema.json`.
+//!
+//! All lists are sorted so that the output is deterministic for the same shader.
+//! Any breaking change to the serialized shape will increment [`REFLECTION_SCHEMA_VERSION`].
+use crate::reflect::semantics::{
+    BindingStage, BufferReflection, MemberOffset, ShaderReflection, TextureSemantics,
+    UniqueSemantics,
+};
+use schemars::schema::RootSchema;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The current version of the reflection schema.
+pub const REFLECTION_SCHEMA_VERSION: u32 = 1;
+
+/// A pipeline stage.
+#[derive(
+    Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum ShaderStage {
+    /// The vertex stage.
+    Vertex,
+    /// The fragment stage.
+    Fragment,
+}
+
+/// The offsets of a uniform within the UBO and push constant block.
+///
+/// A uniform may be present in either or both blocks.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct UniformOffset {
+    /// The offset of the uniform within the UBO in bytes, if present.
+    pub ubo: Option<usize>,
+    /// The offset of the uniform within the push constant block in bytes, if present.
+    pub push_constant: Option<usize>,
+}
+
+/// The layout of the UBO or push constant block.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct BufferLayout {
+    /// The binding point of the buffer, if applicable.
+    ///
+    /// Push constant blocks only have a binding point when they are lowered to a UBO.
+    pub binding: Option<u32>,
+    /// The size of the buffer in bytes, aligned to 16 bytes.
+    pub size: u32,
+    /// The stages the buffer is bound for.
+    pub stages: Vec<ShaderStage>,
+}
+
+/// A uniform bound to a unique semantic.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct UniformLayout {
+    /// The semantic the uniform is bound to.
+    pub semantic: UniqueSemantics,
+    /// The name of the uniform in the shader.
+    pub name: String,
+    /// The offsets of the uniform.
+    pub offset: UniformOffset,
+    /// The number of 32-bit components of the uniform.
+    pub components: u32,
+}
+
+/// A uniform bound to a user parameter.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ParameterLayout {
+    /// The name of the parameter.
+    pub name: String,
+    /// The offsets of the parameter.
+    pub offset: UniformOffset,
+}
+
+/// A `vec4` uniform bound to the size of a texture.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct TextureSizeLayout {
+    /// The semantic of the texture.
+    pub semantic: TextureSemantics,
+    /// The index of the texture for its semantic.
+    pub index: usize,
+    /// The name of the uniform in the shader.
+    pub name: String,
+    /// The offsets of the uniform.
+    pub offset: UniformOffset,
+    /// The stages the uniform is read in.
+    pub stages: Vec<ShaderStage>,
+}
+
+/// A texture sampler binding.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct TextureLayout {
+    /// The semantic of the texture.
+    pub semantic: TextureSemantics,
+    /// The index of the texture for its semantic.
+    pub index: usize,
+    /// The binding point of the texture.
+    pub binding: u32,
+}
+
+/// Reflection information for a single shader pass.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct PassReflection {
+    /// The version of the schema, equal to [`REFLECTION_SCHEMA_VERSION`].
+    pub version: u32,
+    /// The index of the pass in the shader preset.
+    pub pass_number: usize,
+    /// The layout of the UBO, if the shader uses one.
+    pub ubo: Option<BufferLayout>,
+    /// The layout of the push constant block, if the shader uses one.
+    pub push_constant: Option<BufferLayout>,
+    /// Uniforms bound to unique semantics, ordered by semantic.
+    pub uniforms: Vec<UniformLayout>,
+    /// Uniforms bound to user parameters, ordered by name.
+    pub parameters: Vec<ParameterLayout>,
+    /// Uniforms bound to texture sizes, ordered by semantic and index.
+    pub texture_sizes: Vec<TextureSizeLayout>,
+    /// Texture sampler bindings, ordered by semantic and index.
+    pub textures: Vec<TextureLayout>,
+}
+
+impl From<MemberOffset> for UniformOffset {
+    fn from(value: MemberOffset) -> Self {
+        UniformOffset {
+            ubo: value.ubo,
+            push_constant: value.push,
+        }
+    }
+}
+
+fn stages(mask: BindingStage) -> Vec<ShaderStage> {
+    let mut stages = Vec::new();
+    if mask.contains(BindingStage::VERTEX) {
+        stages.push(ShaderStage::Vertex);
+    }
+    if mask.contains(BindingStage::FRAGMENT) {
+        stages.push(ShaderStage::Fragment);
+    }
+    stages
+}
+
+fn buffer_layout<T: Into<Option<u32>> + Copy>(buffer: &BufferReflection<T>) -> BufferLayout {
+    BufferLayout {
+        binding: buffer.binding.into(),
+        size: buffer.size,
+        stages: stages(buffer.stage_mask),
+    }
+}
+
+impl PassReflection {
+    /// Create the versioned reflection information for the given pass.
+    pub fn new(pass_number: usize, reflection: &ShaderReflection) -> Self {
+        let meta = &reflection.meta;
+
+        let mut uniforms: Vec<UniformLayout> = meta
+            .unique_meta
+            .iter()
+            .map(|(semantic, variable)| UniformLayout {
+                semantic: *semantic,
+                name: variable.id.to_string(),
+                offset: variable.offset.into(),
+                components: variable.size,
+            })
+            .collect();
+        uniforms.sort_by_key(|uniform| uniform.semantic);
+
+        let mut parameters: Vec<ParameterLayout> = meta
+            .parameter_meta
+            .iter()
+            .map(|(name, variable)| ParameterLayout {
+                name: name.to_string(),
+                offset: variable.offset.into(),
+            })
+            .collect();
+        parameters.sort_by(|left, right| left.name.cmp(&right.name));
+
+        let mut texture_sizes: Vec<TextureSizeLayout> = meta
+            .texture_size_meta
+            .iter()
+            .map(|(semantic, variable)| TextureSizeLayout {
+                semantic: semantic.semantics,
+                index: semantic.index,
+                name: variable.id.to_string(),
+                offset: variable.offset.into(),
+                stages: stages(variable.stage_mask),
+            })
+            .collect();
+        texture_sizes.sort_by_key(|texture| (texture.semantic, texture.index));
+
+        let mut textures: Vec<TextureLayout> = meta
+            .texture_meta
+            .iter()
+            .map(|(semantic, binding)| TextureLayout {
+                semantic: semantic.semantics,
+                index: semantic.index,
+                binding: binding.binding,
+            })
+            .collect();
+        textures.sort_by_key(|texture| (texture.semantic, texture.index));
+
+        PassReflection {
+            version: REFLECTION_SCHEMA_VERSION,
+            pass_number,
+            ubo: reflection.ubo.as_ref().map(buffer_layout),
+            push_constant: reflection.push_constant.as_ref().map(buffer_layout),
+            uniforms,
+            parameters,
+            texture_sizes,
+            textures,
+        }
+    }
+}
+
+/// Get the JSON schema for [`PassReflection`].
+pub fn json_schema() -> RootSchema {
+    schemars::schema_for!(PassReflection)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::back::targets::SPIRV;
+    use crate::back::FromCompilation;
+    use crate::front::{Glslang, ShaderInputCompiler};
+    use crate::reflect::cross::SpirvCross;
+    use crate::reflect::naga::Naga;
+    use crate::reflect::presets::insert_pass_semantics;
+    use crate::reflect::schema::{json_schema, PassReflection};
+    use crate::reflect::semantics::ShaderSemantics;
+    use crate::reflect::ReflectShader;
+    use librashader_preprocess::ShaderSource;
+    use librashader_presets::ShaderPreset;
+    use std::error::Error;
+
+    const SCHEMA_PATH: &str = "schema/pass-reflection.schema.json";
+
+    #[test]
+    pub fn schema_is_up_to_date() {
+        let generated = serde_json::to_string_pretty(&json_schema()).unwrap() + "\n";
+
+        if std::env::var_os("LIBRASHADER_UPDATE_SCHEMA").is_some() {
+            std::fs::write(SCHEMA_PATH, &generated).unwrap();
+        }
+
+        let checked_in = std::fs::read_to_string(SCHEMA_PATH).unwrap();
+        assert_eq!(
+            checked_in.replace("\r\n", "\n"),
+            generated,
+            "the reflection schema is out of date, run the tests with LIBRASHADER_UPDATE_SCHEMA=1 to regenerate it"
+        );
+    }
+
+    #[test]
+    pub fn reflection_matches_schema() {
+        let schema: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(SCHEMA_PATH).unwrap()).unwrap();
+        let validator = jsonschema::JSONSchema::compile(&schema).unwrap();
+
+        let preset = ShaderPreset::try_parse("../test/reflect/reflect.slangp").unwrap();
+        for (index, pass) in preset.passes.iter().enumerate() {
+            let mut semantics =
+                ShaderSemantics::create_pass_semantics::<Box<dyn Error>>(&preset, index).unwrap();
+            for previous in &preset.passes[..index] {
+                insert_pass_semantics(
+                    &mut semantics.uniform_semantics,
+                    &mut semantics.texture_semantics,
+                    previous.meta.alias.as_ref(),
+                    previous.meta.id as usize,
+                );
+            }
+            let source = ShaderSource::load(&pass.path).unwrap();
+            let compilation = Glslang::compile(&source).unwrap();
+
+            let cross =
+                <SPIRV as FromCompilation<_, SpirvCross>>::from_compilation(compilation.clone())
+                    .unwrap()
+                    .reflect(index, &semantics)
+                    .unwrap();
+            let naga = <SPIRV as FromCompilation<_, Naga>>::from_compilation(compilation)
+                .unwrap()
+                .reflect(index, &semantics)
+                .unwrap();
+
+            let cross = PassReflection::new(index, &cross);
+            let mut naga = PassReflection::new(index, &naga);
+
+            // Naga lowers the push constant block to a UBO, which gives it a binding.
+            if let Some(push_constant) = &mut naga.push_constant {
+                push_constant.binding = None;
+            }
+            assert_eq!(cross, naga);
+
+            let value = serde_json::to_value(&cross).unwrap();
+            if let Err(errors) = validator.validate(&value) {
+                let errors: Vec<String> = errors.map(|error| error.to_string()).collect();
+                panic!("pass {index} does not match the schema: {errors:?}");
+            }
+
+            let roundtrip: PassReflection = serde_json::from_value(value).unwrap();
+            assert_eq!(roundtrip, cross);
+        }
+    }
+}
@@ -28,7 +28,10 @@ pub enum UniformType {
 /// that are always available.
 #[derive(Debug, Ord, PartialOrd, Eq, PartialEq, Copy, Clone, Hash)]
 #[repr(i32)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, schemars::JsonSchema)
+)]
 pub enum UniqueSemantics {
     // mat4, MVP
     /// The Model View Projection matrix for the frame.
@@ -55,9 +58,97 @@ pub enum UniqueSemantics {
     /// A user defined float parameter.
     // float, user defined parameter, array
     FloatParameter = 8,
+    // float, aspect ratio of the original content
+    /// The aspect ratio of the original content.
+    OriginalAspect = 9,
+    // float, aspect ratio of the original content, adjusted for rotation
+    /// The aspect ratio of the original content, inverted if the output is rotated by 90 or 270 degrees.
+    OriginalAspectRotated = 10,
+    // float, refresh rate of the original content
+    /// The refresh rate of the original content, in frames per second.
+    OriginalFPS = 11,
+    // uint, time since the previous frame
+    /// The time elapsed since the previous frame, in microseconds.
+    FrameTimeDelta = 12,
+    // float, rate the core is running at
+    /// The rate at which frames are currently being produced, in frames per second.
+    CoreFPS = 13,
+    // uint, whether HDR output is enabled
+    /// Whether or not the output is HDR (1 = HDR, 0 = SDR).
+    EnableHDR = 14,
+    // float, brightness of paper white in nits
+    /// The brightness of SDR paper white in nits.
+    PaperWhiteNits = 15,
+    // float, peak brightness of the display in nits
+    /// The peak brightness of the display in nits.
+    MaxNits = 16,
+    // uint, whether to expand the gamut when converting to HDR
+    /// Whether or not to expand the Rec. 709 gamut when converting to the HDR colour space.
+    ExpandGamut = 17,
+    // uint, whether to inverse tonemap SDR content
+    /// Whether or not to inverse tonemap SDR content when converting to HDR.
+    InverseTonemap = 18,
 }
 
 impl UniqueSemantics {
+    /// All unique semantics, in order of their discriminant.
+    pub const UNIQUE_SEMANTICS: [UniqueSemantics; 19] = [
+        UniqueSemantics::MVP,
+        UniqueSemantics::Output,
+        UniqueSemantics::FinalViewport,
+        UniqueSemantics::FrameCount,
+        UniqueSemantics::FrameDirection,
+        UniqueSemantics::Rotation,
+        UniqueSemantics::TotalSubFrames,
+        UniqueSemantics::CurrentSubFrame,
+        UniqueSemantics::FloatParameter,
+        UniqueSemantics::OriginalAspect,
+        UniqueSemantics::OriginalAspectRotated,
+        UniqueSemantics::OriginalFPS,
+        UniqueSemantics::FrameTimeDelta,
+        UniqueSemantics::CoreFPS,
+        UniqueSemantics::EnableHDR,
+        UniqueSemantics::PaperWhiteNits,
+        UniqueSemantics::MaxNits,
+        UniqueSemantics::ExpandGamut,
+        UniqueSemantics::InverseTonemap,
+    ];
+
+    /// Get the name of the builtin uniform bound to this semantic.
+    ///
+    /// Returns `None` for [`UniqueSemantics::FloatParameter`], which is bound by
+    /// the name of the parameter instead.
+    pub const fn uniform_name(&self) -> Option<&'static str> {
+        Some(match self {
+            UniqueSemantics::MVP => "MVP",
+            UniqueSemantics::Output => "OutputSize",
+            UniqueSemantics::FinalViewport => "FinalViewportSize",
+            UniqueSemantics::FrameCount => "FrameCount",
+            UniqueSemantics::FrameDirection => "FrameDirection",
+            UniqueSemantics::Rotation => "Rotation",
+            UniqueSemantics::TotalSubFrames => "TotalSubFrames",
+            UniqueSemantics::CurrentSubFrame => "CurrentSubFrame",
+            UniqueSemantics::FloatParameter => return None,
+            UniqueSemantics::OriginalAspect => "OriginalAspect",
+            UniqueSemantics::OriginalAspectRotated => "OriginalAspectRotated",
+            UniqueSemantics::OriginalFPS => "OriginalFPS",
+            UniqueSemantics::FrameTimeDelta => "FrameTimeDelta",
+            UniqueSemantics::CoreFPS => "CoreFPS",
+            UniqueSemantics::EnableHDR => "EnableHDR",
+            UniqueSemantics::PaperWhiteNits => "PaperWhiteNits",
+            UniqueSemantics::MaxNits => "MaxNits",
+            UniqueSemantics::ExpandGamut => "ExpandGamut",
+            UniqueSemantics::InverseTonemap => "InverseTonemap",
+        })
+    }
+
+    /// Get the unique semantic bound to the builtin uniform with the given name.
+    pub fn from_uniform_name(name: &str) -> Option<UniqueSemantics> {
+        Self::UNIQUE_SEMANTICS
+            .into_iter()
+            .find(|semantic| semantic.uniform_name() == Some(name))
+    }
+
     /// Produce a `Semantic` for this `UniqueSemantics`.
     pub const fn semantics(self) -> Semantic<UniqueSemantics, ()> {
         Semantic {
@@ -78,6 +169,16 @@ impl UniqueSemantics {
             UniqueSemantics::TotalSubFrames => UniformType::Unsigned,
             UniqueSemantics::CurrentSubFrame => UniformType::Unsigned,
             UniqueSemantics::FloatParameter => UniformType::Float,
+            UniqueSemantics::OriginalAspect => UniformType::Float,
+            UniqueSemantics::OriginalAspectRotated => UniformType::Float,
+            UniqueSemantics::OriginalFPS => UniformType::Float,
+            UniqueSemantics::FrameTimeDelta => UniformType::Unsigned,
+            UniqueSemantics::CoreFPS => UniformType::Float,
+            UniqueSemantics::EnableHDR => UniformType::Unsigned,
+            UniqueSemantics::PaperWhiteNits => UniformType::Float,
+            UniqueSemantics::MaxNits => UniformType::Float,
+            UniqueSemantics::ExpandGamut => UniformType::Unsigned,
+            UniqueSemantics::InverseTonemap => UniformType::Unsigned,
         }
     }
 
@@ -93,6 +194,16 @@ impl UniqueSemantics {
             UniqueSemantics::TotalSubFrames => "TotalSubFrames",
             UniqueSemantics::CurrentSubFrame => "CurrentSubFrame",
             UniqueSemantics::FloatParameter => "FloatParameter",
+            UniqueSemantics::OriginalAspect => "OriginalAspect",
+            UniqueSemantics::OriginalAspectRotated => "OriginalAspectRotated",
+            UniqueSemantics::OriginalFPS => "OriginalFPS",
+            UniqueSemantics::FrameTimeDelta => "FrameTimeDelta",
+            UniqueSemantics::CoreFPS => "CoreFPS",
+            UniqueSemantics::EnableHDR => "EnableHDR",
+            UniqueSemantics::PaperWhiteNits => "PaperWhiteNits",
+            UniqueSemantics::MaxNits => "MaxNits",
+            UniqueSemantics::ExpandGamut => "ExpandGamut",
+            UniqueSemantics::InverseTonemap => "InverseTonemap",
         }
     }
 }
@@ -108,7 +219,10 @@ impl Display for UniqueSemantics {
 /// Texture semantics are used to relate both texture samplers and `*Size` uniforms.
 #[derive(Debug, Ord, PartialOrd, Eq, PartialEq, Copy, Clone, Hash)]
 #[repr(i32)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, schemars::JsonSchema)
+)]
 pub enum TextureSemantics {
     /// The original input of the filter chain.
     Original = 0,
@@ -427,41 +541,7 @@ impl UniqueSemanticMap for FastHashMap<ShortString, UniformSemantic> {
     fn unique_semantic(&self, name: &str) -> Option<Semantic<UniqueSemantics, ()>> {
         match self.get(name) {
             // existing uniforms in the semantic map have priority
-            None => match name {
-                "MVP" => Some(Semantic {
-                    semantics: UniqueSemantics::MVP,
-                    index: (),
-                }),
-                "OutputSize" => Some(Semantic {
-                    semantics: UniqueSemantics::Output,
-                    index: (),
-                }),
-                "FinalViewportSize" => Some(Semantic {
-                    semantics: UniqueSemantics::FinalViewport,
-                    index: (),
-                }),
-                "FrameCount" => Some(Semantic {
-                    semantics: UniqueSemantics::FrameCount,
-                    index: (),
-                }),
-                "FrameDirection" => Some(Semantic {
-                    semantics: UniqueSemantics::FrameDirection,
-                    index: (),
-                }),
-                "Rotation" => Some(Semantic {
-                    semantics: UniqueSemantics::Rotation,
-                    index: (),
-                }),
-                "TotalSubFrames" => Some(Semantic {
-                    semantics: UniqueSemantics::TotalSubFrames,
-                    index: (),
-                }),
-                "CurrentSubFrame" => Some(Semantic {
-                    semantics: UniqueSemantics::CurrentSubFrame,
-                    index: (),
-                }),
-                _ => None,
-            },
+            None => UniqueSemantics::from_uniform_name(name).map(UniqueSemantics::semantics),
             Some(UniformSemantic::Unique(variable)) => Some(*variable),
             Some(UniformSemantic::Texture(_)) => None,
         }
@@ -652,6 +732,46 @@ mod serde_impl {
                     semantics: UniqueSemantics::CurrentSubFrame,
                     index: (),
                 },
+                "OriginalAspect" => Semantic {
+                    semantics: UniqueSemantics::OriginalAspect,
+                    index: (),
+                },
+                "OriginalAspectRotated" => Semantic {
+                    semantics: UniqueSemantics::OriginalAspectRotated,
+                    index: (),
+                },
+                "OriginalFPS" => Semantic {
+                    semantics: UniqueSemantics::OriginalFPS,
+                    index: (),
+                },
+                "FrameTimeDelta" => Semantic {
+                    semantics: UniqueSemantics::FrameTimeDelta,
+                    index: (),
+                },
+                "CoreFPS" => Semantic {
+                    semantics: UniqueSemantics::CoreFPS,
+                    index: (),
+                },
+                "EnableHDR" => Semantic {
+                    semantics: UniqueSemantics::EnableHDR,
+                    index: (),
+                },
+                "PaperWhiteNits" => Semantic {
+                    semantics: UniqueSemantics::PaperWhiteNits,
+                    index: (),
+                },
+                "MaxNits" => Semantic {
+                    semantics: UniqueSemantics::MaxNits,
+                    index: (),
+                },
+                "ExpandGamut" => Semantic {
+                    semantics: UniqueSemantics::ExpandGamut,
+                    index: (),
+                },
+                "InverseTonemap" => Semantic {
+                    semantics: UniqueSemantics::InverseTonemap,
+                    index: (),
+                },
                 _ => return Err(E::custom(format!("unknown unique semantic {v}"))),
             })
         }
@@ -675,3 +795,28 @@ mod serde_impl {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::reflect::semantics::UniqueSemantics;
+
+    #[test]
+    fn unique_semantics_are_in_discriminant_order() {
+        for (index, semantic) in UniqueSemantics::UNIQUE_SEMANTICS.iter().enumerate() {
+            assert_eq!(*semantic as usize, index);
+        }
+        assert_eq!(
+            UniqueSemantics::UNIQUE_SEMANTICS.len(),
+            UniqueSemantics::InverseTonemap as usize + 1
+        );
+    }
+
+    #[test]
+    fn unique_semantics_round_trip_uniform_names() {
+        for semantic in UniqueSemantics::UNIQUE_SEMANTICS {
+            if let Some(name) = semantic.uniform_name() {
+                assert_eq!(UniqueSemantics::from_uniform_name(name), Some(semantic));
+            }
+        }
+    }
+}
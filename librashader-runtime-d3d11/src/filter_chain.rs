@@ -8,6 +8,7 @@ use librashader_reflect::back::{CompileReflectShader, CompileShader};
 use librashader_reflect::front::SpirvCompilation;
 use librashader_reflect::reflect::semantics::ShaderSemantics;
 use librashader_reflect::reflect::ReflectShader;
+use librashader_runtime::hdr::{append_hdr_output_pass, HdrOutputEncoding};
 use librashader_runtime::image::{ImageError, LoadedTexture, UVDirection, RGBA8};
 use std::collections::VecDeque;
 
@@ -20,6 +21,7 @@ use crate::framebuffer::OwnedImage;
 use crate::graphics_pipeline::D3D11State;
 use crate::luts::LutTexture;
 use crate::options::{FilterChainOptionsD3D11, FrameOptionsD3D11};
+use crate::profiling::D3D11Timestamps;
 use crate::samplers::SamplerSet;
 use crate::util::d3d11_compile_bound_shader;
 use crate::{error, util};
@@ -28,9 +30,13 @@ use librashader_cache::CachedCompilation;
 use librashader_common::GetSize;
 use librashader_presets::context::VideoDriver;
 use librashader_reflect::reflect::cross::SpirvCross;
-use librashader_reflect::reflect::presets::{CompilePresetTarget, ShaderPassArtifact};
+use librashader_reflect::reflect::presets::{
+    CompilePresetTarget, PassObserver, ShaderPassArtifact,
+};
 use librashader_runtime::binding::{BindingUtil, TextureInput};
+use librashader_runtime::compare;
 use librashader_runtime::framebuffer::FramebufferInit;
+use librashader_runtime::profiling::FrameProfiler;
 use librashader_runtime::quad::QuadType;
 use librashader_runtime::render_target::RenderTarget;
 use librashader_runtime::scaling::ScaleFramebuffer;
@@ -48,16 +54,22 @@ use windows::Win32::Graphics::Dxgi::Common::DXGI_FORMAT_R8G8B8A8_UNORM;
 pub struct FilterChainD3D11 {
     pub(crate) common: FilterCommon,
     passes: Vec<FilterPass>,
+    compare_pass: FilterPass,
     output_framebuffers: Box<[OwnedImage]>,
     feedback_framebuffers: Box<[OwnedImage]>,
     history_framebuffers: VecDeque<OwnedImage>,
     state: D3D11State,
     default_options: FrameOptionsD3D11,
     draw_last_pass_feedback: bool,
+    disable_cache: bool,
+    hdr_output: Option<HdrOutputEncoding>,
+    lut_identities: Box<[LutIdentity]>,
+    pending_passes: Option<CompiledPasses<Vec<FilterPass>>>,
+    profiler: FrameProfiler<D3D11Timestamps>,
 }
 
 pub(crate) struct Direct3D11 {
-    pub(crate) _device: ID3D11Device,
+    pub(crate) device: ID3D11Device,
     pub(crate) immediate_context: ID3D11DeviceContext,
 }
 
@@ -90,18 +102,21 @@ mod compile {
         shaders: Vec<PassResource>,
         textures: &[TextureResource],
         disable_cache: bool,
+        observer: Option<PassObserver<'_>>,
     ) -> Result<(Vec<ShaderPassMeta>, ShaderSemantics), FilterChainError> {
+        let observer = observer.unwrap_or(&|_, _| true);
         let (passes, semantics) = if !disable_cache {
-            HLSL::compile_preset_passes::<
+            HLSL::compile_preset_passes_with_observer::<
                 CachedCompilation<SpirvCompilation>,
                 SpirvCross,
                 FilterChainError,
-            >(shaders, textures.iter().map(|t| &t.meta))?
+            >(shaders, textures.iter().map(|t| &t.meta), observer)?
         } else {
-            HLSL::compile_preset_passes::<SpirvCompilation, SpirvCross, FilterChainError>(
-                shaders,
-                textures.iter().map(|t| &t.meta),
-            )?
+            HLSL::compile_preset_passes_with_observer::<
+                SpirvCompilation,
+                SpirvCross,
+                FilterChainError,
+            >(shaders, textures.iter().map(|t| &t.meta), observer)?
         };
 
         Ok((passes, semantics))
@@ -111,6 +126,7 @@ mod compile {
 use compile::{compile_passes, ShaderPassMeta};
 use librashader_pack::{ShaderPresetPack, TextureResource};
 use librashader_runtime::parameters::RuntimeParameters;
+use librashader_runtime::reload::{CompiledPasses, LutIdentity, LutReuse};
 
 impl FilterChainD3D11 {
     /// Load the shader preset at the given path into a filter chain.
@@ -191,18 +207,62 @@ impl FilterChainD3D11 {
         ctx: &ID3D11DeviceContext,
         options: Option<&FilterChainOptionsD3D11>,
     ) -> error::Result<FilterChainD3D11> {
+        unsafe {
+            Self::load_from_pack_deferred_with_observer(preset, device, ctx, options, &|_, _| true)
+        }
+    }
+
+    /// Load a filter chain from a pre-parsed and loaded `ShaderPresetPack`, deferring and GPU-side initialization
+    /// to the caller. This function is therefore requires no external synchronization of the
+    /// immediate context, as long as the immediate context is not used as the input context,
+    /// nor of the device, as long as the device is not single-threaded only.
+    ///
+    /// `observer` is called after each pass is compiled with the index of the pass and the total
+    /// number of passes. If it returns `false`, loading stops with
+    /// [`ShaderCompileError::Cancelled`](librashader_reflect::error::ShaderCompileError::Cancelled).
+    ///
+    /// ## Safety
+    /// The provided context must either be immediate, or immediately submitted after this function
+    /// returns, **before drawing frames**, or lookup textures will fail to load and the filter chain
+    /// will be in an invalid state.
+    ///
+    /// If the context is deferred, it must be ready for command recording, and have no prior commands
+    /// recorded. No commands shall be recorded after, the caller must immediately call [`FinishCommandList`](https://learn.microsoft.com/en-us/windows/win32/api/d3d11/nf-d3d11-id3d11devicecontext-finishcommandlist)
+    /// and execute the command list on the immediate context after this function returns.
+    ///
+    /// If the context is immediate, then access to the immediate context requires external synchronization.
+    pub unsafe fn load_from_pack_deferred_with_observer(
+        mut preset: ShaderPresetPack,
+        device: &ID3D11Device,
+        ctx: &ID3D11DeviceContext,
+        options: Option<&FilterChainOptionsD3D11>,
+        observer: PassObserver<'_>,
+    ) -> error::Result<FilterChainD3D11> {
+        if let Some(encoding) = options.and_then(|o| o.hdr_output) {
+            append_hdr_output_pass(&mut preset, encoding)?;
+        }
         let disable_cache = options.map_or(false, |o| o.disable_cache);
 
-        let (passes, semantics) = compile_passes(preset.passes, &preset.textures, disable_cache)?;
+        let (passes, semantics) = compile_passes(
+            preset.passes,
+            &preset.textures,
+            disable_cache,
+            Some(observer),
+        )?;
 
         let samplers = SamplerSet::new(device)?;
 
         // initialize passes
         let filters = FilterChainD3D11::init_passes(device, passes, &semantics, disable_cache)?;
+        let compare_pass = compare::compile_compare_pass(|passes| {
+            let (passes, semantics) = compile_passes(passes, &[], disable_cache, None)?;
+            FilterChainD3D11::init_passes(device, passes, &semantics, disable_cache)
+        })?;
 
         let immediate_context = unsafe { device.GetImmediateContext()? };
 
         // load luts
+        let lut_identities = LutIdentity::from_textures(&preset.textures);
         let luts = FilterChainD3D11::load_luts(device, &ctx, preset.textures)?;
 
         let framebuffer_gen =
@@ -226,18 +286,25 @@ impl FilterChainD3D11 {
 
         let draw_quad = DrawQuad::new(device)?;
         let state = D3D11State::new(device)?;
+        let config = RuntimeParameters::new(
+            preset.pass_count as usize,
+            preset.parameters,
+            filters.iter().map(|f| &f.source),
+        );
+
         Ok(FilterChainD3D11 {
             draw_last_pass_feedback: framebuffer_init.uses_final_pass_as_feedback(),
             passes: filters,
+            compare_pass,
             output_framebuffers,
             feedback_framebuffers,
             history_framebuffers,
             common: FilterCommon {
                 d3d11: Direct3D11 {
-                    _device: device.clone(),
+                    device: device.clone(),
                     immediate_context,
                 },
-                config: RuntimeParameters::new(preset.pass_count as usize, preset.parameters),
+                config,
                 disable_mipmaps: options.map_or(false, |o| o.force_no_mipmaps),
                 luts,
                 samplers,
@@ -248,8 +315,88 @@ impl FilterChainD3D11 {
             },
             state,
             default_options: Default::default(),
+            disable_cache,
+            hdr_output: options.and_then(|o| o.hdr_output),
+            lut_identities,
+            pending_passes: None,
+            profiler: FrameProfiler::new(3),
         })
     }
+
+    /// Reload the filter chain with a new shader preset.
+    ///
+    /// The passes of the preset are compiled immediately, then swapped in at the next call to
+    /// [`frame`](Self::frame). History and feedback framebuffers are kept, and lookup textures that
+    /// are unchanged from the current preset are reused. Runtime parameters are reset to the
+    /// values of the new preset once the passes are swapped in.
+    pub fn reload(&mut self, mut preset: ShaderPresetPack) -> error::Result<()> {
+        if let Some(encoding) = self.hdr_output {
+            append_hdr_output_pass(&mut preset, encoding)?;
+        }
+        let device = &self.common.d3d11.device;
+        let compiled = CompiledPasses::new(preset, |passes, textures| {
+            let (passes, semantics) = compile_passes(passes, textures, self.disable_cache, None)?;
+            FilterChainD3D11::init_passes(device, passes, &semantics, self.disable_cache)
+        })?;
+
+        self.pending_passes = Some(compiled);
+        Ok(())
+    }
+
+    fn swap_passes(
+        &mut self,
+        compiled: CompiledPasses<Vec<FilterPass>>,
+        ctx: &ID3D11DeviceContext,
+    ) -> error::Result<()> {
+        let CompiledPasses {
+            passes,
+            textures,
+            lut_identities,
+            parameters,
+            pass_count,
+        } = compiled;
+
+        let device = &self.common.d3d11.device;
+        let mut luts = LutReuse::new(
+            std::mem::take(&mut self.common.luts),
+            &self.lut_identities,
+            &lut_identities,
+            textures,
+            drop,
+        );
+        let loaded = FilterChainD3D11::load_luts(device, ctx, luts.take_textures())?;
+        self.common.luts = luts.finish(loaded);
+        self.lut_identities = lut_identities;
+
+        let framebuffer_gen =
+            || OwnedImage::new(device, Size::new(1, 1), ImageFormat::R8G8B8A8Unorm, false);
+        let input_gen = || None;
+        let framebuffer_init = FramebufferInit::new(
+            passes.iter().map(|f| &f.reflection.meta),
+            &framebuffer_gen,
+            &input_gen,
+        );
+
+        let (output_framebuffers, output_textures) = framebuffer_init
+            .reuse_output_framebuffers(std::mem::take(&mut self.output_framebuffers), drop)?;
+        let (feedback_framebuffers, feedback_textures) = framebuffer_init
+            .reuse_output_framebuffers(std::mem::take(&mut self.feedback_framebuffers), drop)?;
+        let (history_framebuffers, history_textures) =
+            framebuffer_init.reuse_history(std::mem::take(&mut self.history_framebuffers), drop)?;
+
+        self.output_framebuffers = output_framebuffers;
+        self.feedback_framebuffers = feedback_framebuffers;
+        self.history_framebuffers = history_framebuffers;
+        self.common.output_textures = output_textures;
+        self.common.feedback_textures = feedback_textures;
+        self.common.history_textures = history_textures;
+        self.common.config =
+            RuntimeParameters::new(pass_count, parameters, passes.iter().map(|f| &f.source));
+        self.draw_last_pass_feedback = framebuffer_init.uses_final_pass_as_feedback();
+        self.passes = passes;
+
+        Ok(())
+    }
 }
 
 impl FilterChainD3D11 {
@@ -284,7 +431,9 @@ impl FilterChainD3D11 {
 
         let builder_fn = |(index, (config, mut reflect)): (usize, ShaderPassMeta)| {
             let reflection = reflect.reflect(index, semantics)?;
-            let hlsl = reflect.compile(None)?;
+            let hlsl = reflect
+                .compile(None)
+                .map_err(|error| error.in_pass(index))?;
 
             let (vs, vertex_dxbc) = cache_shader_object(
                 "dxbc",
@@ -443,13 +592,20 @@ impl FilterChainD3D11 {
         frame_count: usize,
         options: Option<&FrameOptionsD3D11>,
     ) -> error::Result<()> {
-        let max = std::cmp::min(self.passes.len(), self.common.config.passes_enabled());
-
         // Need to clone this because pushing history needs a mutable borrow.
         let immediate_context = &self.common.d3d11.immediate_context.clone();
         let ctx = ctx.unwrap_or(immediate_context);
 
-        let passes = &mut self.passes[0..max];
+        if let Some(compiled) = self.pending_passes.take() {
+            self.swap_passes(compiled, ctx)?;
+        }
+
+        self.common.config.set_automation_frame(frame_count);
+
+        let max = std::cmp::min(self.passes.len(), self.common.config.passes_enabled());
+        let range = options.unwrap_or(&self.default_options).pass_range(max);
+
+        let passes = &mut self.passes[0..range.end];
         if let Some(options) = options {
             if options.clear_history {
                 for framebuffer in &mut self.history_framebuffers {
@@ -510,6 +666,25 @@ impl FilterChainD3D11 {
             )?);
         }
 
+        let timestamps = self.profiler.begin_frame(
+            frame_count,
+            passes
+                .iter()
+                .map(|pass| pass.meta.alias.as_ref().map(|alias| alias.as_str())),
+            |count| {
+                D3D11Timestamps::new(
+                    &self.common.d3d11.device,
+                    &self.common.d3d11.immediate_context,
+                    count,
+                )
+            },
+        )?;
+
+        if let Some(timestamps) = &timestamps {
+            timestamps.begin(ctx);
+            timestamps.write(ctx, 0);
+        }
+
         let passes_len = passes.len();
         let (pass, last) = passes.split_at_mut(passes_len - 1);
 
@@ -517,6 +692,14 @@ impl FilterChainD3D11 {
         self.common.draw_quad.bind_vbo_for_frame(ctx);
 
         for (index, pass) in pass.iter_mut().enumerate() {
+            // skip passes before the range, so that the first pass reads the original image.
+            if index < range.start {
+                if let Some(timestamps) = &timestamps {
+                    timestamps.write(ctx, index + 1);
+                }
+                continue;
+            }
+
             source.filter = pass.meta.filter;
             source.wrap_mode = pass.meta.wrap_mode;
             let target = &self.output_framebuffers[index];
@@ -533,6 +716,10 @@ impl FilterChainD3D11 {
                 QuadType::Offscreen,
             )?;
 
+            if let Some(timestamps) = &timestamps {
+                timestamps.write(ctx, index + 1);
+            }
+
             source = InputTexture {
                 view: target.create_shader_resource_view()?,
                 filter: pass.meta.filter,
@@ -548,10 +735,10 @@ impl FilterChainD3D11 {
             source.filter = pass.meta.filter;
             source.wrap_mode = pass.meta.wrap_mode;
 
-            // Draw to output_framebuffers for proper handling of feedback.
-
-            let feedback_target = &self.output_framebuffers[index];
-            if self.draw_last_pass_feedback {
+            if options.compare_enabled() {
+                // draw the last pass at the size of the viewport, to compare with the original image.
+                let target = &mut self.output_framebuffers[index];
+                compare::scale_compare_source(target, pass, viewport.size, &())?;
                 pass.draw(
                     &ctx,
                     index,
@@ -561,26 +748,72 @@ impl FilterChainD3D11 {
                     viewport,
                     &original,
                     &source,
-                    RenderTarget::viewport_with_output(
-                        &feedback_target.create_render_target_view()?,
+                    RenderTarget::identity(&target.create_render_target_view()?)?,
+                    QuadType::Offscreen,
+                )?;
+
+                let source = InputTexture {
+                    view: target.create_shader_resource_view()?,
+                    filter: self.compare_pass.meta.filter,
+                    wrap_mode: self.compare_pass.meta.wrap_mode,
+                };
+                compare::set_compare_uniforms(
+                    &mut self.compare_pass.source.parameters,
+                    options.compare_split,
+                    options.compare_line_width,
+                );
+                self.compare_pass.draw(
+                    &ctx,
+                    passes_len,
+                    &self.common,
+                    self.compare_pass.meta.get_frame_count(frame_count),
+                    options,
+                    viewport,
+                    &original,
+                    &source,
+                    RenderTarget::viewport(viewport),
+                    QuadType::Final,
+                )?;
+            } else {
+                // Draw to output_framebuffers for proper handling of feedback.
+
+                let feedback_target = &self.output_framebuffers[index];
+                if self.draw_last_pass_feedback {
+                    pass.draw(
+                        &ctx,
+                        index,
+                        &self.common,
+                        pass.meta.get_frame_count(frame_count),
+                        options,
                         viewport,
-                    ),
+                        &original,
+                        &source,
+                        RenderTarget::viewport_with_output(
+                            &feedback_target.create_render_target_view()?,
+                            viewport,
+                        ),
+                        QuadType::Final,
+                    )?;
+                }
+
+                pass.draw(
+                    &ctx,
+                    index,
+                    &self.common,
+                    pass.meta.get_frame_count(frame_count),
+                    options,
+                    viewport,
+                    &original,
+                    &source,
+                    RenderTarget::viewport(viewport),
                     QuadType::Final,
                 )?;
             }
 
-            pass.draw(
-                &ctx,
-                index,
-                &self.common,
-                pass.meta.get_frame_count(frame_count),
-                options,
-                viewport,
-                &original,
-                &source,
-                RenderTarget::viewport(viewport),
-                QuadType::Final,
-            )?;
+            if let Some(timestamps) = &timestamps {
+                timestamps.write(ctx, passes_len);
+                timestamps.end(ctx);
+            }
         }
 
         std::mem::swap(
@@ -595,3 +828,56 @@ impl FilterChainD3D11 {
         Ok(())
     }
 }
+
+use librashader_runtime::impl_filter_chain_debug;
+impl_filter_chain_debug!(FilterChainD3D11, ID3D11ShaderResourceView, |texture| {
+    texture.view.clone()
+});
+
+use librashader_runtime::impl_filter_chain_profiling;
+impl_filter_chain_profiling!(FilterChainD3D11);
+
+impl librashader_runtime::filter_chain::FilterChain for FilterChainD3D11 {
+    type Device<'a> = &'a ID3D11Device;
+    type InputImage<'a> = &'a ID3D11ShaderResourceView;
+    type OutputView<'a> = &'a ID3D11RenderTargetView;
+    type CommandContext<'a> = Option<&'a ID3D11DeviceContext>;
+    type FilterChainOptions = FilterChainOptionsD3D11;
+    type FrameOptions = FrameOptionsD3D11;
+    type Error = FilterChainError;
+
+    unsafe fn load_from_pack(
+        preset: ShaderPresetPack,
+        device: Self::Device<'_>,
+        options: Option<&Self::FilterChainOptions>,
+    ) -> error::Result<Self> {
+        unsafe { FilterChainD3D11::load_from_pack(preset, device, options) }
+    }
+
+    unsafe fn load_from_preset(
+        preset: ShaderPreset,
+        device: Self::Device<'_>,
+        options: Option<&Self::FilterChainOptions>,
+    ) -> error::Result<Self> {
+        unsafe { FilterChainD3D11::load_from_preset(preset, device, options) }
+    }
+
+    unsafe fn load_from_path(
+        path: impl AsRef<Path>,
+        device: Self::Device<'_>,
+        options: Option<&Self::FilterChainOptions>,
+    ) -> error::Result<Self> {
+        unsafe { FilterChainD3D11::load_from_path(path, device, options) }
+    }
+
+    unsafe fn frame<'a>(
+        &mut self,
+        input: Self::InputImage<'a>,
+        viewport: &Viewport<Self::OutputView<'a>>,
+        ctx: Self::CommandContext<'a>,
+        frame_count: usize,
+        options: Option<&Self::FrameOptions>,
+    ) -> error::Result<()> {
+        unsafe { FilterChainD3D11::frame(self, ctx, input, viewport, frame_count, options) }
+    }
+}
@@ -125,6 +125,15 @@ impl FilterPass {
                 rotation: options.rotation,
                 total_subframes: options.total_subframes,
                 current_subframe: options.current_subframe,
+                aspect_ratio: options.aspect_ratio,
+                original_fps: options.original_fps,
+                frametime_delta: options.frametime_delta,
+                core_fps: options.core_fps,
+                enable_hdr: options.enable_hdr,
+                paper_white_nits: options.paper_white_nits,
+                max_nits: options.max_nits,
+                expand_gamut: options.expand_gamut,
+                inverse_tonemap: options.inverse_tonemap,
                 frame_direction: options.frame_direction,
                 framebuffer_size: fb_size,
                 viewport_size,
@@ -12,6 +12,7 @@ mod filter_chain;
 mod filter_pass;
 mod framebuffer;
 mod graphics_pipeline;
+mod profiling;
 mod samplers;
 mod texture;
 mod util;
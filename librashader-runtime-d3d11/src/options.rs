@@ -1,5 +1,6 @@
 //! Direct3D 11 shader runtime options.
 
+use librashader_runtime::hdr::HdrOutputEncoding;
 use librashader_runtime::impl_default_frame_options;
 impl_default_frame_options!(FrameOptionsD3D11);
 
@@ -13,4 +14,8 @@ pub struct FilterChainOptionsD3D11 {
     /// Disable the shader object cache. Shaders will be
     /// recompiled rather than loaded from the cache.
     pub disable_cache: bool,
+    /// Append the built-in HDR output pass, which encodes the output of the shader preset for
+    /// an HDR output. The filter chain should then be drawn to an output with the format given
+    /// by [`HdrOutputEncoding::output_format`].
+    pub hdr_output: Option<HdrOutputEncoding>,
 }
@@ -0,0 +1,107 @@
+use crate::error::{assume_d3d11_init, Result};
+use librashader_runtime::profiling::{timestamp_durations, PassTimer};
+use std::time::Duration;
+use windows::Win32::Graphics::Direct3D11::{
+    ID3D11Device, ID3D11DeviceContext, ID3D11Query, D3D11_ASYNC_GETDATA_DONOTFLUSH, D3D11_QUERY,
+    D3D11_QUERY_DATA_TIMESTAMP_DISJOINT, D3D11_QUERY_DESC, D3D11_QUERY_TIMESTAMP,
+    D3D11_QUERY_TIMESTAMP_DISJOINT,
+};
+
+/// Timestamps written before the first pass and after each pass of a frame.
+pub(crate) struct D3D11Timestamps {
+    immediate_context: ID3D11DeviceContext,
+    disjoint: ID3D11Query,
+    timestamps: Vec<ID3D11Query>,
+}
+
+fn create_query(device: &ID3D11Device, query: D3D11_QUERY) -> Result<ID3D11Query> {
+    let mut handle = None;
+    unsafe {
+        device.CreateQuery(
+            &D3D11_QUERY_DESC {
+                Query: query,
+                MiscFlags: 0,
+            },
+            Some(&mut handle),
+        )?;
+    }
+    assume_d3d11_init!(handle, "CreateQuery");
+    Ok(handle)
+}
+
+impl D3D11Timestamps {
+    pub fn new(
+        device: &ID3D11Device,
+        immediate_context: &ID3D11DeviceContext,
+        passes: usize,
+    ) -> Result<Option<D3D11Timestamps>> {
+        let disjoint = create_query(device, D3D11_QUERY_TIMESTAMP_DISJOINT)?;
+        let timestamps = (0..=passes)
+            .map(|_| create_query(device, D3D11_QUERY_TIMESTAMP))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Some(D3D11Timestamps {
+            immediate_context: immediate_context.clone(),
+            disjoint,
+            timestamps,
+        }))
+    }
+
+    /// Begin the disjoint query that the timestamps of the frame are written in.
+    pub fn begin(&self, ctx: &ID3D11DeviceContext) {
+        unsafe { ctx.Begin(&self.disjoint) }
+    }
+
+    /// Write the timestamp at the given index once all previous commands have completed.
+    pub fn write(&self, ctx: &ID3D11DeviceContext, index: usize) {
+        unsafe { ctx.End(&self.timestamps[index]) }
+    }
+
+    /// End the disjoint query that the timestamps of the frame are written in.
+    pub fn end(&self, ctx: &ID3D11DeviceContext) {
+        unsafe { ctx.End(&self.disjoint) }
+    }
+
+    /// Read the result of a query without flushing. The data is left untouched if the
+    /// result is not available, so a zeroed result means the query has not completed.
+    fn get_data<T>(&self, query: &ID3D11Query, data: &mut T) -> bool {
+        unsafe {
+            self.immediate_context
+                .GetData(
+                    query,
+                    Some(data as *mut T as *mut _),
+                    std::mem::size_of::<T>() as u32,
+                    D3D11_ASYNC_GETDATA_DONOTFLUSH.0 as u32,
+                )
+                .is_ok()
+        }
+    }
+}
+
+impl PassTimer for D3D11Timestamps {
+    fn capacity(&self) -> usize {
+        self.timestamps.len() - 1
+    }
+
+    fn read(&mut self, passes: usize) -> Option<Vec<Duration>> {
+        let mut disjoint = D3D11_QUERY_DATA_TIMESTAMP_DISJOINT::default();
+        if !self.get_data(&self.disjoint, &mut disjoint)
+            || disjoint.Frequency == 0
+            || disjoint.Disjoint.as_bool()
+        {
+            return None;
+        }
+
+        let mut timestamps = vec![0u64; passes + 1];
+        for (query, timestamp) in self.timestamps.iter().zip(timestamps.iter_mut()) {
+            if !self.get_data(query, timestamp) || *timestamp == 0 {
+                return None;
+            }
+        }
+
+        Some(timestamp_durations(
+            &timestamps,
+            1_000_000_000.0 / disjoint.Frequency as f64,
+        ))
+    }
+}
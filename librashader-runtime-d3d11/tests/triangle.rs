@@ -36,6 +36,7 @@ fn triangle_d3d11_args() {
         Some(&FilterChainOptionsD3D11 {
             force_no_mipmaps: false,
             disable_cache: false,
+            hdr_output: None,
         }),
         // replace below with 'None' for the triangle
         Some(image),
@@ -61,6 +62,7 @@ fn triangle_d3d11() {
         Some(&FilterChainOptionsD3D11 {
             force_no_mipmaps: false,
             disable_cache: true,
+            hdr_output: None,
         }),
         // replace below with 'None' for the triangle
         // None,
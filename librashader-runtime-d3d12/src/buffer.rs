@@ -38,7 +38,7 @@ impl Drop for D3D12Buffer {
     fn drop(&mut self) {
         let resource = unsafe { ManuallyDrop::take(&mut self.resource) };
         if let Err(e) = self.allocator.lock().free_resource(resource) {
-            println!("librashader-runtime-d3d12: [warn] failed to deallocate buffer memory {e}")
+            log::warn!("failed to deallocate buffer memory: {e}")
         }
     }
 }
@@ -8,6 +8,7 @@ use crate::graphics_pipeline::{D3D12GraphicsPipeline, D3D12RootSignature};
 use crate::luts::LutTexture;
 use crate::mipmap::D3D12MipmapGen;
 use crate::options::{FilterChainOptionsD3D12, FrameOptionsD3D12};
+use crate::profiling::D3D12Timestamps;
 use crate::samplers::SamplerSet;
 use crate::texture::{D3D12InputImage, D3D12OutputView, InputTexture, OutputDescriptor};
 use crate::{error, util};
@@ -21,11 +22,16 @@ use librashader_presets::ShaderPreset;
 use librashader_reflect::back::targets::{DXIL, HLSL};
 use librashader_reflect::back::{CompileReflectShader, CompileShader};
 use librashader_reflect::front::SpirvCompilation;
-use librashader_reflect::reflect::presets::{CompilePresetTarget, ShaderPassArtifact};
+use librashader_reflect::reflect::presets::{
+    CompilePresetTarget, PassObserver, ShaderPassArtifact,
+};
 use librashader_reflect::reflect::semantics::{ShaderSemantics, MAX_BINDINGS_COUNT};
 use librashader_reflect::reflect::ReflectShader;
 use librashader_runtime::binding::{BindingUtil, TextureInput};
+use librashader_runtime::compare;
+use librashader_runtime::hdr::{append_hdr_output_pass, HdrOutputEncoding};
 use librashader_runtime::image::{ImageError, LoadedTexture, UVDirection};
+use librashader_runtime::profiling::FrameProfiler;
 use librashader_runtime::quad::QuadType;
 use librashader_runtime::uniforms::UniformStorage;
 use parking_lot::Mutex;
@@ -60,10 +66,18 @@ use rayon::prelude::*;
 
 const MIPMAP_RESERVED_WORKHEAP_DESCRIPTORS: usize = 4096;
 
+fn staging_heap_size(shader_count: usize, lut_count: usize) -> usize {
+    // add one, because technically the input image doesn't need to count
+    (1 + MAX_BINDINGS_COUNT as usize) * shader_count
+        + MIPMAP_RESERVED_WORKHEAP_DESCRIPTORS
+        + lut_count
+}
+
 /// A Direct3D 12 filter chain.
 pub struct FilterChainD3D12 {
     pub(crate) common: FilterCommon,
     pub(crate) passes: Vec<FilterPass>,
+    compare_pass: D3D12Passes,
     pub(crate) output_framebuffers: Box<[OwnedImage]>,
     pub(crate) feedback_framebuffers: Box<[OwnedImage]>,
     pub(crate) history_framebuffers: VecDeque<OwnedImage>,
@@ -80,8 +94,22 @@ pub struct FilterChainD3D12 {
 
     default_options: FrameOptionsD3D12,
     draw_last_pass_feedback: bool,
+    disable_cache: bool,
+    force_hlsl_pipeline: bool,
+    hdr_output: Option<HdrOutputEncoding>,
+    lut_identities: Box<[LutIdentity]>,
+    pending_passes: Option<CompiledPasses<D3D12Passes>>,
+    profiler: FrameProfiler<D3D12Timestamps>,
 }
 
+/// The texture heap, sampler heap, filter passes and mipmap heap created by `init_passes`.
+type D3D12Passes = (
+    ID3D12DescriptorHeap,
+    ID3D12DescriptorHeap,
+    Vec<FilterPass>,
+    D3D12DescriptorHeap<ResourceWorkHeap>,
+);
+
 pub(crate) struct FilterCommon {
     pub(crate) d3d12: ID3D12Device,
     pub samplers: SamplerSet,
@@ -103,6 +131,11 @@ pub(crate) struct FrameResiduals {
     mipmap_luts: Vec<D3D12MipmapGen>,
     resources: Vec<ManuallyDrop<Option<ID3D12Resource>>>,
     resource_barriers: Vec<D3D12_RESOURCE_BARRIER>,
+    passes: Vec<FilterPass>,
+    luts: Vec<LutTexture>,
+    owned: Vec<OwnedImage>,
+    heaps: Vec<ID3D12DescriptorHeap>,
+    work_heaps: Vec<D3D12DescriptorHeap<ResourceWorkHeap>>,
 }
 
 impl FrameResiduals {
@@ -113,9 +146,30 @@ impl FrameResiduals {
             mipmap_luts: Vec::new(),
             resources: Vec::new(),
             resource_barriers: Vec::new(),
+            passes: Vec::new(),
+            luts: Vec::new(),
+            owned: Vec::new(),
+            heaps: Vec::new(),
+            work_heaps: Vec::new(),
         }
     }
 
+    pub fn dispose_passes(&mut self, passes: D3D12Passes) {
+        let (texture_heap, sampler_heap, passes, mipmap_heap) = passes;
+        self.heaps.push(texture_heap);
+        self.heaps.push(sampler_heap);
+        self.passes.extend(passes);
+        self.work_heaps.push(mipmap_heap);
+    }
+
+    pub fn dispose_lut(&mut self, lut: LutTexture) {
+        self.luts.push(lut)
+    }
+
+    pub fn dispose_owned(&mut self, image: OwnedImage) {
+        self.owned.push(image)
+    }
+
     pub fn dispose_mipmap_gen(&mut self, mipmap: D3D12MipmapGen) {
         self.mipmap_luts.push(mipmap)
     }
@@ -164,6 +218,11 @@ impl FrameResiduals {
             }
             // other barrier types should be handled manually
         }
+        self.passes.clear();
+        self.luts.clear();
+        self.owned.clear();
+        self.heaps.clear();
+        self.work_heaps.clear();
     }
 }
 
@@ -190,18 +249,21 @@ mod compile {
         shaders: Vec<PassResource>,
         textures: &[TextureResource],
         disable_cache: bool,
+        observer: Option<PassObserver<'_>>,
     ) -> Result<(Vec<DxilShaderPassMeta>, ShaderSemantics), FilterChainError> {
+        let observer = observer.unwrap_or(&|_, _| true);
         let (passes, semantics) = if !disable_cache {
-            DXIL::compile_preset_passes::<
+            DXIL::compile_preset_passes_with_observer::<
                 CachedCompilation<SpirvCompilation>,
                 SpirvCross,
                 FilterChainError,
-            >(shaders, textures.iter().map(|t| &t.meta))?
+            >(shaders, textures.iter().map(|t| &t.meta), observer)?
         } else {
-            DXIL::compile_preset_passes::<SpirvCompilation, SpirvCross, FilterChainError>(
-                shaders,
-                textures.iter().map(|t| &t.meta),
-            )?
+            DXIL::compile_preset_passes_with_observer::<
+                SpirvCompilation,
+                SpirvCross,
+                FilterChainError,
+            >(shaders, textures.iter().map(|t| &t.meta), observer)?
         };
 
         Ok((passes, semantics))
@@ -220,18 +282,21 @@ mod compile {
         shaders: Vec<PassResource>,
         textures: &[TextureResource],
         disable_cache: bool,
+        observer: Option<PassObserver<'_>>,
     ) -> Result<(Vec<HlslShaderPassMeta>, ShaderSemantics), FilterChainError> {
+        let observer = observer.unwrap_or(&|_, _| true);
         let (passes, semantics) = if !disable_cache {
-            HLSL::compile_preset_passes::<
+            HLSL::compile_preset_passes_with_observer::<
                 CachedCompilation<SpirvCompilation>,
                 SpirvCross,
                 FilterChainError,
-            >(shaders, textures.iter().map(|t| &t.meta))?
+            >(shaders, textures.iter().map(|t| &t.meta), observer)?
         } else {
-            HLSL::compile_preset_passes::<SpirvCompilation, SpirvCross, FilterChainError>(
-                shaders,
-                textures.iter().map(|t| &t.meta),
-            )?
+            HLSL::compile_preset_passes_with_observer::<
+                SpirvCompilation,
+                SpirvCross,
+                FilterChainError,
+            >(shaders, textures.iter().map(|t| &t.meta), observer)?
         };
 
         Ok((passes, semantics))
@@ -242,6 +307,7 @@ use crate::resource::OutlivesFrame;
 use compile::{compile_passes_dxil, compile_passes_hlsl, DxilShaderPassMeta, HlslShaderPassMeta};
 use librashader_pack::{ShaderPresetPack, TextureResource};
 use librashader_runtime::parameters::RuntimeParameters;
+use librashader_runtime::reload::{CompiledPasses, LutIdentity, LutReuse};
 
 impl FilterChainD3D12 {
     /// Load the shader preset at the given path into a filter chain.
@@ -335,15 +401,46 @@ impl FilterChainD3D12 {
         cmd: &ID3D12GraphicsCommandList,
         options: Option<&FilterChainOptionsD3D12>,
     ) -> error::Result<FilterChainD3D12> {
+        unsafe {
+            Self::load_from_pack_deferred_with_observer(preset, device, cmd, options, &|_, _| true)
+        }
+    }
+
+    /// Load a filter chain from a pre-parsed, loaded `ShaderPresetPack`, deferring and GPU-side initialization
+    /// to the caller. This function therefore requires no external synchronization of the device queue.
+    ///
+    /// `observer` is called after each pass is compiled with the index of the pass and the total
+    /// number of passes. If it returns `false`, loading stops with
+    /// [`ShaderCompileError::Cancelled`](librashader_reflect::error::ShaderCompileError::Cancelled).
+    ///
+    /// ## Safety
+    /// The provided command list must be ready for recording and contain no prior commands.
+    /// The caller is responsible for ending the command list and immediately submitting it to a
+    /// graphics queue. The command list must be completely executed before calling [`frame`](Self::frame).
+    pub unsafe fn load_from_pack_deferred_with_observer(
+        mut preset: ShaderPresetPack,
+        device: &ID3D12Device,
+        cmd: &ID3D12GraphicsCommandList,
+        options: Option<&FilterChainOptionsD3D12>,
+        observer: PassObserver<'_>,
+    ) -> error::Result<FilterChainD3D12> {
+        if let Some(encoding) = options.and_then(|o| o.hdr_output) {
+            append_hdr_output_pass(&mut preset, encoding)?;
+        }
         let shader_count = preset.passes.len();
         let lut_count = preset.textures.len();
 
         let shader_copy = preset.passes.clone();
         let disable_cache = options.map_or(false, |o| o.disable_cache);
 
-        let (passes, semantics) =
-            compile_passes_dxil(preset.passes, &preset.textures, disable_cache)?;
-        let (hlsl_passes, _) = compile_passes_hlsl(shader_copy, &preset.textures, disable_cache)?;
+        let (passes, semantics) = compile_passes_dxil(
+            preset.passes,
+            &preset.textures,
+            disable_cache,
+            Some(observer),
+        )?;
+        let (hlsl_passes, _) =
+            compile_passes_hlsl(shader_copy, &preset.textures, disable_cache, None)?;
 
         let samplers = SamplerSet::new(device)?;
         let mipmap_gen = D3D12MipmapGen::new(device, false)?;
@@ -356,21 +453,10 @@ impl FilterChainD3D12 {
 
         let draw_quad = DrawQuad::new(&allocator)?;
         let mut staging_heap = unsafe {
-            D3D12DescriptorHeap::new(
-                device,
-                // add one, because technically the input image doesn't need to count
-                (1 + MAX_BINDINGS_COUNT as usize) * shader_count
-                    + MIPMAP_RESERVED_WORKHEAP_DESCRIPTORS
-                    + lut_count,
-            )
+            D3D12DescriptorHeap::new(device, staging_heap_size(shader_count, lut_count))
         }?;
         let rtv_heap = unsafe {
-            D3D12DescriptorHeap::new(
-                device,
-                (1 + MAX_BINDINGS_COUNT as usize) * shader_count
-                    + MIPMAP_RESERVED_WORKHEAP_DESCRIPTORS
-                    + lut_count,
-            )
+            D3D12DescriptorHeap::new(device, staging_heap_size(shader_count, lut_count))
         }?;
 
         let root_signature = D3D12RootSignature::new(device)?;
@@ -386,8 +472,17 @@ impl FilterChainD3D12 {
             disable_cache,
         )?;
 
+        let compare_pass = FilterChainD3D12::init_compare_pass(
+            device,
+            &root_signature,
+            &allocator,
+            options.map_or(false, |o| o.force_hlsl_pipeline),
+            disable_cache,
+        )?;
+
         let mut residuals = FrameResiduals::new();
 
+        let lut_identities = LutIdentity::from_textures(&preset.textures);
         let luts = FilterChainD3D12::load_luts(
             device,
             cmd,
@@ -436,12 +531,17 @@ impl FilterChainD3D12 {
                 mipmap_gen,
                 root_signature,
                 draw_quad,
-                config: RuntimeParameters::new(preset.pass_count as usize, preset.parameters),
+                config: RuntimeParameters::new(
+                    preset.pass_count as usize,
+                    preset.parameters,
+                    filters.iter().map(|f| &f.source),
+                ),
                 history_textures,
             },
             staging_heap,
             rtv_heap,
             passes: filters,
+            compare_pass,
             output_framebuffers,
             feedback_framebuffers,
             history_framebuffers,
@@ -451,9 +551,151 @@ impl FilterChainD3D12 {
             disable_mipmaps: options.map_or(false, |o| o.force_no_mipmaps),
             residuals,
             default_options: Default::default(),
+            disable_cache,
+            force_hlsl_pipeline: options.map_or(false, |o| o.force_hlsl_pipeline),
+            hdr_output: options.and_then(|o| o.hdr_output),
+            lut_identities,
+            pending_passes: None,
+            profiler: FrameProfiler::new(2),
         })
     }
 
+    /// Reload the filter chain with a new shader preset.
+    ///
+    /// The passes of the preset are compiled immediately, then swapped in at the next call to
+    /// [`frame`](Self::frame). History and feedback framebuffers are kept, and lookup textures that
+    /// are unchanged from the current preset are reused. Runtime parameters are reset to the
+    /// values of the new preset once the passes are swapped in.
+    ///
+    /// New lookup textures are uploaded with the command list passed to [`frame`](Self::frame).
+    pub fn reload(&mut self, mut preset: ShaderPresetPack) -> error::Result<()> {
+        if let Some(encoding) = self.hdr_output {
+            append_hdr_output_pass(&mut preset, encoding)?;
+        }
+        let compiled = CompiledPasses::new(preset, |passes, textures| {
+            let shader_copy = passes.clone();
+            let (passes, semantics) =
+                compile_passes_dxil(passes, textures, self.disable_cache, None)?;
+            let (hlsl_passes, _) =
+                compile_passes_hlsl(shader_copy, textures, self.disable_cache, None)?;
+
+            FilterChainD3D12::init_passes(
+                &self.common.d3d12,
+                &self.common.root_signature,
+                &self.common.allocator,
+                passes,
+                hlsl_passes,
+                &semantics,
+                self.force_hlsl_pipeline,
+                self.disable_cache,
+            )
+        })?;
+
+        self.pending_passes = Some(compiled);
+        Ok(())
+    }
+
+    fn swap_passes(
+        &mut self,
+        compiled: CompiledPasses<D3D12Passes>,
+        cmd: &ID3D12GraphicsCommandList,
+    ) -> error::Result<()> {
+        let CompiledPasses {
+            passes: (texture_heap, sampler_heap, passes, mipmap_heap),
+            textures,
+            lut_identities,
+            parameters,
+            pass_count,
+        } = compiled;
+
+        let device = &self.common.d3d12;
+        let allocator = &self.common.allocator;
+        let residuals = &mut self.residuals;
+
+        // Reused lookup textures keep their descriptors alive in the previous staging heap.
+        self.staging_heap = unsafe {
+            D3D12DescriptorHeap::new(device, staging_heap_size(passes.len(), textures.len()))
+        }?;
+        self.rtv_heap = unsafe {
+            D3D12DescriptorHeap::new(device, staging_heap_size(passes.len(), textures.len()))
+        }?;
+
+        let previous = std::mem::replace(&mut self.passes, passes);
+        let previous_work_heap = std::mem::replace(&mut self.work_heap, texture_heap);
+        let previous_sampler_heap = std::mem::replace(&mut self.sampler_heap, sampler_heap);
+        let previous_mipmap_heap = std::mem::replace(&mut self.mipmap_heap, mipmap_heap);
+        residuals.dispose_passes((
+            previous_work_heap,
+            previous_sampler_heap,
+            previous,
+            previous_mipmap_heap,
+        ));
+
+        let mut luts = LutReuse::new(
+            std::mem::take(&mut self.common.luts),
+            &self.lut_identities,
+            &lut_identities,
+            textures,
+            |lut| residuals.dispose_lut(lut),
+        );
+        let loaded = FilterChainD3D12::load_luts(
+            device,
+            cmd,
+            allocator,
+            &mut self.staging_heap,
+            &mut self.mipmap_heap,
+            residuals,
+            luts.take_textures(),
+        )?;
+        self.common.luts = luts.finish(loaded);
+        self.lut_identities = lut_identities;
+
+        let framebuffer_gen = || {
+            OwnedImage::new(
+                device,
+                allocator,
+                Size::new(1, 1),
+                ImageFormat::R8G8B8A8Unorm.into(),
+                false,
+            )
+        };
+        let input_gen = || None;
+        let framebuffer_init = FramebufferInit::new(
+            self.passes.iter().map(|f| &f.reflection.meta),
+            &framebuffer_gen,
+            &input_gen,
+        );
+
+        let (output_framebuffers, output_textures) = framebuffer_init
+            .reuse_output_framebuffers(std::mem::take(&mut self.output_framebuffers), |image| {
+                residuals.dispose_owned(image)
+            })?;
+        let (feedback_framebuffers, feedback_textures) = framebuffer_init
+            .reuse_output_framebuffers(
+                std::mem::take(&mut self.feedback_framebuffers),
+                |image| residuals.dispose_owned(image),
+            )?;
+        let (history_framebuffers, history_textures) = framebuffer_init
+            .reuse_history(std::mem::take(&mut self.history_framebuffers), |image| {
+                residuals.dispose_owned(image)
+            })?;
+
+        self.output_framebuffers = output_framebuffers;
+        self.feedback_framebuffers = feedback_framebuffers;
+        self.history_framebuffers = history_framebuffers;
+        self.common.output_textures = output_textures;
+        self.common.feedback_textures = feedback_textures;
+        self.common.history_textures = history_textures;
+        self.common.config = RuntimeParameters::new(
+            pass_count,
+            parameters,
+            self.passes.iter().map(|f| &f.source),
+        );
+        self.draw_last_pass_feedback = framebuffer_init.uses_final_pass_as_feedback();
+
+        Ok(())
+    }
+
     fn load_luts(
         device: &ID3D12Device,
         cmd: &ID3D12GraphicsCommandList,
@@ -506,6 +748,31 @@ impl FilterChainD3D12 {
         Ok(luts)
     }
 
+    /// Compile the built-in comparison pass into its own descriptor heaps.
+    fn init_compare_pass(
+        device: &ID3D12Device,
+        root_signature: &D3D12RootSignature,
+        allocator: &Arc<Mutex<Allocator>>,
+        force_hlsl: bool,
+        disable_cache: bool,
+    ) -> error::Result<D3D12Passes> {
+        let pass = compare::compare_pass()?;
+        let (passes, semantics) =
+            compile_passes_dxil(vec![pass.clone()], &[], disable_cache, None)?;
+        let (hlsl_passes, _) = compile_passes_hlsl(vec![pass], &[], disable_cache, None)?;
+
+        FilterChainD3D12::init_passes(
+            device,
+            root_signature,
+            allocator,
+            passes,
+            hlsl_passes,
+            &semantics,
+            force_hlsl,
+            disable_cache,
+        )
+    }
+
     fn init_passes(
         device: &ID3D12Device,
         root_signature: &D3D12RootSignature,
@@ -515,12 +782,7 @@ impl FilterChainD3D12 {
         semantics: &ShaderSemantics,
         force_hlsl: bool,
         disable_cache: bool,
-    ) -> error::Result<(
-        ID3D12DescriptorHeap,
-        ID3D12DescriptorHeap,
-        Vec<FilterPass>,
-        D3D12DescriptorHeap<ResourceWorkHeap>,
-    )> {
+    ) -> error::Result<D3D12Passes> {
         let shader_count = passes.len();
         let D3D12PartitionedHeap {
             partitioned: work_heaps,
@@ -574,9 +836,11 @@ impl FilterChainD3D12 {
                     };
 
                     let dxil_reflection = dxil.reflect(index, semantics)?;
-                    let dxil = dxil.compile(Some(
-                        librashader_reflect::back::dxil::ShaderModel::ShaderModel6_0,
-                    ))?;
+                    let dxil = dxil
+                        .compile(Some(
+                            librashader_reflect::back::dxil::ShaderModel::ShaderModel6_0,
+                        ))
+                        .map_err(|error| error.in_pass(index))?;
 
                     let render_format = if let Some(format) = config.meta.get_format_override() {
                         format
@@ -608,9 +872,11 @@ impl FilterChainD3D12 {
                         }
 
                         let hlsl_reflection = hlsl.reflect(index, semantics)?;
-                        let hlsl = hlsl.compile(Some(
-                            librashader_reflect::back::hlsl::HlslShaderModel::ShaderModel6_0,
-                        ))?;
+                        let hlsl = hlsl
+                            .compile(Some(
+                                librashader_reflect::back::hlsl::HlslShaderModel::ShaderModel6_0,
+                            ))
+                            .map_err(|error| error.in_pass(index))?;
 
                         let graphics_pipeline = D3D12GraphicsPipeline::new_from_hlsl(
                             device,
@@ -719,9 +985,16 @@ impl FilterChainD3D12 {
     ) -> error::Result<()> {
         self.residuals.dispose();
 
+        if let Some(compiled) = self.pending_passes.take() {
+            self.swap_passes(compiled, cmd)?;
+        }
+
+        self.common.config.set_automation_frame(frame_count);
+
         // limit number of passes to those enabled.
         let max = std::cmp::min(self.passes.len(), self.common.config.passes_enabled());
-        let passes = &mut self.passes[0..max];
+        let range = options.unwrap_or(&self.default_options).pass_range(max);
+        let passes = &mut self.passes[0..range.end];
         if passes.is_empty() {
             return Ok(());
         }
@@ -827,14 +1100,33 @@ impl FilterChainD3D12 {
 
         self.common.draw_quad.bind_vertices_for_frame(cmd);
 
+        let timestamps = self.profiler.begin_frame(
+            frame_count,
+            pass.iter()
+                .chain(last.iter())
+                .map(|pass| pass.meta.alias.as_ref().map(|alias| alias.as_str())),
+            |count| D3D12Timestamps::new(&self.common.d3d12, &self.common.allocator, count),
+        )?;
+
+        if let Some(timestamps) = &timestamps {
+            timestamps.write(cmd, 0);
+        }
+
         for (index, pass) in pass.iter_mut().enumerate() {
+            // skip passes before the range, so that the first pass reads the original image.
+            if index < range.start {
+                if let Some(timestamps) = &timestamps {
+                    timestamps.write(cmd, index + 1);
+                }
+                continue;
+            }
+
             source.filter = pass.meta.filter;
             source.wrap_mode = pass.meta.wrap_mode;
 
             let target = &self.output_framebuffers[index];
 
             if !pass.pipeline.has_format(target.format) {
-                // eprintln!("recompiling final pipeline");
                 pass.pipeline.recompile(
                     target.format,
                     &self.common.root_signature,
@@ -891,6 +1183,10 @@ impl FilterChainD3D12 {
                 self.residuals.dispose_mipmap_handles(residuals);
             }
 
+            if let Some(timestamps) = &timestamps {
+                timestamps.write(cmd, index + 1);
+            }
+
             self.residuals.dispose_output(view.descriptor);
             source = self.common.output_textures[index].as_ref().unwrap().clone()
         }
@@ -903,13 +1199,14 @@ impl FilterChainD3D12 {
             source.filter = pass.meta.filter;
             source.wrap_mode = pass.meta.wrap_mode;
 
-            if self.draw_last_pass_feedback {
-                let feedback_target = &self.output_framebuffers[index];
+            if options.compare_enabled() {
+                // draw the last pass at the size of the viewport, to compare with the original image.
+                let target = &mut self.output_framebuffers[index];
+                compare::scale_compare_source(target, pass, viewport.size, &())?;
 
-                if !pass.pipeline.has_format(feedback_target.format) {
-                    // eprintln!("recompiling final pipeline");
+                if !pass.pipeline.has_format(target.format) {
                     pass.pipeline.recompile(
-                        feedback_target.format,
+                        target.format,
                         &self.common.root_signature,
                         &self.common.d3d12,
                     )?;
@@ -917,13 +1214,13 @@ impl FilterChainD3D12 {
 
                 util::d3d12_resource_transition::<OutlivesFrame, _>(
                     cmd,
-                    &feedback_target.resource,
+                    &target.resource,
                     D3D12_RESOURCE_STATE_PIXEL_SHADER_RESOURCE,
                     D3D12_RESOURCE_STATE_RENDER_TARGET,
                 );
 
-                let view = feedback_target.create_render_target_view(&mut self.rtv_heap)?;
-                let out = RenderTarget::viewport_with_output(&view, viewport);
+                let view = target.create_render_target_view(&mut self.rtv_heap)?;
+                let out = RenderTarget::identity(&view)?;
                 pass.draw(
                     cmd,
                     index,
@@ -934,39 +1231,128 @@ impl FilterChainD3D12 {
                     &original,
                     &source,
                     &out,
-                    QuadType::Final,
+                    QuadType::Offscreen,
                 )?;
 
                 util::d3d12_resource_transition::<OutlivesFrame, _>(
                     cmd,
-                    &feedback_target.resource,
+                    &target.resource,
                     D3D12_RESOURCE_STATE_RENDER_TARGET,
                     D3D12_RESOURCE_STATE_PIXEL_SHADER_RESOURCE,
                 );
-            }
+                self.residuals.dispose_output(view.descriptor);
+
+                let (texture_heap, sampler_heap, compare_passes, _) = &mut self.compare_pass;
+                let compare_pass = &mut compare_passes[0];
+                compare::set_compare_uniforms(
+                    &mut compare_pass.source.parameters,
+                    options.compare_split,
+                    options.compare_line_width,
+                );
+                let source = target.create_shader_resource_view(
+                    &mut self.staging_heap,
+                    compare_pass.meta.filter,
+                    compare_pass.meta.wrap_mode,
+                )?;
 
-            if !pass.pipeline.has_format(viewport.output.format) {
-                // eprintln!("recompiling final pipeline");
-                pass.pipeline.recompile(
-                    viewport.output.format,
-                    &self.common.root_signature,
-                    &self.common.d3d12,
+                if !compare_pass.pipeline.has_format(viewport.output.format) {
+                    compare_pass.pipeline.recompile(
+                        viewport.output.format,
+                        &self.common.root_signature,
+                        &self.common.d3d12,
+                    )?;
+                }
+
+                // the comparison pass binds its textures from its own descriptor heaps.
+                unsafe {
+                    cmd.SetDescriptorHeaps(&[
+                        Some(texture_heap.clone()),
+                        Some(sampler_heap.clone()),
+                    ]);
+                }
+
+                let out = RenderTarget::viewport(viewport);
+                compare_pass.draw(
+                    cmd,
+                    passes_len,
+                    &self.common,
+                    compare_pass.meta.get_frame_count(frame_count),
+                    options,
+                    viewport,
+                    &original,
+                    &source,
+                    &out,
+                    QuadType::Final,
+                )?;
+            } else {
+                if self.draw_last_pass_feedback {
+                    let feedback_target = &self.output_framebuffers[index];
+
+                    if !pass.pipeline.has_format(feedback_target.format) {
+                        pass.pipeline.recompile(
+                            feedback_target.format,
+                            &self.common.root_signature,
+                            &self.common.d3d12,
+                        )?;
+                    }
+
+                    util::d3d12_resource_transition::<OutlivesFrame, _>(
+                        cmd,
+                        &feedback_target.resource,
+                        D3D12_RESOURCE_STATE_PIXEL_SHADER_RESOURCE,
+                        D3D12_RESOURCE_STATE_RENDER_TARGET,
+                    );
+
+                    let view = feedback_target.create_render_target_view(&mut self.rtv_heap)?;
+                    let out = RenderTarget::viewport_with_output(&view, viewport);
+                    pass.draw(
+                        cmd,
+                        index,
+                        &self.common,
+                        pass.meta.get_frame_count(frame_count),
+                        options,
+                        viewport,
+                        &original,
+                        &source,
+                        &out,
+                        QuadType::Final,
+                    )?;
+
+                    util::d3d12_resource_transition::<OutlivesFrame, _>(
+                        cmd,
+                        &feedback_target.resource,
+                        D3D12_RESOURCE_STATE_RENDER_TARGET,
+                        D3D12_RESOURCE_STATE_PIXEL_SHADER_RESOURCE,
+                    );
+                }
+
+                if !pass.pipeline.has_format(viewport.output.format) {
+                    pass.pipeline.recompile(
+                        viewport.output.format,
+                        &self.common.root_signature,
+                        &self.common.d3d12,
+                    )?;
+                }
+
+                let out = RenderTarget::viewport(viewport);
+                pass.draw(
+                    cmd,
+                    passes_len - 1,
+                    &self.common,
+                    pass.meta.get_frame_count(frame_count),
+                    options,
+                    viewport,
+                    &original,
+                    &source,
+                    &out,
+                    QuadType::Final,
                 )?;
             }
 
-            let out = RenderTarget::viewport(viewport);
-            pass.draw(
-                cmd,
-                passes_len - 1,
-                &self.common,
-                pass.meta.get_frame_count(frame_count),
-                options,
-                viewport,
-                &original,
-                &source,
-                &out,
-                QuadType::Final,
-            )?;
+            if let Some(timestamps) = &timestamps {
+                timestamps.write(cmd, passes_len);
+                timestamps.resolve(cmd, passes_len);
+            }
         }
 
         self.push_history(cmd, &original)?;
@@ -974,3 +1360,56 @@ impl FilterChainD3D12 {
         Ok(())
     }
 }
+
+use librashader_runtime::impl_filter_chain_debug;
+impl_filter_chain_debug!(FilterChainD3D12, ID3D12Resource, |texture| {
+    ID3D12Resource::clone(&texture.resource)
+});
+
+use librashader_runtime::impl_filter_chain_profiling;
+impl_filter_chain_profiling!(FilterChainD3D12);
+
+impl librashader_runtime::filter_chain::FilterChain for FilterChainD3D12 {
+    type Device<'a> = &'a ID3D12Device;
+    type InputImage<'a> = D3D12InputImage;
+    type OutputView<'a> = D3D12OutputView;
+    type CommandContext<'a> = &'a ID3D12GraphicsCommandList;
+    type FilterChainOptions = FilterChainOptionsD3D12;
+    type FrameOptions = FrameOptionsD3D12;
+    type Error = FilterChainError;
+
+    unsafe fn load_from_pack(
+        preset: ShaderPresetPack,
+        device: Self::Device<'_>,
+        options: Option<&Self::FilterChainOptions>,
+    ) -> error::Result<Self> {
+        unsafe { FilterChainD3D12::load_from_pack(preset, device, options) }
+    }
+
+    unsafe fn load_from_preset(
+        preset: ShaderPreset,
+        device: Self::Device<'_>,
+        options: Option<&Self::FilterChainOptions>,
+    ) -> error::Result<Self> {
+        unsafe { FilterChainD3D12::load_from_preset(preset, device, options) }
+    }
+
+    unsafe fn load_from_path(
+        path: impl AsRef<Path>,
+        device: Self::Device<'_>,
+        options: Option<&Self::FilterChainOptions>,
+    ) -> error::Result<Self> {
+        unsafe { FilterChainD3D12::load_from_path(path, device, options) }
+    }
+
+    unsafe fn frame<'a>(
+        &mut self,
+        input: Self::InputImage<'a>,
+        viewport: &Viewport<Self::OutputView<'a>>,
+        cmd: Self::CommandContext<'a>,
+        frame_count: usize,
+        options: Option<&Self::FrameOptions>,
+    ) -> error::Result<()> {
+        unsafe { FilterChainD3D12::frame(self, cmd, input, viewport, frame_count, options) }
+    }
+}
@@ -371,7 +371,7 @@ impl Drop for OwnedImage {
         }
         let resource = unsafe { ManuallyDrop::take(&mut self.handle) };
         if let Err(e) = self.allocator.lock().free_resource(resource) {
-            println!("librashader-runtime-d3d12: [warn] failed to deallocate owned image buffer memory {e}")
+            log::warn!("failed to deallocate owned image buffer memory: {e}")
         }
     }
 }
@@ -329,6 +329,7 @@ impl D3D12GraphicsPipeline {
         root_sig: &D3D12RootSignature,
         device: &ID3D12Device,
     ) -> error::Result<()> {
+        log::debug!("recompiling pipeline for output format {format:?}");
         let (vertex, fragment) = unsafe {
             let library: IDxcUtils = DxcCreateInstance(&CLSID_DxcLibrary)?;
             let vertex = library.CreateBlobFromPinned(
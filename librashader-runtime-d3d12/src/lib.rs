@@ -11,6 +11,7 @@ mod framebuffer;
 mod graphics_pipeline;
 mod luts;
 mod mipmap;
+mod profiling;
 mod samplers;
 mod texture;
 mod util;
@@ -252,12 +252,12 @@ impl Drop for LutTexture {
         // deallocate
         let resource = unsafe { ManuallyDrop::take(&mut self.allocator_resource) };
         if let Err(e) = self.allocator.lock().free_resource(resource) {
-            println!("librashader-runtime-d3d12: [warn] failed to deallocate lut buffer memory {e}")
+            log::warn!("failed to deallocate lut buffer memory: {e}")
         }
 
         let staging = unsafe { ManuallyDrop::take(&mut self.allocator_staging) };
         if let Err(e) = self.allocator.lock().free_resource(staging) {
-            println!("librashader-runtime-d3d12: [warn] failed to deallocate lut staging buffer memory {e}")
+            log::warn!("failed to deallocate lut staging buffer memory: {e}")
         }
     }
 }
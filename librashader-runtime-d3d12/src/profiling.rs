@@ -0,0 +1,168 @@
+use crate::error;
+use crate::error::assume_d3d12_init;
+use gpu_allocator::d3d12::{
+    Allocator, Resource, ResourceCategory, ResourceCreateDesc, ResourceStateOrBarrierLayout,
+    ResourceType,
+};
+use gpu_allocator::MemoryLocation;
+use librashader_runtime::profiling::{timestamp_durations, PassTimer};
+use parking_lot::Mutex;
+use std::mem::ManuallyDrop;
+use std::sync::Arc;
+use std::time::Duration;
+use windows::Win32::Graphics::Direct3D12::{
+    ID3D12CommandQueue, ID3D12Device, ID3D12GraphicsCommandList, ID3D12QueryHeap,
+    D3D12_COMMAND_LIST_TYPE_DIRECT, D3D12_COMMAND_QUEUE_DESC, D3D12_COMMAND_QUEUE_FLAG_NONE,
+    D3D12_QUERY_HEAP_DESC, D3D12_QUERY_HEAP_TYPE_TIMESTAMP, D3D12_QUERY_TYPE_TIMESTAMP,
+    D3D12_RANGE, D3D12_RESOURCE_DESC, D3D12_RESOURCE_DIMENSION_BUFFER,
+    D3D12_RESOURCE_STATE_COPY_DEST, D3D12_TEXTURE_LAYOUT_ROW_MAJOR,
+};
+use windows::Win32::Graphics::Dxgi::Common::DXGI_SAMPLE_DESC;
+
+/// Timestamps written before the first pass and after each pass of a frame.
+///
+/// The previous frame must have completed before `frame` is called again, so the
+/// timestamps are always resolved by the time they are read back.
+pub(crate) struct D3D12Timestamps {
+    heap: ID3D12QueryHeap,
+    readback: ManuallyDrop<Resource>,
+    allocator: Arc<Mutex<Allocator>>,
+    capacity: usize,
+    frequency: u64,
+}
+
+impl D3D12Timestamps {
+    pub fn new(
+        device: &ID3D12Device,
+        allocator: &Arc<Mutex<Allocator>>,
+        passes: usize,
+    ) -> error::Result<Option<D3D12Timestamps>> {
+        // The timestamp frequency is only exposed by command queues. Direct queues of a device share
+        // the same frequency, so the frequency of a temporary queue is used.
+        let frequency = unsafe {
+            let queue: ID3D12CommandQueue =
+                device.CreateCommandQueue(&D3D12_COMMAND_QUEUE_DESC {
+                    Type: D3D12_COMMAND_LIST_TYPE_DIRECT,
+                    Priority: 0,
+                    Flags: D3D12_COMMAND_QUEUE_FLAG_NONE,
+                    NodeMask: 0,
+                })?;
+            queue.GetTimestampFrequency()?
+        };
+
+        if frequency == 0 {
+            return Ok(None);
+        }
+
+        let mut heap: Option<ID3D12QueryHeap> = None;
+        unsafe {
+            device.CreateQueryHeap(
+                &D3D12_QUERY_HEAP_DESC {
+                    Type: D3D12_QUERY_HEAP_TYPE_TIMESTAMP,
+                    Count: passes as u32 + 1,
+                    NodeMask: 0,
+                },
+                &mut heap,
+            )?;
+        }
+        assume_d3d12_init!(heap, "CreateQueryHeap");
+
+        let readback = allocator.lock().create_resource(&ResourceCreateDesc {
+            name: "timestamp readback",
+            memory_location: MemoryLocation::GpuToCpu,
+            resource_category: ResourceCategory::Buffer,
+            resource_desc: &D3D12_RESOURCE_DESC {
+                Dimension: D3D12_RESOURCE_DIMENSION_BUFFER,
+                Width: (passes as u64 + 1) * std::mem::size_of::<u64>() as u64,
+                Height: 1,
+                DepthOrArraySize: 1,
+                MipLevels: 1,
+                Layout: D3D12_TEXTURE_LAYOUT_ROW_MAJOR,
+                SampleDesc: DXGI_SAMPLE_DESC {
+                    Count: 1,
+                    Quality: 0,
+                },
+                ..Default::default()
+            },
+            castable_formats: &[],
+            clear_value: None,
+            initial_state_or_layout: ResourceStateOrBarrierLayout::ResourceState(
+                D3D12_RESOURCE_STATE_COPY_DEST,
+            ),
+            resource_type: &ResourceType::Placed,
+        })?;
+
+        Ok(Some(D3D12Timestamps {
+            heap,
+            readback: ManuallyDrop::new(readback),
+            allocator: Arc::clone(allocator),
+            capacity: passes,
+            frequency,
+        }))
+    }
+
+    /// Write the timestamp at the given index once all previous commands have completed.
+    pub fn write(&self, cmd: &ID3D12GraphicsCommandList, index: usize) {
+        unsafe { cmd.EndQuery(&self.heap, D3D12_QUERY_TYPE_TIMESTAMP, index as u32) }
+    }
+
+    /// Copy the timestamps of the given number of passes to the readback buffer.
+    pub fn resolve(&self, cmd: &ID3D12GraphicsCommandList, passes: usize) {
+        unsafe {
+            cmd.ResolveQueryData(
+                &self.heap,
+                D3D12_QUERY_TYPE_TIMESTAMP,
+                0,
+                passes as u32 + 1,
+                self.readback.resource(),
+                0,
+            )
+        }
+    }
+}
+
+impl PassTimer for D3D12Timestamps {
+    fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn read(&mut self, passes: usize) -> Option<Vec<Duration>> {
+        let size = (passes + 1) * std::mem::size_of::<u64>();
+        let mut timestamps = vec![0u64; passes + 1];
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            self.readback
+                .resource()
+                .Map(
+                    0,
+                    Some(&D3D12_RANGE {
+                        Begin: 0,
+                        End: size,
+                    }),
+                    Some(&mut ptr),
+                )
+                .ok()?;
+
+            // panic-safety: If Map returns Ok then ptr is not null
+            assert!(!ptr.is_null());
+            std::ptr::copy_nonoverlapping(ptr.cast(), timestamps.as_mut_ptr(), passes + 1);
+            self.readback
+                .resource()
+                .Unmap(0, Some(&D3D12_RANGE { Begin: 0, End: 0 }));
+        }
+
+        Some(timestamp_durations(
+            &timestamps,
+            1_000_000_000.0 / self.frequency as f64,
+        ))
+    }
+}
+
+impl Drop for D3D12Timestamps {
+    fn drop(&mut self) {
+        let resource = unsafe { ManuallyDrop::take(&mut self.readback) };
+        if let Err(e) = self.allocator.lock().free_resource(resource) {
+            log::warn!("failed to deallocate timestamp memory: {e}")
+        }
+    }
+}
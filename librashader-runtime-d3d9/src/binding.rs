@@ -124,10 +124,7 @@ impl<T: D3D9UniformScalar> BindUniform<ConstantRegister, T, IDirect3DDevice9>
                 if let Err(err) =
                     device.SetVertexShaderConstantF(location.index, value.as_ptr(), location.count)
                 {
-                    println!(
-                        "[librashader-runtime-d3d9] unable to bind vertex {}: {err}",
-                        location.index
-                    );
+                    log::warn!("unable to bind vertex {}: {err}", location.index);
                 }
             }
         }
@@ -136,10 +133,7 @@ impl<T: D3D9UniformScalar> BindUniform<ConstantRegister, T, IDirect3DDevice9>
                 if let Err(err) =
                     device.SetPixelShaderConstantF(location.index, value.as_ptr(), location.count)
                 {
-                    println!(
-                        "[librashader-runtime-d3d9] unable to bind vertex {}: {err}",
-                        location.index
-                    );
+                    log::warn!("unable to bind fragment {}: {err}", location.index);
                 }
             }
         }
@@ -160,10 +154,7 @@ impl BindUniform<ConstantRegister, &[f32; 4], IDirect3DDevice9> for D3D9UniformB
                 if let Err(err) =
                     device.SetVertexShaderConstantF(location.index, vec4.as_ptr(), location.count)
                 {
-                    println!(
-                        "[librashader-runtime-d3d9] unable to bind vertex {}: {err}",
-                        location.index
-                    );
+                    log::warn!("unable to bind vertex {}: {err}", location.index);
                 }
             }
         }
@@ -172,10 +163,7 @@ impl BindUniform<ConstantRegister, &[f32; 4], IDirect3DDevice9> for D3D9UniformB
                 if let Err(err) =
                     device.SetPixelShaderConstantF(location.index, vec4.as_ptr(), location.count)
                 {
-                    println!(
-                        "[librashader-runtime-d3d9] unable to bind fragment {}: {err}",
-                        location.index
-                    );
+                    log::warn!("unable to bind fragment {}: {err}", location.index);
                 }
             }
         }
@@ -196,10 +184,7 @@ impl BindUniform<ConstantRegister, &[f32; 16], IDirect3DDevice9> for D3D9Uniform
                 if let Err(err) =
                     device.SetVertexShaderConstantF(location.index, mat4.as_ptr(), location.count)
                 {
-                    println!(
-                        "[librashader-runtime-d3d9] unable to bind vertex {}: {err}",
-                        location.index
-                    );
+                    log::warn!("unable to bind vertex {}: {err}", location.index);
                 }
             }
         }
@@ -208,10 +193,7 @@ impl BindUniform<ConstantRegister, &[f32; 16], IDirect3DDevice9> for D3D9Uniform
                 if let Err(err) =
                     device.SetPixelShaderConstantF(location.index, mat4.as_ptr(), location.count)
                 {
-                    println!(
-                        "[librashader-runtime-d3d9] unable to bind fragment {}: {err}",
-                        location.index
-                    );
+                    log::warn!("unable to bind fragment {}: {err}", location.index);
                 }
             }
         }
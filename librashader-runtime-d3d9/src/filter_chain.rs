@@ -5,6 +5,7 @@ use crate::filter_pass::FilterPass;
 use crate::graphics_pipeline::D3D9State;
 use crate::luts::LutTexture;
 use crate::options::{FilterChainOptionsD3D9, FrameOptionsD3D9};
+use crate::profiling::D3D9Timestamps;
 use crate::samplers::SamplerSet;
 use crate::texture::{D3D9InputTexture, D3D9Texture};
 use crate::{error, util};
@@ -22,8 +23,11 @@ use librashader_reflect::reflect::presets::{CompilePresetTarget, ShaderPassArtif
 use librashader_reflect::reflect::semantics::ShaderSemantics;
 use librashader_reflect::reflect::ReflectShader;
 use librashader_runtime::binding::{BindingUtil, TextureInput};
+use librashader_runtime::compare;
 use librashader_runtime::framebuffer::FramebufferInit;
+use librashader_runtime::hdr::{append_hdr_output_pass, HdrOutputEncoding};
 use librashader_runtime::image::{ImageError, LoadedTexture, UVDirection, BGRA8};
+use librashader_runtime::profiling::FrameProfiler;
 use librashader_runtime::quad::QuadType;
 use librashader_runtime::render_target::RenderTarget;
 use librashader_runtime::scaling::ScaleFramebuffer;
@@ -54,11 +58,17 @@ pub(crate) struct FilterCommon {
 pub struct FilterChainD3D9 {
     pub(crate) common: FilterCommon,
     passes: Vec<FilterPass>,
+    compare_pass: FilterPass,
     output_framebuffers: Box<[D3D9Texture]>,
     feedback_framebuffers: Box<[D3D9Texture]>,
     history_framebuffers: VecDeque<D3D9Texture>,
     default_options: FrameOptionsD3D9,
     draw_last_pass_feedback: bool,
+    disable_cache: bool,
+    hdr_output: Option<HdrOutputEncoding>,
+    lut_identities: Box<[LutIdentity]>,
+    pending_passes: Option<CompiledPasses<Vec<FilterPass>>>,
+    profiler: FrameProfiler<D3D9Timestamps>,
 }
 
 mod compile {
@@ -99,6 +109,7 @@ mod compile {
 use compile::{compile_passes, ShaderPassMeta};
 use librashader_pack::{ShaderPresetPack, TextureResource};
 use librashader_runtime::parameters::RuntimeParameters;
+use librashader_runtime::reload::{CompiledPasses, LutIdentity, LutReuse};
 
 impl FilterChainD3D9 {
     fn init_passes(
@@ -109,7 +120,9 @@ impl FilterChainD3D9 {
     ) -> error::Result<Vec<FilterPass>> {
         let builder_fn = |(index, (config, mut reflect)): (usize, ShaderPassMeta)| {
             let mut reflection = reflect.reflect(index, semantics)?;
-            let hlsl = reflect.compile(Some(HlslShaderModel::ShaderModel3_0))?;
+            let hlsl = reflect
+                .compile(Some(HlslShaderModel::ShaderModel3_0))
+                .map_err(|error| error.in_pass(index))?;
 
             // eprintln!("===vs===\n{}", hlsl.vertex);
 
@@ -230,10 +243,13 @@ impl FilterChainD3D9 {
 
     /// Load a filter chain from a pre-parsed `ShaderPreset`.
     pub unsafe fn load_from_pack(
-        preset: ShaderPresetPack,
+        mut preset: ShaderPresetPack,
         device: &IDirect3DDevice9,
         options: Option<&FilterChainOptionsD3D9>,
     ) -> error::Result<FilterChainD3D9> {
+        if let Some(encoding) = options.and_then(|o| o.hdr_output) {
+            append_hdr_output_pass(&mut preset, encoding)?;
+        }
         let disable_cache = options.map_or(false, |o| o.disable_cache);
 
         let (passes, semantics) = compile_passes(preset.passes, &preset.textures, disable_cache)?;
@@ -242,8 +258,13 @@ impl FilterChainD3D9 {
 
         // initialize passes
         let filters = FilterChainD3D9::init_passes(device, passes, &semantics, disable_cache)?;
+        let compare_pass = compare::compile_compare_pass(|passes| {
+            let (passes, semantics) = compile_passes(passes, &[], disable_cache)?;
+            FilterChainD3D9::init_passes(device, passes, &semantics, disable_cache)
+        })?;
 
         // load luts
+        let lut_identities = LutIdentity::from_textures(&preset.textures);
         let luts = FilterChainD3D9::load_luts(device, preset.textures)?;
 
         let framebuffer_gen =
@@ -267,15 +288,22 @@ impl FilterChainD3D9 {
 
         let draw_quad = DrawQuad::new(device)?;
 
+        let config = RuntimeParameters::new(
+            preset.pass_count as usize,
+            preset.parameters,
+            filters.iter().map(|f| &f.source),
+        );
+
         Ok(FilterChainD3D9 {
             draw_last_pass_feedback: framebuffer_init.uses_final_pass_as_feedback(),
             passes: filters,
+            compare_pass,
             output_framebuffers,
             feedback_framebuffers,
             history_framebuffers,
             common: FilterCommon {
                 d3d9: device.clone(),
-                config: RuntimeParameters::new(preset.pass_count as usize, preset.parameters),
+                config,
                 disable_mipmaps: options.map_or(false, |o| o.force_no_mipmaps),
                 luts,
                 samplers,
@@ -285,9 +313,85 @@ impl FilterChainD3D9 {
                 draw_quad,
             },
             default_options: Default::default(),
+            disable_cache,
+            hdr_output: options.and_then(|o| o.hdr_output),
+            lut_identities,
+            pending_passes: None,
+            profiler: FrameProfiler::new(3),
         })
     }
 
+    /// Reload the filter chain with a new shader preset.
+    ///
+    /// The passes of the preset are compiled immediately, then swapped in at the next call to
+    /// [`frame`](Self::frame). History and feedback framebuffers are kept, and lookup textures that
+    /// are unchanged from the current preset are reused. Runtime parameters are reset to the
+    /// values of the new preset once the passes are swapped in.
+    pub fn reload(&mut self, mut preset: ShaderPresetPack) -> error::Result<()> {
+        if let Some(encoding) = self.hdr_output {
+            append_hdr_output_pass(&mut preset, encoding)?;
+        }
+        let device = &self.common.d3d9;
+        let compiled = CompiledPasses::new(preset, |passes, textures| {
+            let (passes, semantics) = compile_passes(passes, textures, self.disable_cache)?;
+            FilterChainD3D9::init_passes(device, passes, &semantics, self.disable_cache)
+        })?;
+
+        self.pending_passes = Some(compiled);
+        Ok(())
+    }
+
+    fn swap_passes(&mut self, compiled: CompiledPasses<Vec<FilterPass>>) -> error::Result<()> {
+        let CompiledPasses {
+            passes,
+            textures,
+            lut_identities,
+            parameters,
+            pass_count,
+        } = compiled;
+
+        let device = &self.common.d3d9;
+        let mut luts = LutReuse::new(
+            std::mem::take(&mut self.common.luts),
+            &self.lut_identities,
+            &lut_identities,
+            textures,
+            drop,
+        );
+        let loaded = FilterChainD3D9::load_luts(device, luts.take_textures())?;
+        self.common.luts = luts.finish(loaded);
+        self.lut_identities = lut_identities;
+
+        let framebuffer_gen =
+            || D3D9Texture::new(device, Size::new(1, 1), ImageFormat::R8G8B8A8Unorm, false);
+        let input_gen = || None;
+        let framebuffer_init = FramebufferInit::new(
+            passes.iter().map(|f| &f.reflection.meta),
+            &framebuffer_gen,
+            &input_gen,
+        );
+
+        let (output_framebuffers, output_textures) = framebuffer_init
+            .reuse_output_framebuffers(std::mem::take(&mut self.output_framebuffers), drop)?;
+        let (feedback_framebuffers, feedback_textures) = framebuffer_init
+            .reuse_output_framebuffers(std::mem::take(&mut self.feedback_framebuffers), drop)?;
+        let (history_framebuffers, history_textures) =
+            framebuffer_init.reuse_history(std::mem::take(&mut self.history_framebuffers), drop)?;
+
+        self.output_framebuffers = output_framebuffers;
+        self.feedback_framebuffers = feedback_framebuffers;
+        self.history_framebuffers = history_framebuffers;
+        self.common.output_textures = output_textures;
+        self.common.feedback_textures = feedback_textures;
+        self.common.history_textures = history_textures;
+        self.common.config =
+            RuntimeParameters::new(pass_count, parameters, passes.iter().map(|f| &f.source));
+        self.draw_last_pass_feedback = framebuffer_init.uses_final_pass_as_feedback();
+        self.passes = passes;
+
+        Ok(())
+    }
+
     fn push_history(&mut self, input: &IDirect3DTexture9) -> error::Result<()> {
         if let Some(mut back) = self.history_framebuffers.pop_back() {
             back.copy_from(&self.common.d3d9, input)?;
@@ -308,9 +412,16 @@ impl FilterChainD3D9 {
         frame_count: usize,
         options: Option<&FrameOptionsD3D9>,
     ) -> error::Result<()> {
+        if let Some(compiled) = self.pending_passes.take() {
+            self.swap_passes(compiled)?;
+        }
+
+        self.common.config.set_automation_frame(frame_count);
+
         let max = std::cmp::min(self.passes.len(), self.common.config.passes_enabled());
+        let range = options.unwrap_or(&self.default_options).pass_range(max);
 
-        let passes = &mut self.passes[0..max];
+        let passes = &mut self.passes[0..range.end];
         if let Some(options) = options {
             if options.clear_history {
                 for framebuffer in &mut self.history_framebuffers {
@@ -369,11 +480,32 @@ impl FilterChainD3D9 {
             *texture = Some(fbo.as_input(pass.meta.filter, pass.meta.filter, pass.meta.wrap_mode));
         }
 
+        let timestamps = self.profiler.begin_frame(
+            frame_count,
+            passes
+                .iter()
+                .map(|pass| pass.meta.alias.as_ref().map(|alias| alias.as_str())),
+            |count| D3D9Timestamps::new(&self.common.d3d9, count),
+        )?;
+
+        if let Some(timestamps) = &timestamps {
+            timestamps.begin()?;
+            timestamps.write(0)?;
+        }
+
         let passes_len = passes.len();
         let (pass, last) = passes.split_at_mut(passes_len - 1);
         let state_guard = D3D9State::new(&self.common.d3d9)?;
 
         for (index, pass) in pass.iter_mut().enumerate() {
+            // skip passes before the range, so that the first pass reads the original image.
+            if index < range.start {
+                if let Some(timestamps) = &timestamps {
+                    timestamps.write(index + 1)?;
+                }
+                continue;
+            }
+
             source.filter = pass.meta.filter;
             source.wrap = pass.meta.wrap_mode;
             source.is_srgb = pass.meta.srgb_framebuffer;
@@ -392,6 +524,10 @@ impl FilterChainD3D9 {
                 QuadType::Offscreen,
             )?;
 
+            if let Some(timestamps) = &timestamps {
+                timestamps.write(index + 1)?;
+            }
+
             source = D3D9InputTexture {
                 handle: target.handle.clone(),
                 filter: pass.meta.filter,
@@ -410,9 +546,66 @@ impl FilterChainD3D9 {
             source.wrap = pass.meta.wrap_mode;
             source.is_srgb = pass.meta.srgb_framebuffer;
 
-            if self.draw_last_pass_feedback {
-                let feedback_target = &self.output_framebuffers[index];
-                let feedback_target_rtv = feedback_target.as_output()?;
+            if options.compare_enabled() {
+                // draw the last pass at the size of the viewport, to compare with the original image.
+                let target = &mut self.output_framebuffers[index];
+                compare::scale_compare_source(target, pass, viewport.size, &())?;
+                let target_rtv = target.as_output()?;
+                pass.draw(
+                    &self.common.d3d9,
+                    index,
+                    &self.common,
+                    pass.meta.get_frame_count(frame_count),
+                    options,
+                    viewport,
+                    &original,
+                    &source,
+                    RenderTarget::identity(&target_rtv)?,
+                    QuadType::Offscreen,
+                )?;
+
+                let source = D3D9InputTexture {
+                    handle: target.handle.clone(),
+                    filter: self.compare_pass.meta.filter,
+                    wrap: self.compare_pass.meta.wrap_mode,
+                    mipmode: self.compare_pass.meta.filter,
+                    is_srgb: pass.meta.srgb_framebuffer,
+                };
+                compare::set_compare_uniforms(
+                    &mut self.compare_pass.source.parameters,
+                    options.compare_split,
+                    options.compare_line_width,
+                );
+                self.compare_pass.draw(
+                    &self.common.d3d9,
+                    passes_len,
+                    &self.common,
+                    self.compare_pass.meta.get_frame_count(frame_count),
+                    options,
+                    viewport,
+                    &original,
+                    &source,
+                    RenderTarget::viewport(viewport),
+                    QuadType::Final,
+                )?;
+            } else {
+                if self.draw_last_pass_feedback {
+                    let feedback_target = &self.output_framebuffers[index];
+                    let feedback_target_rtv = feedback_target.as_output()?;
+
+                    pass.draw(
+                        &self.common.d3d9,
+                        index,
+                        &self.common,
+                        pass.meta.get_frame_count(frame_count),
+                        options,
+                        viewport,
+                        &original,
+                        &source,
+                        RenderTarget::viewport_with_output(&feedback_target_rtv, viewport),
+                        QuadType::Final,
+                    )?;
+                }
 
                 pass.draw(
                     &self.common.d3d9,
@@ -423,23 +616,15 @@ impl FilterChainD3D9 {
                     viewport,
                     &original,
                     &source,
-                    RenderTarget::viewport_with_output(&feedback_target_rtv, viewport),
+                    RenderTarget::viewport(viewport),
                     QuadType::Final,
                 )?;
             }
 
-            pass.draw(
-                &self.common.d3d9,
-                index,
-                &self.common,
-                pass.meta.get_frame_count(frame_count),
-                options,
-                viewport,
-                &original,
-                &source,
-                RenderTarget::viewport(viewport),
-                QuadType::Final,
-            )?;
+            if let Some(timestamps) = &timestamps {
+                timestamps.write(passes_len)?;
+                timestamps.end()?;
+            }
         }
 
         std::mem::swap(
@@ -454,3 +639,56 @@ impl FilterChainD3D9 {
         Ok(())
     }
 }
+
+use librashader_runtime::impl_filter_chain_debug;
+impl_filter_chain_debug!(FilterChainD3D9, IDirect3DTexture9, |texture| {
+    texture.handle.clone()
+});
+
+use librashader_runtime::impl_filter_chain_profiling;
+impl_filter_chain_profiling!(FilterChainD3D9);
+
+impl librashader_runtime::filter_chain::FilterChain for FilterChainD3D9 {
+    type Device<'a> = &'a IDirect3DDevice9;
+    type InputImage<'a> = &'a IDirect3DTexture9;
+    type OutputView<'a> = &'a IDirect3DSurface9;
+    type CommandContext<'a> = ();
+    type FilterChainOptions = FilterChainOptionsD3D9;
+    type FrameOptions = FrameOptionsD3D9;
+    type Error = FilterChainError;
+
+    unsafe fn load_from_pack(
+        preset: ShaderPresetPack,
+        device: Self::Device<'_>,
+        options: Option<&Self::FilterChainOptions>,
+    ) -> error::Result<Self> {
+        unsafe { FilterChainD3D9::load_from_pack(preset, device, options) }
+    }
+
+    unsafe fn load_from_preset(
+        preset: ShaderPreset,
+        device: Self::Device<'_>,
+        options: Option<&Self::FilterChainOptions>,
+    ) -> error::Result<Self> {
+        unsafe { FilterChainD3D9::load_from_preset(preset, device, options) }
+    }
+
+    unsafe fn load_from_path(
+        path: impl AsRef<Path>,
+        device: Self::Device<'_>,
+        options: Option<&Self::FilterChainOptions>,
+    ) -> error::Result<Self> {
+        unsafe { FilterChainD3D9::load_from_path(path, device, options) }
+    }
+
+    unsafe fn frame<'a>(
+        &mut self,
+        input: Self::InputImage<'a>,
+        viewport: &Viewport<Self::OutputView<'a>>,
+        _cmd: Self::CommandContext<'a>,
+        frame_count: usize,
+        options: Option<&Self::FrameOptions>,
+    ) -> error::Result<()> {
+        unsafe { FilterChainD3D9::frame(self, input, viewport, frame_count, options) }
+    }
+}
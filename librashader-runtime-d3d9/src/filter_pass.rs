@@ -60,33 +60,21 @@ impl BindSemantics<D3D9UniformBinder, ConstantRegister> for FilterPass {
         // eprintln!("binding s{}", binding.binding);
         unsafe {
             if let Err(e) = device.SetTexture(binding.binding, &texture.handle) {
-                println!(
-                    "[librashader-runtime-d3d9] failed to texture at {}: {e}",
-                    binding.binding
-                );
+                log::warn!("failed to texture at {}: {e}", binding.binding);
             }
 
             let setter = samplers.get(texture.wrap, texture.filter, texture.mipmode);
             if let Err(e) = setter(&device, binding.binding) {
-                println!(
-                    "[librashader-runtime-d3d9] failed to set sampler at {}: {e}",
-                    binding.binding
-                );
+                log::warn!("failed to set sampler at {}: {e}", binding.binding);
             }
 
             if texture.is_srgb {
                 if let Err(e) = device.SetSamplerState(binding.binding, D3DSAMP_SRGBTEXTURE, 1u32) {
-                    println!(
-                        "[librashader-runtime-d3d9] failed to set srgb at {}: {e}",
-                        binding.binding
-                    );
+                    log::warn!("failed to set srgb at {}: {e}", binding.binding);
                 }
             } else {
                 if let Err(e) = device.SetSamplerState(binding.binding, D3DSAMP_SRGBTEXTURE, 0u32) {
-                    println!(
-                        "[librashader-runtime-d3d9] failed to set srgb at {}: {e}",
-                        binding.binding
-                    );
+                    log::warn!("failed to set srgb at {}: {e}", binding.binding);
                 }
             }
         }
@@ -118,6 +106,15 @@ impl FilterPass {
                 rotation: options.rotation,
                 total_subframes: options.total_subframes,
                 current_subframe: options.current_subframe,
+                aspect_ratio: options.aspect_ratio,
+                original_fps: options.original_fps,
+                frametime_delta: options.frametime_delta,
+                core_fps: options.core_fps,
+                enable_hdr: options.enable_hdr,
+                paper_white_nits: options.paper_white_nits,
+                max_nits: options.max_nits,
+                expand_gamut: options.expand_gamut,
+                inverse_tonemap: options.inverse_tonemap,
                 frame_direction: options.frame_direction,
                 framebuffer_size: fb_size,
                 viewport_size,
@@ -21,7 +21,7 @@ impl D3D9State {
 impl Drop for D3D9State {
     fn drop(&mut self) {
         if let Err(e) = unsafe { self.state.Apply() } {
-            println!("librashader-runtime-d3d9: [warn] failed to restore state {e:?}")
+            log::warn!("failed to restore state {e:?}")
         }
     }
 }
@@ -10,6 +10,7 @@ mod filter_pass;
 mod graphics_pipeline;
 mod luts;
 pub mod options;
+mod profiling;
 mod samplers;
 mod texture;
 mod util;
@@ -0,0 +1,117 @@
+use crate::error::{assume_d3d_init, Result};
+use librashader_runtime::profiling::{timestamp_durations, PassTimer};
+use std::time::Duration;
+use windows::Win32::Foundation::BOOL;
+use windows::Win32::Graphics::Direct3D9::{
+    IDirect3DDevice9, IDirect3DQuery9, D3DISSUE_BEGIN, D3DISSUE_END, D3DQUERYTYPE,
+    D3DQUERYTYPE_TIMESTAMP, D3DQUERYTYPE_TIMESTAMPDISJOINT, D3DQUERYTYPE_TIMESTAMPFREQ,
+};
+
+/// Timestamps issued before the first pass and after each pass of a frame.
+pub(crate) struct D3D9Timestamps {
+    disjoint: IDirect3DQuery9,
+    frequency: IDirect3DQuery9,
+    timestamps: Vec<IDirect3DQuery9>,
+}
+
+fn create_query(device: &IDirect3DDevice9, query: D3DQUERYTYPE) -> Result<IDirect3DQuery9> {
+    let mut handle = None;
+    unsafe {
+        device.CreateQuery(query, &mut handle)?;
+    }
+    assume_d3d_init!(handle, "CreateQuery");
+    Ok(handle)
+}
+
+/// Read the result of a query without flushing. The data is left untouched if the
+/// result is not available.
+fn get_data<T>(query: &IDirect3DQuery9, data: &mut T) -> bool {
+    unsafe {
+        query
+            .GetData(data as *mut T as *mut _, std::mem::size_of::<T>() as u32, 0)
+            .is_ok()
+    }
+}
+
+impl D3D9Timestamps {
+    /// Create timestamp queries for the given number of passes, if the device supports them.
+    pub fn new(device: &IDirect3DDevice9, passes: usize) -> Result<Option<D3D9Timestamps>> {
+        // Passing a null query checks if the query type is supported.
+        let supported = [
+            D3DQUERYTYPE_TIMESTAMPDISJOINT,
+            D3DQUERYTYPE_TIMESTAMPFREQ,
+            D3DQUERYTYPE_TIMESTAMP,
+        ]
+        .into_iter()
+        .all(|query| unsafe { device.CreateQuery(query, std::ptr::null_mut()).is_ok() });
+
+        if !supported {
+            return Ok(None);
+        }
+
+        let disjoint = create_query(device, D3DQUERYTYPE_TIMESTAMPDISJOINT)?;
+        let frequency = create_query(device, D3DQUERYTYPE_TIMESTAMPFREQ)?;
+        let timestamps = (0..=passes)
+            .map(|_| create_query(device, D3DQUERYTYPE_TIMESTAMP))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Some(D3D9Timestamps {
+            disjoint,
+            frequency,
+            timestamps,
+        }))
+    }
+
+    /// Begin the disjoint query that the timestamps of the frame are issued in.
+    pub fn begin(&self) -> Result<()> {
+        unsafe {
+            self.disjoint.Issue(D3DISSUE_BEGIN)?;
+            self.frequency.Issue(D3DISSUE_END)?;
+        }
+        Ok(())
+    }
+
+    /// Issue the timestamp at the given index once all previous commands have completed.
+    pub fn write(&self, index: usize) -> Result<()> {
+        unsafe { self.timestamps[index].Issue(D3DISSUE_END)? };
+        Ok(())
+    }
+
+    /// End the disjoint query that the timestamps of the frame are issued in.
+    pub fn end(&self) -> Result<()> {
+        unsafe { self.disjoint.Issue(D3DISSUE_END)? };
+        Ok(())
+    }
+}
+
+impl PassTimer for D3D9Timestamps {
+    fn capacity(&self) -> usize {
+        self.timestamps.len() - 1
+    }
+
+    fn read(&mut self, passes: usize) -> Option<Vec<Duration>> {
+        // Results are left untouched if they are not available, so start from values
+        // that can not be mistaken for a completed query.
+        let mut disjoint = BOOL::from(true);
+        let mut frequency = 0u64;
+        if !get_data(&self.disjoint, &mut disjoint)
+            || disjoint.as_bool()
+            || !get_data(&self.frequency, &mut frequency)
+            || frequency == 0
+        {
+            return None;
+        }
+
+        let mut timestamps = vec![0u64; passes + 1];
+        for (query, timestamp) in self.timestamps.iter().zip(timestamps.iter_mut()) {
+            if !get_data(query, timestamp) || *timestamp == 0 {
+                return None;
+            }
+        }
+
+        Some(timestamp_durations(
+            &timestamps,
+            1_000_000_000.0 / frequency as f64,
+        ))
+    }
+}
@@ -165,7 +165,7 @@ impl D3D9Texture {
         };
 
         if self.size()? != size || D3DFORMAT::from(self.original_format) != desc.Format {
-            eprintln!("[history] resizing");
+            log::debug!("resizing history texture");
             self.init(size, ImageFormat::from(desc.Format))?;
         }
 
@@ -263,6 +263,7 @@ pub mod d3d9_hello_triangle {
                     Some(&FilterChainOptionsD3D9 {
                         force_no_mipmaps: false,
                         disable_cache: true,
+                        hdr_output: None,
                     }),
                 )
                 .unwrap()
@@ -4,6 +4,7 @@ use librashader_preprocess::PreprocessError;
 use librashader_presets::ParsePresetError;
 use librashader_reflect::error::{ShaderCompileError, ShaderReflectError};
 use librashader_runtime::image::ImageError;
+use librashader_runtime::state::StateError;
 use thiserror::Error;
 
 /// Cumulative error type for OpenGL filter chains.
@@ -38,6 +39,8 @@ pub enum FilterChainError {
     GlInvalidFramebuffer,
     #[error("opengl error: {0}")]
     GlError(String),
+    #[error("filter chain state error")]
+    StateError(#[from] StateError),
     #[error("unreachable")]
     Infallible(#[from] std::convert::Infallible),
 }
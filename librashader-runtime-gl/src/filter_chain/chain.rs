@@ -6,6 +6,7 @@ use crate::gl::{
     OutputFramebuffer, UboRing,
 };
 use crate::options::{FilterChainOptionsGL, FrameOptionsGL};
+use crate::profiling::GlTimeElapsed;
 use crate::samplers::SamplerSet;
 use crate::texture::InputTexture;
 use crate::util::{gl_get_version, gl_u16_to_version};
@@ -26,17 +27,22 @@ use librashader_reflect::reflect::cross::SpirvCross;
 use librashader_reflect::reflect::presets::{CompilePresetTarget, ShaderPassArtifact};
 use librashader_reflect::reflect::ReflectShader;
 use librashader_runtime::binding::BindingUtil;
+use librashader_runtime::compare;
 use librashader_runtime::framebuffer::FramebufferInit;
+use librashader_runtime::hdr::{append_hdr_output_pass, HdrOutputEncoding};
+use librashader_runtime::profiling::FrameProfiler;
 use librashader_runtime::quad::QuadType;
 use librashader_runtime::render_target::RenderTarget;
 use librashader_runtime::scaling::ScaleFramebuffer;
+use librashader_runtime::state::FilterChainState;
 
 use std::collections::VecDeque;
 use std::sync::Arc;
 
 pub(crate) struct FilterChainImpl<T: GLInterface> {
     pub(crate) common: FilterCommon,
-    passes: Box<[FilterPass<T>]>,
+    pub(crate) passes: Box<[FilterPass<T>]>,
+    compare_pass: FilterPass<T>,
     draw_quad: T::DrawQuad,
     output_framebuffers: Box<[GLFramebuffer]>,
     feedback_framebuffers: Box<[GLFramebuffer]>,
@@ -44,6 +50,13 @@ pub(crate) struct FilterChainImpl<T: GLInterface> {
     render_target: OutputFramebuffer,
     default_options: FrameOptionsGL,
     draw_last_pass_feedback: bool,
+    version: GlslVersion,
+    disable_cache: bool,
+    hdr_output: Option<HdrOutputEncoding>,
+    lut_identities: Box<[LutIdentity]>,
+    pending_passes: Option<CompiledPasses<Box<[FilterPass<T>]>>>,
+    pub(crate) profiler: FrameProfiler<GlTimeElapsed>,
+    frame_count: usize,
 }
 
 pub(crate) struct FilterCommon {
@@ -131,14 +144,18 @@ mod compile {
 
 use compile::{compile_passes, ShaderPassMeta};
 use librashader_runtime::parameters::RuntimeParameters;
+use librashader_runtime::reload::{CompiledPasses, LutIdentity, LutReuse};
 
 impl<T: GLInterface> FilterChainImpl<T> {
     /// Load a filter chain from a pre-parsed `ShaderPreset`.
     pub(crate) unsafe fn load_from_pack(
-        preset: ShaderPresetPack,
+        mut preset: ShaderPresetPack,
         context: Arc<glow::Context>,
         options: Option<&FilterChainOptionsGL>,
     ) -> error::Result<Self> {
+        if let Some(encoding) = options.and_then(|o| o.hdr_output) {
+            append_hdr_output_pass(&mut preset, encoding)?;
+        }
         let disable_cache = options.map_or(false, |o| o.disable_cache);
         let (passes, semantics) = compile_passes(preset.passes, &preset.textures, disable_cache)?;
         let version = options.map_or_else(
@@ -148,6 +165,10 @@ impl<T: GLInterface> FilterChainImpl<T> {
 
         // initialize passes
         let filters = Self::init_passes(&context, version, passes, &semantics, disable_cache)?;
+        let compare_pass = compare::compile_compare_pass(|passes| {
+            let (passes, semantics) = compile_passes(passes, &[], disable_cache)?;
+            Self::init_passes(&context, version, passes, &semantics, disable_cache)
+        })?;
 
         let default_filter = filters.first().map(|f| f.meta.filter).unwrap_or_default();
         let default_wrap = filters
@@ -158,6 +179,7 @@ impl<T: GLInterface> FilterChainImpl<T> {
         let samplers = SamplerSet::new(&context)?;
 
         // load luts
+        let lut_identities = LutIdentity::from_textures(&preset.textures);
         let luts = T::LoadLut::load_luts(&context, preset.textures)?;
 
         let framebuffer_gen = || T::FramebufferInterface::new(&context, 1);
@@ -189,15 +211,22 @@ impl<T: GLInterface> FilterChainImpl<T> {
 
         let output = OutputFramebuffer::new(&context);
 
+        let config = RuntimeParameters::new(
+            preset.pass_count as usize,
+            preset.parameters,
+            filters.iter().map(|f| &f.source),
+        );
+
         Ok(FilterChainImpl {
             draw_last_pass_feedback: framebuffer_init.uses_final_pass_as_feedback(),
             passes: filters,
+            compare_pass,
             output_framebuffers,
             feedback_framebuffers,
             history_framebuffers,
             draw_quad,
             common: FilterCommon {
-                config: RuntimeParameters::new(preset.pass_count as usize, preset.parameters),
+                config,
                 disable_mipmaps: options.map_or(false, |o| o.force_no_mipmaps),
                 luts,
                 samplers,
@@ -208,9 +237,142 @@ impl<T: GLInterface> FilterChainImpl<T> {
             },
             default_options: Default::default(),
             render_target: output,
+            version,
+            disable_cache,
+            hdr_output: options.and_then(|o| o.hdr_output),
+            lut_identities,
+            pending_passes: None,
+            profiler: FrameProfiler::new(3),
+            frame_count: 0,
         })
     }
 
+    /// Compile the passes of a shader preset, to be swapped in at the next frame.
+    pub(crate) unsafe fn reload(&mut self, mut preset: ShaderPresetPack) -> error::Result<()> {
+        if let Some(encoding) = self.hdr_output {
+            append_hdr_output_pass(&mut preset, encoding)?;
+        }
+        let compiled = CompiledPasses::new(preset, |passes, textures| {
+            let (passes, semantics) = compile_passes(passes, textures, self.disable_cache)?;
+            Self::init_passes(
+                &self.common.context,
+                self.version,
+                passes,
+                &semantics,
+                self.disable_cache,
+            )
+        })?;
+
+        self.pending_passes = Some(compiled);
+        Ok(())
+    }
+
+    /// Read back the temporal state of the filter chain.
+    pub(crate) fn snapshot_state(&self) -> error::Result<FilterChainState> {
+        // Outputs are swapped into the feedback framebuffers at the end of every frame.
+        Ok(FilterChainState {
+            frame_count: self.frame_count,
+            history: self
+                .history_framebuffers
+                .iter()
+                .map(GLFramebuffer::read_state)
+                .collect::<error::Result<_>>()?,
+            feedback: self
+                .feedback_framebuffers
+                .iter()
+                .map(GLFramebuffer::read_state)
+                .collect::<error::Result<_>>()?,
+        })
+    }
+
+    /// Upload a temporal state read back with [`snapshot_state`](Self::snapshot_state).
+    pub(crate) fn restore_state(&mut self, state: &FilterChainState) -> error::Result<()> {
+        state.check_compatible(
+            self.history_framebuffers.len(),
+            self.feedback_framebuffers.len(),
+        )?;
+
+        for (framebuffer, saved) in self.history_framebuffers.iter_mut().zip(&state.history) {
+            if let Some(saved) = saved {
+                framebuffer.restore_state::<T::FramebufferInterface>(saved)?;
+            }
+        }
+
+        for (framebuffer, saved) in self.feedback_framebuffers.iter_mut().zip(&state.feedback) {
+            if let Some(saved) = saved {
+                framebuffer.restore_state::<T::FramebufferInterface>(saved)?;
+            }
+        }
+
+        self.frame_count = state.frame_count;
+        Ok(())
+    }
+
+    fn swap_passes(&mut self, compiled: CompiledPasses<Box<[FilterPass<T>]>>) -> error::Result<()> {
+        let CompiledPasses {
+            passes,
+            textures,
+            lut_identities,
+            parameters,
+            pass_count,
+        } = compiled;
+
+        let context = &self.common.context;
+        let mut luts = LutReuse::new(
+            std::mem::take(&mut self.common.luts),
+            &self.lut_identities,
+            &lut_identities,
+            textures,
+            |lut| {
+                if let Some(image) = lut.image.handle {
+                    unsafe { context.delete_texture(image) }
+                }
+            },
+        );
+        let loaded = T::LoadLut::load_luts(context, luts.take_textures())?;
+        self.common.luts = luts.finish(loaded);
+        self.lut_identities = lut_identities;
+
+        let default_filter = passes.first().map(|f| f.meta.filter).unwrap_or_default();
+        let default_wrap = passes.first().map(|f| f.meta.wrap_mode).unwrap_or_default();
+
+        let framebuffer_gen = || T::FramebufferInterface::new(context, 1);
+        let input_gen = || InputTexture {
+            image: Default::default(),
+            filter: default_filter,
+            mip_filter: default_filter,
+            wrap_mode: default_wrap,
+        };
+        let framebuffer_init = FramebufferInit::new(
+            passes.iter().map(|f| &f.reflection.meta),
+            &framebuffer_gen,
+            &input_gen,
+        );
+
+        let (output_framebuffers, output_textures) = framebuffer_init
+            .reuse_output_framebuffers(std::mem::take(&mut self.output_framebuffers), drop)?;
+        let (feedback_framebuffers, feedback_textures) = framebuffer_init
+            .reuse_output_framebuffers(std::mem::take(&mut self.feedback_framebuffers), drop)?;
+        let (history_framebuffers, history_textures) =
+            framebuffer_init.reuse_history(std::mem::take(&mut self.history_framebuffers), drop)?;
+
+        self.output_framebuffers = output_framebuffers;
+        self.feedback_framebuffers = feedback_framebuffers;
+        self.history_framebuffers = history_framebuffers;
+        self.common.output_textures = output_textures;
+        self.common.feedback_textures = feedback_textures;
+        self.common.history_textures = history_textures;
+        self.common.config =
+            RuntimeParameters::new(pass_count, parameters, passes.iter().map(|f| &f.source));
+        self.draw_last_pass_feedback = framebuffer_init.uses_final_pass_as_feedback();
+
+        for pass in std::mem::replace(&mut self.passes, passes) {
+            unsafe { self.common.context.delete_program(pass.program) }
+        }
+
+        Ok(())
+    }
+
     fn init_passes(
         context: &glow::Context,
         version: GlslVersion,
@@ -223,7 +385,9 @@ impl<T: GLInterface> FilterChainImpl<T> {
         // initialize passes
         for (index, (config, mut reflect)) in passes.into_iter().enumerate() {
             let reflection = reflect.reflect(index, semantics)?;
-            let glsl = reflect.compile(version)?;
+            let glsl = reflect
+                .compile(version)
+                .map_err(|error| error.in_pass(index))?;
 
             let (program, ubo_location) =
                 T::CompileShader::compile_program(context, glsl, !disable_cache)?;
@@ -289,9 +453,17 @@ impl<T: GLInterface> FilterChainImpl<T> {
         input: &GLImage,
         options: Option<&FrameOptionsGL>,
     ) -> error::Result<()> {
+        if let Some(compiled) = self.pending_passes.take() {
+            self.swap_passes(compiled)?;
+        }
+        self.frame_count = frame_count;
+
+        self.common.config.set_automation_frame(frame_count);
+
         // limit number of passes to those enabled.
         let max = std::cmp::min(self.passes.len(), self.common.config.passes_enabled());
-        let passes = &mut self.passes[0..max];
+        let range = options.unwrap_or(&self.default_options).pass_range(max);
+        let passes = &mut self.passes[0..range.end];
 
         if let Some(options) = options {
             if options.clear_history {
@@ -357,17 +529,38 @@ impl<T: GLInterface> FilterChainImpl<T> {
             texture.image = fbo.as_texture(pass.meta.filter, pass.meta.wrap_mode).image;
         }
 
+        let timers = self.profiler.begin_frame(
+            frame_count,
+            passes
+                .iter()
+                .map(|pass| pass.meta.alias.as_ref().map(|alias| alias.as_str())),
+            |count| GlTimeElapsed::new(&self.common.context, count),
+        )?;
+
         let passes_len = passes.len();
         let (pass, last) = passes.split_at_mut(passes_len - 1);
 
         self.draw_quad
             .bind_vertices(&self.common.context, QuadType::Offscreen);
         for (index, pass) in pass.iter_mut().enumerate() {
+            // skip passes before the range, so that the first pass reads the original image.
+            if index < range.start {
+                if let Some(timers) = &timers {
+                    timers.begin(index);
+                    timers.end();
+                }
+                continue;
+            }
+
             let target = &self.output_framebuffers[index];
             source.filter = pass.meta.filter;
             source.mip_filter = pass.meta.filter;
             source.wrap_mode = pass.meta.wrap_mode;
 
+            if let Some(timers) = &timers {
+                timers.begin(index);
+            }
+
             pass.draw(
                 index,
                 &self.common,
@@ -379,6 +572,10 @@ impl<T: GLInterface> FilterChainImpl<T> {
                 RenderTarget::identity(target)?,
             )?;
 
+            if let Some(timers) = &timers {
+                timers.end();
+            }
+
             let target = target.as_texture(pass.meta.filter, pass.meta.wrap_mode);
             self.common.output_textures[index] = target;
             source = target;
@@ -398,8 +595,70 @@ impl<T: GLInterface> FilterChainImpl<T> {
             source.mip_filter = pass.meta.filter;
             source.wrap_mode = pass.meta.wrap_mode;
 
-            if self.draw_last_pass_feedback {
-                let target = &self.output_framebuffers[index];
+            if let Some(timers) = &timers {
+                timers.begin(index);
+            }
+
+            if options.compare_enabled() {
+                // draw the last pass at the size of the viewport, to compare with the original image.
+                let target = &mut self.output_framebuffers[index];
+                compare::scale_compare_source::<T::FramebufferInterface, _, _>(
+                    target,
+                    pass,
+                    viewport.size,
+                    &(),
+                )?;
+
+                self.draw_quad
+                    .bind_vertices(&self.common.context, QuadType::Offscreen);
+                pass.draw(
+                    index,
+                    &self.common,
+                    pass.meta.get_frame_count(frame_count),
+                    options,
+                    viewport,
+                    &original,
+                    &source,
+                    RenderTarget::identity(target)?,
+                )?;
+
+                let mut source = target.as_texture(pass.meta.filter, pass.meta.wrap_mode);
+                source.filter = self.compare_pass.meta.filter;
+                source.mip_filter = self.compare_pass.meta.filter;
+                source.wrap_mode = self.compare_pass.meta.wrap_mode;
+
+                self.draw_quad
+                    .bind_vertices(&self.common.context, QuadType::Final);
+                compare::set_compare_uniforms(
+                    &mut self.compare_pass.source.parameters,
+                    options.compare_split,
+                    options.compare_line_width,
+                );
+                self.compare_pass.draw(
+                    passes_len,
+                    &self.common,
+                    self.compare_pass.meta.get_frame_count(frame_count),
+                    options,
+                    viewport,
+                    &original,
+                    &source,
+                    RenderTarget::viewport_with_output(final_viewport, viewport),
+                )?;
+            } else {
+                if self.draw_last_pass_feedback {
+                    let target = &self.output_framebuffers[index];
+                    pass.draw(
+                        index,
+                        &self.common,
+                        pass.meta.get_frame_count(frame_count),
+                        options,
+                        viewport,
+                        &original,
+                        &source,
+                        RenderTarget::viewport_with_output(target, viewport),
+                    )?;
+                }
+
                 pass.draw(
                     index,
                     &self.common,
@@ -408,20 +667,13 @@ impl<T: GLInterface> FilterChainImpl<T> {
                     viewport,
                     &original,
                     &source,
-                    RenderTarget::viewport_with_output(target, viewport),
+                    RenderTarget::viewport_with_output(final_viewport, viewport),
                 )?;
             }
 
-            pass.draw(
-                index,
-                &self.common,
-                pass.meta.get_frame_count(frame_count),
-                options,
-                viewport,
-                &original,
-                &source,
-                RenderTarget::viewport_with_output(final_viewport, viewport),
-            )?;
+            if let Some(timers) = &timers {
+                timers.end();
+            }
             self.common.output_textures[passes_len - 1] = viewport
                 .output
                 .as_texture(pass.meta.filter, pass.meta.wrap_mode);
@@ -0,0 +1,77 @@
+use crate::filter_chain::chain::FilterChainImpl;
+use crate::filter_chain::inner::FilterChainDispatch;
+use crate::gl::GLInterface;
+use crate::texture::InputTexture;
+use crate::{FilterChainGL, GLImage};
+use librashader_runtime::debug::FilterChainDebug;
+
+fn bound_image(texture: Option<&InputTexture>) -> Option<GLImage> {
+    texture
+        .filter(|texture| texture.is_bound())
+        .map(|texture| texture.image)
+}
+
+impl FilterChainDispatch {
+    fn debug(&self) -> &dyn FilterChainDebug<Image = GLImage> {
+        match self {
+            FilterChainDispatch::DirectStateAccess(p) => p,
+            FilterChainDispatch::Compatibility(p) => p,
+        }
+    }
+}
+
+impl FilterChainDebug for FilterChainGL {
+    type Image = GLImage;
+
+    fn pass_count(&self) -> usize {
+        self.filter.debug().pass_count()
+    }
+
+    fn pass_alias(&self, index: usize) -> Option<&str> {
+        self.filter.debug().pass_alias(index)
+    }
+
+    fn pass_output(&self, index: usize) -> Option<GLImage> {
+        self.filter.debug().pass_output(index)
+    }
+
+    fn pass_feedback(&self, index: usize) -> Option<GLImage> {
+        self.filter.debug().pass_feedback(index)
+    }
+
+    fn history(&self, index: usize) -> Option<GLImage> {
+        self.filter.debug().history(index)
+    }
+}
+
+impl<T: GLInterface> FilterChainDebug for FilterChainImpl<T> {
+    type Image = GLImage;
+
+    fn pass_count(&self) -> usize {
+        self.passes.len()
+    }
+
+    fn pass_alias(&self, index: usize) -> Option<&str> {
+        self.passes
+            .get(index)?
+            .meta
+            .alias
+            .as_ref()
+            .map(|alias| alias.as_str())
+    }
+
+    fn pass_output(&self, index: usize) -> Option<GLImage> {
+        if index + 1 >= self.passes.len() || index + 1 >= self.common.config.passes_enabled() {
+            return None;
+        }
+        bound_image(self.common.output_textures.get(index))
+    }
+
+    fn pass_feedback(&self, index: usize) -> Option<GLImage> {
+        bound_image(self.common.feedback_textures.get(index))
+    }
+
+    fn history(&self, index: usize) -> Option<GLImage> {
+        bound_image(self.common.history_textures.get(index.checked_sub(1)?))
+    }
+}
@@ -9,13 +9,16 @@ use std::path::Path;
 use std::sync::Arc;
 
 mod chain;
+mod debug;
 mod inner;
 mod parameters;
+mod profiling;
 
 pub(crate) use chain::FilterCommon;
 use librashader_common::Viewport;
 use librashader_pack::ShaderPresetPack;
 use librashader_presets::context::VideoDriver;
+use librashader_runtime::state::FilterChainState;
 
 /// An OpenGL filter chain.
 pub struct FilterChainGL {
@@ -88,6 +91,48 @@ impl FilterChainGL {
         }
     }
 
+    /// Reload the filter chain with a new shader preset.
+    ///
+    /// The passes of the preset are compiled immediately, then swapped in at the next call to
+    /// [`frame`](Self::frame). History and feedback framebuffers are kept, and lookup textures that
+    /// are unchanged from the current preset are reused. Runtime parameters are reset to the
+    /// values of the new preset once the passes are swapped in.
+    ///
+    /// # Safety
+    /// The GL context of the filter chain must be current.
+    pub unsafe fn reload(&mut self, preset: ShaderPresetPack) -> Result<()> {
+        match &mut self.filter {
+            FilterChainDispatch::DirectStateAccess(p) => unsafe { p.reload(preset) },
+            FilterChainDispatch::Compatibility(p) => unsafe { p.reload(preset) },
+        }
+    }
+
+    /// Read back the temporal state of the filter chain.
+    ///
+    /// The state includes the `OriginalHistory` and `PassFeedback` framebuffers, and can be
+    /// restored into a filter chain of any runtime loaded with the same preset.
+    ///
+    /// # Safety
+    /// The GL context of the filter chain must be current.
+    pub unsafe fn snapshot_state(&self) -> Result<FilterChainState> {
+        match &self.filter {
+            FilterChainDispatch::DirectStateAccess(p) => p.snapshot_state(),
+            FilterChainDispatch::Compatibility(p) => p.snapshot_state(),
+        }
+    }
+
+    /// Restore the temporal state of the filter chain from a state read back with
+    /// [`snapshot_state`](Self::snapshot_state).
+    ///
+    /// # Safety
+    /// The GL context of the filter chain must be current.
+    pub unsafe fn restore_state(&mut self, state: &FilterChainState) -> Result<()> {
+        match &mut self.filter {
+            FilterChainDispatch::DirectStateAccess(p) => p.restore_state(state),
+            FilterChainDispatch::Compatibility(p) => p.restore_state(state),
+        }
+    }
+
     /// Get the GL context associated with this filter chain
     pub fn get_context(&self) -> &Arc<glow::Context> {
         match &self.filter {
@@ -96,3 +141,48 @@ impl FilterChainGL {
         }
     }
 }
+
+impl librashader_runtime::filter_chain::FilterChain for FilterChainGL {
+    type Device<'a> = Arc<glow::Context>;
+    type InputImage<'a> = &'a GLImage;
+    type OutputView<'a> = &'a GLImage;
+    type CommandContext<'a> = ();
+    type FilterChainOptions = FilterChainOptionsGL;
+    type FrameOptions = FrameOptionsGL;
+    type Error = FilterChainError;
+
+    unsafe fn load_from_pack(
+        preset: ShaderPresetPack,
+        device: Self::Device<'_>,
+        options: Option<&Self::FilterChainOptions>,
+    ) -> Result<Self> {
+        unsafe { FilterChainGL::load_from_pack(preset, device, options) }
+    }
+
+    unsafe fn load_from_preset(
+        preset: ShaderPreset,
+        device: Self::Device<'_>,
+        options: Option<&Self::FilterChainOptions>,
+    ) -> Result<Self> {
+        unsafe { FilterChainGL::load_from_preset(preset, device, options) }
+    }
+
+    unsafe fn load_from_path(
+        path: impl AsRef<Path>,
+        device: Self::Device<'_>,
+        options: Option<&Self::FilterChainOptions>,
+    ) -> Result<Self> {
+        unsafe { FilterChainGL::load_from_path(path, device, options) }
+    }
+
+    unsafe fn frame<'a>(
+        &mut self,
+        input: Self::InputImage<'a>,
+        viewport: &Viewport<Self::OutputView<'a>>,
+        _cmd: Self::CommandContext<'a>,
+        frame_count: usize,
+        options: Option<&Self::FrameOptions>,
+    ) -> Result<()> {
+        unsafe { FilterChainGL::frame(self, input, viewport, frame_count, options) }
+    }
+}
@@ -0,0 +1,36 @@
+use crate::filter_chain::inner::FilterChainDispatch;
+use crate::profiling::GlTimeElapsed;
+use crate::FilterChainGL;
+use librashader_runtime::profiling::{FilterChainProfiling, FrameProfiler, FrameStatistics};
+
+impl FilterChainDispatch {
+    fn profiler(&self) -> &FrameProfiler<GlTimeElapsed> {
+        match self {
+            FilterChainDispatch::DirectStateAccess(p) => &p.profiler,
+            FilterChainDispatch::Compatibility(p) => &p.profiler,
+        }
+    }
+
+    fn profiler_mut(&mut self) -> &mut FrameProfiler<GlTimeElapsed> {
+        match self {
+            FilterChainDispatch::DirectStateAccess(p) => &mut p.profiler,
+            FilterChainDispatch::Compatibility(p) => &mut p.profiler,
+        }
+    }
+}
+
+/// Timer queries are read back in [`frame_statistics`](FilterChainProfiling::frame_statistics),
+/// so the GL context of the filter chain must be current when it is called.
+impl FilterChainProfiling for FilterChainGL {
+    fn set_profiling(&mut self, enabled: bool) {
+        self.filter.profiler_mut().set_enabled(enabled)
+    }
+
+    fn profiling(&self) -> bool {
+        self.filter.profiler().enabled()
+    }
+
+    fn frame_statistics(&mut self) -> Option<FrameStatistics> {
+        self.filter.profiler_mut().statistics()
+    }
+}
@@ -6,6 +6,7 @@ use glow::HasContext;
 use librashader_common::{FilterMode, GetSize, ImageFormat, Size, WrapMode};
 use librashader_presets::Scale2D;
 use librashader_runtime::scaling::ScaleFramebuffer;
+use librashader_runtime::state::{FramebufferState, StateError};
 use std::sync::Arc;
 
 /// A handle to an OpenGL FBO and its backing texture with format and size information.
@@ -91,6 +92,78 @@ impl GLFramebuffer {
     pub(crate) fn bind<T: FramebufferInterface>(&self) -> Result<()> {
         T::bind(self)
     }
+
+    /// Read back the first mip level of the framebuffer, if it has an image.
+    pub(crate) fn read_state(&self) -> Result<Option<FramebufferState>> {
+        let Some(image) = self.image else {
+            return Ok(None);
+        };
+
+        let format = ImageFormat::from_gl_internal_format(self.format);
+        let (pixel_format, ty) = format
+            .gl_pixel_transfer_format()
+            .ok_or(StateError::UnsupportedFormat(format))?;
+        let mut data = vec![0u8; FramebufferState::data_len(self.size, format)?];
+
+        unsafe {
+            self.ctx.bind_texture(glow::TEXTURE_2D, Some(image));
+            self.ctx.pixel_store_i32(glow::PACK_ROW_LENGTH, 0);
+            self.ctx.pixel_store_i32(glow::PACK_ALIGNMENT, 1);
+            self.ctx.get_tex_image(
+                glow::TEXTURE_2D,
+                0,
+                pixel_format,
+                ty,
+                glow::PixelPackData::Slice(&mut data),
+            );
+            self.ctx.pixel_store_i32(glow::PACK_ALIGNMENT, 4);
+            self.ctx.bind_texture(glow::TEXTURE_2D, None);
+        }
+
+        Ok(Some(FramebufferState::new(self.size, format, data)?))
+    }
+
+    /// Upload saved texels into the first mip level of the framebuffer, reinitializing
+    /// the framebuffer if its size or format do not match.
+    pub(crate) fn restore_state<T: FramebufferInterface>(
+        &mut self,
+        state: &FramebufferState,
+    ) -> Result<()> {
+        let (pixel_format, ty) = state
+            .format
+            .gl_pixel_transfer_format()
+            .ok_or(StateError::UnsupportedFormat(state.format))?;
+
+        if self.image.is_none() || self.size != state.size || self.format != u32::from(state.format)
+        {
+            T::init(self, state.size, state.format)?;
+        }
+
+        unsafe {
+            self.ctx.bind_texture(glow::TEXTURE_2D, self.image);
+            self.ctx.pixel_store_i32(glow::UNPACK_ROW_LENGTH, 0);
+            self.ctx.pixel_store_i32(glow::UNPACK_ALIGNMENT, 1);
+            self.ctx.tex_sub_image_2d(
+                glow::TEXTURE_2D,
+                0,
+                0,
+                0,
+                state.size.width as i32,
+                state.size.height as i32,
+                pixel_format,
+                ty,
+                glow::PixelUnpackData::Slice(&state.data),
+            );
+            self.ctx.pixel_store_i32(glow::UNPACK_ALIGNMENT, 4);
+
+            if self.mip_levels > 1 {
+                self.ctx.generate_mipmap(glow::TEXTURE_2D);
+            }
+            self.ctx.bind_texture(glow::TEXTURE_2D, None);
+        }
+
+        Ok(())
+    }
 }
 
 /// A state-checked wrapper around a raw framebuffer, used exclusively for output images.
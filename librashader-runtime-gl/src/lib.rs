@@ -9,6 +9,7 @@ mod binding;
 mod filter_chain;
 mod filter_pass;
 mod framebuffer;
+mod profiling;
 mod util;
 
 mod gl;
@@ -0,0 +1,100 @@
+use crate::error;
+use crate::error::FilterChainError;
+use glow::HasContext;
+use librashader_runtime::profiling::PassTimer;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// `GL_TIME_ELAPSED` queries around each pass of a frame.
+pub(crate) struct GlTimeElapsed {
+    context: Arc<glow::Context>,
+    queries: Vec<glow::Query>,
+}
+
+impl GlTimeElapsed {
+    /// Create queries for the given number of passes, if the context supports timer queries.
+    pub fn new(context: &Arc<glow::Context>, passes: usize) -> error::Result<Option<Self>> {
+        let version = context.version();
+        if (version.is_embedded || (version.major, version.minor) < (3, 3))
+            && !context
+                .supported_extensions()
+                .contains("GL_ARB_timer_query")
+        {
+            return Ok(None);
+        }
+
+        let mut queries = Vec::with_capacity(passes);
+        for _ in 0..passes {
+            let query = unsafe { context.create_query() }.map_err(FilterChainError::GlError)?;
+            queries.push(query);
+        }
+
+        Ok(Some(GlTimeElapsed {
+            context: Arc::clone(context),
+            queries,
+        }))
+    }
+
+    /// Begin timing the pass at the given index.
+    pub fn begin(&self, index: usize) {
+        unsafe {
+            self.context
+                .begin_query(glow::TIME_ELAPSED, self.queries[index]);
+        }
+    }
+
+    /// Stop timing the current pass.
+    pub fn end(&self) {
+        unsafe {
+            self.context.end_query(glow::TIME_ELAPSED);
+        }
+    }
+}
+
+impl PassTimer for GlTimeElapsed {
+    fn capacity(&self) -> usize {
+        self.queries.len()
+    }
+
+    fn read(&mut self, passes: usize) -> Option<Vec<Duration>> {
+        let queries = &self.queries[..passes];
+        // Queries complete in order, so the results are available once the last query is.
+        let available = match queries.last() {
+            Some(query) => unsafe {
+                self.context
+                    .get_query_parameter_u32(*query, glow::QUERY_RESULT_AVAILABLE)
+                    != 0
+            },
+            None => true,
+        };
+
+        if !available {
+            return None;
+        }
+
+        Some(
+            queries
+                .iter()
+                .map(|query| unsafe {
+                    // The elapsed time is read as 64 bits, a 32-bit result wraps after ~4.29s.
+                    // With no query buffer bound, the offset is the address to write the result to.
+                    let mut nanos = 0u64;
+                    self.context.get_query_parameter_u64_with_offset(
+                        *query,
+                        glow::QUERY_RESULT,
+                        &mut nanos as *mut u64 as usize,
+                    );
+                    Duration::from_nanos(nanos)
+                })
+                .collect(),
+        )
+    }
+}
+
+impl Drop for GlTimeElapsed {
+    fn drop(&mut self) {
+        for query in self.queries.drain(..) {
+            unsafe { self.context.delete_query(query) }
+        }
+    }
+}
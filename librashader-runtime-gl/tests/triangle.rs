@@ -18,6 +18,7 @@ fn triangle_gl() {
                 use_dsa: false,
                 force_no_mipmaps: false,
                 disable_cache: true,
+                hdr_output: None,
             }),
         )
         // FilterChain::load_from_path("../test/slang-shaders/bezel/Mega_Bezel/Presets/MBZ__0__SMOOTH-ADV.slangp", None)
@@ -42,6 +43,7 @@ fn triangle_gl46() {
                 use_dsa: true,
                 force_no_mipmaps: false,
                 disable_cache: false,
+                hdr_output: None,
             }),
         )
         // FilterChain::load_from_path("../test/slang-shaders/bezel/Mega_Bezel/Presets/MBZ__0__SMOOTH-ADV.slangp", None)
@@ -6,8 +6,9 @@ use crate::filter_pass::FilterPass;
 use crate::graphics_pipeline::MetalGraphicsPipeline;
 use crate::luts::LutTexture;
 use crate::options::{FilterChainOptionsMetal, FrameOptionsMetal};
+use crate::profiling::MetalTimestamps;
 use crate::samplers::SamplerSet;
-use crate::texture::{get_texture_size, InputTexture, MetalTextureRef, OwnedTexture};
+use crate::texture::{get_texture_size, InputTexture, MetalTexture, MetalTextureRef, OwnedTexture};
 use librashader_common::map::FastHashMap;
 use librashader_common::{ImageFormat, Size, Viewport};
 use librashader_presets::context::VideoDriver;
@@ -17,12 +18,17 @@ use librashader_reflect::back::targets::MSL;
 use librashader_reflect::back::{CompileReflectShader, CompileShader};
 use librashader_reflect::front::SpirvCompilation;
 use librashader_reflect::reflect::cross::SpirvCross;
-use librashader_reflect::reflect::presets::{CompilePresetTarget, ShaderPassArtifact};
+use librashader_reflect::reflect::presets::{
+    CompilePresetTarget, PassObserver, ShaderPassArtifact,
+};
 use librashader_reflect::reflect::semantics::ShaderSemantics;
 use librashader_reflect::reflect::ReflectShader;
 use librashader_runtime::binding::BindingUtil;
+use librashader_runtime::compare;
 use librashader_runtime::framebuffer::FramebufferInit;
+use librashader_runtime::hdr::{append_hdr_output_pass, HdrOutputEncoding};
 use librashader_runtime::image::{ImageError, LoadedTexture, UVDirection, BGRA8};
+use librashader_runtime::profiling::FrameProfiler;
 use librashader_runtime::quad::QuadType;
 use librashader_runtime::render_target::RenderTarget;
 use librashader_runtime::scaling::ScaleFramebuffer;
@@ -54,12 +60,14 @@ mod compile {
     pub fn compile_passes(
         shaders: Vec<PassResource>,
         textures: &[TextureResource],
+        observer: Option<PassObserver<'_>>,
     ) -> Result<(Vec<ShaderPassMeta>, ShaderSemantics), FilterChainError> {
-        let (passes, semantics) = MSL::compile_preset_passes::<
+        let observer = observer.unwrap_or(&|_, _| true);
+        let (passes, semantics) = MSL::compile_preset_passes_with_observer::<
             SpirvCompilation,
             SpirvCross,
             FilterChainError,
-        >(shaders, textures.iter().map(|t| &t.meta))?;
+        >(shaders, textures.iter().map(|t| &t.meta), observer)?;
         Ok((passes, semantics))
     }
 }
@@ -67,11 +75,13 @@ mod compile {
 use compile::{compile_passes, ShaderPassMeta};
 use librashader_pack::{ShaderPresetPack, TextureResource};
 use librashader_runtime::parameters::RuntimeParameters;
+use librashader_runtime::reload::{CompiledPasses, LutIdentity, LutReuse};
 
 /// A Metal filter chain.
 pub struct FilterChainMetal {
     pub(crate) common: FilterCommon,
     passes: Box<[FilterPass]>,
+    compare_pass: FilterPass,
     output_framebuffers: Box<[OwnedTexture]>,
     feedback_framebuffers: Box<[OwnedTexture]>,
     history_framebuffers: VecDeque<OwnedTexture>,
@@ -86,6 +96,10 @@ pub struct FilterChainMetal {
     disable_mipmaps: bool,
     default_options: FrameOptionsMetal,
     draw_last_pass_feedback: bool,
+    hdr_output: Option<HdrOutputEncoding>,
+    lut_identities: Box<[LutIdentity]>,
+    pending_passes: Option<CompiledPasses<Box<[FilterPass]>>>,
+    profiler: FrameProfiler<MetalTimestamps>,
 }
 
 impl Debug for FilterChainMetal {
@@ -138,7 +152,7 @@ impl FilterChainMetal {
             .ok_or(FilterChainError::FailedToCreateCommandBuffer)?;
 
         let filter_chain =
-            Self::load_from_pack_deferred_internal(preset, queue.device(), &cmd, options)?;
+            Self::load_from_pack_deferred_internal(preset, queue.device(), &cmd, options, None)?;
 
         cmd.commit();
         unsafe { cmd.waitUntilCompleted() };
@@ -181,7 +195,9 @@ impl FilterChainMetal {
             .enumerate()
             .map(|(index, (config, mut reflect))| {
                 let reflection = reflect.reflect(index, semantics)?;
-                let msl = reflect.compile(Some(MslVersion::new(2, 0, 0)))?;
+                let msl = reflect
+                    .compile(Some(MslVersion::new(2, 0, 0)))
+                    .map_err(|error| error.in_pass(index))?;
 
                 let ubo_size = reflection.ubo.as_ref().map_or(0, |ubo| ubo.size as usize);
                 let push_size = reflection
@@ -299,27 +315,64 @@ impl FilterChainMetal {
         cmd: &ProtocolObject<dyn MTLCommandBuffer>,
         options: Option<&FilterChainOptionsMetal>,
     ) -> error::Result<FilterChainMetal> {
-        Self::load_from_pack_deferred_internal(preset, queue.device(), &cmd, options)
+        Self::load_from_pack_deferred_internal(preset, queue.device(), &cmd, options, None)
     }
 
     /// Load a filter chain from a pre-parsed `ShaderPreset`, deferring and GPU-side initialization
     /// to the caller. This function therefore requires no external synchronization of the device queue.
     ///
+    /// `observer` is called after each pass is compiled with the index of the pass and the total
+    /// number of passes. If it returns `false`, loading stops with
+    /// [`ShaderCompileError::Cancelled`](librashader_reflect::error::ShaderCompileError::Cancelled).
+    ///
     /// ## Safety
     /// The provided command buffer must be ready for recording.
     /// The caller is responsible for ending the command buffer and immediately submitting it to a
     /// graphics queue. The command buffer must be completely executed before calling [`frame`](Self::frame).
-    fn load_from_pack_deferred_internal(
+    pub fn load_from_pack_deferred_with_observer(
         preset: ShaderPresetPack,
+        queue: &ProtocolObject<dyn MTLCommandQueue>,
+        cmd: &ProtocolObject<dyn MTLCommandBuffer>,
+        options: Option<&FilterChainOptionsMetal>,
+        observer: PassObserver<'_>,
+    ) -> error::Result<FilterChainMetal> {
+        Self::load_from_pack_deferred_internal(
+            preset,
+            queue.device(),
+            &cmd,
+            options,
+            Some(observer),
+        )
+    }
+
+    /// Load a filter chain from a pre-parsed `ShaderPreset`, deferring and GPU-side initialization
+    /// to the caller. This function therefore requires no external synchronization of the device queue.
+    ///
+    /// ## Safety
+    /// The provided command buffer must be ready for recording.
+    /// The caller is responsible for ending the command buffer and immediately submitting it to a
+    /// graphics queue. The command buffer must be completely executed before calling [`frame`](Self::frame).
+    fn load_from_pack_deferred_internal(
+        mut preset: ShaderPresetPack,
         device: Id<ProtocolObject<dyn MTLDevice>>,
         cmd: &ProtocolObject<dyn MTLCommandBuffer>,
         options: Option<&FilterChainOptionsMetal>,
+        observer: Option<PassObserver<'_>>,
     ) -> error::Result<FilterChainMetal> {
-        let (passes, semantics) = compile_passes(preset.passes, &preset.textures)?;
+        if let Some(encoding) = options.and_then(|o| o.hdr_output) {
+            append_hdr_output_pass(&mut preset, encoding)?;
+        }
+        let (passes, semantics) = compile_passes(preset.passes, &preset.textures, observer)?;
 
         let filters = Self::init_passes(&device, passes, &semantics)?;
 
+        let compare_pass = compare::compile_compare_pass(|passes| {
+            let (passes, semantics) = compile_passes(passes, &[], None)?;
+            Self::init_passes(&device, passes, &semantics)
+        })?;
+
         let samplers = SamplerSet::new(&device)?;
+        let lut_identities = LutIdentity::from_textures(&preset.textures);
         let luts = FilterChainMetal::load_luts(&device, &cmd, preset.textures)?;
         let framebuffer_gen = || {
             Ok::<_, error::FilterChainError>(OwnedTexture::new(
@@ -352,7 +405,11 @@ impl FilterChainMetal {
             common: FilterCommon {
                 luts,
                 samplers,
-                config: RuntimeParameters::new(preset.pass_count as usize, preset.parameters),
+                config: RuntimeParameters::new(
+                    preset.pass_count as usize,
+                    preset.parameters,
+                    filters.iter().map(|f| &f.source),
+                ),
                 draw_quad,
                 device,
                 output_textures,
@@ -360,15 +417,102 @@ impl FilterChainMetal {
                 history_textures,
             },
             passes: filters,
+            compare_pass,
             output_framebuffers,
             feedback_framebuffers,
             history_framebuffers,
             prev_frame_history_buffer: history_buffer,
             disable_mipmaps: options.map(|f| f.force_no_mipmaps).unwrap_or(false),
             default_options: Default::default(),
+            hdr_output: options.and_then(|o| o.hdr_output),
+            lut_identities,
+            pending_passes: None,
+            profiler: FrameProfiler::new(3),
         })
     }
 
+    /// Reload the filter chain with a new shader preset.
+    ///
+    /// The passes of the preset are compiled immediately, then swapped in at the next call to
+    /// [`frame`](Self::frame). History and feedback framebuffers are kept, and lookup textures that
+    /// are unchanged from the current preset are reused. Runtime parameters are reset to the
+    /// values of the new preset once the passes are swapped in.
+    ///
+    /// New lookup textures are uploaded with the command buffer passed to [`frame`](Self::frame).
+    pub fn reload(&mut self, mut preset: ShaderPresetPack) -> error::Result<()> {
+        if let Some(encoding) = self.hdr_output {
+            append_hdr_output_pass(&mut preset, encoding)?;
+        }
+        let compiled = CompiledPasses::new(preset, |passes, textures| {
+            let (passes, semantics) = compile_passes(passes, textures, None)?;
+            Self::init_passes(&self.common.device, passes, &semantics)
+        })?;
+
+        self.pending_passes = Some(compiled);
+        Ok(())
+    }
+
+    fn swap_passes(
+        &mut self,
+        compiled: CompiledPasses<Box<[FilterPass]>>,
+        cmd: &ProtocolObject<dyn MTLCommandBuffer>,
+    ) -> error::Result<()> {
+        let CompiledPasses {
+            passes,
+            textures,
+            lut_identities,
+            parameters,
+            pass_count,
+        } = compiled;
+
+        let device = &self.common.device;
+        let mut luts = LutReuse::new(
+            std::mem::take(&mut self.common.luts),
+            &self.lut_identities,
+            &lut_identities,
+            textures,
+            drop,
+        );
+        let loaded = FilterChainMetal::load_luts(device, cmd, luts.take_textures())?;
+        self.common.luts = luts.finish(loaded);
+        self.lut_identities = lut_identities;
+
+        let framebuffer_gen = || {
+            Ok::<_, error::FilterChainError>(OwnedTexture::new(
+                device,
+                Size::new(1, 1),
+                1,
+                ImageFormat::R8G8B8A8Unorm.into(),
+            )?)
+        };
+        let input_gen = || None;
+        let framebuffer_init = FramebufferInit::new(
+            passes.iter().map(|f| &f.reflection.meta),
+            &framebuffer_gen,
+            &input_gen,
+        );
+
+        let (output_framebuffers, output_textures) = framebuffer_init
+            .reuse_output_framebuffers(std::mem::take(&mut self.output_framebuffers), drop)?;
+        let (feedback_framebuffers, feedback_textures) = framebuffer_init
+            .reuse_output_framebuffers(std::mem::take(&mut self.feedback_framebuffers), drop)?;
+        let (history_framebuffers, history_textures) =
+            framebuffer_init.reuse_history(std::mem::take(&mut self.history_framebuffers), drop)?;
+
+        self.output_framebuffers = output_framebuffers;
+        self.feedback_framebuffers = feedback_framebuffers;
+        self.history_framebuffers = history_framebuffers;
+        self.common.output_textures = output_textures;
+        self.common.feedback_textures = feedback_textures;
+        self.common.history_textures = history_textures;
+        self.common.config =
+            RuntimeParameters::new(pass_count, parameters, passes.iter().map(|f| &f.source));
+        self.draw_last_pass_feedback = framebuffer_init.uses_final_pass_as_feedback();
+        self.passes = passes;
+
+        Ok(())
+    }
+
     /// Records shader rendering commands to the provided command encoder.
     ///
     /// SAFETY: The `MTLCommandBuffer` provided must not have an active encoder.
@@ -380,7 +524,14 @@ impl FilterChainMetal {
         frame_count: usize,
         options: Option<&FrameOptionsMetal>,
     ) -> error::Result<()> {
+        if let Some(compiled) = self.pending_passes.take() {
+            self.swap_passes(compiled, cmd)?;
+        }
+
+        self.common.config.set_automation_frame(frame_count);
+
         let max = std::cmp::min(self.passes.len(), self.common.config.passes_enabled());
+        let range = options.unwrap_or(&self.default_options).pass_range(max);
         if let Some(options) = &options {
             let clear_desc = unsafe { MTLRenderPassDescriptor::new() };
             if options.clear_history {
@@ -404,7 +555,7 @@ impl FilterChainMetal {
 
         self.push_history(&cmd, &input)?;
 
-        let passes = &mut self.passes[0..max];
+        let passes = &mut self.passes[0..range.end];
         if passes.is_empty() {
             return Ok(());
         }
@@ -465,11 +616,31 @@ impl FilterChainMetal {
             }),
         )?;
 
+        let timestamps = self.profiler.begin_frame(
+            frame_count,
+            passes
+                .iter()
+                .map(|pass| pass.meta.alias.as_ref().map(|alias| alias.as_str())),
+            |count| MetalTimestamps::new(&self.common.device, count),
+        )?;
+
+        if let Some(timestamps) = &timestamps {
+            timestamps.write(&cmd, 0)?;
+        }
+
         let passes_len = passes.len();
         let (pass, last) = passes.split_at_mut(passes_len - 1);
         let options = options.unwrap_or(&self.default_options);
 
         for (index, pass) in pass.iter_mut().enumerate() {
+            // skip passes before the range, so that the first pass reads the original image.
+            if index < range.start {
+                if let Some(timestamps) = &timestamps {
+                    timestamps.write(&cmd, index + 1)?;
+                }
+                continue;
+            }
+
             let target = &self.output_framebuffers[index];
             source.filter_mode = pass.meta.filter;
             source.wrap_mode = pass.meta.wrap_mode;
@@ -493,6 +664,10 @@ impl FilterChainMetal {
                 target.generate_mipmaps(&cmd)?;
             }
 
+            if let Some(timestamps) = &timestamps {
+                timestamps.write(&cmd, index + 1)?;
+            }
+
             self.common.output_textures[index] =
                 Some(target.as_input(pass.meta.filter, pass.meta.wrap_mode)?);
             source = self.common.output_textures[index]
@@ -519,12 +694,81 @@ impl FilterChainMetal {
             source.mip_filter = pass.meta.filter;
             let index = passes_len - 1;
 
-            if self.draw_last_pass_feedback {
-                let output_image = &self.output_framebuffers[index].texture;
-                let out = RenderTarget::viewport_with_output(output_image.as_ref(), viewport);
+            if options.compare_enabled() {
+                // draw the last pass at the size of the viewport, to compare with the original image.
+                let target = &mut self.output_framebuffers[index];
+                compare::scale_compare_source(target, pass, viewport.size, &self.common.device)?;
+
+                let out = RenderTarget::identity(target.texture.as_ref())?;
+                pass.draw(
+                    &cmd,
+                    index,
+                    &self.common,
+                    pass.meta.get_frame_count(frame_count),
+                    options,
+                    viewport,
+                    &original,
+                    &source,
+                    &out,
+                    QuadType::Offscreen,
+                )?;
+
+                let compare_pass = &mut self.compare_pass;
+                compare::set_compare_uniforms(
+                    &mut compare_pass.source.parameters,
+                    options.compare_split,
+                    options.compare_line_width,
+                );
+                if !compare_pass
+                    .graphics_pipeline
+                    .has_format(viewport.output.pixelFormat())
+                {
+                    compare_pass
+                        .graphics_pipeline
+                        .recompile(&self.common.device, viewport.output.pixelFormat())?;
+                }
+
+                self.common.output_textures[index] =
+                    Some(target.as_input(pass.meta.filter, pass.meta.wrap_mode)?);
+                let mut source =
+                    target.as_input(compare_pass.meta.filter, compare_pass.meta.wrap_mode)?;
+                source.mip_filter = compare_pass.meta.filter;
+
+                let out = RenderTarget::viewport(viewport);
+                compare_pass.draw(
+                    &cmd,
+                    passes_len,
+                    &self.common,
+                    compare_pass.meta.get_frame_count(frame_count),
+                    options,
+                    viewport,
+                    &original,
+                    &source,
+                    &out,
+                    QuadType::Final,
+                )?;
+            } else {
+                if self.draw_last_pass_feedback {
+                    let output_image = &self.output_framebuffers[index].texture;
+                    let out = RenderTarget::viewport_with_output(output_image.as_ref(), viewport);
+                    pass.draw(
+                        &cmd,
+                        passes_len - 1,
+                        &self.common,
+                        pass.meta.get_frame_count(frame_count),
+                        options,
+                        viewport,
+                        &original,
+                        &source,
+                        &out,
+                        QuadType::Final,
+                    )?;
+                }
+
+                let out = RenderTarget::viewport(viewport);
                 pass.draw(
                     &cmd,
-                    passes_len - 1,
+                    index,
                     &self.common,
                     pass.meta.get_frame_count(frame_count),
                     options,
@@ -536,21 +780,64 @@ impl FilterChainMetal {
                 )?;
             }
 
-            let out = RenderTarget::viewport(viewport);
-            pass.draw(
-                &cmd,
-                index,
-                &self.common,
-                pass.meta.get_frame_count(frame_count),
-                options,
-                viewport,
-                &original,
-                &source,
-                &out,
-                QuadType::Final,
-            )?;
+            if let Some(timestamps) = &timestamps {
+                timestamps.write(&cmd, passes_len)?;
+            }
         }
 
         Ok(())
     }
 }
+
+use librashader_runtime::impl_filter_chain_debug;
+impl_filter_chain_debug!(FilterChainMetal, MetalTexture, |texture| {
+    texture.texture.clone()
+});
+
+use librashader_runtime::impl_filter_chain_profiling;
+impl_filter_chain_profiling!(FilterChainMetal);
+
+impl librashader_runtime::filter_chain::FilterChain for FilterChainMetal {
+    type Device<'a> = &'a ProtocolObject<dyn MTLCommandQueue>;
+    type InputImage<'a> = &'a ProtocolObject<dyn MTLTexture>;
+    type OutputView<'a> = MetalTextureRef<'a>;
+    type CommandContext<'a> = &'a ProtocolObject<dyn MTLCommandBuffer>;
+    type FilterChainOptions = FilterChainOptionsMetal;
+    type FrameOptions = FrameOptionsMetal;
+    type Error = FilterChainError;
+
+    unsafe fn load_from_pack(
+        preset: ShaderPresetPack,
+        device: Self::Device<'_>,
+        options: Option<&Self::FilterChainOptions>,
+    ) -> error::Result<Self> {
+        FilterChainMetal::load_from_pack(preset, device, options)
+    }
+
+    unsafe fn load_from_preset(
+        preset: ShaderPreset,
+        device: Self::Device<'_>,
+        options: Option<&Self::FilterChainOptions>,
+    ) -> error::Result<Self> {
+        FilterChainMetal::load_from_preset(preset, device, options)
+    }
+
+    unsafe fn load_from_path(
+        path: impl AsRef<Path>,
+        device: Self::Device<'_>,
+        options: Option<&Self::FilterChainOptions>,
+    ) -> error::Result<Self> {
+        FilterChainMetal::load_from_path(path, device, options)
+    }
+
+    unsafe fn frame<'a>(
+        &mut self,
+        input: Self::InputImage<'a>,
+        viewport: &Viewport<Self::OutputView<'a>>,
+        cmd: Self::CommandContext<'a>,
+        frame_count: usize,
+        options: Option<&Self::FrameOptions>,
+    ) -> error::Result<()> {
+        FilterChainMetal::frame(self, input, viewport, cmd, frame_count, options)
+    }
+}
@@ -172,6 +172,7 @@ impl MetalGraphicsPipeline {
         device: &ProtocolObject<dyn MTLDevice>,
         format: MTLPixelFormat,
     ) -> Result<()> {
+        log::debug!("recompiling pipeline for output format {format:?}");
         let render_pipeline = self.layout.create_pipeline(device, format)?;
         self.render_pipelines.insert(format, render_pipeline);
         Ok(())
@@ -12,6 +12,7 @@ mod filter_chain;
 mod filter_pass;
 mod graphics_pipeline;
 mod luts;
+mod profiling;
 mod samplers;
 mod texture;
 
@@ -1,5 +1,6 @@
 //! Metal shader runtime options.
 
+use librashader_runtime::hdr::HdrOutputEncoding;
 use librashader_runtime::impl_default_frame_options;
 impl_default_frame_options!(FrameOptionsMetal);
 
@@ -9,4 +10,8 @@ impl_default_frame_options!(FrameOptionsMetal);
 pub struct FilterChainOptionsMetal {
     /// Whether or not to explicitly disable mipmap generation regardless of shader preset settings.
     pub force_no_mipmaps: bool,
+    /// Append the built-in HDR output pass, which encodes the output of the shader preset for
+    /// an HDR output. The filter chain should then be drawn to an output with the format given
+    /// by [`HdrOutputEncoding::output_format`].
+    pub hdr_output: Option<HdrOutputEncoding>,
 }
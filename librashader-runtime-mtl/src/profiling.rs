@@ -0,0 +1,105 @@
+use crate::error::{FilterChainError, Result};
+use librashader_runtime::profiling::{timestamp_durations, PassTimer};
+use objc2::rc::Retained;
+use objc2::runtime::ProtocolObject;
+use objc2::Message;
+use objc2_foundation::{NSRange, NSString};
+use objc2_metal::{
+    MTLBlitPassDescriptor, MTLCommandBuffer, MTLCommandEncoder, MTLCommonCounterSetTimestamp,
+    MTLCounterDontSample, MTLCounterErrorValue, MTLCounterResultTimestamp, MTLCounterSampleBuffer,
+    MTLCounterSampleBufferDescriptor, MTLCounterSamplingPoint, MTLCounterSet, MTLDevice,
+    MTLStorageMode,
+};
+use std::time::Duration;
+
+/// Timestamps sampled before the first pass and after each pass of a frame.
+///
+/// Apple GPUs can only sample counters at the boundaries of a pass, so each timestamp
+/// is sampled at the end of an empty blit pass.
+pub(crate) struct MetalTimestamps {
+    samples: Retained<ProtocolObject<dyn MTLCounterSampleBuffer>>,
+    capacity: usize,
+}
+
+impl MetalTimestamps {
+    pub fn new(
+        device: &ProtocolObject<dyn MTLDevice>,
+        passes: usize,
+    ) -> Result<Option<MetalTimestamps>> {
+        if !device.supportsCounterSampling(MTLCounterSamplingPoint::AtStageBoundary) {
+            return Ok(None);
+        }
+
+        unsafe {
+            let Some(counter_set) = device.counterSets().and_then(|sets| {
+                sets.iter()
+                    .find(|set| &*set.name() == MTLCommonCounterSetTimestamp)
+                    .map(|set| set.retain())
+            }) else {
+                return Ok(None);
+            };
+
+            let descriptor = MTLCounterSampleBufferDescriptor::new();
+            descriptor.setCounterSet(Some(&counter_set));
+            descriptor.setStorageMode(MTLStorageMode::Shared);
+            descriptor.setSampleCount(passes + 1);
+            descriptor.setLabel(&NSString::from_str("librashader timestamps"));
+
+            let samples = device.newCounterSampleBufferWithDescriptor_error(&descriptor)?;
+
+            Ok(Some(MetalTimestamps {
+                samples,
+                capacity: passes,
+            }))
+        }
+    }
+
+    /// Sample the timestamp at the given index once all previous passes have completed.
+    pub fn write(&self, cmd: &ProtocolObject<dyn MTLCommandBuffer>, index: usize) -> Result<()> {
+        unsafe {
+            let descriptor = MTLBlitPassDescriptor::blitPassDescriptor();
+            let attachment = descriptor
+                .sampleBufferAttachments()
+                .objectAtIndexedSubscript(0);
+            attachment.setSampleBuffer(Some(&self.samples));
+            attachment.setStartOfEncoderSampleIndex(MTLCounterDontSample);
+            attachment.setEndOfEncoderSampleIndex(index);
+
+            let encoder = cmd
+                .blitCommandEncoderWithDescriptor(&descriptor)
+                .ok_or(FilterChainError::FailedToCreateCommandBuffer)?;
+            encoder.endEncoding();
+        }
+        Ok(())
+    }
+}
+
+impl PassTimer for MetalTimestamps {
+    fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn read(&mut self, passes: usize) -> Option<Vec<Duration>> {
+        let data = unsafe {
+            self.samples
+                .resolveCounterRange(NSRange::new(0, passes + 1))?
+        };
+
+        let timestamps: Vec<u64> = data
+            .bytes()
+            .chunks_exact(std::mem::size_of::<MTLCounterResultTimestamp>())
+            .map(|sample| u64::from_ne_bytes(sample.try_into().unwrap()))
+            .collect();
+
+        if timestamps.len() != passes + 1
+            || timestamps
+                .iter()
+                .any(|timestamp| *timestamp == 0 || *timestamp == MTLCounterErrorValue)
+        {
+            return None;
+        }
+
+        // Counter timestamps on Apple GPUs are in nanoseconds.
+        Some(timestamp_durations(&timestamps, 1.0))
+    }
+}
@@ -6,6 +6,7 @@ use crate::graphics_pipeline::VulkanGraphicsPipeline;
 use crate::luts::LutTexture;
 use crate::memory::RawVulkanBuffer;
 use crate::options::{FilterChainOptionsVulkan, FrameOptionsVulkan};
+use crate::profiling::{get_timestamp_period, VulkanTimestamps};
 use crate::queue_selection::get_graphics_queue;
 use crate::samplers::SamplerSet;
 use crate::texture::{InputImage, OwnedImage, OwnedImageLayout, VulkanImage};
@@ -23,12 +24,17 @@ use librashader_reflect::back::targets::SPIRV;
 use librashader_reflect::back::{CompileReflectShader, CompileShader};
 use librashader_reflect::front::SpirvCompilation;
 use librashader_reflect::reflect::cross::SpirvCross;
-use librashader_reflect::reflect::presets::{CompilePresetTarget, ShaderPassArtifact};
+use librashader_reflect::reflect::presets::{
+    CompilePresetTarget, PassObserver, ShaderPassArtifact,
+};
 use librashader_reflect::reflect::semantics::ShaderSemantics;
 use librashader_reflect::reflect::ReflectShader;
 use librashader_runtime::binding::BindingUtil;
+use librashader_runtime::compare;
 use librashader_runtime::framebuffer::FramebufferInit;
+use librashader_runtime::hdr::append_hdr_output_pass;
 use librashader_runtime::image::{ImageError, LoadedTexture, UVDirection, BGRA8};
+use librashader_runtime::profiling::FrameProfiler;
 use librashader_runtime::quad::QuadType;
 use librashader_runtime::render_target::RenderTarget;
 use librashader_runtime::scaling::ScaleFramebuffer;
@@ -48,6 +54,10 @@ pub struct VulkanObjects {
     pub alloc: Arc<Mutex<Allocator>>,
     /// The graphics queue to do work on.
     pub queue: vk::Queue,
+    /// The number of nanoseconds per timestamp tick on the graphics queue, from `VkPhysicalDeviceLimits`.
+    ///
+    /// If this is 0, timestamp queries are unsupported and the filter chain can not be profiled.
+    pub timestamp_period: f32,
 }
 
 /// A collection of handles needed to access the Vulkan instance.
@@ -98,12 +108,14 @@ impl TryFrom<VulkanInstance> for VulkanObjects {
                 vulkan.physical_device,
             ));
 
+            let timestamp_period = get_timestamp_period(&instance, vulkan.physical_device);
             let alloc = memory::create_allocator(device.clone(), instance, vulkan.physical_device)?;
 
             Ok(VulkanObjects {
                 device: Arc::new(device),
                 alloc,
                 queue,
+                timestamp_period,
             })
         }
     }
@@ -121,12 +133,14 @@ impl TryFrom<(vk::PhysicalDevice, ash::Instance, ash::Device)> for VulkanObjects
 
         let queue = get_graphics_queue(&value.1, &device, value.0);
 
+        let timestamp_period = get_timestamp_period(&value.1, value.0);
         let alloc = memory::create_allocator(device.clone(), value.1, value.0)?;
 
         Ok(VulkanObjects {
             alloc,
             device: Arc::new(device),
             queue,
+            timestamp_period,
         })
     }
 }
@@ -148,12 +162,14 @@ impl TryFrom<(vk::PhysicalDevice, ash::Instance, ash::Device, vk::Queue)> for Vu
             value.3
         };
 
+        let timestamp_period = get_timestamp_period(&value.1, value.0);
         let alloc = memory::create_allocator(device.clone(), value.1, value.0)?;
 
         Ok(VulkanObjects {
             alloc,
             device: Arc::new(device),
             queue,
+            timestamp_period,
         })
     }
 }
@@ -162,6 +178,7 @@ impl TryFrom<(vk::PhysicalDevice, ash::Instance, ash::Device, vk::Queue)> for Vu
 pub struct FilterChainVulkan {
     pub(crate) common: FilterCommon,
     passes: Box<[FilterPass]>,
+    compare_pass: FilterPass,
     vulkan: VulkanObjects,
     output_framebuffers: Box<[OwnedImage]>,
     feedback_framebuffers: Box<[OwnedImage]>,
@@ -170,8 +187,17 @@ pub struct FilterChainVulkan {
     residuals: Box<[FrameResiduals]>,
     default_options: FrameOptionsVulkan,
     draw_last_pass_feedback: bool,
+    options: FilterChainOptionsVulkan,
+    lut_identities: Box<[LutIdentity]>,
+    pending_passes: Option<CompiledPasses<Box<[FilterPass]>>>,
+    profiler: FrameProfiler<VulkanTimestamps>,
 }
 
+/// Shader passes of a preset compiled for a Vulkan filter chain.
+///
+/// Compiled passes are swapped into a filter chain with [`FilterChainVulkan::replace_passes`].
+pub struct CompiledPassesVulkan(CompiledPasses<Box<[FilterPass]>>);
+
 pub(crate) struct FilterCommon {
     pub(crate) luts: FastHashMap<usize, LutTexture>,
     pub samplers: SamplerSet,
@@ -195,6 +221,8 @@ struct FrameResiduals {
     image_views: Vec<vk::ImageView>,
     owned: Vec<OwnedImage>,
     framebuffers: Vec<Option<vk::Framebuffer>>,
+    passes: Vec<FilterPass>,
+    luts: Vec<LutTexture>,
 }
 
 impl FrameResiduals {
@@ -204,6 +232,8 @@ impl FrameResiduals {
             image_views: Vec::new(),
             owned: Vec::new(),
             framebuffers: Vec::new(),
+            passes: Vec::new(),
+            luts: Vec::new(),
         }
     }
 
@@ -219,6 +249,14 @@ impl FrameResiduals {
         self.framebuffers.push(fb)
     }
 
+    pub(crate) fn dispose_passes(&mut self, passes: Box<[FilterPass]>) {
+        self.passes.extend(passes)
+    }
+
+    pub(crate) fn dispose_lut(&mut self, lut: LutTexture) {
+        self.luts.push(lut)
+    }
+
     /// Dispose of the intermediate objects created during a frame.
     pub fn dispose(&mut self) {
         for image_view in self.image_views.drain(0..) {
@@ -237,7 +275,9 @@ impl FrameResiduals {
                 }
             }
         }
-        self.owned.clear()
+        self.owned.clear();
+        self.passes.clear();
+        self.luts.clear();
     }
 }
 
@@ -265,18 +305,21 @@ mod compile {
         shaders: Vec<PassResource>,
         textures: &[TextureResource],
         disable_cache: bool,
+        observer: Option<PassObserver<'_>>,
     ) -> Result<(Vec<ShaderPassMeta>, ShaderSemantics), FilterChainError> {
+        let observer = observer.unwrap_or(&|_, _| true);
         let (passes, semantics) = if !disable_cache {
-            SPIRV::compile_preset_passes::<
+            SPIRV::compile_preset_passes_with_observer::<
                 CachedCompilation<SpirvCompilation>,
                 SpirvCross,
                 FilterChainError,
-            >(shaders, textures.iter().map(|t| &t.meta))?
+            >(shaders, textures.iter().map(|t| &t.meta), observer)?
         } else {
-            SPIRV::compile_preset_passes::<SpirvCompilation, SpirvCross, FilterChainError>(
-                shaders,
-                textures.iter().map(|t| &t.meta),
-            )?
+            SPIRV::compile_preset_passes_with_observer::<
+                SpirvCompilation,
+                SpirvCross,
+                FilterChainError,
+            >(shaders, textures.iter().map(|t| &t.meta), observer)?
         };
 
         Ok((passes, semantics))
@@ -286,6 +329,7 @@ mod compile {
 use compile::{compile_passes, ShaderPassMeta};
 use librashader_pack::{ShaderPresetPack, TextureResource};
 use librashader_runtime::parameters::RuntimeParameters;
+use librashader_runtime::reload::{CompiledPasses, LutIdentity, LutReuse};
 
 impl FilterChainVulkan {
     /// Load the shader preset at the given path into a filter chain.
@@ -414,8 +458,43 @@ impl FilterChainVulkan {
         V: TryInto<VulkanObjects, Error = E>,
         FilterChainError: From<E>,
     {
+        unsafe {
+            Self::load_from_pack_deferred_with_observer(preset, vulkan, cmd, options, &|_, _| true)
+        }
+    }
+
+    /// Load a filter chain from a pre-parsed, loaded `ShaderPresetPack`, deferring and GPU-side initialization
+    /// to the caller. This function therefore requires no external synchronization of the device queue.
+    ///
+    /// `observer` is called after each pass is compiled with the index of the pass and the total
+    /// number of passes. If it returns `false`, loading stops with
+    /// [`ShaderCompileError::Cancelled`](librashader_reflect::error::ShaderCompileError::Cancelled).
+    ///
+    /// ## Safety
+    /// The provided command buffer must be ready for recording and contain no prior commands.
+    /// The caller is responsible for ending the command buffer and immediately submitting it to a
+    /// graphics queue. The command buffer must be completely executed before calling [`frame`](Self::frame).
+    pub unsafe fn load_from_pack_deferred_with_observer<V, E>(
+        mut preset: ShaderPresetPack,
+        vulkan: V,
+        cmd: vk::CommandBuffer,
+        options: Option<&FilterChainOptionsVulkan>,
+        observer: PassObserver<'_>,
+    ) -> error::Result<FilterChainVulkan>
+    where
+        V: TryInto<VulkanObjects, Error = E>,
+        FilterChainError: From<E>,
+    {
+        if let Some(encoding) = options.and_then(|o| o.hdr_output) {
+            append_hdr_output_pass(&mut preset, encoding)?;
+        }
         let disable_cache = options.map_or(false, |o| o.disable_cache);
-        let (passes, semantics) = compile_passes(preset.passes, &preset.textures, disable_cache)?;
+        let (passes, semantics) = compile_passes(
+            preset.passes,
+            &preset.textures,
+            disable_cache,
+            Some(observer),
+        )?;
 
         let device = vulkan.try_into().map_err(From::from)?;
 
@@ -434,6 +513,19 @@ impl FilterChainVulkan {
             disable_cache,
         )?;
 
+        let compare_pass = compare::compile_compare_pass(|passes| {
+            let (passes, semantics) = compile_passes(passes, &[], disable_cache, None)?;
+            Self::init_passes(
+                &device,
+                passes,
+                &semantics,
+                frames_in_flight,
+                options.is_some_and(|o| o.use_dynamic_rendering),
+                disable_cache,
+            )
+        })?;
+
+        let lut_identities = LutIdentity::from_textures(&preset.textures);
         let luts = FilterChainVulkan::load_luts(&device, cmd, preset.textures)?;
         let samplers = SamplerSet::new(&device.device)?;
 
@@ -466,7 +558,11 @@ impl FilterChainVulkan {
             common: FilterCommon {
                 luts,
                 samplers,
-                config: RuntimeParameters::new(preset.pass_count as usize, preset.parameters),
+                config: RuntimeParameters::new(
+                    preset.pass_count as usize,
+                    preset.parameters,
+                    filters.iter().map(|f| &f.source),
+                ),
                 draw_quad: DrawQuad::new(&device.device, &device.alloc)?,
                 device: device.device.clone(),
                 output_textures,
@@ -475,6 +571,7 @@ impl FilterChainVulkan {
                 internal_frame_count: 0,
             },
             passes: filters,
+            compare_pass,
             vulkan: device,
             output_framebuffers,
             feedback_framebuffers,
@@ -482,9 +579,151 @@ impl FilterChainVulkan {
             residuals: intermediates.into_boxed_slice(),
             disable_mipmaps: options.map_or(false, |o| o.force_no_mipmaps),
             default_options: Default::default(),
+            options: FilterChainOptionsVulkan {
+                frames_in_flight,
+                ..options.cloned().unwrap_or_default()
+            },
+            lut_identities,
+            pending_passes: None,
+            profiler: FrameProfiler::new(frames_in_flight as usize),
         })
     }
 
+    /// Compile the passes of a shader preset, to be swapped into a filter chain created
+    /// with the same device with [`replace_passes`](Self::replace_passes).
+    ///
+    /// Compiling passes does not require access to the filter chain, and can be done on any thread.
+    /// The options must match the options the filter chain was created with.
+    pub fn compile_passes<V, E>(
+        preset: ShaderPresetPack,
+        vulkan: V,
+        options: Option<&FilterChainOptionsVulkan>,
+    ) -> error::Result<CompiledPassesVulkan>
+    where
+        V: TryInto<VulkanObjects, Error = E>,
+        FilterChainError: From<E>,
+    {
+        let vulkan = vulkan.try_into()?;
+        Self::compile_passes_for(preset, &vulkan, options).map(CompiledPassesVulkan)
+    }
+
+    fn compile_passes_for(
+        mut preset: ShaderPresetPack,
+        vulkan: &VulkanObjects,
+        options: Option<&FilterChainOptionsVulkan>,
+    ) -> error::Result<CompiledPasses<Box<[FilterPass]>>> {
+        if let Some(encoding) = options.and_then(|o| o.hdr_output) {
+            append_hdr_output_pass(&mut preset, encoding)?;
+        }
+        let disable_cache = options.is_some_and(|o| o.disable_cache);
+
+        let mut frames_in_flight = options.map_or(0, |o| o.frames_in_flight);
+        if frames_in_flight == 0 {
+            frames_in_flight = 3;
+        }
+
+        CompiledPasses::new(preset, |passes, textures| {
+            let (passes, semantics) = compile_passes(passes, textures, disable_cache, None)?;
+            Self::init_passes(
+                vulkan,
+                passes,
+                &semantics,
+                frames_in_flight,
+                options.is_some_and(|o| o.use_dynamic_rendering),
+                disable_cache,
+            )
+        })
+    }
+
+    /// Replace the passes of the filter chain with compiled passes.
+    ///
+    /// The passes are swapped in at the next call to [`frame`](Self::frame). History and feedback
+    /// framebuffers are kept, and lookup textures that are unchanged from the current preset are reused.
+    /// Runtime parameters are reset to the values of the new preset once the passes are swapped in.
+    ///
+    /// Resources of the previous passes are released once the frames in flight have completed.
+    pub fn replace_passes(&mut self, passes: CompiledPassesVulkan) {
+        self.pending_passes = Some(passes.0);
+    }
+
+    /// Reload the filter chain with a new shader preset.
+    ///
+    /// The passes of the preset are compiled immediately, then swapped in at the next call to
+    /// [`frame`](Self::frame) as with [`replace_passes`](Self::replace_passes).
+    pub fn reload(&mut self, preset: ShaderPresetPack) -> error::Result<()> {
+        self.pending_passes = Some(Self::compile_passes_for(
+            preset,
+            &self.vulkan,
+            Some(&self.options),
+        )?);
+        Ok(())
+    }
+
+    fn swap_passes(
+        &mut self,
+        compiled: CompiledPasses<Box<[FilterPass]>>,
+        cmd: vk::CommandBuffer,
+    ) -> error::Result<()> {
+        let CompiledPasses {
+            passes,
+            textures,
+            lut_identities,
+            parameters,
+            pass_count,
+        } = compiled;
+
+        let residuals =
+            &mut self.residuals[self.common.internal_frame_count % self.residuals.len()];
+
+        let mut luts = LutReuse::new(
+            std::mem::take(&mut self.common.luts),
+            &self.lut_identities,
+            &lut_identities,
+            textures,
+            |lut| residuals.dispose_lut(lut),
+        );
+        let loaded = FilterChainVulkan::load_luts(&self.vulkan, cmd, luts.take_textures())?;
+        self.common.luts = luts.finish(loaded);
+        self.lut_identities = lut_identities;
+
+        let vulkan = &self.vulkan;
+        let framebuffer_gen =
+            || OwnedImage::new(vulkan, Size::new(1, 1), ImageFormat::R8G8B8A8Unorm, 1);
+        let input_gen = || None;
+        let framebuffer_init = FramebufferInit::new(
+            passes.iter().map(|f| &f.reflection.meta),
+            &framebuffer_gen,
+            &input_gen,
+        );
+
+        let (output_framebuffers, output_textures) = framebuffer_init
+            .reuse_output_framebuffers(std::mem::take(&mut self.output_framebuffers), |image| {
+                residuals.dispose_owned(image)
+            })?;
+        let (feedback_framebuffers, feedback_textures) = framebuffer_init
+            .reuse_output_framebuffers(
+                std::mem::take(&mut self.feedback_framebuffers),
+                |image| residuals.dispose_owned(image),
+            )?;
+        let (history_framebuffers, history_textures) = framebuffer_init
+            .reuse_history(std::mem::take(&mut self.history_framebuffers), |image| {
+                residuals.dispose_owned(image)
+            })?;
+
+        self.output_framebuffers = output_framebuffers;
+        self.feedback_framebuffers = feedback_framebuffers;
+        self.history_framebuffers = history_framebuffers;
+        self.common.output_textures = output_textures;
+        self.common.feedback_textures = feedback_textures;
+        self.common.history_textures = history_textures;
+        self.common.config =
+            RuntimeParameters::new(pass_count, parameters, passes.iter().map(|f| &f.source));
+        self.draw_last_pass_feedback = framebuffer_init.uses_final_pass_as_feedback();
+        residuals.dispose_passes(std::mem::replace(&mut self.passes, passes));
+
+        Ok(())
+    }
+
     fn init_passes(
         vulkan: &VulkanObjects,
         passes: Vec<ShaderPassMeta>,
@@ -500,7 +739,9 @@ impl FilterChainVulkan {
             .enumerate()
             .map(|(index, (config, mut reflect))| {
                 let reflection = reflect.reflect(index, semantics)?;
-                let spirv_words = reflect.compile(None)?;
+                let spirv_words = reflect
+                    .compile(None)
+                    .map_err(|error| error.in_pass(index))?;
 
                 let ubo_size = reflection.ubo.as_ref().map_or(0, |ubo| ubo.size as usize);
                 let uniform_storage = UniformStorage::new_with_ubo_storage(
@@ -644,13 +885,21 @@ impl FilterChainVulkan {
         frame_count: usize,
         options: Option<&FrameOptionsVulkan>,
     ) -> error::Result<()> {
-        let intermediates =
-            &mut self.residuals[self.common.internal_frame_count % self.residuals.len()];
-        intermediates.dispose();
+        let residual_index = self.common.internal_frame_count % self.residuals.len();
+        self.residuals[residual_index].dispose();
+
+        if let Some(compiled) = self.pending_passes.take() {
+            self.swap_passes(compiled, cmd)?;
+        }
+
+        let intermediates = &mut self.residuals[residual_index];
+
+        self.common.config.set_automation_frame(frame_count);
 
         // limit number of passes to those enabled.
         let max = std::cmp::min(self.passes.len(), self.common.config.passes_enabled());
-        let passes = &mut self.passes[0..max];
+        let range = options.unwrap_or(&self.default_options).pass_range(max);
+        let passes = &mut self.passes[0..range.end];
 
         if let Some(options) = &options {
             if options.clear_history {
@@ -743,6 +992,19 @@ impl FilterChainVulkan {
             }),
         )?;
 
+        let timestamps = self.profiler.begin_frame(
+            frame_count,
+            passes
+                .iter()
+                .map(|pass| pass.meta.alias.as_ref().map(|alias| alias.as_str())),
+            |count| VulkanTimestamps::new(&self.vulkan.device, count, self.vulkan.timestamp_period),
+        )?;
+
+        if let Some(timestamps) = &timestamps {
+            timestamps.reset(cmd);
+            timestamps.write(cmd, 0);
+        }
+
         let passes_len = passes.len();
         let (pass, last) = passes.split_at_mut(passes_len - 1);
 
@@ -752,6 +1014,14 @@ impl FilterChainVulkan {
             .draw_quad
             .bind_vbo_for_frame(&self.vulkan.device, cmd);
         for (index, pass) in pass.iter_mut().enumerate() {
+            // skip passes before the range, so that the first pass reads the original image.
+            if index < range.start {
+                if let Some(timestamps) = &timestamps {
+                    timestamps.write(cmd, index + 1);
+                }
+                continue;
+            }
+
             let target = &self.output_framebuffers[index];
             source.filter_mode = pass.meta.filter;
             source.wrap_mode = pass.meta.wrap_mode;
@@ -781,6 +1051,10 @@ impl FilterChainVulkan {
                 out.output.end_pass(&self.vulkan.device, cmd);
             }
 
+            if let Some(timestamps) = &timestamps {
+                timestamps.write(cmd, index + 1);
+            }
+
             source = self.common.output_textures[index].clone().unwrap();
             intermediates.dispose_outputs(output_image);
             intermediates.dispose_framebuffers(residual_fb);
@@ -804,11 +1078,26 @@ impl FilterChainVulkan {
             source.wrap_mode = pass.meta.wrap_mode;
             source.mip_filter = pass.meta.filter;
 
-            if self.draw_last_pass_feedback {
-                let target = &self.output_framebuffers[index];
+            if options.compare_enabled() {
+                // draw the last pass at the size of the viewport, to compare with the original image.
+                let target = &mut self.output_framebuffers[index];
+                compare::scale_compare_source(
+                    target,
+                    pass,
+                    viewport.size,
+                    &Some(OwnedImageLayout {
+                        dst_layout: vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
+                        dst_access: vk::AccessFlags::SHADER_READ,
+                        src_stage: vk::PipelineStageFlags::TOP_OF_PIPE,
+                        dst_stage: vk::PipelineStageFlags::FRAGMENT_SHADER,
+                        cmd,
+                    }),
+                )?;
+                self.common.output_textures[index] =
+                    Some(target.as_input(pass.meta.filter, pass.meta.wrap_mode));
 
                 let output_image = OutputImage::new(&self.vulkan.device, target.image.clone())?;
-                let out = RenderTarget::viewport_with_output(&output_image, viewport);
+                let out = RenderTarget::identity(&output_image)?;
 
                 let residual_fb = pass.draw(
                     cmd,
@@ -821,34 +1110,106 @@ impl FilterChainVulkan {
                     &original,
                     &source,
                     &out,
-                    QuadType::Final,
-                    true,
+                    QuadType::Offscreen,
+                    false,
                 )?;
                 out.output.end_pass(&self.vulkan.device, cmd);
                 intermediates.dispose_outputs(output_image);
                 intermediates.dispose_framebuffers(residual_fb);
-            }
 
-            let output_image = OutputImage::new(&self.vulkan.device, viewport.output.clone())?;
-            let out = RenderTarget::viewport_with_output(&output_image, viewport);
+                let compare_pass = &mut self.compare_pass;
+                compare::set_compare_uniforms(
+                    &mut compare_pass.source.parameters,
+                    options.compare_split,
+                    options.compare_line_width,
+                );
+                if compare_pass
+                    .graphics_pipeline
+                    .render_passes
+                    .get(&viewport.output.format)
+                    .is_none()
+                {
+                    compare_pass
+                        .graphics_pipeline
+                        .recompile(viewport.output.format)?;
+                }
 
-            let residual_fb = pass.draw(
-                cmd,
-                viewport.output.format,
-                index,
-                &self.common,
-                pass.meta.get_frame_count(frame_count),
-                options,
-                viewport,
-                &original,
-                &source,
-                &out,
-                QuadType::Final,
-                false,
-            )?;
+                let mut source = self.common.output_textures[index].clone().unwrap();
+                source.filter_mode = compare_pass.meta.filter;
+                source.wrap_mode = compare_pass.meta.wrap_mode;
+                source.mip_filter = compare_pass.meta.filter;
 
-            intermediates.dispose_outputs(output_image);
-            intermediates.dispose_framebuffers(residual_fb);
+                let output_image = OutputImage::new(&self.vulkan.device, viewport.output.clone())?;
+                let out = RenderTarget::viewport_with_output(&output_image, viewport);
+
+                let residual_fb = compare_pass.draw(
+                    cmd,
+                    viewport.output.format,
+                    passes_len,
+                    &self.common,
+                    compare_pass.meta.get_frame_count(frame_count),
+                    options,
+                    viewport,
+                    &original,
+                    &source,
+                    &out,
+                    QuadType::Final,
+                    false,
+                )?;
+
+                intermediates.dispose_outputs(output_image);
+                intermediates.dispose_framebuffers(residual_fb);
+            } else {
+                if self.draw_last_pass_feedback {
+                    let target = &self.output_framebuffers[index];
+
+                    let output_image = OutputImage::new(&self.vulkan.device, target.image.clone())?;
+                    let out = RenderTarget::viewport_with_output(&output_image, viewport);
+
+                    let residual_fb = pass.draw(
+                        cmd,
+                        target.image.format,
+                        index,
+                        &self.common,
+                        pass.meta.get_frame_count(frame_count),
+                        options,
+                        viewport,
+                        &original,
+                        &source,
+                        &out,
+                        QuadType::Final,
+                        true,
+                    )?;
+                    out.output.end_pass(&self.vulkan.device, cmd);
+                    intermediates.dispose_outputs(output_image);
+                    intermediates.dispose_framebuffers(residual_fb);
+                }
+
+                let output_image = OutputImage::new(&self.vulkan.device, viewport.output.clone())?;
+                let out = RenderTarget::viewport_with_output(&output_image, viewport);
+
+                let residual_fb = pass.draw(
+                    cmd,
+                    viewport.output.format,
+                    index,
+                    &self.common,
+                    pass.meta.get_frame_count(frame_count),
+                    options,
+                    viewport,
+                    &original,
+                    &source,
+                    &out,
+                    QuadType::Final,
+                    false,
+                )?;
+
+                intermediates.dispose_outputs(output_image);
+                intermediates.dispose_framebuffers(residual_fb);
+            }
+
+            if let Some(timestamps) = &timestamps {
+                timestamps.write(cmd, passes_len);
+            }
         }
 
         self.push_history(input, cmd)?;
@@ -856,3 +1217,56 @@ impl FilterChainVulkan {
         Ok(())
     }
 }
+
+use librashader_runtime::impl_filter_chain_debug;
+impl_filter_chain_debug!(FilterChainVulkan, VulkanImage, |texture| {
+    texture.image.clone()
+});
+
+use librashader_runtime::impl_filter_chain_profiling;
+impl_filter_chain_profiling!(FilterChainVulkan);
+
+impl librashader_runtime::filter_chain::FilterChain for FilterChainVulkan {
+    type Device<'a> = VulkanObjects;
+    type InputImage<'a> = &'a VulkanImage;
+    type OutputView<'a> = VulkanImage;
+    type CommandContext<'a> = vk::CommandBuffer;
+    type FilterChainOptions = FilterChainOptionsVulkan;
+    type FrameOptions = FrameOptionsVulkan;
+    type Error = FilterChainError;
+
+    unsafe fn load_from_pack(
+        preset: ShaderPresetPack,
+        device: Self::Device<'_>,
+        options: Option<&Self::FilterChainOptions>,
+    ) -> error::Result<Self> {
+        unsafe { FilterChainVulkan::load_from_pack(preset, device, options) }
+    }
+
+    unsafe fn load_from_preset(
+        preset: ShaderPreset,
+        device: Self::Device<'_>,
+        options: Option<&Self::FilterChainOptions>,
+    ) -> error::Result<Self> {
+        unsafe { FilterChainVulkan::load_from_preset(preset, device, options) }
+    }
+
+    unsafe fn load_from_path(
+        path: impl AsRef<Path>,
+        device: Self::Device<'_>,
+        options: Option<&Self::FilterChainOptions>,
+    ) -> error::Result<Self> {
+        unsafe { FilterChainVulkan::load_from_path(path, device, options) }
+    }
+
+    unsafe fn frame<'a>(
+        &mut self,
+        input: Self::InputImage<'a>,
+        viewport: &Viewport<Self::OutputView<'a>>,
+        cmd: Self::CommandContext<'a>,
+        frame_count: usize,
+        options: Option<&Self::FrameOptions>,
+    ) -> error::Result<()> {
+        unsafe { FilterChainVulkan::frame(self, input, viewport, cmd, frame_count, options) }
+    }
+}
@@ -386,6 +386,7 @@ impl VulkanGraphicsPipeline {
     }
 
     pub(crate) fn recompile(&mut self, format: vk::Format) -> error::Result<()> {
+        log::debug!("recompiling pipeline for output format {format:?}");
         let new_renderpass = if self.use_render_pass {
             Some(VulkanRenderPass::create_render_pass(&self.device, format)?)
         } else {
@@ -12,11 +12,13 @@ mod framebuffer;
 mod graphics_pipeline;
 mod luts;
 mod memory;
+mod profiling;
 mod queue_selection;
 mod samplers;
 mod texture;
 mod util;
 
+pub use filter_chain::CompiledPassesVulkan;
 pub use filter_chain::FilterChainVulkan;
 pub use filter_chain::VulkanInstance;
 pub use filter_chain::VulkanObjects;
@@ -0,0 +1,101 @@
+use crate::error;
+use ash::vk;
+use librashader_runtime::profiling::{timestamp_durations, PassTimer};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Get the number of nanoseconds per timestamp tick of the physical device,
+/// or 0 if timestamp queries are not supported on graphics queues.
+pub(crate) fn get_timestamp_period(
+    instance: &ash::Instance,
+    physical_device: vk::PhysicalDevice,
+) -> f32 {
+    let limits = unsafe { instance.get_physical_device_properties(physical_device) }.limits;
+    if limits.timestamp_compute_and_graphics == vk::FALSE {
+        return 0.0;
+    }
+    limits.timestamp_period
+}
+
+/// Timestamps written before the first pass and after each pass of a frame.
+pub(crate) struct VulkanTimestamps {
+    device: Arc<ash::Device>,
+    pool: vk::QueryPool,
+    capacity: usize,
+    period: f32,
+}
+
+impl VulkanTimestamps {
+    pub fn new(
+        device: &Arc<ash::Device>,
+        passes: usize,
+        period: f32,
+    ) -> error::Result<Option<VulkanTimestamps>> {
+        if period <= 0.0 {
+            return Ok(None);
+        }
+
+        let create_info = vk::QueryPoolCreateInfo::default()
+            .query_type(vk::QueryType::TIMESTAMP)
+            .query_count(passes as u32 + 1);
+
+        let pool = unsafe { device.create_query_pool(&create_info, None)? };
+
+        Ok(Some(VulkanTimestamps {
+            device: Arc::clone(device),
+            pool,
+            capacity: passes,
+            period,
+        }))
+    }
+
+    /// Reset the queries of the pool. Must be recorded outside of a render pass.
+    pub fn reset(&self, cmd: vk::CommandBuffer) {
+        unsafe {
+            self.device
+                .cmd_reset_query_pool(cmd, self.pool, 0, self.capacity as u32 + 1);
+        }
+    }
+
+    /// Write the timestamp at the given index once all previous commands have completed.
+    pub fn write(&self, cmd: vk::CommandBuffer, index: usize) {
+        unsafe {
+            self.device.cmd_write_timestamp(
+                cmd,
+                vk::PipelineStageFlags::BOTTOM_OF_PIPE,
+                self.pool,
+                index as u32,
+            );
+        }
+    }
+}
+
+impl PassTimer for VulkanTimestamps {
+    fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn read(&mut self, passes: usize) -> Option<Vec<Duration>> {
+        let mut timestamps = vec![0u64; passes + 1];
+        unsafe {
+            self.device
+                .get_query_pool_results(
+                    self.pool,
+                    0,
+                    &mut timestamps,
+                    vk::QueryResultFlags::TYPE_64,
+                )
+                .ok()?;
+        }
+
+        Some(timestamp_durations(&timestamps, self.period as f64))
+    }
+}
+
+impl Drop for VulkanTimestamps {
+    fn drop(&mut self) {
+        unsafe {
+            self.device.destroy_query_pool(self.pool, None);
+        }
+    }
+}
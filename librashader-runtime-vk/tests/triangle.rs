@@ -21,6 +21,7 @@ fn triangle_vk() {
                 force_no_mipmaps: false,
                 use_dynamic_rendering: false,
                 disable_cache: true,
+                hdr_output: None,
             }),
         )
         .unwrap();
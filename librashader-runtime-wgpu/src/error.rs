@@ -3,6 +3,7 @@ use librashader_preprocess::PreprocessError;
 use librashader_presets::ParsePresetError;
 use librashader_reflect::error::{ShaderCompileError, ShaderReflectError};
 use librashader_runtime::image::ImageError;
+use librashader_runtime::state::StateError;
 use thiserror::Error;
 
 /// Cumulative error type for wgpu filter chains.
@@ -19,6 +20,10 @@ pub enum FilterChainError {
     ShaderReflectError(#[from] ShaderReflectError),
     #[error("lut loading error")]
     LutLoadError(#[from] ImageError),
+    #[error("filter chain state error")]
+    StateError(#[from] StateError),
+    #[error("failed to read back framebuffer")]
+    ReadbackError,
     #[error("unreachable")]
     Infallible(#[from] std::convert::Infallible),
 }
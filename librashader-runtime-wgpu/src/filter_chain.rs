@@ -3,18 +3,25 @@ use librashader_presets::ShaderPreset;
 use librashader_reflect::back::targets::WGSL;
 use librashader_reflect::back::{CompileReflectShader, CompileShader};
 use librashader_reflect::front::SpirvCompilation;
-use librashader_reflect::reflect::presets::{CompilePresetTarget, ShaderPassArtifact};
+use librashader_reflect::reflect::presets::{
+    CompilePresetTarget, PassObserver, ShaderPassArtifact,
+};
 use librashader_reflect::reflect::semantics::ShaderSemantics;
 use librashader_reflect::reflect::ReflectShader;
 use librashader_runtime::binding::BindingUtil;
+use librashader_runtime::compare;
+use librashader_runtime::hdr::append_hdr_output_pass;
 use librashader_runtime::image::{ImageError, LoadedTexture, UVDirection};
+use librashader_runtime::profiling::FrameProfiler;
 use librashader_runtime::quad::QuadType;
+use librashader_runtime::state::FilterChainState;
 use librashader_runtime::uniforms::UniformStorage;
 #[cfg(not(target_arch = "wasm32"))]
 use rayon::prelude::*;
 use std::collections::VecDeque;
 use std::path::Path;
 
+#[cfg(not(target_arch = "wasm32"))]
 use rayon::ThreadPoolBuilder;
 use std::sync::Arc;
 
@@ -35,6 +42,7 @@ use crate::graphics_pipeline::WgpuGraphicsPipeline;
 use crate::luts::LutTexture;
 use crate::mipmap::MipmapGen;
 use crate::options::{FilterChainOptionsWgpu, FrameOptionsWgpu};
+use crate::profiling::WgpuTimestamps;
 use crate::samplers::SamplerSet;
 use crate::texture::{InputImage, OwnedImage};
 
@@ -53,12 +61,14 @@ mod compile {
     pub fn compile_passes(
         shaders: Vec<PassResource>,
         textures: &[TextureResource],
+        observer: Option<PassObserver<'_>>,
     ) -> Result<(Vec<ShaderPassMeta>, ShaderSemantics), FilterChainError> {
-        let (passes, semantics) = WGSL::compile_preset_passes::<
+        let observer = observer.unwrap_or(&|_, _| true);
+        let (passes, semantics) = WGSL::compile_preset_passes_with_observer::<
             SpirvCompilation,
             Naga,
             FilterChainError,
-        >(shaders, textures.iter().map(|t| &t.meta))?;
+        >(shaders, textures.iter().map(|t| &t.meta), observer)?;
         Ok((passes, semantics))
     }
 }
@@ -66,11 +76,13 @@ mod compile {
 use compile::{compile_passes, ShaderPassMeta};
 use librashader_pack::{ShaderPresetPack, TextureResource};
 use librashader_runtime::parameters::RuntimeParameters;
+use librashader_runtime::reload::{CompiledPasses, LutIdentity, LutReuse};
 
 /// A wgpu filter chain.
 pub struct FilterChainWgpu {
     pub(crate) common: FilterCommon,
     passes: Box<[FilterPass]>,
+    compare_pass: FilterPass,
     output_framebuffers: Box<[OwnedImage]>,
     feedback_framebuffers: Box<[OwnedImage]>,
     history_framebuffers: VecDeque<OwnedImage>,
@@ -78,8 +90,18 @@ pub struct FilterChainWgpu {
     mipmapper: MipmapGen,
     default_frame_options: FrameOptionsWgpu,
     draw_last_pass_feedback: bool,
+    options: FilterChainOptionsWgpu,
+    lut_identities: Box<[LutIdentity]>,
+    pending_passes: Option<CompiledPasses<Box<[FilterPass]>>>,
+    profiler: FrameProfiler<WgpuTimestamps>,
+    frame_count: usize,
 }
 
+/// Shader passes of a preset compiled for a wgpu filter chain.
+///
+/// Compiled passes are swapped into a filter chain with [`FilterChainWgpu::replace_passes`].
+pub struct CompiledPassesWgpu(CompiledPasses<Box<[FilterPass]>>);
+
 pub(crate) struct FilterCommon {
     pub output_textures: Box<[Option<InputImage>]>,
     pub feedback_textures: Box<[Option<InputImage>]>,
@@ -176,7 +198,34 @@ impl FilterChainWgpu {
         cmd: &mut wgpu::CommandEncoder,
         options: Option<&FilterChainOptionsWgpu>,
     ) -> error::Result<FilterChainWgpu> {
-        let (passes, semantics) = compile_passes(preset.passes, &preset.textures)?;
+        Self::load_from_pack_deferred_with_observer(preset, device, queue, cmd, options, &|_, _| {
+            true
+        })
+    }
+
+    /// Load a filter chain from a pre-parsed `ShaderPreset`, deferring and GPU-side initialization
+    /// to the caller. This function therefore requires no external synchronization of the device queue.
+    ///
+    /// `observer` is called after each pass is compiled with the index of the pass and the total
+    /// number of passes. If it returns `false`, loading stops with
+    /// [`ShaderCompileError::Cancelled`](librashader_reflect::error::ShaderCompileError::Cancelled).
+    ///
+    /// ## Safety
+    /// The provided command buffer must be ready for recording and contain no prior commands.
+    /// The caller is responsible for ending the command buffer and immediately submitting it to a
+    /// graphics queue. The command buffer must be completely executed before calling [`frame`](Self::frame).
+    pub fn load_from_pack_deferred_with_observer(
+        mut preset: ShaderPresetPack,
+        device: Arc<Device>,
+        queue: Arc<wgpu::Queue>,
+        cmd: &mut wgpu::CommandEncoder,
+        options: Option<&FilterChainOptionsWgpu>,
+        observer: PassObserver<'_>,
+    ) -> error::Result<FilterChainWgpu> {
+        if let Some(encoding) = options.and_then(|o| o.hdr_output) {
+            append_hdr_output_pass(&mut preset, encoding)?;
+        }
+        let (passes, semantics) = compile_passes(preset.passes, &preset.textures, Some(observer))?;
 
         // cache is opt-in for wgpu, not opt-out because of feature requirements.
         let disable_cache = options.map_or(true, |o| !o.enable_cache);
@@ -190,8 +239,20 @@ impl FilterChainWgpu {
             disable_cache,
         )?;
 
+        let compare_pass = compare::compile_compare_pass(|passes| {
+            let (passes, semantics) = compile_passes(passes, &[], None)?;
+            Self::init_passes(
+                Arc::clone(&device),
+                passes,
+                &semantics,
+                options.and_then(|o| o.adapter_info.as_ref()),
+                disable_cache,
+            )
+        })?;
+
         let samplers = SamplerSet::new(&device);
         let mut mipmapper = MipmapGen::new(Arc::clone(&device));
+        let lut_identities = LutIdentity::from_textures(&preset.textures);
         let luts = FilterChainWgpu::load_luts(
             &device,
             &queue,
@@ -234,7 +295,11 @@ impl FilterChainWgpu {
             common: FilterCommon {
                 luts,
                 samplers,
-                config: RuntimeParameters::new(preset.pass_count as usize, preset.parameters),
+                config: RuntimeParameters::new(
+                    preset.pass_count as usize,
+                    preset.parameters,
+                    filters.iter().map(|f| &f.source),
+                ),
                 draw_quad,
                 device,
                 queue,
@@ -243,15 +308,237 @@ impl FilterChainWgpu {
                 history_textures,
             },
             passes: filters,
+            compare_pass,
             output_framebuffers,
             feedback_framebuffers,
             history_framebuffers,
             disable_mipmaps: options.map(|f| f.force_no_mipmaps).unwrap_or(false),
             mipmapper,
             default_frame_options: Default::default(),
+            options: options.cloned().unwrap_or_default(),
+            lut_identities,
+            pending_passes: None,
+            profiler: FrameProfiler::new(3),
+            frame_count: 0,
         })
     }
 
+    /// Compile the passes of a shader preset, to be swapped into a filter chain created
+    /// with the same device with [`replace_passes`](Self::replace_passes).
+    ///
+    /// Compiling passes does not require access to the filter chain, and can be done on any thread.
+    pub fn compile_passes(
+        mut preset: ShaderPresetPack,
+        device: Arc<Device>,
+        options: Option<&FilterChainOptionsWgpu>,
+    ) -> error::Result<CompiledPassesWgpu> {
+        if let Some(encoding) = options.and_then(|o| o.hdr_output) {
+            append_hdr_output_pass(&mut preset, encoding)?;
+        }
+        // cache is opt-in for wgpu, not opt-out because of feature requirements.
+        let disable_cache = options.is_none_or(|o| !o.enable_cache);
+
+        let compiled = CompiledPasses::new(preset, |passes, textures| {
+            let (passes, semantics) = compile_passes(passes, textures, None)?;
+            Self::init_passes(
+                device,
+                passes,
+                &semantics,
+                options.and_then(|o| o.adapter_info.as_ref()),
+                disable_cache,
+            )
+        })?;
+
+        Ok(CompiledPassesWgpu(compiled))
+    }
+
+    /// Replace the passes of the filter chain with compiled passes.
+    ///
+    /// The passes are swapped in at the next call to [`frame`](Self::frame). History and feedback
+    /// framebuffers are kept, and lookup textures that are unchanged from the current preset are reused.
+    /// Runtime parameters are reset to the values of the new preset once the passes are swapped in.
+    pub fn replace_passes(&mut self, passes: CompiledPassesWgpu) {
+        self.pending_passes = Some(passes.0);
+    }
+
+    /// Reload the filter chain with a new shader preset.
+    ///
+    /// The passes of the preset are compiled immediately, then swapped in at the next call to
+    /// [`frame`](Self::frame) as with [`replace_passes`](Self::replace_passes).
+    pub fn reload(&mut self, preset: ShaderPresetPack) -> error::Result<()> {
+        let passes =
+            Self::compile_passes(preset, Arc::clone(&self.common.device), Some(&self.options))?;
+        self.replace_passes(passes);
+        Ok(())
+    }
+
+    /// Read back the temporal state of the filter chain, to be restored later with
+    /// [`restore_state`](Self::restore_state).
+    ///
+    /// This submits a copy of the history and feedback framebuffers to the queue of the filter chain,
+    /// and blocks until it completes. Commands recorded by [`frame`](Self::frame) must have been
+    /// submitted before the state is saved.
+    pub fn snapshot_state(&self) -> error::Result<FilterChainState> {
+        let mut cmd = self
+            .common
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("snapshot_state"),
+            });
+
+        let history = self
+            .history_framebuffers
+            .iter()
+            .map(|image| image.readback(&mut cmd))
+            .collect::<error::Result<Vec<_>>>()?;
+        let feedback = self
+            .output_framebuffers
+            .iter()
+            .map(|image| image.readback(&mut cmd))
+            .collect::<error::Result<Vec<_>>>()?;
+
+        self.common.queue.submit([cmd.finish()]);
+        let mapped: Vec<_> = history
+            .iter()
+            .chain(feedback.iter())
+            .map(|readback| readback.map())
+            .collect();
+        self.common.device.poll(wgpu::Maintain::Wait);
+
+        for mapped in mapped {
+            match mapped.get() {
+                Some(Ok(())) => {}
+                _ => return Err(FilterChainError::ReadbackError),
+            }
+        }
+
+        Ok(FilterChainState {
+            frame_count: self.frame_count,
+            history: history
+                .into_iter()
+                .map(|readback| readback.read().map(Some))
+                .collect::<error::Result<_>>()?,
+            feedback: feedback
+                .into_iter()
+                .map(|readback| readback.read().map(Some))
+                .collect::<error::Result<_>>()?,
+        })
+    }
+
+    /// Restore temporal state saved with [`snapshot_state`](Self::snapshot_state) into the
+    /// history and feedback framebuffers of the filter chain.
+    ///
+    /// The state must have been saved from a filter chain with the same shader preset, but
+    /// can come from a different runtime. The state is uploaded with the queue of the filter
+    /// chain, and is visible to the next call to [`frame`](Self::frame).
+    pub fn restore_state(&mut self, state: &FilterChainState) -> error::Result<()> {
+        state.check_compatible(
+            self.history_framebuffers.len(),
+            self.output_framebuffers.len(),
+        )?;
+
+        for (image, saved) in self.history_framebuffers.iter_mut().zip(&state.history) {
+            if let Some(saved) = saved {
+                image.restore(&self.common.queue, saved)?;
+            }
+        }
+
+        let mut cmd = self
+            .common
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("restore_state"),
+            });
+
+        for (image, saved) in self.output_framebuffers.iter_mut().zip(&state.feedback) {
+            let Some(saved) = saved else {
+                continue;
+            };
+
+            image.restore(&self.common.queue, saved)?;
+            if image.max_miplevels > 1 && !self.disable_mipmaps {
+                let sampler = self.common.samplers.get(
+                    WrapMode::ClampToEdge,
+                    FilterMode::Linear,
+                    FilterMode::Nearest,
+                );
+                image.generate_mipmaps(&mut cmd, &mut self.mipmapper, &sampler);
+            }
+        }
+
+        self.common.queue.submit([cmd.finish()]);
+        self.frame_count = state.frame_count;
+        Ok(())
+    }
+
+    fn swap_passes(
+        &mut self,
+        compiled: CompiledPasses<Box<[FilterPass]>>,
+        cmd: &mut wgpu::CommandEncoder,
+    ) -> error::Result<()> {
+        let CompiledPasses {
+            passes,
+            textures,
+            lut_identities,
+            parameters,
+            pass_count,
+        } = compiled;
+
+        let mut luts = LutReuse::new(
+            std::mem::take(&mut self.common.luts),
+            &self.lut_identities,
+            &lut_identities,
+            textures,
+            drop,
+        );
+        let loaded = FilterChainWgpu::load_luts(
+            &self.common.device,
+            &self.common.queue,
+            cmd,
+            &mut self.mipmapper,
+            &self.common.samplers,
+            luts.take_textures(),
+        )?;
+        self.common.luts = luts.finish(loaded);
+        self.lut_identities = lut_identities;
+
+        let device = Arc::clone(&self.common.device);
+        let framebuffer_gen = || {
+            Ok::<_, error::FilterChainError>(OwnedImage::new(
+                Arc::clone(&device),
+                Size::new(1, 1),
+                1,
+                TextureFormat::Bgra8Unorm,
+            ))
+        };
+        let input_gen = || None;
+        let framebuffer_init = FramebufferInit::new(
+            passes.iter().map(|f| &f.reflection.meta),
+            &framebuffer_gen,
+            &input_gen,
+        );
+
+        let (output_framebuffers, output_textures) = framebuffer_init
+            .reuse_output_framebuffers(std::mem::take(&mut self.output_framebuffers), drop)?;
+        let (feedback_framebuffers, feedback_textures) = framebuffer_init
+            .reuse_output_framebuffers(std::mem::take(&mut self.feedback_framebuffers), drop)?;
+        let (history_framebuffers, history_textures) =
+            framebuffer_init.reuse_history(std::mem::take(&mut self.history_framebuffers), drop)?;
+
+        self.output_framebuffers = output_framebuffers;
+        self.feedback_framebuffers = feedback_framebuffers;
+        self.history_framebuffers = history_framebuffers;
+        self.common.output_textures = output_textures;
+        self.common.feedback_textures = feedback_textures;
+        self.common.history_textures = history_textures;
+        self.common.config =
+            RuntimeParameters::new(pass_count, parameters, passes.iter().map(|f| &f.source));
+        self.draw_last_pass_feedback = framebuffer_init.uses_final_pass_as_feedback();
+        self.passes = passes;
+
+        Ok(())
+    }
+
     fn load_luts(
         device: &wgpu::Device,
         queue: &wgpu::Queue,
@@ -306,8 +593,8 @@ impl FilterChainWgpu {
         adapter_info: Option<&wgpu::AdapterInfo>,
         disable_cache: bool,
     ) -> error::Result<Box<[FilterPass]>> {
-        #[cfg(not(target_arch = "wasm32"))]
         let filter_creation_fn = || {
+            #[cfg(not(target_arch = "wasm32"))]
             let passes_iter = passes.into_par_iter();
             #[cfg(target_arch = "wasm32")]
             let passes_iter = passes.into_iter();
@@ -316,10 +603,12 @@ impl FilterChainWgpu {
                 .enumerate()
                 .map(|(index, (config, mut reflect))| {
                     let reflection = reflect.reflect(index, semantics)?;
-                    let wgsl = reflect.compile(NagaLoweringOptions {
-                        write_pcb_as_ubo: true,
-                        sampler_bind_group: 1,
-                    })?;
+                    let wgsl = reflect
+                        .compile(NagaLoweringOptions {
+                            write_pcb_as_ubo: true,
+                            sampler_bind_group: 1,
+                        })
+                        .map_err(|error| error.in_pass(index))?;
 
                     let ubo_size = reflection.ubo.as_ref().map_or(0, |ubo| ubo.size as usize);
                     let push_size = reflection
@@ -403,8 +692,18 @@ impl FilterChainWgpu {
         frame_count: usize,
         options: Option<&FrameOptionsWgpu>,
     ) -> error::Result<()> {
+        if let Some(compiled) = self.pending_passes.take() {
+            self.swap_passes(compiled, cmd)?;
+        }
+        self.frame_count = frame_count;
+
+        self.common.config.set_automation_frame(frame_count);
+
         let max = std::cmp::min(self.passes.len(), self.common.config.passes_enabled());
-        let passes = &mut self.passes[0..max];
+        let range = options
+            .unwrap_or(&self.default_frame_options)
+            .pass_range(max);
+        let passes = &mut self.passes[0..range.end];
 
         if let Some(options) = &options {
             if options.clear_history {
@@ -471,12 +770,39 @@ impl FilterChainWgpu {
             }),
         )?;
 
+        let mut timestamps = self.profiler.begin_frame(
+            frame_count,
+            passes
+                .iter()
+                .map(|pass| pass.meta.alias.as_ref().map(|alias| alias.as_str())),
+            |count| {
+                Ok::<_, FilterChainError>(WgpuTimestamps::new(
+                    &self.common.device,
+                    &self.common.queue,
+                    count,
+                ))
+            },
+        )?;
+
+        if let Some(timestamps) = &mut timestamps {
+            timestamps.begin();
+            timestamps.write(cmd, 0);
+        }
+
         let passes_len = passes.len();
         let (pass, last) = passes.split_at_mut(passes_len - 1);
 
         let options = options.unwrap_or(&self.default_frame_options);
 
         for (index, pass) in pass.iter_mut().enumerate() {
+            // skip passes before the range, so that the first pass reads the original image.
+            if index < range.start {
+                if let Some(timestamps) = &timestamps {
+                    timestamps.write(cmd, index + 1);
+                }
+                continue;
+            }
+
             source.filter_mode = pass.meta.filter;
             source.wrap_mode = pass.meta.wrap_mode;
             source.mip_filter = pass.meta.filter;
@@ -508,6 +834,10 @@ impl FilterChainWgpu {
                 target.generate_mipmaps(cmd, &mut self.mipmapper, &sampler);
             }
 
+            if let Some(timestamps) = &timestamps {
+                timestamps.write(cmd, index + 1);
+            }
+
             source = self.common.output_textures[index].clone().unwrap();
         }
 
@@ -525,11 +855,82 @@ impl FilterChainWgpu {
             source.wrap_mode = pass.meta.wrap_mode;
             source.mip_filter = pass.meta.filter;
 
-            if self.draw_last_pass_feedback {
-                let target = &self.output_framebuffers[index];
-                let output_image = WgpuOutputView::from(target);
-                let out = RenderTarget::viewport_with_output(&output_image, viewport);
+            if options.compare_enabled() {
+                // draw the last pass at the size of the viewport, to compare with the original image.
+                let target = &mut self.output_framebuffers[index];
+                compare::scale_compare_source(target, pass, viewport.size, &())?;
+                self.common.output_textures[index] =
+                    Some(target.as_input(pass.meta.filter, pass.meta.wrap_mode));
 
+                let output_image = WgpuOutputView::from(&*target);
+                let out = RenderTarget::identity(&output_image)?;
+                pass.draw(
+                    cmd,
+                    index,
+                    &self.common,
+                    pass.meta.get_frame_count(frame_count),
+                    options,
+                    viewport,
+                    &original,
+                    &source,
+                    &out,
+                    QuadType::Offscreen,
+                )?;
+
+                let compare_pass = &mut self.compare_pass;
+                compare::set_compare_uniforms(
+                    &mut compare_pass.source.parameters,
+                    options.compare_split,
+                    options.compare_line_width,
+                );
+                if !compare_pass
+                    .graphics_pipeline
+                    .has_format(viewport.output.format)
+                {
+                    compare_pass
+                        .graphics_pipeline
+                        .recompile(viewport.output.format);
+                }
+
+                let mut source = self.common.output_textures[index].clone().unwrap();
+                source.filter_mode = compare_pass.meta.filter;
+                source.wrap_mode = compare_pass.meta.wrap_mode;
+                source.mip_filter = compare_pass.meta.filter;
+
+                let out = RenderTarget::viewport(viewport);
+                compare_pass.draw(
+                    cmd,
+                    passes_len,
+                    &self.common,
+                    compare_pass.meta.get_frame_count(frame_count),
+                    options,
+                    viewport,
+                    &original,
+                    &source,
+                    &out,
+                    QuadType::Final,
+                )?;
+            } else {
+                if self.draw_last_pass_feedback {
+                    let target = &self.output_framebuffers[index];
+                    let output_image = WgpuOutputView::from(target);
+                    let out = RenderTarget::viewport_with_output(&output_image, viewport);
+
+                    pass.draw(
+                        cmd,
+                        index,
+                        &self.common,
+                        pass.meta.get_frame_count(frame_count),
+                        options,
+                        viewport,
+                        &original,
+                        &source,
+                        &out,
+                        QuadType::Final,
+                    )?;
+                }
+
+                let out = RenderTarget::viewport(viewport);
                 pass.draw(
                     cmd,
                     index,
@@ -544,22 +945,66 @@ impl FilterChainWgpu {
                 )?;
             }
 
-            let out = RenderTarget::viewport(viewport);
-            pass.draw(
-                cmd,
-                index,
-                &self.common,
-                pass.meta.get_frame_count(frame_count),
-                options,
-                viewport,
-                &original,
-                &source,
-                &out,
-                QuadType::Final,
-            )?;
+            if let Some(timestamps) = &timestamps {
+                timestamps.write(cmd, passes_len);
+                timestamps.resolve(cmd, passes_len);
+            }
         }
 
         self.push_history(&input, cmd);
         Ok(())
     }
 }
+
+use librashader_runtime::impl_filter_chain_debug;
+impl_filter_chain_debug!(FilterChainWgpu, Arc<wgpu::Texture>, |texture| {
+    Arc::clone(&texture.image)
+});
+
+use librashader_runtime::impl_filter_chain_profiling;
+impl_filter_chain_profiling!(FilterChainWgpu);
+
+impl librashader_runtime::filter_chain::FilterChain for FilterChainWgpu {
+    type Device<'a> = (Arc<Device>, Arc<wgpu::Queue>);
+    type InputImage<'a> = Arc<wgpu::Texture>;
+    type OutputView<'a> = WgpuOutputView<'a>;
+    type CommandContext<'a> = &'a mut wgpu::CommandEncoder;
+    type FilterChainOptions = FilterChainOptionsWgpu;
+    type FrameOptions = FrameOptionsWgpu;
+    type Error = FilterChainError;
+
+    unsafe fn load_from_pack(
+        preset: ShaderPresetPack,
+        (device, queue): Self::Device<'_>,
+        options: Option<&Self::FilterChainOptions>,
+    ) -> error::Result<Self> {
+        FilterChainWgpu::load_from_pack(preset, device, queue, options)
+    }
+
+    unsafe fn load_from_preset(
+        preset: ShaderPreset,
+        (device, queue): Self::Device<'_>,
+        options: Option<&Self::FilterChainOptions>,
+    ) -> error::Result<Self> {
+        FilterChainWgpu::load_from_preset(preset, device, queue, options)
+    }
+
+    unsafe fn load_from_path(
+        path: impl AsRef<Path>,
+        (device, queue): Self::Device<'_>,
+        options: Option<&Self::FilterChainOptions>,
+    ) -> error::Result<Self> {
+        FilterChainWgpu::load_from_path(path, device, queue, options)
+    }
+
+    unsafe fn frame<'a>(
+        &mut self,
+        input: Self::InputImage<'a>,
+        viewport: &Viewport<Self::OutputView<'a>>,
+        cmd: Self::CommandContext<'a>,
+        frame_count: usize,
+        options: Option<&Self::FrameOptions>,
+    ) -> error::Result<()> {
+        FilterChainWgpu::frame(self, input, viewport, cmd, frame_count, options)
+    }
+}
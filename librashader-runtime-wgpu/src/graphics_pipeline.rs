@@ -1,5 +1,6 @@
 use crate::framebuffer::WgpuOutputView;
 use crate::util;
+#[cfg(not(target_arch = "wasm32"))]
 use librashader_cache::cache_pipeline;
 use librashader_common::map::FastHashMap;
 use librashader_reflect::back::wgsl::NagaWgslContext;
@@ -8,6 +9,7 @@ use librashader_reflect::reflect::ShaderReflection;
 use librashader_runtime::quad::VertexInput;
 use librashader_runtime::render_target::RenderTarget;
 use std::borrow::Cow;
+#[cfg(not(target_arch = "wasm32"))]
 use std::convert::Infallible;
 use std::sync::Arc;
 use wgpu::{
@@ -226,30 +228,7 @@ impl WgpuGraphicsPipeline {
         let cache = if bypass_cache {
             None
         } else {
-            let name = adapter_info
-                .and_then(|o| wgpu::util::pipeline_cache_key(o))
-                .unwrap_or_else(|| String::from("wgpu"));
-
-            cache_pipeline(
-                &name,
-                &[
-                    &shader_assembly.vertex.as_str(),
-                    &shader_assembly.fragment.as_str(),
-                ],
-                |pipeline_data| {
-                    let descriptor = wgpu::PipelineCacheDescriptor {
-                        label: Some("librashader-wgpu"),
-                        data: pipeline_data.as_deref(),
-                        fallback: true,
-                    };
-
-                    let cache = unsafe { device.create_pipeline_cache(&descriptor) };
-                    Ok::<_, Infallible>(cache)
-                },
-                |cache| Ok(cache.get_data()),
-                bypass_cache,
-            )
-            .ok()
+            Self::load_pipeline_cache(&device, shader_assembly, adapter_info)
         };
 
         let layout = PipelineLayoutObjects::new(reflection, shader_assembly, device);
@@ -265,11 +244,54 @@ impl WgpuGraphicsPipeline {
         }
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
+    fn load_pipeline_cache(
+        device: &wgpu::Device,
+        shader_assembly: &ShaderCompilerOutput<String, NagaWgslContext>,
+        adapter_info: Option<&wgpu::AdapterInfo>,
+    ) -> Option<wgpu::PipelineCache> {
+        let name = adapter_info
+            .and_then(wgpu::util::pipeline_cache_key)
+            .unwrap_or_else(|| String::from("wgpu"));
+
+        cache_pipeline(
+            &name,
+            &[
+                &shader_assembly.vertex.as_str(),
+                &shader_assembly.fragment.as_str(),
+            ],
+            |pipeline_data| {
+                let descriptor = wgpu::PipelineCacheDescriptor {
+                    label: Some("librashader-wgpu"),
+                    data: pipeline_data.as_deref(),
+                    fallback: true,
+                };
+
+                let cache = unsafe { device.create_pipeline_cache(&descriptor) };
+                Ok::<_, Infallible>(cache)
+            },
+            |cache| Ok(cache.get_data()),
+            false,
+        )
+        .ok()
+    }
+
+    // Pipeline caches are not supported on WebGPU, and there is no file system to persist them to.
+    #[cfg(target_arch = "wasm32")]
+    fn load_pipeline_cache(
+        _device: &wgpu::Device,
+        _shader_assembly: &ShaderCompilerOutput<String, NagaWgslContext>,
+        _adapter_info: Option<&wgpu::AdapterInfo>,
+    ) -> Option<wgpu::PipelineCache> {
+        None
+    }
+
     pub fn has_format(&self, format: TextureFormat) -> bool {
         self.render_pipelines.contains_key(&format)
     }
 
     pub fn recompile(&mut self, format: TextureFormat) {
+        log::debug!("recompiling pipeline for output format {format:?}");
         let render_pipeline = self.layout.create_pipeline(format, self.cache.as_ref());
         self.render_pipelines.insert(format, render_pipeline);
     }
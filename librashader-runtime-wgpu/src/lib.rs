@@ -14,10 +14,12 @@ mod graphics_pipeline;
 mod handle;
 mod luts;
 mod mipmap;
+mod profiling;
 mod samplers;
 mod texture;
 mod util;
 
+pub use filter_chain::CompiledPassesWgpu;
 pub use filter_chain::FilterChainWgpu;
 pub use framebuffer::WgpuOutputView;
 
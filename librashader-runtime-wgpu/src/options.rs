@@ -1,5 +1,6 @@
 //! wgpu shader runtime options.
 
+use librashader_runtime::hdr::HdrOutputEncoding;
 use librashader_runtime::impl_default_frame_options;
 impl_default_frame_options!(FrameOptionsWgpu);
 
@@ -16,4 +17,8 @@ pub struct FilterChainOptionsWgpu {
     /// If this is not provided, then it will fallback to a default "wgpu" index, which
     /// may clobber the cache for a different device using WGPU.
     pub adapter_info: Option<wgpu::AdapterInfo>,
+    /// Append the built-in HDR output pass, which encodes the output of the shader preset for
+    /// an HDR output. The filter chain should then be drawn to an output with the format given
+    /// by [`HdrOutputEncoding::output_format`].
+    pub hdr_output: Option<HdrOutputEncoding>,
 }
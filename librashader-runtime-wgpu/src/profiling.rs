@@ -0,0 +1,136 @@
+use librashader_runtime::profiling::{timestamp_durations, PassTimer};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use wgpu::{Buffer, BufferUsages, Device, Features, QuerySet};
+
+const MAP_PENDING: u8 = 0;
+const MAP_READY: u8 = 1;
+const MAP_FAILED: u8 = 2;
+
+/// Timestamps written before the first pass and after each pass of a frame.
+pub(crate) struct WgpuTimestamps {
+    device: Arc<Device>,
+    query_set: QuerySet,
+    resolve: Buffer,
+    readback: Buffer,
+    map_state: Option<Arc<AtomicU8>>,
+    capacity: usize,
+    period: f32,
+}
+
+impl WgpuTimestamps {
+    /// Create timestamp queries for the given number of passes, if the device supports
+    /// writing timestamps to command encoders.
+    pub fn new(device: &Arc<Device>, queue: &wgpu::Queue, passes: usize) -> Option<Self> {
+        if !device
+            .features()
+            .contains(Features::TIMESTAMP_QUERY | Features::TIMESTAMP_QUERY_INSIDE_ENCODERS)
+        {
+            return None;
+        }
+
+        let size = (passes as u64 + 1) * std::mem::size_of::<u64>() as u64;
+        let query_set = device.create_query_set(&wgpu::QuerySetDescriptor {
+            label: Some("librashader timestamps"),
+            ty: wgpu::QueryType::Timestamp,
+            count: passes as u32 + 1,
+        });
+
+        let resolve = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("librashader timestamp resolve"),
+            size,
+            usage: BufferUsages::QUERY_RESOLVE | BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+
+        let readback = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("librashader timestamp readback"),
+            size,
+            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Some(WgpuTimestamps {
+            device: Arc::clone(device),
+            query_set,
+            resolve,
+            readback,
+            map_state: None,
+            capacity: passes,
+            period: queue.get_timestamp_period(),
+        })
+    }
+
+    /// Prepare the queries to be recorded for a new frame.
+    ///
+    /// Any results of the previous frame that were not read back are discarded.
+    pub fn begin(&mut self) {
+        if self.map_state.take().is_some() {
+            self.readback.unmap();
+        }
+    }
+
+    /// Write the timestamp at the given index once all previous commands have completed.
+    pub fn write(&self, cmd: &mut wgpu::CommandEncoder, index: usize) {
+        cmd.write_timestamp(&self.query_set, index as u32);
+    }
+
+    /// Copy the timestamps of the given number of passes to the readback buffer.
+    pub fn resolve(&self, cmd: &mut wgpu::CommandEncoder, passes: usize) {
+        let count = passes as u32 + 1;
+        cmd.resolve_query_set(&self.query_set, 0..count, &self.resolve, 0);
+        cmd.copy_buffer_to_buffer(
+            &self.resolve,
+            0,
+            &self.readback,
+            0,
+            count as u64 * std::mem::size_of::<u64>() as u64,
+        );
+    }
+}
+
+impl PassTimer for WgpuTimestamps {
+    fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn read(&mut self, passes: usize) -> Option<Vec<Duration>> {
+        let state = self.map_state.get_or_insert_with(|| {
+            let state = Arc::new(AtomicU8::new(MAP_PENDING));
+            let callback_state = Arc::clone(&state);
+            self.readback
+                .slice(..)
+                .map_async(wgpu::MapMode::Read, move |result| {
+                    let value = if result.is_ok() {
+                        MAP_READY
+                    } else {
+                        MAP_FAILED
+                    };
+                    callback_state.store(value, Ordering::Release);
+                });
+            state
+        });
+
+        if state.load(Ordering::Acquire) == MAP_PENDING {
+            self.device.poll(wgpu::Maintain::Poll);
+        }
+
+        match state.load(Ordering::Acquire) {
+            MAP_READY => {
+                let durations = {
+                    let range = self.readback.slice(..).get_mapped_range();
+                    let timestamps: &[u64] = bytemuck::cast_slice(&range);
+                    timestamp_durations(&timestamps[..passes + 1], self.period as f64)
+                };
+                self.begin();
+                Some(durations)
+            }
+            MAP_FAILED => {
+                self.map_state = None;
+                None
+            }
+            _ => None,
+        }
+    }
+}
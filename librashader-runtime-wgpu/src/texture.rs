@@ -1,10 +1,12 @@
+use crate::error;
 use crate::error::FilterChainError;
 use crate::mipmap::MipmapGen;
 use crate::WgpuOutputView;
 use librashader_common::{FilterMode, GetSize, ImageFormat, Size, WrapMode};
 use librashader_presets::Scale2D;
 use librashader_runtime::scaling::{MipmapSize, ScaleFramebuffer, ViewportSize};
-use std::sync::Arc;
+use librashader_runtime::state::{FramebufferState, StateError};
+use std::sync::{Arc, OnceLock};
 use wgpu::TextureFormat;
 
 pub struct OwnedImage {
@@ -141,6 +143,132 @@ impl OwnedImage {
     }
 }
 
+/// The first mip level of an image, copied into a buffer to be read back.
+pub(crate) struct ImageReadback {
+    buffer: Arc<wgpu::Buffer>,
+    size: Size<u32>,
+    format: ImageFormat,
+    swap_red_blue: bool,
+    bytes_per_row: usize,
+    padded_bytes_per_row: usize,
+}
+
+impl ImageReadback {
+    /// Map the buffer for reading once the copy has been submitted.
+    ///
+    /// The buffer is mapped the next time the device is polled.
+    pub fn map(&self) -> Arc<OnceLock<Result<(), wgpu::BufferAsyncError>>> {
+        let mapped = Arc::new(OnceLock::new());
+        let result = Arc::clone(&mapped);
+        self.buffer
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, move |r| {
+                let _ = result.set(r);
+            });
+        mapped
+    }
+
+    /// Read the texels of the image once the buffer has been mapped.
+    pub fn read(self) -> error::Result<FramebufferState> {
+        let mut data = Vec::with_capacity(self.bytes_per_row * self.size.height as usize);
+        {
+            let mapped = self.buffer.slice(..).get_mapped_range();
+            for row in mapped.chunks(self.padded_bytes_per_row) {
+                data.extend_from_slice(&row[..self.bytes_per_row]);
+            }
+        }
+        self.buffer.unmap();
+
+        if self.swap_red_blue {
+            for texel in data.chunks_exact_mut(4) {
+                texel.swap(0, 2);
+            }
+        }
+
+        Ok(FramebufferState::new(self.size, self.format, data)?)
+    }
+}
+
+impl OwnedImage {
+    /// Copy the first mip level of the image into a buffer to be read back.
+    pub(crate) fn readback(&self, cmd: &mut wgpu::CommandEncoder) -> error::Result<ImageReadback> {
+        // BGRA images can only come from history, and are read back as RGBA.
+        let (format, swap_red_blue) = match self.image.format() {
+            TextureFormat::Bgra8Unorm => (ImageFormat::R8G8B8A8Unorm, true),
+            TextureFormat::Bgra8UnormSrgb => (ImageFormat::R8G8B8A8Srgb, true),
+            format => (ImageFormat::from(format), false),
+        };
+
+        let bytes_per_row = FramebufferState::data_len(Size::new(self.size.width, 1), format)?;
+        let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as usize;
+        let padded_bytes_per_row = bytes_per_row.div_ceil(align) * align;
+
+        let buffer = Arc::new(self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("state readback"),
+            size: (padded_bytes_per_row * self.size.height as usize) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        }));
+
+        cmd.copy_texture_to_buffer(
+            self.image.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: &buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_bytes_per_row as u32),
+                    rows_per_image: None,
+                },
+            },
+            self.size.into(),
+        );
+
+        Ok(ImageReadback {
+            buffer,
+            size: self.size,
+            format,
+            swap_red_blue,
+            bytes_per_row,
+            padded_bytes_per_row,
+        })
+    }
+
+    /// Upload saved texels into the first mip level of the image, recreating the image
+    /// if its size or format do not match.
+    pub(crate) fn restore(
+        &mut self,
+        queue: &wgpu::Queue,
+        state: &FramebufferState,
+    ) -> error::Result<()> {
+        let format: Option<TextureFormat> = state.format.into();
+        let format = format.ok_or(StateError::UnsupportedFormat(state.format))?;
+
+        if self.size != state.size || self.image.format() != format {
+            let mut new = OwnedImage::new(
+                Arc::clone(&self.device),
+                state.size,
+                self.max_miplevels,
+                format,
+            );
+            std::mem::swap(self, &mut new);
+        }
+
+        let bytes_per_row =
+            FramebufferState::data_len(Size::new(state.size.width, 1), state.format)?;
+        queue.write_texture(
+            self.image.as_image_copy(),
+            &state.data,
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(bytes_per_row as u32),
+                rows_per_image: None,
+            },
+            state.size.into(),
+        );
+        Ok(())
+    }
+}
+
 impl ScaleFramebuffer for OwnedImage {
     type Error = FilterChainError;
     type Context = ();
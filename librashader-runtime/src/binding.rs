@@ -1,6 +1,6 @@
 use crate::parameters::RuntimeParameters;
 use crate::uniforms::{BindUniform, NoUniformBinder, UniformStorage};
-use librashader_common::map::{FastHashMap, ShortString};
+use librashader_common::map::{FastHashMap, FastIndexMap, ShortString};
 use librashader_common::Size;
 use librashader_preprocess::ShaderParameter;
 use librashader_reflect::reflect::semantics::{
@@ -60,6 +60,26 @@ pub struct UniformInputs<'a> {
     pub total_subframes: u32,
     /// CurrentSubFrame
     pub current_subframe: u32,
+    /// OriginalAspect
+    ///
+    /// If zero or negative, the aspect ratio of the original texture is used.
+    pub aspect_ratio: f32,
+    /// OriginalFPS
+    pub original_fps: f32,
+    /// FrameTimeDelta
+    pub frametime_delta: u32,
+    /// CoreFPS
+    pub core_fps: f32,
+    /// EnableHDR
+    pub enable_hdr: bool,
+    /// PaperWhiteNits
+    pub paper_white_nits: f32,
+    /// MaxNits
+    pub max_nits: f32,
+    /// ExpandGamut
+    pub expand_gamut: bool,
+    /// InverseTonemap
+    pub inverse_tonemap: bool,
     /// FrameDirection
     pub frame_direction: i32,
     /// OutputSize
@@ -120,9 +140,11 @@ where
         pass_feedback: impl Iterator<Item = Option<impl AsRef<Self::InputTexture>>>,
         original_history: impl Iterator<Item = Option<impl AsRef<Self::InputTexture>>>,
         lookup_textures: impl Iterator<Item = (usize, impl AsRef<Self::InputTexture>)>,
-        parameter_defaults: &FastHashMap<ShortString, ShaderParameter>,
+        parameter_defaults: &FastIndexMap<ShortString, ShaderParameter>,
         runtime_parameters: &RuntimeParameters,
     ) {
+        let parameters = runtime_parameters;
+        let automation = parameters.automation.load();
         let runtime_parameters = runtime_parameters.parameters.load();
         // Bind MVP
         if let Some(offset) = uniform_bindings.get(&UniqueSemantics::MVP.into()) {
@@ -204,6 +226,108 @@ where
             );
         }
 
+        let original_aspect = if uniform_inputs.aspect_ratio > 0.0 {
+            uniform_inputs.aspect_ratio
+        } else {
+            let size = original.size();
+            size.width as f32 / size.height.max(1) as f32
+        };
+
+        // bind OriginalAspect
+        if let Some(offset) = uniform_bindings.get(&UniqueSemantics::OriginalAspect.into()) {
+            uniform_storage.bind_scalar(offset.offset(), original_aspect, offset.context(), device);
+        }
+
+        // bind OriginalAspectRotated
+        if let Some(offset) = uniform_bindings.get(&UniqueSemantics::OriginalAspectRotated.into()) {
+            let rotated_aspect = if uniform_inputs.rotation % 2 == 1 {
+                1.0 / original_aspect
+            } else {
+                original_aspect
+            };
+            uniform_storage.bind_scalar(offset.offset(), rotated_aspect, offset.context(), device);
+        }
+
+        // bind OriginalFPS
+        if let Some(offset) = uniform_bindings.get(&UniqueSemantics::OriginalFPS.into()) {
+            uniform_storage.bind_scalar(
+                offset.offset(),
+                uniform_inputs.original_fps,
+                offset.context(),
+                device,
+            );
+        }
+
+        // bind FrameTimeDelta
+        if let Some(offset) = uniform_bindings.get(&UniqueSemantics::FrameTimeDelta.into()) {
+            uniform_storage.bind_scalar(
+                offset.offset(),
+                uniform_inputs.frametime_delta,
+                offset.context(),
+                device,
+            );
+        }
+
+        // bind CoreFPS
+        if let Some(offset) = uniform_bindings.get(&UniqueSemantics::CoreFPS.into()) {
+            uniform_storage.bind_scalar(
+                offset.offset(),
+                uniform_inputs.core_fps,
+                offset.context(),
+                device,
+            );
+        }
+
+        // bind EnableHDR
+        if let Some(offset) = uniform_bindings.get(&UniqueSemantics::EnableHDR.into()) {
+            uniform_storage.bind_scalar(
+                offset.offset(),
+                uniform_inputs.enable_hdr as u32,
+                offset.context(),
+                device,
+            );
+        }
+
+        // bind PaperWhiteNits
+        if let Some(offset) = uniform_bindings.get(&UniqueSemantics::PaperWhiteNits.into()) {
+            uniform_storage.bind_scalar(
+                offset.offset(),
+                uniform_inputs.paper_white_nits,
+                offset.context(),
+                device,
+            );
+        }
+
+        // bind MaxNits
+        if let Some(offset) = uniform_bindings.get(&UniqueSemantics::MaxNits.into()) {
+            uniform_storage.bind_scalar(
+                offset.offset(),
+                uniform_inputs.max_nits,
+                offset.context(),
+                device,
+            );
+        }
+
+        // bind ExpandGamut
+        if let Some(offset) = uniform_bindings.get(&UniqueSemantics::ExpandGamut.into()) {
+            uniform_storage.bind_scalar(
+                offset.offset(),
+                uniform_inputs.expand_gamut as u32,
+                offset.context(),
+                device,
+            );
+        }
+
+        // bind InverseTonemap
+        if let Some(offset) = uniform_bindings.get(&UniqueSemantics::InverseTonemap.into()) {
+            uniform_storage.bind_scalar(
+                offset.offset(),
+                uniform_inputs.inverse_tonemap as u32,
+                offset.context(),
+                device,
+            );
+        }
+
         // bind Original sampler
         if let Some(binding) = texture_meta.get(&TextureSemantics::Original.semantics(0)) {
             Self::bind_texture(descriptor_set, sampler_set, binding, original, device);
@@ -327,7 +451,10 @@ where
 
             let default = parameter_defaults.get(id).map_or(0f32, |f| f.initial);
 
-            let value = *runtime_parameters.get(id).unwrap_or(&default);
+            let value = automation
+                .as_deref()
+                .and_then(|automation| parameters.automated_value(automation, id))
+                .unwrap_or_else(|| *runtime_parameters.get(id).unwrap_or(&default));
 
             uniform_storage.bind_scalar(offset.offset(), value, offset.context(), device);
         }
@@ -468,6 +595,67 @@ macro_rules! impl_default_frame_options {
             pub total_subframes: u32,
             /// The current sub frame. Default is 1.
             pub current_subframe: u32,
+            /// The aspect ratio of the original content.
+            /// If zero, the aspect ratio of the input image is used. Default is 0.
+            pub aspect_ratio: f32,
+            /// The refresh rate of the original content, in frames per second. Default is 60.
+            pub original_fps: f32,
+            /// The time elapsed since the previous frame, in microseconds. Default is 0.
+            pub frametime_delta: u32,
+            /// The rate at which frames are currently being produced, in frames per second.
+            /// Default is 60.
+            pub core_fps: f32,
+            /// Whether or not the output is HDR. Default is false.
+            pub enable_hdr: bool,
+            /// The brightness of SDR paper white in nits. Default is 200.
+            pub paper_white_nits: f32,
+            /// The peak brightness of the display in nits. Default is 1000.
+            pub max_nits: f32,
+            /// Whether or not to expand the Rec. 709 gamut when converting to HDR. Default is false.
+            pub expand_gamut: bool,
+            /// Whether or not to inverse tonemap SDR content when converting to HDR. Default is false.
+            pub inverse_tonemap: bool,
+            /// The index of the first pass to render. Default is 0.
+            ///
+            /// Passes before the first pass are skipped, and the first pass reads the original
+            /// image as its `Source`. Framebuffers are still sized as if every pass was rendered.
+            pub first_pass: u32,
+            /// The number of passes to render, starting from the first pass.
+            /// If zero, all enabled passes after the first pass are rendered. Default is 0.
+            ///
+            /// The last pass rendered is drawn to the output viewport.
+            pub pass_count: u32,
+            /// The position of the split line to compare the output with the original image,
+            /// as a fraction of the width of the viewport. Default is 0.
+            ///
+            /// If greater than zero, the original image is drawn to the left of the split line,
+            /// and the output of the last pass rendered to the right of it.
+            pub compare_split: f32,
+            /// The width of the comparison split line, in pixels. Default is 1.
+            pub compare_line_width: f32,
+        }
+
+        impl $ty {
+            /// Get the range of passes to render, out of the number of passes enabled.
+            ///
+            /// The range always contains at least one pass if any passes are enabled.
+            pub fn pass_range(&self, passes_enabled: usize) -> ::std::ops::Range<usize> {
+                let first_pass = self.first_pass as usize;
+                let end = if self.pass_count == 0 {
+                    passes_enabled
+                } else {
+                    ::std::cmp::min(
+                        passes_enabled,
+                        first_pass.saturating_add(self.pass_count as usize),
+                    )
+                };
+                ::std::cmp::min(first_pass, end.saturating_sub(1))..end
+            }
+
+            /// Whether or not the output should be compared with the original image.
+            pub fn compare_enabled(&self) -> bool {
+                self.compare_split > 0.0
+            }
         }
 
         impl Default for $ty {
@@ -478,6 +666,19 @@ macro_rules! impl_default_frame_options {
                     rotation: 0,
                     total_subframes: 1,
                     current_subframe: 1,
+                    aspect_ratio: 0.0,
+                    original_fps: 60.0,
+                    frametime_delta: 0,
+                    core_fps: 60.0,
+                    enable_hdr: false,
+                    paper_white_nits: 200.0,
+                    max_nits: 1000.0,
+                    expand_gamut: false,
+                    inverse_tonemap: false,
+                    first_pass: 0,
+                    pass_count: 0,
+                    compare_split: 0.0,
+                    compare_line_width: 1.0,
                 }
             }
         }
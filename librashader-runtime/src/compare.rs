@@ -0,0 +1,152 @@
+use crate::filter_pass::FilterPassMeta;
+use crate::scaling::{scale_2d, ScaleFramebuffer};
+use librashader_common::map::{FastIndexMap, ShortString};
+use librashader_common::{FilterMode, Size, WrapMode};
+use librashader_pack::PassResource;
+use librashader_preprocess::{PreprocessError, ShaderParameter, ShaderSource};
+use librashader_presets::{PassMeta, ScaleType};
+
+const COMPARE_SOURCE: &str = include_str!("../shader/compare.slang");
+
+const COMPARE_SPLIT: &str = "librashader_compare_split";
+const COMPARE_LINE_WIDTH: &str = "librashader_compare_line_width";
+
+/// Create the built-in comparison pass.
+///
+/// The comparison pass draws the unfiltered `Original` image to the left of a vertical split
+/// line, and its `Source` to the right of it. The position and width of the split line are
+/// members of the push constant block of the pass, which are set by [`set_compare_uniforms`]
+/// from the `compare_split` and `compare_line_width` frame options. They are not semantics, so
+/// they can not be bound by the passes of a preset.
+///
+/// Runtimes compile the comparison pass once when the filter chain is created, and draw it after
+/// the last pass rendered for frames where comparison is enabled.
+pub fn compare_pass() -> Result<PassResource, PreprocessError> {
+    let source = ShaderSource::parse(COMPARE_SOURCE)?;
+    Ok(PassResource {
+        data: source,
+        meta: PassMeta {
+            id: 0,
+            alias: None,
+            filter: FilterMode::Nearest,
+            wrap_mode: WrapMode::ClampToEdge,
+            frame_count_mod: 0,
+            srgb_framebuffer: false,
+            float_framebuffer: false,
+            mipmap_input: false,
+            scaling: scale_2d(ScaleType::Input, false),
+        },
+    })
+}
+
+/// Set the position and width of the split line of the comparison pass before it is drawn.
+///
+/// `parameters` are the parameters of the source of the comparison pass. They are bound with
+/// their initial value, since their names are reserved for the comparison pass and are never
+/// declared by the passes of a preset.
+pub fn set_compare_uniforms(
+    parameters: &mut FastIndexMap<ShortString, ShaderParameter>,
+    split: f32,
+    line_width: f32,
+) {
+    for (id, value) in [(COMPARE_SPLIT, split), (COMPARE_LINE_WIDTH, line_width)] {
+        if let Some(parameter) = parameters.get_mut(id) {
+            parameter.initial = value;
+        }
+    }
+}
+
+/// Compile the built-in comparison pass with the function used to compile the passes of a preset.
+pub fn compile_compare_pass<P, E>(
+    compile: impl FnOnce(Vec<PassResource>) -> Result<P, E>,
+) -> Result<P::Item, E>
+where
+    P: IntoIterator,
+    E: From<PreprocessError>,
+{
+    let passes = compile(vec![compare_pass()?])?;
+    Ok(passes
+        .into_iter()
+        .next()
+        .expect("the comparison pass should compile to exactly one pass"))
+}
+
+/// Scale the framebuffer of the last pass rendered to the size of the viewport, so that it can
+/// be drawn by the comparison pass without resampling.
+pub fn scale_compare_source<T, F, P>(
+    framebuffer: &mut F,
+    pass: &P,
+    viewport_size: Size<u32>,
+    context: &F::Context,
+) -> Result<Size<u32>, F::Error>
+where
+    F: ScaleFramebuffer<T>,
+    P: FilterPassMeta,
+{
+    framebuffer.scale(
+        scale_2d(ScaleType::Viewport, true),
+        pass.get_format(),
+        &viewport_size,
+        &viewport_size,
+        &viewport_size,
+        false,
+        context,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use crate::compare::{compare_pass, set_compare_uniforms, COMPARE_LINE_WIDTH, COMPARE_SPLIT};
+    use librashader_reflect::back::targets::SPIRV;
+    use librashader_reflect::back::{CompileShader, FromCompilation};
+    use librashader_reflect::front::SpirvCompilation;
+    use librashader_reflect::reflect::cross::SpirvCross;
+    use librashader_reflect::reflect::semantics::{
+        ShaderSemantics, TextureSemantics, UniformSemantic, UniqueSemantics,
+    };
+    use librashader_reflect::reflect::ReflectShader;
+
+    #[test]
+    pub fn compiles_compare_pass() {
+        let pass = compare_pass().unwrap();
+        let parameters: Vec<_> = pass.data.parameters.keys().map(|id| id.as_str()).collect();
+        assert_eq!(parameters, [COMPARE_SPLIT, COMPARE_LINE_WIDTH]);
+
+        let compilation = SpirvCompilation::try_from(&pass.data).unwrap();
+        let mut compilation =
+            <SPIRV as FromCompilation<SpirvCompilation, SpirvCross>>::from_compilation(compilation)
+                .unwrap();
+
+        let semantics = ShaderSemantics {
+            uniform_semantics: pass
+                .data
+                .parameters
+                .keys()
+                .map(|id| {
+                    (
+                        id.clone(),
+                        UniformSemantic::Unique(UniqueSemantics::FloatParameter.semantics()),
+                    )
+                })
+                .collect(),
+            texture_semantics: Default::default(),
+        };
+        let reflection = compilation.reflect(0, &semantics).unwrap();
+        assert!(reflection
+            .meta
+            .texture_meta
+            .contains_key(&TextureSemantics::Original.semantics(0)));
+        for id in [COMPARE_SPLIT, COMPARE_LINE_WIDTH] {
+            assert!(reflection.meta.parameter_meta.contains_key(id));
+        }
+        compilation.compile(None).unwrap();
+    }
+
+    #[test]
+    pub fn sets_compare_uniforms() {
+        let mut pass = compare_pass().unwrap();
+        set_compare_uniforms(&mut pass.data.parameters, 0.25, 3.0);
+        assert_eq!(pass.data.parameters[COMPARE_SPLIT].initial, 0.25);
+        assert_eq!(pass.data.parameters[COMPARE_LINE_WIDTH].initial, 3.0);
+    }
+}
@@ -0,0 +1,80 @@
+/// Trait for filter chains that allow access to the intermediate framebuffers of their passes.
+///
+/// The images returned are the native handles of the framebuffers that were bound during the
+/// last call to `frame`. They are owned by the filter chain and are only valid until the next
+/// call to `frame`, or until the filter chain is reloaded or dropped. The contents of the images
+/// must be synchronized with any work submitted by `frame` before they are read.
+pub trait FilterChainDebug {
+    /// The native handle to a framebuffer image.
+    type Image;
+
+    /// Get the number of passes in the filter chain, including disabled passes.
+    fn pass_count(&self) -> usize;
+
+    /// Get the alias of the pass at the given index, if it has one.
+    fn pass_alias(&self, index: usize) -> Option<&str>;
+
+    /// Get the image the pass at the given index rendered to during the last frame.
+    ///
+    /// The final pass renders directly to the output viewport, so this returns `None` for the
+    /// final pass, for passes that were disabled during the last frame, and before the first frame.
+    /// When a range of passes is rendered, passes outside the range keep the image they rendered
+    /// to during the last frame they were in the range.
+    fn pass_output(&self, index: usize) -> Option<Self::Image>;
+
+    /// Get the image bound as `PassFeedback` for the pass at the given index during the last frame.
+    ///
+    /// This is the output of the pass from the frame before the last frame.
+    fn pass_feedback(&self, index: usize) -> Option<Self::Image>;
+
+    /// Get the image bound as `OriginalHistory{index}` during the last frame.
+    ///
+    /// `OriginalHistory0` is the input image of the frame, so this always returns `None` for index `0`.
+    fn history(&self, index: usize) -> Option<Self::Image>;
+}
+
+#[macro_export]
+macro_rules! impl_filter_chain_debug {
+    ($ty:ty, $image:ty, |$texture:ident| $handle:expr) => {
+        impl ::librashader_runtime::debug::FilterChainDebug for $ty {
+            type Image = $image;
+
+            fn pass_count(&self) -> usize {
+                self.passes.len()
+            }
+
+            fn pass_alias(&self, index: usize) -> Option<&str> {
+                self.passes
+                    .get(index)?
+                    .meta
+                    .alias
+                    .as_ref()
+                    .map(|alias| alias.as_str())
+            }
+
+            fn pass_output(&self, index: usize) -> Option<$image> {
+                if index + 1 >= self.passes.len()
+                    || index + 1 >= self.common.config.passes_enabled()
+                {
+                    return None;
+                }
+                let $texture = self.common.output_textures.get(index)?.as_ref()?;
+                Some($handle)
+            }
+
+            fn pass_feedback(&self, index: usize) -> Option<$image> {
+                let $texture = self.common.feedback_textures.get(index)?.as_ref()?;
+                Some($handle)
+            }
+
+            fn history(&self, index: usize) -> Option<$image> {
+                let $texture = self
+                    .common
+                    .history_textures
+                    .get(index.checked_sub(1)?)?
+                    .as_ref()?;
+                Some($handle)
+            }
+        }
+    };
+}
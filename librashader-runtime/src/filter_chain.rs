@@ -0,0 +1,73 @@
+use crate::parameters::FilterChainParameters;
+use librashader_common::Viewport;
+use librashader_pack::ShaderPresetPack;
+use librashader_presets::ShaderPreset;
+use std::path::Path;
+
+/// Trait for the filter chains of every runtime, to load and drive a filter chain without
+/// depending on a specific runtime.
+///
+/// The associated types mirror the arguments of the inherent methods of each runtime, which
+/// the methods of this trait call into. Runtime specific functionality such as deferred loading
+/// is only available through the inherent methods.
+pub trait FilterChain: FilterChainParameters + Sized {
+    /// The device, context, or queue a filter chain is created for.
+    type Device<'a>;
+    /// The input image of a frame.
+    type InputImage<'a>;
+    /// The output view a frame is drawn to.
+    type OutputView<'a>;
+    /// The command buffer, encoder, or device context a frame is recorded to.
+    ///
+    /// This is `()` for runtimes that record frames to the device directly.
+    type CommandContext<'a>;
+    /// Options for filter chain creation.
+    type FilterChainOptions: Default;
+    /// Options for each frame.
+    type FrameOptions: Default;
+    /// The error type of the runtime.
+    type Error: std::error::Error;
+
+    /// Load a filter chain from a pre-parsed and loaded `ShaderPresetPack`.
+    ///
+    /// # Safety
+    /// The safety requirements of the inherent `load_from_pack` of the runtime apply.
+    unsafe fn load_from_pack(
+        preset: ShaderPresetPack,
+        device: Self::Device<'_>,
+        options: Option<&Self::FilterChainOptions>,
+    ) -> Result<Self, Self::Error>;
+
+    /// Load a filter chain from a pre-parsed `ShaderPreset`.
+    ///
+    /// # Safety
+    /// The safety requirements of the inherent `load_from_preset` of the runtime apply.
+    unsafe fn load_from_preset(
+        preset: ShaderPreset,
+        device: Self::Device<'_>,
+        options: Option<&Self::FilterChainOptions>,
+    ) -> Result<Self, Self::Error>;
+
+    /// Load the shader preset at the given path into a filter chain.
+    ///
+    /// # Safety
+    /// The safety requirements of the inherent `load_from_path` of the runtime apply.
+    unsafe fn load_from_path(
+        path: impl AsRef<Path>,
+        device: Self::Device<'_>,
+        options: Option<&Self::FilterChainOptions>,
+    ) -> Result<Self, Self::Error>;
+
+    /// Record the commands to process a frame with the input image to the output viewport.
+    ///
+    /// # Safety
+    /// The safety requirements of the inherent `frame` of the runtime apply.
+    unsafe fn frame<'a>(
+        &mut self,
+        input: Self::InputImage<'a>,
+        viewport: &Viewport<Self::OutputView<'a>>,
+        cmd: Self::CommandContext<'a>,
+        frame_count: usize,
+        options: Option<&Self::FrameOptions>,
+    ) -> Result<(), Self::Error>;
+}
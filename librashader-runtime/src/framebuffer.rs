@@ -2,6 +2,9 @@ use crate::binding::{BindingRequirements, BindingUtil};
 use librashader_reflect::reflect::semantics::BindingMeta;
 use std::collections::VecDeque;
 
+/// Owned output framebuffers, and the views of them bound as inputs, indexed by pass.
+pub type OutputFramebuffers<F, I> = (Box<[F]>, Box<[I]>);
+
 /// Helper to initialize framebuffers in a graphics API agnostic way.
 pub struct FramebufferInit<'a, F, I, E> {
     owned_generator: &'a dyn Fn() -> Result<F, E>,
@@ -47,6 +50,50 @@ impl<'a, F, I, E> FramebufferInit<'a, F, I, E> {
         )
     }
 
+    /// Reuse existing history framebuffers and create views.
+    ///
+    /// The most recent history framebuffers are kept. Framebuffers that are no longer
+    /// required are passed to `dispose`.
+    pub fn reuse_history(
+        &self,
+        framebuffers: VecDeque<F>,
+        dispose: impl FnMut(F),
+    ) -> Result<(VecDeque<F>, Box<[I]>), E> {
+        let required_images = if self.requirements.required_history <= 1 {
+            0
+        } else {
+            self.requirements.required_history
+        };
+
+        reuse_framebuffers(
+            framebuffers,
+            required_images,
+            self.owned_generator,
+            self.input_generator,
+            dispose,
+        )
+    }
+
+    /// Reuse existing output framebuffers and create views.
+    ///
+    /// Framebuffers are kept by pass index. Framebuffers that are no longer
+    /// required are passed to `dispose`.
+    pub fn reuse_output_framebuffers(
+        &self,
+        framebuffers: Box<[F]>,
+        dispose: impl FnMut(F),
+    ) -> Result<OutputFramebuffers<F, I>, E> {
+        let (framebuffers, textures) = reuse_framebuffers(
+            framebuffers.into_vec().into(),
+            self.filters_count,
+            self.owned_generator,
+            self.input_generator,
+            dispose,
+        )?;
+
+        Ok((Vec::from(framebuffers).into_boxed_slice(), textures))
+    }
+
     /// Get if the final pass is used as feedback.
     pub const fn uses_final_pass_as_feedback(&self) -> bool {
         self.requirements.uses_final_pass_as_feedback
@@ -96,3 +143,26 @@ fn init_output_framebuffers<F, I, E>(
         output_textures.into_boxed_slice(),
     ))
 }
+
+fn reuse_framebuffers<F, I, E>(
+    mut framebuffers: VecDeque<F>,
+    len: usize,
+    owned_generator: impl Fn() -> Result<F, E>,
+    input_generator: impl Fn() -> I,
+    mut dispose: impl FnMut(F),
+) -> Result<(VecDeque<F>, Box<[I]>), E> {
+    while framebuffers.len() > len {
+        if let Some(framebuffer) = framebuffers.pop_back() {
+            dispose(framebuffer);
+        }
+    }
+
+    while framebuffers.len() < len {
+        framebuffers.push_back(owned_generator()?);
+    }
+
+    let mut textures = Vec::new();
+    textures.resize_with(len, input_generator);
+
+    Ok((framebuffers, textures.into_boxed_slice()))
+}
@@ -0,0 +1,130 @@
+use crate::scaling::scale_2d;
+use librashader_common::{FilterMode, ImageFormat, WrapMode};
+use librashader_pack::{PassResource, ShaderPresetPack};
+use librashader_preprocess::{PreprocessError, ShaderSource};
+use librashader_presets::{PassMeta, ScaleType};
+
+const HDR_OUTPUT_SOURCE: &str = include_str!("../shader/hdr_output.slang");
+
+/// The encoding of the output of the built-in HDR output pass.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum HdrOutputEncoding {
+    /// Linear scRGB with Rec. 709 primaries, where 1.0 corresponds to 80 nits.
+    ScRgb,
+    /// Rec. 2020 primaries encoded with the SMPTE ST 2084 (PQ) transfer function.
+    Hdr10,
+}
+
+impl HdrOutputEncoding {
+    /// The format of the output render target expected by this encoding.
+    pub const fn output_format(&self) -> ImageFormat {
+        match self {
+            HdrOutputEncoding::ScRgb => ImageFormat::R16G16B16A16Sfloat,
+            HdrOutputEncoding::Hdr10 => ImageFormat::A2B10G10R10UnormPack32,
+        }
+    }
+}
+
+/// Append the built-in HDR output pass to the shader preset.
+///
+/// The output pass converts the SDR output of the preset to the given HDR encoding, using the
+/// `PaperWhiteNits`, `MaxNits`, `ExpandGamut` and `InverseTonemap` values from the frame options.
+/// The filter chain should then be drawn to an output with the format returned by
+/// [`HdrOutputEncoding::output_format`].
+///
+/// The pass that was previously the final pass is rendered to a floating point framebuffer
+/// the size of the viewport, so that the output of the preset is otherwise unchanged.
+pub fn append_hdr_output_pass(
+    preset: &mut ShaderPresetPack,
+    encoding: HdrOutputEncoding,
+) -> Result<(), PreprocessError> {
+    let mut linear_input = false;
+    if let Some(last) = preset.passes.last_mut() {
+        // A pass that renders to an sRGB framebuffer writes linear values.
+        linear_input = last.meta.srgb_framebuffer || last.data.format == ImageFormat::R8G8B8A8Srgb;
+
+        last.meta.srgb_framebuffer = false;
+        last.meta.float_framebuffer = true;
+        last.meta.scaling = scale_2d(ScaleType::Viewport, true);
+    }
+
+    let mut defines = String::new();
+    if encoding == HdrOutputEncoding::Hdr10 {
+        defines.push_str("#define LIBRASHADER_HDR10\n");
+    }
+    if linear_input {
+        defines.push_str("#define LIBRASHADER_LINEAR_INPUT\n");
+    }
+
+    let (version, body) = HDR_OUTPUT_SOURCE
+        .split_once('\n')
+        .ok_or(PreprocessError::UnexpectedEof)?;
+    let source = ShaderSource::parse(&format!("{version}\n{defines}{body}"))?;
+
+    preset.passes.push(PassResource {
+        data: source,
+        meta: PassMeta {
+            id: preset.passes.len() as i32,
+            alias: None,
+            filter: FilterMode::Nearest,
+            wrap_mode: WrapMode::ClampToEdge,
+            frame_count_mod: 0,
+            srgb_framebuffer: false,
+            float_framebuffer: false,
+            mipmap_input: false,
+            scaling: scale_2d(ScaleType::Input, false),
+        },
+    });
+    preset.pass_count += 1;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::hdr::{append_hdr_output_pass, HdrOutputEncoding};
+    use librashader_pack::ShaderPresetPack;
+    use librashader_reflect::back::targets::SPIRV;
+    use librashader_reflect::back::{CompileShader, FromCompilation};
+    use librashader_reflect::front::SpirvCompilation;
+    use librashader_reflect::reflect::cross::SpirvCross;
+    use librashader_reflect::reflect::semantics::{ShaderSemantics, UniqueSemantics};
+    use librashader_reflect::reflect::ReflectShader;
+
+    #[test]
+    pub fn compiles_hdr_output_pass() {
+        for encoding in [HdrOutputEncoding::ScRgb, HdrOutputEncoding::Hdr10] {
+            let mut preset = ShaderPresetPack {
+                pass_count: 0,
+                passes: vec![],
+                textures: vec![],
+                parameters: vec![],
+            };
+            append_hdr_output_pass(&mut preset, encoding).unwrap();
+            assert_eq!(preset.pass_count, 1);
+
+            let compilation = SpirvCompilation::try_from(&preset.passes[0].data).unwrap();
+            let mut compilation =
+                <SPIRV as FromCompilation<SpirvCompilation, SpirvCross>>::from_compilation(
+                    compilation,
+                )
+                .unwrap();
+
+            let semantics = ShaderSemantics {
+                uniform_semantics: Default::default(),
+                texture_semantics: Default::default(),
+            };
+            let reflection = compilation.reflect(0, &semantics).unwrap();
+            for semantic in [
+                UniqueSemantics::MVP,
+                UniqueSemantics::PaperWhiteNits,
+                UniqueSemantics::MaxNits,
+                UniqueSemantics::ExpandGamut,
+                UniqueSemantics::InverseTonemap,
+            ] {
+                assert!(reflection.meta.unique_meta.contains_key(&semantic));
+            }
+            compilation.compile(None).unwrap();
+        }
+    }
+}
@@ -0,0 +1,323 @@
+//! Loading filter chains on a worker pool with progress reporting and cancellation.
+//!
+//! Loading a filter chain preprocesses and compiles every pass of a shader preset before any
+//! device objects are created, which can take a considerable amount of time for presets with
+//! many passes. A [`LoadJob`] does this work on the rayon thread pool instead of the calling
+//! thread. The sources of each pass are preprocessed in parallel, after which the filter chain
+//! is created on a worker thread while the number of compiled passes is reported as each pass
+//! finishes compiling.
+//!
+//! Because the filter chain is created on a worker thread, it should be created with the deferred
+//! loading functions of a runtime such as `load_from_pack_deferred_with_observer`, which record GPU
+//! commands for the caller to submit instead of using the device queue.
+//!
+//! OpenGL, Direct3D 9, and Metal can not be loaded with a [`LoadJob`]. An OpenGL filter chain
+//! must be created on the thread its context is current on, Direct3D 9 has no deferred loading,
+//! as its devices are not free-threaded by default, and Metal filter chains are not [`Send`].
+use image::ImageError;
+use librashader_pack::{LoadableResource, ShaderPresetPack};
+use librashader_preprocess::{PreprocessError, ShaderSource};
+use librashader_presets::{ShaderPreset, TextureMeta};
+use librashader_reflect::reflect::presets::PassObserver;
+use parking_lot::{Condvar, Mutex};
+use rayon::prelude::*;
+use std::any::Any;
+use std::fmt::{Display, Formatter};
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// A stage of loading a filter chain.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum LoadStage {
+    /// The source of a pass was loaded and preprocessed.
+    Preprocess,
+    /// A pass was compiled and reflected.
+    Compile,
+}
+
+/// The progress of a [`LoadJob`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct LoadProgress {
+    /// The stage that was reached.
+    pub stage: LoadStage,
+    /// The number of passes that have completed the stage so far.
+    ///
+    /// Passes complete each stage in parallel and in no particular order, so this counts up
+    /// from 1 to `pass_count` rather than naming a pass.
+    pub completed: usize,
+    /// The number of passes in the shader preset.
+    pub pass_count: usize,
+}
+
+/// The error returned when a [`LoadJob`] does not complete.
+#[derive(Debug)]
+pub enum LoadJobError<E> {
+    /// The job was cancelled before the filter chain was created.
+    Cancelled,
+    /// The job panicked with the given payload.
+    Panicked(Box<dyn Any + Send>),
+    /// The filter chain could not be loaded.
+    Load(E),
+}
+
+impl<E: Display> Display for LoadJobError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadJobError::Cancelled => write!(f, "loading the filter chain was cancelled"),
+            LoadJobError::Panicked(_) => write!(f, "loading the filter chain panicked"),
+            LoadJobError::Load(error) => Display::fmt(error, f),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for LoadJobError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LoadJobError::Load(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+struct JobState<T, E> {
+    result: Mutex<Option<Result<T, LoadJobError<E>>>>,
+    finished: Condvar,
+}
+
+/// A filter chain that is being loaded on the rayon thread pool.
+///
+/// Dropping a `LoadJob` before it has finished cancels it.
+pub struct LoadJob<T, E> {
+    cancelled: Arc<AtomicBool>,
+    state: Arc<JobState<T, E>>,
+}
+
+impl<T, E> LoadJob<T, E>
+where
+    T: Send + 'static,
+    E: From<PreprocessError> + From<ImageError> + Send + 'static,
+{
+    /// Load a shader preset on the rayon thread pool.
+    ///
+    /// The sources of the passes of the preset are preprocessed in parallel, then `create` is
+    /// called on a worker thread with the loaded preset to create the filter chain. `progress`
+    /// is called from the worker threads as each pass is preprocessed and compiled, and must
+    /// return quickly.
+    ///
+    /// `create` is given a [`PassObserver`] that should be passed on to the runtime, such as to
+    /// `load_from_pack_deferred_with_observer`. Compilation progress is reported, and the job can
+    /// be cancelled while compiling, only through this observer.
+    pub fn spawn(
+        preset: ShaderPreset,
+        progress: impl Fn(LoadProgress) + Send + Sync + 'static,
+        create: impl FnOnce(ShaderPresetPack, PassObserver<'_>) -> Result<T, E> + Send + 'static,
+    ) -> Self {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let state = Arc::new(JobState {
+            result: Mutex::new(None),
+            finished: Condvar::new(),
+        });
+
+        let job_cancelled = Arc::clone(&cancelled);
+        let job_state = Arc::clone(&state);
+        rayon::spawn(move || {
+            let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+                run(job_cancelled, preset, progress, create)
+            }))
+            .unwrap_or_else(|panic| Err(LoadJobError::Panicked(panic)));
+
+            *job_state.result.lock() = Some(result);
+            job_state.finished.notify_all();
+        });
+
+        LoadJob { cancelled, state }
+    }
+}
+
+impl<T, E> LoadJob<T, E> {
+    /// Request that the job be cancelled.
+    ///
+    /// The job stops after the pass it is currently preprocessing or compiling. A filter chain
+    /// that is already being created will still be created, then dropped.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+    }
+
+    /// Whether the job has finished, either by creating the filter chain or with an error.
+    pub fn is_finished(&self) -> bool {
+        self.state.result.lock().is_some()
+    }
+
+    /// Block until the job has finished, and return the created filter chain.
+    pub fn wait(self) -> Result<T, LoadJobError<E>> {
+        let mut result = self.state.result.lock();
+        loop {
+            if let Some(result) = result.take() {
+                return result;
+            }
+            self.state.finished.wait(&mut result);
+        }
+    }
+}
+
+impl<T, E> Drop for LoadJob<T, E> {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+fn run<T, E>(
+    cancelled: Arc<AtomicBool>,
+    preset: ShaderPreset,
+    progress: impl Fn(LoadProgress) + Sync + 'static,
+    create: impl FnOnce(ShaderPresetPack, PassObserver<'_>) -> Result<T, E>,
+) -> Result<T, LoadJobError<E>>
+where
+    E: From<PreprocessError> + From<ImageError> + Send,
+{
+    let pass_count = preset.passes.len();
+    let check_cancelled = || {
+        if cancelled.load(Ordering::Acquire) {
+            Err(LoadJobError::Cancelled)
+        } else {
+            Ok(())
+        }
+    };
+
+    let preprocessed = AtomicUsize::new(0);
+    let sources = preset
+        .passes
+        .par_iter()
+        .map(|pass| {
+            check_cancelled()?;
+            let source = ShaderSource::load(&pass.path)
+                .map_err(|error| LoadJobError::Load(E::from(error)))?;
+            progress(LoadProgress {
+                stage: LoadStage::Preprocess,
+                completed: preprocessed.fetch_add(1, Ordering::AcqRel) + 1,
+                pass_count,
+            });
+            Ok(source)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let textures = preset
+        .textures
+        .par_iter()
+        .map(|texture| {
+            check_cancelled()?;
+            TextureMeta::load(&texture.path).map_err(|error| LoadJobError::Load(E::from(error)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    check_cancelled()?;
+
+    let mut sources = sources.into_iter();
+    let mut textures = textures.into_iter();
+    let pack = ShaderPresetPack::load_from_preset_with::<LoadJobError<E>>(
+        preset,
+        |_, _| Ok(sources.next().expect("every pass was preprocessed")),
+        |_, _| Ok(textures.next().expect("every texture was loaded")),
+    )?;
+
+    let observer = |completed, pass_count| {
+        progress(LoadProgress {
+            stage: LoadStage::Compile,
+            completed,
+            pass_count,
+        });
+        !cancelled.load(Ordering::Acquire)
+    };
+
+    match create(pack, &observer) {
+        Ok(filter_chain) => Ok(filter_chain),
+        Err(_) if cancelled.load(Ordering::Acquire) => Err(LoadJobError::Cancelled),
+        Err(error) => Err(LoadJobError::Load(error)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::job::{LoadJob, LoadJobError, LoadProgress, LoadStage};
+    use librashader_presets::ShaderPreset;
+    use librashader_reflect::back::targets::SPIRV;
+    use librashader_reflect::front::SpirvCompilation;
+    use librashader_reflect::reflect::cross::SpirvCross;
+    use librashader_reflect::reflect::presets::CompilePresetTarget;
+    use parking_lot::Mutex;
+    use std::error::Error;
+    use std::sync::{mpsc, Arc};
+    use std::time::Duration;
+
+    type CompileError = Box<dyn Error + Send + Sync>;
+
+    fn preset() -> ShaderPreset {
+        ShaderPreset::try_parse("../test/reflect/reflect.slangp").unwrap()
+    }
+
+    fn spawn(
+        progress: impl Fn(LoadProgress) + Send + Sync + 'static,
+        wait: Option<mpsc::Receiver<()>>,
+    ) -> LoadJob<usize, CompileError> {
+        LoadJob::spawn(preset(), progress, move |pack, observer| {
+            if let Some(wait) = wait {
+                let _ = wait.recv();
+            }
+            let (passes, _) =
+                SPIRV::compile_preset_passes_with_observer::<
+                    SpirvCompilation,
+                    SpirvCross,
+                    CompileError,
+                >(pack.passes, pack.textures.iter().map(|t| &t.meta), observer)?;
+            Ok(passes.len())
+        })
+    }
+
+    #[test]
+    pub fn reports_progress_in_order() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let job = spawn(
+            {
+                let events = Arc::clone(&events);
+                move |progress| events.lock().push(progress)
+            },
+            None,
+        );
+        assert_eq!(job.wait().unwrap(), 2);
+
+        let events = events.lock();
+        let (preprocess, compile) = events.split_at(2);
+        assert!(preprocess
+            .iter()
+            .all(|progress| progress.stage == LoadStage::Preprocess && progress.pass_count == 2));
+        let mut preprocessed = preprocess.iter().map(|p| p.completed).collect::<Vec<_>>();
+        preprocessed.sort();
+        assert_eq!(preprocessed, [1, 2]);
+
+        assert!(compile
+            .iter()
+            .all(|progress| progress.stage == LoadStage::Compile && progress.pass_count == 2));
+        let mut compiled = compile.iter().map(|p| p.completed).collect::<Vec<_>>();
+        compiled.sort();
+        assert_eq!(compiled, [1, 2]);
+    }
+
+    #[test]
+    pub fn cancel_returns_cancelled() {
+        let (send, wait) = mpsc::channel();
+        let job = spawn(|_| {}, Some(wait));
+        job.cancel();
+        let _ = send.send(());
+        assert!(matches!(job.wait(), Err(LoadJobError::Cancelled)));
+    }
+
+    #[test]
+    pub fn wait_after_finished() {
+        let job = spawn(|_| {}, None);
+        while !job.is_finished() {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(job.wait().unwrap(), 2);
+    }
+}
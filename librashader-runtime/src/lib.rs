@@ -25,14 +25,43 @@ pub mod ringbuffer;
 /// Generic implementation of semantics binding.
 pub mod binding;
 
+/// Built-in HDR output pass.
+pub mod hdr;
+
+/// Built-in pass to compare the output of a preset with the original image.
+pub mod compare;
+
 /// VBO helper utilities.
 pub mod quad;
 
 /// Filter pass helpers and common traits.
 pub mod filter_pass;
 
+/// Runtime-agnostic filter chain trait.
+pub mod filter_chain;
+
 /// Common types for render targets.
 pub mod render_target;
 
 /// Helpers for handling framebuffers.
 pub mod framebuffer;
+
+/// Helpers for reloading the passes of a filter chain.
+pub mod reload;
+
+/// Access to the intermediate framebuffers of a filter chain for debugging.
+pub mod debug;
+
+/// GPU timing of the passes of a filter chain.
+pub mod profiling;
+
+/// Saving and restoring the temporal state of a filter chain.
+pub mod state;
+
+/// File watching to reload shader presets during development.
+#[cfg(all(feature = "watch", not(target_arch = "wasm32")))]
+pub mod watch;
+
+/// Loading filter chains on a worker pool with progress reporting and cancellation.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod job;
@@ -1,7 +1,9 @@
-use arc_swap::ArcSwap;
-use librashader_common::map::{FastHashMap, ShortString};
+use arc_swap::{ArcSwap, ArcSwapOption};
+use librashader_common::map::{FastHashMap, FastIndexMap, ShortString};
+use librashader_preprocess::ShaderSource;
 use librashader_presets::ParameterMeta;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use parking_lot::Mutex;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 
 /// Trait for filter chains that allow runtime reflection of shader parameters.
@@ -10,6 +12,54 @@ pub trait FilterChainParameters {
     fn parameters(&self) -> &RuntimeParameters;
 }
 
+/// Metadata of a shader parameter declared by the passes of a filter chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuntimeParameter {
+    /// The name of the parameter.
+    pub id: ShortString,
+    /// The description of the parameter.
+    pub description: String,
+    /// The initial value of the parameter declared in the shader source.
+    pub initial: f32,
+    /// The value of the parameter when the filter chain was loaded.
+    ///
+    /// This is the value set by the shader preset if it overrides the parameter,
+    /// and the initial value otherwise.
+    pub default: f32,
+    /// The minimum value that the parameter can be set to.
+    pub minimum: f32,
+    /// The maximum value that the parameter can be set to.
+    pub maximum: f32,
+    /// The step by which this parameter can be incremented or decremented.
+    pub step: f32,
+    /// The indices of the passes that declare the parameter.
+    pub passes: Vec<usize>,
+}
+
+impl RuntimeParameter {
+    /// Clamp a value to the range of the parameter, and snap it to the nearest step
+    /// from the minimum value.
+    pub fn clamp(&self, value: f32) -> f32 {
+        let value = self.clamp_to_range(value);
+        if self.step <= 0.0 || self.minimum > self.maximum {
+            return value;
+        }
+
+        let steps = ((value - self.minimum) / self.step).round();
+        self.clamp_to_range(self.minimum + steps * self.step)
+    }
+
+    /// Clamp a value to the range of the parameter, without snapping it to a step.
+    pub fn clamp_to_range(&self, value: f32) -> f32 {
+        // Some shaders declare their range backwards, which would make f32::clamp panic.
+        if self.minimum > self.maximum {
+            return value;
+        }
+
+        value.clamp(self.minimum, self.maximum)
+    }
+}
+
 /// Runtime reflection of shader parameters for filter chains.
 ///
 /// All operations on runtime parameters are atomic and can be done on
@@ -17,20 +67,71 @@ pub trait FilterChainParameters {
 pub struct RuntimeParameters {
     passes_enabled: AtomicUsize,
     pub(crate) parameters: ArcSwap<FastHashMap<ShortString, f32>>,
+    defaults: Arc<FastHashMap<ShortString, f32>>,
+    meta: FastIndexMap<ShortString, RuntimeParameter>,
+    generation: AtomicU64,
+    changes: Mutex<FastHashMap<ShortString, u64>>,
+    callback: Mutex<Option<ChangeCallback>>,
+    pub(crate) automation: ArcSwapOption<ParameterAutomation>,
+    automation_frame: AtomicUsize,
 }
 
+type ChangeCallback = Arc<dyn Fn(u64, &[ShortString]) + Send + Sync>;
+
 impl RuntimeParameters {
-    /// Create a new instance of runtime parameters from a `Vec` of
-    /// shader parameters from a [`ShaderPreset`](librashader_presets::ShaderPreset).
-    pub fn new(passes_enabled: usize, parameters: Vec<ParameterMeta>) -> Self {
+    /// Create a new instance of runtime parameters from the parameter values of a
+    /// [`ShaderPreset`](librashader_presets::ShaderPreset), and the shader sources of its passes.
+    ///
+    /// Parameters declared by more than one pass take their metadata from the first pass that
+    /// declares them.
+    pub fn new<'a>(
+        passes_enabled: usize,
+        parameters: Vec<ParameterMeta>,
+        sources: impl IntoIterator<Item = &'a ShaderSource>,
+    ) -> Self {
+        let mut meta: FastIndexMap<ShortString, RuntimeParameter> = FastIndexMap::default();
+        for (index, source) in sources.into_iter().enumerate() {
+            for parameter in source.parameters.values() {
+                let declared =
+                    meta.entry(parameter.id.clone())
+                        .or_insert_with(|| RuntimeParameter {
+                            id: parameter.id.clone(),
+                            description: parameter.description.clone(),
+                            initial: parameter.initial,
+                            default: parameter.initial,
+                            minimum: parameter.minimum,
+                            maximum: parameter.maximum,
+                            step: parameter.step,
+                            passes: Vec::new(),
+                        });
+                declared.passes.push(index);
+            }
+        }
+
+        let mut defaults: FastHashMap<ShortString, f32> = meta
+            .values()
+            .map(|param| (param.id.clone(), param.initial))
+            .collect();
+
+        // Preset values are kept as is even if they are out of range.
+        for param in parameters {
+            if let Some(declared) = meta.get_mut(&param.name) {
+                declared.default = param.value;
+            }
+            defaults.insert(param.name, param.value);
+        }
+
+        let defaults = Arc::new(defaults);
         RuntimeParameters {
             passes_enabled: AtomicUsize::new(passes_enabled),
-            parameters: ArcSwap::new(Arc::new(
-                parameters
-                    .into_iter()
-                    .map(|param| (param.name, param.value))
-                    .collect(),
-            )),
+            parameters: ArcSwap::new(Arc::clone(&defaults)),
+            defaults,
+            meta,
+            generation: AtomicU64::new(0),
+            changes: Mutex::new(FastHashMap::default()),
+            callback: Mutex::new(None),
+            automation: ArcSwapOption::empty(),
+            automation_frame: AtomicUsize::new(0),
         }
     }
 
@@ -39,30 +140,201 @@ impl RuntimeParameters {
         self.parameters.load().get::<str>(name.as_ref()).copied()
     }
 
-    /// Set a runtime parameter.
+    /// Get the metadata of a runtime parameter.
+    pub fn parameter_meta(&self, name: &str) -> Option<&RuntimeParameter> {
+        self.meta.get::<str>(name)
+    }
+
+    /// Iterate over the metadata and current value of the parameters declared by the passes of
+    /// the filter chain, in the order they are declared.
+    ///
+    /// The values are read once when the iterator is created.
+    pub fn iter(&self) -> impl Iterator<Item = (&RuntimeParameter, f32)> + '_ {
+        let values = self.parameters.load_full();
+        self.meta.values().map(move |param| {
+            let value = values.get(&param.id).copied().unwrap_or(param.default);
+            (param, value)
+        })
+    }
+
+    /// Set a runtime parameter, returning the previous value.
+    ///
+    /// The value is clamped to the range of the parameter and snapped to its step.
     ///
     /// This is a relatively slow operation as it will be synchronized across threads.
     /// If updating multiple parameters, see [`RuntimeParameters::update_parameters`].
     pub fn set_parameter_value(&self, name: &str, new_value: f32) -> Option<f32> {
-        let mut updated_map = FastHashMap::clone(&self.parameters.load());
-
-        if let Some(value) = updated_map.get_mut::<str>(name.as_ref()) {
+        self.commit(|updated_map| {
+            let value = updated_map.get_mut::<str>(name.as_ref())?;
             let old = *value;
-            *value = new_value;
-
-            self.parameters.store(Arc::new(updated_map));
-
+            *value = match self.meta.get::<str>(name) {
+                Some(meta) => meta.clamp(new_value),
+                None => new_value,
+            };
             Some(old)
-        } else {
-            None
-        }
+        })
     }
 
     /// Update multiple runtime parameters atomically through a function.
+    ///
+    /// The changed values are clamped to the range of each parameter and snapped to its step.
     pub fn update_parameters(&self, updater: impl FnOnce(&mut FastHashMap<ShortString, f32>)) {
-        let mut updated_map = FastHashMap::clone(&self.parameters.load());
-        updater(&mut updated_map);
+        self.commit(|updated_map| {
+            let current = self.parameters.load();
+            updater(updated_map);
+            for (name, value) in updated_map.iter_mut() {
+                if current.get(name) == Some(value) {
+                    continue;
+                }
+                if let Some(meta) = self.meta.get(name) {
+                    *value = meta.clamp(*value);
+                }
+            }
+        })
+    }
+
+    /// Reset every runtime parameter to the value it had when the filter chain was loaded.
+    pub fn reset_to_defaults(&self) {
+        self.commit(|updated_map| updated_map.clone_from(&self.defaults))
+    }
+
+    /// Get the generation of the runtime parameters.
+    ///
+    /// The generation starts at `0`, and increases by one every time a change to the value of
+    /// any parameter is stored.
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    /// Get the names of the parameters whose value changed after the given generation.
+    ///
+    /// Passing the result of an earlier call to [`RuntimeParameters::generation`] returns every
+    /// parameter that changed since then.
+    pub fn changed_since(&self, generation: u64) -> Vec<ShortString> {
+        self.changes
+            .lock()
+            .iter()
+            .filter(|(_, changed)| **changed > generation)
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Get the parameters whose value differs from the value they had when the filter chain
+    /// was loaded.
+    ///
+    /// Parameters declared by the passes of the filter chain are returned in the order they are
+    /// declared. The result can be written as the parameter overrides of a shader preset.
+    pub fn modified_parameters(&self) -> Vec<ParameterMeta> {
+        let values = self.parameters.load();
+        let declared = self.meta.keys();
+        let undeclared = values.keys().filter(|name| !self.meta.contains_key(*name));
+        declared
+            .chain(undeclared)
+            .filter_map(|name| {
+                let value = *values.get(name)?;
+                if self.defaults.get(name) == Some(&value) {
+                    return None;
+                }
+                Some(ParameterMeta {
+                    name: name.clone(),
+                    value,
+                })
+            })
+            .collect()
+    }
+
+    /// Set a callback that is called with the new generation and the names of the changed
+    /// parameters every time a change to the value of any parameter is stored.
+    ///
+    /// The callback is called on the thread that changed the parameters, after the change is
+    /// visible. It replaces any previously set callback, and is not kept when the filter chain
+    /// is reloaded with a new preset.
+    pub fn set_change_callback(
+        &self,
+        callback: impl Fn(u64, &[ShortString]) + Send + Sync + 'static,
+    ) {
+        *self.callback.lock() = Some(Arc::new(callback));
+    }
+
+    /// Remove the callback set with [`RuntimeParameters::set_change_callback`].
+    pub fn clear_change_callback(&self) {
+        *self.callback.lock() = None;
+    }
+
+    /// Set the keyframed curves that animate parameters over time, replacing any previous
+    /// automation.
+    ///
+    /// Automated parameters are evaluated at the frame count of each frame when the uniforms of
+    /// the frame are bound, and clamped to the range of the parameter. Their values set through
+    /// [`RuntimeParameters::set_parameter_value`] are ignored while they are automated.
+    pub fn set_automation(&self, automation: Option<ParameterAutomation>) {
+        self.automation.store(automation.map(Arc::new));
+    }
+
+    /// Get the keyframed curves that animate parameters over time.
+    pub fn automation(&self) -> Option<Arc<ParameterAutomation>> {
+        self.automation.load_full()
+    }
+
+    /// Set the frame count that parameter automation is evaluated at.
+    ///
+    /// This is called by the runtime at the start of every frame.
+    #[inline(always)]
+    pub fn set_automation_frame(&self, frame_count: usize) {
+        self.automation_frame.store(frame_count, Ordering::Relaxed);
+    }
+
+    /// Get the value of an automated parameter at the current frame, clamped to its range.
+    pub(crate) fn automated_value(
+        &self,
+        automation: &ParameterAutomation,
+        name: &str,
+    ) -> Option<f32> {
+        let frame_count = self.automation_frame.load(Ordering::Relaxed);
+        let value = automation.evaluate(name, frame_count)?;
+        Some(match self.meta.get::<str>(name) {
+            Some(meta) => meta.clamp_to_range(value),
+            None => value,
+        })
+    }
+
+    fn commit<R>(&self, update: impl FnOnce(&mut FastHashMap<ShortString, f32>) -> R) -> R {
+        // Writers are serialized so that concurrent updates are not lost.
+        let mut changes = self.changes.lock();
+        let current = self.parameters.load_full();
+        let mut updated_map = FastHashMap::clone(&current);
+        let result = update(&mut updated_map);
+
+        let changed: Vec<ShortString> = updated_map
+            .iter()
+            .filter(|(name, value)| current.get(*name) != Some(*value))
+            .map(|(name, _)| name.clone())
+            .chain(
+                current
+                    .keys()
+                    .filter(|name| !updated_map.contains_key(*name))
+                    .cloned(),
+            )
+            .collect();
+
+        if changed.is_empty() {
+            return result;
+        }
+
         self.parameters.store(Arc::new(updated_map));
+        let generation = self.generation.fetch_add(1, Ordering::AcqRel) + 1;
+        for name in &changed {
+            changes.insert(name.clone(), generation);
+        }
+        drop(changes);
+
+        // Take the callback out of the lock, so that it can change parameters itself.
+        let callback = self.callback.lock().clone();
+        if let Some(callback) = callback {
+            callback(generation, &changed);
+        }
+
+        result
     }
 
     /// Get a reference to the runtime parameters.
@@ -88,6 +360,100 @@ impl RuntimeParameters {
     }
 }
 
+/// How the value of an automated parameter changes from a keyframe to the next.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Interpolation {
+    /// Interpolate linearly between the values of the keyframes.
+    #[default]
+    Linear,
+    /// Hold the value of the keyframe until the next keyframe.
+    Step,
+    /// Interpolate along a smoothstep curve, easing out of and into the keyframes.
+    Smoothstep,
+}
+
+/// A keyframe of an automated parameter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Keyframe {
+    /// The frame count at which the parameter has the value of the keyframe.
+    pub frame: usize,
+    /// The value of the parameter at the keyframe.
+    pub value: f32,
+    /// How the value changes from this keyframe to the next.
+    pub interpolation: Interpolation,
+}
+
+/// Keyframed curves that animate shader parameters by frame count.
+///
+/// Before the first keyframe of a curve, the parameter has the value of the first keyframe,
+/// and after the last keyframe, it keeps the value of the last keyframe.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParameterAutomation {
+    curves: FastHashMap<ShortString, Vec<Keyframe>>,
+}
+
+impl ParameterAutomation {
+    /// Create an empty parameter automation.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a keyframe to the curve of a parameter, replacing any keyframe at the same frame.
+    pub fn add_keyframe(&mut self, name: &str, keyframe: Keyframe) {
+        let curve = self.curves.entry(ShortString::from(name)).or_default();
+        match curve.binary_search_by_key(&keyframe.frame, |k| k.frame) {
+            Ok(index) => curve[index] = keyframe,
+            Err(index) => curve.insert(index, keyframe),
+        }
+    }
+
+    /// Remove the curve of a parameter, returning its keyframes.
+    pub fn remove_parameter(&mut self, name: &str) -> Option<Vec<Keyframe>> {
+        self.curves.remove::<str>(name)
+    }
+
+    /// Get the keyframes of the curve of a parameter, ordered by frame.
+    pub fn keyframes(&self, name: &str) -> Option<&[Keyframe]> {
+        self.curves.get::<str>(name).map(Vec::as_slice)
+    }
+
+    /// Iterate over the names of the automated parameters.
+    pub fn parameters(&self) -> impl Iterator<Item = &str> {
+        self.curves.keys().map(ShortString::as_str)
+    }
+
+    /// Get the frame of the last keyframe of any curve.
+    pub fn last_frame(&self) -> Option<usize> {
+        self.curves
+            .values()
+            .filter_map(|curve| curve.last())
+            .map(|keyframe| keyframe.frame)
+            .max()
+    }
+
+    /// Evaluate the curve of a parameter at the given frame count.
+    ///
+    /// Returns `None` if the parameter is not automated.
+    pub fn evaluate(&self, name: &str, frame_count: usize) -> Option<f32> {
+        let curve = self.curves.get::<str>(name)?;
+        let next = curve.partition_point(|keyframe| keyframe.frame <= frame_count);
+        let Some(from) = next.checked_sub(1).map(|index| curve[index]) else {
+            return curve.first().map(|keyframe| keyframe.value);
+        };
+        let Some(to) = curve.get(next) else {
+            return Some(from.value);
+        };
+
+        let t = (frame_count - from.frame) as f32 / (to.frame - from.frame) as f32;
+        let t = match from.interpolation {
+            Interpolation::Linear => t,
+            Interpolation::Step => 0.0,
+            Interpolation::Smoothstep => t * t * (3.0 - 2.0 * t),
+        };
+        Some(from.value + (to.value - from.value) * t)
+    }
+}
+
 #[macro_export]
 macro_rules! impl_filter_chain_parameters {
     ($ty:ty) => {
@@ -98,3 +464,152 @@ macro_rules! impl_filter_chain_parameters {
         }
     };
 }
+
+#[cfg(test)]
+mod test {
+    use crate::parameters::{Interpolation, Keyframe, ParameterAutomation, RuntimeParameters};
+    use librashader_common::map::ShortString;
+    use librashader_preprocess::ShaderSource;
+    use librashader_presets::ParameterMeta;
+    use parking_lot::Mutex;
+    use std::sync::Arc;
+
+    fn source(parameters: &str) -> ShaderSource {
+        ShaderSource::parse(&format!(
+            "#version 450\n{parameters}\n#pragma stage vertex\nvoid main() {{}}\n#pragma stage fragment\nvoid main() {{}}\n"
+        ))
+        .unwrap()
+    }
+
+    fn parameters() -> RuntimeParameters {
+        let first = source(
+            "#pragma parameter ZOOM \"Zoom\" 1.0 0.5 2.0 0.25\n#pragma parameter ALPHA \"Alpha\" 0.5 0.0 1.0 0.1",
+        );
+        let second = source("#pragma parameter BETA \"Beta\" 2.0 0.0 4.0 1.0\n#pragma parameter ZOOM \"Zoom\" 1.0 0.5 2.0 0.25");
+        RuntimeParameters::new(
+            2,
+            vec![ParameterMeta {
+                name: "BETA".into(),
+                value: 3.0,
+            }],
+            [&first, &second],
+        )
+    }
+
+    #[test]
+    fn iterates_in_declaration_order() {
+        let parameters = parameters();
+        let declared: Vec<_> = parameters
+            .iter()
+            .map(|(meta, value)| (meta.id.as_str().to_string(), meta.passes.clone(), value))
+            .collect();
+        assert_eq!(
+            declared,
+            vec![
+                ("ZOOM".to_string(), vec![0, 1], 1.0),
+                ("ALPHA".to_string(), vec![0], 0.5),
+                ("BETA".to_string(), vec![1], 3.0),
+            ]
+        );
+        assert_eq!(parameters.parameter_meta("BETA").unwrap().initial, 2.0);
+        assert_eq!(parameters.parameter_meta("BETA").unwrap().default, 3.0);
+    }
+
+    #[test]
+    fn clamps_and_snaps_values() {
+        let parameters = parameters();
+        assert_eq!(parameters.set_parameter_value("ZOOM", 5.0), Some(1.0));
+        assert_eq!(parameters.parameter_value("ZOOM"), Some(2.0));
+        parameters.set_parameter_value("ZOOM", 0.9);
+        assert_eq!(parameters.parameter_value("ZOOM"), Some(1.0));
+        parameters.update_parameters(|values| {
+            values.insert("BETA".into(), -1.0);
+        });
+        assert_eq!(parameters.parameter_value("BETA"), Some(0.0));
+        assert_eq!(parameters.set_parameter_value("UNKNOWN", 1.0), None);
+    }
+
+    #[test]
+    fn resets_to_defaults() {
+        let parameters = parameters();
+        parameters.set_parameter_value("ALPHA", 1.0);
+        parameters.set_parameter_value("BETA", 0.0);
+        parameters.reset_to_defaults();
+        assert_eq!(parameters.parameter_value("ALPHA"), Some(0.5));
+        assert_eq!(parameters.parameter_value("BETA"), Some(3.0));
+    }
+
+    #[test]
+    fn tracks_changes() {
+        let parameters = parameters();
+        assert_eq!(parameters.generation(), 0);
+        assert!(parameters.modified_parameters().is_empty());
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let callback_seen = Arc::clone(&seen);
+        parameters.set_change_callback(move |generation, names| {
+            callback_seen.lock().push((generation, names.to_vec()));
+        });
+
+        parameters.set_parameter_value("ALPHA", 0.8);
+        let start = parameters.generation();
+        assert_eq!(start, 1);
+
+        // setting the same value is not a change
+        parameters.set_parameter_value("ALPHA", 0.8);
+        assert_eq!(parameters.generation(), 1);
+
+        parameters.set_parameter_value("BETA", 1.0);
+        assert_eq!(parameters.changed_since(0).len(), 2);
+        assert_eq!(
+            parameters.changed_since(start),
+            vec![ShortString::from("BETA")]
+        );
+
+        let modified: Vec<_> = parameters
+            .modified_parameters()
+            .into_iter()
+            .map(|param| (param.name, param.value))
+            .collect();
+        assert_eq!(modified, vec![("ALPHA".into(), 0.8), ("BETA".into(), 1.0)]);
+
+        parameters.clear_change_callback();
+        parameters.reset_to_defaults();
+        assert_eq!(parameters.generation(), 3);
+        assert!(parameters.modified_parameters().is_empty());
+        assert_eq!(
+            *seen.lock(),
+            vec![(1, vec!["ALPHA".into()]), (2, vec!["BETA".into()])]
+        );
+    }
+
+    #[test]
+    fn evaluates_automation() {
+        let mut automation = ParameterAutomation::new();
+        let keyframe = |frame, value, interpolation| Keyframe {
+            frame,
+            value,
+            interpolation,
+        };
+        automation.add_keyframe("ZOOM", keyframe(20, 2.0, Interpolation::Step));
+        automation.add_keyframe("ZOOM", keyframe(10, 0.0, Interpolation::Linear));
+        automation.add_keyframe("ZOOM", keyframe(30, 0.0, Interpolation::Smoothstep));
+        automation.add_keyframe("ZOOM", keyframe(40, 1.0, Interpolation::Linear));
+
+        assert_eq!(automation.evaluate("ZOOM", 0), Some(0.0));
+        assert_eq!(automation.evaluate("ZOOM", 15), Some(1.0));
+        assert_eq!(automation.evaluate("ZOOM", 25), Some(2.0));
+        assert_eq!(automation.evaluate("ZOOM", 35), Some(0.5));
+        assert_eq!(automation.evaluate("ZOOM", 100), Some(1.0));
+        assert_eq!(automation.evaluate("ALPHA", 0), None);
+        assert_eq!(automation.last_frame(), Some(40));
+
+        let parameters = parameters();
+        parameters.set_automation(Some(automation));
+        parameters.set_automation_frame(25);
+        let automation = parameters.automation().unwrap();
+        assert_eq!(parameters.automated_value(&automation, "ZOOM"), Some(2.0));
+        parameters.set_automation_frame(0);
+        assert_eq!(parameters.automated_value(&automation, "ZOOM"), Some(0.5));
+    }
+}
@@ -0,0 +1,319 @@
+use std::time::Duration;
+
+/// GPU timings of the passes of a single frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrameStatistics {
+    /// The frame count passed to `frame` for the frame that was timed.
+    pub frame_count: usize,
+    /// The alias and GPU time of each pass that ran during the frame, in order.
+    pub per_pass: Vec<(Option<String>, Duration)>,
+}
+
+impl FrameStatistics {
+    /// The total GPU time of all passes of the frame.
+    pub fn total(&self) -> Duration {
+        self.per_pass.iter().map(|(_, duration)| *duration).sum()
+    }
+}
+
+/// Trait for filter chains that can time their passes with GPU timestamp queries.
+///
+/// Timestamp queries are only recorded while profiling is enabled. Results are read back
+/// without blocking, so the statistics of a frame are only available a few frames after it
+/// was submitted.
+pub trait FilterChainProfiling {
+    /// Enable or disable timestamp queries for subsequent frames.
+    ///
+    /// If the device does not support timestamp queries, profiling is disabled again at the next frame.
+    fn set_profiling(&mut self, enabled: bool);
+
+    /// Whether timestamp queries are recorded for subsequent frames.
+    fn profiling(&self) -> bool;
+
+    /// Get the statistics of the most recent frame whose timings have become available since
+    /// the last call, if any.
+    fn frame_statistics(&mut self) -> Option<FrameStatistics>;
+}
+
+/// The queries used to time the passes of a single frame.
+pub trait PassTimer {
+    /// The number of passes that can be timed.
+    fn capacity(&self) -> usize;
+
+    /// Read back the GPU time of the first `passes` passes recorded, without blocking.
+    ///
+    /// Returns `None` if the results are not yet available.
+    fn read(&mut self, passes: usize) -> Option<Vec<Duration>>;
+}
+
+/// Get the durations between consecutive timestamps, given the number of nanoseconds per tick.
+pub fn timestamp_durations(timestamps: &[u64], period: f64) -> Vec<Duration> {
+    timestamps
+        .windows(2)
+        .map(|ticks| {
+            Duration::from_nanos((ticks[1].saturating_sub(ticks[0]) as f64 * period) as u64)
+        })
+        .collect()
+}
+
+struct PendingFrame {
+    sequence: u64,
+    frame_count: usize,
+    aliases: Vec<Option<String>>,
+}
+
+struct ProfilerSlot<T> {
+    timer: Option<T>,
+    pending: Option<PendingFrame>,
+}
+
+/// Keeps the timestamp queries of the frames in flight, and collects their results
+/// once they become available.
+pub struct FrameProfiler<T> {
+    enabled: bool,
+    slots: Box<[ProfilerSlot<T>]>,
+    index: usize,
+    sequence: u64,
+    latest: Option<FrameStatistics>,
+    resolved: u64,
+}
+
+impl<T: PassTimer> FrameProfiler<T> {
+    /// Create a profiler that can keep the queries of the given number of frames in flight.
+    pub fn new(frames_in_flight: usize) -> Self {
+        let mut slots = Vec::new();
+        slots.resize_with(std::cmp::max(frames_in_flight, 2), || ProfilerSlot {
+            timer: None,
+            pending: None,
+        });
+
+        FrameProfiler {
+            enabled: false,
+            slots: slots.into_boxed_slice(),
+            index: 0,
+            sequence: 0,
+            latest: None,
+            resolved: 0,
+        }
+    }
+
+    /// Whether queries are recorded for subsequent frames.
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enable or disable recording queries for subsequent frames.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Begin timing the passes of a frame, returning the timer to record the queries of the frame into.
+    ///
+    /// Returns `None` if profiling is disabled. The results of the frame that previously used the timer
+    /// are collected if available, and discarded otherwise. If the timer can not time all the passes,
+    /// it is replaced with one created by `create`. If `create` returns `None`, timestamp queries are
+    /// unsupported and profiling is disabled.
+    pub fn begin_frame<'a, E>(
+        &mut self,
+        frame_count: usize,
+        aliases: impl IntoIterator<Item = Option<&'a str>>,
+        create: impl FnOnce(usize) -> Result<Option<T>, E>,
+    ) -> Result<Option<&mut T>, E> {
+        if !self.enabled {
+            return Ok(None);
+        }
+
+        let aliases: Vec<Option<String>> = aliases
+            .into_iter()
+            .map(|alias| alias.map(String::from))
+            .collect();
+
+        let index = self.index;
+        self.index = (self.index + 1) % self.slots.len();
+
+        let slot = &mut self.slots[index];
+        Self::resolve(slot, &mut self.latest, &mut self.resolved);
+        slot.pending = None;
+
+        let reusable = match &slot.timer {
+            Some(timer) => timer.capacity() >= aliases.len(),
+            None => false,
+        };
+
+        if !reusable {
+            let Some(timer) = create(aliases.len())? else {
+                self.enabled = false;
+                return Ok(None);
+            };
+            slot.timer = Some(timer);
+        }
+
+        self.sequence += 1;
+        slot.pending = Some(PendingFrame {
+            sequence: self.sequence,
+            frame_count,
+            aliases,
+        });
+
+        Ok(slot.timer.as_mut())
+    }
+
+    /// Get the statistics of the most recent frame whose results have become available
+    /// since the last call, if any.
+    ///
+    /// The most recently recorded frame is never read back, since it may not have been submitted yet.
+    pub fn statistics(&mut self) -> Option<FrameStatistics> {
+        let len = self.slots.len();
+        for offset in 0..len - 1 {
+            let slot = &mut self.slots[(self.index + offset) % len];
+            Self::resolve(slot, &mut self.latest, &mut self.resolved);
+        }
+
+        self.latest.take()
+    }
+
+    fn resolve(
+        slot: &mut ProfilerSlot<T>,
+        latest: &mut Option<FrameStatistics>,
+        resolved: &mut u64,
+    ) {
+        let (Some(timer), Some(pending)) = (slot.timer.as_mut(), slot.pending.take()) else {
+            return;
+        };
+
+        let Some(durations) = timer.read(pending.aliases.len()) else {
+            slot.pending = Some(pending);
+            return;
+        };
+
+        // Never go back to a frame older than one that was already collected.
+        if pending.sequence <= *resolved {
+            return;
+        }
+
+        *resolved = pending.sequence;
+        *latest = Some(FrameStatistics {
+            frame_count: pending.frame_count,
+            per_pass: pending.aliases.into_iter().zip(durations).collect(),
+        });
+    }
+}
+
+/// Implement [`FilterChainProfiling`] for a filter chain that keeps a [`FrameProfiler`]
+/// in a `profiler` field.
+#[macro_export]
+macro_rules! impl_filter_chain_profiling {
+    ($ty:ty) => {
+        impl ::librashader_runtime::profiling::FilterChainProfiling for $ty {
+            fn set_profiling(&mut self, enabled: bool) {
+                self.profiler.set_enabled(enabled)
+            }
+
+            fn profiling(&self) -> bool {
+                self.profiler.enabled()
+            }
+
+            fn frame_statistics(
+                &mut self,
+            ) -> Option<::librashader_runtime::profiling::FrameStatistics> {
+                self.profiler.statistics()
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    struct TestTimer {
+        capacity: usize,
+        ready: Rc<Cell<bool>>,
+        durations: Vec<Duration>,
+    }
+
+    impl PassTimer for TestTimer {
+        fn capacity(&self) -> usize {
+            self.capacity
+        }
+
+        fn read(&mut self, passes: usize) -> Option<Vec<Duration>> {
+            self.ready.get().then(|| self.durations[..passes].to_vec())
+        }
+    }
+
+    fn timer(
+        ready: &Rc<Cell<bool>>,
+        nanos: u64,
+    ) -> impl FnOnce(usize) -> Result<Option<TestTimer>, ()> {
+        let ready = Rc::clone(ready);
+        move |capacity| {
+            Ok(Some(TestTimer {
+                capacity,
+                ready,
+                durations: vec![Duration::from_nanos(nanos); capacity],
+            }))
+        }
+    }
+
+    #[test]
+    fn collects_frames_once_available() {
+        let ready = Rc::new(Cell::new(false));
+        let mut profiler = FrameProfiler::new(3);
+        assert!(profiler
+            .begin_frame(0, [Some("a"), None], timer(&ready, 1))
+            .unwrap()
+            .is_none());
+
+        profiler.set_enabled(true);
+        profiler
+            .begin_frame(1, [Some("a"), None], timer(&ready, 10))
+            .unwrap()
+            .unwrap();
+        profiler
+            .begin_frame(2, [Some("a"), None], timer(&ready, 20))
+            .unwrap()
+            .unwrap();
+        assert_eq!(profiler.statistics(), None);
+
+        ready.set(true);
+        let statistics = profiler.statistics().unwrap();
+        assert_eq!(statistics.frame_count, 1);
+        assert_eq!(
+            statistics.per_pass,
+            vec![
+                (Some(String::from("a")), Duration::from_nanos(10)),
+                (None, Duration::from_nanos(10))
+            ]
+        );
+        assert_eq!(statistics.total(), Duration::from_nanos(20));
+        assert_eq!(profiler.statistics(), None);
+
+        profiler
+            .begin_frame(3, [Some("a"), None], timer(&ready, 30))
+            .unwrap()
+            .unwrap();
+        assert_eq!(profiler.statistics().unwrap().frame_count, 2);
+    }
+
+    #[test]
+    fn disables_when_unsupported() {
+        let mut profiler = FrameProfiler::<TestTimer>::new(2);
+        profiler.set_enabled(true);
+        assert!(profiler
+            .begin_frame(0, [None], |_| Ok::<_, ()>(None))
+            .unwrap()
+            .is_none());
+        assert!(!profiler.enabled());
+    }
+
+    #[test]
+    fn converts_timestamps() {
+        assert_eq!(
+            timestamp_durations(&[100, 150, 400], 2.0),
+            vec![Duration::from_nanos(100), Duration::from_nanos(500)]
+        );
+    }
+}
@@ -0,0 +1,191 @@
+use librashader_common::map::{FastHashMap, ShortString};
+use librashader_common::{FilterMode, WrapMode};
+use librashader_pack::{PassResource, ShaderPresetPack, TextureResource};
+use librashader_presets::ParameterMeta;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+/// Identifies a lookup texture by its metadata and image contents,
+/// to determine whether it can be reused when a filter chain is reloaded.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct LutIdentity {
+    name: ShortString,
+    wrap_mode: WrapMode,
+    filter_mode: FilterMode,
+    mipmap: bool,
+    width: u32,
+    height: u32,
+    contents: u64,
+}
+
+impl LutIdentity {
+    /// Create the identity of a lookup texture.
+    pub fn new(texture: &TextureResource) -> Self {
+        let mut hasher = DefaultHasher::new();
+        texture.data.as_ref().hash(&mut hasher);
+
+        LutIdentity {
+            name: texture.meta.name.clone(),
+            wrap_mode: texture.meta.wrap_mode,
+            filter_mode: texture.meta.filter_mode,
+            mipmap: texture.meta.mipmap,
+            width: texture.data.width(),
+            height: texture.data.height(),
+            contents: hasher.finish(),
+        }
+    }
+
+    /// Create the identities of a list of lookup textures.
+    pub fn from_textures(textures: &[TextureResource]) -> Box<[LutIdentity]> {
+        textures.iter().map(LutIdentity::new).collect()
+    }
+}
+
+/// Shader passes and resources of a preset that have been compiled for a filter chain,
+/// but are yet to be swapped in.
+pub struct CompiledPasses<P> {
+    /// The compiled passes.
+    pub passes: P,
+    /// The lookup textures of the preset.
+    pub textures: Vec<TextureResource>,
+    /// The identities of the lookup textures of the preset.
+    pub lut_identities: Box<[LutIdentity]>,
+    /// The user parameters of the preset.
+    pub parameters: Vec<ParameterMeta>,
+    /// The number of passes enabled in the preset.
+    pub pass_count: usize,
+}
+
+impl<P> CompiledPasses<P> {
+    /// Split the resources out of a shader preset, compiling the passes with the given function.
+    pub fn new<E>(
+        preset: ShaderPresetPack,
+        compile: impl FnOnce(Vec<PassResource>, &[TextureResource]) -> Result<P, E>,
+    ) -> Result<Self, E> {
+        let passes = compile(preset.passes, &preset.textures)?;
+        Ok(CompiledPasses {
+            passes,
+            lut_identities: LutIdentity::from_textures(&preset.textures),
+            textures: preset.textures,
+            parameters: preset.parameters,
+            pass_count: preset.pass_count as usize,
+        })
+    }
+}
+
+/// Helper to reuse unchanged lookup textures when a filter chain is reloaded.
+pub struct LutReuse<T> {
+    luts: FastHashMap<usize, T>,
+    textures: Vec<TextureResource>,
+    indices: Vec<usize>,
+}
+
+impl<T> LutReuse<T> {
+    /// Reuse the lookup textures of the previous preset that are unchanged in the new preset.
+    ///
+    /// Lookup textures that can not be reused are passed to `dispose`.
+    pub fn new(
+        mut luts: FastHashMap<usize, T>,
+        previous: &[LutIdentity],
+        current: &[LutIdentity],
+        textures: Vec<TextureResource>,
+        mut dispose: impl FnMut(T),
+    ) -> Self {
+        let mut reused = FastHashMap::default();
+        let mut pending = Vec::new();
+        let mut indices = Vec::new();
+
+        for (index, (identity, texture)) in current.iter().zip(textures).enumerate() {
+            let lut = previous
+                .iter()
+                .position(|previous| previous == identity)
+                .and_then(|previous| luts.remove(&previous));
+
+            if let Some(lut) = lut {
+                reused.insert(index, lut);
+            } else {
+                pending.push(texture);
+                indices.push(index);
+            }
+        }
+
+        for (_, lut) in luts {
+            dispose(lut);
+        }
+
+        LutReuse {
+            luts: reused,
+            textures: pending,
+            indices,
+        }
+    }
+
+    /// Take the lookup textures that could not be reused and need to be loaded.
+    pub fn take_textures(&mut self) -> Vec<TextureResource> {
+        std::mem::take(&mut self.textures)
+    }
+
+    /// Get the lookup textures of the new preset, given the textures loaded from
+    /// [`take_textures`](Self::take_textures), keyed by their index in that list.
+    pub fn finish(self, loaded: FastHashMap<usize, T>) -> FastHashMap<usize, T> {
+        let mut luts = self.luts;
+        for (index, lut) in loaded {
+            if let Some(index) = self.indices.get(index) {
+                luts.insert(*index, lut);
+            }
+        }
+        luts
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::reload::{LutIdentity, LutReuse};
+    use image::RgbaImage;
+    use librashader_common::map::FastHashMap;
+    use librashader_common::{FilterMode, WrapMode};
+    use librashader_pack::TextureResource;
+    use librashader_presets::TextureMeta;
+
+    fn texture(name: &str, pixel: u8) -> TextureResource {
+        TextureResource {
+            data: RgbaImage::from_pixel(2, 2, image::Rgba([pixel; 4])).into(),
+            meta: TextureMeta {
+                name: name.into(),
+                wrap_mode: WrapMode::ClampToEdge,
+                filter_mode: FilterMode::Linear,
+                mipmap: false,
+            },
+        }
+    }
+
+    #[test]
+    pub fn reuses_unchanged_luts() {
+        let previous = vec![texture("a", 0), texture("b", 1), texture("c", 2)];
+        let current = vec![texture("c", 2), texture("b", 3), texture("d", 4)];
+
+        let previous_identities = LutIdentity::from_textures(&previous);
+        let current_identities = LutIdentity::from_textures(&current);
+
+        let luts: FastHashMap<usize, &str> = [(0, "a"), (1, "b"), (2, "c")].into_iter().collect();
+        let mut disposed = Vec::new();
+        let mut reuse = LutReuse::new(
+            luts,
+            &previous_identities,
+            &current_identities,
+            current,
+            |lut| disposed.push(lut),
+        );
+        disposed.sort();
+        assert_eq!(disposed, ["a", "b"]);
+
+        let textures = reuse.take_textures();
+        let names: Vec<&str> = textures.iter().map(|t| t.meta.name.as_str()).collect();
+        assert_eq!(names, ["b", "d"]);
+
+        let loaded = [(0, "new b"), (1, "new d")].into_iter().collect();
+        let luts = reuse.finish(loaded);
+        assert_eq!(luts.get(&0), Some(&"c"));
+        assert_eq!(luts.get(&1), Some(&"new b"));
+        assert_eq!(luts.get(&2), Some(&"new d"));
+    }
+}
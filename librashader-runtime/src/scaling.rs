@@ -116,6 +116,20 @@ where
     }
 }
 
+/// Create a `Scale2D` that scales both axes by a factor of 1 relative to the given scale type.
+pub(crate) fn scale_2d(scale_type: ScaleType, valid: bool) -> Scale2D {
+    let scaling = Scaling {
+        scale_type,
+        factor: ScaleFactor::Float(1.0),
+    };
+
+    Scale2D {
+        valid,
+        x: scaling.clone(),
+        y: scaling,
+    }
+}
+
 /// Trait for owned framebuffer objects that can be scaled.
 pub trait ScaleFramebuffer<T = ()> {
     type Error;
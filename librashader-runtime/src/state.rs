@@ -0,0 +1,309 @@
+use librashader_common::{ImageFormat, Size};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+const MAGIC: &[u8; 4] = b"LRST";
+
+/// The version of the serialized form written by [`FilterChainState::to_bytes`].
+pub const STATE_VERSION: u32 = 1;
+
+/// An error saving or restoring the temporal state of a filter chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StateError {
+    /// The data is not a serialized filter chain state.
+    InvalidHeader,
+    /// The data was serialized with an unsupported version of the format.
+    UnsupportedVersion(u32),
+    /// The data ended before the state was fully read.
+    UnexpectedEof,
+    /// A framebuffer has a format that can not be saved or restored.
+    UnsupportedFormat(ImageFormat),
+    /// The pixel data of a framebuffer does not match its size and format.
+    InvalidPixelData {
+        /// The expected length of the pixel data in bytes.
+        expected: usize,
+        /// The actual length of the pixel data in bytes.
+        actual: usize,
+    },
+    /// The state has a different number of history framebuffers than the filter chain.
+    HistoryMismatch {
+        /// The number of history framebuffers of the filter chain.
+        expected: usize,
+        /// The number of history framebuffers in the state.
+        actual: usize,
+    },
+    /// The state has a different number of passes than the filter chain.
+    PassCountMismatch {
+        /// The number of passes of the filter chain.
+        expected: usize,
+        /// The number of passes in the state.
+        actual: usize,
+    },
+}
+
+impl Display for StateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StateError::InvalidHeader => write!(f, "not a serialized filter chain state"),
+            StateError::UnsupportedVersion(version) => {
+                write!(f, "unsupported filter chain state version {version}")
+            }
+            StateError::UnexpectedEof => write!(f, "unexpected end of filter chain state"),
+            StateError::UnsupportedFormat(format) => {
+                write!(
+                    f,
+                    "framebuffer format {format:?} can not be saved or restored"
+                )
+            }
+            StateError::InvalidPixelData { expected, actual } => write!(
+                f,
+                "expected {expected} bytes of pixel data for framebuffer, got {actual}"
+            ),
+            StateError::HistoryMismatch { expected, actual } => write!(
+                f,
+                "expected {expected} history framebuffers in filter chain state, got {actual}"
+            ),
+            StateError::PassCountMismatch { expected, actual } => write!(
+                f,
+                "expected {expected} passes in filter chain state, got {actual}"
+            ),
+        }
+    }
+}
+
+impl Error for StateError {}
+
+/// The contents of a single framebuffer in a [`FilterChainState`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FramebufferState {
+    /// The size of the framebuffer.
+    pub size: Size<u32>,
+    /// The format of the framebuffer.
+    pub format: ImageFormat,
+    /// The texels of the first mip level of the framebuffer, in tightly packed rows.
+    ///
+    /// The first row is the row at texture coordinate `v = 0`.
+    pub data: Vec<u8>,
+}
+
+impl FramebufferState {
+    /// Create a framebuffer state, checking that the pixel data matches the size and format.
+    pub fn new(
+        size: Size<u32>,
+        format: ImageFormat,
+        data: Vec<u8>,
+    ) -> Result<FramebufferState, StateError> {
+        let expected = Self::data_len(size, format)?;
+        if data.len() != expected {
+            return Err(StateError::InvalidPixelData {
+                expected,
+                actual: data.len(),
+            });
+        }
+
+        Ok(FramebufferState { size, format, data })
+    }
+
+    /// The length in bytes of the texels of a framebuffer with the given size and format.
+    pub fn data_len(size: Size<u32>, format: ImageFormat) -> Result<usize, StateError> {
+        let bytes_per_pixel = format
+            .bytes_per_pixel()
+            .ok_or(StateError::UnsupportedFormat(format))?;
+        Ok(size.width as usize * size.height as usize * bytes_per_pixel)
+    }
+}
+
+/// The temporal state of a filter chain, read back from its framebuffers.
+///
+/// The state includes the `OriginalHistory` framebuffers, and the output of each pass in the
+/// last frame, which is read as `PassFeedback` in the next frame. It does not depend on the
+/// runtime it was saved from, and can be serialized with [`to_bytes`](Self::to_bytes).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FilterChainState {
+    /// The frame count of the last frame rendered before the state was saved.
+    ///
+    /// Frame counts are passed to the filter chain every frame, so this is only recorded for
+    /// the caller to resume counting frames from.
+    pub frame_count: usize,
+    /// The `OriginalHistory` framebuffers, starting with `OriginalHistory1`.
+    ///
+    /// Framebuffers that have not been rendered to are `None`.
+    pub history: Vec<Option<FramebufferState>>,
+    /// The output of each pass in the last frame, which becomes `PassFeedback` in the next frame.
+    ///
+    /// Framebuffers that have not been rendered to are `None`.
+    pub feedback: Vec<Option<FramebufferState>>,
+}
+
+impl FilterChainState {
+    /// Check that the state can be restored into a filter chain with the given number of
+    /// history framebuffers and passes.
+    pub fn check_compatible(&self, history: usize, passes: usize) -> Result<(), StateError> {
+        if self.history.len() != history {
+            return Err(StateError::HistoryMismatch {
+                expected: history,
+                actual: self.history.len(),
+            });
+        }
+
+        if self.feedback.len() != passes {
+            return Err(StateError::PassCountMismatch {
+                expected: passes,
+                actual: self.feedback.len(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Serialize the state into a versioned binary form.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&STATE_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&(self.frame_count as u64).to_le_bytes());
+        for framebuffers in [&self.history, &self.feedback] {
+            bytes.extend_from_slice(&(framebuffers.len() as u32).to_le_bytes());
+            for framebuffer in framebuffers.iter() {
+                let Some(framebuffer) = framebuffer else {
+                    bytes.push(0);
+                    continue;
+                };
+                bytes.push(1);
+                bytes.extend_from_slice(&(framebuffer.format as u32).to_le_bytes());
+                bytes.extend_from_slice(&framebuffer.size.width.to_le_bytes());
+                bytes.extend_from_slice(&framebuffer.size.height.to_le_bytes());
+                bytes.extend_from_slice(&(framebuffer.data.len() as u64).to_le_bytes());
+                bytes.extend_from_slice(&framebuffer.data);
+            }
+        }
+        bytes
+    }
+
+    /// Deserialize a state from the binary form written by [`to_bytes`](Self::to_bytes).
+    pub fn from_bytes(bytes: &[u8]) -> Result<FilterChainState, StateError> {
+        let mut reader = Reader(bytes);
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(StateError::InvalidHeader);
+        }
+
+        let version = reader.u32()?;
+        if version != STATE_VERSION {
+            return Err(StateError::UnsupportedVersion(version));
+        }
+
+        let frame_count = reader.u64()? as usize;
+        let history = reader.framebuffers()?;
+        let feedback = reader.framebuffers()?;
+
+        Ok(FilterChainState {
+            frame_count,
+            history,
+            feedback,
+        })
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], StateError> {
+        if self.0.len() < len {
+            return Err(StateError::UnexpectedEof);
+        }
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8, StateError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, StateError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, StateError> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn framebuffers(&mut self) -> Result<Vec<Option<FramebufferState>>, StateError> {
+        let count = self.u32()?;
+        let mut framebuffers = Vec::new();
+        for _ in 0..count {
+            if self.u8()? == 0 {
+                framebuffers.push(None);
+                continue;
+            }
+
+            let format = ImageFormat::try_from(self.u32()?).unwrap_or(ImageFormat::Unknown);
+            let size = Size::new(self.u32()?, self.u32()?);
+            let len = self.u64()?;
+            let data = self.take(usize::try_from(len).map_err(|_| StateError::UnexpectedEof)?)?;
+            framebuffers.push(Some(FramebufferState::new(size, format, data.to_vec())?));
+        }
+        Ok(framebuffers)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn state() -> FilterChainState {
+        let size = Size::new(2, 1);
+        FilterChainState {
+            frame_count: 120,
+            history: vec![
+                Some(FramebufferState::new(size, ImageFormat::R8G8B8A8Unorm, vec![7; 8]).unwrap()),
+                None,
+            ],
+            feedback: vec![
+                None,
+                Some(
+                    FramebufferState::new(size, ImageFormat::R16Sfloat, vec![1, 2, 3, 4]).unwrap(),
+                ),
+            ],
+        }
+    }
+
+    #[test]
+    fn round_trips() {
+        let state = state();
+        assert_eq!(FilterChainState::from_bytes(&state.to_bytes()), Ok(state));
+    }
+
+    #[test]
+    fn rejects_invalid_data() {
+        let bytes = state().to_bytes();
+        assert_eq!(
+            FilterChainState::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(StateError::UnexpectedEof)
+        );
+        assert_eq!(
+            FilterChainState::from_bytes(b"not a state"),
+            Err(StateError::InvalidHeader)
+        );
+        assert_eq!(
+            FramebufferState::new(Size::new(2, 2), ImageFormat::R8Unorm, vec![0; 3]),
+            Err(StateError::InvalidPixelData {
+                expected: 4,
+                actual: 3
+            })
+        );
+    }
+
+    #[test]
+    fn checks_compatibility() {
+        let state = state();
+        assert_eq!(state.check_compatible(2, 2), Ok(()));
+        assert_eq!(
+            state.check_compatible(2, 3),
+            Err(StateError::PassCountMismatch {
+                expected: 3,
+                actual: 2
+            })
+        );
+    }
+}
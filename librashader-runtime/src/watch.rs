@@ -0,0 +1,349 @@
+//! Polling file watcher to reload shader presets during development.
+//!
+//! A [`PresetWatcher`] tracks the shader preset, the presets it includes with `#reference`,
+//! the source of every pass along with every file reached through `#include`, and the lookup
+//! textures of the preset. When any of these files change, the passes that depend on them are
+//! preprocessed and checked for compile errors again, and the resulting [`ShaderPresetPack`] can be
+//! passed to the `reload` method of a filter chain, which compiles the passes for the runtime.
+//!
+//! Files are watched by polling their modification time, so no platform specific file system
+//! notification APIs are required.
+use librashader_common::map::FastHashMap;
+use librashader_pack::{LoadableResource, ShaderPresetPack, TextureBuffer};
+use librashader_preprocess::{PreprocessError, ShaderSource};
+use librashader_presets::{ShaderPreset, TextureMeta, WildcardContext};
+use librashader_reflect::diagnostics::{DiagnosticSeverity, ShaderDiagnostic};
+use librashader_reflect::front::{Glslang, ShaderInputCompiler, SpirvCompilation};
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// A shader preset that was reloaded after a change.
+#[derive(Debug, Clone)]
+pub struct ReloadedPreset {
+    /// The parsed shader preset.
+    pub preset: ShaderPreset,
+    /// The loaded resources of the preset, ready to be swapped into a filter chain.
+    pub pack: ShaderPresetPack,
+    /// The indices of the passes that were preprocessed and checked for compile errors again.
+    pub changed_passes: Vec<usize>,
+}
+
+/// A shader preset that could not be reloaded after a change.
+#[derive(Debug, Clone)]
+pub struct ReloadFailure {
+    /// The index of the pass that failed to compile, if the failure is specific to a pass.
+    pub pass: Option<usize>,
+    /// Diagnostics describing why the preset could not be reloaded.
+    pub diagnostics: Vec<ShaderDiagnostic>,
+}
+
+/// The result of reloading a shader preset after a change.
+#[derive(Debug, Clone)]
+pub enum WatchEvent {
+    /// The shader preset was reloaded successfully.
+    Reloaded(ReloadedPreset),
+    /// The shader preset could not be reloaded.
+    Failed(ReloadFailure),
+}
+
+impl Display for ReloadFailure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(pass) = self.pass {
+            writeln!(f, "failed to reload pass {pass}:")?;
+        } else {
+            writeln!(f, "failed to reload preset:")?;
+        }
+        for diagnostic in &self.diagnostics {
+            writeln!(f, "{diagnostic}")?;
+        }
+        Ok(())
+    }
+}
+
+impl ReloadFailure {
+    fn new(pass: Option<usize>, file: Option<&Path>, message: String) -> Self {
+        ReloadFailure {
+            pass,
+            diagnostics: vec![ShaderDiagnostic {
+                severity: DiagnosticSeverity::Error,
+                file: file.map(|file| file.display().to_string()),
+                line: None,
+                column: None,
+                message,
+            }],
+        }
+    }
+
+    fn preprocess(pass: usize, path: &Path, error: PreprocessError) -> Self {
+        let file = match &error {
+            PreprocessError::IOError(file, _) | PreprocessError::EncodingError(file) => {
+                file.as_path()
+            }
+            _ => path,
+        };
+        ReloadFailure::new(Some(pass), Some(file), error.to_string())
+    }
+}
+
+struct CachedPass {
+    source: ShaderSource,
+    includes: Vec<PathBuf>,
+}
+
+/// Watches the files of a shader preset, reloading it when any of them change.
+pub struct PresetWatcher {
+    path: PathBuf,
+    context: WildcardContext,
+    files: FastHashMap<PathBuf, Option<SystemTime>>,
+    passes: FastHashMap<PathBuf, CachedPass>,
+    textures: FastHashMap<PathBuf, TextureBuffer>,
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+impl PresetWatcher {
+    /// Watch the shader preset at the given path, parsing it with the given wildcard context.
+    ///
+    /// The preset is not loaded until the first call to [`poll`](Self::poll).
+    pub fn new(path: impl AsRef<Path>, context: WildcardContext) -> Self {
+        PresetWatcher {
+            path: path.as_ref().to_path_buf(),
+            context,
+            files: FastHashMap::default(),
+            passes: FastHashMap::default(),
+            textures: FastHashMap::default(),
+        }
+    }
+
+    /// The files that are currently being watched.
+    pub fn files(&self) -> impl Iterator<Item = &Path> {
+        self.files.keys().map(PathBuf::as_path)
+    }
+
+    /// Check the watched files for changes, reloading the preset if any have changed.
+    ///
+    /// The first call always loads the preset. Returns `None` if no watched file has changed
+    /// since the last call.
+    pub fn poll(&mut self) -> Option<WatchEvent> {
+        let changed: Vec<PathBuf> = self
+            .files
+            .iter()
+            .filter(|(path, last)| modified(path) != **last)
+            .map(|(path, _)| path.clone())
+            .collect();
+
+        if !self.files.is_empty() && changed.is_empty() {
+            return None;
+        }
+
+        for path in &changed {
+            self.passes.remove(path);
+            self.textures.remove(path);
+        }
+        self.passes.retain(|_, pass| {
+            !pass
+                .includes
+                .iter()
+                .any(|include| changed.contains(include))
+        });
+
+        Some(self.reload())
+    }
+
+    /// Block until a watched file changes, checking for changes at the given interval,
+    /// and return the result of reloading the preset.
+    pub fn wait(&mut self, interval: Duration) -> WatchEvent {
+        loop {
+            if let Some(event) = self.poll() {
+                return event;
+            }
+            std::thread::sleep(interval);
+        }
+    }
+
+    fn reload(&mut self) -> WatchEvent {
+        let result = self.load();
+
+        // Keep watching the previous files if the preset could not be parsed,
+        // so that fixing the error triggers a reload.
+        let files = match &result {
+            Ok((preset, _)) => self.preset_files(preset),
+            Err(_) => self
+                .files
+                .keys()
+                .cloned()
+                .chain(std::iter::once(self.path.clone()))
+                .collect(),
+        };
+
+        self.files = files
+            .into_iter()
+            .map(|path| {
+                let modified = modified(&path);
+                (path, modified)
+            })
+            .collect();
+
+        match result {
+            Ok((preset, changed_passes)) => WatchEvent::Reloaded(ReloadedPreset {
+                pack: self.pack(&preset),
+                preset,
+                changed_passes,
+            }),
+            Err(failure) => WatchEvent::Failed(failure),
+        }
+    }
+
+    fn load(&mut self) -> Result<(ShaderPreset, Vec<usize>), ReloadFailure> {
+        let preset = ShaderPreset::try_parse_with_context(&self.path, self.context.clone())
+            .map_err(|error| ReloadFailure::new(None, Some(&self.path), error.to_string()))?;
+
+        let mut changed_passes = Vec::new();
+        let mut loaded: Vec<&Path> = Vec::new();
+        for (index, pass) in preset.passes.iter().enumerate() {
+            if loaded.contains(&pass.path.as_path()) {
+                changed_passes.push(index);
+                continue;
+            }
+            if self.passes.contains_key(&pass.path) {
+                continue;
+            }
+
+            // Watch the pass even if it fails to load.
+            self.files.insert(pass.path.clone(), None);
+            let (source, includes) = ShaderSource::load_with_includes(&pass.path)
+                .map_err(|error| ReloadFailure::preprocess(index, &pass.path, error))?;
+
+            for include in &includes {
+                self.files.insert(include.clone(), None);
+            }
+            <Glslang as ShaderInputCompiler<SpirvCompilation>>::compile(&source).map_err(
+                |error| ReloadFailure {
+                    pass: Some(index),
+                    diagnostics: error.diagnostics(),
+                },
+            )?;
+
+            self.passes
+                .insert(pass.path.clone(), CachedPass { source, includes });
+            loaded.push(&pass.path);
+            changed_passes.push(index);
+        }
+
+        for texture in &preset.textures {
+            if self.textures.contains_key(&texture.path) {
+                continue;
+            }
+
+            let image = TextureMeta::load(&texture.path).map_err(|error| {
+                ReloadFailure::new(None, Some(&texture.path), error.to_string())
+            })?;
+            self.textures.insert(texture.path.clone(), image);
+        }
+
+        Ok((preset, changed_passes))
+    }
+
+    fn preset_files(&self, preset: &ShaderPreset) -> Vec<PathBuf> {
+        let mut files = vec![self.path.clone()];
+        files.extend(preset.references.iter().cloned());
+        for pass in &preset.passes {
+            files.push(pass.path.clone());
+            if let Some(cached) = self.passes.get(&pass.path) {
+                files.extend(cached.includes.iter().cloned());
+            }
+        }
+        files.extend(preset.textures.iter().map(|texture| texture.path.clone()));
+        files
+    }
+
+    fn pack(&self, preset: &ShaderPreset) -> ShaderPresetPack {
+        // Every pass and texture was loaded into the cache by `load`.
+        let result: Result<_, ()> = ShaderPresetPack::load_from_preset_with(
+            preset.clone(),
+            |_, path| {
+                self.passes
+                    .get(path)
+                    .map(|pass| pass.source.clone())
+                    .ok_or(())
+            },
+            |_, path| self.textures.get(path).cloned().ok_or(()),
+        );
+        result.expect("all resources of the preset should be loaded")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::watch::{PresetWatcher, WatchEvent};
+    use librashader_presets::WildcardContext;
+    use std::fs::File;
+    use std::path::{Path, PathBuf};
+    use std::time::{Duration, SystemTime};
+
+    fn copy_fixture() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("librashader-watch-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for entry in std::fs::read_dir("../test/watch").unwrap() {
+            let entry = entry.unwrap();
+            std::fs::copy(entry.path(), dir.join(entry.file_name())).unwrap();
+        }
+        dir
+    }
+
+    fn write(path: &Path, contents: &str, seconds: u64) {
+        std::fs::write(path, contents).unwrap();
+        // Move the modification time forward, in case the file system has a coarse resolution.
+        File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(seconds))
+            .unwrap();
+    }
+
+    #[test]
+    pub fn reloads_changed_passes() {
+        let dir = copy_fixture();
+        let include = dir.join("tint.inc");
+        let mut watcher = PresetWatcher::new(dir.join("watch.slangp"), WildcardContext::new());
+
+        let Some(WatchEvent::Reloaded(reloaded)) = watcher.poll() else {
+            panic!("the preset should load");
+        };
+        assert_eq!(reloaded.changed_passes, [0, 1]);
+        assert_eq!(reloaded.pack.passes.len(), 2);
+        assert_eq!(reloaded.pack.textures.len(), 1);
+        for file in ["base.slangp", "tint.inc", "tint.png"] {
+            assert!(watcher.files().any(|path| path.ends_with(file)));
+        }
+        assert!(watcher.poll().is_none());
+
+        let source = std::fs::read_to_string(&include).unwrap();
+        write(&include, &source.replace("color * lut", "color + lut"), 10);
+        let Some(WatchEvent::Reloaded(reloaded)) = watcher.poll() else {
+            panic!("the preset should reload");
+        };
+        assert_eq!(reloaded.changed_passes, [0]);
+
+        write(&include, "vec4 tint(", 20);
+        let Some(WatchEvent::Failed(failure)) = watcher.poll() else {
+            panic!("the preset should fail to reload");
+        };
+        assert_eq!(failure.pass, Some(0));
+        assert!(!failure.diagnostics.is_empty());
+
+        write(&include, &source, 30);
+        let Some(WatchEvent::Reloaded(reloaded)) = watcher.poll() else {
+            panic!("the preset should reload");
+        };
+        assert_eq!(reloaded.changed_passes, [0]);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,45 @@
+//! In-memory files for presets, shader sources and lookup textures.
+use librashader_common::vfs::MemoryFileSystem;
+use wasm_bindgen::prelude::*;
+
+/// A file system held in memory that presets and shader sources are loaded from.
+///
+/// Paths are resolved against the root, so `crt/crt-geom.slangp` and `/crt/crt-geom.slangp`
+/// refer to the same file. Directories exist implicitly for every file they contain.
+#[wasm_bindgen(js_name = MemoryFileSystem)]
+#[derive(Debug, Default, Clone)]
+pub struct JsMemoryFileSystem(pub(crate) MemoryFileSystem);
+
+#[wasm_bindgen(js_class = MemoryFileSystem)]
+impl JsMemoryFileSystem {
+    /// Create an empty file system.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a file with the given contents, replacing any file already at the path.
+    pub fn insert(&mut self, path: &str, contents: Vec<u8>) {
+        self.0.insert(path, contents);
+    }
+
+    /// Add a text file, such as a preset or a shader source.
+    #[wasm_bindgen(js_name = insertText)]
+    pub fn insert_text(&mut self, path: &str, text: &str) {
+        self.0.insert(path, text);
+    }
+
+    /// Remove the file at the given path, returning whether it existed.
+    pub fn remove(&mut self, path: &str) -> bool {
+        self.0.remove(path).is_some()
+    }
+
+    /// The paths of every file.
+    #[wasm_bindgen(getter)]
+    pub fn paths(&self) -> Vec<String> {
+        self.0
+            .paths()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect()
+    }
+}
@@ -0,0 +1,36 @@
+//! WebAssembly bindings for librashader.
+//!
+//! This crate builds a `wasm32-unknown-unknown` module with [wasm-bindgen](https://rustwasm.github.io/docs/wasm-bindgen/)
+//! that exposes the CPU-side parts of librashader to JavaScript: parsing shader presets,
+//! preprocessing shader sources, and transpiling shaders to WGSL. Together with
+//! `librashader-runtime-wgpu` on WebGPU, this is enough to build a shader preset previewer
+//! for the web.
+//!
+//! There is no file system in the browser, so presets and shaders are loaded from a
+//! [`MemoryFileSystem`](fs::JsMemoryFileSystem) that is populated from JavaScript. Shaders are
+//! compiled with the naga GLSL frontend instead of glslang, which does not support every shader
+//! that glslang does.
+//!
+//! ```sh
+//! wasm-pack build --target web librashader-wasm -- --features stable
+//! ```
+//!
+//! ```js
+//! import init, { MemoryFileSystem, ShaderPreset, ShaderSource } from "./pkg/librashader_wasm.js";
+//!
+//! await init();
+//! const fs = new MemoryFileSystem();
+//! fs.insertText("crt/crt-geom.slangp", presetText);
+//! fs.insertText("crt/shaders/crt-geom.slang", shaderText);
+//!
+//! const preset = ShaderPreset.parse(fs, "crt/crt-geom.slangp");
+//! for (const path of preset.passPaths) {
+//!     const output = ShaderSource.load(fs, path).transpileWgsl(true, 1);
+//!     console.log(output.fragment);
+//! }
+//! ```
+#![forbid(missing_docs)]
+
+pub mod fs;
+pub mod preprocess;
+pub mod presets;
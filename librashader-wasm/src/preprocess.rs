@@ -0,0 +1,121 @@
+//! Loading and preprocessing of shader sources, and transpilation to WGSL.
+use crate::fs::JsMemoryFileSystem;
+use librashader_preprocess::ShaderSource;
+use librashader_reflect::back::targets::WGSL;
+use librashader_reflect::back::{CompileShader, FromCompilation};
+use librashader_reflect::front::SpirvCompilation;
+use librashader_reflect::reflect::naga::NagaLoweringOptions;
+use librashader_reflect::reflect::ReflectShader;
+use wasm_bindgen::prelude::*;
+
+/// A user tweakable parameter of a shader as declared in its source.
+#[wasm_bindgen(js_name = ShaderParameter, getter_with_clone)]
+#[derive(Debug, Clone)]
+pub struct JsShaderParameter {
+    /// The name of the parameter.
+    pub id: String,
+    /// The description of the parameter.
+    pub description: String,
+    /// The initial value the parameter is set to.
+    pub initial: f32,
+    /// The minimum value that the parameter can be set to.
+    pub minimum: f32,
+    /// The maximum value that the parameter can be set to.
+    pub maximum: f32,
+    /// The step by which this parameter can be incremented or decremented.
+    pub step: f32,
+}
+
+impl From<&librashader_preprocess::ShaderParameter> for JsShaderParameter {
+    fn from(value: &librashader_preprocess::ShaderParameter) -> Self {
+        JsShaderParameter {
+            id: value.id.to_string(),
+            description: value.description.clone(),
+            initial: value.initial,
+            minimum: value.minimum,
+            maximum: value.maximum,
+            step: value.step,
+        }
+    }
+}
+
+/// The vertex and fragment WGSL of a transpiled shader.
+#[wasm_bindgen(js_name = ShaderOutput, getter_with_clone)]
+#[derive(Debug, Clone)]
+pub struct JsShaderOutput {
+    /// The vertex shader.
+    pub vertex: String,
+    /// The fragment shader.
+    pub fragment: String,
+}
+
+/// The preprocessed source of a single shader pass.
+#[wasm_bindgen(js_name = ShaderSource)]
+#[derive(Debug, Clone)]
+pub struct JsShaderSource(ShaderSource);
+
+#[wasm_bindgen(js_class = ShaderSource)]
+impl JsShaderSource {
+    /// Load and preprocess the `.slang` shader source at the given path of the file system.
+    ///
+    /// `#include` directives are resolved relative to the path, and `#pragma` directives
+    /// are parsed.
+    pub fn load(fs: &JsMemoryFileSystem, path: &str) -> Result<JsShaderSource, JsError> {
+        Ok(JsShaderSource(ShaderSource::load_with_fs(path, &fs.0)?))
+    }
+
+    /// The source of the vertex shader.
+    #[wasm_bindgen(getter)]
+    pub fn vertex(&self) -> String {
+        self.0.vertex.clone()
+    }
+
+    /// The source of the fragment shader.
+    #[wasm_bindgen(getter)]
+    pub fn fragment(&self) -> String {
+        self.0.fragment.clone()
+    }
+
+    /// The alias of the shader if available.
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> Option<String> {
+        self.0.name.as_ref().map(ToString::to_string)
+    }
+
+    /// The parameters declared by the shader, in the order they were declared.
+    #[wasm_bindgen(getter)]
+    pub fn parameters(&self) -> Vec<JsShaderParameter> {
+        self.0.parameters.values().map(Into::into).collect()
+    }
+
+    /// The name of the image format of the output framebuffer, such as `R8G8B8A8Unorm`.
+    #[wasm_bindgen(getter)]
+    pub fn format(&self) -> String {
+        format!("{:?}", self.0.format)
+    }
+
+    /// Compile the shader source and transpile it to WGSL.
+    ///
+    /// `writePcbAsUbo` places the push constant block in a uniform buffer, as WebGPU has no
+    /// push constants, and `samplerBindGroup` is the bind group samplers are moved to. The
+    /// filter chains of `librashader-runtime-wgpu` use `true` and `1`.
+    #[wasm_bindgen(js_name = transpileWgsl)]
+    pub fn transpile_wgsl(
+        &self,
+        write_pcb_as_ubo: bool,
+        sampler_bind_group: u32,
+    ) -> Result<JsShaderOutput, JsError> {
+        let compilation = SpirvCompilation::try_from(&self.0)?;
+        let mut compilation = WGSL::from_compilation(compilation)?;
+        compilation.validate()?;
+        let output = compilation.compile(NagaLoweringOptions {
+            write_pcb_as_ubo,
+            sampler_bind_group,
+        })?;
+
+        Ok(JsShaderOutput {
+            vertex: output.vertex,
+            fragment: output.fragment,
+        })
+    }
+}
@@ -0,0 +1,227 @@
+//! Shader preset parsing and wildcard contexts.
+use crate::fs::JsMemoryFileSystem;
+use librashader_presets::context::{
+    ContextItem, Orientation, PresetExtension, Rotation, ShaderExtension, VideoDriver,
+};
+use librashader_presets::{ShaderPreset, WildcardContext};
+use std::path::Path;
+use wasm_bindgen::prelude::*;
+
+/// The graphics runtime a preset is loaded for.
+#[wasm_bindgen(js_name = VideoDriver)]
+#[derive(Debug, Copy, Clone)]
+pub enum JsVideoDriver {
+    /// No runtime.
+    None,
+    /// OpenGL 3.3+
+    GlCore,
+    /// Vulkan
+    Vulkan,
+    /// Direct3D 11
+    Direct3D11,
+    /// Direct3D 12
+    Direct3D12,
+    /// Metal
+    Metal,
+    /// Direct3D 9
+    Direct3D9Hlsl,
+}
+
+impl From<JsVideoDriver> for VideoDriver {
+    fn from(value: JsVideoDriver) -> Self {
+        match value {
+            JsVideoDriver::None => VideoDriver::None,
+            JsVideoDriver::GlCore => VideoDriver::GlCore,
+            JsVideoDriver::Vulkan => VideoDriver::Vulkan,
+            JsVideoDriver::Direct3D11 => VideoDriver::Direct3D11,
+            JsVideoDriver::Direct3D12 => VideoDriver::Direct3D12,
+            JsVideoDriver::Metal => VideoDriver::Metal,
+            JsVideoDriver::Direct3D9Hlsl => VideoDriver::Direct3D9Hlsl,
+        }
+    }
+}
+
+/// The orientation of the view or the core.
+#[wasm_bindgen(js_name = Orientation)]
+#[derive(Debug, Copy, Clone)]
+pub enum JsOrientation {
+    /// Vertical orientation.
+    Vertical,
+    /// Horizontal orientation.
+    Horizontal,
+}
+
+impl From<JsOrientation> for Orientation {
+    fn from(value: JsOrientation) -> Self {
+        match value {
+            JsOrientation::Vertical => Orientation::Vertical,
+            JsOrientation::Horizontal => Orientation::Horizontal,
+        }
+    }
+}
+
+/// The variables used to replace wildcards in the paths of a shader preset.
+///
+/// `PRESET_DIR` and `PRESET` are inferred from the path of the preset when it is parsed.
+/// Every variable, including inferred variables, can be overridden with `setParam`.
+/// See <https://github.com/libretro/RetroArch/pull/15023> for the expected string values.
+#[wasm_bindgen(js_name = WildcardContext)]
+#[derive(Debug, Clone)]
+pub struct JsWildcardContext(WildcardContext);
+
+impl Default for JsWildcardContext {
+    fn default() -> Self {
+        JsWildcardContext(WildcardContext::new())
+    }
+}
+
+#[wasm_bindgen(js_class = WildcardContext)]
+impl JsWildcardContext {
+    /// Create an empty wildcard context.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the core name (`CORE`) variable.
+    #[wasm_bindgen(js_name = setCoreName)]
+    pub fn set_core_name(&mut self, name: String) {
+        self.0.append_item(ContextItem::CoreName(name));
+    }
+
+    /// Set the content directory (`CONTENT-DIR`) variable.
+    #[wasm_bindgen(js_name = setContentDir)]
+    pub fn set_content_dir(&mut self, name: String) {
+        self.0.append_item(ContextItem::ContentDirectory(name));
+    }
+
+    /// Set the game name (`GAME`) variable.
+    #[wasm_bindgen(js_name = setGameName)]
+    pub fn set_game_name(&mut self, name: String) {
+        self.0.append_item(ContextItem::GameName(name));
+    }
+
+    /// Set a custom string variable.
+    ///
+    /// If a path of the preset contains this variable, it will be replaced with `value`.
+    #[wasm_bindgen(js_name = setParam)]
+    pub fn set_param(&mut self, name: String, value: String) {
+        self.0.append_item(ContextItem::ExternContext(name, value));
+    }
+
+    /// Set the graphics runtime (`VID-DRV`) variable.
+    ///
+    /// This also sets `VID-DRV-SHADER-EXT` and `VID-DRV-PRESET-EXT` to `slang` and `slangp`.
+    #[wasm_bindgen(js_name = setRuntime)]
+    pub fn set_runtime(&mut self, runtime: JsVideoDriver) {
+        self.0.append_item(ContextItem::VideoDriverPresetExtension(
+            PresetExtension::Slangp,
+        ));
+        self.0.append_item(ContextItem::VideoDriverShaderExtension(
+            ShaderExtension::Slang,
+        ));
+        self.0.append_item(ContextItem::VideoDriver(runtime.into()));
+    }
+
+    /// Set the core requested rotation (`CORE-REQ-ROT`) variable, in quarter rotations.
+    #[wasm_bindgen(js_name = setCoreRotation)]
+    pub fn set_core_rotation(&mut self, value: u32) {
+        self.0
+            .append_item(ContextItem::CoreRequestedRotation(Rotation::from(value)));
+    }
+
+    /// Set the user rotation (`VID-USER-ROT`) variable, in quarter rotations.
+    #[wasm_bindgen(js_name = setUserRotation)]
+    pub fn set_user_rotation(&mut self, value: u32) {
+        self.0
+            .append_item(ContextItem::UserRotation(Rotation::from(value)));
+    }
+
+    /// Set the screen orientation (`SCREEN-ORIENT`) variable, in quarter rotations.
+    #[wasm_bindgen(js_name = setScreenOrientation)]
+    pub fn set_screen_orientation(&mut self, value: u32) {
+        self.0
+            .append_item(ContextItem::ScreenOrientation(Rotation::from(value)));
+    }
+
+    /// Set whether or not to allow rotation (`VID-ALLOW-CORE-ROT`).
+    #[wasm_bindgen(js_name = setAllowRotation)]
+    pub fn set_allow_rotation(&mut self, value: bool) {
+        self.0.append_item(ContextItem::AllowCoreRotation(value));
+    }
+
+    /// Set the view aspect orientation (`VIEW-ASPECT-ORIENT`) variable.
+    #[wasm_bindgen(js_name = setViewAspectOrientation)]
+    pub fn set_view_aspect_orientation(&mut self, value: JsOrientation) {
+        self.0
+            .append_item(ContextItem::ViewAspectOrientation(value.into()));
+    }
+
+    /// Set the core aspect orientation (`CORE-ASPECT-ORIENT`) variable.
+    #[wasm_bindgen(js_name = setCoreAspectOrientation)]
+    pub fn set_core_aspect_orientation(&mut self, value: JsOrientation) {
+        self.0
+            .append_item(ContextItem::CoreAspectOrientation(value.into()));
+    }
+}
+
+/// A parsed shader preset, including the paths of its passes and lookup textures.
+#[wasm_bindgen(js_name = ShaderPreset)]
+#[derive(Debug, Clone)]
+pub struct JsShaderPreset(ShaderPreset);
+
+#[wasm_bindgen(js_class = ShaderPreset)]
+impl JsShaderPreset {
+    /// Parse the shader preset at the given path of the file system.
+    pub fn parse(fs: &JsMemoryFileSystem, path: &str) -> Result<JsShaderPreset, JsError> {
+        Self::parse_with_context(fs, path, &JsWildcardContext::new())
+    }
+
+    /// Parse the shader preset at the given path of the file system, replacing wildcards in
+    /// the paths of the preset with the variables of the context.
+    #[wasm_bindgen(js_name = parseWithContext)]
+    pub fn parse_with_context(
+        fs: &JsMemoryFileSystem,
+        path: &str,
+        context: &JsWildcardContext,
+    ) -> Result<JsShaderPreset, JsError> {
+        let preset = ShaderPreset::try_parse_with_fs(path, context.0.clone(), &fs.0)?;
+        Ok(JsShaderPreset(preset))
+    }
+
+    /// The number of shader passes enabled in the preset.
+    #[wasm_bindgen(getter, js_name = passCount)]
+    pub fn pass_count(&self) -> i32 {
+        self.0.pass_count
+    }
+
+    /// The resolved paths of the source of each shader pass.
+    #[wasm_bindgen(getter, js_name = passPaths)]
+    pub fn pass_paths(&self) -> Vec<String> {
+        self.0
+            .passes
+            .iter()
+            .map(|pass| path_to_string(&pass.path))
+            .collect()
+    }
+
+    /// The resolved paths of each lookup texture.
+    #[wasm_bindgen(getter, js_name = texturePaths)]
+    pub fn texture_paths(&self) -> Vec<String> {
+        self.0
+            .textures
+            .iter()
+            .map(|texture| path_to_string(&texture.path))
+            .collect()
+    }
+
+    /// Serialize the preset as JSON, in the same format as `librashader-cli parse`.
+    #[wasm_bindgen(js_name = toJson)]
+    pub fn to_json(&self) -> Result<String, JsError> {
+        Ok(serde_json::to_string(&self.0)?)
+    }
+}
+
+fn path_to_string(path: &Path) -> String {
+    path.to_string_lossy().into_owned()
+}
@@ -167,7 +167,14 @@ pub mod reflect {
 
     pub use librashader_reflect::error::*;
 
-    pub use librashader_reflect::reflect::{semantics, ReflectShader, ShaderReflection};
+    pub use librashader_reflect::diagnostics::{DiagnosticSeverity, ShaderDiagnostic};
+
+    pub use librashader_reflect::reflect::{lint, semantics, ReflectShader, ShaderReflection};
+
+    /// Versioned reflection output for external tooling.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(feature = "docsrs", doc(cfg(feature = "serde")))]
+    pub use librashader_reflect::reflect::schema;
 
     pub use librashader_reflect::back::{
         targets::OutputTarget, CompileReflectShader, CompileShader, CompilerBackend,
@@ -186,14 +193,24 @@ pub mod reflect {
         ///
         pub use librashader_reflect::back::glsl::GlslVersion;
 
+        pub use librashader_reflect::back::glsl::{
+            glsl_es_version_from_number, glsl_version_from_number, parse_glsl_version,
+        };
+
         /// The HLSL Shader Model to target.
         ///
         pub use librashader_reflect::back::hlsl::HlslShaderModel;
 
+        pub use librashader_reflect::back::hlsl::{
+            hlsl_shader_model_from_number, parse_hlsl_shader_model,
+        };
+
         /// The MSL version to target.
         ///
         pub use librashader_reflect::back::msl::MslVersion;
 
+        pub use librashader_reflect::back::msl::{msl_version_from_number, parse_msl_version};
+
         pub use librashader_reflect::back::glsl::CrossGlslContext;
 
         pub use librashader_reflect::back::hlsl::CrossHlslContext;
@@ -226,9 +243,23 @@ pub mod reflect {
         pub use librashader_reflect::reflect::naga::NagaLoweringOptions;
     }
 
+    /// Transpilation of a shader source to any supported shader target.
+    #[cfg(all(feature = "reflect-cross", feature = "reflect-naga"))]
+    #[cfg_attr(
+        feature = "docsrs",
+        doc(cfg(all(feature = "reflect-cross", feature = "reflect-naga")))
+    )]
+    pub mod transpile {
+        pub use librashader_reflect::back::transpile::{
+            transpile, TranspileOptions, TranspileOutput, TranspileTarget,
+        };
+    }
+
     pub use librashader_reflect::reflect::semantics::BindingMeta;
 
-    pub use librashader_reflect::reflect::presets::{CompilePresetTarget, ShaderPassArtifact};
+    pub use librashader_reflect::reflect::presets::{
+        CompilePresetTarget, CompiledPresetPasses, PassObserver, ShaderPassArtifact,
+    };
 
     pub use librashader_reflect::front::ShaderInputCompiler;
 
@@ -242,13 +273,52 @@ pub mod reflect {
     }
 }
 
+#[cfg(feature = "watch")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "watch")))]
+/// File watching to reload shader presets during development.
+///
+/// A [`PresetWatcher`](crate::watch::PresetWatcher) watches a shader preset, the presets it includes
+/// with `#reference`, every file reached through `#include` by the preprocessor, and the lookup
+/// textures of the preset. When a file changes, the affected passes are preprocessed and checked
+/// for compile errors again, and either a [`ShaderPresetPack`](crate::presets::ShaderPresetPack)
+/// or the diagnostics of the failure are returned. The watcher does not compile passes for a runtime;
+/// the pack is recompiled by the filter chain when it is passed to `reload`.
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use librashader::presets::WildcardContext;
+/// use librashader::watch::{PresetWatcher, WatchEvent};
+///
+/// let mut watcher = PresetWatcher::new("crt-royale.slangp", WildcardContext::new());
+/// loop {
+///     match watcher.wait(Duration::from_millis(250)) {
+///         WatchEvent::Reloaded(reloaded) => {
+///             // filter_chain.reload(reloaded.pack)?;
+///         }
+///         WatchEvent::Failed(failure) => eprintln!("{failure}"),
+///     }
+/// }
+/// ```
+pub mod watch {
+    pub use librashader_runtime::watch::*;
+}
+
 /// Shader runtimes to execute a filter chain on a GPU surface.
 #[cfg(feature = "runtime")]
 #[cfg_attr(feature = "docsrs", doc(cfg(feature = "runtime")))]
 pub mod runtime {
     pub use librashader_common::{Size, Viewport};
+    pub use librashader_runtime::debug::FilterChainDebug;
+    pub use librashader_runtime::filter_chain::FilterChain;
+    pub use librashader_runtime::hdr::{append_hdr_output_pass, HdrOutputEncoding};
+    #[cfg(not(target_arch = "wasm32"))]
+    pub use librashader_runtime::job::{LoadJob, LoadJobError, LoadProgress, LoadStage};
     pub use librashader_runtime::parameters::FilterChainParameters;
-    pub use librashader_runtime::parameters::RuntimeParameters;
+    pub use librashader_runtime::parameters::{
+        Interpolation, Keyframe, ParameterAutomation, RuntimeParameter, RuntimeParameters,
+    };
+    pub use librashader_runtime::profiling::{FilterChainProfiling, FrameStatistics};
+    pub use librashader_runtime::state::{FilterChainState, FramebufferState, StateError};
 
     #[cfg(feature = "runtime-gl")]
     #[cfg_attr(feature = "docsrs", doc(cfg(feature = "runtime-gl")))]
@@ -320,7 +390,8 @@ pub mod runtime {
             options::{
                 FilterChainOptionsVulkan as FilterChainOptions, FrameOptionsVulkan as FrameOptions,
             },
-            FilterChainVulkan as FilterChain, VulkanImage, VulkanInstance, VulkanObjects,
+            CompiledPassesVulkan as CompiledPasses, FilterChainVulkan as FilterChain, VulkanImage,
+            VulkanInstance, VulkanObjects,
         };
     }
 
@@ -349,7 +420,7 @@ pub mod runtime {
             options::{
                 FilterChainOptionsWgpu as FilterChainOptions, FrameOptionsWgpu as FrameOptions,
             },
-            FilterChainWgpu as FilterChain, WgpuOutputView,
+            CompiledPassesWgpu as CompiledPasses, FilterChainWgpu as FilterChain, WgpuOutputView,
         };
     }
 }
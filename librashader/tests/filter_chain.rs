@@ -0,0 +1,287 @@
+//! Tests that drive a filter chain only through the runtime-agnostic `FilterChain` trait.
+//!
+//! Each runtime provides a [`Harness`] that creates the device and images, and records and
+//! submits frames. The tests themselves are generic over the harness.
+//!
+//! Tests that need a device are ignored by default, and can be run with `cargo test -- --ignored`
+//! on a machine with a supported adapter. They fail instead of passing if no device is available.
+
+// Only the wgpu runtime can be tested without a window or platform specific device.
+#![cfg_attr(not(feature = "runtime-wgpu"), allow(dead_code))]
+
+use librashader::presets::{ShaderPreset, ShaderPresetPack};
+use librashader::runtime::{FilterChain, FilterChainParameters, RuntimeParameters, Viewport};
+
+const PRESET_PATH: &str = "../test/basic.slangp";
+
+type Input<'a, C> = <C as FilterChain>::InputImage<'a>;
+type Output<'a, C> = <C as FilterChain>::OutputView<'a>;
+type Command<'a, C> = <C as FilterChain>::CommandContext<'a>;
+type Error<C> = <C as FilterChain>::Error;
+
+/// The resources needed to drive the filter chain of a runtime.
+trait Harness {
+    type Chain: FilterChain;
+
+    /// Get the device to load filter chains with.
+    fn device(&self) -> <Self::Chain as FilterChain>::Device<'_>;
+
+    /// Record a frame with the input image and output viewport of the harness,
+    /// then submit it and wait for it to complete.
+    fn frame(
+        &mut self,
+        record: impl for<'a> FnOnce(
+            Input<'a, Self::Chain>,
+            &Viewport<Output<'a, Self::Chain>>,
+            Command<'a, Self::Chain>,
+        ) -> Result<(), Error<Self::Chain>>,
+    ) -> Result<(), Error<Self::Chain>>;
+}
+
+fn load_pack() -> ShaderPresetPack {
+    let preset = ShaderPreset::try_parse(PRESET_PATH).unwrap();
+    ShaderPresetPack::load_from_preset::<Box<dyn std::error::Error + Send + Sync>>(preset).unwrap()
+}
+
+fn render_frames<H: Harness>(harness: &mut H, chain: &mut H::Chain, frames: usize) {
+    for frame_count in 0..frames {
+        let options = <H::Chain as FilterChain>::FrameOptions::default();
+        harness
+            .frame(|input, viewport, cmd| unsafe {
+                chain.frame(input, viewport, cmd, frame_count, Some(&options))
+            })
+            .expect("failed to render frame");
+    }
+}
+
+fn loads_from_path<H: Harness>(harness: &mut H) {
+    let mut chain =
+        unsafe { H::Chain::load_from_path(PRESET_PATH, harness.device(), None) }.unwrap();
+    render_frames(harness, &mut chain, 3);
+}
+
+fn loads_from_preset<H: Harness>(harness: &mut H) {
+    let preset = ShaderPreset::try_parse(PRESET_PATH).unwrap();
+    let options = <H::Chain as FilterChain>::FilterChainOptions::default();
+    let mut chain =
+        unsafe { H::Chain::load_from_preset(preset, harness.device(), Some(&options)) }.unwrap();
+    render_frames(harness, &mut chain, 3);
+}
+
+fn loads_from_pack<H: Harness>(harness: &mut H) {
+    let mut chain =
+        unsafe { H::Chain::load_from_pack(load_pack(), harness.device(), None) }.unwrap();
+    render_frames(harness, &mut chain, 3);
+}
+
+fn sets_parameters<H: Harness>(harness: &mut H) {
+    let mut chain =
+        unsafe { H::Chain::load_from_path(PRESET_PATH, harness.device(), None) }.unwrap();
+    let parameters = chain.parameters();
+    assert_eq!(parameters.parameter_value("ColorMod"), Some(1.7));
+    parameters.set_parameter_value("ColorMod", 0.5);
+    parameters.set_passes_enabled(1);
+    render_frames(harness, &mut chain, 1);
+    assert_eq!(chain.parameters().parameter_value("ColorMod"), Some(0.5));
+}
+
+fn run_suite<H: Harness>(mut harness: H) {
+    loads_from_path(&mut harness);
+    loads_from_preset(&mut harness);
+    loads_from_pack(&mut harness);
+    sets_parameters(&mut harness);
+}
+
+#[cfg(feature = "runtime-wgpu")]
+mod wgpu_harness {
+    use super::*;
+    use librashader::runtime::wgpu::{FilterChain, FilterChainOptions, WgpuOutputView};
+    use librashader::runtime::{HdrOutputEncoding, Size};
+    use std::sync::Arc;
+    use wgpu::{Device, Queue, Texture, TextureFormat};
+
+    pub struct WgpuHarness {
+        device: Arc<Device>,
+        queue: Arc<Queue>,
+        input: Arc<Texture>,
+        output: Texture,
+        format: TextureFormat,
+    }
+
+    impl WgpuHarness {
+        pub fn new(format: TextureFormat) -> Option<Self> {
+            pollster::block_on(async {
+                let instance = wgpu::Instance::default();
+                let adapter = instance
+                    .request_adapter(&wgpu::RequestAdapterOptions::default())
+                    .await?;
+                let (device, queue) = adapter
+                    .request_device(
+                        &wgpu::DeviceDescriptor {
+                            required_features: wgpu::Features::ADDRESS_MODE_CLAMP_TO_BORDER
+                                | wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES
+                                | wgpu::Features::FLOAT32_FILTERABLE,
+                            required_limits: wgpu::Limits::default(),
+                            label: None,
+                            memory_hints: Default::default(),
+                        },
+                        None,
+                    )
+                    .await
+                    .ok()?;
+
+                let texture = |format, usage| {
+                    device.create_texture(&wgpu::TextureDescriptor {
+                        label: None,
+                        size: Size::new(64, 64).into(),
+                        mip_level_count: 1,
+                        sample_count: 1,
+                        dimension: wgpu::TextureDimension::D2,
+                        format,
+                        usage,
+                        view_formats: &[format],
+                    })
+                };
+                let input = texture(
+                    TextureFormat::Rgba8Unorm,
+                    wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                );
+                let output = texture(format, wgpu::TextureUsages::RENDER_ATTACHMENT);
+
+                Some(WgpuHarness {
+                    device: Arc::new(device),
+                    queue: Arc::new(queue),
+                    input: Arc::new(input),
+                    output,
+                    format,
+                })
+            })
+        }
+    }
+
+    impl Harness for WgpuHarness {
+        type Chain = FilterChain;
+
+        fn device(&self) -> (Arc<Device>, Arc<Queue>) {
+            (Arc::clone(&self.device), Arc::clone(&self.queue))
+        }
+
+        fn frame(
+            &mut self,
+            record: impl for<'a> FnOnce(
+                Arc<Texture>,
+                &Viewport<WgpuOutputView<'a>>,
+                &'a mut wgpu::CommandEncoder,
+            ) -> Result<(), Error<FilterChain>>,
+        ) -> Result<(), Error<FilterChain>> {
+            let mut cmd = self
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+            let view = self
+                .output
+                .create_view(&wgpu::TextureViewDescriptor::default());
+            let output =
+                WgpuOutputView::new_from_raw(&view, self.output.size().into(), self.format);
+            let viewport = Viewport::new_render_target_sized_origin(output, None).unwrap();
+
+            record(Arc::clone(&self.input), &viewport, &mut cmd)?;
+
+            let index = self.queue.submit([cmd.finish()]);
+            self.device
+                .poll(wgpu::Maintain::WaitForSubmissionIndex(index));
+            Ok(())
+        }
+    }
+
+    #[test]
+    #[ignore = "requires a wgpu adapter"]
+    fn filter_chain_wgpu() {
+        let harness =
+            WgpuHarness::new(TextureFormat::Rgba8Unorm).expect("no wgpu adapter is available");
+        run_suite(harness);
+    }
+
+    #[test]
+    #[ignore = "requires a wgpu adapter"]
+    fn filter_chain_wgpu_hdr_output() {
+        for encoding in [HdrOutputEncoding::ScRgb, HdrOutputEncoding::Hdr10] {
+            let format = Option::<TextureFormat>::from(encoding.output_format()).unwrap();
+            let mut harness = WgpuHarness::new(format).expect("no wgpu adapter is available");
+
+            let options = FilterChainOptions {
+                hdr_output: Some(encoding),
+                ..Default::default()
+            };
+            let pack = load_pack();
+            let pass_count = pack.passes.len();
+            let mut chain = unsafe {
+                <FilterChain as librashader::runtime::FilterChain>::load_from_pack(
+                    pack,
+                    harness.device(),
+                    Some(&options),
+                )
+            }
+            .unwrap();
+            assert_eq!(chain.parameters().passes_enabled(), pass_count + 1);
+            render_frames(&mut harness, &mut chain, 3);
+        }
+    }
+}
+
+#[test]
+fn preset_values_override_parameters() {
+    let pack = load_pack();
+    let parameters = RuntimeParameters::new(
+        pack.pass_count as usize,
+        pack.parameters,
+        pack.passes.iter().map(|pass| &pass.data),
+    );
+
+    assert_eq!(parameters.parameter_value("ColorMod"), Some(1.7));
+    assert_eq!(parameters.parameter_value("ColorMod2"), Some(1.0));
+
+    let color_mod = parameters.parameter_meta("ColorMod").unwrap();
+    assert_eq!(color_mod.initial, 1.0);
+    assert_eq!(color_mod.default, 1.7);
+    assert_eq!(color_mod.passes, [0]);
+
+    assert_eq!(parameters.set_parameter_value("ColorMod", 0.5), Some(1.7));
+    assert_eq!(parameters.changed_since(0), ["ColorMod"]);
+    parameters.reset_to_defaults();
+    assert_eq!(parameters.parameter_value("ColorMod"), Some(1.7));
+}
+
+#[test]
+#[cfg(feature = "reflect-cross")]
+fn binds_preset_semantics() {
+    use librashader::reflect::cross::SpirvCross;
+    use librashader::reflect::semantics::{TextureSemantics, UniqueSemantics};
+    use librashader::reflect::targets::SPIRV;
+    use librashader::reflect::{CompilePresetTarget, ReflectShader, SpirvCompilation};
+
+    let pack = load_pack();
+    let (passes, semantics) =
+        SPIRV::compile_preset_passes::<SpirvCompilation, SpirvCross, Box<dyn std::error::Error>>(
+            pack.passes,
+            pack.textures.iter().map(|texture| &texture.meta),
+        )
+        .unwrap();
+
+    assert!(semantics.uniform_semantics.contains_key("ColorMod"));
+    assert!(semantics.uniform_semantics.contains_key("ColorMod2"));
+
+    for (index, (_, mut compiler)) in passes.into_iter().enumerate() {
+        let meta = compiler.reflect(index, &semantics).unwrap().meta;
+
+        let color_mod = &meta.parameter_meta.get("ColorMod").unwrap().offset;
+        assert!(color_mod.ubo.is_some() && color_mod.push.is_none());
+        let color_mod2 = &meta.parameter_meta.get("ColorMod2").unwrap().offset;
+        assert!(color_mod2.push.is_some() && color_mod2.ubo.is_none());
+
+        let mvp = &meta.unique_meta.get(&UniqueSemantics::MVP).unwrap().offset;
+        assert!(mvp.ubo.is_some());
+        assert!(meta
+            .texture_meta
+            .contains_key(&TextureSemantics::Source.semantics(0)));
+    }
+}
@@ -130,6 +130,7 @@ fn compile_presets<O: OutputTarget, R: TypeDebug>()
 where
     O: Sized,
     O: FromCompilation<SpirvCompilation, R>,
+    <O as FromCompilation<SpirvCompilation, R>>::Output: Send,
     O: TypeDebug,
 {
     let presets = ALL_SLANG_PRESETS.read().unwrap();
@@ -14,7 +14,6 @@ Source:  https://github.com/SnowflakePowered/%{name}/archive/%{commit}/%{name}-%
 BuildRequires: gcc
 BuildRequires: g++
 BuildRequires: ninja-build
-BuildRequires: patchelf
 BuildRequires: rustc
 BuildRequires: cargo
 
@@ -32,14 +31,12 @@ Provides:       librashader
 RUSTC_BOOTSTRAP=1 cargo run -p librashader-build-script -- --profile %{profile}
 
 %install
-mkdir -p %{buildroot}/%{_libdir}
-mkdir -p %{buildroot}/%{_includedir}/librashader
-patchelf --set-soname librashader.so.1 target/%{profile}/librashader.so
-install -m 0755 target/%{profile}/librashader.so %{buildroot}%{_libdir}/librashader.so
-cp target/%{profile}/librashader.h %{buildroot}%{_includedir}/librashader/librashader.h 
-cp include/librashader_ld.h %{buildroot}%{_includedir}/librashader/librashader_ld.h 
+RUSTC_BOOTSTRAP=1 cargo run -p librashader-build-script -- --profile %{profile} --install --prefix %{_prefix} --libdir %{_libdir} --includedir %{_includedir} --destdir %{buildroot}
 
 
 %files 
 %{_libdir}/librashader.so
+%{_libdir}/librashader.so.2
+%{_libdir}/pkgconfig/librashader.pc
+%{_libdir}/cmake/librashader/
 %{_includedir}/librashader/
@@ -9,7 +9,6 @@ URL:      https://github.com/SnowflakePowered/%{name}
 Source0:  librashader-%{version}.tar.xz
 Source1:  vendor.tar.xz
 Source2:  cargo_config
-BuildRequires: patchelf
 BuildRequires: gcc
 BuildRequires: gcc-c++
 BuildRequires: cargo
@@ -30,17 +29,12 @@ cp %{SOURCE2} .cargo/config # and automatically uses this config
 RUSTC_BOOTSTRAP=1 cargo run --ignore-rust-version -p librashader-build-script -- --profile %{profile}
 
 %install
-mkdir -p %{buildroot}/%{_libdir}
-mkdir -p %{buildroot}/%{_includedir}/librashader
-patchelf --set-soname librashader.so.2 target/%{profile}/librashader.so
-install -m 0755 target/%{profile}/librashader.so %{buildroot}%{_libdir}/librashader.so
-cp target/%{profile}/librashader.h %{buildroot}%{_includedir}/librashader/librashader.h 
-cp include/librashader_ld.h %{buildroot}%{_includedir}/librashader/librashader_ld.h 
+RUSTC_BOOTSTRAP=1 cargo run --ignore-rust-version -p librashader-build-script -- --profile %{profile} --install --prefix %{_prefix} --libdir %{_libdir} --includedir %{_includedir} --destdir %{buildroot}
 
 
 %files 
 %{_libdir}/librashader.so
-%if !0%{?suse_version}
 %{_libdir}/librashader.so.2
-%endif
-%{_includedir}/librashader/
\ No newline at end of file
+%{_libdir}/pkgconfig/librashader.pc
+%{_libdir}/cmake/librashader/
+%{_includedir}/librashader/